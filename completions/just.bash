@@ -20,26 +20,26 @@ _just() {
 
     case "${cmd}" in
         just)
-            opts=" -n -q -u -v -e -l -h -V -f -d -c -s  --check --dry-run --highlight --no-dotenv --no-highlight --quiet --shell-command --clear-shell-args --unsorted --unstable --verbose --changelog --choose --dump --edit --evaluate --fmt --init --list --summary --variables --help --version --chooser --color --command-color --dump-format --list-heading --list-prefix --justfile --set --shell --shell-arg --working-directory --command --completions --show --dotenv-filename --dotenv-path  <ARGUMENTS>... "
+            opts=" -n -g -q -u -v -e -l -h -V -f -d -c -s  --check --clean-env --diff-env --diff-only --dry-run --global --highlight --inline-dependencies --internal-debug --keep-going --list-templates --no-deps --no-dotenv --no-fallback --no-highlight --offline --one-shot --prompt --quiet --resolve-imports --shell-command --clear-shell-args --stdout --timestamps --unsorted --unstable --yes --verbose --changelog --choose --dump --dump-ast --edit --evaluate --export-script --fmt --init --list --list-groups --lsp --migrate --summary --trust --variables --help --version --attribute --chooser --color --command-color --dump-format --echo --error-format --exit-status --list-heading --list-prefix --no-attribute --output --justfile --report --set --shell --shell-arg --stall-warning --timestamp-format --working-directory --command --complete --completions --doc --emit-aliases --export-tasks --install-lib --show --dotenv-filename --dotenv-path  <ARGUMENTS>... "
                 if [[ ${cur} == -* ]] ; then
                     COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                     return 0
-                elif [[ ${COMP_CWORD} -eq 1 ]]; then
-                    local recipes=$(just --summary 2> /dev/null)
-
-                    if echo "${cur}" | \grep -qF '/'; then
-                        local path_prefix=$(echo "${cur}" | sed 's/[/][^/]*$/\//')
-                        local recipes=$(just --summary 2> /dev/null -- "${path_prefix}")
-                        local recipes=$(printf "${path_prefix}%s\t" $recipes)
-                    fi
+                elif [[ ${COMP_CWORD} -eq 1 ]] && echo "${cur}" | \grep -qF '/'; then
+                    local path_prefix=$(echo "${cur}" | sed 's/[/][^/]*$/\//')
+                    local recipes=$(just --summary 2> /dev/null -- "${path_prefix}")
 
                     if [[ $? -eq 0 ]]; then
+                        local recipes=$(printf "${path_prefix}%s\t" $recipes)
                         COMPREPLY=( $(compgen -W "${recipes}" -- "${cur}") )
                         return 0
                     fi
                 fi
             case "${prev}" in
                 
+                --attribute)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --chooser)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -56,6 +56,18 @@ _just() {
                     COMPREPLY=($(compgen -W "just json" -- "${cur}"))
                     return 0
                     ;;
+                --echo)
+                    COMPREPLY=($(compgen -W "all failed none prefixed" -- "${cur}"))
+                    return 0
+                    ;;
+                --error-format)
+                    COMPREPLY=($(compgen -W "human json" -- "${cur}"))
+                    return 0
+                    ;;
+                --exit-status)
+                    COMPREPLY=($(compgen -W "passthrough categorized" -- "${cur}"))
+                    return 0
+                    ;;
                 --list-heading)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -64,6 +76,14 @@ _just() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --no-attribute)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --output)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --justfile)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -72,6 +92,10 @@ _just() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --report)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --set)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -84,6 +108,14 @@ _just() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --stall-warning)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --timestamp-format)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --working-directory)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -100,8 +132,28 @@ _just() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --complete)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --completions)
-                    COMPREPLY=($(compgen -W "zsh bash fish powershell elvish" -- "${cur}"))
+                    COMPREPLY=($(compgen -W "bash elvish fish nushell powershell zsh" -- "${cur}"))
+                    return 0
+                    ;;
+                --doc)
+                    COMPREPLY=($(compgen -W "man markdown" -- "${cur}"))
+                    return 0
+                    ;;
+                --emit-aliases)
+                    COMPREPLY=($(compgen -W "bash fish pwsh" -- "${cur}"))
+                    return 0
+                    ;;
+                --export-tasks)
+                    COMPREPLY=($(compgen -W "jetbrains vscode" -- "${cur}"))
+                    return 0
+                    ;;
+                --install-lib)
+                    COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
                 --show)
@@ -124,7 +176,21 @@ _just() {
                     COMPREPLY=()
                     ;;
             esac
-            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+
+            # Split the line ourselves, since bash splits words at `=`
+            local line="${COMP_LINE:0:COMP_POINT}"
+            local words
+            read -ra words <<< "${line}"
+            if [[ -z ${line##*[[:space:]]} ]]; then
+                words+=("")
+            fi
+
+            # Completions replace `cur`, which may be only part of the word
+            local word="${words[${#words[@]}-1]}"
+            local prefix="${word%"${cur}"}"
+
+            COMPREPLY=( $(just --complete "${words[@]:1}" 2> /dev/null | cut -f1) )
+            COMPREPLY=( "${COMPREPLY[@]#"${prefix}"}" )
             return 0
             ;;
         
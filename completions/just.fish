@@ -1,5 +1,5 @@
 function __fish_just_complete_recipes
-    just --list 2> /dev/null | sed -e '1d; s/^\s*\([^[:space:]]*\)[^#]*$/\1/' -e 's/^\s*\([^[:space:]]*\)[^#]*# \(.*\)$/\1\t\2/'
+    just --complete (commandline -opc)[2..-1] (commandline -ct) 2> /dev/null
 end
 
 # don't suggest files right off
@@ -9,42 +9,79 @@ complete -c just -n "__fish_is_first_arg" --no-files
 complete -c just -a '(__fish_just_complete_recipes)'
 
 # autogenerated completions
+complete -c just -n "__fish_use_subcommand" -l attribute -d 'Run the recipes given on the command line as if they had attribute <NAME>, with argument <VALUE>, e.g. `working-directory:build`'
 complete -c just -n "__fish_use_subcommand" -l chooser -d 'Override binary invoked by `--choose`'
 complete -c just -n "__fish_use_subcommand" -l color -d 'Print colorful output' -r -f -a "auto always never"
 complete -c just -n "__fish_use_subcommand" -l command-color -d 'Echo recipe lines in <COMMAND-COLOR>' -r -f -a "black blue cyan green purple red yellow"
-complete -c just -n "__fish_use_subcommand" -l dump-format -d 'Dump justfile as <FORMAT>' -r -f -a "just json"
+complete -c just -n "__fish_use_subcommand" -l dump-format -d 'Dump justfile, or print variables with `--evaluate`, as <FORMAT>' -r -f -a "just json"
+complete -c just -n "__fish_use_subcommand" -l echo -d 'Echo recipe lines before running them with `all`, only after they fail with `failed`, never with `none`, or prefixed with the recipe name with `prefixed`, overriding `set echo`' -r -f -a "all failed none prefixed"
+complete -c just -n "__fish_use_subcommand" -l error-format -d 'Print errors as colored messages with `human`, the default, or as JSON objects, one per line, with `json`' -r -f -a "human json"
+complete -c just -n "__fish_use_subcommand" -l exit-status -d 'Exit with the exit code of the failed recipe line with `passthrough`, the default, or with a code for the kind of error with `categorized`' -r -f -a "passthrough categorized"
 complete -c just -n "__fish_use_subcommand" -l list-heading -d 'Print <TEXT> before list'
 complete -c just -n "__fish_use_subcommand" -l list-prefix -d 'Print <TEXT> before each list item'
-complete -c just -n "__fish_use_subcommand" -s f -l justfile -d 'Use <JUSTFILE> as justfile'
+complete -c just -n "__fish_use_subcommand" -l no-attribute -d 'Run the recipes given on the command line as if they didn\'t have attribute <NAME>, e.g. `confirm`'
+complete -c just -n "__fish_use_subcommand" -l output -d 'Write the script exported with `--export-script` to <PATH> and make it executable'
+complete -c just -n "__fish_use_subcommand" -s f -l justfile -d 'Use <JUSTFILE> as justfile, or read justfile from standard input if <JUSTFILE> is `-`'
+complete -c just -n "__fish_use_subcommand" -l report -d 'Write a JSON report of the recipes run, their order, outcomes, and durations to <PATH>'
 complete -c just -n "__fish_use_subcommand" -l set -d 'Override <VARIABLE> with <VALUE>'
 complete -c just -n "__fish_use_subcommand" -l shell -d 'Invoke <SHELL> to run recipes'
 complete -c just -n "__fish_use_subcommand" -l shell-arg -d 'Invoke shell with <SHELL-ARG> as an argument'
+complete -c just -n "__fish_use_subcommand" -l stall-warning -d 'Print a warning, along with the line being run, whenever a recipe produces no output for <INTERVAL>, e.g. `60s`'
+complete -c just -n "__fish_use_subcommand" -l timestamp-format -d 'Format timestamps with the strftime-style <FORMAT>, by default `%H:%M:%S`'
 complete -c just -n "__fish_use_subcommand" -s d -l working-directory -d 'Use <WORKING-DIRECTORY> as working directory. --justfile must also be set'
 complete -c just -n "__fish_use_subcommand" -s c -l command -d 'Run an arbitrary command with the working directory, `.env`, overrides, and exports set'
-complete -c just -n "__fish_use_subcommand" -l completions -d 'Print shell completion script for <SHELL>' -r -f -a "zsh bash fish powershell elvish"
+complete -c just -n "__fish_use_subcommand" -l complete -d 'Print completions for the last of <WORDS>, for use by shell completion scripts'
+complete -c just -n "__fish_use_subcommand" -l completions -d 'Print shell completion script for <SHELL>' -r -f -a "bash elvish fish nushell powershell zsh"
+complete -c just -n "__fish_use_subcommand" -l doc -d 'Print documentation for public recipes, with their parameters, aliases, and groups, as <FORMAT>' -r -f -a "man markdown"
+complete -c just -n "__fish_use_subcommand" -l emit-aliases -d 'Print <SHELL> aliases for public recipes, e.g. `alias jb=\'just build\'`' -r -f -a "bash fish pwsh"
+complete -c just -n "__fish_use_subcommand" -l export-tasks -d 'Print <EDITOR> task definitions for public recipes, `.vscode/tasks.json` for `vscode` or a `.run/*.run.xml` file for `jetbrains`' -r -f -a "jetbrains vscode"
+complete -c just -n "__fish_use_subcommand" -l install-lib -d 'Install recipe library from <SOURCE>, `github:ORG/REPO@REF` or `git:URL@REF`, and pin it in the `just-libs.lock` next to the justfile'
 complete -c just -n "__fish_use_subcommand" -s s -l show -d 'Show information about <RECIPE>'
 complete -c just -n "__fish_use_subcommand" -l dotenv-filename -d 'Search for environment file named <DOTENV-FILENAME> instead of `.env`'
 complete -c just -n "__fish_use_subcommand" -l dotenv-path -d 'Load environment file at <DOTENV-PATH> instead of searching for one'
-complete -c just -n "__fish_use_subcommand" -l check -d 'Run `--fmt` in \'check\' mode. Exits with 0 if justfile is formatted correctly. Exits with 1 and prints a diff if formatting is required.'
+complete -c just -n "__fish_use_subcommand" -l check -d 'Run `--fmt` in \'check\' mode. Exits with 0 if justfile is formatted correctly. Exits with 1 and prints a unified diff if formatting is required.'
+complete -c just -n "__fish_use_subcommand" -l clean-env -d 'Run recipes with a clean environment, containing only the variables in `env-passthrough`, `.env` variables, and exported variables'
+complete -c just -n "__fish_use_subcommand" -l diff-env -d 'Print the environment variables added, changed, or removed for each recipe'
+complete -c just -n "__fish_use_subcommand" -l diff-only -d 'Print only the changed lines of the `--check` diff, without context, and no error message'
 complete -c just -n "__fish_use_subcommand" -s n -l dry-run -d 'Print what just would do without doing it'
-complete -c just -n "__fish_use_subcommand" -l highlight -d 'Highlight echoed recipe lines in bold'
+complete -c just -n "__fish_use_subcommand" -s g -l global -d 'Use global justfile, found in `just/justfile` in `$XDG_CONFIG_HOME` or `~/.config`, or at `~/justfile` or `~/.justfile`'
+complete -c just -n "__fish_use_subcommand" -l highlight -d 'Highlight echoed recipe lines in bold, and the syntax of recipes shown with `--show`'
+complete -c just -n "__fish_use_subcommand" -l inline-dependencies -d 'Include the dependencies of recipes exported with `--export-script`'
+complete -c just -n "__fish_use_subcommand" -l internal-debug -d 'Include the evaluator\'s state in internal evaluation errors, for bug reports'
+complete -c just -n "__fish_use_subcommand" -l keep-going -d 'Keep running the recipes given on the command line after one fails, and fail at the end if any did'
+complete -c just -n "__fish_use_subcommand" -l list-templates -d 'List templates for `--init`'
+complete -c just -n "__fish_use_subcommand" -l no-deps -d 'Run recipes given on the command line without their prior dependencies'
 complete -c just -n "__fish_use_subcommand" -l no-dotenv -d 'Don\'t load `.env` file'
-complete -c just -n "__fish_use_subcommand" -l no-highlight -d 'Don\'t highlight echoed recipe lines in bold'
+complete -c just -n "__fish_use_subcommand" -l no-fallback -d 'Don\'t search other justfiles for recipes which aren\'t found, even with `set fallback`'
+complete -c just -n "__fish_use_subcommand" -l no-highlight -d 'Don\'t highlight echoed recipe lines in bold, or the syntax of recipes shown with `--show`'
+complete -c just -n "__fish_use_subcommand" -l offline -d 'Use cached copies of remote `!include`s instead of fetching them'
+complete -c just -n "__fish_use_subcommand" -l one-shot -d 'Do as little startup work as possible, for shell prompts which print metadata on every render, by not reading `.just.toml` or fetching remote includes, and using only cached copies of them. May only be used with `--dump`, `--list`, `--list-groups`, `--summary`, or `--variables`.'
+complete -c just -n "__fish_use_subcommand" -l prompt -d 'Prompt for missing recipe arguments when run from a terminal'
 complete -c just -n "__fish_use_subcommand" -s q -l quiet -d 'Suppress all output'
+complete -c just -n "__fish_use_subcommand" -l resolve-imports -d 'Dump justfile with `!include` directives replaced by the contents of the included files, annotated with the paths they were included from'
 complete -c just -n "__fish_use_subcommand" -l shell-command -d 'Invoke <COMMAND> with the shell used to run recipe lines and backticks'
 complete -c just -n "__fish_use_subcommand" -l clear-shell-args -d 'Clear shell arguments'
+complete -c just -n "__fish_use_subcommand" -l stdout -d 'Print formatted justfile to stdout instead of overwriting it. Only valid with `--fmt`'
+complete -c just -n "__fish_use_subcommand" -l timestamps -d 'Print the time before each echoed recipe line and error message'
 complete -c just -n "__fish_use_subcommand" -s u -l unsorted -d 'Return list and summary entries in source order'
 complete -c just -n "__fish_use_subcommand" -l unstable -d 'Enable unstable features'
+complete -c just -n "__fish_use_subcommand" -l yes -d 'Automatically confirm all recipes and `confirm()` prompts'
 complete -c just -n "__fish_use_subcommand" -s v -l verbose -d 'Use verbose output'
 complete -c just -n "__fish_use_subcommand" -l changelog -d 'Print changelog'
 complete -c just -n "__fish_use_subcommand" -l choose -d 'Select one or more recipes to run using a binary. If `--chooser` is not passed the chooser defaults to the value of $JUST_CHOOSER, falling back to `fzf`'
 complete -c just -n "__fish_use_subcommand" -l dump -d 'Print justfile'
-complete -c just -n "__fish_use_subcommand" -s e -l edit -d 'Edit justfile with editor given by $VISUAL or $EDITOR, falling back to `vim`'
-complete -c just -n "__fish_use_subcommand" -l evaluate -d 'Evaluate and print all variables. If a variable name is given as an argument, only print that variable\'s value.'
+complete -c just -n "__fish_use_subcommand" -l dump-ast -d 'Print the justfile\'s syntax tree, with the location of each name, as JSON'
+complete -c just -n "__fish_use_subcommand" -s e -l edit -d 'Edit justfile with editor given by $VISUAL or $EDITOR, falling back to `vim`. With `just --edit RECIPE`, open the file where RECIPE is defined at its line'
+complete -c just -n "__fish_use_subcommand" -l evaluate -d 'Evaluate and print all variables. If a variable name is given as an argument, only print that variable\'s value. If several are given, print them as `NAME=VALUE` lines. With `--dump-format json`, print variables as a JSON object.'
+complete -c just -n "__fish_use_subcommand" -l export-script -d 'Print the recipes given as arguments, evaluated, as a standalone shell script which runs them without just'
 complete -c just -n "__fish_use_subcommand" -l fmt -d 'Format and overwrite justfile'
-complete -c just -n "__fish_use_subcommand" -l init -d 'Initialize new justfile in project root'
+complete -c just -n "__fish_use_subcommand" -l init -d 'Initialize new justfile in project root. With `just --init TEMPLATE`, write starter recipes for a kind of project, listed by `just --init --list-templates`'
 complete -c just -n "__fish_use_subcommand" -s l -l list -d 'List available recipes and their arguments'
+complete -c just -n "__fish_use_subcommand" -l list-groups -d 'List recipe groups'
+complete -c just -n "__fish_use_subcommand" -l lsp -d 'Run a language server for justfiles, which communicates over stdin and stdout'
+complete -c just -n "__fish_use_subcommand" -l migrate -d 'Rewrite legacy constructs in justfile to their modern equivalents'
 complete -c just -n "__fish_use_subcommand" -l summary -d 'List names of available recipes'
+complete -c just -n "__fish_use_subcommand" -l trust -d 'Trust justfile, allowing it to run if `require-trust` is enabled in the global configuration file'
 complete -c just -n "__fish_use_subcommand" -l variables -d 'List names of variables'
 complete -c just -n "__fish_use_subcommand" -s h -l help -d 'Print help information'
 complete -c just -n "__fish_use_subcommand" -s V -l version -d 'Print version information'
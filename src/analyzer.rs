@@ -1,12 +1,30 @@
 use {super::*, CompileErrorKind::*};
 
-const VALID_ALIAS_ATTRIBUTES: [Attribute; 1] = [Attribute::Private];
+const VALID_ALIAS_ATTRIBUTES: [Attribute<'static>; 1] = [Attribute::Private];
+
+const CONFLICTING_ATTRIBUTES: [(AttributeDiscriminant, AttributeDiscriminant); 4] = [
+  (
+    AttributeDiscriminant::NoCd,
+    AttributeDiscriminant::WorkingDirectory,
+  ),
+  (AttributeDiscriminant::Script, AttributeDiscriminant::Shell),
+  (
+    AttributeDiscriminant::Script,
+    AttributeDiscriminant::ScriptStdin,
+  ),
+  (
+    AttributeDiscriminant::ScriptStdin,
+    AttributeDiscriminant::Shell,
+  ),
+];
 
 #[derive(Default)]
 pub(crate) struct Analyzer<'src> {
   assignments: Table<'src, Assignment<'src>>,
   aliases: Table<'src, Alias<'src, Name<'src>>>,
+  functions: Table<'src, UserFunction<'src>>,
   sets: Table<'src, Set<'src>>,
+  warnings: Vec<Warning<'src>>,
 }
 
 impl<'src> Analyzer<'src> {
@@ -15,6 +33,19 @@ impl<'src> Analyzer<'src> {
   }
 
   fn justfile(mut self, ast: &Ast<'src>) -> CompileResult<'src, Justfile<'src>> {
+    let mut groups = BTreeMap::<&str, &GroupDeclaration>::new();
+
+    for item in &ast.items {
+      if let Item::Group(group) = item {
+        if let Some(first) = groups.insert(&group.name.cooked, group) {
+          return Err(group.keyword.error(DuplicateGroup {
+            group: group.name.cooked.clone(),
+            first: first.keyword.line,
+          }));
+        }
+      }
+    }
+
     let mut recipes = Vec::new();
 
     for item in &ast.items {
@@ -27,10 +58,15 @@ impl<'src> Analyzer<'src> {
           self.analyze_assignment(assignment)?;
           self.assignments.insert(assignment.clone());
         }
-        Item::Comment(_) => (),
+        Item::Comment(_) | Item::Group(_) => (),
+        Item::Function(function) => {
+          self.analyze_function(function)?;
+          self.functions.insert(function.clone());
+        }
         Item::Recipe(recipe) => {
+          let recipe = Self::inherit_group_attributes(recipe, &groups);
           if recipe.enabled() {
-            Self::analyze_recipe(recipe)?;
+            self.analyze_recipe(&recipe)?;
             recipes.push(recipe);
           }
         }
@@ -43,9 +79,60 @@ impl<'src> Analyzer<'src> {
 
     let settings = Settings::from_setting_iter(self.sets.into_iter().map(|(_, set)| set.value));
 
+    if settings.export {
+      let names = self
+        .assignments
+        .values()
+        .filter(|assignment| !assignment.export)
+        .map(|assignment| assignment.name)
+        .chain(recipes.iter().flat_map(|recipe| {
+          recipe
+            .parameters
+            .iter()
+            .filter(|parameter| !parameter.export)
+            .map(|parameter| parameter.name)
+        }));
+
+      for name in names {
+        if !is_env_var_name(&settings.export_name(name.lexeme())) {
+          self.warnings.push(Warning::UnexportedName { name });
+        }
+      }
+    }
+
+    // `export-prefix` uppercases names, so names which differ only in case,
+    // like `foo` and `FOO`, would be exported as the same variable
+    if settings.export_prefix.is_some() {
+      Self::check_export_names(
+        &settings,
+        self
+          .assignments
+          .values()
+          .filter(|assignment| settings.export || assignment.export)
+          .map(|assignment| assignment.name),
+      )?;
+
+      for recipe in &recipes {
+        Self::check_export_names(
+          &settings,
+          recipe
+            .parameters
+            .iter()
+            .filter(|parameter| settings.export || parameter.export)
+            .map(|parameter| parameter.name),
+        )?;
+      }
+    }
+
     let mut recipe_table: Table<'src, UnresolvedRecipe<'src>> = Table::default();
 
-    AssignmentResolver::resolve_assignments(&self.assignments)?;
+    FunctionResolver::resolve_functions(
+      &self.functions,
+      &self.assignments,
+      &recipes.iter().collect::<Vec<&UnresolvedRecipe>>(),
+    )?;
+
+    AssignmentResolver::resolve_assignments(&self.assignments, &self.functions)?;
 
     for recipe in recipes {
       if let Some(original) = recipe_table.get(recipe.name.lexeme()) {
@@ -56,7 +143,7 @@ impl<'src> Analyzer<'src> {
           }));
         }
       }
-      recipe_table.insert(recipe.clone());
+      recipe_table.insert(recipe);
     }
 
     let recipes = RecipeResolver::resolve_recipes(recipe_table, &self.assignments)?;
@@ -66,8 +153,19 @@ impl<'src> Analyzer<'src> {
       aliases.insert(Self::resolve_alias(&recipes, alias)?);
     }
 
+    for recipe in recipes.values() {
+      for related in recipe.see_also() {
+        if !recipes.contains_key(related) && !aliases.contains_key(related) {
+          return Err(recipe.name.error(UnknownSeeAlso {
+            recipe: recipe.name(),
+            unknown: related.to_owned(),
+          }));
+        }
+      }
+    }
+
     Ok(Justfile {
-      warnings: ast.warnings.clone(),
+      warnings: ast.warnings.iter().cloned().chain(self.warnings).collect(),
       first: recipes
         .values()
         .fold(None, |accumulator, next| match accumulator {
@@ -80,12 +178,92 @@ impl<'src> Analyzer<'src> {
         }),
       aliases,
       assignments: self.assignments,
+      functions: self.functions,
       recipes,
       settings,
     })
   }
 
-  fn analyze_recipe(recipe: &UnresolvedRecipe<'src>) -> CompileResult<'src, ()> {
+  /// Add the attributes of the declarations of the groups that `recipe` is
+  /// in to it, unless it already has an attribute of the same kind, one
+  /// which conflicts with it, or an `[env]` attribute setting the same
+  /// variable, so that its own attributes take precedence
+  /// Check that no two of `names` are exported as the same environment
+  /// variable
+  fn check_export_names(
+    settings: &Settings,
+    names: impl Iterator<Item = Name<'src>>,
+  ) -> CompileResult<'src, ()> {
+    let mut names = names.collect::<Vec<Name>>();
+
+    names.sort_by_key(|name| name.offset);
+
+    let mut exported = BTreeMap::<String, &'src str>::new();
+
+    for name in names {
+      let export_name = settings.export_name(name.lexeme());
+      if let Some(&first) = exported.get(&export_name) {
+        return Err(name.error(DuplicateExport {
+          variable: name.lexeme(),
+          first,
+          name: export_name,
+        }));
+      }
+      exported.insert(export_name, name.lexeme());
+    }
+
+    Ok(())
+  }
+
+  fn inherit_group_attributes(
+    recipe: &UnresolvedRecipe<'src>,
+    groups: &BTreeMap<&str, &GroupDeclaration<'src>>,
+  ) -> UnresolvedRecipe<'src> {
+    let mut recipe = recipe.clone();
+
+    let inherited = recipe
+      .attributes
+      .iter()
+      .filter_map(|attribute| match attribute {
+        Attribute::Group(name) => groups.get(name.cooked.as_str()),
+        _ => None,
+      })
+      .flat_map(|group| &group.attributes)
+      .cloned()
+      .collect::<Vec<Attribute>>();
+
+    for attribute in inherited {
+      let discriminant = AttributeDiscriminant::from(&attribute);
+
+      let present = |discriminant: AttributeDiscriminant| {
+        recipe
+          .attributes
+          .iter()
+          .any(|attribute| AttributeDiscriminant::from(attribute) == discriminant)
+      };
+
+      let conflicts = CONFLICTING_ATTRIBUTES.iter().any(|&(first, second)| {
+        (first == discriminant && present(second)) || (second == discriminant && present(first))
+      });
+
+      let overridden = match &attribute {
+        Attribute::Env { name, .. } => recipe.attributes.iter().any(|attribute| {
+          matches!(attribute, Attribute::Env { name: other, .. } if other.cooked == name.cooked)
+        }),
+        _ => false,
+      };
+
+      if conflicts || overridden || (!discriminant.repeatable() && present(discriminant)) {
+        continue;
+      }
+
+      recipe.attributes.insert(attribute);
+    }
+
+    recipe
+  }
+
+  fn analyze_recipe(&mut self, recipe: &UnresolvedRecipe<'src>) -> CompileResult<'src, ()> {
     let mut parameters = BTreeSet::new();
     let mut passed_default = false;
 
@@ -98,6 +276,12 @@ impl<'src> Analyzer<'src> {
       }
       parameters.insert(parameter.name.lexeme());
 
+      if parameter.export && !is_env_var_name(parameter.name.lexeme()) {
+        self.warnings.push(Warning::InvalidExportName {
+          name: parameter.name,
+        });
+      }
+
       if parameter.default.is_some() {
         passed_default = true;
       } else if passed_default {
@@ -112,9 +296,29 @@ impl<'src> Analyzer<'src> {
       }
     }
 
+    let mut captures = BTreeSet::new();
+
+    for (i, dependency) in recipe.dependencies.iter().enumerate() {
+      if let Some(capture) = dependency.capture {
+        if i >= recipe.priors {
+          return Err(capture.error(CaptureSubsequentDependency {
+            recipe: recipe.name.lexeme(),
+            dependency: dependency.recipe.lexeme(),
+          }));
+        }
+
+        if parameters.contains(capture.lexeme()) || !captures.insert(capture.lexeme()) {
+          return Err(capture.error(DuplicateCapture {
+            recipe: recipe.name.lexeme(),
+            capture: capture.lexeme(),
+          }));
+        }
+      }
+    }
+
     let mut continued = false;
     for line in &recipe.body {
-      if !recipe.shebang && !continued {
+      if !recipe.is_script() && !continued {
         if let Some(Fragment::Text { token }) = line.fragments.first() {
           let text = token.lexeme();
 
@@ -127,15 +331,120 @@ impl<'src> Analyzer<'src> {
       continued = line.is_continuation();
     }
 
+    let attributes = recipe
+      .attributes
+      .iter()
+      .map(AttributeDiscriminant::from)
+      .collect::<BTreeSet<AttributeDiscriminant>>();
+
+    if recipe.shebang && attributes.contains(&AttributeDiscriminant::Shell) {
+      return Err(recipe.name.error(ShebangRecipeWithShellAttribute {
+        recipe: recipe.name.lexeme(),
+      }));
+    }
+
+    let mut variables = BTreeSet::new();
+
+    for attribute in &recipe.attributes {
+      if let Attribute::Env { name, .. } = attribute {
+        if !is_env_var_name(&name.cooked) {
+          return Err(recipe.name.error(InvalidExportName { name: name.raw }));
+        }
+
+        if !variables.insert(name.cooked.as_str()) {
+          return Err(recipe.name.error(DuplicateEnv {
+            recipe: recipe.name.lexeme(),
+            variable: name.cooked.clone(),
+          }));
+        }
+      }
+
+      if let Some(parameter) = attribute.parameter() {
+        if !parameters.contains(parameter) {
+          return Err(recipe.name.error(UnknownAttributeParameter {
+            recipe: recipe.name.lexeme(),
+            attribute: attribute.name(),
+            parameter: parameter.to_owned(),
+          }));
+        }
+
+        let default = recipe
+          .parameters
+          .iter()
+          .find(|candidate| candidate.name.lexeme() == parameter)
+          .and_then(|parameter| parameter.default.as_ref());
+
+        if let Some(Expression::StringLiteral { string_literal }) = default {
+          if !attribute.accepts(&string_literal.cooked) {
+            return Err(recipe.name.error(InvalidParameterDefault {
+              recipe: recipe.name.lexeme(),
+              attribute: attribute.name(),
+              parameter: parameter.to_owned(),
+              default: string_literal.cooked.clone(),
+            }));
+          }
+        }
+      }
+    }
+
+    for (first, second) in CONFLICTING_ATTRIBUTES {
+      if attributes.contains(&first) && attributes.contains(&second) {
+        return Err(recipe.name.error(ConflictingAttributes {
+          recipe: recipe.name.lexeme(),
+          first: first.into(),
+          second: second.into(),
+        }));
+      }
+    }
+
     Ok(())
   }
 
-  fn analyze_assignment(&self, assignment: &Assignment<'src>) -> CompileResult<'src, ()> {
+  fn analyze_assignment(&mut self, assignment: &Assignment<'src>) -> CompileResult<'src, ()> {
     if self.assignments.contains_key(assignment.name.lexeme()) {
       return Err(assignment.name.token().error(DuplicateVariable {
         variable: assignment.name.lexeme(),
       }));
     }
+
+    if assignment.export && !is_env_var_name(env_var_name(assignment.name.lexeme())) {
+      self.warnings.push(Warning::InvalidExportName {
+        name: assignment.name,
+      });
+    }
+
+    Ok(())
+  }
+
+  fn analyze_function(&self, function: &UserFunction<'src>) -> CompileResult<'src, ()> {
+    let name = function.name.lexeme();
+
+    if let Some(original) = self.functions.get(name) {
+      return Err(function.name.error(DuplicateFunction {
+        function: name,
+        first: original.line_number(),
+      }));
+    }
+
+    if function::get(name).is_some() {
+      return Err(
+        function
+          .name
+          .error(FunctionShadowsBuiltin { function: name }),
+      );
+    }
+
+    let mut parameters = BTreeSet::new();
+
+    for parameter in &function.parameters {
+      if !parameters.insert(parameter.lexeme()) {
+        return Err(parameter.error(DuplicateFunctionParameter {
+          function: name,
+          parameter: parameter.lexeme(),
+        }));
+      }
+    }
+
     Ok(())
   }
 
@@ -153,7 +462,7 @@ impl<'src> Analyzer<'src> {
       if !VALID_ALIAS_ATTRIBUTES.contains(attr) {
         return Err(alias.name.token().error(AliasInvalidAttribute {
           alias: name,
-          attr: *attr,
+          attr: attr.clone(),
         }));
       }
     }
@@ -290,6 +599,36 @@ mod tests {
     kind:   DuplicateVariable{variable: "a"},
   }
 
+  analysis_error! {
+    name:   duplicate_function,
+    input:  "fn f() := 'a'\nfn f() := 'b'",
+    offset: 17,
+    line:   1,
+    column: 3,
+    width:  1,
+    kind:   DuplicateFunction{function: "f", first: 0},
+  }
+
+  analysis_error! {
+    name:   duplicate_function_parameter,
+    input:  "fn f(a, a) := a",
+    offset: 8,
+    line:   0,
+    column: 8,
+    width:  1,
+    kind:   DuplicateFunctionParameter{function: "f", parameter: "a"},
+  }
+
+  analysis_error! {
+    name:   function_shadows_builtin,
+    input:  "fn uppercase(s) := s",
+    offset: 3,
+    line:   0,
+    column: 3,
+    width:  9,
+    kind:   FunctionShadowsBuiltin{function: "uppercase"},
+  }
+
   analysis_error! {
     name:   extra_whitespace,
     input:  "a:\n blah\n  blarg",
@@ -8,6 +8,9 @@ impl<'src> Display for Assignment<'src> {
     if self.export {
       write!(f, "export ")?;
     }
+    if self.lazy {
+      write!(f, "lazy ")?;
+    }
     write!(f, "{} := {}", self.name, self.value)
   }
 }
@@ -2,6 +2,9 @@ use {super::*, CompileErrorKind::*};
 
 pub(crate) struct AssignmentResolver<'src: 'run, 'run> {
   assignments: &'run Table<'src, Assignment<'src>>,
+  functions: &'run Table<'src, UserFunction<'src>>,
+  /// Parameters of the user-defined function whose body is being resolved
+  parameters: Vec<&'src str>,
   stack: Vec<&'src str>,
   evaluated: BTreeSet<&'src str>,
 }
@@ -9,11 +12,14 @@ pub(crate) struct AssignmentResolver<'src: 'run, 'run> {
 impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
   pub(crate) fn resolve_assignments(
     assignments: &Table<'src, Assignment<'src>>,
+    functions: &Table<'src, UserFunction<'src>>,
   ) -> CompileResult<'src, ()> {
     let mut resolver = AssignmentResolver {
+      parameters: Vec::new(),
       stack: Vec::new(),
       evaluated: BTreeSet::new(),
       assignments,
+      functions,
     };
 
     for name in assignments.keys() {
@@ -31,7 +37,9 @@ impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
     self.stack.push(name);
 
     if let Some(assignment) = self.assignments.get(name) {
+      let parameters = mem::take(&mut self.parameters);
       self.resolve_expression(&assignment.value)?;
+      self.parameters = parameters;
       self.evaluated.insert(name);
     } else {
       let message = format!("attempted to resolve unknown assignment `{name}`");
@@ -55,7 +63,7 @@ impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
     match expression {
       Expression::Variable { name } => {
         let variable = name.lexeme();
-        if self.evaluated.contains(variable) {
+        if self.parameters.contains(&variable) || self.evaluated.contains(variable) {
           Ok(())
         } else if self.stack.contains(&variable) {
           let token = self.assignments[variable].name.token();
@@ -66,6 +74,8 @@ impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
           }))
         } else if self.assignments.contains_key(variable) {
           self.resolve_assignment(variable)
+        } else if dotenv_key(variable).is_some() {
+          Ok(())
         } else {
           Err(name.token().error(UndefinedVariable { variable }))
         }
@@ -80,6 +90,15 @@ impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
           }
           Ok(())
         }
+        Thunk::UnaryPlus {
+          args: (a, rest), ..
+        } => {
+          self.resolve_expression(a)?;
+          for arg in rest {
+            self.resolve_expression(arg)?;
+          }
+          Ok(())
+        }
         Thunk::Binary { args: [a, b], .. } => {
           self.resolve_expression(a)?;
           self.resolve_expression(b)
@@ -102,6 +121,24 @@ impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
           self.resolve_expression(b)?;
           self.resolve_expression(c)
         }
+        Thunk::User { name, args } => {
+          for arg in args {
+            self.resolve_expression(arg)?;
+          }
+          // Variables used in the body of the function are evaluated when it
+          // is called, so they must not depend on the assignment being
+          // resolved either
+          if let Some(function) = self.functions.get(name.lexeme()) {
+            let parameters = mem::replace(
+              &mut self.parameters,
+              function.parameters.iter().map(Name::lexeme).collect(),
+            );
+            let result = self.resolve_expression(&function.body);
+            self.parameters = parameters;
+            result?;
+          }
+          Ok(())
+        }
       },
       Expression::Concatenation { lhs, rhs } => {
         self.resolve_expression(lhs)?;
@@ -114,14 +151,13 @@ impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
         self.resolve_expression(rhs)
       }
       Expression::Conditional {
-        lhs,
-        rhs,
+        condition,
         then,
         otherwise,
-        ..
       } => {
-        self.resolve_expression(lhs)?;
-        self.resolve_expression(rhs)?;
+        for expression in condition.expressions() {
+          self.resolve_expression(expression)?;
+        }
         self.resolve_expression(then)?;
         self.resolve_expression(otherwise)
       }
@@ -155,6 +191,16 @@ mod tests {
     kind:   CircularVariableDependency{variable: "a", circle: vec!["a", "a"]},
   }
 
+  analysis_error! {
+    name:   circular_variable_dependency_through_function,
+    input:  "fn f() := a\na := f()",
+    offset: 12,
+    line:   1,
+    column: 0,
+    width:  1,
+    kind:   CircularVariableDependency{variable: "a", circle: vec!["a", "a"]},
+  }
+
   analysis_error! {
     name:   unknown_expression_variable,
     input:  "x := yy",
@@ -8,25 +8,99 @@ pub(crate) struct Ast<'src> {
   /// Items in the justfile
   pub(crate) items: Vec<Item<'src>>,
   /// Non-fatal warnings encountered during parsing
-  pub(crate) warnings: Vec<Warning>,
+  pub(crate) warnings: Vec<Warning<'src>>,
 }
 
-impl<'src> Display for Ast<'src> {
-  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-    let mut iter = self.items.iter().peekable();
+impl<'src> Ast<'src> {
+  /// Format this AST for `--fmt`, in the style given by the `fmt-*` settings
+  /// in `settings`
+  pub(crate) fn format(&self, settings: &Settings) -> String {
+    let mut formatted = String::new();
+
+    self
+      .write(
+        &mut formatted,
+        settings.fmt_indent.as_deref().unwrap_or("    "),
+        settings.fmt_align_assignments,
+        settings.fmt_blank_lines.unwrap_or(1),
+      )
+      .unwrap();
+
+    formatted
+  }
 
-    while let Some(item) = iter.next() {
-      writeln!(f, "{item}")?;
+  /// Write this AST to `f`, with recipe bodies indented by `indent`, the `:=`
+  /// of consecutive assignments aligned if `align_assignments` is true, and
+  /// `blank_lines` blank lines after each recipe and between items of
+  /// different kinds
+  fn write(
+    &self,
+    f: &mut dyn fmt::Write,
+    indent: &str,
+    align_assignments: bool,
+    blank_lines: usize,
+  ) -> fmt::Result {
+    let mut width = 0;
+
+    for (i, item) in self.items.iter().enumerate() {
+      match item {
+        Item::Assignment(assignment) if align_assignments => {
+          if !matches!(
+            i.checked_sub(1).map(|i| &self.items[i]),
+            Some(Item::Assignment(_))
+          ) {
+            width = self.items[i..]
+              .iter()
+              .map_while(|item| match item {
+                Item::Assignment(assignment) => Some(Self::assignment_lhs(assignment).len()),
+                _ => None,
+              })
+              .max()
+              .unwrap_or_default();
+          }
+
+          writeln!(
+            f,
+            "{:width$} := {}",
+            Self::assignment_lhs(assignment),
+            assignment.value
+          )?;
+        }
+        Item::Recipe(recipe) => {
+          recipe.write(f, Color::never(), indent)?;
+          writeln!(f)?;
+        }
+        _ => writeln!(f, "{item}")?,
+      }
 
-      if let Some(next_item) = iter.peek() {
+      if let Some(next_item) = self.items.get(i + 1) {
         if matches!(item, Item::Recipe(_))
           || mem::discriminant(item) != mem::discriminant(next_item)
         {
-          writeln!(f)?;
+          for _ in 0..blank_lines {
+            writeln!(f)?;
+          }
         }
       }
     }
 
     Ok(())
   }
+
+  /// The part of `assignment` before its `:=`
+  fn assignment_lhs(assignment: &Assignment) -> String {
+    if assignment.export {
+      format!("export {}", assignment.name)
+    } else if assignment.lazy {
+      format!("lazy {}", assignment.name)
+    } else {
+      assignment.name.to_string()
+    }
+  }
+}
+
+impl<'src> Display for Ast<'src> {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    self.write(f, "    ", false, 1)
+  }
 }
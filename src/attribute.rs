@@ -1,28 +1,279 @@
 use super::*;
 
 #[derive(
-  EnumString, PartialEq, Debug, Copy, Clone, Serialize, Ord, PartialOrd, Eq, IntoStaticStr,
+  EnumDiscriminants, PartialEq, Debug, Clone, Serialize, Ord, PartialOrd, Eq, IntoStaticStr,
 )]
 #[strum(serialize_all = "kebab-case")]
 #[serde(rename_all = "kebab-case")]
-pub(crate) enum Attribute {
+#[strum_discriminants(name(AttributeDiscriminant))]
+#[strum_discriminants(derive(EnumString, IntoStaticStr, Ord, PartialOrd))]
+#[strum_discriminants(strum(serialize_all = "kebab-case"))]
+pub(crate) enum Attribute<'src> {
+  Arch(Vec<StringLiteral<'src>>),
+  Choices {
+    parameter: StringLiteral<'src>,
+    choices: Vec<StringLiteral<'src>>,
+  },
+  Confirm(Option<StringLiteral<'src>>),
+  Doc(Option<StringLiteral<'src>>),
+  Dotenv(Vec<StringLiteral<'src>>),
+  Env {
+    name: StringLiteral<'src>,
+    value: Expression<'src>,
+  },
+  Group(StringLiteral<'src>),
   Linux,
   Macos,
   NoCd,
   NoExitMessage,
+  Os(Vec<StringLiteral<'src>>),
+  OsFamily(Vec<StringLiteral<'src>>),
+  ParamDoc {
+    parameter: StringLiteral<'src>,
+    doc: StringLiteral<'src>,
+  },
+  Pattern {
+    parameter: StringLiteral<'src>,
+    pattern: StringLiteral<'src>,
+  },
   Private,
+  RequiresEnv(Vec<StringLiteral<'src>>),
+  Script(Shell<'src>),
+  ScriptStdin(Shell<'src>),
+  SeeAlso(Vec<StringLiteral<'src>>),
+  Shell(Shell<'src>),
   Unix,
   Windows,
+  WorkingDirectory(StringLiteral<'src>),
 }
 
-impl Attribute {
-  pub(crate) fn from_name(name: Name) -> Option<Attribute> {
-    name.lexeme().parse().ok()
+impl AttributeDiscriminant {
+  fn argument_range(self) -> RangeInclusive<usize> {
+    match self {
+      Self::Linux
+      | Self::Macos
+      | Self::NoCd
+      | Self::NoExitMessage
+      | Self::Private
+      | Self::Unix
+      | Self::Windows => 0..=0,
+      Self::Confirm | Self::Doc => 0..=1,
+      Self::Arch
+      | Self::Dotenv
+      | Self::Os
+      | Self::OsFamily
+      | Self::RequiresEnv
+      | Self::Script
+      | Self::ScriptStdin
+      | Self::SeeAlso
+      | Self::Shell => 1..=usize::MAX,
+      Self::Choices => 2..=usize::MAX,
+      Self::Group | Self::WorkingDirectory => 1..=1,
+      Self::Env | Self::ParamDoc | Self::Pattern => 2..=2,
+    }
   }
 
-  pub(crate) fn to_str(self) -> &'static str {
+  /// Whether a recipe may have more than one attribute of this kind
+  pub(crate) fn repeatable(self) -> bool {
+    matches!(
+      self,
+      Self::Choices
+        | Self::Env
+        | Self::Group
+        | Self::ParamDoc
+        | Self::Pattern
+        | Self::RequiresEnv
+        | Self::SeeAlso
+    )
+  }
+}
+
+impl<'src> Attribute<'src> {
+  pub(crate) fn new(
+    name: Name<'src>,
+    mut arguments: Vec<StringLiteral<'src>>,
+  ) -> CompileResult<'src, Self> {
+    use AttributeDiscriminant::*;
+
+    let discriminant = name
+      .lexeme()
+      .parse::<AttributeDiscriminant>()
+      .map_err(|_| {
+        name.error(CompileErrorKind::UnknownAttribute {
+          attribute: name.lexeme(),
+        })
+      })?;
+
+    let found = arguments.len();
+    let range = discriminant.argument_range();
+
+    if !range.contains(&found) {
+      return Err(
+        name.error(CompileErrorKind::AttributeArgumentCountMismatch {
+          attribute: name.lexeme(),
+          found,
+          min: *range.start(),
+          max: *range.end(),
+        }),
+      );
+    }
+
+    Ok(match discriminant {
+      Arch => Self::Arch(arguments),
+      Choices => {
+        let parameter = arguments.remove(0);
+        Self::Choices {
+          parameter,
+          choices: arguments,
+        }
+      }
+      Confirm => Self::Confirm(arguments.pop()),
+      Doc => Self::Doc(arguments.pop()),
+      Dotenv => Self::Dotenv(arguments),
+      Env => {
+        let string_literal = arguments.pop().unwrap();
+        Self::Env {
+          name: arguments.remove(0),
+          value: Expression::StringLiteral { string_literal },
+        }
+      }
+      Group => Self::Group(arguments.remove(0)),
+      Linux => Self::Linux,
+      Macos => Self::Macos,
+      NoCd => Self::NoCd,
+      NoExitMessage => Self::NoExitMessage,
+      Os => Self::Os(arguments),
+      OsFamily => Self::OsFamily(arguments),
+      ParamDoc => {
+        let doc = arguments.pop().unwrap();
+        Self::ParamDoc {
+          parameter: arguments.remove(0),
+          doc,
+        }
+      }
+      Pattern => {
+        let pattern = arguments.pop().unwrap();
+
+        if let Err(source) = Self::compile_pattern(&pattern.cooked) {
+          return Err(name.error(CompileErrorKind::InvalidPattern {
+            pattern: pattern.cooked,
+            message: source.to_string(),
+          }));
+        }
+
+        Self::Pattern {
+          parameter: arguments.remove(0),
+          pattern,
+        }
+      }
+      Private => Self::Private,
+      RequiresEnv => Self::RequiresEnv(arguments),
+      Script => {
+        let command = arguments.remove(0);
+        Self::Script(self::Shell { arguments, command })
+      }
+      ScriptStdin => {
+        let command = arguments.remove(0);
+        Self::ScriptStdin(self::Shell { arguments, command })
+      }
+      SeeAlso => Self::SeeAlso(arguments),
+      Shell => {
+        let command = arguments.remove(0);
+        Self::Shell(self::Shell { arguments, command })
+      }
+      Unix => Self::Unix,
+      Windows => Self::Windows,
+      WorkingDirectory => Self::WorkingDirectory(arguments.remove(0)),
+    })
+  }
+
+  pub(crate) fn name(&self) -> &'static str {
     self.into()
   }
+
+  /// Compile the regular expression of a `[pattern]` attribute, which must
+  /// match the whole of an argument
+  pub(crate) fn compile_pattern(pattern: &str) -> Result<Regex, regex::Error> {
+    Regex::new(&format!("^(?:{pattern})$"))
+  }
+
+  /// Whether `value` is accepted by this attribute, which is only false for
+  /// `[choices]` and `[pattern]` attributes
+  pub(crate) fn accepts(&self, value: &str) -> bool {
+    match self {
+      Self::Choices { choices, .. } => choices.iter().any(|choice| choice.cooked == value),
+      Self::Pattern { pattern, .. } => {
+        Self::compile_pattern(&pattern.cooked).is_ok_and(|regex| regex.is_match(value))
+      }
+      _ => true,
+    }
+  }
+
+  /// The name of the parameter constrained or documented by this attribute, if
+  /// any
+  pub(crate) fn parameter(&self) -> Option<&str> {
+    match self {
+      Self::Choices { parameter, .. }
+      | Self::ParamDoc { parameter, .. }
+      | Self::Pattern { parameter, .. } => Some(&parameter.cooked),
+      _ => None,
+    }
+  }
+
+  fn arguments(&self) -> Vec<&StringLiteral<'src>> {
+    match self {
+      Self::Choices { parameter, choices } => iter::once(parameter).chain(choices).collect(),
+      Self::Confirm(argument) | Self::Doc(argument) => argument.iter().collect(),
+      Self::Script(shell) | Self::ScriptStdin(shell) | Self::Shell(shell) => {
+        iter::once(&shell.command).chain(&shell.arguments).collect()
+      }
+      Self::Group(argument) | Self::WorkingDirectory(argument) => vec![argument],
+      Self::ParamDoc { parameter, doc } => vec![parameter, doc],
+      Self::Pattern { parameter, pattern } => vec![parameter, pattern],
+      Self::Arch(arguments)
+      | Self::Dotenv(arguments)
+      | Self::Os(arguments)
+      | Self::OsFamily(arguments)
+      | Self::RequiresEnv(arguments)
+      | Self::SeeAlso(arguments) => arguments.iter().collect(),
+      Self::Env { .. }
+      | Self::Linux
+      | Self::Macos
+      | Self::NoCd
+      | Self::NoExitMessage
+      | Self::Private
+      | Self::Unix
+      | Self::Windows => Vec::new(),
+    }
+  }
+}
+
+impl<'src> Display for Attribute<'src> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.name())?;
+
+    if let Self::Env { name, value } = self {
+      return write!(f, "({name}, {value})");
+    }
+
+    let arguments = self.arguments();
+
+    if !arguments.is_empty() {
+      write!(f, "(")?;
+
+      for (i, argument) in arguments.iter().enumerate() {
+        if i > 0 {
+          write!(f, ", ")?;
+        }
+
+        write!(f, "{argument}")?;
+      }
+
+      write!(f, ")")?;
+    }
+
+    Ok(())
+  }
 }
 
 #[cfg(test)]
@@ -30,7 +281,7 @@ mod tests {
   use super::*;
 
   #[test]
-  fn to_str() {
-    assert_eq!(Attribute::NoExitMessage.to_str(), "no-exit-message");
+  fn name() {
+    assert_eq!(Attribute::NoExitMessage.name(), "no-exit-message");
   }
 }
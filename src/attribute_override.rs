@@ -0,0 +1,171 @@
+use super::*;
+
+/// An override of an attribute of the recipes invoked on the command line,
+/// given with `--attribute NAME[:VALUE]` to add an attribute, or replace an
+/// existing one of the same kind, or with `--no-attribute NAME` to remove one.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) enum AttributeOverride {
+  Remove(AttributeDiscriminant),
+  Set {
+    attribute: AttributeDiscriminant,
+    value: Option<String>,
+  },
+}
+
+impl AttributeOverride {
+  /// Attributes which only affect how a recipe is run, and so may be
+  /// overridden for a single run
+  const OVERRIDABLE: [AttributeDiscriminant; 4] = [
+    AttributeDiscriminant::Confirm,
+    AttributeDiscriminant::NoCd,
+    AttributeDiscriminant::NoExitMessage,
+    AttributeDiscriminant::WorkingDirectory,
+  ];
+
+  fn attribute(name: &str) -> Result<AttributeDiscriminant, String> {
+    let attribute = name
+      .parse::<AttributeDiscriminant>()
+      .map_err(|_| format!("Unknown attribute `{name}`"))?;
+
+    if !Self::OVERRIDABLE.contains(&attribute) {
+      return Err(format!(
+        "Attribute `{name}` cannot be overridden, only {} can be",
+        List::and_ticked(
+          Self::OVERRIDABLE
+            .iter()
+            .map(|attribute| <&str>::from(*attribute))
+        ),
+      ));
+    }
+
+    Ok(attribute)
+  }
+
+  /// Parse the argument of `--attribute`
+  pub(crate) fn set(text: &str) -> Result<Self, String> {
+    let (name, value) = match text.split_once(':') {
+      Some((name, value)) => (name, Some(value)),
+      None => (text, None),
+    };
+
+    let attribute = Self::attribute(name)?;
+
+    let takes_value = match attribute {
+      AttributeDiscriminant::Confirm => value.is_some(),
+      AttributeDiscriminant::WorkingDirectory => true,
+      _ => false,
+    };
+
+    if takes_value != value.is_some() {
+      return Err(if takes_value {
+        format!("Attribute `{name}` requires a value, e.g. `{name}:VALUE`")
+      } else {
+        format!("Attribute `{name}` does not take a value")
+      });
+    }
+
+    Ok(Self::Set {
+      attribute,
+      value: value.map(str::to_owned),
+    })
+  }
+
+  /// Parse the argument of `--no-attribute`
+  pub(crate) fn remove(name: &str) -> Result<Self, String> {
+    Ok(Self::Remove(Self::attribute(name)?))
+  }
+
+  /// Apply this override to `attributes`. Setting `[no-cd]` or
+  /// `[working-directory]` removes the other, since they conflict.
+  pub(crate) fn apply<'src>(&self, attributes: &mut BTreeSet<Attribute<'src>>) {
+    match self {
+      Self::Remove(attribute) => {
+        attributes.retain(|existing| AttributeDiscriminant::from(existing) != *attribute);
+      }
+      Self::Set { attribute, value } => {
+        let attribute = *attribute;
+
+        let conflicting = match attribute {
+          AttributeDiscriminant::NoCd => Some(AttributeDiscriminant::WorkingDirectory),
+          AttributeDiscriminant::WorkingDirectory => Some(AttributeDiscriminant::NoCd),
+          _ => None,
+        };
+
+        attributes.retain(|existing| {
+          let existing = AttributeDiscriminant::from(existing);
+          existing != attribute && Some(existing) != conflicting
+        });
+
+        // Overrides are not part of the justfile, so their values have no
+        // source text, which is only used when displaying the attribute
+        let value = value.as_ref().map(|value| StringLiteral {
+          kind: StringKind::from_token_start("'").unwrap(),
+          raw: "",
+          cooked: value.clone(),
+        });
+
+        attributes.insert(match (attribute, value) {
+          (AttributeDiscriminant::Confirm, prompt) => Attribute::Confirm(prompt),
+          (AttributeDiscriminant::NoCd, _) => Attribute::NoCd,
+          (AttributeDiscriminant::NoExitMessage, _) => Attribute::NoExitMessage,
+          (AttributeDiscriminant::WorkingDirectory, Some(directory)) => {
+            Attribute::WorkingDirectory(directory)
+          }
+          _ => unreachable!("attribute `{attribute:?}` cannot be overridden"),
+        });
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse() {
+    assert_eq!(
+      AttributeOverride::set("confirm").unwrap(),
+      AttributeOverride::Set {
+        attribute: AttributeDiscriminant::Confirm,
+        value: None,
+      },
+    );
+    assert_eq!(
+      AttributeOverride::set("working-directory:a:b").unwrap(),
+      AttributeOverride::Set {
+        attribute: AttributeDiscriminant::WorkingDirectory,
+        value: Some("a:b".into()),
+      },
+    );
+    assert_eq!(
+      AttributeOverride::remove("no-cd").unwrap(),
+      AttributeOverride::Remove(AttributeDiscriminant::NoCd),
+    );
+  }
+
+  #[test]
+  fn parse_error() {
+    assert!(AttributeOverride::set("foo").is_err());
+    assert!(AttributeOverride::set("private").is_err());
+    assert!(AttributeOverride::set("no-cd:foo").is_err());
+    assert!(AttributeOverride::set("working-directory").is_err());
+    assert!(AttributeOverride::remove("confirm:foo").is_err());
+  }
+
+  #[test]
+  fn set_replaces_conflicting() {
+    let mut attributes = BTreeSet::new();
+    attributes.insert(Attribute::NoCd);
+    attributes.insert(Attribute::Private);
+
+    AttributeOverride::set("working-directory:foo")
+      .unwrap()
+      .apply(&mut attributes);
+
+    assert_eq!(
+      attributes.iter().map(Attribute::name).collect::<Vec<_>>(),
+      ["private", "working-directory"],
+    );
+  }
+}
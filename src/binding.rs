@@ -5,6 +5,8 @@ use super::*;
 pub(crate) struct Binding<'src, V = String> {
   /// Export binding as an environment variable to child processes
   pub(crate) export: bool,
+  /// Evaluate binding on first use, instead of before running recipes
+  pub(crate) lazy: bool,
   /// Binding name
   pub(crate) name: Name<'src>,
   /// Binding value
@@ -1,6 +1,6 @@
 use {
   super::*,
-  ansi_term::{ANSIGenericString, Color::*, Prefix, Style, Suffix},
+  ansi_term::{ANSIGenericString, Prefix, Style, Suffix},
   atty::Stream,
 };
 
@@ -9,6 +9,7 @@ pub(crate) struct Color {
   use_color: UseColor,
   atty: bool,
   style: Style,
+  theme: Theme,
 }
 
 impl Color {
@@ -52,6 +53,10 @@ impl Color {
     }
   }
 
+  pub(crate) fn theme(self, theme: Theme) -> Self {
+    Self { theme, ..self }
+  }
+
   pub(crate) fn stderr(self) -> Self {
     self.redirect(Stream::Stderr)
   }
@@ -61,51 +66,64 @@ impl Color {
   }
 
   pub(crate) fn doc(self) -> Self {
-    self.restyle(Style::new().fg(Blue))
+    self.restyle(self.theme.doc)
   }
 
   pub(crate) fn error(self) -> Self {
-    self.restyle(Style::new().fg(Red).bold())
+    self.restyle(self.theme.error)
   }
 
   pub(crate) fn warning(self) -> Self {
-    self.restyle(Style::new().fg(Yellow).bold())
+    self.restyle(self.theme.warning)
   }
 
   pub(crate) fn banner(self) -> Self {
-    self.restyle(Style::new().fg(Cyan).bold())
+    self.restyle(self.theme.banner)
   }
 
   pub(crate) fn command(self, foreground: Option<ansi_term::Color>) -> Self {
     self.restyle(Style {
-      foreground,
-      is_bold: true,
-      ..Style::default()
+      foreground: foreground.or(self.theme.command.foreground),
+      ..self.theme.command
     })
   }
 
+  pub(crate) fn keyword(self) -> Self {
+    self.restyle(self.theme.keyword)
+  }
+
   pub(crate) fn parameter(self) -> Self {
-    self.restyle(Style::new().fg(Cyan))
+    self.restyle(self.theme.parameter)
   }
 
   pub(crate) fn message(self) -> Self {
-    self.restyle(Style::new().bold())
+    self.restyle(self.theme.message)
   }
 
   pub(crate) fn annotation(self) -> Self {
-    self.restyle(Style::new().fg(Purple))
+    self.restyle(self.theme.annotation)
   }
 
   pub(crate) fn string(self) -> Self {
-    self.restyle(Style::new().fg(Green))
+    self.restyle(self.theme.string)
   }
 
   pub(crate) fn diff_added(self) -> Self {
-    self.restyle(Style::new().fg(Green))
+    self.restyle(self.theme.diff_added)
   }
 
   pub(crate) fn diff_deleted(self) -> Self {
-    self.restyle(Style::new().fg(Red))
+    self.restyle(self.theme.diff_deleted)
+  }
+
+  /// Whether output is written to a terminal, whether or not it is colored
+  pub(crate) fn terminal(&self) -> bool {
+    self.atty
+  }
+
+  #[cfg(test)]
+  pub(crate) fn to_terminal(self) -> Self {
+    Self { atty: true, ..self }
   }
 
   pub(crate) fn active(&self) -> bool {
@@ -135,6 +153,7 @@ impl Default for Color {
       use_color: UseColor::Auto,
       atty: false,
       style: Style::new(),
+      theme: Theme::default(),
     }
   }
 }
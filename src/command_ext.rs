@@ -1,13 +1,43 @@
 use super::*;
 
 pub(crate) trait CommandExt {
-  fn export(&mut self, settings: &Settings, dotenv: &BTreeMap<String, String>, scope: &Scope);
+  fn export(
+    &mut self,
+    config: &Config,
+    settings: &Settings,
+    login_env: &BTreeMap<String, String>,
+    dotenv: &BTreeMap<String, String>,
+    scope: &Scope,
+  );
 
   fn export_scope(&mut self, settings: &Settings, scope: &Scope);
 }
 
 impl CommandExt for Command {
-  fn export(&mut self, settings: &Settings, dotenv: &BTreeMap<String, String>, scope: &Scope) {
+  fn export(
+    &mut self,
+    config: &Config,
+    settings: &Settings,
+    login_env: &BTreeMap<String, String>,
+    dotenv: &BTreeMap<String, String>,
+    scope: &Scope,
+  ) {
+    // Variables are removed one by one, rather than with `env_clear`, so
+    // that `--diff-env` can report them as removed
+    if settings.clean_env(config) {
+      for (name, _value) in env::vars_os() {
+        if !settings.passes_through(&name) {
+          self.env_remove(name);
+        }
+      }
+    }
+
+    // The login environment takes the place of the environment `just` was
+    // run in, so it is exported before, and overridden by, `.env` files
+    for (name, value) in login_env {
+      self.env(name, value);
+    }
+
     for (name, value) in dotenv {
       self.env(name, value);
     }
@@ -23,8 +53,13 @@ impl CommandExt for Command {
     }
 
     for binding in scope.bindings() {
-      if settings.export || binding.export {
-        self.env(binding.name.lexeme(), &binding.value);
+      // Explicitly exported names are checked by the analyzer, but `set
+      // export` exports every variable and parameter, so those whose names
+      // are not valid environment variable names are skipped, as are lazy
+      // variables, which may not have been evaluated
+      let name = settings.export_name(binding.name.lexeme());
+      if binding.export || settings.export && !binding.lazy && is_env_var_name(&name) {
+        self.env(name, &binding.value);
       }
     }
   }
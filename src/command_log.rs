@@ -0,0 +1,51 @@
+use {super::*, chrono::SecondsFormat, std::fs::OpenOptions};
+
+/// An entry in the audit log of commands run by recipes, written to the path
+/// given by `--command-log` or `set command-log := "…"`. Each entry is
+/// appended to the log as a single line of JSON, so that the log can be
+/// processed line by line, and so that concurrent runs don't interleave their
+/// entries.
+#[derive(Debug, Serialize)]
+pub(crate) struct CommandLog<'a> {
+  pub(crate) command: &'a str,
+  pub(crate) directory: &'a Path,
+  pub(crate) exit_code: Option<i32>,
+  pub(crate) recipe: &'a str,
+  pub(crate) timestamp: String,
+}
+
+impl CommandLog<'_> {
+  /// The path of the command log, if any. `--command-log` is relative to the
+  /// invocation directory and takes precedence over `set command-log`, which
+  /// is relative to the working directory.
+  pub(crate) fn path(config: &Config, settings: &Settings, search: &Search) -> Option<PathBuf> {
+    config
+      .command_log
+      .as_ref()
+      .map(|path| config.invocation_directory.join(path))
+      .or_else(|| {
+        settings
+          .command_log
+          .as_ref()
+          .map(|path| search.working_directory.join(path))
+      })
+  }
+
+  /// The current time, formatted for the `timestamp` of an entry
+  pub(crate) fn now() -> String {
+    chrono::Local::now().to_rfc3339_opts(SecondsFormat::Millis, false)
+  }
+
+  /// Append this entry to the log at `path`, creating it if necessary
+  pub(crate) fn append(&self, path: &Path) -> io::Result<()> {
+    let mut line = serde_json::to_string(self)?;
+
+    line.push('\n');
+
+    OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(path)
+      .and_then(|mut file| file.write_all(line.as_bytes()))
+  }
+}
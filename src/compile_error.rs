@@ -27,7 +27,7 @@ impl Display for CompileError<'_> {
       AliasInvalidAttribute { alias, attr } => write!(
         f,
         "Alias {alias} has an invalid attribute `{}`",
-        attr.to_str(),
+        attr.name(),
       ),
       AliasShadowsRecipe { alias, recipe_line } => write!(
         f,
@@ -35,15 +35,82 @@ impl Display for CompileError<'_> {
         self.token.line.ordinal(),
         recipe_line.ordinal(),
       ),
+      AttributeArgumentCountMismatch {
+        attribute,
+        found,
+        min,
+        max,
+      } => {
+        write!(
+          f,
+          "Attribute `{attribute}` got {found} {} but takes ",
+          Count("argument", *found),
+        )?;
+
+        if min == max {
+          let expected = min;
+          write!(f, "{expected} {}", Count("argument", *expected))
+        } else if found < min {
+          write!(f, "at least {min} {}", Count("argument", *min))
+        } else {
+          write!(f, "at most {max} {}", Count("argument", *max))
+        }
+      }
       BacktickShebang => write!(f, "Backticks may not start with `#!`"),
-      CircularRecipeDependency { recipe, ref circle } => {
+      CaptureSubsequentDependency { recipe, dependency } => write!(
+        f,
+        "Recipe `{recipe}` cannot capture the output of subsequent dependency `{dependency}`, \
+         since it runs after the recipe"
+      ),
+      CircularRecipeDependency {
+        recipe,
+        ref circle,
+        ref edges,
+      } => {
         if circle.len() == 2 {
-          write!(f, "Recipe `{recipe}` depends on itself")
+          return write!(f, "Recipe `{recipe}` depends on itself");
+        }
+
+        write!(
+          f,
+          "Recipe `{recipe}` has circular dependency `{}",
+          circle[0]
+        )?;
+
+        for (name, (_, subsequent)) in circle.iter().skip(1).zip(edges) {
+          write!(f, " {} {name}", if *subsequent { "&&" } else { "->" })?;
+        }
+
+        write!(f, "`")?;
+
+        for (pair, (line, subsequent)) in circle.windows(2).zip(edges) {
+          write!(
+            f,
+            "\n  `{}` {} `{}` on line {}",
+            pair[0],
+            if *subsequent {
+              "runs subsequent dependency"
+            } else {
+              "depends on"
+            },
+            pair[1],
+            line.ordinal(),
+          )?;
+        }
+
+        Ok(())
+      }
+      CircularFunctionDependency {
+        function,
+        ref circle,
+      } => {
+        if circle.len() == 2 {
+          write!(f, "Function `{function}` calls itself")
         } else {
           write!(
             f,
-            "Recipe `{recipe}` has circular dependency `{}`",
-            circle.join(" -> ")
+            "Function `{function}` calls itself indirectly: `{}`",
+            circle.join(" -> "),
           )
         }
       }
@@ -61,6 +128,14 @@ impl Display for CompileError<'_> {
           )
         }
       }
+      ConflictingAttributes {
+        recipe,
+        first,
+        second,
+      } => write!(
+        f,
+        "Recipe `{recipe}` has both `[{first}]` and `[{second}]` attributes"
+      ),
       DependencyArgumentCountMismatch {
         dependency,
         found,
@@ -94,6 +169,43 @@ impl Display for CompileError<'_> {
         first.ordinal(),
         self.token.line.ordinal(),
       ),
+      DuplicateCapture { recipe, capture } => write!(
+        f,
+        "Recipe `{recipe}` already has a parameter or capture named `{capture}`"
+      ),
+      DuplicateEnv { recipe, variable } => write!(
+        f,
+        "Recipe `{recipe}` sets environment variable `{variable}` more than once"
+      ),
+      DuplicateExport {
+        variable,
+        first,
+        name,
+      } => write!(
+        f,
+        "`{first}` and `{variable}` are both exported as `{name}`"
+      ),
+      DuplicateFunction { function, first } => write!(
+        f,
+        "Function `{function}` first defined on line {} is redefined on line {}",
+        first.ordinal(),
+        self.token.line.ordinal(),
+      ),
+      DuplicateFunctionParameter {
+        function,
+        parameter,
+      } => {
+        write!(
+          f,
+          "Function `{function}` has duplicate parameter `{parameter}`"
+        )
+      }
+      DuplicateGroup { group, first } => write!(
+        f,
+        "Group `{group}` first declared on line {} is redeclared on line {}",
+        first.ordinal(),
+        self.token.line.ordinal(),
+      ),
       DuplicateParameter { recipe, parameter } => {
         write!(f, "Recipe `{recipe}` has duplicate parameter `{parameter}`")
       }
@@ -124,7 +236,16 @@ impl Display for CompileError<'_> {
           write!(f, "Expected keyword {expected} but found `{}`", found.kind)
         }
       }
+      ExportedLazyVariable { variable } => {
+        write!(f, "Lazy variable `{variable}` cannot be exported")
+      }
       ExtraLeadingWhitespace => write!(f, "Recipe line has extra leading whitespace"),
+      FunctionShadowsBuiltin { function } => {
+        write!(
+          f,
+          "Function `{function}` has the same name as a built-in function"
+        )
+      }
       FunctionArgumentCountMismatch {
         function,
         found,
@@ -158,6 +279,40 @@ impl Display for CompileError<'_> {
           _ => character.escape_default().collect(),
         }
       ),
+      InvalidExportName { name } => write!(
+        f,
+        "`{name}` cannot be exported, since it is not a valid environment variable name, \
+         which may only contain letters, digits, and underscores, and may not start with a digit"
+      ),
+      InvalidExportPrefix { prefix } => write!(
+        f,
+        "Export prefix `{prefix}` is not a valid environment variable name, which may only \
+         contain letters, digits, and underscores, and may not start with a digit"
+      ),
+      InvalidEchoMode { message }
+      | InvalidFormatSetting { message }
+      | InvalidInterpolationDelimiters { message }
+      | InvalidInterval { message }
+      | InvalidWindowsShebang { message } => {
+        write!(f, "{message}")
+      }
+      InvalidParameterDefault {
+        recipe,
+        attribute,
+        parameter,
+        default,
+      } => write!(
+        f,
+        "Recipe `{recipe}` parameter `{parameter}` has default `{default}`, \
+         which is not accepted by its `{attribute}` attribute"
+      ),
+      InvalidPattern { pattern, message } => {
+        write!(f, "Invalid pattern `{pattern}`: {message}")
+      }
+      InvalidRequiredVersion {
+        requirement,
+        message,
+      } => write!(f, "Invalid required version `{requirement}`: {message}"),
       MismatchedClosingDelimiter {
         open,
         open_line,
@@ -183,6 +338,10 @@ impl Display for CompileError<'_> {
         f,
         "Non-default parameter `{parameter}` follows default parameter"
       ),
+      ShebangRecipeWithShellAttribute { recipe } => write!(
+        f,
+        "Shebang recipe `{recipe}` may not have a `[shell]` attribute"
+      ),
       UndefinedVariable { variable } => write!(f, "Variable `{variable}` not defined"),
       UnexpectedCharacter { expected } => write!(f, "Expected character `{expected}`"),
       UnexpectedClosingDelimiter { close } => {
@@ -199,10 +358,24 @@ impl Display for CompileError<'_> {
         write!(f, "Alias `{alias}` has an unknown target `{target}`")
       }
       UnknownAttribute { attribute } => write!(f, "Unknown attribute `{attribute}`"),
+      UnknownAttributeParameter {
+        recipe,
+        attribute,
+        parameter,
+      } => write!(
+        f,
+        "Recipe `{recipe}` has `{attribute}` attribute for unknown parameter `{parameter}`"
+      ),
       UnknownDependency { recipe, unknown } => {
         write!(f, "Recipe `{recipe}` has unknown dependency `{unknown}`")
       }
       UnknownFunction { function } => write!(f, "Call to unknown function `{function}`"),
+      UnknownSeeAlso { recipe, unknown } => {
+        write!(
+          f,
+          "Recipe `{recipe}` has `see-also` attribute for unknown recipe `{unknown}`"
+        )
+      }
       UnknownSetting { setting } => write!(f, "Unknown setting `{setting}`"),
       UnknownStartOfToken => write!(f, "Unknown start of token:"),
       UnpairedCarriageReturn => write!(f, "Unpaired carriage return"),
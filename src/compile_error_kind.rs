@@ -1,24 +1,47 @@
 use super::*;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, IntoStaticStr)]
+#[strum(serialize_all = "kebab-case")]
 pub(crate) enum CompileErrorKind<'src> {
   AliasInvalidAttribute {
     alias: &'src str,
-    attr: Attribute,
+    attr: Attribute<'src>,
   },
   AliasShadowsRecipe {
     alias: &'src str,
     recipe_line: usize,
   },
+  AttributeArgumentCountMismatch {
+    attribute: &'src str,
+    found: usize,
+    min: usize,
+    max: usize,
+  },
   BacktickShebang,
+  CaptureSubsequentDependency {
+    recipe: &'src str,
+    dependency: &'src str,
+  },
   CircularRecipeDependency {
     recipe: &'src str,
     circle: Vec<&'src str>,
+    /// The line of each dependency in `circle`, and whether it is a
+    /// subsequent dependency
+    edges: Vec<(usize, bool)>,
+  },
+  CircularFunctionDependency {
+    function: &'src str,
+    circle: Vec<&'src str>,
   },
   CircularVariableDependency {
     variable: &'src str,
     circle: Vec<&'src str>,
   },
+  ConflictingAttributes {
+    recipe: &'src str,
+    first: &'static str,
+    second: &'static str,
+  },
   DependencyArgumentCountMismatch {
     dependency: &'src str,
     found: usize,
@@ -33,6 +56,31 @@ pub(crate) enum CompileErrorKind<'src> {
     attribute: &'src str,
     first: usize,
   },
+  DuplicateCapture {
+    recipe: &'src str,
+    capture: &'src str,
+  },
+  DuplicateEnv {
+    recipe: &'src str,
+    variable: String,
+  },
+  DuplicateExport {
+    variable: &'src str,
+    first: &'src str,
+    name: String,
+  },
+  DuplicateFunction {
+    function: &'src str,
+    first: usize,
+  },
+  DuplicateFunctionParameter {
+    function: &'src str,
+    parameter: &'src str,
+  },
+  DuplicateGroup {
+    group: String,
+    first: usize,
+  },
   DuplicateParameter {
     recipe: &'src str,
     parameter: &'src str,
@@ -52,7 +100,13 @@ pub(crate) enum CompileErrorKind<'src> {
     expected: Vec<Keyword>,
     found: Token<'src>,
   },
+  ExportedLazyVariable {
+    variable: &'src str,
+  },
   ExtraLeadingWhitespace,
+  FunctionShadowsBuiltin {
+    function: &'src str,
+  },
   FunctionArgumentCountMismatch {
     function: &'src str,
     found: usize,
@@ -65,9 +119,44 @@ pub(crate) enum CompileErrorKind<'src> {
   Internal {
     message: String,
   },
+  InvalidEchoMode {
+    message: String,
+  },
   InvalidEscapeSequence {
     character: char,
   },
+  InvalidExportName {
+    name: &'src str,
+  },
+  InvalidExportPrefix {
+    prefix: String,
+  },
+  InvalidFormatSetting {
+    message: String,
+  },
+  InvalidInterpolationDelimiters {
+    message: String,
+  },
+  InvalidInterval {
+    message: String,
+  },
+  InvalidParameterDefault {
+    recipe: &'src str,
+    attribute: &'static str,
+    parameter: String,
+    default: String,
+  },
+  InvalidPattern {
+    pattern: String,
+    message: String,
+  },
+  InvalidRequiredVersion {
+    requirement: String,
+    message: String,
+  },
+  InvalidWindowsShebang {
+    message: String,
+  },
   MismatchedClosingDelimiter {
     close: Delimiter,
     open: Delimiter,
@@ -83,6 +172,9 @@ pub(crate) enum CompileErrorKind<'src> {
   RequiredParameterFollowsDefaultParameter {
     parameter: &'src str,
   },
+  ShebangRecipeWithShellAttribute {
+    recipe: &'src str,
+  },
   UndefinedVariable {
     variable: &'src str,
   },
@@ -106,6 +198,11 @@ pub(crate) enum CompileErrorKind<'src> {
   UnknownAttribute {
     attribute: &'src str,
   },
+  UnknownAttributeParameter {
+    recipe: &'src str,
+    attribute: &'static str,
+    parameter: String,
+  },
   UnknownDependency {
     recipe: &'src str,
     unknown: &'src str,
@@ -113,6 +210,10 @@ pub(crate) enum CompileErrorKind<'src> {
   UnknownFunction {
     function: &'src str,
   },
+  UnknownSeeAlso {
+    recipe: &'src str,
+    unknown: String,
+  },
   UnknownSetting {
     setting: &'src str,
   },
@@ -3,10 +3,38 @@ use super::*;
 pub(crate) struct Compiler;
 
 impl Compiler {
-  pub(crate) fn compile(src: &str) -> CompileResult<(Ast, Justfile)> {
-    let tokens = Lexer::lex(src)?;
+  pub(crate) fn compile(src: &str) -> Result<(Ast, Justfile), Vec<CompileError>> {
+    let tokens =
+      Lexer::lex(src, &InterpolationDelimiters::scan(src)).map_err(|error| vec![error])?;
     let ast = Parser::parse(&tokens)?;
-    let justfile = Analyzer::analyze(&ast)?;
+    let justfile = Analyzer::analyze(&ast).map_err(|error| vec![error])?;
+
+    Ok((ast, justfile))
+  }
+
+  /// Compile `src` with the items of `overlay`, the contents of a
+  /// `justfile.local`, merged on top of it. The default recipe is still the
+  /// first recipe of `src`, even if `overlay` redefines it.
+  pub(crate) fn compile_with_overlay<'src>(
+    src: &'src str,
+    overlay: &'src str,
+  ) -> Result<(Ast<'src>, Justfile<'src>), Vec<CompileError<'src>>> {
+    let mut ast = Parser::parse(
+      &Lexer::lex(src, &InterpolationDelimiters::scan(src)).map_err(|error| vec![error])?,
+    )?;
+    let overlay = Parser::parse(
+      &Lexer::lex(overlay, &InterpolationDelimiters::scan(overlay)).map_err(|error| vec![error])?,
+    )?;
+
+    Item::overlay(&mut ast.items, overlay.items);
+    ast.warnings.extend(overlay.warnings);
+
+    let mut justfile = Analyzer::analyze(&ast).map_err(|error| vec![error])?;
+
+    justfile.first = ast.items.iter().find_map(|item| match item {
+      Item::Recipe(recipe) => justfile.recipes.get(recipe.name()).cloned(),
+      _ => None,
+    });
 
     Ok((ast, justfile))
   }
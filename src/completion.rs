@@ -0,0 +1,304 @@
+use super::*;
+
+/// A candidate for the word being completed on the command line, printed by
+/// the hidden `--complete` subcommand which shell completion scripts call
+#[derive(Debug, PartialEq)]
+pub(crate) struct Completion {
+  pub(crate) description: Option<String>,
+  pub(crate) value: String,
+}
+
+impl Completion {
+  fn new(value: impl Into<String>, description: Option<String>) -> Self {
+    Self {
+      description,
+      value: value.into(),
+    }
+  }
+
+  /// Completions for the last of `words`, the arguments on the command line
+  /// after `just`. Earlier words are grouped into overrides, recipes, and
+  /// recipe arguments as they would be when running recipes.
+  pub(crate) fn complete(justfile: &Justfile, words: &[String]) -> Vec<Self> {
+    let (current, previous) = match words.split_last() {
+      Some((current, previous)) => (current.as_str(), previous),
+      None => ("", words),
+    };
+
+    let mut seen_recipe = false;
+    let mut state: Option<(&Recipe, usize, BTreeMap<&str, &str>)> = None;
+
+    for word in previous {
+      if let Some((recipe, _, keywords)) = &mut state {
+        if let Ok(Some((parameter, value))) = recipe.keyword_argument(word) {
+          keywords.insert(parameter, value);
+          continue;
+        }
+      }
+
+      if word.starts_with('-') {
+        continue;
+      }
+
+      if let Some((recipe, positional, keywords)) = &mut state {
+        if *positional < recipe.positional_capacity(keywords) {
+          *positional += 1;
+          continue;
+        }
+      } else if !seen_recipe && word.contains('=') {
+        continue;
+      }
+
+      seen_recipe = true;
+      state = justfile
+        .get_recipe(word)
+        .map(|recipe| (recipe, 0, BTreeMap::new()));
+    }
+
+    let mut completions = Vec::new();
+
+    let mut recipes = true;
+
+    if let Some((recipe, positional, keywords)) = &state {
+      if *positional < recipe.positional_capacity(keywords) {
+        let unbound = recipe
+          .parameters
+          .iter()
+          .filter(|parameter| !keywords.contains_key(parameter.name.lexeme()))
+          .collect::<Vec<&Parameter>>();
+
+        if let Some(next) = unbound.get(*positional).or_else(|| {
+          unbound
+            .last()
+            .filter(|parameter| parameter.kind.is_variadic())
+        }) {
+          for choice in Self::choices(recipe, next.name.lexeme()) {
+            completions.push(Self::new(
+              choice,
+              Some(format!("{} of `{}`", next.name, recipe.name())),
+            ));
+          }
+        }
+
+        // Keep the `--` marker of a keyword argument being completed
+        let marker = if current.starts_with("--") { "--" } else { "" };
+
+        for parameter in &unbound {
+          for choice in Self::choices(recipe, parameter.name.lexeme()) {
+            completions.push(Self::new(
+              format!("{marker}{}={choice}", parameter.name),
+              None,
+            ));
+          }
+
+          completions.push(Self::new(
+            format!("{marker}{}=", parameter.name),
+            Some(match &parameter.default {
+              Some(default) => format!("default: {default}"),
+              None => format!("parameter of `{}`", recipe.name()),
+            }),
+          ));
+        }
+
+        // Recipes may only follow once every required parameter has a value
+        recipes = unbound
+          .iter()
+          .skip(*positional)
+          .all(|parameter| parameter.default.is_some() || parameter.kind == ParameterKind::Star);
+      }
+    } else if !seen_recipe {
+      for name in justfile.assignments.keys() {
+        completions.push(Self::new(
+          format!("{name}="),
+          Some("variable override".into()),
+        ));
+      }
+    }
+
+    if recipes {
+      for recipe in justfile.public_recipes(false) {
+        completions.push(Self::new(recipe.name(), recipe.doc().map(str::to_owned)));
+      }
+    }
+
+    completions.retain(|completion| completion.value.starts_with(current));
+
+    completions
+  }
+
+  fn choices<'a>(recipe: &'a Recipe, parameter: &str) -> Vec<&'a str> {
+    recipe
+      .attributes
+      .iter()
+      .filter_map(|attribute| match attribute {
+        Attribute::Choices {
+          parameter: name,
+          choices,
+        } if name.cooked == parameter => Some(choices),
+        _ => None,
+      })
+      .flatten()
+      .map(|choice| choice.cooked.as_str())
+      .collect()
+  }
+}
+
+impl Display for Completion {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.value)?;
+
+    if let Some(description) = &self.description {
+      write!(f, "\t{description}")?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn case(justfile: &str, words: &[&str], expected: &[&str]) {
+    let justfile = testing::compile(justfile);
+
+    let words = words
+      .iter()
+      .map(|word| (*word).to_owned())
+      .collect::<Vec<String>>();
+
+    assert_eq!(
+      Completion::complete(&justfile, &words)
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>(),
+      expected,
+    );
+  }
+
+  #[test]
+  fn recipes_and_overrides() {
+    case(
+      "x := 'a'\n# build it\nbuild:\n_private:\ntest:",
+      &[""],
+      &["x=\tvariable override", "build\tbuild it", "test"],
+    );
+  }
+
+  #[test]
+  fn prefix() {
+    case("build:\nbench:\ntest:", &["b"], &["bench", "build"]);
+  }
+
+  #[test]
+  fn no_overrides_after_recipe() {
+    case(
+      "x := 'a'\nbuild:\ntest:",
+      &["build", ""],
+      &["build", "test"],
+    );
+  }
+
+  #[test]
+  fn overrides_before_recipe() {
+    case(
+      "x := 'a'\nbuild:",
+      &["x=b", ""],
+      &["x=\tvariable override", "build"],
+    );
+  }
+
+  #[test]
+  fn required_parameter() {
+    case(
+      "build target:\ntest:",
+      &["build", ""],
+      &["target=\tparameter of `build`"],
+    );
+  }
+
+  #[test]
+  fn optional_parameter() {
+    case(
+      "build target='debug':\ntest:",
+      &["build", ""],
+      &["target=\tdefault: 'debug'", "build", "test"],
+    );
+  }
+
+  #[test]
+  fn filled_parameters() {
+    case(
+      "build target:\ntest:",
+      &["build", "x", ""],
+      &["build", "test"],
+    );
+  }
+
+  #[test]
+  fn keyword_arguments_are_bound() {
+    case(
+      "build a b:\ntest:",
+      &["build", "b=x", ""],
+      &["a=\tparameter of `build`"],
+    );
+  }
+
+  #[test]
+  fn choices() {
+    case(
+      "[choices('mode', 'debug', 'release')]\nbuild mode:",
+      &["build", ""],
+      &[
+        "debug\tmode of `build`",
+        "release\tmode of `build`",
+        "mode=debug",
+        "mode=release",
+        "mode=\tparameter of `build`",
+      ],
+    );
+  }
+
+  #[test]
+  fn choices_as_keyword_argument() {
+    case(
+      "[choices('mode', 'debug', 'release')]\nbuild mode:",
+      &["build", "mode=r"],
+      &["mode=release"],
+    );
+  }
+
+  #[test]
+  fn marked_keyword_arguments() {
+    case(
+      "[choices('mode', 'debug', 'release')]\nbuild mode:",
+      &["build", "--mode=r"],
+      &["--mode=release"],
+    );
+  }
+
+  #[test]
+  fn variadic_parameter() {
+    case(
+      "[choices('x', 'a', 'b')]\nbuild +x:",
+      &["build", "a", ""],
+      &[
+        "a\tx of `build`",
+        "b\tx of `build`",
+        "x=a",
+        "x=b",
+        "x=\tparameter of `build`",
+        "build",
+      ],
+    );
+  }
+
+  #[test]
+  fn flags_are_skipped() {
+    case(
+      "build target:",
+      &["--verbose", "build", "-n", ""],
+      &["target=\tparameter of `build`"],
+    );
+  }
+}
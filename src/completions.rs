@@ -1,5 +1,13 @@
+use {
+  super::*,
+  clap::{App, ArgSettings},
+};
+
+/// Shells for which `--completions` can print a completion script
+pub(crate) const SHELLS: &[&str] = &["bash", "elvish", "fish", "nushell", "powershell", "zsh"];
+
 pub(crate) const FISH_RECIPE_COMPLETIONS: &str = r#"function __fish_just_complete_recipes
-    just --list 2> /dev/null | sed -e '1d; s/^\s*\([^[:space:]]*\)[^#]*$/\1/' -e 's/^\s*\([^[:space:]]*\)[^#]*# \(.*\)$/\1\t\2/'
+    just --complete (commandline -opc)[2..-1] (commandline -ct) 2> /dev/null
 end
 
 # don't suggest files right off
@@ -61,14 +69,16 @@ pub(crate) const ZSH_COMPLETION_REPLACEMENTS: &[(&str, &str)] = &[
             if [[ $lastarg = */* ]]; then
                 # Arguments contain slash would be recognised as a file
                 _arguments -s -S $common '*:: :_files'
+            elif [[ $recipe ]]; then
+                # Complete recipe arguments, or further recipes
+                local completions; completions=(
+                    ${(f)"$(_call_program completions just --complete ${(q)words[2,CURRENT]} 2> /dev/null)"}
+                )
+                completions=(${${completions//:/\\:}//$'\t'/:})
+                _describe -t arguments 'recipe arguments' completions && ret=0
             elif [[ $lastarg = *=* ]]; then
                 # Arguments contain equal would be recognised as a variable
                 _message "value"
-            elif [[ $recipe ]]; then
-                # Show usage message
-                _message "`just --show $recipe`"
-                # Or complete with other commands
-                #_arguments -s -S $common '*:: :_just_commands'
             else
                 _arguments -s -S $common '*:: :_just_commands'
             fi
@@ -88,7 +98,7 @@ pub(crate) const ZSH_COMPLETION_REPLACEMENTS: &[(&str, &str)] = &[
         ${(s: :)$(_call_program commands just --variables)}
     )
     local commands; commands=(
-        ${${${(M)"${(f)$(_call_program commands just --list)}":#    *}/ ##/}/ ##/:Args: }
+        ${${${(M)"${(f)$(_call_program commands just --list)}":#    [^[]*}/ ##/}/ ##/:Args: }
     )
 "#,
   ),
@@ -165,20 +175,157 @@ pub(crate) const BASH_COMPLETION_REPLACEMENTS: &[(&str, &str)] = &[
     r#"                if [[ ${cur} == -* ]] ; then
                     COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                     return 0
-                elif [[ ${COMP_CWORD} -eq 1 ]]; then
-                    local recipes=$(just --summary 2> /dev/null)
-
-                    if echo "${cur}" | \grep -qF '/'; then
-                        local path_prefix=$(echo "${cur}" | sed 's/[/][^/]*$/\//')
-                        local recipes=$(just --summary 2> /dev/null -- "${path_prefix}")
-                        local recipes=$(printf "${path_prefix}%s\t" $recipes)
-                    fi
+                elif [[ ${COMP_CWORD} -eq 1 ]] && echo "${cur}" | \grep -qF '/'; then
+                    local path_prefix=$(echo "${cur}" | sed 's/[/][^/]*$/\//')
+                    local recipes=$(just --summary 2> /dev/null -- "${path_prefix}")
 
                     if [[ $? -eq 0 ]]; then
+                        local recipes=$(printf "${path_prefix}%s\t" $recipes)
                         COMPREPLY=( $(compgen -W "${recipes}" -- "${cur}") )
                         return 0
                     fi
                 fi"#,
   ),
+  (
+    r#"            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0"#,
+    r#"            esac
+
+            # Split the line ourselves, since bash splits words at `=`
+            local line="${COMP_LINE:0:COMP_POINT}"
+            local words
+            read -ra words <<< "${line}"
+            if [[ -z ${line##*[[:space:]]} ]]; then
+                words+=("")
+            fi
+
+            # Completions replace `cur`, which may be only part of the word
+            local word="${words[${#words[@]}-1]}"
+            local prefix="${word%"${cur}"}"
+
+            COMPREPLY=( $(just --complete "${words[@]:1}" 2> /dev/null | cut -f1) )
+            COMPREPLY=( "${COMPREPLY[@]#"${prefix}"}" )
+            return 0"#,
+  ),
   (r#"            just)"#, r#"            "$1")"#),
 ];
+
+pub(crate) const ELVISH_COMPLETION_REPLACEMENTS: &[(&str, &str)] = &[
+  (
+    "edit:completion:arg-completer[just] = [@words]{",
+    "use str
+
+set edit:completion:arg-completer[just] = {|@words|",
+  ),
+  ("fn spaces [n]{", "fn spaces {|n|"),
+  ("repeat $n ' ' | joins ''", "str:join '' [(repeat $n ' ')]"),
+  ("fn cand [text desc]{", "fn cand {|text desc|"),
+  ("    command = 'just'", "    var command = 'just'"),
+  ("for word $words[1:-1] {", "for word $words[1..-1] {"),
+  (
+    "        command = $command';'$word",
+    "        set command = $command';'$word",
+  ),
+  ("    completions = [", "    var completions = ["),
+  (
+    "    $completions[$command]",
+    r#"    if (has-prefix $words[-1] '-') {
+        $completions['just']
+    } else {
+        try {
+            just --complete (all $words[1..]) 2>/dev/null | from-lines | each {|line|
+                var @fields = (str:split "\t" $line)
+                edit:complex-candidate $fields[0] &display-suffix=' '(str:join '' $fields[1..])
+            }
+        } catch {
+        }
+    }"#,
+  ),
+];
+
+const NUSHELL_RECIPE_COMPLETIONS: &str = r#"def "nu-complete just" [context: string] {
+    let words = ($context | split row --regex '\s+' | skip 1)
+    ^just --complete ...$words | complete | get stdout | lines | each {|line|
+        let fields = ($line | split row "\t")
+        { value: $fields.0, description: ($fields | skip 1 | str join) }
+    }
+}
+"#;
+
+/// A nushell `extern` declaration for `just`, which nushell doesn't get from
+/// clap, so it is generated from the flags and options of `app`
+pub(crate) fn nushell(app: &App) -> String {
+  fn switch(long: Option<&str>, short: Option<char>) -> String {
+    match (long, short) {
+      (Some(long), Some(short)) => format!("--{long}(-{short})"),
+      (Some(long), None) => format!("--{long}"),
+      (None, Some(short)) => format!("-{short}"),
+      (None, None) => unreachable!("flag without long or short name"),
+    }
+  }
+
+  fn help(help: Option<&str>) -> String {
+    help
+      .unwrap_or_default()
+      .split_whitespace()
+      .collect::<Vec<&str>>()
+      .join(" ")
+  }
+
+  let mut completers = vec![NUSHELL_RECIPE_COMPLETIONS.to_owned()];
+
+  let mut parameters = Vec::new();
+
+  for flag in &app.p.flags {
+    if !flag.b.is_set(ArgSettings::Hidden) {
+      parameters.push(format!(
+        "    {} # {}",
+        switch(flag.s.long, flag.s.short),
+        help(flag.b.help)
+      ));
+    }
+  }
+
+  for opt in &app.p.opts {
+    if opt.b.is_set(ArgSettings::Hidden) {
+      continue;
+    }
+
+    let completer = if let Some(values) = &opt.v.possible_vals {
+      let completer = format!("nu-complete just {}", opt.s.long.unwrap_or(opt.b.name));
+      completers.push(format!(
+        "def \"{completer}\" [] {{\n    [{}]\n}}\n",
+        values
+          .iter()
+          .map(|value| format!("\"{value}\""))
+          .collect::<Vec<String>>()
+          .join(" "),
+      ));
+      format!("@\"{completer}\"")
+    } else {
+      String::new()
+    };
+
+    parameters.push(format!(
+      "    {}: string{completer} # {}",
+      switch(opt.s.long, opt.s.short),
+      help(opt.b.help),
+    ));
+  }
+
+  for (_, positional) in &app.p.positionals {
+    parameters.push(format!(
+      "    ...{}: string@\"nu-complete just\" # {}",
+      positional.b.name.to_lowercase(),
+      help(positional.b.help),
+    ));
+  }
+
+  format!(
+    "{}\n# {}\nexport extern \"just\" [\n{}\n]",
+    completers.join("\n"),
+    app.p.meta.about.unwrap_or_default(),
+    parameters.join("\n"),
+  )
+}
@@ -0,0 +1,102 @@
+use super::*;
+
+/// The condition of a conditional expression, e.g. `a == b && !(c =~ d)`.
+///
+/// `!` binds more tightly than `&&`, which binds more tightly than `||`.
+#[derive(PartialEq, Debug, Clone)]
+pub(crate) enum Condition<'src> {
+  /// `lhs && rhs`
+  And {
+    lhs: Box<Condition<'src>>,
+    rhs: Box<Condition<'src>>,
+  },
+  /// `lhs == rhs`, `lhs != rhs`, or `lhs =~ rhs`
+  Comparison {
+    lhs: Box<Expression<'src>>,
+    rhs: Box<Expression<'src>>,
+    operator: ConditionalOperator,
+  },
+  /// `(contents)`
+  Group { contents: Box<Condition<'src>> },
+  /// `!condition`
+  Not { condition: Box<Condition<'src>> },
+  /// `lhs || rhs`
+  Or {
+    lhs: Box<Condition<'src>>,
+    rhs: Box<Condition<'src>>,
+  },
+}
+
+impl<'src> Condition<'src> {
+  /// The expressions compared in this condition, from left to right
+  pub(crate) fn expressions(&self) -> Vec<&Expression<'src>> {
+    match self {
+      Self::And { lhs, rhs } | Self::Or { lhs, rhs } => {
+        let mut expressions = lhs.expressions();
+        expressions.extend(rhs.expressions());
+        expressions
+      }
+      Self::Comparison { lhs, rhs, .. } => vec![lhs, rhs],
+      Self::Group { contents } => contents.expressions(),
+      Self::Not { condition } => condition.expressions(),
+    }
+  }
+
+  /// Serialize the operator of this condition followed by its operands into
+  /// `seq`, so that conditional expressions can be serialized as `["if",
+  /// operator, operands…, then, otherwise]`
+  pub(crate) fn serialize_elements<S>(&self, seq: &mut S) -> Result<(), S::Error>
+  where
+    S: SerializeSeq,
+  {
+    match self {
+      Self::And { lhs, rhs } => {
+        seq.serialize_element("&&")?;
+        seq.serialize_element(lhs)?;
+        seq.serialize_element(rhs)
+      }
+      Self::Comparison { lhs, rhs, operator } => {
+        seq.serialize_element(&operator.to_string())?;
+        seq.serialize_element(lhs)?;
+        seq.serialize_element(rhs)
+      }
+      Self::Group { contents } => contents.serialize_elements(seq),
+      Self::Not { condition } => {
+        seq.serialize_element("!")?;
+        seq.serialize_element(condition)
+      }
+      Self::Or { lhs, rhs } => {
+        seq.serialize_element("||")?;
+        seq.serialize_element(lhs)?;
+        seq.serialize_element(rhs)
+      }
+    }
+  }
+}
+
+impl<'src> Display for Condition<'src> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::And { lhs, rhs } => write!(f, "{lhs} && {rhs}"),
+      Self::Comparison { lhs, rhs, operator } => write!(f, "{lhs} {operator} {rhs}"),
+      Self::Group { contents } => write!(f, "({contents})"),
+      Self::Not { condition } => write!(f, "!{condition}"),
+      Self::Or { lhs, rhs } => write!(f, "{lhs} || {rhs}"),
+    }
+  }
+}
+
+impl<'src> Serialize for Condition<'src> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    if let Self::Group { contents } = self {
+      return contents.serialize(serializer);
+    }
+
+    let mut seq = serializer.serialize_seq(None)?;
+    self.serialize_elements(&mut seq)?;
+    seq.end()
+  }
+}
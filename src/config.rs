@@ -13,99 +13,184 @@ pub(crate) const CHOOSE_HELP: &str = "Select one or more recipes to run using a
 #[derive(Debug, PartialEq)]
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct Config {
+  pub(crate) attribute_overrides: Vec<AttributeOverride>,
   pub(crate) check: bool,
+  pub(crate) clean_env: bool,
   pub(crate) color: Color,
   pub(crate) command_color: Option<ansi_term::Color>,
+  pub(crate) command_log: Option<PathBuf>,
+  pub(crate) diff_env: bool,
+  pub(crate) diff_only: bool,
   pub(crate) dotenv_filename: Option<String>,
   pub(crate) dotenv_path: Option<PathBuf>,
   pub(crate) dry_run: bool,
   pub(crate) dump_format: DumpFormat,
+  pub(crate) echo: Option<EchoMode>,
+  pub(crate) error_format: ErrorFormat,
+  pub(crate) exit_status: ExitStatusMode,
+  pub(crate) fallback: bool,
   pub(crate) highlight: bool,
+  pub(crate) internal_debug: bool,
   pub(crate) invocation_directory: PathBuf,
+  pub(crate) keep_going: bool,
+  pub(crate) keep_tempfiles: bool,
   pub(crate) list_heading: String,
   pub(crate) list_prefix: String,
+  pub(crate) list_source: bool,
+  pub(crate) list_source_file: Option<PathBuf>,
   pub(crate) load_dotenv: bool,
+  pub(crate) no_deps: bool,
+  pub(crate) offline: bool,
+  pub(crate) one_shot: bool,
+  pub(crate) prompt: bool,
+  pub(crate) report: Option<PathBuf>,
+  pub(crate) resolve_imports: bool,
+  /// The justfile found with `search_config` while loading the workspace
+  /// configuration, if any
+  pub(crate) search: Option<Search>,
   pub(crate) search_config: SearchConfig,
   pub(crate) shell: Option<String>,
   pub(crate) shell_args: Option<Vec<String>>,
   pub(crate) shell_command: bool,
+  pub(crate) stall_warning: Option<Interval>,
+  pub(crate) stdout: bool,
   pub(crate) subcommand: Subcommand,
+  pub(crate) timestamps: Option<Timestamps>,
   pub(crate) unsorted: bool,
   pub(crate) unstable: bool,
   pub(crate) verbosity: Verbosity,
+  pub(crate) yes: bool,
 }
 
 mod cmd {
   pub(crate) const CHANGELOG: &str = "CHANGELOG";
   pub(crate) const CHOOSE: &str = "CHOOSE";
   pub(crate) const COMMAND: &str = "COMMAND";
+  pub(crate) const COMPLETE: &str = "COMPLETE";
   pub(crate) const COMPLETIONS: &str = "COMPLETIONS";
+  pub(crate) const DOC: &str = "DOC";
   pub(crate) const DUMP: &str = "DUMP";
+  pub(crate) const DUMP_AST: &str = "DUMP-AST";
   pub(crate) const EDIT: &str = "EDIT";
+  pub(crate) const EMIT_ALIASES: &str = "EMIT-ALIASES";
   pub(crate) const EVALUATE: &str = "EVALUATE";
+  pub(crate) const EXPORT_SCRIPT: &str = "EXPORT-SCRIPT";
+  pub(crate) const EXPORT_TASKS: &str = "EXPORT-TASKS";
   pub(crate) const FORMAT: &str = "FORMAT";
   pub(crate) const INIT: &str = "INIT";
+  pub(crate) const INSTALL_LIB: &str = "INSTALL-LIB";
   pub(crate) const LIST: &str = "LIST";
+  pub(crate) const LIST_GROUPS: &str = "LIST-GROUPS";
+  pub(crate) const LSP: &str = "LSP";
+  pub(crate) const MIGRATE: &str = "MIGRATE";
   pub(crate) const SHOW: &str = "SHOW";
   pub(crate) const SUMMARY: &str = "SUMMARY";
+  pub(crate) const TRUST: &str = "TRUST";
   pub(crate) const VARIABLES: &str = "VARIABLES";
 
   pub(crate) const ALL: &[&str] = &[
     CHANGELOG,
     CHOOSE,
     COMMAND,
+    COMPLETE,
     COMPLETIONS,
+    DOC,
     DUMP,
+    DUMP_AST,
     EDIT,
+    EMIT_ALIASES,
     EVALUATE,
+    EXPORT_SCRIPT,
+    EXPORT_TASKS,
     FORMAT,
     INIT,
+    INSTALL_LIB,
     LIST,
+    LIST_GROUPS,
+    LSP,
+    MIGRATE,
     SHOW,
     SUMMARY,
+    TRUST,
     VARIABLES,
   ];
 
   pub(crate) const ARGLESS: &[&str] = &[
     CHANGELOG,
     COMPLETIONS,
+    DOC,
     DUMP,
-    EDIT,
+    DUMP_AST,
+    EMIT_ALIASES,
+    EXPORT_TASKS,
     FORMAT,
-    INIT,
+    INSTALL_LIB,
     LIST,
+    LIST_GROUPS,
+    LSP,
+    MIGRATE,
     SHOW,
     SUMMARY,
+    TRUST,
     VARIABLES,
   ];
 }
 
 mod arg {
   pub(crate) const ARGUMENTS: &str = "ARGUMENTS";
+  pub(crate) const ATTRIBUTE: &str = "ATTRIBUTE";
   pub(crate) const CHECK: &str = "CHECK";
   pub(crate) const CHOOSER: &str = "CHOOSER";
+  pub(crate) const CLEAN_ENV: &str = "CLEAN-ENV";
   pub(crate) const CLEAR_SHELL_ARGS: &str = "CLEAR-SHELL-ARGS";
   pub(crate) const COLOR: &str = "COLOR";
   pub(crate) const COMMAND_COLOR: &str = "COMMAND-COLOR";
+  pub(crate) const COMMAND_LOG: &str = "COMMAND-LOG";
+  pub(crate) const DIFF_ENV: &str = "DIFF-ENV";
+  pub(crate) const DIFF_ONLY: &str = "DIFF-ONLY";
   pub(crate) const DOTENV_FILENAME: &str = "DOTENV-FILENAME";
   pub(crate) const DOTENV_PATH: &str = "DOTENV-PATH";
   pub(crate) const DRY_RUN: &str = "DRY-RUN";
   pub(crate) const DUMP_FORMAT: &str = "DUMP-FORMAT";
+  pub(crate) const ECHO: &str = "ECHO";
+  pub(crate) const ERROR_FORMAT: &str = "ERROR-FORMAT";
+  pub(crate) const EXIT_STATUS: &str = "EXIT-STATUS";
+  pub(crate) const GLOBAL: &str = "GLOBAL";
   pub(crate) const HIGHLIGHT: &str = "HIGHLIGHT";
+  pub(crate) const INLINE_DEPENDENCIES: &str = "INLINE-DEPENDENCIES";
+  pub(crate) const INTERNAL_DEBUG: &str = "INTERNAL-DEBUG";
   pub(crate) const JUSTFILE: &str = "JUSTFILE";
+  pub(crate) const KEEP_GOING: &str = "KEEP-GOING";
+  pub(crate) const KEEP_TEMPFILES: &str = "KEEP-TEMPFILES";
   pub(crate) const LIST_HEADING: &str = "LIST-HEADING";
   pub(crate) const LIST_PREFIX: &str = "LIST-PREFIX";
+  pub(crate) const LIST_TEMPLATES: &str = "LIST-TEMPLATES";
+  pub(crate) const NO_ATTRIBUTE: &str = "NO-ATTRIBUTE";
+  pub(crate) const NO_DEPS: &str = "NO-DEPS";
   pub(crate) const NO_DOTENV: &str = "NO-DOTENV";
+  pub(crate) const NO_FALLBACK: &str = "NO-FALLBACK";
   pub(crate) const NO_HIGHLIGHT: &str = "NO-HIGHLIGHT";
+  pub(crate) const OFFLINE: &str = "OFFLINE";
+  pub(crate) const ONE_SHOT: &str = "ONE-SHOT";
+  pub(crate) const OUTPUT: &str = "OUTPUT";
+  pub(crate) const PROMPT: &str = "PROMPT";
   pub(crate) const QUIET: &str = "QUIET";
+  pub(crate) const REPORT: &str = "REPORT";
+  pub(crate) const RESOLVE_IMPORTS: &str = "RESOLVE-IMPORTS";
   pub(crate) const SET: &str = "SET";
   pub(crate) const SHELL: &str = "SHELL";
   pub(crate) const SHELL_ARG: &str = "SHELL-ARG";
   pub(crate) const SHELL_COMMAND: &str = "SHELL-COMMAND";
+  pub(crate) const SOURCE: &str = "SOURCE";
+  pub(crate) const STALL_WARNING: &str = "STALL-WARNING";
+  pub(crate) const STDOUT: &str = "STDOUT";
+  pub(crate) const TIMESTAMP_FORMAT: &str = "TIMESTAMP-FORMAT";
+  pub(crate) const TIMESTAMPS: &str = "TIMESTAMPS";
   pub(crate) const UNSORTED: &str = "UNSORTED";
   pub(crate) const UNSTABLE: &str = "UNSTABLE";
   pub(crate) const VERBOSE: &str = "VERBOSE";
   pub(crate) const WORKING_DIRECTORY: &str = "WORKING-DIRECTORY";
+  pub(crate) const YES: &str = "YES";
 
   pub(crate) const COLOR_ALWAYS: &str = "always";
   pub(crate) const COLOR_AUTO: &str = "auto";
@@ -132,6 +217,15 @@ mod arg {
   pub(crate) const DUMP_FORMAT_JSON: &str = "json";
   pub(crate) const DUMP_FORMAT_JUST: &str = "just";
   pub(crate) const DUMP_FORMAT_VALUES: &[&str] = &[DUMP_FORMAT_JUST, DUMP_FORMAT_JSON];
+
+  pub(crate) const ERROR_FORMAT_HUMAN: &str = "human";
+  pub(crate) const ERROR_FORMAT_JSON: &str = "json";
+  pub(crate) const ERROR_FORMAT_VALUES: &[&str] = &[ERROR_FORMAT_HUMAN, ERROR_FORMAT_JSON];
+
+  pub(crate) const EXIT_STATUS_CATEGORIZED: &str = "categorized";
+  pub(crate) const EXIT_STATUS_PASSTHROUGH: &str = "passthrough";
+  pub(crate) const EXIT_STATUS_VALUES: &[&str] =
+    &[EXIT_STATUS_PASSTHROUGH, EXIT_STATUS_CATEGORIZED];
 }
 
 impl Config {
@@ -141,11 +235,24 @@ impl Config {
       .version_message("Print version information")
       .setting(AppSettings::ColoredHelp)
       .setting(AppSettings::TrailingVarArg)
+      .arg(
+        Arg::with_name(arg::ATTRIBUTE)
+          .long("attribute")
+          .takes_value(true)
+          .value_name("NAME[:VALUE]")
+          .multiple(true)
+          .number_of_values(1)
+          .validator(|value| AttributeOverride::set(&value).map(|_| ()))
+          .help(
+            "Run the recipes given on the command line as if they had attribute <NAME>, with \
+             argument <VALUE>, e.g. `working-directory:build`",
+          ),
+      )
       .arg(
         Arg::with_name(arg::CHECK)
           .long("check")
           .requires(cmd::FORMAT)
-          .help("Run `--fmt` in 'check' mode. Exits with 0 if justfile is formatted correctly. Exits with 1 and prints a diff if formatting is required."),
+          .help("Run `--fmt` in 'check' mode. Exits with 0 if justfile is formatted correctly. Exits with 1 and prints a unified diff if formatting is required."),
       )
       .arg(
         Arg::with_name(arg::CHOOSER)
@@ -168,6 +275,38 @@ impl Config {
           .possible_values(arg::COMMAND_COLOR_VALUES)
           .help("Echo recipe lines in <COMMAND-COLOR>"),
       )
+      .arg(
+        Arg::with_name(arg::COMMAND_LOG)
+          .long("command-log")
+          .takes_value(true)
+          .value_name("PATH")
+          .help(
+            "Append each command run by a recipe, with its directory, start time, and exit \
+             status, to <PATH>. Overrides `set command-log`",
+          ),
+      )
+      .arg(
+        Arg::with_name(arg::CLEAN_ENV)
+          .long("clean-env")
+          .help(
+            "Run recipes with a clean environment, containing only the variables in \
+             `env-passthrough`, `.env` variables, and exported variables",
+          ),
+      )
+      .arg(
+        Arg::with_name(arg::DIFF_ENV)
+          .long("diff-env")
+          .help("Print the environment variables added, changed, or removed for each recipe"),
+      )
+      .arg(
+        Arg::with_name(arg::DIFF_ONLY)
+          .long("diff-only")
+          .requires(arg::CHECK)
+          .help(
+            "Print only the changed lines of the `--check` diff, without context, and no error \
+             message",
+          ),
+      )
       .arg(
         Arg::with_name(arg::DRY_RUN)
           .short("n")
@@ -182,14 +321,85 @@ impl Config {
           .possible_values(arg::DUMP_FORMAT_VALUES)
           .default_value(arg::DUMP_FORMAT_JUST)
           .value_name("FORMAT")
-          .help("Dump justfile as <FORMAT>"),
+          .help("Dump justfile, or print variables with `--evaluate`, as <FORMAT>"),
+      )
+      .arg(
+        Arg::with_name(arg::ECHO)
+          .long("echo")
+          .takes_value(true)
+          .possible_values(EchoMode::VALUES)
+          .value_name("MODE")
+          .help(
+            "Echo recipe lines before running them with `all`, only after they fail with \
+             `failed`, never with `none`, or prefixed with the recipe name with `prefixed`, \
+             overriding `set echo`",
+          ),
+      )
+      .arg(
+        Arg::with_name(arg::ERROR_FORMAT)
+          .long("error-format")
+          .takes_value(true)
+          .possible_values(arg::ERROR_FORMAT_VALUES)
+          .value_name("FORMAT")
+          .help(
+            "Print errors as colored messages with `human`, the default, or as JSON objects, one \
+             per line, with `json`",
+          ),
+      )
+      .arg(
+        Arg::with_name(arg::EXIT_STATUS)
+          .long("exit-status")
+          .takes_value(true)
+          .possible_values(arg::EXIT_STATUS_VALUES)
+          .value_name("MODE")
+          .help(
+            "Exit with the exit code of the failed recipe line with `passthrough`, the default, or \
+             with a code for the kind of error with `categorized`",
+          ),
+      )
+      .arg(
+        Arg::with_name(arg::GLOBAL)
+          .short("g")
+          .long("global")
+          .conflicts_with_all(&[arg::JUSTFILE, arg::WORKING_DIRECTORY])
+          .help(
+            "Use global justfile, found in `just/justfile` in `$XDG_CONFIG_HOME` or `~/.config`, \
+             or at `~/justfile` or `~/.justfile`",
+          ),
       )
       .arg(
         Arg::with_name(arg::HIGHLIGHT)
           .long("highlight")
-          .help("Highlight echoed recipe lines in bold")
+          .help("Highlight echoed recipe lines in bold, and the syntax of recipes shown with `--show`")
           .overrides_with(arg::NO_HIGHLIGHT),
       )
+      .arg(
+        Arg::with_name(arg::INLINE_DEPENDENCIES)
+          .long("inline-dependencies")
+          .requires(cmd::EXPORT_SCRIPT)
+          .help("Include the dependencies of recipes exported with `--export-script`"),
+      )
+      .arg(
+        Arg::with_name(arg::INTERNAL_DEBUG)
+          .long("internal-debug")
+          .help("Include the evaluator's state in internal evaluation errors, for bug reports"),
+      )
+      .arg(
+        Arg::with_name(arg::KEEP_GOING)
+          .long("keep-going")
+          .help(
+            "Keep running the recipes given on the command line after one fails, and fail at the \
+             end if any did",
+          ),
+      )
+      .arg(
+        Arg::with_name(arg::KEEP_TEMPFILES)
+          .long("keep-tempfiles")
+          .help(
+            "Keep the temporary files that shebang and `[script]` recipes are saved to, and \
+             print their paths",
+          ),
+      )
       .arg(
         Arg::with_name(arg::LIST_HEADING)
           .long("list-heading")
@@ -204,23 +414,80 @@ impl Config {
           .value_name("TEXT")
           .takes_value(true),
       )
+      .arg(
+        Arg::with_name(arg::LIST_TEMPLATES)
+          .long("list-templates")
+          .requires(cmd::INIT)
+          .help("List templates for `--init`"),
+      )
+      .arg(
+        Arg::with_name(arg::NO_ATTRIBUTE)
+          .long("no-attribute")
+          .takes_value(true)
+          .value_name("NAME")
+          .multiple(true)
+          .number_of_values(1)
+          .validator(|value| AttributeOverride::remove(&value).map(|_| ()))
+          .help(
+            "Run the recipes given on the command line as if they didn't have attribute <NAME>, \
+             e.g. `confirm`",
+          ),
+      )
+      .arg(
+        Arg::with_name(arg::NO_DEPS)
+          .long("no-deps")
+          .help("Run recipes given on the command line without their prior dependencies"),
+      )
       .arg(
         Arg::with_name(arg::NO_DOTENV)
           .long("no-dotenv")
           .help("Don't load `.env` file"),
       )
+      .arg(
+        Arg::with_name(arg::NO_FALLBACK)
+          .long("no-fallback")
+          .help("Don't search other justfiles for recipes which aren't found, even with `set fallback`"),
+      )
       .arg(
         Arg::with_name(arg::NO_HIGHLIGHT)
           .long("no-highlight")
-          .help("Don't highlight echoed recipe lines in bold")
+          .help("Don't highlight echoed recipe lines in bold, or the syntax of recipes shown with `--show`")
           .overrides_with(arg::HIGHLIGHT),
       )
+      .arg(
+        Arg::with_name(arg::OFFLINE)
+          .long("offline")
+          .help("Use cached copies of remote `!include`s instead of fetching them"),
+      )
+      .arg(
+        Arg::with_name(arg::ONE_SHOT)
+          .long("one-shot")
+          .help(
+            "Do as little startup work as possible, for shell prompts which print metadata on \
+             every render, by not reading `.just.toml` or fetching remote includes, and using \
+             only cached copies of them. May only be used with `--dump`, `--list`, \
+             `--list-groups`, `--summary`, or `--variables`.",
+          ),
+      )
+      .arg(
+        Arg::with_name(arg::OUTPUT)
+          .long("output")
+          .takes_value(true)
+          .value_name("PATH")
+          .requires(cmd::EXPORT_SCRIPT)
+          .help("Write the script exported with `--export-script` to <PATH> and make it executable"),
+      )
       .arg(
         Arg::with_name(arg::JUSTFILE)
           .short("f")
           .long("justfile")
           .takes_value(true)
-          .help("Use <JUSTFILE> as justfile"),
+          .help("Use <JUSTFILE> as justfile, or read justfile from standard input if <JUSTFILE> is `-`"),
+      )
+      .arg(
+        Arg::with_name(arg::PROMPT)
+          .long("prompt")
+          .help("Prompt for missing recipe arguments when run from a terminal"),
       )
       .arg(
         Arg::with_name(arg::QUIET)
@@ -229,6 +496,25 @@ impl Config {
           .help("Suppress all output")
           .conflicts_with(arg::DRY_RUN),
       )
+      .arg(
+        Arg::with_name(arg::REPORT)
+          .long("report")
+          .takes_value(true)
+          .value_name("PATH")
+          .help(
+            "Write a JSON report of the recipes run, their order, outcomes, and durations to \
+             <PATH>",
+          ),
+      )
+      .arg(
+        Arg::with_name(arg::RESOLVE_IMPORTS)
+          .long("resolve-imports")
+          .requires(cmd::DUMP)
+          .help(
+            "Dump justfile with `!include` directives replaced by the contents of the included \
+             files, annotated with the paths they were included from",
+          ),
+      )
       .arg(
         Arg::with_name(arg::SET)
           .long("set")
@@ -266,6 +552,51 @@ impl Config {
           .overrides_with(arg::SHELL_ARG)
           .help("Clear shell arguments"),
       )
+      .arg(
+        Arg::with_name(arg::SOURCE)
+          .long("source")
+          .requires(cmd::LIST)
+          .takes_value(true)
+          .min_values(0)
+          .max_values(1)
+          .value_name("PATH")
+          .help(
+            "Print the file each recipe was defined in with `--list`. If <PATH> is given, only \
+             list recipes defined in <PATH>",
+          ),
+      )
+      .arg(
+        Arg::with_name(arg::STALL_WARNING)
+          .long("stall-warning")
+          .takes_value(true)
+          .value_name("INTERVAL")
+          .validator(|value| value.parse::<Interval>().map(|_| ()))
+          .help(
+            "Print a warning, along with the line being run, whenever a recipe produces no \
+             output for <INTERVAL>, e.g. `60s`",
+          ),
+      )
+      .arg(
+        Arg::with_name(arg::STDOUT)
+          .long("stdout")
+          .requires(cmd::FORMAT)
+          .conflicts_with(arg::CHECK)
+          .help("Print formatted justfile to stdout instead of overwriting it. Only valid with `--fmt`"),
+      )
+      .arg(
+        Arg::with_name(arg::TIMESTAMPS)
+          .long("timestamps")
+          .help("Print the time before each echoed recipe line and error message"),
+      )
+      .arg(
+        Arg::with_name(arg::TIMESTAMP_FORMAT)
+          .long("timestamp-format")
+          .takes_value(true)
+          .value_name("FORMAT")
+          .requires(arg::TIMESTAMPS)
+          .validator(|value| Timestamps::new(&value).map(|_| ()))
+          .help("Format timestamps with the strftime-style <FORMAT>, by default `%H:%M:%S`"),
+      )
       .arg(
         Arg::with_name(arg::UNSORTED)
           .long("unsorted")
@@ -277,6 +608,11 @@ impl Config {
           .long("unstable")
           .help("Enable unstable features"),
       )
+      .arg(
+        Arg::with_name(arg::YES)
+          .long("yes")
+          .help("Automatically confirm all recipes and `confirm()` prompts"),
+      )
       .arg(
         Arg::with_name(arg::VERBOSE)
           .short("v")
@@ -309,30 +645,90 @@ impl Config {
              set",
           ),
       )
+      .arg(
+        Arg::with_name(cmd::COMPLETE)
+          .long("complete")
+          .takes_value(true)
+          .value_name("WORDS")
+          .multiple(true)
+          .allow_hyphen_values(true)
+          .hidden(true)
+          .help("Print completions for the last of <WORDS>, for use by shell completion scripts"),
+      )
       .arg(
         Arg::with_name(cmd::COMPLETIONS)
           .long("completions")
           .takes_value(true)
           .value_name("SHELL")
-          .possible_values(&clap::Shell::variants())
+          .possible_values(completions::SHELLS)
           .set(ArgSettings::CaseInsensitive)
           .help("Print shell completion script for <SHELL>"),
       )
+      .arg(
+        Arg::with_name(cmd::DOC)
+          .long("doc")
+          .takes_value(true)
+          .value_name("FORMAT")
+          .possible_values(&["man", "markdown"])
+          .set(ArgSettings::CaseInsensitive)
+          .help(
+            "Print documentation for public recipes, with their parameters, aliases, and groups, \
+             as <FORMAT>",
+          ),
+      )
       .arg(
         Arg::with_name(cmd::DUMP)
           .long("dump")
           .help("Print justfile"),
       )
+      .arg(
+        Arg::with_name(cmd::DUMP_AST)
+          .long("dump-ast")
+          .help("Print the justfile's syntax tree, with the location of each name, as JSON"),
+      )
       .arg(
         Arg::with_name(cmd::EDIT)
           .short("e")
           .long("edit")
-          .help("Edit justfile with editor given by $VISUAL or $EDITOR, falling back to `vim`"),
+          .help(
+            "Edit justfile with editor given by $VISUAL or $EDITOR, falling back to `vim`. With \
+             `just --edit RECIPE`, open the file where RECIPE is defined at its line",
+          ),
+      )
+      .arg(
+        Arg::with_name(cmd::EMIT_ALIASES)
+          .long("emit-aliases")
+          .takes_value(true)
+          .value_name("SHELL")
+          .possible_values(&["bash", "fish", "pwsh"])
+          .set(ArgSettings::CaseInsensitive)
+          .help("Print <SHELL> aliases for public recipes, e.g. `alias jb='just build'`"),
       )
       .arg(Arg::with_name(cmd::EVALUATE).long("evaluate").help(
         "Evaluate and print all variables. If a variable name is given as an argument, only print \
-         that variable's value.",
+         that variable's value. If several are given, print them as `NAME=VALUE` lines. With \
+         `--dump-format json`, print variables as a JSON object.",
       ))
+      .arg(
+        Arg::with_name(cmd::EXPORT_SCRIPT)
+          .long("export-script")
+          .help(
+            "Print the recipes given as arguments, evaluated, as a standalone shell script which \
+             runs them without just",
+          ),
+      )
+      .arg(
+        Arg::with_name(cmd::EXPORT_TASKS)
+          .long("export-tasks")
+          .takes_value(true)
+          .value_name("EDITOR")
+          .possible_values(&["jetbrains", "vscode"])
+          .set(ArgSettings::CaseInsensitive)
+          .help(
+            "Print <EDITOR> task definitions for public recipes, `.vscode/tasks.json` for \
+             `vscode` or a `.run/*.run.xml` file for `jetbrains`",
+          ),
+      )
       .arg(
         Arg::with_name(cmd::FORMAT)
           .long("fmt")
@@ -341,7 +737,20 @@ impl Config {
       .arg(
         Arg::with_name(cmd::INIT)
           .long("init")
-          .help("Initialize new justfile in project root"),
+          .help(
+            "Initialize new justfile in project root. With `just --init TEMPLATE`, write \
+             starter recipes for a kind of project, listed by `just --init --list-templates`",
+          ),
+      )
+      .arg(
+        Arg::with_name(cmd::INSTALL_LIB)
+          .long("install-lib")
+          .takes_value(true)
+          .value_name("SOURCE")
+          .help(
+            "Install recipe library from <SOURCE>, `github:ORG/REPO@REF` or `git:URL@REF`, and \
+             pin it in the `just-libs.lock` next to the justfile",
+          ),
       )
       .arg(
         Arg::with_name(cmd::LIST)
@@ -349,6 +758,21 @@ impl Config {
           .long("list")
           .help("List available recipes and their arguments"),
       )
+      .arg(
+        Arg::with_name(cmd::LIST_GROUPS)
+          .long("list-groups")
+          .help("List recipe groups"),
+      )
+      .arg(
+        Arg::with_name(cmd::LSP)
+          .long("lsp")
+          .help("Run a language server for justfiles, which communicates over stdin and stdout"),
+      )
+      .arg(
+        Arg::with_name(cmd::MIGRATE)
+          .long("migrate")
+          .help("Rewrite legacy constructs in justfile to their modern equivalents"),
+      )
       .arg(
         Arg::with_name(cmd::SHOW)
           .short("s")
@@ -362,6 +786,12 @@ impl Config {
           .long("summary")
           .help("List names of available recipes"),
       )
+      .arg(
+        Arg::with_name(cmd::TRUST).long("trust").help(
+          "Trust justfile, allowing it to run if `require-trust` is enabled in the global \
+           configuration file",
+        ),
+      )
       .arg(
         Arg::with_name(cmd::VARIABLES)
           .long("variables")
@@ -457,6 +887,67 @@ impl Config {
     }
   }
 
+  fn error_format_from_matches(matches: &ArgMatches) -> ConfigResult<ErrorFormat> {
+    match matches.value_of(arg::ERROR_FORMAT) {
+      Some(arg::ERROR_FORMAT_JSON) => Ok(ErrorFormat::Json),
+      Some(arg::ERROR_FORMAT_HUMAN) | None => Ok(ErrorFormat::Human),
+      Some(value) => Err(ConfigError::Internal {
+        message: format!("Invalid argument `{value}` to --error-format."),
+      }),
+    }
+  }
+
+  fn echo_from_matches(matches: &ArgMatches) -> ConfigResult<Option<EchoMode>> {
+    matches
+      .value_of(arg::ECHO)
+      .map(|value| {
+        value
+          .parse()
+          .map_err(|message| ConfigError::Internal { message })
+      })
+      .transpose()
+  }
+
+  fn exit_status_from_matches(matches: &ArgMatches) -> ConfigResult<ExitStatusMode> {
+    match matches.value_of(arg::EXIT_STATUS) {
+      Some(arg::EXIT_STATUS_CATEGORIZED) => Ok(ExitStatusMode::Categorized),
+      Some(arg::EXIT_STATUS_PASSTHROUGH) | None => Ok(ExitStatusMode::Passthrough),
+      Some(value) => Err(ConfigError::Internal {
+        message: format!("Invalid argument `{value}` to --exit-status."),
+      }),
+    }
+  }
+
+  /// The single optional argument of `subcommand`, like the template of
+  /// `--init TEMPLATE`, which may not be used with overrides
+  fn optional_argument(
+    subcommand: &'static str,
+    overrides: &BTreeMap<String, String>,
+    arguments: Vec<String>,
+  ) -> ConfigResult<Option<String>> {
+    if !overrides.is_empty() {
+      return Err(ConfigError::SubcommandOverrides {
+        subcommand,
+        overrides: overrides.clone(),
+      });
+    }
+
+    let mut arguments = arguments.into_iter();
+
+    let argument = arguments.next();
+
+    let extra = arguments.collect::<Vec<String>>();
+
+    if !extra.is_empty() {
+      return Err(ConfigError::SubcommandArguments {
+        subcommand,
+        arguments: extra,
+      });
+    }
+
+    Ok(argument)
+  }
+
   pub(crate) fn from_matches(matches: &ArgMatches) -> ConfigResult<Self> {
     let invocation_directory = env::current_dir().context(config_error::CurrentDirContext)?;
 
@@ -466,7 +957,6 @@ impl Config {
       Verbosity::from_flag_occurrences(matches.occurrences_of(arg::VERBOSE))
     };
 
-    let color = Self::color_from_matches(matches)?;
     let command_color = Self::command_color_from_matches(matches)?;
 
     let set_count = matches.occurrences_of(arg::SET);
@@ -492,10 +982,12 @@ impl Config {
       let working_directory = matches.value_of(arg::WORKING_DIRECTORY).map(PathBuf::from);
 
       if let Some(search_directory) = positional.search_directory.map(PathBuf::from) {
-        if justfile.is_some() || working_directory.is_some() {
+        if justfile.is_some() || working_directory.is_some() || matches.is_present(arg::GLOBAL) {
           return Err(ConfigError::SearchDirConflict);
         }
         SearchConfig::FromSearchDirectory { search_directory }
+      } else if matches.is_present(arg::GLOBAL) {
+        SearchConfig::GlobalJustfile
       } else {
         match (justfile, working_directory) {
           (None, None) => SearchConfig::FromInvocationDirectory,
@@ -515,6 +1007,29 @@ impl Config {
       }
     };
 
+    // Errors are reported by the subcommands which need a justfile, see
+    // `Config::search`
+    let search = Search::find(&search_config, &invocation_directory).ok();
+
+    let one_shot = matches.is_present(arg::ONE_SHOT);
+
+    // `--one-shot` skips reading `.just.toml`, since its defaults are mostly
+    // for running recipes
+    let workspace = match &search {
+      Some(search) if !one_shot => WorkspaceConfig::load(search)?,
+      _ => WorkspaceConfig::default(),
+    };
+
+    let color = match workspace.color {
+      Some(use_color) if matches.occurrences_of(arg::COLOR) == 0 => match use_color {
+        UseColor::Auto => Color::auto(),
+        UseColor::Always => Color::always(),
+        UseColor::Never => Color::never(),
+      },
+      _ => Self::color_from_matches(matches)?,
+    }
+    .theme(Theme::from_env().map_err(|message| ConfigError::Colors { message })?);
+
     for subcommand in cmd::ARGLESS {
       if matches.is_present(subcommand) {
         match (!overrides.is_empty(), !positional.arguments.is_empty()) {
@@ -556,40 +1071,78 @@ impl Config {
         arguments,
         overrides,
       }
+    } else if let Some(words) = matches.values_of(cmd::COMPLETE) {
+      Subcommand::Complete {
+        words: words.map(str::to_owned).collect(),
+      }
     } else if let Some(shell) = matches.value_of(cmd::COMPLETIONS) {
       Subcommand::Completions {
         shell: shell.to_owned(),
       }
+    } else if let Some(format) = matches.value_of(cmd::DOC) {
+      Subcommand::Doc {
+        format: format.to_owned(),
+      }
     } else if matches.is_present(cmd::EDIT) {
-      Subcommand::Edit
+      Subcommand::Edit {
+        recipe: Self::optional_argument(cmd::EDIT, &overrides, positional.arguments)?,
+      }
+    } else if let Some(shell) = matches.value_of(cmd::EMIT_ALIASES) {
+      Subcommand::EmitAliases {
+        shell: shell.to_owned(),
+      }
+    } else if matches.is_present(cmd::EXPORT_SCRIPT) {
+      Subcommand::ExportScript {
+        arguments: positional.arguments,
+        inline_dependencies: matches.is_present(arg::INLINE_DEPENDENCIES),
+        output: matches.value_of_os(arg::OUTPUT).map(PathBuf::from),
+        overrides,
+      }
+    } else if let Some(editor) = matches.value_of(cmd::EXPORT_TASKS) {
+      Subcommand::ExportTasks {
+        editor: editor.to_owned(),
+      }
     } else if matches.is_present(cmd::SUMMARY) {
       Subcommand::Summary
     } else if matches.is_present(cmd::DUMP) {
       Subcommand::Dump
+    } else if matches.is_present(cmd::DUMP_AST) {
+      Subcommand::DumpAst
     } else if matches.is_present(cmd::FORMAT) {
       Subcommand::Format
+    } else if matches.is_present(arg::LIST_TEMPLATES) {
+      Subcommand::ListTemplates
     } else if matches.is_present(cmd::INIT) {
-      Subcommand::Init
+      let template = Self::optional_argument(cmd::INIT, &overrides, positional.arguments)?
+        .map(|template| {
+          Template::get(&template)
+            .map(|template| template.name.to_owned())
+            .ok_or(ConfigError::UnknownTemplate { template })
+        })
+        .transpose()?;
+
+      Subcommand::Init { template }
+    } else if let Some(source) = matches.value_of(cmd::INSTALL_LIB) {
+      Subcommand::InstallLib {
+        source: source.to_owned(),
+      }
     } else if matches.is_present(cmd::LIST) {
       Subcommand::List
+    } else if matches.is_present(cmd::LIST_GROUPS) {
+      Subcommand::ListGroups
+    } else if matches.is_present(cmd::LSP) {
+      Subcommand::Lsp
+    } else if matches.is_present(cmd::MIGRATE) {
+      Subcommand::Migrate
+    } else if matches.is_present(cmd::TRUST) {
+      Subcommand::Trust
     } else if let Some(name) = matches.value_of(cmd::SHOW) {
       Subcommand::Show {
         name: name.to_owned(),
       }
     } else if matches.is_present(cmd::EVALUATE) {
-      if positional.arguments.len() > 1 {
-        return Err(ConfigError::SubcommandArguments {
-          subcommand: cmd::EVALUATE,
-          arguments: positional
-            .arguments
-            .into_iter()
-            .skip(1)
-            .collect::<Vec<String>>(),
-        });
-      }
-
       Subcommand::Evaluate {
-        variable: positional.arguments.into_iter().next(),
+        variables: positional.arguments,
         overrides,
       }
     } else if matches.is_present(cmd::VARIABLES) {
@@ -601,6 +1154,34 @@ impl Config {
       }
     };
 
+    if one_shot
+      && !matches!(
+        subcommand,
+        Subcommand::Dump
+          | Subcommand::DumpAst
+          | Subcommand::List
+          | Subcommand::ListGroups
+          | Subcommand::Summary
+          | Subcommand::Variables
+      )
+    {
+      return Err(ConfigError::OneShot);
+    }
+
+    if matches.value_of_os(arg::JUSTFILE) == Some(OsStr::new("-"))
+      && matches!(
+        subcommand,
+        Subcommand::Edit { .. }
+          | Subcommand::Format
+          | Subcommand::Init { .. }
+          | Subcommand::InstallLib { .. }
+          | Subcommand::Migrate
+          | Subcommand::Trust
+      )
+    {
+      return Err(ConfigError::StdinJustfile);
+    }
+
     let shell_args = if matches.occurrences_of(arg::SHELL_ARG) > 0
       || matches.occurrences_of(arg::CLEAR_SHELL_ARGS) > 0
     {
@@ -612,44 +1193,132 @@ impl Config {
           }),
       )
     } else {
-      None
+      workspace.shell_args
     };
 
     let unstable = matches.is_present(arg::UNSTABLE)
       || env::var_os("JUST_UNSTABLE")
         .map(|val| !(val == "false" || val == "0" || val.is_empty()))
+        .or(workspace.unstable)
         .unwrap_or_default();
 
+    let mut attribute_overrides = Vec::new();
+
+    for name in matches.values_of(arg::NO_ATTRIBUTE).into_iter().flatten() {
+      attribute_overrides.push(AttributeOverride::remove(name).map_err(ConfigError::internal)?);
+    }
+
+    for attribute in matches.values_of(arg::ATTRIBUTE).into_iter().flatten() {
+      attribute_overrides.push(AttributeOverride::set(attribute).map_err(ConfigError::internal)?);
+    }
+
     Ok(Self {
+      attribute_overrides,
       check: matches.is_present(arg::CHECK),
+      clean_env: matches.is_present(arg::CLEAN_ENV),
+      diff_env: matches.is_present(arg::DIFF_ENV),
+      diff_only: matches.is_present(arg::DIFF_ONLY),
       dry_run: matches.is_present(arg::DRY_RUN),
       dump_format: Self::dump_format_from_matches(matches)?,
+      echo: Self::echo_from_matches(matches)?,
+      error_format: Self::error_format_from_matches(matches)?,
+      exit_status: Self::exit_status_from_matches(matches)?,
+      fallback: !matches.is_present(arg::NO_FALLBACK),
       highlight: !matches.is_present(arg::NO_HIGHLIGHT),
-      shell: matches.value_of(arg::SHELL).map(str::to_owned),
+      internal_debug: matches.is_present(arg::INTERNAL_DEBUG),
+      shell: matches
+        .value_of(arg::SHELL)
+        .map(str::to_owned)
+        .or(workspace.shell),
       load_dotenv: !matches.is_present(arg::NO_DOTENV),
+      keep_going: matches.is_present(arg::KEEP_GOING),
+      keep_tempfiles: matches.is_present(arg::KEEP_TEMPFILES),
+      no_deps: matches.is_present(arg::NO_DEPS),
+      offline: matches.is_present(arg::OFFLINE),
+      one_shot,
+      prompt: matches.is_present(arg::PROMPT),
+      report: matches.value_of_os(arg::REPORT).map(PathBuf::from),
+      command_log: matches.value_of_os(arg::COMMAND_LOG).map(PathBuf::from),
+      resolve_imports: matches.is_present(arg::RESOLVE_IMPORTS),
       shell_command: matches.is_present(arg::SHELL_COMMAND),
-      unsorted: matches.is_present(arg::UNSORTED),
+      stall_warning: matches
+        .value_of(arg::STALL_WARNING)
+        .map(str::parse)
+        .transpose()
+        .map_err(ConfigError::internal)?,
+      stdout: matches.is_present(arg::STDOUT),
+      timestamps: if matches.is_present(arg::TIMESTAMPS) {
+        Some(
+          Timestamps::new(
+            matches
+              .value_of(arg::TIMESTAMP_FORMAT)
+              .unwrap_or(Timestamps::DEFAULT_FORMAT),
+          )
+          .map_err(ConfigError::internal)?,
+        )
+      } else {
+        None
+      },
+      unsorted: matches.is_present(arg::UNSORTED) || workspace.unsorted.unwrap_or_default(),
       unstable,
       list_heading: matches
         .value_of(arg::LIST_HEADING)
-        .unwrap_or("Available recipes:\n")
-        .to_owned(),
+        .map(str::to_owned)
+        .or(workspace.list_heading)
+        .unwrap_or_else(|| "Available recipes:\n".into()),
       list_prefix: matches
         .value_of(arg::LIST_PREFIX)
-        .unwrap_or("    ")
-        .to_owned(),
+        .map(str::to_owned)
+        .or(workspace.list_prefix)
+        .unwrap_or_else(|| "    ".into()),
+      list_source: matches.is_present(arg::SOURCE),
+      list_source_file: matches.value_of_os(arg::SOURCE).map(PathBuf::from),
       color,
       command_color,
       invocation_directory,
+      search,
       search_config,
       shell_args,
       subcommand,
-      dotenv_filename: matches.value_of(arg::DOTENV_FILENAME).map(str::to_owned),
+      dotenv_filename: matches
+        .value_of(arg::DOTENV_FILENAME)
+        .map(str::to_owned)
+        .or(workspace.dotenv_filename),
       dotenv_path: matches.value_of(arg::DOTENV_PATH).map(PathBuf::from),
       verbosity,
+      yes: matches.is_present(arg::YES),
     })
   }
 
+  /// Ask the user to confirm `prompt` on stderr, returning whether they
+  /// answered `y` or `yes`. Prompts are confirmed without asking if `--yes`
+  /// was passed, and refused without asking if stdin is not a terminal, since
+  /// then nobody is there to answer.
+  pub(crate) fn confirm(&self, prompt: &str) -> io::Result<bool> {
+    if self.yes {
+      return Ok(true);
+    }
+
+    if !atty::is(atty::Stream::Stdin) {
+      return Ok(false);
+    }
+
+    let answer = Self::read_answer(&format!("{prompt} [y/N]"))?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+  }
+
+  /// Print `prompt` to stderr and read the user's answer from stdin
+  pub(crate) fn read_answer(prompt: &str) -> io::Result<String> {
+    eprint!("{prompt} ");
+    io::stderr().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+
+    Ok(answer.trim_end_matches(['\r', '\n']).to_owned())
+  }
+
   pub(crate) fn require_unstable(&self, message: &str) -> Result<(), Error<'static>> {
     if self.unstable {
       Ok(())
@@ -660,12 +1329,30 @@ impl Config {
     }
   }
 
-  pub(crate) fn run(self, loader: &Loader) -> Result<(), Error> {
-    if let Err(error) = InterruptHandler::install(self.verbosity) {
-      warn!("Failed to set CTRL-C handler: {error}");
+  /// The justfile selected by `search_config`, reusing the one found when
+  /// the configuration was loaded. If none was found, the search is
+  /// repeated to return the error.
+  pub(crate) fn search(&self) -> SearchResult<Search> {
+    match &self.search {
+      Some(search) => Ok(search.clone()),
+      None => Search::find(&self.search_config, &self.invocation_directory),
     }
+  }
 
-    self.subcommand.execute(&self, loader)
+  pub(crate) fn run<'src>(
+    self,
+    loader: &'src Loader,
+    report: &RefCell<Report>,
+  ) -> Result<(), Error<'src>> {
+    // One-shot subcommands only print metadata and never run child processes,
+    // so they skip creating the pipe and thread used to handle signals
+    if !self.one_shot {
+      if let Err(error) = InterruptHandler::install(self.verbosity) {
+        warn!("Failed to set CTRL-C handler: {error}");
+      }
+    }
+
+    self.subcommand.execute(&self, loader, report)
   }
 }
 
@@ -679,14 +1366,28 @@ mod tests {
     {
       name: $name:ident,
       args: [$($arg:expr),*],
+      $(clean_env: $clean_env:expr,)?
       $(color: $color:expr,)?
+      $(command_log: $command_log:expr,)?
       $(dry_run: $dry_run:expr,)?
       $(dump_format: $dump_format:expr,)?
+      $(echo: $echo:expr,)?
+      $(error_format: $error_format:expr,)?
+      $(exit_status: $exit_status:expr,)?
       $(highlight: $highlight:expr,)?
+      $(internal_debug: $internal_debug:expr,)?
+      $(keep_going: $keep_going:expr,)?
+      $(keep_tempfiles: $keep_tempfiles:expr,)?
+      $(list_source: $list_source:expr,)?
+      $(list_source_file: $list_source_file:expr,)?
+      $(no_deps: $no_deps:expr,)?
+      $(one_shot: $one_shot:expr,)?
+      $(report: $report:expr,)?
       $(search_config: $search_config:expr,)?
       $(shell: $shell:expr,)?
       $(shell_args: $shell_args:expr,)?
       $(subcommand: $subcommand:expr,)?
+      $(timestamps: $timestamps:expr,)?
       $(unsorted: $unsorted:expr,)?
       $(verbosity: $verbosity:expr,)?
     } => {
@@ -698,14 +1399,28 @@ mod tests {
         ];
 
         let want = Config {
+          $(clean_env: $clean_env,)?
           $(color: $color,)?
+          $(command_log: $command_log,)?
           $(dry_run: $dry_run,)?
           $(dump_format: $dump_format,)?
+          $(echo: $echo,)?
+          $(error_format: $error_format,)?
+          $(exit_status: $exit_status,)?
           $(highlight: $highlight,)?
+          $(internal_debug: $internal_debug,)?
+          $(keep_going: $keep_going,)?
+          $(keep_tempfiles: $keep_tempfiles,)?
+          $(list_source: $list_source,)?
+          $(list_source_file: $list_source_file,)?
+          $(no_deps: $no_deps,)?
+          $(one_shot: $one_shot,)?
+          $(report: $report,)?
           $(search_config: $search_config,)?
           $(shell: $shell,)?
           $(shell_args: $shell_args,)?
           $(subcommand: $subcommand,)?
+          $(timestamps: $timestamps,)?
           $(unsorted: $unsorted,)?
           $(verbosity: $verbosity,)?
           ..testing::config(&[])
@@ -722,7 +1437,17 @@ mod tests {
       .get_matches_from_safe(arguments)
       .expect("argument parsing failed");
     let have = Config::from_matches(&matches).expect("config parsing failed");
-    assert_eq!(have, want);
+    // The justfile found depends on the directory the tests are run in
+    assert_eq!(
+      Config {
+        search: None,
+        ..have
+      },
+      Config {
+        search: None,
+        ..want
+      },
+    );
   }
 
   macro_rules! error {
@@ -889,6 +1614,164 @@ mod tests {
     unsorted: true,
   }
 
+  test! {
+    name: error_format_default,
+    args: [],
+    error_format: ErrorFormat::Human,
+  }
+
+  test! {
+    name: error_format_human,
+    args: ["--error-format", "human"],
+    error_format: ErrorFormat::Human,
+  }
+
+  test! {
+    name: error_format_json,
+    args: ["--error-format", "json"],
+    error_format: ErrorFormat::Json,
+  }
+
+  error! {
+    name: error_format_invalid,
+    args: ["--error-format", "foo"],
+  }
+
+  test! {
+    name: exit_status_default,
+    args: [],
+    exit_status: ExitStatusMode::Passthrough,
+  }
+
+  test! {
+    name: exit_status_categorized,
+    args: ["--exit-status", "categorized"],
+    exit_status: ExitStatusMode::Categorized,
+  }
+
+  test! {
+    name: exit_status_passthrough,
+    args: ["--exit-status", "passthrough"],
+    exit_status: ExitStatusMode::Passthrough,
+  }
+
+  error! {
+    name: exit_status_invalid,
+    args: ["--exit-status", "foo"],
+  }
+
+  test! {
+    name: keep_going_default,
+    args: [],
+    keep_going: false,
+  }
+
+  test! {
+    name: echo_default,
+    args: [],
+    echo: None,
+  }
+
+  test! {
+    name: echo_prefixed,
+    args: ["--echo", "prefixed"],
+    echo: Some(EchoMode::Prefixed),
+  }
+
+  test! {
+    name: internal_debug_default,
+    args: [],
+    internal_debug: false,
+  }
+
+  test! {
+    name: internal_debug,
+    args: ["--internal-debug"],
+    internal_debug: true,
+  }
+
+  test! {
+    name: keep_going,
+    args: ["--keep-going"],
+    keep_going: true,
+  }
+
+  test! {
+    name: command_log_default,
+    args: [],
+    command_log: None,
+  }
+
+  test! {
+    name: command_log,
+    args: ["--command-log", "commands.log"],
+    command_log: Some(PathBuf::from("commands.log")),
+  }
+
+  test! {
+    name: keep_tempfiles_default,
+    args: [],
+    keep_tempfiles: false,
+  }
+
+  test! {
+    name: keep_tempfiles,
+    args: ["--keep-tempfiles"],
+    keep_tempfiles: true,
+  }
+
+  test! {
+    name: clean_env_default,
+    args: [],
+    clean_env: false,
+  }
+
+  test! {
+    name: clean_env,
+    args: ["--clean-env"],
+    clean_env: true,
+  }
+
+  test! {
+    name: no_deps_default,
+    args: [],
+    no_deps: false,
+  }
+
+  test! {
+    name: no_deps,
+    args: ["--no-deps"],
+    no_deps: true,
+  }
+
+  test! {
+    name: timestamps_default,
+    args: [],
+    timestamps: None,
+  }
+
+  test! {
+    name: timestamps,
+    args: ["--timestamps"],
+    timestamps: Some(Timestamps::new(Timestamps::DEFAULT_FORMAT).unwrap()),
+  }
+
+  test! {
+    name: timestamp_format,
+    args: ["--timestamps", "--timestamp-format", "%s"],
+    timestamps: Some(Timestamps::new("%s").unwrap()),
+  }
+
+  error! {
+    name: timestamp_format_without_timestamps,
+    args: ["--timestamp-format", "%s"],
+  }
+
+  error! {
+    name: timestamp_format_invalid,
+    args: ["--timestamps", "--timestamp-format", "%Q"],
+  }
+
   test! {
     name: unsorted_short,
     args: ["-u"],
@@ -913,6 +1796,18 @@ mod tests {
     verbosity: Verbosity::Quiet,
   }
 
+  test! {
+    name: report_default,
+    args: [],
+    report: None,
+  }
+
+  test! {
+    name: report,
+    args: ["--report", "out.json"],
+    report: Some(PathBuf::from("out.json")),
+  }
+
   error! {
     name: dotenv_both_filename_and_path,
     args: ["--dotenv-filename", "foo", "--dotenv-path", "bar"],
@@ -1083,6 +1978,25 @@ mod tests {
     subcommand: Subcommand::Completions{shell: "bash".to_owned()},
   }
 
+  test! {
+    name: subcommand_complete,
+    args: ["--complete", "build", "--verbose", ""],
+    subcommand: Subcommand::Complete {
+      words: vec!["build".to_owned(), "--verbose".to_owned(), String::new()],
+    },
+  }
+
+  test! {
+    name: subcommand_doc,
+    args: ["--doc", "markdown"],
+    subcommand: Subcommand::Doc { format: "markdown".to_owned() },
+  }
+
+  error! {
+    name: subcommand_doc_invalid,
+    args: ["--doc", "html"],
+  }
+
   test! {
     name: subcommand_completions_uppercase,
     args: ["--completions", "BASH"],
@@ -1094,12 +2008,98 @@ mod tests {
     args: ["--completions", "monstersh"],
   }
 
+  test! {
+    name: subcommand_emit_aliases,
+    args: ["--emit-aliases", "fish"],
+    subcommand: Subcommand::EmitAliases{shell: "fish".to_owned()},
+  }
+
+  error! {
+    name: subcommand_emit_aliases_invalid,
+    args: ["--emit-aliases", "monstersh"],
+  }
+
+  test! {
+    name: trailing_arguments,
+    args: ["x=y", "test", "--", "--ignored", "a=b"],
+    subcommand: Subcommand::Run {
+      arguments: vec![
+        String::from("test"),
+        String::from("--"),
+        String::from("--ignored"),
+        String::from("a=b"),
+      ],
+      overrides: map!{"x": "y"},
+    },
+  }
+
+  test! {
+    name: subcommand_export_script,
+    args: ["--export-script", "x=y", "build", "release"],
+    subcommand: Subcommand::ExportScript {
+      arguments: vec![String::from("build"), String::from("release")],
+      inline_dependencies: false,
+      output: None,
+      overrides: map!{"x": "y"},
+    },
+  }
+
+  test! {
+    name: subcommand_export_script_options,
+    args: ["--inline-dependencies", "--output", "build.sh", "--export-script", "build"],
+    subcommand: Subcommand::ExportScript {
+      arguments: vec![String::from("build")],
+      inline_dependencies: true,
+      output: Some(PathBuf::from("build.sh")),
+      overrides: map!{},
+    },
+  }
+
+  error! {
+    name: inline_dependencies_requires_export_script,
+    args: ["--inline-dependencies"],
+  }
+
+  error! {
+    name: output_requires_export_script,
+    args: ["--output", "build.sh", "build"],
+  }
+
+  test! {
+    name: subcommand_export_tasks,
+    args: ["--export-tasks", "vscode"],
+    subcommand: Subcommand::ExportTasks{editor: "vscode".to_owned()},
+  }
+
+  error! {
+    name: subcommand_export_tasks_invalid,
+    args: ["--export-tasks", "emacs"],
+  }
+
+  test! {
+    name: subcommand_install_lib,
+    args: ["--install-lib", "github:org/just-lib@v1"],
+    subcommand: Subcommand::InstallLib{source: "github:org/just-lib@v1".to_owned()},
+  }
+
   test! {
     name: subcommand_dump,
     args: ["--dump"],
     subcommand: Subcommand::Dump,
   }
 
+  test! {
+    name: subcommand_dump_ast,
+    args: ["--dump-ast"],
+    subcommand: Subcommand::DumpAst,
+  }
+
+  test! {
+    name: subcommand_lsp,
+    args: ["--lsp"],
+    subcommand: Subcommand::Lsp,
+  }
+
   test! {
     name: dump_format,
     args: ["--dump-format", "json"],
@@ -1109,7 +2109,23 @@ mod tests {
   test! {
     name: subcommand_edit,
     args: ["--edit"],
-    subcommand: Subcommand::Edit,
+    subcommand: Subcommand::Edit { recipe: None },
+  }
+
+  test! {
+    name: subcommand_edit_recipe,
+    args: ["--edit", "build"],
+    subcommand: Subcommand::Edit { recipe: Some("build".to_owned()) },
+  }
+
+  error! {
+    name: edit_overrides,
+    args: ["--edit", "a=b"],
+    error: ConfigError::SubcommandOverrides { subcommand, overrides },
+    check: {
+      assert_eq!(subcommand, cmd::EDIT);
+      assert_eq!(overrides, map!{"a": "b"});
+    },
   }
 
   test! {
@@ -1117,7 +2133,7 @@ mod tests {
     args: ["--evaluate"],
     subcommand: Subcommand::Evaluate {
       overrides: map!{},
-      variable: None,
+      variables: Vec::new(),
     },
   }
 
@@ -1126,7 +2142,7 @@ mod tests {
     args: ["--evaluate", "x=y"],
     subcommand: Subcommand::Evaluate {
       overrides: map!{"x": "y"},
-      variable: None,
+      variables: Vec::new(),
     },
   }
 
@@ -1135,7 +2151,16 @@ mod tests {
     args: ["--evaluate", "x=y", "foo"],
     subcommand: Subcommand::Evaluate {
       overrides: map!{"x": "y"},
-      variable: Some("foo".to_owned()),
+      variables: vec!["foo".to_owned()],
+    },
+  }
+
+  test! {
+    name: subcommand_evaluate_multiple,
+    args: ["--evaluate", "foo", "bar"],
+    subcommand: Subcommand::Evaluate {
+      overrides: map!{},
+      variables: vec!["foo".to_owned(), "bar".to_owned()],
     },
   }
 
@@ -1145,12 +2170,40 @@ mod tests {
     subcommand: Subcommand::List,
   }
 
+  test! {
+    name: subcommand_list_groups,
+    args: ["--list-groups"],
+    subcommand: Subcommand::ListGroups,
+  }
+
+  test! {
+    name: subcommand_migrate,
+    args: ["--migrate"],
+    subcommand: Subcommand::Migrate,
+  }
+
   test! {
     name: subcommand_list_short,
     args: ["-l"],
     subcommand: Subcommand::List,
   }
 
+  test! {
+    name: subcommand_list_source,
+    args: ["--list", "--source"],
+    list_source: true,
+    list_source_file: None,
+    subcommand: Subcommand::List,
+  }
+
+  test! {
+    name: subcommand_list_source_file,
+    args: ["--list", "--source", "foo.just"],
+    list_source: true,
+    list_source_file: Some(PathBuf::from("foo.just")),
+    subcommand: Subcommand::List,
+  }
+
   test! {
     name: subcommand_show_long,
     args: ["--show", "build"],
@@ -1174,6 +2227,12 @@ mod tests {
     subcommand: Subcommand::Summary,
   }
 
+  test! {
+    name: subcommand_trust,
+    args: ["--trust"],
+    subcommand: Subcommand::Trust,
+  }
+
   test! {
     name: arguments,
     args: ["foo", "bar"],
@@ -1286,6 +2345,18 @@ mod tests {
     },
   }
 
+  test! {
+    name: search_config_global_long,
+    args: ["--global"],
+    search_config: SearchConfig::GlobalJustfile,
+  }
+
+  test! {
+    name: search_config_global_short,
+    args: ["-g"],
+    search_config: SearchConfig::GlobalJustfile,
+  }
+
   test! {
     name: search_config_justfile_long,
     args: ["--justfile", "foo"],
@@ -1344,18 +2415,55 @@ mod tests {
     subcommand: Subcommand::Run { arguments: vec!["build".to_owned()], overrides: BTreeMap::new() },
   }
 
+  test! {
+    name: one_shot_summary,
+    args: ["--one-shot", "--summary"],
+    one_shot: true,
+    subcommand: Subcommand::Summary,
+  }
+
+  error! {
+    name: one_shot_run,
+    args: ["--one-shot", "build"],
+    error: ConfigError::OneShot,
+  }
+
   error! {
     name: search_directory_conflict_justfile,
     args: ["--justfile", "bar", "foo/build"],
     error: ConfigError::SearchDirConflict,
   }
 
+  error! {
+    name: search_directory_conflict_global,
+    args: ["--global", "foo/build"],
+    error: ConfigError::SearchDirConflict,
+  }
+
   error! {
     name: search_directory_conflict_working_directory,
     args: ["--justfile", "bar", "--working-directory", "baz", "foo/build"],
     error: ConfigError::SearchDirConflict,
   }
 
+  error! {
+    name: stdin_justfile_format,
+    args: ["--justfile", "-", "--fmt"],
+    error: ConfigError::StdinJustfile,
+  }
+
+  error! {
+    name: stdin_justfile_migrate,
+    args: ["--justfile", "-", "--migrate"],
+    error: ConfigError::StdinJustfile,
+  }
+
+  error! {
+    name: stdin_justfile_init,
+    args: ["-f", "-", "--init"],
+    error: ConfigError::StdinJustfile,
+  }
+
   error! {
     name: completions_arguments,
     args: ["--completions", "zsh", "foo"],
@@ -1396,9 +2504,19 @@ mod tests {
     },
   }
 
+  error! {
+    name: dump_ast_arguments,
+    args: ["--dump-ast", "bar"],
+    error: ConfigError::SubcommandArguments { subcommand, arguments },
+    check: {
+      assert_eq!(subcommand, cmd::DUMP_AST);
+      assert_eq!(arguments, &["bar"]);
+    },
+  }
+
   error! {
     name: edit_arguments,
-    args: ["--edit", "bar"],
+    args: ["--edit", "foo", "bar"],
     error: ConfigError::SubcommandArguments { subcommand, arguments },
     check: {
       assert_eq!(subcommand, cmd::EDIT);
@@ -1416,9 +2534,52 @@ mod tests {
     },
   }
 
+  test! {
+    name: subcommand_init,
+    args: ["--init"],
+    subcommand: Subcommand::Init { template: None },
+  }
+
+  test! {
+    name: subcommand_init_template,
+    args: ["--init", "rust"],
+    subcommand: Subcommand::Init { template: Some("rust".to_owned()) },
+  }
+
+  test! {
+    name: subcommand_list_templates,
+    args: ["--init", "--list-templates"],
+    subcommand: Subcommand::ListTemplates,
+  }
+
   error! {
-    name: init_arguments,
+    name: list_templates_requires_init,
+    args: ["--list-templates"],
+  }
+
+  error! {
+    name: source_requires_list,
+    args: ["--source"],
+  }
+
+  error! {
+    name: init_unknown_template,
     args: ["--init", "bar"],
+    error: ConfigError::UnknownTemplate { template },
+    check: {
+      assert_eq!(template, "bar");
+    },
+  }
+
+  test! {
+    name: subcommand_init_template_case_insensitive,
+    args: ["--init", "Rust"],
+    subcommand: Subcommand::Init { template: Some("rust".to_owned()) },
+  }
+
+  error! {
+    name: init_arguments,
+    args: ["--init", "rust", "bar"],
     error: ConfigError::SubcommandArguments { subcommand, arguments },
     check: {
       assert_eq!(subcommand, cmd::INIT);
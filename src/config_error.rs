@@ -3,6 +3,8 @@ use super::*;
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub(crate)), context(suffix(Context)))]
 pub(crate) enum ConfigError {
+  #[snafu(display("Invalid `JUST_COLORS`: {}", message))]
+  Colors { message: String },
   #[snafu(display("Failed to get current directory: {}", source))]
   CurrentDir { source: io::Error },
   #[snafu(display(
@@ -12,9 +14,20 @@ pub(crate) enum ConfigError {
   ))]
   Internal { message: String },
   #[snafu(display(
-    "Path-prefixed recipes may not be used with `--working-directory` or `--justfile`."
+    "`--one-shot` may only be used with `--dump`, `--list`, `--list-groups`, `--summary`, or \
+     `--variables`."
+  ))]
+  OneShot,
+  #[snafu(display(
+    "Path-prefixed recipes may not be used with `--working-directory`, `--justfile`, or \
+     `--global`."
   ))]
   SearchDirConflict,
+  #[snafu(display(
+    "`--justfile -` may not be used with `--edit`, `--fmt`, `--init`, `--install-lib`, \
+     `--migrate`, or `--trust`, since they need a justfile on disk."
+  ))]
+  StdinJustfile,
   #[snafu(display(
     "`--{}` used with unexpected {}: {}",
     subcommand.to_lowercase(),
@@ -45,6 +58,25 @@ pub(crate) enum ConfigError {
     overrides: BTreeMap<String, String>,
     arguments: Vec<String>,
   },
+  #[snafu(display(
+    "Unknown template `{}`, expected {}",
+    template,
+    List::or_ticked(Template::names())
+  ))]
+  UnknownTemplate { template: String },
+  #[snafu(display("Failed to read `{}`: {}", path.display(), source))]
+  WorkspaceConfigIo { path: PathBuf, source: io::Error },
+  #[snafu(display(
+    "Error in `{}`{}: {}",
+    path.display(),
+    line.map(|line| format!(" on line {}", line + 1)).unwrap_or_default(),
+    message,
+  ))]
+  WorkspaceConfigParse {
+    path: PathBuf,
+    line: Option<usize>,
+    message: String,
+  },
 }
 
 impl ConfigError {
@@ -0,0 +1,76 @@
+use serde_json::Value;
+
+/// Look up `path` in `data`, parsed from JSON, TOML, or YAML by the `json()`,
+/// `toml()`, and `yaml()` functions. Paths are written like `jq` filters, as
+/// `.` followed by keys and array indices, e.g. `.package.authors[0]`, with
+/// `.` alone selecting the whole document.
+pub(crate) fn lookup_data_path<'a>(data: &'a Value, path: &str) -> Result<&'a Value, String> {
+  let invalid = || format!("Invalid path `{path}`, paths look like `.key[0].key`");
+
+  let mut rest = path.strip_prefix('.').ok_or_else(invalid)?;
+  let mut value = data;
+
+  while !rest.is_empty() {
+    let found = if let Some(index) = rest.strip_prefix('[') {
+      let (index, after) = index.split_once(']').ok_or_else(invalid)?;
+      let index = index.trim().parse::<usize>().map_err(|_| invalid())?;
+      rest = after;
+      value.get(index)
+    } else {
+      let end = rest.find(['.', '[']).unwrap_or(rest.len());
+      let key = &rest[..end];
+      if key.is_empty() {
+        return Err(invalid());
+      }
+      rest = &rest[end..];
+      value.get(key)
+    };
+
+    value = found.ok_or_else(|| format!("Path `{path}` not found"))?;
+
+    if let Some(after) = rest.strip_prefix('.') {
+      if after.is_empty() || after.starts_with('[') {
+        return Err(invalid());
+      }
+      rest = after;
+    } else if !rest.is_empty() && !rest.starts_with('[') {
+      return Err(invalid());
+    }
+  }
+
+  Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, serde_json::json};
+
+  #[test]
+  fn lookup() {
+    let data = json!({"a": {"b": [1, {"c": "d"}]}});
+    assert_eq!(lookup_data_path(&data, ".").unwrap(), &data);
+    assert_eq!(lookup_data_path(&data, ".a.b[0]").unwrap(), &json!(1));
+    assert_eq!(lookup_data_path(&data, ".a.b[1].c").unwrap(), &json!("d"));
+  }
+
+  #[test]
+  fn not_found() {
+    let data = json!({"a": [1]});
+    assert!(lookup_data_path(&data, ".b").is_err());
+    assert!(lookup_data_path(&data, ".a[1]").is_err());
+    assert!(lookup_data_path(&data, ".a.b").is_err());
+  }
+
+  #[test]
+  fn invalid() {
+    let data = json!({"a": [1]});
+    for path in ["", "a", ".a.", ".a..b", ".a[x]", ".a[0", ".a[0]b", ".a.[0]"] {
+      assert!(
+        lookup_data_path(&data, path)
+          .unwrap_err()
+          .starts_with("Invalid path"),
+        "{path}",
+      );
+    }
+  }
+}
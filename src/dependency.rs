@@ -1,18 +1,26 @@
 use super::*;
 
-#[derive(PartialEq, Debug, Serialize)]
+#[derive(PartialEq, Debug, Clone, Serialize)]
 pub(crate) struct Dependency<'src> {
   pub(crate) arguments: Vec<Expression<'src>>,
+  /// The variable bound to the dependency's standard output, if any
+  pub(crate) capture: Option<Name<'src>>,
   #[serde(serialize_with = "keyed::serialize")]
   pub(crate) recipe: Rc<Recipe<'src>>,
 }
 
 impl<'src> Display for Dependency<'src> {
   fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-    if self.arguments.is_empty() {
+    if self.arguments.is_empty() && self.capture.is_none() {
       write!(f, "{}", self.recipe.name())
     } else {
-      write!(f, "({}", self.recipe.name())?;
+      write!(f, "(")?;
+
+      if let Some(capture) = self.capture {
+        write!(f, "{capture} := ")?;
+      }
+
+      write!(f, "{}", self.recipe.name())?;
 
       for argument in &self.arguments {
         write!(f, " {argument}")?;
@@ -0,0 +1,165 @@
+use super::*;
+
+/// The public aliases of `recipe`
+fn aliases<'a>(justfile: &'a Justfile, recipe: &Recipe<Dependency>) -> Vec<&'a str> {
+  justfile
+    .aliases
+    .values()
+    .filter(|alias| !alias.is_private() && alias.target.name() == recipe.name())
+    .map(|alias| alias.name.lexeme())
+    .collect()
+}
+
+/// How to run `recipe`, with its parameters as shown by `--list`
+fn usage(recipe: &Recipe<Dependency>) -> String {
+  iter::once(format!("just {}", recipe.name()))
+    .chain(
+      recipe
+        .parameters
+        .iter()
+        .map(|parameter| parameter.color_display(Color::never()).to_string()),
+    )
+    .collect::<Vec<String>>()
+    .join(" ")
+}
+
+/// Markdown documentation for `recipes`, with a section for each giving its
+/// documentation, usage, parameters, aliases, and groups
+pub(crate) fn markdown(justfile: &Justfile, recipes: &[&Recipe<Dependency>]) -> String {
+  fn code(text: &str) -> String {
+    format!("`{}`", text.replace('|', "\\|"))
+  }
+
+  let mut lines = vec!["# Recipes".to_owned()];
+
+  for recipe in recipes {
+    lines.push(String::new());
+    lines.push(format!("## {}", code(recipe.name())));
+
+    if let Some(doc) = recipe.doc() {
+      lines.push(String::new());
+      lines.push(doc.to_owned());
+    }
+
+    lines.push(String::new());
+    lines.push("```sh".to_owned());
+    lines.push(usage(recipe));
+    lines.push("```".to_owned());
+
+    if !recipe.parameters.is_empty() {
+      lines.push(String::new());
+      lines.push("| Parameter | Default | Description |".to_owned());
+      lines.push("| --- | --- | --- |".to_owned());
+
+      for parameter in &recipe.parameters {
+        lines.push(format!(
+          "| {} | {} | {} |",
+          code(&format!(
+            "{}{}",
+            parameter.kind.prefix().unwrap_or_default(),
+            parameter.name,
+          )),
+          parameter
+            .default
+            .as_ref()
+            .map(|default| code(&default.to_string()))
+            .unwrap_or_default(),
+          parameter
+            .doc
+            .as_deref()
+            .unwrap_or_default()
+            .replace('|', "\\|")
+            .replace('\n', " "),
+        ));
+      }
+    }
+
+    for (heading, names) in [
+      ("Aliases", aliases(justfile, recipe)),
+      ("Groups", recipe.groups()),
+    ] {
+      if !names.is_empty() {
+        lines.push(String::new());
+        lines.push(format!(
+          "**{heading}:** {}",
+          names
+            .iter()
+            .map(|name| code(name))
+            .collect::<Vec<String>>()
+            .join(", "),
+        ));
+      }
+    }
+  }
+
+  lines.join("\n") + "\n"
+}
+
+/// A man page documenting `recipes`, with the same contents as the Markdown
+/// documentation
+pub(crate) fn man(justfile: &Justfile, recipes: &[&Recipe<Dependency>]) -> String {
+  fn escape(text: &str) -> String {
+    text
+      .replace('\\', "\\e")
+      .replace('-', "\\-")
+      .lines()
+      .map(|line| {
+        if line.starts_with('.') || line.starts_with('\'') {
+          format!("\\&{line}")
+        } else {
+          line.to_owned()
+        }
+      })
+      .collect::<Vec<String>>()
+      .join("\n")
+  }
+
+  let mut lines = vec![
+    ".TH JUSTFILE 7".to_owned(),
+    ".SH NAME".to_owned(),
+    "justfile \\- recipes".to_owned(),
+    ".SH RECIPES".to_owned(),
+  ];
+
+  for recipe in recipes {
+    lines.push(format!(".SS {}", escape(recipe.name())));
+    lines.push(".PP".to_owned());
+    lines.push(format!(".B {}", escape(&usage(recipe))));
+
+    if let Some(doc) = recipe.doc() {
+      lines.push(".PP".to_owned());
+      lines.push(escape(doc));
+    }
+
+    for parameter in &recipe.parameters {
+      lines.push(".TP".to_owned());
+      lines.push(format!(
+        ".B {}{}",
+        parameter.kind.prefix().unwrap_or_default(),
+        escape(parameter.name.lexeme()),
+      ));
+
+      let description = match (&parameter.default, &parameter.doc) {
+        (Some(default), Some(doc)) => format!("{doc} (default: {default})"),
+        (Some(default), None) => format!("Default: {default}"),
+        (None, Some(doc)) => doc.clone(),
+        (None, None) if parameter.kind == ParameterKind::Star => "Optional".to_owned(),
+        (None, None) => "Required".to_owned(),
+      };
+
+      lines.push(escape(&description));
+    }
+
+    for (heading, names) in [
+      ("Aliases", aliases(justfile, recipe)),
+      ("Groups", recipe.groups()),
+    ] {
+      if !names.is_empty() {
+        lines.push(".PP".to_owned());
+        lines.push(escape(&format!("{heading}: {}", names.join(", "))));
+      }
+    }
+  }
+
+  lines.join("\n") + "\n"
+}
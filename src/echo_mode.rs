@@ -0,0 +1,63 @@
+use super::*;
+
+/// Which recipe lines are echoed before they are run, selected with
+/// `set echo := "…"` or `--echo`. Lines which would not be echoed anyway,
+/// for example because they start with `@`, are never echoed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum EchoMode {
+  /// Echo lines before they are run
+  #[default]
+  All,
+  /// Echo lines only after they fail
+  Failed,
+  /// Don't echo lines
+  None,
+  /// Echo lines before they are run, prefixed with the recipe name
+  Prefixed,
+}
+
+impl EchoMode {
+  pub(crate) const VALUES: &'static [&'static str] = &["all", "failed", "none", "prefixed"];
+
+  /// Whether lines are echoed before they are run
+  pub(crate) fn before(self) -> bool {
+    matches!(self, Self::All | Self::Prefixed)
+  }
+
+  /// The text echoed for `command`, a line of `recipe`
+  pub(crate) fn text(self, recipe: &str, command: &str) -> String {
+    match self {
+      Self::Prefixed => format!("[{recipe}] {command}"),
+      Self::All | Self::Failed | Self::None => command.to_owned(),
+    }
+  }
+}
+
+impl FromStr for EchoMode {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "all" => Ok(Self::All),
+      "failed" => Ok(Self::Failed),
+      "none" => Ok(Self::None),
+      "prefixed" => Ok(Self::Prefixed),
+      _ => Err(format!(
+        "Invalid echo mode `{s}`, expected {}",
+        List::or_ticked(Self::VALUES)
+      )),
+    }
+  }
+}
+
+impl Display for EchoMode {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.write_str(match self {
+      Self::All => "all",
+      Self::Failed => "failed",
+      Self::None => "none",
+      Self::Prefixed => "prefixed",
+    })
+  }
+}
@@ -0,0 +1,152 @@
+use super::*;
+
+/// Run the `env-hook` command and parse the environment variables it prints,
+/// either as a JSON object, as produced by `direnv export json`, or as
+/// `KEY=VALUE` lines, as produced by `mise env`.
+pub(crate) fn env_hook(
+  config: &Config,
+  hook: &Shell,
+  working_directory: &Path,
+) -> RunResult<'static, BTreeMap<String, String>> {
+  let mut command = Command::new(&hook.command.cooked);
+
+  command.args(hook.arguments.iter().map(|argument| &argument.cooked));
+
+  command.current_dir(working_directory);
+
+  command.stdin(Stdio::inherit());
+
+  command.stderr(if config.verbosity.quiet() {
+    Stdio::null()
+  } else {
+    Stdio::inherit()
+  });
+
+  let stdout =
+    InterruptHandler::guard(|| output(command)).map_err(|output_error| Error::EnvHook {
+      hook: hook.to_string(),
+      output_error,
+    })?;
+
+  parse(&stdout).map_err(|message| Error::EnvHookParse {
+    hook: hook.to_string(),
+    message,
+  })
+}
+
+fn parse(stdout: &str) -> Result<BTreeMap<String, String>, String> {
+  let stdout = stdout.trim();
+
+  if stdout.starts_with('{') {
+    // `direnv` uses `null` for variables which should be unset, which we
+    // cannot represent, so they are skipped
+    let variables = serde_json::from_str::<BTreeMap<String, Option<String>>>(stdout)
+      .map_err(|serde_json_error| serde_json_error.to_string())?;
+
+    if let Some(key) = variables.keys().find(|key| !is_env_var_name(key)) {
+      return Err(invalid_name(key));
+    }
+
+    return Ok(
+      variables
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, value?)))
+        .collect(),
+    );
+  }
+
+  let mut variables = BTreeMap::new();
+
+  for line in stdout.lines() {
+    let line = line.trim();
+
+    if line.is_empty() || line.starts_with('#') {
+      continue;
+    }
+
+    let line = line.strip_prefix("export ").unwrap_or(line);
+
+    let (key, value) = line
+      .split_once('=')
+      .ok_or_else(|| format!("expected `KEY=VALUE` but found `{line}`"))?;
+
+    let value = value.trim();
+
+    let value = if value.len() >= 2
+      && ((value.starts_with('"') && value.ends_with('"'))
+        || (value.starts_with('\'') && value.ends_with('\'')))
+    {
+      &value[1..value.len() - 1]
+    } else {
+      value
+    };
+
+    let key = key.trim();
+
+    if !is_env_var_name(key) {
+      return Err(invalid_name(key));
+    }
+
+    variables.insert(key.to_owned(), value.to_owned());
+  }
+
+  Ok(variables)
+}
+
+fn invalid_name(key: &str) -> String {
+  format!("`{key}` is not a valid environment variable name")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn map(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    pairs
+      .iter()
+      .map(|(key, value)| ((*key).to_owned(), (*value).to_owned()))
+      .collect()
+  }
+
+  #[test]
+  fn json() {
+    assert_eq!(
+      parse(r#"{"FOO": "bar", "BAZ": null}"#).unwrap(),
+      map(&[("FOO", "bar")]),
+    );
+  }
+
+  #[test]
+  fn lines() {
+    assert_eq!(
+      parse("FOO=bar\n\n# comment\nexport BAZ='a b'\nQUX=\"c=d\"\n").unwrap(),
+      map(&[("FOO", "bar"), ("BAZ", "a b"), ("QUX", "c=d")]),
+    );
+  }
+
+  #[test]
+  fn empty() {
+    assert_eq!(parse("").unwrap(), BTreeMap::new());
+  }
+
+  #[test]
+  fn invalid_line() {
+    assert_eq!(
+      parse("FOO").unwrap_err(),
+      "expected `KEY=VALUE` but found `FOO`"
+    );
+  }
+
+  #[test]
+  fn invalid_name() {
+    assert_eq!(
+      parse("FOO-BAR=baz").unwrap_err(),
+      "`FOO-BAR` is not a valid environment variable name"
+    );
+  }
+
+  #[test]
+  fn invalid_json() {
+    assert!(parse("{").is_err());
+  }
+}
@@ -0,0 +1,43 @@
+/// Whether `name` is a portable environment variable name, consisting of
+/// ASCII letters, digits, and underscores, and not starting with a digit.
+/// Shells silently ignore variables with other names, like `foo-bar`.
+pub(crate) fn is_env_var_name(name: &str) -> bool {
+  let mut chars = name.chars();
+
+  chars
+    .next()
+    .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+    && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// The name of the environment variable that a binding named `name` is
+/// exported as, which doesn't include the namespace of bindings from files
+/// included with `!include PATH as NAMESPACE`
+pub(crate) fn env_var_name(name: &str) -> &str {
+  name.rsplit_once("::").map_or(name, |(_, name)| name)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn valid() {
+    assert!(is_env_var_name("FOO"));
+    assert!(is_env_var_name("_foo_1"));
+  }
+
+  #[test]
+  fn invalid() {
+    assert!(!is_env_var_name(""));
+    assert!(!is_env_var_name("foo-bar"));
+    assert!(!is_env_var_name("1FOO"));
+    assert!(!is_env_var_name("FOO BAR"));
+  }
+
+  #[test]
+  fn namespaced() {
+    assert_eq!(env_var_name("FOO"), "FOO");
+    assert_eq!(env_var_name("ci::FOO"), "FOO");
+  }
+}
@@ -1,18 +1,38 @@
 use super::*;
 
-#[derive(Debug)]
+#[derive(Debug, IntoStaticStr)]
+#[strum(serialize_all = "kebab-case")]
 pub(crate) enum Error<'src> {
+  ArgumentChoice {
+    recipe: &'src str,
+    parameter: &'src str,
+    value: String,
+    choices: Vec<String>,
+  },
   ArgumentCountMismatch {
     recipe: &'src str,
     parameters: Vec<Parameter<'src>>,
     found: usize,
     min: usize,
     max: usize,
+    doc: Option<String>,
+  },
+  ArgumentPattern {
+    recipe: &'src str,
+    parameter: &'src str,
+    value: String,
+    pattern: String,
+  },
+  ArgumentPrompt {
+    io_error: io::Error,
   },
   Backtick {
     token: Token<'src>,
     output_error: OutputError,
   },
+  CaptureAfterRun {
+    recipe: &'src str,
+  },
   ChooserInvoke {
     shell_binary: String,
     shell_arguments: String,
@@ -37,7 +57,7 @@ pub(crate) enum Error<'src> {
   },
   Code {
     recipe: &'src str,
-    line_number: Option<usize>,
+    token: Option<Token<'src>>,
     code: i32,
     print_message: bool,
   },
@@ -53,6 +73,11 @@ pub(crate) enum Error<'src> {
   },
   Compile {
     compile_error: CompileError<'src>,
+    path: Option<PathBuf>,
+  },
+  CompileErrors {
+    compile_errors: Vec<CompileError<'src>>,
+    path: Option<PathBuf>,
   },
   Config {
     config_error: ConfigError,
@@ -68,6 +93,16 @@ pub(crate) enum Error<'src> {
   Dotenv {
     dotenv_error: dotenvy::Error,
   },
+  DotenvUndefined {
+    variable: Name<'src>,
+  },
+  DotenvRequired {
+    path: PathBuf,
+  },
+  DuplicateArgument {
+    recipe: &'src str,
+    parameter: &'src str,
+  },
   DumpJson {
     serde_json_error: serde_json::Error,
   },
@@ -79,15 +114,44 @@ pub(crate) enum Error<'src> {
     editor: OsString,
     status: ExitStatus,
   },
+  EnvHook {
+    hook: String,
+    output_error: OutputError,
+  },
+  EnvHookParse {
+    hook: String,
+    message: String,
+  },
   EvalUnknownVariable {
     variable: String,
     suggestion: Option<Suggestion<'src>>,
   },
-  FormatCheckFoundDiff,
+  ExportScriptCapture {
+    recipe: &'src str,
+    dependency: &'src str,
+  },
+  ExportScriptShell {
+    shell: String,
+  },
+  FormatCheckFoundDiff {
+    print_message: bool,
+  },
   FunctionCall {
     function: Name<'src>,
     message: String,
   },
+  GetConfirmation {
+    io_error: io::Error,
+  },
+  GlobalConfig {
+    path: PathBuf,
+    message: String,
+  },
+  IncludeGlob {
+    justfile: PathBuf,
+    pattern: String,
+    message: String,
+  },
   IncludeMissingPath {
     file: PathBuf,
     line: usize,
@@ -98,22 +162,111 @@ pub(crate) enum Error<'src> {
   Internal {
     message: String,
   },
+  InternalEvaluation {
+    message: String,
+    stack: Vec<String>,
+    state: Option<String>,
+  },
+  Interrupted {
+    signal: i32,
+  },
   InvalidDirective {
     line: String,
   },
+  InvalidImport {
+    file: PathBuf,
+    line: usize,
+    message: String,
+  },
   Io {
     recipe: &'src str,
+    token: Option<Token<'src>>,
     io_error: io::Error,
   },
+  LanguageServer {
+    io_error: io::Error,
+  },
+  LibraryDirectory,
+  LibraryFetch {
+    library: String,
+    output_error: OutputError,
+  },
+  LibraryHash {
+    library: String,
+    manifest: PathBuf,
+    path: PathBuf,
+  },
+  LibraryIo {
+    path: PathBuf,
+    io_error: io::Error,
+  },
+  LibraryName {
+    name: String,
+  },
+  LibraryNotFound {
+    name: String,
+    manifest: Option<PathBuf>,
+  },
+  LibrarySource {
+    library: String,
+  },
   Load {
     path: PathBuf,
     io_error: io::Error,
   },
+  LoginEnv {
+    shell: String,
+    output_error: OutputError,
+  },
+  MissingArgument {
+    recipe: &'src str,
+    parameter: &'src str,
+  },
+  MissingRequiredEnv {
+    variables: Vec<String>,
+  },
+  RequiredVersion {
+    requirement: String,
+  },
   NoChoosableRecipes,
   NoRecipes,
+  NotConfirmed {
+    recipe: &'src str,
+  },
+  PreviouslyFailed {
+    recipe: &'src str,
+  },
+  RecipesFailed {
+    code: i32,
+    recipes: Vec<&'src str>,
+    errors: Vec<Error<'src>>,
+  },
   RegexCompile {
     source: regex::Error,
   },
+  RemoteDirectory,
+  RemoteFetch {
+    url: String,
+    output_error: OutputError,
+  },
+  RemoteHash {
+    url: String,
+    expected: String,
+    actual: String,
+  },
+  RemoteIo {
+    path: PathBuf,
+    io_error: io::Error,
+  },
+  RemoteNotCached {
+    url: String,
+    path: PathBuf,
+  },
+  Script {
+    recipe: &'src str,
+    interpreter: String,
+    io_error: io::Error,
+  },
   Search {
     search_error: SearchError,
   },
@@ -123,33 +276,68 @@ pub(crate) enum Error<'src> {
     argument: Option<String>,
     io_error: io::Error,
   },
+  ShellFallbacksNotFound {
+    shells: Vec<String>,
+  },
   Signal {
     recipe: &'src str,
-    line_number: Option<usize>,
+    token: Option<Token<'src>>,
     signal: i32,
   },
+  Timeout {
+    recipe: &'src str,
+    token: Option<Token<'src>>,
+    timeout: Interval,
+  },
   TmpdirIo {
     recipe: &'src str,
     io_error: io::Error,
   },
+  TooManyArguments {
+    recipe: &'src str,
+    variadic: &'src str,
+    found: usize,
+    max: usize,
+  },
+  TrustDirectory,
+  TrustStore {
+    path: PathBuf,
+    io_error: io::Error,
+  },
   Unknown {
     recipe: &'src str,
-    line_number: Option<usize>,
+    token: Option<Token<'src>>,
+  },
+  UnknownKeywordArgument {
+    recipe: &'src str,
+    argument: String,
+    name: String,
   },
   UnknownOverrides {
     overrides: Vec<String>,
   },
   UnknownRecipes {
     recipes: Vec<String>,
-    suggestion: Option<Suggestion<'src>>,
+    suggestions: Vec<Suggestion<'src>>,
   },
   Unstable {
     message: String,
   },
+  Untrusted {
+    justfile: PathBuf,
+  },
   WriteJustfile {
     justfile: PathBuf,
     io_error: io::Error,
   },
+  WriteReport {
+    path: PathBuf,
+    io_error: io::Error,
+  },
+  WriteScript {
+    path: PathBuf,
+    io_error: io::Error,
+  },
 }
 
 impl<'src> Error<'src> {
@@ -159,41 +347,130 @@ impl<'src> Error<'src> {
       | Self::Backtick {
         output_error: OutputError::Code(code),
         ..
-      } => Some(*code),
+      }
+      | Self::RecipesFailed { code, .. } => Some(*code),
       Self::ChooserStatus { status, .. } | Self::EditorStatus { status, .. } => status.code(),
       _ => None,
     }
   }
 
-  fn context(&self) -> Option<Token<'src>> {
+  pub(crate) fn context(&self) -> Option<Token<'src>> {
     match self {
       Self::Backtick { token, .. } => Some(*token),
-      Self::Compile { compile_error } => Some(compile_error.context()),
+      Self::Code { token, .. }
+      | Self::Io { token, .. }
+      | Self::Signal { token, .. }
+      | Self::Timeout { token, .. }
+      | Self::Unknown { token, .. } => *token,
+      Self::Compile { compile_error, .. } => Some(compile_error.context()),
+      Self::DotenvUndefined { variable } => Some(variable.token()),
       Self::FunctionCall { function, .. } => Some(function.token()),
       _ => None,
     }
   }
 
+  /// Record that compile errors are in the justfile at `path`, so that it is
+  /// included in the summary printed after multiple errors, and in errors
+  /// printed with `--error-format json`
+  pub(crate) fn in_justfile(self, justfile: &Path) -> Self {
+    match self {
+      Self::Compile { compile_error, .. } => Self::Compile {
+        compile_error,
+        path: Some(justfile.to_owned()),
+      },
+      Self::CompileErrors { compile_errors, .. } => Self::CompileErrors {
+        compile_errors,
+        path: Some(justfile.to_owned()),
+      },
+      error => error,
+    }
+  }
+
   pub(crate) fn internal(message: impl Into<String>) -> Self {
     Self::Internal {
       message: message.into(),
     }
   }
 
+  /// The message of this error, without the `error:` prefix, color, or the
+  /// source context which is printed after it
+  pub(crate) fn message(&self) -> String {
+    let mut message = self.color_display(Color::never()).to_string();
+
+    if let Some(token) = self.context() {
+      let context = format!("\n{}", token.color_display(Color::never()));
+      if message.ends_with(&context) {
+        message.truncate(message.len() - context.len());
+      }
+    }
+
+    message
+      .strip_prefix("error: ")
+      .map(str::to_owned)
+      .unwrap_or(message)
+  }
+
   pub(crate) fn print_message(&self) -> bool {
     !matches!(
       self,
       Error::Code {
         print_message: false,
         ..
+      } | Error::FormatCheckFoundDiff {
+        print_message: false
       }
     )
   }
+
+  /// The recipe this error occurred in, if any
+  pub(crate) fn recipe(&self) -> Option<&'src str> {
+    match self {
+      Self::ArgumentChoice { recipe, .. }
+      | Self::ArgumentCountMismatch { recipe, .. }
+      | Self::ArgumentPattern { recipe, .. }
+      | Self::Code { recipe, .. }
+      | Self::Cygpath { recipe, .. }
+      | Self::DefaultRecipeRequiresArguments { recipe, .. }
+      | Self::DuplicateArgument { recipe, .. }
+      | Self::Io { recipe, .. }
+      | Self::MissingArgument { recipe, .. }
+      | Self::NotConfirmed { recipe, .. }
+      | Self::PreviouslyFailed { recipe }
+      | Self::Script { recipe, .. }
+      | Self::Shebang { recipe, .. }
+      | Self::Signal { recipe, .. }
+      | Self::Timeout { recipe, .. }
+      | Self::TmpdirIo { recipe, .. }
+      | Self::TooManyArguments { recipe, .. }
+      | Self::Unknown { recipe, .. }
+      | Self::UnknownKeywordArgument { recipe, .. } => Some(recipe),
+      _ => None,
+    }
+  }
 }
 
 impl<'src> From<CompileError<'src>> for Error<'src> {
   fn from(compile_error: CompileError<'src>) -> Self {
-    Self::Compile { compile_error }
+    Self::Compile {
+      compile_error,
+      path: None,
+    }
+  }
+}
+
+impl<'src> From<Vec<CompileError<'src>>> for Error<'src> {
+  fn from(mut compile_errors: Vec<CompileError<'src>>) -> Self {
+    if compile_errors.len() == 1 {
+      Self::Compile {
+        compile_error: compile_errors.remove(0),
+        path: None,
+      }
+    } else {
+      Self::CompileErrors {
+        compile_errors,
+        path: None,
+      }
+    }
   }
 }
 
@@ -224,6 +501,9 @@ impl<'src> ColorDisplay for Error<'src> {
     write!(f, "{error}: {message}")?;
 
     match self {
+      ArgumentChoice { recipe, parameter, value, choices } => {
+        write!(f, "Recipe `{recipe}` parameter `{parameter}` got invalid value `{value}`, expected {}", List::or_ticked(choices))?;
+      }
       ArgumentCountMismatch { recipe, found, min, max, .. } => {
         let count = Count("argument", *found);
         if min == max {
@@ -236,6 +516,12 @@ impl<'src> ColorDisplay for Error<'src> {
           write!(f, "Recipe `{recipe}` got {found} {count} but takes at most {max}")?;
         }
       }
+      ArgumentPattern { recipe, parameter, value, pattern } => {
+        write!(f, "Recipe `{recipe}` parameter `{parameter}` got invalid value `{value}`, which does not match pattern `{pattern}`")?;
+      }
+      ArgumentPrompt { io_error } => {
+        write!(f, "Failed to read argument from stdin: {io_error}")?;
+      }
       Backtick { output_error, .. } => match output_error {
         OutputError::Code(code) => write!(f, "Backtick failed with exit code {code}")?,
         OutputError::Signal(signal) => write!(f, "Backtick was terminated by signal {signal}")?,
@@ -247,6 +533,9 @@ impl<'src> ColorDisplay for Error<'src> {
           }?,
         OutputError::Utf8(utf8_error) => write!(f, "Backtick succeeded but stdout was not utf8: {utf8_error}")?,
       }
+      CaptureAfterRun { recipe } => {
+        write!(f, "The output of recipe `{recipe}` can't be captured, since it has already run without its output being captured")?;
+      }
       ChooserInvoke { shell_binary, shell_arguments, chooser, io_error} => {
         let chooser = chooser.to_string_lossy();
         write!(f, "Chooser `{shell_binary} {shell_arguments} {chooser}` invocation failed: {io_error}")?;
@@ -268,8 +557,8 @@ impl<'src> ColorDisplay for Error<'src> {
         let current = current.display();
         write!(f, "Include `{include}` in `{current}` is a circular include")?;
       }
-      Code { recipe, line_number, code, .. } => {
-        if let Some(n) = line_number {
+      Code { recipe, token, code, .. } => {
+        if let Some(n) = token.map(|token| token.line.ordinal()) {
           write!(f, "Recipe `{recipe}` failed on line {n} with exit code {code}")?;
         } else {
           write!(f, "Recipe `{recipe}` failed with exit code {code}")?;
@@ -283,7 +572,16 @@ impl<'src> ColorDisplay for Error<'src> {
         let cmd = format_cmd(binary, arguments);
         write!(f, "Command {cmd} failed: {status}")?;
       }
-      Compile { compile_error } => Display::fmt(compile_error, f)?,
+      Compile { compile_error, .. } => Display::fmt(compile_error, f)?,
+      CompileErrors { compile_errors, .. } => {
+        for (i, compile_error) in compile_errors.iter().enumerate() {
+          if i > 0 {
+            write!(f, "\n\n{error}: {message}")?;
+          }
+          writeln!(f, "{compile_error}{}", color.message().suffix())?;
+          write!(f, "{}", compile_error.context().color_display(color.error()))?;
+        }
+      }
       Config { config_error } => Display::fmt(config_error, f)?,
       Cygpath { recipe, output_error} => match output_error {
         OutputError::Code(code) => write!(f, "Cygpath failed with exit code {code} while translating recipe `{recipe}` shebang interpreter path")?,
@@ -305,6 +603,16 @@ impl<'src> ColorDisplay for Error<'src> {
       Dotenv { dotenv_error } => {
         write!(f, "Failed to load environment file: {dotenv_error}")?;
       }
+      DotenvUndefined { variable } => {
+        let key = dotenv_key(variable.lexeme()).unwrap_or_default();
+        write!(f, "`{key}` was not loaded from an environment file")?;
+      }
+      DotenvRequired { path } => {
+        write!(f, "Required environment file `{}` not found", path.display())?;
+      }
+      DuplicateArgument { recipe, parameter } => {
+        write!(f, "Recipe `{recipe}` got more than one value for parameter `{parameter}`")?;
+      }
       DumpJson { serde_json_error } => {
         write!(f, "Failed to dump JSON to stdout: {serde_json_error}")?;
       }
@@ -316,19 +624,55 @@ impl<'src> ColorDisplay for Error<'src> {
         let editor = editor.to_string_lossy();
         write!(f, "Editor `{editor}` failed: {status}")?;
       }
+      EnvHook { hook, output_error } => match output_error {
+        OutputError::Code(code) => write!(f, "Env hook `{hook}` failed with exit code {code}")?,
+        OutputError::Signal(signal) => write!(f, "Env hook `{hook}` was terminated by signal {signal}")?,
+        OutputError::Unknown => write!(f, "Env hook `{hook}` failed for an unknown reason")?,
+        OutputError::Io(io_error) => write!(f, "Env hook `{hook}` could not be run: {io_error}")?,
+        OutputError::Utf8(utf8_error) => write!(f, "Env hook `{hook}` succeeded but stdout was not utf8: {utf8_error}")?,
+      }
+      EnvHookParse { hook, message } => {
+        write!(f, "Failed to parse output of env hook `{hook}`: {message}")?;
+      }
       EvalUnknownVariable { variable, suggestion} => {
         write!(f, "Justfile does not contain variable `{variable}`.")?;
         if let Some(suggestion) = suggestion {
           write!(f, "\n{suggestion}")?;
         }
       }
-      FormatCheckFoundDiff => {
+      ExportScriptCapture { recipe, dependency } => {
+        write!(
+          f,
+          "Recipe `{recipe}` can't be exported, since it uses the output of dependency `{dependency}`"
+        )?;
+      }
+      ExportScriptShell { shell } => {
+        write!(
+          f,
+          "Recipes run with `{shell}` can't be exported, since it isn't a POSIX shell"
+        )?;
+      }
+      FormatCheckFoundDiff { .. } => {
         write!(f, "Formatted justfile differs from original.")?;
       }
       FunctionCall { function, message } => {
         let function = function.lexeme();
         write!(f, "Call to function `{function}` failed: {message}")?;
       }
+      GetConfirmation { io_error } => {
+        write!(f, "Failed to read confirmation from stdin: {io_error}")?;
+      }
+      GlobalConfig { path, message } => {
+        write!(f, "Failed to load global configuration file `{}`: {message}", path.display())?;
+      }
+      IncludeGlob {
+        justfile,
+        pattern,
+        message,
+      } => {
+        let justfile = justfile.display();
+        write!(f, "!include pattern `{pattern}` in `{justfile}` failed: {message}")?;
+      }
       IncludeMissingPath { file: justfile, line } => {
         let line = line.ordinal();
         let justfile = justfile.display();
@@ -341,23 +685,122 @@ impl<'src> ColorDisplay for Error<'src> {
         write!(f, "Internal runtime error, this may indicate a bug in just: {message} \
                    consider filing an issue: https://github.com/casey/just/issues/new")?;
       }
+      InternalEvaluation { message, stack, state } => {
+        write!(f, "Internal evaluation error, this may indicate a bug in just: {message}")?;
+        for frame in stack.iter().rev() {
+          write!(f, "\n  while evaluating {frame}")?;
+        }
+        if let Some(state) = state {
+          write!(f, "\nEvaluator state:")?;
+          for line in state.lines() {
+            write!(f, "\n  {line}")?;
+          }
+        } else {
+          write!(f, "\nRun with `--internal-debug` to include the evaluator's state in this message.")?;
+        }
+        write!(f, "\nConsider filing an issue: https://github.com/casey/just/issues/new")?;
+      }
+      Interrupted { signal } => {
+        write!(f, "Interrupted by signal {signal}")?;
+      }
       InvalidDirective { line } => {
         write!(f, "Invalid directive: {line}")?;
       }
-      Io { recipe, io_error } => {
+      InvalidImport {
+        file,
+        line,
+        message,
+      } => {
+        let line = line.ordinal();
+        let file = file.display();
+        write!(f, "Invalid import on line {line} of `{file}`: {message}")?;
+      }
+      Io { recipe, io_error, .. } => {
         match io_error.kind() {
           io::ErrorKind::NotFound => write!(f, "Recipe `{recipe}` could not be run because just could not find the shell: {io_error}"),
           io::ErrorKind::PermissionDenied => write!(f, "Recipe `{recipe}` could not be run because just could not run the shell: {io_error}"),
           _ => write!(f, "Recipe `{recipe}` could not be run because of an IO error while launching the shell: {io_error}"),
         }?;
       }
+      LanguageServer { io_error } => {
+        write!(f, "Language server I/O error: {io_error}")?;
+      }
+      LibraryDirectory => {
+        write!(f, "Could not determine library directory, set `$JUST_LIB_DIR`, `$XDG_DATA_HOME`, or `$HOME`")?;
+      }
+      LibraryFetch { library, output_error } => {
+        write!(f, "Failed to fetch library `{library}` with `git`: {output_error}")?;
+      }
+      LibraryHash { library, manifest, path } => {
+        write!(f, "Contents of library `{library}` at `{}` do not match the hash pinned in `{}`, try `just --install-lib`", path.display(), manifest.display())?;
+      }
+      LibraryIo { path, io_error } => {
+        write!(f, "I/O error installing library at `{}`: {io_error}", path.display())?;
+      }
+      LibraryName { name } => {
+        write!(f, "Invalid library name `{name}`, library names may not contain path separators or `..`")?;
+      }
+      LibraryNotFound { name, manifest: Some(manifest) } => {
+        write!(f, "Library `{name}` not found in any of the libraries listed in `{}`, try `just --install-lib`", manifest.display())?;
+      }
+      LibraryNotFound { name, manifest: None } => {
+        write!(f, "Library `{name}` not found, no `{}` manifest found, try `just --install-lib`", Library::MANIFEST_FILENAME)?;
+      }
+      LibrarySource { library } => {
+        write!(f, "Invalid library source `{library}`, expected `github:ORG/REPO@REF` or `git:URL@REF`")?;
+      }
       Load { io_error, path } => {
         let path = path.display();
         write!(f, "Failed to read justfile at `{path}`: {io_error}")?;
       }
+      LoginEnv { shell, output_error } => match output_error {
+        OutputError::Code(code) => write!(f, "Login shell `{shell}` failed with exit code {code} while loading its environment")?,
+        OutputError::Signal(signal) => write!(f, "Login shell `{shell}` was terminated by signal {signal} while loading its environment")?,
+        OutputError::Unknown => write!(f, "Login shell `{shell}` failed for an unknown reason while loading its environment")?,
+        OutputError::Io(io_error) => write!(f, "Login shell `{shell}` could not be run to load its environment: {io_error}")?,
+        OutputError::Utf8(utf8_error) => write!(f, "Login shell `{shell}` printed an environment which was not utf8: {utf8_error}")?,
+      }
+      MissingArgument { recipe, parameter } => {
+        write!(f, "Recipe `{recipe}` got no value for parameter `{parameter}`")?;
+      }
+      MissingRequiredEnv { variables } => {
+        let count = Count("variable", variables.len());
+        let verb = if variables.len() == 1 { "is" } else { "are" };
+        write!(f, "Required environment {count} {} {verb} not set", List::and_ticked(variables))?;
+      }
       NoChoosableRecipes => write!(f, "Justfile contains no choosable recipes.")?,
       NoRecipes => write!(f, "Justfile contains no recipes.")?,
+      NotConfirmed { recipe } => write!(f, "Recipe `{recipe}` was not confirmed")?,
+      PreviouslyFailed { recipe } => {
+        write!(f, "Recipe `{recipe}` was not run again, since it already failed")?;
+      }
+      RecipesFailed { recipes, .. } => {
+        let count = Count("Recipe", recipes.len());
+        write!(f, "{count} {} failed", List::and_ticked(recipes))?;
+      }
       RegexCompile { source } => write!(f, "{source}")?,
+      RemoteDirectory => {
+        write!(f, "Could not determine cache directory for remote includes, set `$JUST_CACHE_DIR`, `$XDG_CACHE_HOME`, or `$HOME`")?;
+      }
+      RemoteFetch { url, output_error } => {
+        write!(f, "Failed to fetch `{url}` with `curl`: {output_error}")?;
+      }
+      RemoteHash { url, expected, actual } => {
+        write!(f, "Contents of `{url}` have SHA-256 hash `{actual}`, but `{expected}` was expected")?;
+      }
+      RemoteIo { path, io_error } => {
+        write!(f, "I/O error caching remote include at `{}`: {io_error}", path.display())?;
+      }
+      RemoteNotCached { url, path } => {
+        write!(f, "`{url}` is not cached at `{}`, and `--offline` or `--one-shot` was given", path.display())?;
+      }
+      RequiredVersion { requirement } => {
+        let version = env!("CARGO_PKG_VERSION");
+        write!(f, "Justfile requires `just` version `{requirement}`, but this is version {version}. Please upgrade `just`.")?;
+      }
+      Script { recipe, interpreter, io_error } => {
+        write!(f, "Recipe `{recipe}` with script interpreter `{interpreter}` execution error: {io_error}")?;
+      }
       Search { search_error } => Display::fmt(search_error, f)?,
       Shebang { recipe, command, argument, io_error} => {
         if let Some(argument) = argument {
@@ -366,57 +809,119 @@ impl<'src> ColorDisplay for Error<'src> {
           write!(f, "Recipe `{recipe}` with shebang `#!{command}` execution error: {io_error}")?;
         }
       }
-      Signal { recipe, line_number, signal } => {
-        if let Some(n) = line_number {
+      ShellFallbacksNotFound { shells } => {
+        write!(f, "None of the shells in `shell-fallbacks` could be found, tried {}", List::and_ticked(shells))?;
+      }
+      Signal { recipe, token, signal, .. } => {
+        if let Some(n) = token.map(|token| token.line.ordinal()) {
           write!(f, "Recipe `{recipe}` was terminated on line {n} by signal {signal}")?;
         } else {
           write!(f, "Recipe `{recipe}` was terminated by signal {signal}")?;
         }
       }
+      Timeout { recipe, token, timeout, .. } => {
+        if let Some(n) = token.map(|token| token.line.ordinal()) {
+          write!(f, "Recipe `{recipe}` timed out on line {n} after {timeout}")?;
+        } else {
+          write!(f, "Recipe `{recipe}` timed out after {timeout}")?;
+        }
+      }
       TmpdirIo { recipe, io_error } => {
         write!(f, "Recipe `{recipe}` could not be run because of an IO error while trying to create a temporary \
                    directory or write a file to that directory`:{io_error}")?;
       }
-      Unknown { recipe, line_number} => {
-        if let Some(n) = line_number {
+      TooManyArguments { recipe, variadic, found, max } => {
+        let count = Count("argument", *found);
+        write!(f, "Recipe `{recipe}` got {found} positional {count} but takes at most {max}, since variadic parameter `{variadic}` was given by name")?;
+      }
+      TrustDirectory => {
+        write!(f, "Could not determine trust store directory, set `$XDG_DATA_HOME` or `$HOME`")?;
+      }
+      TrustStore { path, io_error } => {
+        write!(f, "I/O error checking whether `{}` is trusted: {io_error}", path.display())?;
+      }
+      Unknown { recipe, token, .. } => {
+        if let Some(n) = token.map(|token| token.line.ordinal()) {
           write!(f, "Recipe `{recipe}` failed on line {n} for an unknown reason")?;
         } else {
           write!(f, "Recipe `{recipe}` failed for an unknown reason")?;
         }
       }
+      UnknownKeywordArgument { recipe, argument, name } => {
+        write!(f, "Recipe `{recipe}` got keyword argument `{argument}`, but has no parameter `{name}`")?;
+      }
       UnknownOverrides { overrides } => {
         let count = Count("Variable", overrides.len());
         let overrides = List::and_ticked(overrides);
         write!(f, "{count} {overrides} overridden on the command line but not present in justfile")?;
       }
-      UnknownRecipes { recipes, suggestion } => {
+      UnknownRecipes { recipes, suggestions } => {
         let count = Count("recipe", recipes.len());
         let recipes = List::or_ticked(recipes);
         write!(f, "Justfile does not contain {count} {recipes}.")?;
-        if let Some(suggestion) = suggestion {
-          write!(f, "\n{suggestion}")?;
+        match suggestions.as_slice() {
+          [] => {}
+          [suggestion] => write!(f, "\n{suggestion}")?,
+          suggestions => write!(f, "\nDid you mean {}?", List::or(suggestions.iter().map(Suggestion::candidate)))?,
         }
       }
       Unstable { message } => {
         write!(f, "{message} Invoke `just` with the `--unstable` flag to enable unstable features.")?;
       }
+      Untrusted { justfile } => {
+        write!(f, "Justfile `{}` is not trusted, run `just --trust` to trust it", justfile.display())?;
+      }
       WriteJustfile { justfile, io_error } => {
         let justfile = justfile.display();
         write!(f, "Failed to write justfile to `{justfile}`: {io_error}")?;
       }
+      WriteReport { path, io_error } => {
+        let path = path.display();
+        write!(f, "Failed to write report to `{path}`: {io_error}")?;
+      }
+      WriteScript { path, io_error } => {
+        let path = path.display();
+        write!(f, "Failed to write script to `{path}`: {io_error}")?;
+      }
     }
 
     write!(f, "{}", color.message().suffix())?;
 
     if let ArgumentCountMismatch {
-      recipe, parameters, ..
+      recipe,
+      parameters,
+      doc,
+      ..
     } = self
     {
+      // The recipe's doc comment and a hint to show its definition are only
+      // included when the error is printed to a terminal
+      let help = color.terminal();
+
       writeln!(f)?;
       write!(f, "{}:\n    just {recipe}", color.message().paint("usage"))?;
       for param in parameters {
         write!(f, " {}", param.color_display(color))?;
       }
+      if let Some(doc) = doc.as_ref().filter(|_| help) {
+        write!(f, " {}", color.doc().paint(&format!("# {doc}")))?;
+      }
+      for param in parameters {
+        if let Some(doc) = &param.doc {
+          write!(
+            f,
+            "\n        {}: {doc}",
+            color.parameter().paint(param.name.lexeme())
+          )?;
+        }
+      }
+      if help {
+        write!(
+          f,
+          "\n{} Run `just --show {recipe}` to see the recipe's definition",
+          color.banner().paint("note:")
+        )?;
+      }
     }
 
     if let Some(token) = self.context() {
@@ -0,0 +1,183 @@
+use {super::*, std::fmt::Write as _};
+
+/// How errors are printed, selected with `--error-format`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ErrorFormat {
+  /// As colored messages, followed by the source line they occurred on
+  Human,
+  /// As JSON objects, one per line, for editors and other tools
+  Json,
+}
+
+impl ErrorFormat {
+  pub(crate) fn print(
+    self,
+    error: &Error,
+    loader: &Loader,
+    color: Color,
+    timestamps: Option<&Timestamps>,
+    exit_code: i32,
+  ) {
+    match self {
+      Self::Human => {
+        let message = error.color_display(color).to_string() + &Self::summary(error, loader, color);
+        match timestamps {
+          Some(timestamps) => eprintln!("{}", timestamps.prefix(&message)),
+          None => eprintln!("{message}"),
+        }
+      }
+      Self::Json => {
+        for json_error in JsonError::new(error, loader, exit_code) {
+          eprintln!("{}", serde_json::to_string(&json_error).unwrap());
+        }
+      }
+    }
+  }
+
+  /// A summary of the errors making up `error`, if there is more than one,
+  /// each on its own line, after the file, line, and column it occurred on,
+  /// so that they aren't lost in the output above
+  fn summary(error: &Error, loader: &Loader, color: Color) -> String {
+    let position = |token: Token, path: Option<&Path>| {
+      let (path, line) = Self::location(loader, token, path);
+      let position = format!("{}:{}", line.ordinal(), token.column.ordinal());
+      match path {
+        Some(path) => format!("{}:{position}", path.display()),
+        None => position,
+      }
+    };
+
+    let errors = match error {
+      Error::CompileErrors {
+        compile_errors,
+        path,
+      } => compile_errors
+        .iter()
+        .map(|compile_error| {
+          (
+            position(compile_error.context(), path.as_deref()),
+            compile_error.to_string(),
+          )
+        })
+        .collect::<Vec<(String, String)>>(),
+      Error::RecipesFailed {
+        recipes, errors, ..
+      } if errors.len() > 1 => recipes
+        .iter()
+        .zip(errors)
+        .map(|(recipe, error)| {
+          (
+            error
+              .context()
+              .map_or_else(|| format!("`{recipe}`"), |token| position(token, None)),
+            error.message(),
+          )
+        })
+        .collect(),
+      _ => return String::new(),
+    };
+
+    let width = errors
+      .iter()
+      .map(|(position, _)| position.chars().count())
+      .max()
+      .unwrap_or_default();
+
+    let mut summary = format!(
+      "\n\n{}: {}Found {} errors:{}",
+      color.error().paint("error"),
+      color.message().prefix(),
+      errors.len(),
+      color.message().suffix(),
+    );
+
+    for (position, message) in &errors {
+      write!(
+        summary,
+        "\n  {}{:padding$}  {}",
+        color.annotation().paint(position),
+        "",
+        message.lines().next().unwrap_or_default(),
+        padding = width - position.chars().count(),
+      )
+      .unwrap();
+    }
+
+    summary
+  }
+
+  /// The path, relative to the current directory if it is inside it, and
+  /// zero-based line of the file which `token` was loaded from, falling back
+  /// to `path` and the line of `token` in its source if that isn't known
+  fn location(loader: &Loader, token: Token, path: Option<&Path>) -> (Option<PathBuf>, usize) {
+    match loader.origin(token) {
+      Some((origin, line)) => {
+        let relative = env::current_dir()
+          .ok()
+          .and_then(|directory| origin.strip_prefix(directory).ok().map(Path::to_owned));
+        (Some(relative.unwrap_or(origin)), line)
+      }
+      None => (path.map(Path::to_owned), token.line),
+    }
+  }
+}
+
+/// An error printed with `--error-format json`. Each compile error in a
+/// justfile with more than one is printed as its own object. `path` and
+/// `line` are those of the file the error occurred in, which may be an
+/// included file.
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct JsonError<'a> {
+  kind: &'static str,
+  message: String,
+  recipe: Option<&'a str>,
+  path: Option<PathBuf>,
+  line: Option<usize>,
+  column: Option<usize>,
+  exit_code: i32,
+}
+
+impl<'a> JsonError<'a> {
+  fn new(error: &'a Error, loader: &Loader, exit_code: i32) -> Vec<Self> {
+    let compile_error = |compile_error: &CompileError, path: &Option<PathBuf>| {
+      let (path, line) = ErrorFormat::location(loader, compile_error.token, path.as_deref());
+      Self {
+        kind: (&*compile_error.kind).into(),
+        message: compile_error.to_string(),
+        recipe: None,
+        path,
+        line: Some(line.ordinal()),
+        column: Some(compile_error.token.column.ordinal()),
+        exit_code,
+      }
+    };
+
+    match error {
+      Error::Compile {
+        compile_error: error,
+        path,
+      } => vec![compile_error(error, path)],
+      Error::CompileErrors {
+        compile_errors,
+        path,
+      } => compile_errors
+        .iter()
+        .map(|error| compile_error(error, path))
+        .collect(),
+      _ => {
+        let context = error.context();
+        let location = context.map(|token| ErrorFormat::location(loader, token, None));
+        vec![Self {
+          kind: error.into(),
+          message: error.message(),
+          recipe: error.recipe(),
+          path: location.as_ref().and_then(|(path, _)| path.clone()),
+          line: location.map(|(_, line)| line.ordinal()),
+          column: context.map(|token| token.column.ordinal()),
+          exit_code,
+        }]
+      }
+    }
+  }
+}
@@ -1,50 +1,145 @@
 use super::*;
 
 pub(crate) struct Evaluator<'src: 'run, 'run> {
+  /// Arguments of the user-defined function being evaluated, if any
+  arguments: Option<BTreeMap<&'src str, String>>,
   assignments: Option<&'run Table<'src, Assignment<'src>>>,
   config: &'run Config,
   dotenv: &'run BTreeMap<String, String>,
+  functions: &'run Table<'src, UserFunction<'src>>,
+  /// The environment loaded with `set inherit-login-env`
+  login_env: &'run BTreeMap<String, String>,
+  /// The shell set with the `[shell]` attribute of the recipe being
+  /// evaluated, if any
+  recipe_shell: Option<&'run Shell<'src>>,
   scope: Scope<'src, 'run>,
   settings: &'run Settings<'run>,
   search: &'run Search,
+  /// The recipe, variables, and user-defined functions being evaluated,
+  /// outermost first
+  stack: Vec<Frame<'src>>,
+}
+
+/// Something which an `Evaluator` is in the middle of evaluating
+#[derive(Clone, Copy, PartialEq)]
+enum Frame<'src> {
+  Function(&'src str),
+  Recipe(&'src str),
+  Variable(&'src str),
+}
+
+impl Display for Frame<'_> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Function(name) => write!(f, "function `{name}`"),
+      Self::Recipe(name) => write!(f, "recipe `{name}`"),
+      Self::Variable(name) => write!(f, "variable `{name}`"),
+    }
+  }
 }
 
 impl<'src, 'run> Evaluator<'src, 'run> {
+  #[allow(clippy::too_many_arguments)]
   pub(crate) fn evaluate_assignments(
     assignments: &'run Table<'src, Assignment<'src>>,
     config: &'run Config,
     dotenv: &'run BTreeMap<String, String>,
+    functions: &'run Table<'src, UserFunction<'src>>,
+    login_env: &'run BTreeMap<String, String>,
     overrides: Scope<'src, 'run>,
     settings: &'run Settings<'run>,
     search: &'run Search,
   ) -> RunResult<'src, Scope<'src, 'run>> {
     let mut evaluator = Evaluator {
+      arguments: None,
       scope: overrides,
       assignments: Some(assignments),
       config,
       dotenv,
+      functions,
+      login_env,
+      recipe_shell: None,
       settings,
       search,
+      stack: Vec::new(),
     };
 
+    // `--evaluate` prints variables, so lazy assignments are only deferred
+    // when running recipes
+    let defer = !matches!(config.subcommand, Subcommand::Evaluate { .. });
+
+    for assignment in assignments.values() {
+      if !(assignment.lazy && defer) {
+        evaluator.evaluate_assignment(assignment)?;
+      }
+    }
+
+    let mut scope = evaluator.scope;
+
     for assignment in assignments.values() {
-      evaluator.evaluate_assignment(assignment)?;
+      if assignment.lazy && !scope.bound(assignment.name.lexeme()) {
+        scope.defer(assignment);
+      }
     }
 
-    Ok(evaluator.scope)
+    Ok(scope)
   }
 
   fn evaluate_assignment(&mut self, assignment: &Assignment<'src>) -> RunResult<'src, &str> {
     let name = assignment.name.lexeme();
 
     if !self.scope.bound(name) {
-      let value = self.evaluate_expression(&assignment.value)?;
-      self.scope.bind(assignment.export, assignment.name, value);
+      let frame = Frame::Variable(name);
+
+      if self.stack.contains(&frame) {
+        return Err(self.internal_error(format!("re-entrant evaluation of {frame}")));
+      }
+
+      self.stack.push(frame);
+      let value = self.evaluate_expression(&assignment.value);
+      self.stack.pop();
+
+      self.scope.bind_assignment(assignment, value?);
     }
 
     Ok(self.scope.value(name).unwrap())
   }
 
+  /// Evaluate lazy `assignment` on its first use after the justfile's other
+  /// variables have been evaluated, and memoize its value for later uses
+  fn evaluate_deferred(&self, assignment: &Assignment<'src>) -> RunResult<'src, String> {
+    let name = assignment.name.lexeme();
+    let frame = Frame::Variable(name);
+
+    if self.stack.contains(&frame) {
+      return Err(self.internal_error(format!("re-entrant evaluation of {frame}")));
+    }
+
+    let root = self.scope.root();
+
+    // Lazy assignments can only refer to variables, like other assignments
+    let mut evaluator = Evaluator {
+      arguments: None,
+      assignments: None,
+      config: self.config,
+      dotenv: self.dotenv,
+      functions: self.functions,
+      login_env: self.login_env,
+      recipe_shell: None,
+      scope: root.child(),
+      settings: self.settings,
+      search: self.search,
+      stack: self.stack.clone(),
+    };
+
+    evaluator.stack.push(frame);
+    let value = evaluator.evaluate_expression(&assignment.value)?;
+
+    root.memoize(name, value.clone());
+
+    Ok(value)
+  }
+
   pub(crate) fn evaluate_expression(
     &mut self,
     expression: &Expression<'src>,
@@ -52,31 +147,48 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     match expression {
       Expression::Variable { name, .. } => {
         let variable = name.lexeme();
-        if let Some(value) = self.scope.value(variable) {
+
+        // The body of a user-defined function can only refer to its
+        // parameters and to variables, not to the parameters of the recipe
+        // it was called from
+        let scope = if let Some(arguments) = &self.arguments {
+          if let Some(value) = arguments.get(variable) {
+            return Ok(value.clone());
+          }
+          self.scope.root()
+        } else {
+          &self.scope
+        };
+
+        if let Some(value) = scope.value(variable) {
           Ok(value.to_owned())
         } else if let Some(assignment) = self
           .assignments
           .and_then(|assignments| assignments.get(variable))
         {
           Ok(self.evaluate_assignment(assignment)?.to_owned())
+        } else if let Some(value) = self.scope.root().evaluated(variable) {
+          Ok(value)
+        } else if let Some(assignment) = self.scope.root().deferred(variable) {
+          self.evaluate_deferred(assignment)
+        } else if let Some(key) = dotenv_key(variable) {
+          self
+            .dotenv
+            .get(key)
+            .cloned()
+            .ok_or(Error::DotenvUndefined { variable: *name })
         } else {
-          Err(Error::Internal {
-            message: format!("attempted to evaluate undefined variable `{variable}`"),
-          })
+          Err(self.internal_error(format!(
+            "attempted to evaluate undefined variable `{variable}`"
+          )))
         }
       }
       Expression::Call { thunk } => {
         use Thunk::*;
 
-        let context = FunctionContext {
-          dotenv: self.dotenv,
-          invocation_directory: &self.config.invocation_directory,
-          search: self.search,
-        };
-
         match thunk {
           Nullary { name, function, .. } => {
-            function(&context).map_err(|message| Error::FunctionCall {
+            function(&self.function_context()).map_err(|message| Error::FunctionCall {
               function: *name,
               message,
             })
@@ -86,12 +198,14 @@ impl<'src, 'run> Evaluator<'src, 'run> {
             function,
             arg,
             ..
-          } => function(&context, &self.evaluate_expression(arg)?).map_err(|message| {
-            Error::FunctionCall {
+          } => {
+            let arg = self.evaluate_expression(arg)?;
+
+            function(&self.function_context(), &arg).map_err(|message| Error::FunctionCall {
               function: *name,
               message,
-            }
-          }),
+            })
+          }
           UnaryOpt {
             name,
             function,
@@ -104,9 +218,31 @@ impl<'src, 'run> Evaluator<'src, 'run> {
               None => None,
             };
 
-            function(&context, &a, b.as_deref()).map_err(|message| Error::FunctionCall {
-              function: *name,
-              message,
+            function(&self.function_context(), &a, b.as_deref()).map_err(|message| {
+              Error::FunctionCall {
+                function: *name,
+                message,
+              }
+            })
+          }
+          UnaryPlus {
+            name,
+            function,
+            args: (a, rest),
+            ..
+          } => {
+            let a = self.evaluate_expression(a)?;
+
+            let mut rest_evaluated = Vec::new();
+            for arg in rest {
+              rest_evaluated.push(self.evaluate_expression(arg)?);
+            }
+
+            function(&self.function_context(), &a, &rest_evaluated).map_err(|message| {
+              Error::FunctionCall {
+                function: *name,
+                message,
+              }
             })
           }
           Binary {
@@ -114,15 +250,15 @@ impl<'src, 'run> Evaluator<'src, 'run> {
             function,
             args: [a, b],
             ..
-          } => function(
-            &context,
-            &self.evaluate_expression(a)?,
-            &self.evaluate_expression(b)?,
-          )
-          .map_err(|message| Error::FunctionCall {
-            function: *name,
-            message,
-          }),
+          } => {
+            let a = self.evaluate_expression(a)?;
+            let b = self.evaluate_expression(b)?;
+
+            function(&self.function_context(), &a, &b).map_err(|message| Error::FunctionCall {
+              function: *name,
+              message,
+            })
+          }
           BinaryPlus {
             name,
             function,
@@ -137,9 +273,11 @@ impl<'src, 'run> Evaluator<'src, 'run> {
               rest_evaluated.push(self.evaluate_expression(arg)?);
             }
 
-            function(&context, &a, &b, &rest_evaluated).map_err(|message| Error::FunctionCall {
-              function: *name,
-              message,
+            function(&self.function_context(), &a, &b, &rest_evaluated).map_err(|message| {
+              Error::FunctionCall {
+                function: *name,
+                message,
+              }
             })
           }
           Ternary {
@@ -147,16 +285,32 @@ impl<'src, 'run> Evaluator<'src, 'run> {
             function,
             args: [a, b, c],
             ..
-          } => function(
-            &context,
-            &self.evaluate_expression(a)?,
-            &self.evaluate_expression(b)?,
-            &self.evaluate_expression(c)?,
-          )
-          .map_err(|message| Error::FunctionCall {
-            function: *name,
-            message,
-          }),
+          } => {
+            let a = self.evaluate_expression(a)?;
+            let b = self.evaluate_expression(b)?;
+            let c = self.evaluate_expression(c)?;
+
+            function(&self.function_context(), &a, &b, &c).map_err(|message| Error::FunctionCall {
+              function: *name,
+              message,
+            })
+          }
+          User { name, args } => {
+            let functions = self.functions;
+            let function = &functions[name.lexeme()];
+
+            let mut arguments = BTreeMap::new();
+            for (parameter, arg) in function.parameters.iter().zip(args) {
+              arguments.insert(parameter.lexeme(), self.evaluate_expression(arg)?);
+            }
+
+            let caller = self.arguments.replace(arguments);
+            self.stack.push(Frame::Function(name.lexeme()));
+            let value = self.evaluate_expression(&function.body);
+            self.stack.pop();
+            self.arguments = caller;
+            value
+          }
         }
       }
       Expression::StringLiteral { string_literal } => Ok(string_literal.cooked.clone()),
@@ -171,22 +325,11 @@ impl<'src, 'run> Evaluator<'src, 'run> {
         Ok(self.evaluate_expression(lhs)? + &self.evaluate_expression(rhs)?)
       }
       Expression::Conditional {
-        lhs,
-        rhs,
+        condition,
         then,
         otherwise,
-        operator,
       } => {
-        let lhs_value = self.evaluate_expression(lhs)?;
-        let rhs_value = self.evaluate_expression(rhs)?;
-        let condition = match operator {
-          ConditionalOperator::Equality => lhs_value == rhs_value,
-          ConditionalOperator::Inequality => lhs_value != rhs_value,
-          ConditionalOperator::RegexMatch => Regex::new(&rhs_value)
-            .map_err(|source| Error::RegexCompile { source })?
-            .is_match(&lhs_value),
-        };
-        if condition {
+        if self.evaluate_condition(condition)? {
           self.evaluate_expression(then)
         } else {
           self.evaluate_expression(otherwise)
@@ -201,14 +344,59 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     }
   }
 
+  /// Evaluate `condition`, only evaluating the right-hand side of `&&` and `||`
+  /// if it is needed to determine the result
+  fn evaluate_condition(&mut self, condition: &Condition<'src>) -> RunResult<'src, bool> {
+    match condition {
+      Condition::And { lhs, rhs } => {
+        Ok(self.evaluate_condition(lhs)? && self.evaluate_condition(rhs)?)
+      }
+      Condition::Comparison { lhs, rhs, operator } => {
+        let lhs_value = self.evaluate_expression(lhs)?;
+        let rhs_value = self.evaluate_expression(rhs)?;
+        Ok(match operator {
+          ConditionalOperator::Equality => lhs_value == rhs_value,
+          ConditionalOperator::Inequality => lhs_value != rhs_value,
+          ConditionalOperator::RegexMatch => Regex::new(&rhs_value)
+            .map_err(|source| Error::RegexCompile { source })?
+            .is_match(&lhs_value),
+        })
+      }
+      Condition::Group { contents } => self.evaluate_condition(contents),
+      Condition::Not { condition } => Ok(!self.evaluate_condition(condition)?),
+      Condition::Or { lhs, rhs } => {
+        Ok(self.evaluate_condition(lhs)? || self.evaluate_condition(rhs)?)
+      }
+    }
+  }
+
+  fn function_context(&self) -> FunctionContext<'src, '_> {
+    FunctionContext {
+      config: self.config,
+      dotenv: self.dotenv,
+      invocation_directory: &self.config.invocation_directory,
+      login_env: self.login_env,
+      recipe_shell: self.recipe_shell,
+      scope: &self.scope,
+      search: self.search,
+      settings: self.settings,
+    }
+  }
+
   fn run_backtick(&self, raw: &str, token: &Token<'src>) -> RunResult<'src, String> {
-    let mut cmd = self.settings.shell_command(self.config);
+    let mut cmd = self.settings.shell_command(self.config)?;
 
     cmd.arg(raw);
 
     cmd.current_dir(&self.search.working_directory);
 
-    cmd.export(self.settings, self.dotenv, &self.scope);
+    cmd.export(
+      self.config,
+      self.settings,
+      self.login_env,
+      self.dotenv,
+      &self.scope,
+    );
 
     cmd.stdin(Stdio::inherit());
 
@@ -230,12 +418,13 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     &mut self,
     line: &Line<'src>,
     continued: bool,
+    interpolation_delimiters: &InterpolationDelimiters,
   ) -> RunResult<'src, String> {
     let mut evaluated = String::new();
     for (i, fragment) in line.fragments.iter().enumerate() {
       match fragment {
         Fragment::Text { token } => {
-          let lexeme = token.lexeme().replace("{{{{", "{{");
+          let lexeme = interpolation_delimiters.unescape(token.lexeme());
 
           if i == 0 && continued {
             evaluated += lexeme.trim_start();
@@ -251,31 +440,37 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     Ok(evaluated)
   }
 
-  pub(crate) fn evaluate_parameters(
-    config: &'run Config,
+  pub(crate) fn evaluate_parameters<D>(
+    context: &'run RecipeContext<'src, 'run>,
     dotenv: &'run BTreeMap<String, String>,
-    parameters: &[Parameter<'src>],
+    recipe: &'run Recipe<'src, D>,
     arguments: &[&str],
-    scope: &'run Scope<'src, 'run>,
-    settings: &'run Settings,
-    search: &'run Search,
+    keywords: &BTreeMap<&str, &str>,
   ) -> RunResult<'src, (Scope<'src, 'run>, Vec<String>)> {
     let mut evaluator = Evaluator {
+      arguments: None,
       assignments: None,
-      scope: scope.child(),
-      search,
-      settings,
+      functions: context.functions,
+      login_env: context.login_env,
+      recipe_shell: recipe.shell(),
+      scope: context.scope.child(),
+      search: context.search,
+      settings: context.settings,
       dotenv,
-      config,
+      config: context.config,
+      stack: vec![Frame::Recipe(recipe.name())],
     };
 
-    let mut scope = scope.child();
+    let mut scope = context.scope.child();
 
     let mut positional = Vec::new();
 
     let mut rest = arguments;
-    for parameter in parameters {
-      let value = if rest.is_empty() {
+    for parameter in &recipe.parameters {
+      let value = if let Some(value) = keywords.get(parameter.name.lexeme()) {
+        positional.push((*value).to_owned());
+        (*value).to_owned()
+      } else if rest.is_empty() {
         if let Some(ref default) = parameter.default {
           let value = evaluator.evaluate_expression(default)?;
           positional.push(value.clone());
@@ -283,9 +478,10 @@ impl<'src, 'run> Evaluator<'src, 'run> {
         } else if parameter.kind == ParameterKind::Star {
           String::new()
         } else {
-          return Err(Error::Internal {
-            message: "missing parameter without default".to_owned(),
-          });
+          return Err(evaluator.internal_error(format!(
+            "missing value for parameter `{}` without default",
+            parameter.name
+          )));
         }
       } else if parameter.kind.is_variadic() {
         for value in rest {
@@ -306,28 +502,143 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     Ok((scope, positional))
   }
 
-  pub(crate) fn recipe_evaluator(
-    config: &'run Config,
+  pub(crate) fn recipe_evaluator<D>(
+    context: &RecipeContext<'src, 'run>,
     dotenv: &'run BTreeMap<String, String>,
+    recipe: &'run Recipe<'src, D>,
     scope: &'run Scope<'src, 'run>,
-    settings: &'run Settings,
-    search: &'run Search,
   ) -> Evaluator<'src, 'run> {
     Evaluator {
+      arguments: None,
       assignments: None,
+      functions: context.functions,
+      login_env: context.login_env,
+      recipe_shell: recipe.shell(),
       scope: Scope::child(scope),
-      search,
-      settings,
+      search: context.search,
+      settings: context.settings,
       dotenv,
-      config,
+      config: context.config,
+      stack: vec![Frame::Recipe(recipe.name())],
+    }
+  }
+
+  /// An error for a state which evaluation should never reach, such as a
+  /// variable being evaluated while it is already being evaluated, which
+  /// includes what was being evaluated, and with `--internal-debug`, the
+  /// evaluator's state, to make reports of such bugs actionable
+  fn internal_error(&self, message: String) -> Error<'src> {
+    Error::InternalEvaluation {
+      message,
+      stack: self.stack.iter().map(ToString::to_string).collect(),
+      state: self.config.internal_debug.then(|| self.state()),
     }
   }
+
+  fn state(&self) -> String {
+    let mut lines = Vec::new();
+
+    if let Some(arguments) = &self.arguments {
+      for (name, value) in arguments {
+        lines.push(format!("argument `{name}` = {value:?}"));
+      }
+    }
+
+    let mut scope = Some(&self.scope);
+    let mut depth = 0;
+    while let Some(current) = scope {
+      for binding in current.bindings() {
+        lines.push(format!(
+          "scope {depth}: `{}` = {:?}",
+          binding.name, binding.value
+        ));
+      }
+      scope = current.parent();
+      depth += 1;
+    }
+
+    if let Some(assignments) = self.assignments {
+      let unevaluated = assignments
+        .keys()
+        .filter(|name| !self.scope.bound(name))
+        .map(|name| format!("`{name}`"))
+        .collect::<Vec<String>>();
+
+      if !unevaluated.is_empty() {
+        lines.push(format!("unevaluated: {}", unevaluated.join(", ")));
+      }
+    }
+
+    lines.join("\n")
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  fn reentrant_evaluation_error(args: &[&str]) -> String {
+    let justfile = testing::compile("x := 'x'\na := x\nb := a\n");
+    let config = testing::config(args);
+    let search = testing::search(&config);
+    let dotenv = BTreeMap::new();
+    let settings = Settings::default();
+
+    let mut evaluator = Evaluator {
+      arguments: None,
+      assignments: Some(&justfile.assignments),
+      config: &config,
+      dotenv: &dotenv,
+      functions: &justfile.functions,
+      login_env: &dotenv,
+      recipe_shell: None,
+      scope: Scope::new(),
+      settings: &settings,
+      search: &search,
+      stack: vec![Frame::Recipe("foo"), Frame::Variable("a")],
+    };
+
+    evaluator
+      .evaluate_assignment(&justfile.assignments["x"])
+      .unwrap();
+
+    evaluator
+      .evaluate_assignment(&justfile.assignments["b"])
+      .unwrap_err()
+      .color_display(Color::never())
+      .to_string()
+  }
+
+  #[test]
+  fn reentrant_evaluation() {
+    assert_eq!(
+      reentrant_evaluation_error(&[]),
+      "error: Internal evaluation error, this may indicate a bug in just: re-entrant evaluation of \
+       variable `a`
+  while evaluating variable `b`
+  while evaluating variable `a`
+  while evaluating recipe `foo`
+Run with `--internal-debug` to include the evaluator's state in this message.
+Consider filing an issue: https://github.com/casey/just/issues/new",
+    );
+  }
+
+  #[test]
+  fn reentrant_evaluation_internal_debug() {
+    assert_eq!(
+      reentrant_evaluation_error(&["--internal-debug"]),
+      "error: Internal evaluation error, this may indicate a bug in just: re-entrant evaluation of \
+       variable `a`
+  while evaluating variable `b`
+  while evaluating variable `a`
+  while evaluating recipe `foo`
+Evaluator state:
+  scope 0: `x` = \"x\"
+  unevaluated: `a`, `b`
+Consider filing an issue: https://github.com/casey/just/issues/new",
+    );
+  }
+
   run_error! {
     name: backtick_code,
     src: "
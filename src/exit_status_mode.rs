@@ -0,0 +1,79 @@
+use super::*;
+
+/// How errors are mapped to the exit code of `just`, selected with
+/// `--exit-status`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ExitStatusMode {
+  /// Exit with one of a fixed set of codes describing the kind of error
+  Categorized,
+  /// Exit with the exit code of the recipe line or command which failed, if
+  /// any, and otherwise `EXIT_FAILURE`
+  Passthrough,
+}
+
+impl ExitStatusMode {
+  /// Errors other than the ones below, such as invalid justfiles or
+  /// arguments, which is also the code used for command line usage errors
+  pub(crate) const OTHER: i32 = EXIT_FAILURE;
+  /// A recipe, a backtick, or the command run by `--command`, failed, or
+  /// couldn't be run
+  pub(crate) const RECIPE_FAILED: i32 = 2;
+  /// A recipe given on the command line doesn't exist
+  pub(crate) const UNKNOWN_RECIPE: i32 = 3;
+  /// `just` itself encountered a bug
+  pub(crate) const INTERNAL: i32 = 4;
+
+  pub(crate) fn code(self, error: &Error) -> i32 {
+    match self {
+      Self::Passthrough => error.code().unwrap_or(EXIT_FAILURE),
+      Self::Categorized => match error {
+        Error::Backtick { .. }
+        | Error::Code { .. }
+        | Error::CommandStatus { .. }
+        | Error::Cygpath { .. }
+        | Error::Interrupted { .. }
+        | Error::Io { .. }
+        | Error::PreviouslyFailed { .. }
+        | Error::RecipesFailed { .. }
+        | Error::Shebang { .. }
+        | Error::Signal { .. }
+        | Error::Timeout { .. }
+        | Error::Unknown { .. } => Self::RECIPE_FAILED,
+        Error::UnknownRecipes { .. } => Self::UNKNOWN_RECIPE,
+        Error::Internal { .. } | Error::InternalEvaluation { .. } => Self::INTERNAL,
+        _ => Self::OTHER,
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn categorized_failures_to_run_recipes() {
+    for error in [
+      Error::Cygpath {
+        recipe: "foo",
+        output_error: OutputError::Code(1),
+      },
+      Error::Io {
+        recipe: "foo",
+        token: None,
+        io_error: io::Error::from(io::ErrorKind::NotFound),
+      },
+      Error::Shebang {
+        recipe: "foo",
+        command: "bar".into(),
+        argument: None,
+        io_error: io::Error::from(io::ErrorKind::NotFound),
+      },
+    ] {
+      assert_eq!(
+        ExitStatusMode::Categorized.code(&error),
+        ExitStatusMode::RECIPE_FAILED,
+      );
+    }
+  }
+}
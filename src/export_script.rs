@@ -0,0 +1,146 @@
+use super::*;
+
+/// Shells which accept `set` options, and so which recipes run with can be
+/// exported as a script
+const SHELLS: &[&str] = &["ash", "bash", "dash", "ksh", "mksh", "sh", "zsh"];
+
+/// Quote `s` for a POSIX shell, leaving it as-is if it contains no characters
+/// which the shell would treat specially
+pub(crate) fn quote(s: &str) -> String {
+  if !s.is_empty()
+    && s
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || "%+,-./:=@_".contains(c))
+  {
+    s.to_owned()
+  } else {
+    format!("'{}'", s.replace('\'', "'\\''"))
+  }
+}
+
+/// The shebang line and `set` command which start an exported script,
+/// running it with the shell which recipe lines are run with, and with that
+/// shell's options plus `-e`, so that the script stops at the first failing
+/// command, as running the recipe does
+pub(crate) fn preamble<'src>(settings: &Settings, config: &Config) -> RunResult<'src, String> {
+  let (command, arguments) = settings.shell(config)?;
+
+  let family = Path::new(command)
+    .file_stem()
+    .and_then(OsStr::to_str)
+    .unwrap_or_default()
+    .to_lowercase();
+
+  if !SHELLS.contains(&family.as_str()) {
+    return Err(Error::ExportScriptShell {
+      shell: command.to_owned(),
+    });
+  }
+
+  // `-c` is dropped, including from clusters of short options like `-cu`,
+  // since the script is run as a file, and other arguments are kept as-is
+  let mut options = arguments
+    .iter()
+    .filter(|argument| **argument != "-c")
+    .map(|argument| match argument.strip_prefix('-') {
+      Some(cluster) if cluster.len() > 1 && cluster.chars().all(|c| c.is_ascii_alphabetic()) => {
+        format!("-{}", cluster.replace('c', ""))
+      }
+      _ => (*argument).to_owned(),
+    })
+    .collect::<Vec<String>>();
+
+  match options.first_mut() {
+    Some(first) if first.starts_with('-') && !first.starts_with("--") => {
+      if !first.contains('e') {
+        first.insert(1, 'e');
+      }
+    }
+    _ => options.insert(0, "-e".into()),
+  }
+
+  let shebang = if Path::new(command).is_absolute() {
+    format!("#!{command}")
+  } else {
+    format!("#!/usr/bin/env {command}")
+  };
+
+  Ok(format!("{shebang}\nset {}", options.join(" ")))
+}
+
+/// The environment variables which commands run with `login_env`, `dotenv`,
+/// and `scope` have set
+pub(crate) fn environment(
+  config: &Config,
+  settings: &Settings,
+  login_env: &BTreeMap<String, String>,
+  dotenv: &BTreeMap<String, String>,
+  scope: &Scope,
+) -> BTreeMap<String, String> {
+  let mut command = Command::new("sh");
+
+  command.export(config, settings, login_env, dotenv, scope);
+
+  command
+    .get_envs()
+    .filter_map(|(name, value)| {
+      Some((
+        name.to_string_lossy().into_owned(),
+        value?.to_string_lossy().into_owned(),
+      ))
+    })
+    .collect()
+}
+
+/// A heredoc delimiter which isn't a line of `text`
+pub(crate) fn delimiter(text: &str) -> String {
+  let mut delimiter = "JUST_SCRIPT".to_owned();
+
+  while text.lines().any(|line| line == delimiter) {
+    delimiter.push('_');
+  }
+
+  delimiter
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn quoting() {
+    assert_eq!(quote("foo/bar.txt"), "foo/bar.txt");
+    assert_eq!(quote(""), "''");
+    assert_eq!(quote("a b"), "'a b'");
+    assert_eq!(quote("it's"), "'it'\\''s'");
+  }
+
+  #[test]
+  fn preamble_options() {
+    let preamble = |arguments: &[&str]| {
+      let mut args = vec!["--shell", "bash"];
+      for argument in arguments {
+        args.push("--shell-arg");
+        args.push(argument);
+      }
+      super::preamble(&Settings::default(), &testing::config(&args)).unwrap()
+    };
+
+    assert_eq!(preamble(&["-cu"]), "#!/usr/bin/env bash\nset -eu");
+    assert_eq!(preamble(&["-eu", "-c"]), "#!/usr/bin/env bash\nset -eu");
+    assert_eq!(
+      preamble(&["-o", "pipefail", "-c"]),
+      "#!/usr/bin/env bash\nset -eo pipefail"
+    );
+    assert_eq!(
+      preamble(&["--rcfile", "-c"]),
+      "#!/usr/bin/env bash\nset -e --rcfile"
+    );
+  }
+
+  #[test]
+  fn heredoc_delimiter() {
+    assert_eq!(delimiter("foo"), "JUST_SCRIPT");
+    assert_eq!(delimiter("JUST_SCRIPT\nfoo"), "JUST_SCRIPT_");
+  }
+}
@@ -0,0 +1,170 @@
+use {super::*, serde_json::json};
+
+/// A parameter of a recipe which editors should prompt for when running it
+struct Prompt<'a> {
+  choices: Vec<&'a str>,
+  default: Option<&'a str>,
+  description: String,
+  name: &'a str,
+}
+
+/// The parameters to prompt for when running `recipe`. Defaults which are not
+/// string literals can't be prefilled, so such parameters, and any following
+/// them, are left to take their default values.
+fn prompts<'a>(recipe: &'a Recipe<Dependency>) -> Vec<Prompt<'a>> {
+  let mut prompts = Vec::new();
+
+  for parameter in &recipe.parameters {
+    let default = match &parameter.default {
+      None => None,
+      Some(Expression::StringLiteral { string_literal }) => Some(string_literal.cooked.as_str()),
+      Some(_) => break,
+    };
+
+    let name = parameter.name.lexeme();
+
+    let choices = recipe
+      .attributes
+      .iter()
+      .filter_map(|attribute| match attribute {
+        Attribute::Choices { parameter, choices } if parameter.cooked == name => Some(choices),
+        _ => None,
+      })
+      .flatten()
+      .map(|choice| choice.cooked.as_str())
+      .collect();
+
+    prompts.push(Prompt {
+      choices,
+      default,
+      description: parameter.doc.clone().unwrap_or_else(|| name.to_owned()),
+      name,
+    });
+  }
+
+  prompts
+}
+
+/// VS Code `tasks.json` with a shell task for each of `recipes`, using inputs
+/// to prompt for their parameters
+pub(crate) fn vscode_tasks(recipes: &[&Recipe<Dependency>]) -> String {
+  let mut tasks = Vec::new();
+  let mut inputs = Vec::new();
+
+  for recipe in recipes {
+    let mut args = vec![json!(recipe.name())];
+
+    for prompt in prompts(recipe) {
+      let id = format!("{}.{}", recipe.name(), prompt.name);
+
+      args.push(json!(format!("${{input:{id}}}")));
+
+      let mut input = if prompt.choices.is_empty() {
+        json!({
+          "id": id,
+          "type": "promptString",
+          "description": prompt.description,
+        })
+      } else {
+        json!({
+          "id": id,
+          "type": "pickString",
+          "description": prompt.description,
+          "options": prompt.choices,
+        })
+      };
+
+      if let Some(default) = prompt.default {
+        input["default"] = json!(default);
+      }
+
+      inputs.push(input);
+    }
+
+    let mut task = json!({
+      "label": format!("just {}", recipe.name()),
+      "type": "shell",
+      "command": "just",
+      "args": args,
+      "problemMatcher": [],
+    });
+
+    if let Some(doc) = recipe.doc() {
+      task["detail"] = json!(doc);
+    }
+
+    tasks.push(task);
+  }
+
+  let mut tasks = json!({
+    "version": "2.0.0",
+    "tasks": tasks,
+  });
+
+  if !inputs.is_empty() {
+    tasks["inputs"] = json!(inputs);
+  }
+
+  format!("{tasks:#}\n")
+}
+
+/// A `JetBrains` run configuration file with a shell script configuration
+/// for each of `recipes`, using `$Prompt$` macros to prompt for their
+/// parameters. The macros are expanded before the script is passed to the
+/// shell, so they are single-quoted to pass answers containing spaces or
+/// shell metacharacters, other than `'`, as a single argument.
+pub(crate) fn jetbrains_run_configurations(recipes: &[&Recipe<Dependency>]) -> String {
+  fn escape(text: &str) -> String {
+    text
+      .replace('&', "&amp;")
+      .replace('<', "&lt;")
+      .replace('>', "&gt;")
+      .replace('"', "&quot;")
+      .replace('\n', "&#10;")
+  }
+
+  let mut lines = vec!["<component name=\"ProjectRunConfigurationManager\">".to_owned()];
+
+  for recipe in recipes {
+    let script = iter::once(format!("just {}", recipe.name()))
+      .chain(
+        prompts(recipe)
+          .iter()
+          .map(|prompt| format!("'$Prompt:{}$'", prompt.description.replace('$', ""))),
+      )
+      .collect::<Vec<String>>()
+      .join(" ");
+
+    lines.push(format!(
+      "  <configuration default=\"false\" name=\"just {}\" type=\"ShConfigurationType\">",
+      escape(recipe.name()),
+    ));
+
+    for (name, value) in [
+      ("SCRIPT_TEXT", script.as_str()),
+      ("INDEPENDENT_SCRIPT_PATH", "true"),
+      ("SCRIPT_PATH", ""),
+      ("SCRIPT_OPTIONS", ""),
+      ("INDEPENDENT_SCRIPT_WORKING_DIRECTORY", "true"),
+      ("SCRIPT_WORKING_DIRECTORY", "$PROJECT_DIR$"),
+      ("INDEPENDENT_INTERPRETER_PATH", "true"),
+      ("INTERPRETER_PATH", ""),
+      ("INTERPRETER_OPTIONS", ""),
+      ("EXECUTE_IN_TERMINAL", "true"),
+      ("EXECUTE_SCRIPT_FILE", "false"),
+    ] {
+      lines.push(format!(
+        "    <option name=\"{name}\" value=\"{}\" />",
+        escape(value)
+      ));
+    }
+
+    lines.push("    <envs />".to_owned());
+    lines.push("    <method v=\"2\" />".to_owned());
+    lines.push("  </configuration>".to_owned());
+  }
+
+  lines.push("</component>".to_owned());
+
+  lines.join("\n") + "\n"
+}
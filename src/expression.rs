@@ -20,13 +20,11 @@ pub(crate) enum Expression<'src> {
     lhs: Box<Expression<'src>>,
     rhs: Box<Expression<'src>>,
   },
-  /// `if lhs == rhs { then } else { otherwise }`
+  /// `if condition { then } else { otherwise }`
   Conditional {
-    lhs: Box<Expression<'src>>,
-    rhs: Box<Expression<'src>>,
+    condition: Condition<'src>,
     then: Box<Expression<'src>>,
     otherwise: Box<Expression<'src>>,
-    operator: ConditionalOperator,
   },
   /// `(contents)`
   Group { contents: Box<Expression<'src>> },
@@ -47,6 +45,23 @@ impl<'src> Expression<'src> {
   }
 }
 
+impl<'src> Eq for Expression<'src> {}
+
+/// Expressions are ordered by their source text, so that attributes which
+/// contain them, like `[env]`, can be kept in the sorted set of a recipe's
+/// attributes
+impl<'src> PartialOrd for Expression<'src> {
+  fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<'src> Ord for Expression<'src> {
+  fn cmp(&self, other: &Self) -> cmp::Ordering {
+    self.to_string().cmp(&other.to_string())
+  }
+}
+
 impl<'src> Display for Expression<'src> {
   fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
     match self {
@@ -58,15 +73,10 @@ impl<'src> Display for Expression<'src> {
       } => write!(f, "{lhs} / {rhs}"),
       Expression::Concatenation { lhs, rhs } => write!(f, "{lhs} + {rhs}"),
       Expression::Conditional {
-        lhs,
-        rhs,
+        condition,
         then,
         otherwise,
-        operator,
-      } => write!(
-        f,
-        "if {lhs} {operator} {rhs} {{ {then} }} else {{ {otherwise} }}"
-      ),
+      } => write!(f, "if {condition} {{ {then} }} else {{ {otherwise} }}"),
       Expression::StringLiteral { string_literal } => write!(f, "{string_literal}"),
       Expression::Variable { name } => write!(f, "{}", name.lexeme()),
       Expression::Call { thunk } => write!(f, "{thunk}"),
@@ -103,17 +113,13 @@ impl<'src> Serialize for Expression<'src> {
         seq.end()
       }
       Self::Conditional {
-        lhs,
-        rhs,
+        condition,
         then,
         otherwise,
-        operator,
       } => {
         let mut seq = serializer.serialize_seq(None)?;
         seq.serialize_element("if")?;
-        seq.serialize_element(&operator.to_string())?;
-        seq.serialize_element(lhs)?;
-        seq.serialize_element(rhs)?;
+        condition.serialize_elements(&mut seq)?;
         seq.serialize_element(then)?;
         seq.serialize_element(otherwise)?;
         seq.end()
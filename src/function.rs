@@ -11,47 +11,154 @@ pub(crate) enum Function {
   Nullary(fn(&FunctionContext) -> Result<String, String>),
   Unary(fn(&FunctionContext, &str) -> Result<String, String>),
   UnaryOpt(fn(&FunctionContext, &str, Option<&str>) -> Result<String, String>),
+  UnaryPlus(fn(&FunctionContext, &str, &[String]) -> Result<String, String>),
   Binary(fn(&FunctionContext, &str, &str) -> Result<String, String>),
   BinaryPlus(fn(&FunctionContext, &str, &str, &[String]) -> Result<String, String>),
   Ternary(fn(&FunctionContext, &str, &str, &str) -> Result<String, String>),
 }
 
+/// The names of the built-in functions
+pub(crate) const NAMES: &[&str] = &[
+  "absolute_path",
+  "add",
+  "arch",
+  "blake3_file",
+  "can_connect",
+  "capitalize",
+  "choose",
+  "clean",
+  "confirm",
+  "datetime",
+  "datetime_utc",
+  "div",
+  "dotenv",
+  "env",
+  "env_var",
+  "env_var_or_default",
+  "error",
+  "exec",
+  "extension",
+  "file_name",
+  "file_stem",
+  "first_line",
+  "glob",
+  "head",
+  "invocation_directory",
+  "invocation_directory_native",
+  "join",
+  "json",
+  "just_executable",
+  "justfile",
+  "justfile_directory",
+  "kebabcase",
+  "last_line",
+  "line",
+  "line_count",
+  "lines",
+  "lowercamelcase",
+  "lowercase",
+  "mul",
+  "num_cpus",
+  "os",
+  "os_family",
+  "parent_directory",
+  "path_exists",
+  "poll",
+  "quote",
+  "quote_all",
+  "quote_win",
+  "read",
+  "rem",
+  "replace",
+  "replace_regex",
+  "semver_matches",
+  "sha256",
+  "sha256_file",
+  "shoutykebabcase",
+  "shoutysnakecase",
+  "snakecase",
+  "sub",
+  "tail",
+  "titlecase",
+  "toml",
+  "trim",
+  "trim_end",
+  "trim_end_match",
+  "trim_end_matches",
+  "trim_start",
+  "trim_start_match",
+  "trim_start_matches",
+  "uppercamelcase",
+  "uppercase",
+  "uuid",
+  "without_extension",
+  "yaml",
+];
+
 pub(crate) fn get(name: &str) -> Option<Function> {
   let function = match name {
     "absolute_path" => Unary(absolute_path),
+    "add" => Binary(add),
     "arch" => Nullary(arch),
+    "blake3_file" => Unary(blake3_file),
+    "can_connect" => Ternary(can_connect),
     "capitalize" => Unary(capitalize),
+    "choose" => Binary(choose),
     "clean" => Unary(clean),
+    "confirm" => Unary(confirm),
+    "datetime" => Unary(datetime),
+    "datetime_utc" => Unary(datetime_utc),
+    "div" => Binary(div),
+    "dotenv" => UnaryOpt(dotenv),
     "env" => UnaryOpt(env),
     "env_var" => Unary(env_var),
     "env_var_or_default" => Binary(env_var_or_default),
     "error" => Unary(error),
+    "exec" => UnaryPlus(exec),
     "extension" => Unary(extension),
     "file_name" => Unary(file_name),
     "file_stem" => Unary(file_stem),
+    "first_line" => Unary(first_line),
+    "glob" => UnaryOpt(glob),
+    "head" => Binary(head),
     "invocation_directory" => Nullary(invocation_directory),
     "invocation_directory_native" => Nullary(invocation_directory_native),
     "join" => BinaryPlus(join),
+    "json" => Binary(json),
     "just_executable" => Nullary(just_executable),
     "justfile" => Nullary(justfile),
     "justfile_directory" => Nullary(justfile_directory),
     "kebabcase" => Unary(kebabcase),
+    "last_line" => Unary(last_line),
+    "line" => Binary(line),
+    "line_count" => Unary(line_count),
+    "lines" => UnaryOpt(lines),
     "lowercamelcase" => Unary(lowercamelcase),
     "lowercase" => Unary(lowercase),
+    "mul" => Binary(mul),
     "num_cpus" => Nullary(num_cpus),
     "os" => Nullary(os),
     "os_family" => Nullary(os_family),
     "parent_directory" => Unary(parent_directory),
     "path_exists" => Unary(path_exists),
+    "poll" => Ternary(poll),
     "quote" => Unary(quote),
+    "quote_all" => UnaryPlus(quote_all),
+    "quote_win" => Unary(quote_win),
+    "read" => Unary(read),
+    "rem" => Binary(rem),
     "replace" => Ternary(replace),
     "replace_regex" => Ternary(replace_regex),
+    "semver_matches" => Binary(semver_matches),
     "sha256" => Unary(sha256),
     "sha256_file" => Unary(sha256_file),
     "shoutykebabcase" => Unary(shoutykebabcase),
     "shoutysnakecase" => Unary(shoutysnakecase),
     "snakecase" => Unary(snakecase),
+    "sub" => Binary(sub),
+    "tail" => Binary(tail),
     "titlecase" => Unary(titlecase),
+    "toml" => Binary(toml),
     "trim" => Unary(trim),
     "trim_end" => Unary(trim_end),
     "trim_end_match" => Binary(trim_end_match),
@@ -63,6 +170,7 @@ pub(crate) fn get(name: &str) -> Option<Function> {
     "uppercase" => Unary(uppercase),
     "uuid" => Nullary(uuid),
     "without_extension" => Unary(without_extension),
+    "yaml" => Binary(yaml),
     _ => return None,
   };
   Some(function)
@@ -74,6 +182,7 @@ impl Function {
       Nullary(_) => 0..0,
       Unary(_) => 1..1,
       UnaryOpt(_) => 1..2,
+      UnaryPlus(_) => 1..usize::MAX,
       Binary(_) => 2..2,
       BinaryPlus(_) => 2..usize::MAX,
       Ternary(_) => 3..3,
@@ -92,8 +201,113 @@ fn absolute_path(context: &FunctionContext, path: &str) -> Result<String, String
   }
 }
 
+fn add(_context: &FunctionContext, a: &str, b: &str) -> Result<String, String> {
+  arithmetic(a, b, "+", i64::checked_add)
+}
+
 fn arch(_context: &FunctionContext) -> Result<String, String> {
-  Ok(target::arch().to_owned())
+  Ok(target_arch().to_owned())
+}
+
+/// The architecture returned by `arch()` and matched by `[arch]`
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn target_arch() -> &'static str {
+  target::arch()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn target_arch() -> &'static str {
+  env::consts::ARCH
+}
+
+/// Parse `a` and `b` as integers and apply `operation`, which returns `None`
+/// on overflow or division by zero
+fn arithmetic(
+  a: &str,
+  b: &str,
+  operator: &str,
+  operation: fn(i64, i64) -> Option<i64>,
+) -> Result<String, String> {
+  let integer = |s: &str| {
+    s.trim()
+      .parse::<i64>()
+      .map_err(|_| format!("`{s}` is not an integer"))
+  };
+
+  let (a, b) = (integer(a)?, integer(b)?);
+
+  if b == 0 && (operator == "/" || operator == "%") {
+    return Err(format!("Division by zero in `{a} {operator} {b}`"));
+  }
+
+  operation(a, b)
+    .map(|result| result.to_string())
+    .ok_or_else(|| format!("Integer overflow in `{a} {operator} {b}`"))
+}
+
+fn blake3_file(context: &FunctionContext, path: &str) -> Result<String, String> {
+  let justpath = context.search.working_directory.join(path);
+  let mut hasher = blake3::Hasher::new();
+  let mut file = fs::File::open(&justpath)
+    .map_err(|err| format!("Failed to open file at `{:?}`: {err}", justpath.to_str()))?;
+  hasher
+    .update_reader(&mut file)
+    .map_err(|err| format!("Failed to read file at `{:?}`: {err}", justpath.to_str()))?;
+  Ok(hasher.finalize().to_string())
+}
+
+/// Whether a TCP connection can be made to `port` on `host` within
+/// `timeout`, which bounds both resolving `host` and connecting to each of its
+/// addresses in turn. Resolution runs on its own thread, so that a
+/// nonresponsive DNS server can't block past the timeout.
+fn can_connect(
+  _context: &FunctionContext,
+  host: &str,
+  port: &str,
+  timeout: &str,
+) -> Result<String, String> {
+  use std::{
+    net::{TcpStream, ToSocketAddrs},
+    sync::mpsc,
+    thread,
+  };
+
+  let port = port
+    .parse::<u16>()
+    .map_err(|err| format!("`{port}` is not a valid port: {err}"))?;
+
+  let deadline = Instant::now() + timeout.parse::<Interval>()?.duration();
+
+  let (sender, receiver) = mpsc::channel();
+
+  let address = (host.to_owned(), port);
+
+  thread::spawn(move || {
+    let addresses = address
+      .to_socket_addrs()
+      .map(Iterator::collect::<Vec<_>>)
+      .unwrap_or_default();
+    sender.send(addresses).ok();
+  });
+
+  let Ok(addresses) = receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+  else {
+    return Ok(false.to_string());
+  };
+
+  for address in addresses {
+    let remaining = deadline.saturating_duration_since(Instant::now());
+
+    if remaining.is_zero() {
+      break;
+    }
+
+    if TcpStream::connect_timeout(&address, remaining).is_ok() {
+      return Ok(true.to_string());
+    }
+  }
+
+  Ok(false.to_string())
 }
 
 fn capitalize(_context: &FunctionContext, s: &str) -> Result<String, String> {
@@ -108,14 +322,92 @@ fn capitalize(_context: &FunctionContext, s: &str) -> Result<String, String> {
   Ok(capitalized)
 }
 
+fn choose(_context: &FunctionContext, n: &str, alphabet: &str) -> Result<String, String> {
+  use rand::seq::SliceRandom;
+
+  let n = n
+    .parse::<usize>()
+    .map_err(|err| format!("Failed to parse `{n}` as a positive integer: {err}"))?;
+
+  let mut chars = Vec::new();
+  for c in alphabet.chars() {
+    if chars.contains(&c) {
+      return Err(format!("Alphabet contains repeated character `{c}`"));
+    }
+    chars.push(c);
+  }
+
+  let mut rng = rand::thread_rng();
+
+  (0..n)
+    .map(|_| {
+      chars
+        .choose(&mut rng)
+        .ok_or_else(|| "Alphabet is empty".to_owned())
+    })
+    .collect()
+}
+
 fn clean(_context: &FunctionContext, path: &str) -> Result<String, String> {
   Ok(Path::new(path).lexiclean().to_str().unwrap().to_owned())
 }
 
+fn confirm(context: &FunctionContext, prompt: &str) -> Result<String, String> {
+  context
+    .config
+    .confirm(prompt)
+    .map(|confirmed| confirmed.to_string())
+    .map_err(|io_error| format!("Failed to read confirmation from stdin: {io_error}"))
+}
+
+fn datetime(_context: &FunctionContext, format: &str) -> Result<String, String> {
+  format_datetime(chrono::Local::now(), format)
+}
+
+fn datetime_utc(_context: &FunctionContext, format: &str) -> Result<String, String> {
+  format_datetime(chrono::Utc::now(), format)
+}
+
+/// Format `datetime` with the strftime-style `format`, which is checked first,
+/// since formatting with an invalid format panics
+fn format_datetime<Tz: chrono::TimeZone>(
+  datetime: chrono::DateTime<Tz>,
+  format: &str,
+) -> Result<String, String>
+where
+  Tz::Offset: Display,
+{
+  use chrono::format::{Item, StrftimeItems};
+
+  let items = StrftimeItems::new(format).collect::<Vec<Item>>();
+
+  if items.contains(&Item::Error) {
+    return Err(format!("Invalid datetime format `{format}`"));
+  }
+
+  Ok(datetime.format_with_items(items.into_iter()).to_string())
+}
+
+fn div(_context: &FunctionContext, a: &str, b: &str) -> Result<String, String> {
+  arithmetic(a, b, "/", i64::checked_div)
+}
+
+fn dotenv(context: &FunctionContext, key: &str, default: Option<&str>) -> Result<String, String> {
+  match (context.dotenv.get(key), default) {
+    (Some(value), _) => Ok(value.clone()),
+    (None, Some(default)) => Ok(default.to_owned()),
+    (None, None) => Err(format!("`{key}` was not loaded from an environment file")),
+  }
+}
+
 fn env_var(context: &FunctionContext, key: &str) -> Result<String, String> {
   use std::env::VarError::*;
 
-  if let Some(value) = context.dotenv.get(key) {
+  if let Some(value) = context
+    .dotenv
+    .get(key)
+    .or_else(|| context.login_env.get(key))
+  {
     return Ok(value.clone());
   }
 
@@ -135,7 +427,11 @@ fn env_var_or_default(
 ) -> Result<String, String> {
   use std::env::VarError::*;
 
-  if let Some(value) = context.dotenv.get(key) {
+  if let Some(value) = context
+    .dotenv
+    .get(key)
+    .or_else(|| context.login_env.get(key))
+  {
     return Ok(value.clone());
   }
 
@@ -159,6 +455,34 @@ fn error(_context: &FunctionContext, message: &str) -> Result<String, String> {
   Err(message.to_owned())
 }
 
+fn exec(context: &FunctionContext, program: &str, args: &[String]) -> Result<String, String> {
+  let mut cmd = Command::new(program);
+
+  cmd.args(args);
+
+  cmd.current_dir(&context.search.working_directory);
+
+  cmd.export(
+    context.config,
+    context.settings,
+    context.login_env,
+    context.dotenv,
+    context.scope,
+  );
+
+  cmd.stdin(Stdio::inherit());
+
+  cmd.stderr(if context.config.verbosity.quiet() {
+    Stdio::null()
+  } else {
+    Stdio::inherit()
+  });
+
+  InterruptHandler::guard(|| {
+    output(cmd).map_err(|output_error| format!("Failed to run `{program}`: {output_error}"))
+  })
+}
+
 fn extension(_context: &FunctionContext, path: &str) -> Result<String, String> {
   Utf8Path::new(path)
     .extension()
@@ -180,6 +504,75 @@ fn file_stem(_context: &FunctionContext, path: &str) -> Result<String, String> {
     .ok_or_else(|| format!("Could not extract file stem from `{path}`"))
 }
 
+fn first_line(_context: &FunctionContext, s: &str) -> Result<String, String> {
+  Ok(s.lines().next().unwrap_or_default().to_owned())
+}
+
+/// Expand `pattern`, relative to the justfile directory, into the paths it
+/// matches, separated by `separator`, or a space if there is none. As in
+/// shells, wildcards don't match leading dots or path separators.
+fn glob(
+  context: &FunctionContext,
+  pattern: &str,
+  separator: Option<&str>,
+) -> Result<String, String> {
+  let directory = context
+    .search
+    .justfile
+    .parent()
+    .unwrap_or(&context.search.working_directory);
+
+  let escaped = directory
+    .to_str()
+    .map(glob::Pattern::escape)
+    .ok_or_else(|| {
+      format!(
+        "Justfile directory is not valid unicode: {}",
+        directory.display()
+      )
+    })?;
+
+  let options = glob::MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: true,
+  };
+
+  let paths = glob::glob_with(
+    &Path::new(&escaped).join(pattern).to_string_lossy(),
+    options,
+  )
+  .map_err(|error| format!("Invalid glob pattern `{pattern}`: {}", error.msg))?;
+
+  let mut matches = Vec::new();
+
+  for path in paths {
+    let path = path.map_err(|error| {
+      format!(
+        "Failed to read `{}`: {}",
+        error.path().display(),
+        error.error()
+      )
+    })?;
+
+    let path = path.strip_prefix(directory).unwrap_or(&path);
+
+    matches.push(
+      path
+        .to_str()
+        .ok_or_else(|| format!("Path is not valid unicode: {}", path.display()))?
+        .to_owned(),
+    );
+  }
+
+  Ok(matches.join(separator.unwrap_or(" ")))
+}
+
+fn head(_context: &FunctionContext, n: &str, s: &str) -> Result<String, String> {
+  let n = line_number(n)?;
+  Ok(s.lines().take(n).collect::<Vec<&str>>().join("\n"))
+}
+
 fn invocation_directory(context: &FunctionContext) -> Result<String, String> {
   Platform::convert_native_path(
     &context.search.working_directory,
@@ -214,6 +607,20 @@ fn join(
   Ok(result.to_string())
 }
 
+fn json(_context: &FunctionContext, text: &str, path: &str) -> Result<String, String> {
+  let data = serde_json::from_str(text).map_err(|err| format!("Failed to parse JSON: {err}"))?;
+  data_value(&data, path)
+}
+
+/// Look up `path` in `data`, returning strings as is, and other values,
+/// including arrays and objects, as JSON
+fn data_value(data: &serde_json::Value, path: &str) -> Result<String, String> {
+  Ok(match lookup_data_path(data, path)? {
+    serde_json::Value::String(string) => string.clone(),
+    value => value.to_string(),
+  })
+}
+
 fn just_executable(_context: &FunctionContext) -> Result<String, String> {
   let exe_path =
     env::current_exe().map_err(|e| format!("Error getting current executable: {e}"))?;
@@ -263,6 +670,46 @@ fn kebabcase(_context: &FunctionContext, s: &str) -> Result<String, String> {
   Ok(s.to_kebab_case())
 }
 
+fn last_line(_context: &FunctionContext, s: &str) -> Result<String, String> {
+  Ok(s.lines().next_back().unwrap_or_default().to_owned())
+}
+
+fn line(_context: &FunctionContext, n: &str, s: &str) -> Result<String, String> {
+  let n = line_number(n)?;
+
+  if n == 0 {
+    return Err("Line numbers start at 1".into());
+  }
+
+  s.lines().nth(n - 1).map(str::to_owned).ok_or_else(|| {
+    let count = s.lines().count();
+    format!(
+      "Line {n} out of range, text has {count} {}",
+      Count("line", count)
+    )
+  })
+}
+
+fn line_count(_context: &FunctionContext, s: &str) -> Result<String, String> {
+  Ok(s.lines().count().to_string())
+}
+
+fn line_number(n: &str) -> Result<usize, String> {
+  n.trim()
+    .parse()
+    .map_err(|_| format!("`{n}` is not a valid line number"))
+}
+
+/// The lines of `s`, without line endings, separated by `separator`, or a
+/// newline if there is none
+fn lines(_context: &FunctionContext, s: &str, separator: Option<&str>) -> Result<String, String> {
+  Ok(
+    s.lines()
+      .collect::<Vec<&str>>()
+      .join(separator.unwrap_or("\n")),
+  )
+}
+
 fn lowercamelcase(_context: &FunctionContext, s: &str) -> Result<String, String> {
   Ok(s.to_lower_camel_case())
 }
@@ -271,17 +718,44 @@ fn lowercase(_context: &FunctionContext, s: &str) -> Result<String, String> {
   Ok(s.to_lowercase())
 }
 
+fn mul(_context: &FunctionContext, a: &str, b: &str) -> Result<String, String> {
+  arithmetic(a, b, "*", i64::checked_mul)
+}
+
 fn num_cpus(_context: &FunctionContext) -> Result<String, String> {
   let num = num_cpus::get();
   Ok(num.to_string())
 }
 
 fn os(_context: &FunctionContext) -> Result<String, String> {
-  Ok(target::os().to_owned())
+  Ok(target_os().to_owned())
 }
 
 fn os_family(_context: &FunctionContext) -> Result<String, String> {
-  Ok(target::family().to_owned())
+  Ok(target_os_family().to_owned())
+}
+
+/// The operating system returned by `os()` and matched by `[os]`
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn target_os() -> &'static str {
+  target::os()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn target_os() -> &'static str {
+  env::consts::OS
+}
+
+/// The operating system family returned by `os_family()` and matched by
+/// `[os-family]`
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn target_os_family() -> &'static str {
+  target::family()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn target_os_family() -> &'static str {
+  env::consts::FAMILY
 }
 
 fn parent_directory(_context: &FunctionContext, path: &str) -> Result<String, String> {
@@ -302,10 +776,164 @@ fn path_exists(context: &FunctionContext, path: &str) -> Result<String, String>
   )
 }
 
+/// Run `command` with the recipe shell every `interval` until it succeeds,
+/// returning its output, or fail if it hasn't succeeded within `timeout`.
+/// The timeout is checked between attempts, so an attempt which hangs is not
+/// interrupted.
+fn poll(
+  context: &FunctionContext,
+  command: &str,
+  interval: &str,
+  timeout: &str,
+) -> Result<String, String> {
+  use std::thread;
+
+  let interval = interval.parse::<Interval>()?.duration();
+
+  let deadline = Instant::now() + timeout.parse::<Interval>()?.duration();
+
+  loop {
+    let mut cmd = context
+      .settings
+      .shell_command(context.config)
+      .map_err(|err| err.color_display(Color::never()).to_string())?;
+
+    cmd.arg(command);
+
+    cmd.current_dir(&context.search.working_directory);
+
+    cmd.export(
+      context.config,
+      context.settings,
+      context.login_env,
+      context.dotenv,
+      context.scope,
+    );
+
+    cmd.stdin(Stdio::inherit());
+
+    cmd.stderr(if context.config.verbosity.quiet() {
+      Stdio::null()
+    } else {
+      Stdio::inherit()
+    });
+
+    let output_error = match InterruptHandler::guard(|| output(cmd)) {
+      Ok(stdout) => return Ok(stdout),
+      Err(output_error) => output_error,
+    };
+
+    let remaining = deadline.saturating_duration_since(Instant::now());
+
+    if remaining.is_zero() {
+      return Err(format!(
+        "`{command}` did not succeed within {timeout}: {output_error}"
+      ));
+    }
+
+    thread::sleep(interval.min(remaining));
+  }
+}
+
 fn quote(_context: &FunctionContext, s: &str) -> Result<String, String> {
   Ok(format!("'{}'", s.replace('\'', "'\\''")))
 }
 
+/// Quote each argument for the shell which the current recipe is run with,
+/// and join them with spaces
+fn quote_all(context: &FunctionContext, s: &str, rest: &[String]) -> Result<String, String> {
+  let (command, _) = context
+    .settings
+    .recipe_shell(context.config, context.recipe_shell)
+    .map_err(|err| err.color_display(Color::never()).to_string())?;
+
+  let family = Path::new(command)
+    .file_stem()
+    .and_then(OsStr::to_str)
+    .unwrap_or_default()
+    .to_lowercase();
+
+  let quote = match family.as_str() {
+    "cmd" => quote_cmd,
+    "powershell" | "pwsh" => quote_powershell,
+    _ => quote,
+  };
+
+  Ok(
+    iter::once(s)
+      .chain(rest.iter().map(String::as_str))
+      .map(|s| quote(context, s))
+      .collect::<Result<Vec<String>, String>>()?
+      .join(" "),
+  )
+}
+
+/// Quote `s` for `cmd.exe`, which expands `%VARIABLE%` and, with delayed
+/// expansion, `!VARIABLE!` even inside double quotes, and has no escape for
+/// double quotes inside them, so arguments containing those characters are
+/// refused rather than quoted incorrectly
+fn quote_cmd(context: &FunctionContext, s: &str) -> Result<String, String> {
+  if let Some(c) = s
+    .chars()
+    .find(|c| matches!(c, '%' | '!' | '"' | '\n' | '\r'))
+  {
+    return Err(format!(
+      "Cannot quote `{}` for `cmd`, since it contains {}",
+      s.escape_default(),
+      match c {
+        '\n' => "a newline".to_owned(),
+        '\r' => "a carriage return".to_owned(),
+        c => format!("`{c}`"),
+      },
+    ));
+  }
+
+  quote_win(context, s)
+}
+
+/// Quote `s` as a PowerShell verbatim string, in which single quotes are
+/// escaped by doubling them
+fn quote_powershell(_context: &FunctionContext, s: &str) -> Result<String, String> {
+  Ok(format!("'{}'", s.replace('\'', "''")))
+}
+
+/// Quote `s` so that it is parsed as a single argument by programs which use
+/// the Microsoft C runtime's rules for splitting their command line
+fn quote_win(_context: &FunctionContext, s: &str) -> Result<String, String> {
+  let mut quoted = vec!['"'];
+  let mut backslashes = 0;
+
+  for c in s.chars() {
+    match c {
+      '\\' => backslashes += 1,
+      '"' => {
+        quoted.extend(iter::repeat('\\').take(backslashes * 2 + 1));
+        quoted.push('"');
+        backslashes = 0;
+      }
+      _ => {
+        quoted.extend(iter::repeat('\\').take(backslashes));
+        quoted.push(c);
+        backslashes = 0;
+      }
+    }
+  }
+
+  quoted.extend(iter::repeat('\\').take(backslashes * 2));
+  quoted.push('"');
+
+  Ok(quoted.into_iter().collect())
+}
+
+fn read(context: &FunctionContext, path: &str) -> Result<String, String> {
+  fs::read_to_string(context.search.working_directory.join(path))
+    .map_err(|err| format!("I/O error reading `{path}`: {err}"))
+}
+
+fn rem(_context: &FunctionContext, a: &str, b: &str) -> Result<String, String> {
+  arithmetic(a, b, "%", i64::checked_rem)
+}
+
 fn replace(_context: &FunctionContext, s: &str, from: &str, to: &str) -> Result<String, String> {
   Ok(s.replace(from, to))
 }
@@ -324,6 +952,20 @@ fn replace_regex(
   )
 }
 
+fn semver_matches(
+  _context: &FunctionContext,
+  version: &str,
+  requirement: &str,
+) -> Result<String, String> {
+  let version =
+    semver::Version::parse(version).map_err(|err| format!("Invalid version `{version}`: {err}"))?;
+
+  let requirement = semver::VersionReq::parse(requirement)
+    .map_err(|err| format!("Invalid version requirement `{requirement}`: {err}"))?;
+
+  Ok(requirement.matches(&version).to_string())
+}
+
 fn sha256(_context: &FunctionContext, s: &str) -> Result<String, String> {
   use sha2::{Digest, Sha256};
   let mut hasher = Sha256::new();
@@ -356,10 +998,43 @@ fn snakecase(_context: &FunctionContext, s: &str) -> Result<String, String> {
   Ok(s.to_snake_case())
 }
 
+fn sub(_context: &FunctionContext, a: &str, b: &str) -> Result<String, String> {
+  arithmetic(a, b, "-", i64::checked_sub)
+}
+
+fn tail(_context: &FunctionContext, n: &str, s: &str) -> Result<String, String> {
+  let n = line_number(n)?;
+  let lines = s.lines().collect::<Vec<&str>>();
+  Ok(lines[lines.len().saturating_sub(n)..].join("\n"))
+}
+
 fn titlecase(_context: &FunctionContext, s: &str) -> Result<String, String> {
   Ok(s.to_title_case())
 }
 
+fn toml(_context: &FunctionContext, text: &str, path: &str) -> Result<String, String> {
+  fn to_json(value: toml::Value) -> serde_json::Value {
+    match value {
+      toml::Value::Array(array) => array.into_iter().map(to_json).collect(),
+      toml::Value::Boolean(boolean) => boolean.into(),
+      toml::Value::Datetime(datetime) => datetime.to_string().into(),
+      toml::Value::Float(float) => float.into(),
+      toml::Value::Integer(integer) => integer.into(),
+      toml::Value::String(string) => string.into(),
+      toml::Value::Table(table) => table
+        .into_iter()
+        .map(|(key, value)| (key, to_json(value)))
+        .collect(),
+    }
+  }
+
+  let data = text
+    .parse::<toml::Table>()
+    .map_err(|err| format!("Failed to parse TOML: {}", err.message()))?;
+
+  data_value(&to_json(toml::Value::Table(data)), path)
+}
+
 fn trim(_context: &FunctionContext, s: &str) -> Result<String, String> {
   Ok(s.trim().to_owned())
 }
@@ -411,3 +1086,20 @@ fn without_extension(_context: &FunctionContext, path: &str) -> Result<String, S
 
   Ok(parent.join(file_stem).to_string())
 }
+
+fn yaml(_context: &FunctionContext, text: &str, path: &str) -> Result<String, String> {
+  let data = serde_yaml::from_str(text).map_err(|err| format!("Failed to parse YAML: {err}"))?;
+  data_value(&data, path)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn names_are_functions() {
+    for name in NAMES {
+      assert!(get(name).is_some(), "{name}");
+    }
+  }
+}
@@ -1,7 +1,12 @@
 use super::*;
 
-pub(crate) struct FunctionContext<'run> {
+pub(crate) struct FunctionContext<'src: 'run, 'run> {
+  pub(crate) config: &'run Config,
   pub(crate) dotenv: &'run BTreeMap<String, String>,
   pub(crate) invocation_directory: &'run Path,
+  pub(crate) login_env: &'run BTreeMap<String, String>,
+  pub(crate) recipe_shell: Option<&'run Shell<'src>>,
+  pub(crate) scope: &'run Scope<'src, 'run>,
   pub(crate) search: &'run Search,
+  pub(crate) settings: &'run Settings<'run>,
 }
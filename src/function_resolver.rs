@@ -0,0 +1,247 @@
+use {super::*, CompileErrorKind::*};
+
+pub(crate) struct FunctionResolver<'src: 'run, 'run> {
+  assignments: &'run Table<'src, Assignment<'src>>,
+  functions: &'run Table<'src, UserFunction<'src>>,
+  resolved: BTreeSet<&'src str>,
+  stack: Vec<&'src str>,
+}
+
+impl<'src: 'run, 'run> FunctionResolver<'src, 'run> {
+  /// Check that the bodies of user-defined functions only refer to their
+  /// parameters and to variables, and that every call to a user-defined
+  /// function, in a function, assignment, or recipe, is to a function which
+  /// exists, with the right number of arguments, and doesn't lead back to
+  /// itself.
+  pub(crate) fn resolve_functions(
+    functions: &'run Table<'src, UserFunction<'src>>,
+    assignments: &'run Table<'src, Assignment<'src>>,
+    recipes: &[&UnresolvedRecipe<'src>],
+  ) -> CompileResult<'src, ()> {
+    let mut resolver = FunctionResolver {
+      resolved: BTreeSet::new(),
+      stack: Vec::new(),
+      assignments,
+      functions,
+    };
+
+    for function in functions.values() {
+      resolver.resolve_function(function)?;
+    }
+
+    for assignment in assignments.values() {
+      resolver.resolve_expression(&assignment.value, None)?;
+    }
+
+    for recipe in recipes {
+      for parameter in &recipe.parameters {
+        if let Some(default) = &parameter.default {
+          resolver.resolve_expression(default, None)?;
+        }
+      }
+
+      for dependency in &recipe.dependencies {
+        for argument in &dependency.arguments {
+          resolver.resolve_expression(argument, None)?;
+        }
+      }
+
+      for attribute in &recipe.attributes {
+        if let Attribute::Env { value, .. } = attribute {
+          resolver.resolve_expression(value, None)?;
+        }
+      }
+
+      for line in &recipe.body {
+        for fragment in &line.fragments {
+          if let Fragment::Interpolation { expression, .. } = fragment {
+            resolver.resolve_expression(expression, None)?;
+          }
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  fn resolve_function(&mut self, function: &UserFunction<'src>) -> CompileResult<'src, ()> {
+    let name = function.name.lexeme();
+
+    if self.resolved.contains(name) {
+      return Ok(());
+    }
+
+    self.stack.push(name);
+    self.resolve_expression(&function.body, Some(&function.parameters))?;
+    self.stack.pop();
+
+    self.resolved.insert(name);
+
+    Ok(())
+  }
+
+  /// Check the calls in `expression`, and if `parameters` is not `None`, that
+  /// every variable it refers to is one of `parameters` or an assignment
+  fn resolve_expression(
+    &mut self,
+    expression: &Expression<'src>,
+    parameters: Option<&[Name<'src>]>,
+  ) -> CompileResult<'src, ()> {
+    match expression {
+      Expression::Variable { name } => {
+        if let Some(parameters) = parameters {
+          let variable = name.lexeme();
+          if !parameters
+            .iter()
+            .any(|parameter| parameter.lexeme() == variable)
+            && !self.assignments.contains_key(variable)
+            && dotenv_key(variable).is_none()
+          {
+            return Err(name.token().error(UndefinedVariable { variable }));
+          }
+        }
+        Ok(())
+      }
+      Expression::Call { thunk } => {
+        for argument in thunk.arguments() {
+          self.resolve_expression(argument, parameters)?;
+        }
+        if let Thunk::User { name, args } = thunk {
+          self.resolve_call(*name, args.len())?;
+        }
+        Ok(())
+      }
+      Expression::Concatenation { lhs, rhs } => {
+        self.resolve_expression(lhs, parameters)?;
+        self.resolve_expression(rhs, parameters)
+      }
+      Expression::Conditional {
+        condition,
+        then,
+        otherwise,
+      } => {
+        for expression in condition.expressions() {
+          self.resolve_expression(expression, parameters)?;
+        }
+        self.resolve_expression(then, parameters)?;
+        self.resolve_expression(otherwise, parameters)
+      }
+      Expression::Group { contents } => self.resolve_expression(contents, parameters),
+      Expression::Join { lhs, rhs } => {
+        if let Some(lhs) = lhs {
+          self.resolve_expression(lhs, parameters)?;
+        }
+        self.resolve_expression(rhs, parameters)
+      }
+      Expression::StringLiteral { .. } | Expression::Backtick { .. } => Ok(()),
+    }
+  }
+
+  fn resolve_call(&mut self, name: Name<'src>, found: usize) -> CompileResult<'src, ()> {
+    let function = self.functions.get(name.lexeme()).ok_or_else(|| {
+      name.error(UnknownFunction {
+        function: name.lexeme(),
+      })
+    })?;
+
+    let expected = function.parameters.len();
+
+    if found != expected {
+      return Err(name.error(FunctionArgumentCountMismatch {
+        function: name.lexeme(),
+        found,
+        expected: expected..expected,
+      }));
+    }
+
+    if let Some(index) = self
+      .stack
+      .iter()
+      .position(|&caller| caller == name.lexeme())
+    {
+      let mut circle = self.stack[index..].to_vec();
+      circle.push(name.lexeme());
+      return Err(name.error(CircularFunctionDependency {
+        function: name.lexeme(),
+        circle,
+      }));
+    }
+
+    self.resolve_function(function)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  analysis_error! {
+    name:   unknown_function,
+    input:  "a := foo()",
+    offset: 5,
+    line:   0,
+    column: 5,
+    width:  3,
+    kind:   UnknownFunction{function: "foo"},
+  }
+
+  analysis_error! {
+    name:   unknown_function_in_interpolation,
+    input:  "a:\n echo {{bar()}}",
+    offset: 11,
+    line:   1,
+    column: 8,
+    width:  3,
+    kind:   UnknownFunction{function: "bar"},
+  }
+
+  analysis_error! {
+    name:   unknown_function_in_default,
+    input:  "a f=baz():",
+    offset: 4,
+    line:   0,
+    column: 4,
+    width:  3,
+    kind:   UnknownFunction{function: "baz"},
+  }
+
+  analysis_error! {
+    name:   function_argument_count_mismatch,
+    input:  "fn f(a) := a\nx := f()",
+    offset: 18,
+    line:   1,
+    column: 5,
+    width:  1,
+    kind:   FunctionArgumentCountMismatch{function: "f", found: 0, expected: 1..1},
+  }
+
+  analysis_error! {
+    name:   undefined_variable_in_function,
+    input:  "fn f(a) := b",
+    offset: 11,
+    line:   0,
+    column: 11,
+    width:  1,
+    kind:   UndefinedVariable{variable: "b"},
+  }
+
+  analysis_error! {
+    name:   self_function_dependency,
+    input:  "fn f() := f()",
+    offset: 10,
+    line:   0,
+    column: 10,
+    width:  1,
+    kind:   CircularFunctionDependency{function: "f", circle: vec!["f", "f"]},
+  }
+
+  analysis_error! {
+    name:   circular_function_dependency,
+    input:  "fn f() := g()\nfn g() := f()",
+    offset: 24,
+    line:   1,
+    column: 10,
+    width:  1,
+    kind:   CircularFunctionDependency{function: "f", circle: vec!["f", "g", "f"]},
+  }
+}
@@ -0,0 +1,25 @@
+use super::*;
+
+/// A group declaration, `[group('NAME'): ATTRIBUTE…]`, whose attributes are
+/// inherited by every recipe in the group
+#[derive(Debug, Clone)]
+pub(crate) struct GroupDeclaration<'src> {
+  pub(crate) attributes: BTreeSet<Attribute<'src>>,
+  pub(crate) keyword: Name<'src>,
+  pub(crate) name: StringLiteral<'src>,
+}
+
+impl<'src> Display for GroupDeclaration<'src> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "[group({}): ", self.name)?;
+
+    for (i, attribute) in self.attributes.iter().enumerate() {
+      if i > 0 {
+        write!(f, ", ")?;
+      }
+      write!(f, "{attribute}")?;
+    }
+
+    write!(f, "]")
+  }
+}
@@ -0,0 +1,69 @@
+use super::*;
+
+/// `src`, a fragment of justfile source such as an expression, with its
+/// strings, backticks, comments, interpolation delimiters, and keywords
+/// painted. Source which can't be lexed is returned unpainted.
+pub(crate) fn highlight(src: &str, color: Color) -> String {
+  if !color.active() {
+    return src.to_owned();
+  }
+
+  let Ok(tokens) = Lexer::lex(src, &InterpolationDelimiters::scan(src)) else {
+    return src.to_owned();
+  };
+
+  let mut highlighted = String::new();
+
+  for token in tokens {
+    let lexeme = token.lexeme();
+
+    let color = match token.kind {
+      TokenKind::Backtick | TokenKind::StringToken => color.string(),
+      TokenKind::Comment => color.doc(),
+      TokenKind::InterpolationEnd | TokenKind::InterpolationStart => color.annotation(),
+      TokenKind::Identifier
+        if matches!(
+          Keyword::from_lexeme(lexeme),
+          Some(Keyword::If | Keyword::Else)
+        ) =>
+      {
+        color.keyword()
+      }
+      _ => {
+        highlighted += lexeme;
+        continue;
+      }
+    };
+
+    highlighted += &color.paint(lexeme).to_string();
+  }
+
+  highlighted
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn inactive() {
+    assert_eq!(
+      highlight("if a == 'b' { c } else { d }", Color::never()),
+      "if a == 'b' { c } else { d }"
+    );
+  }
+
+  #[test]
+  fn expression() {
+    assert_eq!(
+      highlight("if a == 'b' { `c` } else { d }", Color::always()),
+      "\u{1b}[33mif\u{1b}[0m a == \u{1b}[32m'b'\u{1b}[0m { \u{1b}[32m`c`\u{1b}[0m } \
+       \u{1b}[33melse\u{1b}[0m { d }",
+    );
+  }
+
+  #[test]
+  fn invalid() {
+    assert_eq!(highlight("'a", Color::always()), "'a");
+  }
+}
@@ -0,0 +1,270 @@
+use {super::*, std::borrow::Cow};
+
+/// An `!include` directive or `import` statement, which may only appear
+/// before the first item of a justfile
+#[derive(Debug, PartialEq)]
+pub(crate) struct Include<'line> {
+  /// The path, URL, glob pattern, or `lib('NAME')` to include
+  pub(crate) argument: Cow<'line, str>,
+  /// Whether written as `import`, rather than with the deprecated `!include`
+  /// directive
+  pub(crate) import: bool,
+  pub(crate) namespace: Option<&'line str>,
+  pub(crate) optional: bool,
+}
+
+impl<'line> Include<'line> {
+  /// Parse zero-based line `i` of `file`, returning `None` if it isn't an
+  /// `!include` directive or `import` statement
+  pub(crate) fn parse(line: &'line str, file: &Path, i: usize) -> Option<RunResult<'static, Self>> {
+    if line.starts_with('!') {
+      return Some(Self::parse_directive(line, file, i));
+    }
+
+    let rest = line.strip_prefix("import")?;
+
+    let (optional, rest) = match rest.strip_prefix('?') {
+      Some(rest) => (true, rest),
+      None => (false, rest),
+    };
+
+    let argument = rest.trim_start();
+
+    // `import` is not a keyword, so a line is only an `import` statement if
+    // it can't be anything else, for example, an assignment to `import`
+    if !(argument.starts_with(['\'', '"']) || argument.starts_with("lib(")) {
+      return None;
+    }
+
+    if !optional && argument.len() == rest.len() {
+      return None;
+    }
+
+    Some(Self::parse_import(argument, optional, file, i))
+  }
+
+  fn parse_directive(line: &'line str, file: &Path, i: usize) -> RunResult<'static, Self> {
+    let include = line
+      .strip_prefix("!include")
+      .ok_or_else(|| Error::InvalidDirective { line: line.into() })?;
+
+    let (optional, include) = match include.strip_prefix('?') {
+      Some(include) => (true, include),
+      None => (false, include),
+    };
+
+    let argument = include.trim();
+
+    if argument.is_empty() {
+      return Err(Error::IncludeMissingPath {
+        file: file.to_owned(),
+        line: i,
+      });
+    }
+
+    let (argument, namespace) = Self::split_namespace(argument);
+
+    Ok(Self {
+      argument: argument.into(),
+      import: false,
+      namespace,
+      optional,
+    })
+  }
+
+  fn parse_import(
+    argument: &'line str,
+    optional: bool,
+    file: &Path,
+    i: usize,
+  ) -> RunResult<'static, Self> {
+    let error = |message: &str| Error::InvalidImport {
+      file: file.to_owned(),
+      line: i,
+      message: message.into(),
+    };
+
+    let (argument, rest) = if argument.starts_with("lib(") {
+      let end = argument
+        .find(')')
+        .ok_or_else(|| error("unterminated `lib(`"))?;
+      (Cow::Borrowed(&argument[..=end]), &argument[end + 1..])
+    } else {
+      Self::parse_string(argument).map_err(error)?
+    };
+
+    let rest = rest.trim();
+
+    let namespace = if rest.is_empty() {
+      None
+    } else {
+      match rest.strip_prefix("as ").map(str::trim) {
+        Some(name) if Lexer::is_identifier(name) => Some(name),
+        _ => return Err(error("expected `as NAME` after path")),
+      }
+    };
+
+    Ok(Self {
+      argument,
+      import: true,
+      namespace,
+      optional,
+    })
+  }
+
+  /// Parse the quoted string at the start of `text`, returning its contents
+  /// and the text following it. Single-quoted strings are raw, and
+  /// double-quoted strings support the same escape sequences as justfile
+  /// strings.
+  fn parse_string(text: &'line str) -> Result<(Cow<'line, str>, &'line str), &'static str> {
+    if let Some(text) = text.strip_prefix('\'') {
+      let end = text.find('\'').ok_or("unterminated string")?;
+      return Ok((Cow::Borrowed(&text[..end]), &text[end + 1..]));
+    }
+
+    let text = &text[1..];
+    let mut cooked = String::new();
+    let mut chars = text.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+      match c {
+        '"' => return Ok((Cow::Owned(cooked), &text[i + 1..])),
+        '\\' => match chars.next().map(|(_, c)| c) {
+          Some('n') => cooked.push('\n'),
+          Some('r') => cooked.push('\r'),
+          Some('t') => cooked.push('\t'),
+          Some('\\') => cooked.push('\\'),
+          Some('"') => cooked.push('"'),
+          _ => return Err("invalid escape sequence"),
+        },
+        '\n' | '\r' => break,
+        c => cooked.push(c),
+      }
+    }
+
+    Err("unterminated string")
+  }
+
+  /// Split the namespace from an `!include PATH as NAMESPACE` argument
+  fn split_namespace(argument: &str) -> (&str, Option<&str>) {
+    match argument.rsplit_once(" as ") {
+      Some((path, name)) if Lexer::is_identifier(name.trim()) => {
+        (path.trim_end(), Some(name.trim()))
+      }
+      _ => (argument, None),
+    }
+  }
+}
+
+impl Display for Include<'_> {
+  /// The equivalent `import` statement
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "import{} ", if self.optional { "?" } else { "" })?;
+
+    if self.argument.starts_with("lib(") && self.argument.ends_with(')') {
+      write!(f, "{}", self.argument)?;
+    } else if self.argument.contains(['\'', '\n', '\r']) {
+      write!(f, "\"")?;
+      for c in self.argument.chars() {
+        match c {
+          '\n' => write!(f, "\\n")?,
+          '\r' => write!(f, "\\r")?,
+          '\t' => write!(f, "\\t")?,
+          '\\' => write!(f, "\\\\")?,
+          '"' => write!(f, "\\\"")?,
+          c => write!(f, "{c}")?,
+        }
+      }
+      write!(f, "\"")?;
+    } else {
+      write!(f, "'{}'", self.argument)?;
+    }
+
+    if let Some(namespace) = self.namespace {
+      write!(f, " as {namespace}")?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(line: &str) -> Option<Include> {
+    Include::parse(line, Path::new("justfile"), 0).map(Result::unwrap)
+  }
+
+  #[test]
+  fn directive() {
+    assert_eq!(
+      parse("!include? foo.just as foo\n"),
+      Some(Include {
+        argument: "foo.just".into(),
+        import: false,
+        namespace: Some("foo"),
+        optional: true,
+      }),
+    );
+  }
+
+  #[test]
+  fn import() {
+    assert_eq!(
+      parse("import 'foo bar.just'\n"),
+      Some(Include {
+        argument: "foo bar.just".into(),
+        import: true,
+        namespace: None,
+        optional: false,
+      }),
+    );
+
+    assert_eq!(
+      parse("import? \"a\\\\b.just\" as ci\n"),
+      Some(Include {
+        argument: "a\\b.just".into(),
+        import: true,
+        namespace: Some("ci"),
+        optional: true,
+      }),
+    );
+
+    assert_eq!(
+      parse("import lib('rust')\n").unwrap().argument,
+      "lib('rust')"
+    );
+  }
+
+  #[test]
+  fn not_import() {
+    assert_eq!(parse("import := 'foo'\n"), None);
+    assert_eq!(parse("import foo:\n"), None);
+    assert_eq!(parse("imports 'foo'\n"), None);
+    assert_eq!(parse("import'foo'\n"), None);
+  }
+
+  #[test]
+  fn invalid_import() {
+    for line in ["import 'foo\n", "import \"\\q\"\n", "import 'foo' bar\n"] {
+      assert!(matches!(
+        Include::parse(line, Path::new("justfile"), 0),
+        Some(Err(Error::InvalidImport { .. })),
+      ));
+    }
+  }
+
+  #[test]
+  fn display() {
+    for (line, import) in [
+      ("!include foo.just", "import 'foo.just'"),
+      ("!include? just/*.just as ci", "import? 'just/*.just' as ci"),
+      ("!include lib('rust')", "import lib('rust')"),
+      ("!include it's.just", "import \"it's.just\""),
+    ] {
+      assert_eq!(parse(line).unwrap().to_string(), import);
+      assert_eq!(parse(import).unwrap().to_string(), import);
+    }
+  }
+}
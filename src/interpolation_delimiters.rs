@@ -0,0 +1,149 @@
+use super::*;
+
+/// The delimiters of interpolations in recipe bodies, `{{` and `}}` unless
+/// changed with `set interpolation-delimiters := ['<%', '%>']`
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct InterpolationDelimiters {
+  pub(crate) open: String,
+  pub(crate) close: String,
+}
+
+impl InterpolationDelimiters {
+  pub(crate) fn new(open: &str, close: &str) -> Result<Self, String> {
+    for delimiter in [open, close] {
+      if delimiter.is_empty() {
+        return Err("Interpolation delimiters may not be empty".into());
+      }
+
+      if delimiter.chars().any(char::is_whitespace) {
+        return Err(format!(
+          "Interpolation delimiter `{delimiter}` may not contain whitespace"
+        ));
+      }
+    }
+
+    Ok(Self {
+      open: open.into(),
+      close: close.into(),
+    })
+  }
+
+  /// The delimiters set by a `set interpolation-delimiters := [...]` line
+  /// in `src`, a justfile including the files it imports, or the default
+  /// delimiters if there is none. This is found before `src` is lexed, so
+  /// the setting applies to the whole justfile, regardless of where it
+  /// appears. Errors in the setting are reported by the parser.
+  pub(crate) fn scan(src: &str) -> Self {
+    src
+      .lines()
+      .filter(|line| line.starts_with(Keyword::Set.lexeme()))
+      .find_map(|line| Self::from_setting(&Lexer::lex(line, &Self::default()).ok()?))
+      .unwrap_or_default()
+  }
+
+  /// Like `scan`, but for the tokens of a justfile which have already been
+  /// lexed
+  pub(crate) fn scan_tokens(tokens: &[Token]) -> Self {
+    tokens
+      .split(|token| token.kind == TokenKind::Eol)
+      .find_map(Self::from_setting)
+      .unwrap_or_default()
+  }
+
+  /// The delimiters set by `line`, the tokens of a line of a justfile, if it
+  /// is a valid `set interpolation-delimiters := [...]` setting
+  fn from_setting(line: &[Token]) -> Option<Self> {
+    use TokenKind::*;
+
+    let line = line
+      .iter()
+      .filter(|token| !matches!(token.kind, Comment | Dedent | Eof | Eol | Whitespace))
+      .collect::<Vec<&Token>>();
+
+    let (open, close) = match line[..] {
+      [set, name, colon_equals, bracket_l, open, comma, close, ref rest @ ..]
+        if Keyword::Set == set.lexeme()
+          && Keyword::InterpolationDelimiters == name.lexeme()
+          && colon_equals.kind == ColonEquals
+          && bracket_l.kind == BracketL
+          && open.kind == StringToken
+          && comma.kind == Comma
+          && close.kind == StringToken
+          && matches!(
+            rest
+              .iter()
+              .map(|token| token.kind)
+              .collect::<Vec<TokenKind>>()[..],
+            [BracketR] | [Comma, BracketR]
+          ) =>
+      {
+        (open, close)
+      }
+      _ => return None,
+    };
+
+    let contents = |token: &Token<'_>| {
+      let lexeme = token.lexeme();
+      let delimiter_len = StringKind::from_token_start(lexeme).map_or(0, StringKind::delimiter_len);
+      lexeme[delimiter_len..lexeme.len() - delimiter_len].to_owned()
+    };
+
+    Self::new(&contents(open), &contents(close)).ok()
+  }
+
+  /// An opening delimiter written twice, e.g. `{{{{`, which is an escaped
+  /// opening delimiter in recipe text rather than the start of an
+  /// interpolation
+  pub(crate) fn escaped_open(&self) -> String {
+    self.open.repeat(2)
+  }
+
+  /// Replace escaped opening delimiters in `text` with the delimiter itself
+  pub(crate) fn unescape(&self, text: &str) -> String {
+    text.replace(&self.escaped_open(), &self.open)
+  }
+}
+
+impl Default for InterpolationDelimiters {
+  fn default() -> Self {
+    Self {
+      open: "{{".into(),
+      close: "}}".into(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn invalid() {
+    assert!(InterpolationDelimiters::new("", "%>").is_err());
+    assert!(InterpolationDelimiters::new("<%", "% >").is_err());
+  }
+
+  #[test]
+  fn scan() {
+    assert_eq!(
+      InterpolationDelimiters::scan("foo:\n  echo\nset interpolation-delimiters := ['<%', '%>']\n"),
+      InterpolationDelimiters::new("<%", "%>").unwrap(),
+    );
+
+    assert_eq!(
+      InterpolationDelimiters::scan("foo:\n  set interpolation-delimiters := ['<%', '%>']\n"),
+      InterpolationDelimiters::default(),
+    );
+
+    assert_eq!(
+      InterpolationDelimiters::scan("set interpolation-delimiters := ['', '%>']\n"),
+      InterpolationDelimiters::default(),
+    );
+  }
+
+  #[test]
+  fn unescape() {
+    let delimiters = InterpolationDelimiters::new("<%", "%>").unwrap();
+    assert_eq!(delimiters.unescape("<%<% {{{{ %>"), "<% {{{{ %>");
+  }
+}
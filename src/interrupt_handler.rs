@@ -2,15 +2,16 @@ use super::*;
 
 pub(crate) struct InterruptHandler {
   blocks: u32,
-  interrupted: bool,
+  interrupted: Option<i32>,
+  process_groups: Vec<u32>,
   verbosity: Verbosity,
 }
 
 impl InterruptHandler {
-  pub(crate) fn install(verbosity: Verbosity) -> Result<(), ctrlc::Error> {
+  pub(crate) fn install(verbosity: Verbosity) -> Result<(), io::Error> {
     let mut instance = Self::instance();
     instance.verbosity = verbosity;
-    ctrlc::set_handler(|| Self::instance().interrupt())
+    Platform::install_signal_handler(|signal| Self::instance().interrupt(signal))
   }
 
   pub(crate) fn instance() -> MutexGuard<'static, Self> {
@@ -34,23 +35,62 @@ impl InterruptHandler {
   const fn new() -> Self {
     Self {
       blocks: 0,
-      interrupted: false,
+      interrupted: None,
+      process_groups: Vec::new(),
       verbosity: Verbosity::default(),
     }
   }
 
-  fn interrupt(&mut self) {
-    self.interrupted = true;
+  fn interrupt(&mut self, signal: i32) {
+    self.interrupted = Some(signal);
 
+    for process_group in &self.process_groups {
+      Platform::signal_process_group(*process_group, signal);
+    }
+
+    // While a child process is running, `just` waits for it to exit and then
+    // stops through the normal error path, so that the error is reported and
+    // temporary files are cleaned up. Otherwise, there is nothing to wait
+    // for, and `just` may be blocked, for example reading from stdin, so it
+    // exits immediately.
     if self.blocks > 0 {
       return;
     }
 
-    Self::exit();
+    process::exit(128 + signal);
+  }
+
+  pub(crate) fn interrupted(&self) -> bool {
+    self.interrupted.is_some()
   }
 
-  fn exit() {
-    process::exit(130);
+  /// The signal received while a child process was running, if any
+  pub(crate) fn signal(&self) -> Option<i32> {
+    self.interrupted
+  }
+
+  /// Return an `Error::Signal` for `recipe` if a signal was received, so that
+  /// `just` stops even if the child process which received it exited
+  /// successfully
+  pub(crate) fn check<'src>(recipe: &'src str, token: Option<Token<'src>>) -> RunResult<'src, ()> {
+    match Self::instance().signal() {
+      Some(signal) => Err(Error::Signal {
+        recipe,
+        token,
+        signal,
+      }),
+      None => Ok(()),
+    }
+  }
+
+  /// Forward signals received while `process_group` is registered to all
+  /// processes in that group
+  pub(crate) fn add_process_group(&mut self, process_group: u32) {
+    self.process_groups.push(process_group);
+  }
+
+  pub(crate) fn remove_process_group(&mut self, process_group: u32) {
+    self.process_groups.retain(|group| *group != process_group);
   }
 
   pub(crate) fn block(&mut self) {
@@ -73,10 +113,6 @@ impl InterruptHandler {
     }
 
     self.blocks -= 1;
-
-    if self.interrupted {
-      Self::exit();
-    }
   }
 
   pub(crate) fn guard<T, F: FnOnce() -> T>(function: F) -> T {
@@ -0,0 +1,124 @@
+use super::*;
+
+/// A length of time, written as a whole number followed by an optional unit,
+/// one of `ms`, `s`, `m`, or `h`, for example `500ms` or `30s`. A number
+/// without a unit is a number of seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Interval(Duration);
+
+impl Interval {
+  pub(crate) fn duration(self) -> Duration {
+    self.0
+  }
+}
+
+impl From<Duration> for Interval {
+  fn from(duration: Duration) -> Self {
+    Self(duration)
+  }
+}
+
+impl FromStr for Interval {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let s = s.trim();
+
+    let digits = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+
+    let (number, unit) = s.split_at(digits);
+
+    let unit = unit.trim_start();
+
+    let invalid = || format!("`{s}` is not a valid interval, expected a number and a unit");
+
+    let number = number.parse::<u64>().map_err(|_| invalid())?;
+
+    let duration = match unit {
+      "ms" => Duration::from_millis(number),
+      "" | "s" => Duration::from_secs(number),
+      "m" => Duration::from_secs(number.saturating_mul(60)),
+      "h" => Duration::from_secs(number.saturating_mul(60 * 60)),
+      _ if !unit.chars().all(char::is_alphabetic) => return Err(invalid()),
+      _ => {
+        return Err(format!(
+          "`{unit}` is not a valid interval unit, expected `ms`, `s`, `m`, or `h`"
+        ))
+      }
+    };
+
+    if duration.is_zero() {
+      return Err("Interval must be greater than zero".into());
+    }
+
+    Ok(Self(duration))
+  }
+}
+
+impl Display for Interval {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let millis = self.0.as_millis();
+
+    if millis % 3_600_000 == 0 {
+      write!(f, "{}h", millis / 3_600_000)
+    } else if millis % 60_000 == 0 {
+      write!(f, "{}m", millis / 60_000)
+    } else if millis % 1000 == 0 {
+      write!(f, "{}s", millis / 1000)
+    } else {
+      write!(f, "{millis}ms")
+    }
+  }
+}
+
+impl Serialize for Interval {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.collect_str(self)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse() {
+    #[track_caller]
+    fn case(text: &str, duration: Duration) {
+      assert_eq!(text.parse::<Interval>().unwrap().duration(), duration);
+    }
+
+    case("500ms", Duration::from_millis(500));
+    case("30s", Duration::from_secs(30));
+    case("30", Duration::from_secs(30));
+    case("5m", Duration::from_secs(300));
+    case("2h", Duration::from_secs(7200));
+    case(" 10 s ", Duration::from_secs(10));
+  }
+
+  #[test]
+  fn parse_error() {
+    assert!("".parse::<Interval>().is_err());
+    assert!("s".parse::<Interval>().is_err());
+    assert!("10d".parse::<Interval>().is_err());
+    assert!("1.5s".parse::<Interval>().is_err());
+    assert!("0s".parse::<Interval>().is_err());
+  }
+
+  #[test]
+  fn display() {
+    #[track_caller]
+    fn case(text: &str, expected: &str) {
+      assert_eq!(text.parse::<Interval>().unwrap().to_string(), expected);
+    }
+
+    case("1500ms", "1500ms");
+    case("2000ms", "2s");
+    case("90s", "90s");
+    case("120", "2m");
+    case("60m", "1h");
+  }
+}
@@ -6,16 +6,65 @@ pub(crate) enum Item<'src> {
   Alias(Alias<'src, Name<'src>>),
   Assignment(Assignment<'src>),
   Comment(&'src str),
+  Function(UserFunction<'src>),
+  Group(GroupDeclaration<'src>),
   Recipe(UnresolvedRecipe<'src>),
   Set(Set<'src>),
 }
 
+impl<'src> Item<'src> {
+  /// The kind and name of the definition made by this item, if any. Aliases
+  /// and recipes share a kind, since they can't have the same name.
+  fn definition(&self) -> Option<(&'static str, &'src str)> {
+    match self {
+      Item::Alias(alias) => Some(("recipe", alias.name.lexeme())),
+      Item::Assignment(assignment) => Some(("variable", assignment.name.lexeme())),
+      Item::Comment(_) => None,
+      Item::Function(function) => Some(("function", function.name.lexeme())),
+      Item::Group(group) => Some(("group", group.name.raw)),
+      Item::Recipe(recipe) => Some(("recipe", recipe.name())),
+      Item::Set(set) => Some(("setting", set.name.lexeme())),
+    }
+  }
+
+  /// Merge the items of `overlay` on top of `items`, replacing items which
+  /// define the same name with their definition in `overlay`, and appending
+  /// the rest of `overlay`
+  pub(crate) fn overlay(items: &mut Vec<Item<'src>>, overlay: Vec<Item<'src>>) {
+    let mut overlay = overlay.into_iter().map(Some).collect::<Vec<Option<Item>>>();
+
+    let definitions = overlay
+      .iter()
+      .enumerate()
+      .filter_map(|(i, item)| Some((item.as_ref()?.definition()?, i)))
+      .collect::<BTreeMap<(&str, &str), usize>>();
+
+    items.retain_mut(|item| {
+      let Some(&i) = item.definition().and_then(|key| definitions.get(&key)) else {
+        return true;
+      };
+
+      match overlay[i].take() {
+        Some(replacement) => {
+          *item = replacement;
+          true
+        }
+        None => false,
+      }
+    });
+
+    items.extend(overlay.into_iter().flatten());
+  }
+}
+
 impl<'src> Display for Item<'src> {
   fn fmt(&self, f: &mut Formatter) -> fmt::Result {
     match self {
       Item::Alias(alias) => write!(f, "{alias}"),
       Item::Assignment(assignment) => write!(f, "{assignment}"),
       Item::Comment(comment) => write!(f, "{comment}"),
+      Item::Function(function) => write!(f, "{function}"),
+      Item::Group(group) => write!(f, "{group}"),
       Item::Recipe(recipe) => write!(f, "{}", recipe.color_display(Color::never())),
       Item::Set(set) => write!(f, "{set}"),
     }
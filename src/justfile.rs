@@ -1,4 +1,4 @@
-use {super::*, serde::Serialize};
+use {super::*, serde::Serialize, std::cell::RefCell};
 
 #[derive(Debug, PartialEq, Serialize)]
 pub(crate) struct Justfile<'src> {
@@ -6,9 +6,10 @@ pub(crate) struct Justfile<'src> {
   pub(crate) assignments: Table<'src, Assignment<'src>>,
   #[serde(serialize_with = "keyed::serialize_option")]
   pub(crate) first: Option<Rc<Recipe<'src>>>,
+  pub(crate) functions: Table<'src, UserFunction<'src>>,
   pub(crate) recipes: Table<'src, Rc<Recipe<'src>>>,
   pub(crate) settings: Settings<'src>,
-  pub(crate) warnings: Vec<Warning>,
+  pub(crate) warnings: Vec<Warning<'src>>,
 }
 
 impl<'src> Justfile<'src> {
@@ -16,19 +17,30 @@ impl<'src> Justfile<'src> {
     self.recipes.len()
   }
 
-  pub(crate) fn suggest_recipe(&self, input: &str) -> Option<Suggestion<'src>> {
+  const MAX_SUGGESTIONS: usize = 3;
+
+  /// The recipes and aliases with names closest to `input`, if any are close
+  /// enough, of which there may be several if they are equally close. Unless
+  /// `input` is itself namespaced, namespaced names are also compared without
+  /// their namespace, so that `deploy` suggests `ci::deploy`.
+  pub(crate) fn suggest_recipes(&self, input: &str) -> Vec<Suggestion<'src>> {
+    let distance = |name: &str| {
+      let distance = edit_distance(name, input);
+      match name.rsplit_once("::") {
+        Some((_namespace, base)) if !input.contains("::") => {
+          distance.min(edit_distance(base, input))
+        }
+        _ => distance,
+      }
+    };
+
     let mut suggestions = self
       .recipes
       .keys()
-      .map(|name| {
-        (
-          edit_distance(name, input),
-          Suggestion { name, target: None },
-        )
-      })
+      .map(|name| (distance(name), Suggestion { name, target: None }))
       .chain(self.aliases.iter().map(|(name, alias)| {
         (
-          edit_distance(name, input),
+          distance(name),
           Suggestion {
             name,
             target: Some(alias.target.name.lexeme()),
@@ -37,12 +49,16 @@ impl<'src> Justfile<'src> {
       }))
       .filter(|(distance, _suggestion)| distance < &3)
       .collect::<Vec<(usize, Suggestion)>>();
-    suggestions.sort_by_key(|(distance, _suggestion)| *distance);
+    suggestions.sort_by_key(|(distance, suggestion)| (*distance, suggestion.name));
+
+    let closest = suggestions.first().map(|(distance, _suggestion)| *distance);
 
     suggestions
       .into_iter()
+      .take_while(|(distance, _suggestion)| Some(*distance) == closest)
       .map(|(_distance, suggestion)| suggestion)
-      .next()
+      .take(Self::MAX_SUGGESTIONS)
+      .collect()
   }
 
   pub(crate) fn suggest_variable(&self, input: &str) -> Option<Suggestion<'src>> {
@@ -68,9 +84,11 @@ impl<'src> Justfile<'src> {
   pub(crate) fn run(
     &self,
     config: &Config,
+    loader: &Loader,
     search: &Search,
     overrides: &BTreeMap<String, String>,
     arguments: &[String],
+    report: &RefCell<Report>,
   ) -> RunResult<'src, ()> {
     let unknown_overrides = overrides
       .keys()
@@ -84,19 +102,29 @@ impl<'src> Justfile<'src> {
       });
     }
 
-    let dotenv = if config.load_dotenv {
+    let login_env = if self.settings.inherit_login_env {
+      login_env(config)?
+    } else {
+      BTreeMap::new()
+    };
+
+    let mut dotenv = if config.load_dotenv {
       load_dotenv(config, &self.settings, &search.working_directory)?
     } else {
       BTreeMap::new()
     };
 
+    if let Some(hook) = &self.settings.env_hook {
+      dotenv.extend(env_hook(config, hook, &search.working_directory)?);
+    }
+
     let scope = {
       let mut scope = Scope::new();
       let mut unknown_overrides = Vec::new();
 
       for (name, value) in overrides {
         if let Some(assignment) = self.assignments.get(name) {
-          scope.bind(assignment.export, assignment.name, value.clone());
+          scope.bind_assignment(assignment, value.clone());
         } else {
           unknown_overrides.push(name.clone());
         }
@@ -112,6 +140,8 @@ impl<'src> Justfile<'src> {
         &self.assignments,
         config,
         &dotenv,
+        &self.functions,
+        &login_env,
         scope,
         &self.settings,
         search,
@@ -123,7 +153,7 @@ impl<'src> Justfile<'src> {
         binary, arguments, ..
       } => {
         let mut command = if config.shell_command {
-          let mut command = self.settings.shell_command(config);
+          let mut command = self.settings.shell_command(config)?;
           command.arg(binary);
           command
         } else {
@@ -136,7 +166,7 @@ impl<'src> Justfile<'src> {
 
         let scope = scope.child();
 
-        command.export(&self.settings, &dotenv, &scope);
+        command.export(config, &self.settings, &login_env, &dotenv, &scope);
 
         let status = InterruptHandler::guard(|| command.status()).map_err(|io_error| {
           Error::CommandInvoke {
@@ -154,17 +184,45 @@ impl<'src> Justfile<'src> {
           });
         };
 
+        if let Some(signal) = InterruptHandler::instance().signal() {
+          return Err(Error::Interrupted { signal });
+        }
+
         return Ok(());
       }
-      Subcommand::Evaluate { variable, .. } => {
-        if let Some(variable) = variable {
-          if let Some(value) = scope.value(variable) {
-            print!("{value}");
-          } else {
+      Subcommand::Evaluate { variables, .. } => {
+        let mut values = Vec::new();
+
+        for variable in variables {
+          let Some(value) = scope.value(variable) else {
             return Err(Error::EvalUnknownVariable {
               suggestion: self.suggest_variable(variable),
               variable: variable.clone(),
             });
+          };
+
+          values.push((variable.as_str(), value));
+        }
+
+        if config.dump_format == DumpFormat::Json {
+          if variables.is_empty() {
+            values = scope
+              .bindings()
+              .map(|binding| (binding.name.lexeme(), binding.value.as_str()))
+              .collect();
+          }
+
+          serde_json::to_writer(
+            io::stdout(),
+            &values.into_iter().collect::<BTreeMap<_, _>>(),
+          )
+          .map_err(|serde_json_error| Error::DumpJson { serde_json_error })?;
+          println!();
+        } else if let [(_, value)] = values.as_slice() {
+          print!("{value}");
+        } else if !values.is_empty() {
+          for (name, value) in values {
+            println!("{name}={value}");
           }
         } else {
           let mut width = 0;
@@ -188,11 +246,17 @@ impl<'src> Justfile<'src> {
       _ => {}
     }
 
+    // arguments after `--` are passed to the last recipe verbatim
+    let (arguments, trailing) = match arguments.iter().position(|argument| argument == "--") {
+      Some(i) => (&arguments[..i], &arguments[i + 1..]),
+      None => (arguments, &[][..]),
+    };
+
     let argvec: Vec<&str> = if !arguments.is_empty() {
       arguments.iter().map(String::as_str).collect()
     } else if let Some(recipe) = &self.first {
       let min_arguments = recipe.min_arguments();
-      if min_arguments > 0 {
+      if min_arguments > trailing.len() {
         return Err(Error::DefaultRecipeRequiresArguments {
           recipe: recipe.name.lexeme(),
           min_arguments,
@@ -205,29 +269,65 @@ impl<'src> Justfile<'src> {
 
     let arguments = argvec.as_slice();
 
+    let answers = Arena::new();
     let mut missing = vec![];
     let mut grouped = vec![];
     let mut rest = arguments;
 
     while let Some((argument, mut tail)) = rest.split_first() {
-      if let Some(recipe) = self.get_recipe(argument) {
-        if recipe.parameters.is_empty() {
-          grouped.push((recipe, &[][..]));
-        } else {
-          let argument_range = recipe.argument_range();
-          let argument_count = cmp::min(tail.len(), recipe.max_arguments());
-          if !argument_range.range_contains(&argument_count) {
-            return Err(Error::ArgumentCountMismatch {
-              recipe: recipe.name(),
-              parameters: recipe.parameters.clone(),
-              found: tail.len(),
-              min: recipe.min_arguments(),
-              max: recipe.max_arguments(),
-            });
+      let recipe = self.get_recipe(argument).or_else(|| {
+        if !self.settings.case_insensitive_recipes() {
+          return None;
+        }
+
+        let recipe = self.get_recipe_case_insensitive(argument)?;
+
+        if config.verbosity.loud() {
+          let message = config.color.stderr().message();
+          eprintln!(
+            "{} {}Running recipe `{}` for `{argument}`{}",
+            config.color.stderr().banner().paint("note:"),
+            message.prefix(),
+            recipe.name(),
+            message.suffix(),
+          );
+        }
+
+        Some(recipe)
+      });
+
+      if let Some(recipe) = recipe {
+        let mut positional = Vec::new();
+        let mut keywords = BTreeMap::new();
+
+        while let Some((argument, rest)) = tail.split_first() {
+          if let Some((parameter, value)) = recipe.keyword_argument(argument)? {
+            if keywords.insert(parameter, value).is_some() {
+              return Err(Error::DuplicateArgument {
+                recipe: recipe.name(),
+                parameter,
+              });
+            }
+          } else if positional.len() < recipe.positional_capacity(&keywords) {
+            positional.push(*argument);
+          } else {
+            break;
           }
-          grouped.push((recipe, &tail[0..argument_count]));
-          tail = &tail[argument_count..];
+
+          tail = rest;
+        }
+
+        if tail.is_empty() {
+          positional.extend(trailing.iter().map(String::as_str));
         }
+
+        if (config.prompt || self.settings.interactive_arguments) && atty::is(atty::Stream::Stdin) {
+          recipe.prompt_for_arguments(&positional, &mut keywords, &answers)?;
+        }
+
+        recipe.check_argument_count(&positional, &keywords)?;
+
+        grouped.push((recipe, positional, keywords));
       } else {
         missing.push((*argument).to_owned());
       }
@@ -235,32 +335,183 @@ impl<'src> Justfile<'src> {
     }
 
     if !missing.is_empty() {
-      let suggestion = if missing.len() == 1 {
-        self.suggest_recipe(missing.first().unwrap())
+      let suggestions = if missing.len() == 1 {
+        self.suggest_recipes(missing.first().unwrap())
       } else {
-        None
+        Vec::new()
       };
       return Err(Error::UnknownRecipes {
         recipes: missing,
-        suggestion,
+        suggestions,
       });
     }
 
+    for (recipe, positional, keywords) in &grouped {
+      recipe.check_arguments(positional, keywords)?;
+    }
+
+    for (recipe, positional, keywords) in &grouped {
+      report
+        .borrow_mut()
+        .request(recipe.name(), positional, keywords);
+    }
+
+    self.check_required_env(
+      config,
+      grouped.iter().map(|(recipe, _, _)| *recipe),
+      &login_env,
+      &dotenv,
+      &scope,
+    )?;
+
     let context = RecipeContext {
       settings: &self.settings,
+      functions: &self.functions,
+      login_env: &login_env,
       config,
+      report,
       scope,
       search,
     };
 
-    let mut ran = BTreeSet::new();
-    for (recipe, arguments) in grouped {
-      Self::run_recipe(&context, recipe, arguments, &dotenv, search, &mut ran)?;
+    if let Subcommand::ExportScript { output, .. } = &config.subcommand {
+      return Self::export_script(&context, grouped, &dotenv, output.as_deref());
+    }
+
+    let mut ran = BTreeMap::new();
+    let mut failed = Vec::new();
+    for (recipe, positional, keywords) in grouped {
+      let mut recipe = recipe.with_attribute_overrides(&config.attribute_overrides);
+
+      if config.no_deps {
+        recipe.dependencies.drain(..recipe.priors);
+        recipe.priors = 0;
+      }
+
+      let result = Self::run_recipe(
+        &context,
+        &recipe,
+        &positional,
+        &keywords,
+        &dotenv,
+        &mut ran,
+        false,
+      );
+
+      match result {
+        Err(error) if config.keep_going && !matches!(error, Error::Signal { .. }) => {
+          if !config.verbosity.quiet() && error.print_message() {
+            config.error_format.print(
+              &error,
+              loader,
+              config.color.stderr(),
+              config.timestamps.as_ref(),
+              config.exit_status.code(&error),
+            );
+          }
+          failed.push((recipe.name(), error));
+        }
+        result => {
+          result?;
+        }
+      }
+    }
+
+    if let Some((_, error)) = failed.first() {
+      let code = error.code().unwrap_or(EXIT_FAILURE);
+      let (recipes, errors) = failed.into_iter().unzip();
+      return Err(Error::RecipesFailed {
+        code,
+        recipes,
+        errors,
+      });
     }
 
     Ok(())
   }
 
+  /// Check that the environment variables required by the justfile, and by
+  /// `recipes` and their dependencies, are all set, either in just's own
+  /// environment, or by the `.env` file or exported variables and parameters
+  fn check_required_env<'a>(
+    &self,
+    config: &Config,
+    recipes: impl Iterator<Item = &'a Recipe<'src>>,
+    login_env: &BTreeMap<String, String>,
+    dotenv: &BTreeMap<String, String>,
+    scope: &Scope,
+  ) -> RunResult<'src, ()>
+  where
+    'src: 'a,
+  {
+    let mut required = self
+      .settings
+      .required_env
+      .iter()
+      .map(String::as_str)
+      .collect::<BTreeSet<&str>>();
+
+    let mut exported_parameters = BTreeSet::new();
+    let mut seen = BTreeSet::new();
+    let mut stack = recipes.collect::<Vec<&Recipe>>();
+
+    while let Some(recipe) = stack.pop() {
+      if !seen.insert(recipe.name()) {
+        continue;
+      }
+
+      required.extend(recipe.required_env());
+
+      exported_parameters.extend(
+        recipe
+          .parameters
+          .iter()
+          .filter(|parameter| parameter.export || self.settings.export)
+          .map(|parameter| self.settings.export_name(parameter.name.lexeme())),
+      );
+
+      stack.extend(
+        recipe
+          .dependencies
+          .iter()
+          .map(|dependency| dependency.recipe.as_ref()),
+      );
+    }
+
+    if required.is_empty() {
+      return Ok(());
+    }
+
+    let child = scope.child();
+    let mut command = Command::new("");
+    command.export(config, &self.settings, login_env, dotenv, &child);
+
+    // variables which are exported, or removed by `clean-env`, mapped to
+    // whether they are set
+    let exported = command
+      .get_envs()
+      .map(|(name, value)| (name.to_owned(), value.is_some()))
+      .collect::<BTreeMap<OsString, bool>>();
+
+    let missing = required
+      .into_iter()
+      .filter(|variable| {
+        !exported
+          .get(OsStr::new(variable))
+          .copied()
+          .unwrap_or_else(|| env::var_os(variable).is_some())
+          && !exported_parameters.contains(*variable)
+      })
+      .map(str::to_owned)
+      .collect::<Vec<String>>();
+
+    if missing.is_empty() {
+      Ok(())
+    } else {
+      Err(Error::MissingRequiredEnv { variables: missing })
+    }
+  }
+
   pub(crate) fn get_alias(&self, name: &str) -> Option<&Alias<'src>> {
     self.aliases.get(name)
   }
@@ -273,60 +524,360 @@ impl<'src> Justfile<'src> {
       .or_else(|| self.aliases.get(name).map(|alias| alias.target.as_ref()))
   }
 
-  fn run_recipe(
+  /// Find the recipe or alias whose name matches `name` ignoring case, if
+  /// there is exactly one
+  fn get_recipe_case_insensitive(&self, name: &str) -> Option<&Recipe<'src>> {
+    let name = name.to_lowercase();
+
+    let mut matches = self
+      .recipes
+      .keys()
+      .chain(self.aliases.keys())
+      .filter(|candidate| candidate.to_lowercase() == name);
+
+    let candidate = matches.next()?;
+
+    if matches.next().is_some() {
+      return None;
+    }
+
+    self.get_recipe(candidate)
+  }
+
+  /// Print, or write to `output`, a shell script which runs the recipes in
+  /// `grouped` as running them would, without needing just
+  fn export_script(
+    context: &RecipeContext<'src, '_>,
+    grouped: Vec<(&Recipe<'src>, Vec<&str>, BTreeMap<&str, &str>)>,
+    dotenv: &BTreeMap<String, String>,
+    output: Option<&Path>,
+  ) -> RunResult<'src, ()> {
+    let mut lines = vec![export_script::preamble(context.settings, context.config)?];
+
+    let environment = export_script::environment(
+      context.config,
+      context.settings,
+      context.login_env,
+      dotenv,
+      &context.scope.child(),
+    );
+
+    for (name, value) in &environment {
+      lines.push(format!("export {name}={}", export_script::quote(value)));
+    }
+
+    let mut ran = BTreeSet::new();
+    for (recipe, positional, keywords) in grouped {
+      let mut recipe = recipe.with_attribute_overrides(&context.config.attribute_overrides);
+
+      if context.config.no_deps {
+        recipe.dependencies.clear();
+        recipe.priors = 0;
+      }
+
+      lines.extend(Self::export_recipe(
+        context,
+        &recipe,
+        &positional,
+        &keywords,
+        dotenv,
+        &environment,
+        &mut ran,
+      )?);
+    }
+
+    let script = lines.join("\n") + "\n";
+
+    if let Some(path) = output {
+      fs::write(path, script)
+        .and_then(|()| Platform::set_execute_permission(path))
+        .map_err(|io_error| Error::WriteScript {
+          path: path.into(),
+          io_error,
+        })
+    } else {
+      print!("{script}");
+      Ok(())
+    }
+  }
+
+  /// The lines of an exported script which run `recipe`, and with
+  /// `--inline-dependencies`, its dependencies, evaluated as `run_recipe`
+  /// evaluates them
+  fn export_recipe(
     context: &RecipeContext<'src, '_>,
     recipe: &Recipe<'src>,
     arguments: &[&str],
+    keywords: &BTreeMap<&str, &str>,
     dotenv: &BTreeMap<String, String>,
-    search: &Search,
+    environment: &BTreeMap<String, String>,
     ran: &mut BTreeSet<Vec<String>>,
-  ) -> RunResult<'src, ()> {
+  ) -> RunResult<'src, Vec<String>> {
+    let search = context.search;
+
+    let inline_dependencies = matches!(
+      context.config.subcommand,
+      Subcommand::ExportScript {
+        inline_dependencies: true,
+        ..
+      }
+    );
+
+    let mut lines = Vec::new();
+
     let mut invocation = vec![recipe.name().to_owned()];
     for argument in arguments {
       invocation.push((*argument).to_string());
     }
+    for (parameter, value) in keywords {
+      invocation.push(format!("--{parameter}={value}"));
+    }
 
     if ran.contains(&invocation) {
-      return Ok(());
+      return Ok(lines);
     }
 
-    let (outer, positional) = Evaluator::evaluate_parameters(
-      context.config,
-      dotenv,
-      &recipe.parameters,
+    let loaded;
+
+    let recipe_dotenv = match recipe.dotenv_filenames() {
+      Some(filenames) if context.config.load_dotenv => {
+        let mut merged = dotenv.clone();
+        merged.extend(load_from_filenames(
+          filenames,
+          &search.working_directory,
+          false,
+        )?);
+        loaded = merged;
+        &loaded
+      }
+      _ => dotenv,
+    };
+
+    let (outer, positional) =
+      Evaluator::evaluate_parameters(context, recipe_dotenv, recipe, arguments, keywords)?;
+
+    recipe.check_arguments(&positional, &BTreeMap::new())?;
+
+    let scope = outer.child();
+
+    let mut evaluator = Evaluator::recipe_evaluator(context, recipe_dotenv, recipe, &scope);
+
+    let mut dependencies = Vec::new();
+
+    for Dependency {
+      recipe: dependency,
       arguments,
-      &context.scope,
-      context.settings,
-      search,
-    )?;
+      capture,
+    } in &recipe.dependencies
+    {
+      if capture.is_some() {
+        return Err(Error::ExportScriptCapture {
+          recipe: recipe.name(),
+          dependency: dependency.name(),
+        });
+      }
+
+      let arguments = arguments
+        .iter()
+        .map(|argument| evaluator.evaluate_expression(argument))
+        .collect::<RunResult<Vec<String>>>()?;
+
+      dependencies.push((dependency, arguments));
+    }
+
+    let (priors, subsequents) = dependencies.split_at(recipe.priors);
+
+    if inline_dependencies {
+      for (dependency, arguments) in priors {
+        lines.extend(Self::export_recipe(
+          context,
+          dependency,
+          &arguments.iter().map(String::as_ref).collect::<Vec<&str>>(),
+          &BTreeMap::new(),
+          dotenv,
+          environment,
+          ran,
+        )?);
+      }
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+      "# {}",
+      invocation
+        .iter()
+        .map(|word| export_script::quote(word))
+        .collect::<Vec<String>>()
+        .join(" ")
+    ));
+
+    if !inline_dependencies && !dependencies.is_empty() {
+      lines.push(format!(
+        "# Dependencies omitted: {}",
+        dependencies
+          .iter()
+          .map(|(dependency, _)| dependency.name())
+          .collect::<Vec<&str>>()
+          .join(", ")
+      ));
+    }
+
+    lines.extend(recipe.export(
+      context,
+      recipe_dotenv,
+      scope.child(),
+      &positional,
+      environment,
+    )?);
+
+    if inline_dependencies {
+      let mut ran = BTreeSet::new();
+
+      for (dependency, arguments) in subsequents {
+        lines.extend(Self::export_recipe(
+          context,
+          dependency,
+          &arguments.iter().map(String::as_ref).collect::<Vec<&str>>(),
+          &BTreeMap::new(),
+          dotenv,
+          environment,
+          &mut ran,
+        )?);
+      }
+    }
+
+    ran.insert(invocation);
+    Ok(lines)
+  }
+
+  /// Run `recipe` and its dependencies, unless it has already been run with
+  /// the same arguments, returning its output if `capture` is true. `ran`
+  /// holds the invocations which have been run, and the output of those whose
+  /// output was captured, so a recipe whose output is captured after it ran
+  /// uncaptured is run again.
+  fn run_recipe(
+    context: &RecipeContext<'src, '_>,
+    recipe: &Recipe<'src>,
+    arguments: &[&str],
+    keywords: &BTreeMap<&str, &str>,
+    dotenv: &BTreeMap<String, String>,
+    ran: &mut BTreeMap<Vec<String>, Result<Option<String>, ()>>,
+    capture: bool,
+  ) -> RunResult<'src, Option<String>> {
+    let search = context.search;
+
+    let mut invocation = vec![recipe.name().to_owned()];
+    for argument in arguments {
+      invocation.push((*argument).to_string());
+    }
+    for (parameter, value) in keywords {
+      invocation.push(format!("--{parameter}={value}"));
+    }
+
+    // Recipes only run once, so if a recipe ran without its output being
+    // captured, it isn't run again to capture it
+    match ran.get(&invocation) {
+      Some(Err(())) => {
+        return Err(Error::PreviouslyFailed {
+          recipe: recipe.name(),
+        })
+      }
+      Some(Ok(output)) if !capture => return Ok(output.clone()),
+      Some(Ok(Some(output))) => return Ok(Some(output.clone())),
+      Some(Ok(None)) => {
+        return Err(Error::CaptureAfterRun {
+          recipe: recipe.name(),
+        })
+      }
+      None => {}
+    }
+
+    // The recipe is recorded as having failed until it succeeds, so that
+    // with `--keep-going`, a failed recipe isn't run again by other recipes
+    // which depend on it
+    ran.insert(invocation.clone(), Err(()));
+
+    let loaded;
+
+    let recipe_dotenv = match recipe.dotenv_filenames() {
+      Some(filenames) if context.config.load_dotenv => {
+        let mut merged = dotenv.clone();
+        merged.extend(load_from_filenames(
+          filenames,
+          &search.working_directory,
+          false,
+        )?);
+        loaded = merged;
+        &loaded
+      }
+      _ => dotenv,
+    };
+
+    let (outer, positional) =
+      Evaluator::evaluate_parameters(context, recipe_dotenv, recipe, arguments, keywords)?;
+
+    recipe.check_arguments(&positional, &BTreeMap::new())?;
+
+    if !recipe.confirm(context.config)? {
+      return Err(Error::NotConfirmed {
+        recipe: recipe.name(),
+      });
+    }
 
     let scope = outer.child();
 
-    let mut evaluator =
-      Evaluator::recipe_evaluator(context.config, dotenv, &scope, context.settings, search);
+    let mut evaluator = Evaluator::recipe_evaluator(context, recipe_dotenv, recipe, &scope);
 
-    for Dependency { recipe, arguments } in recipe.dependencies.iter().take(recipe.priors) {
+    let mut captured = scope.child();
+
+    for Dependency {
+      recipe,
+      arguments,
+      capture,
+    } in recipe.dependencies.iter().take(recipe.priors)
+    {
       let arguments = arguments
         .iter()
         .map(|argument| evaluator.evaluate_expression(argument))
         .collect::<RunResult<Vec<String>>>()?;
 
-      Self::run_recipe(
+      let output = Self::run_recipe(
         context,
         recipe,
         &arguments.iter().map(String::as_ref).collect::<Vec<&str>>(),
+        &BTreeMap::new(),
         dotenv,
-        search,
         ran,
+        capture.is_some(),
       )?;
+
+      if let (Some(name), Some(output)) = (capture, output) {
+        captured.bind(false, *name, output);
+      }
     }
 
-    recipe.run(context, dotenv, scope.child(), search, &positional)?;
+    let start = Instant::now();
+
+    let result = recipe
+      .run(context, recipe_dotenv, captured, &positional, capture)
+      .and_then(|output| InterruptHandler::check(recipe.name(), None).map(|()| output));
+
+    context.report.borrow_mut().record(
+      recipe.name(),
+      &invocation[1..],
+      start.elapsed(),
+      result.as_ref().err(),
+    );
+
+    let output = result?;
 
     {
-      let mut ran = BTreeSet::new();
+      let mut ran = BTreeMap::new();
 
-      for Dependency { recipe, arguments } in recipe.dependencies.iter().skip(recipe.priors) {
+      for Dependency {
+        recipe, arguments, ..
+      } in recipe.dependencies.iter().skip(recipe.priors)
+      {
         let mut evaluated = Vec::new();
 
         for argument in arguments {
@@ -337,15 +888,16 @@ impl<'src> Justfile<'src> {
           context,
           recipe,
           &evaluated.iter().map(String::as_ref).collect::<Vec<&str>>(),
+          &BTreeMap::new(),
           dotenv,
-          search,
           &mut ran,
+          false,
         )?;
       }
     }
 
-    ran.insert(invocation);
-    Ok(())
+    ran.insert(invocation, Ok(output.clone()));
+    Ok(output)
   }
 
   pub(crate) fn public_recipes(&self, source_order: bool) -> Vec<&Recipe<'src, Dependency>> {
@@ -362,6 +914,46 @@ impl<'src> Justfile<'src> {
 
     recipes
   }
+
+  /// The groups of public recipes, in order of first appearance if
+  /// `source_order` is true, and otherwise sorted
+  pub(crate) fn public_groups(&self, source_order: bool) -> Vec<&str> {
+    let mut groups = Vec::new();
+
+    for recipe in self.public_recipes(source_order) {
+      for group in recipe.groups() {
+        if !groups.contains(&group) {
+          groups.push(group);
+        }
+      }
+    }
+
+    if !source_order {
+      groups.sort_unstable();
+    }
+
+    groups
+  }
+
+  /// The namespaces of public recipes without groups, which are listed
+  /// separately
+  pub(crate) fn public_namespaces(&self, source_order: bool) -> Vec<&str> {
+    let mut namespaces = Vec::new();
+
+    for recipe in self.public_recipes(source_order) {
+      if let Some(namespace) = recipe.namespace() {
+        if recipe.groups().is_empty() && !namespaces.contains(&namespace) {
+          namespaces.push(namespace);
+        }
+      }
+    }
+
+    if !source_order {
+      namespaces.sort_unstable();
+    }
+
+    namespaces
+  }
 }
 
 impl<'src> ColorDisplay for Justfile<'src> {
@@ -371,6 +963,9 @@ impl<'src> ColorDisplay for Justfile<'src> {
       if assignment.export {
         write!(f, "export ")?;
       }
+      if assignment.lazy {
+        write!(f, "lazy ")?;
+      }
       write!(f, "{name} := {}", assignment.value)?;
       items -= 1;
       if items != 0 {
@@ -408,11 +1003,11 @@ mod tests {
     args: ["a", "x", "y", "z"],
     error: UnknownRecipes {
       recipes,
-      suggestion,
+      suggestions,
     },
     check: {
       assert_eq!(recipes, &["x", "y", "z"]);
-      assert_eq!(suggestion, None);
+      assert_eq!(suggestions, []);
     }
   }
 
@@ -427,15 +1022,14 @@ mod tests {
     args: ["zz"],
     error: UnknownRecipes {
       recipes,
-      suggestion,
+      suggestions,
     },
     check: {
       assert_eq!(recipes, &["zz"]);
-      assert_eq!(suggestion, Some(Suggestion {
+      assert_eq!(suggestions, [Suggestion {
         name: "z",
         target: Some("foo"),
-      }
-    ));
+      }]);
     }
   }
 
@@ -457,14 +1051,14 @@ mod tests {
     args: ["a"],
     error: Code {
       recipe,
-      line_number,
+      token,
       code,
       print_message,
     },
     check: {
       assert_eq!(recipe, "a");
       assert_eq!(code, 200);
-      assert_eq!(line_number, None);
+      assert_eq!(token, None);
       assert!(print_message);
     }
   }
@@ -478,14 +1072,15 @@ mod tests {
     args: ["fail"],
     error: Code {
       recipe,
-      line_number,
+      token,
       code,
       print_message,
     },
     check: {
       assert_eq!(recipe, "fail");
       assert_eq!(code, 100);
-      assert_eq!(line_number, Some(2));
+      assert_eq!(token.map(|token| token.line.ordinal()), Some(2));
+      assert_eq!(token.map(|token| token.lexeme()), Some("@exit 100"));
       assert!(print_message);
     }
   }
@@ -499,14 +1094,14 @@ mod tests {
     args: ["a", "return", "15"],
     error: Code {
       recipe,
-      line_number,
+      token,
       code,
       print_message,
     },
     check: {
       assert_eq!(recipe, "a");
       assert_eq!(code, 150);
-      assert_eq!(line_number, Some(2));
+      assert_eq!(token.map(|token| token.line.ordinal()), Some(2));
       assert!(print_message);
     }
   }
@@ -521,6 +1116,7 @@ mod tests {
       found,
       min,
       max,
+      ..
     },
     check: {
       let param_names = parameters
@@ -535,6 +1131,52 @@ mod tests {
     }
   }
 
+  #[test]
+  fn missing_arguments_help() {
+    let config = testing::config(&["a"]);
+    let search = testing::search(&config);
+
+    let Subcommand::Run {
+      overrides,
+      arguments,
+    } = &config.subcommand
+    else {
+      panic!("Unexpected subcommand: {:?}", config.subcommand);
+    };
+
+    let src = unindent::unindent("# do the thing\na b:");
+
+    let justfile = Compiler::compile(&src).unwrap().1;
+
+    let error = justfile
+      .run(
+        &config,
+        &Loader::new(false, false),
+        &search,
+        overrides,
+        arguments,
+        &RefCell::default(),
+      )
+      .unwrap_err();
+
+    assert_eq!(
+      error.color_display(Color::never()).to_string(),
+      "error: Recipe `a` got 0 arguments but takes 1\n\
+       usage:\n    \
+       just a b",
+    );
+
+    assert_eq!(
+      error
+        .color_display(Color::never().to_terminal())
+        .to_string(),
+      "error: Recipe `a` got 0 arguments but takes 1\n\
+       usage:\n    \
+       just a b # do the thing\n\
+       note: Run `just --show a` to see the recipe's definition",
+    );
+  }
+
   run_error! {
     name: missing_some_arguments_variadic,
     src: "a b c +d:",
@@ -545,6 +1187,7 @@ mod tests {
       found,
       min,
       max,
+      ..
     },
     check: {
       let param_names = parameters
@@ -569,6 +1212,7 @@ mod tests {
       found,
       min,
       max,
+      ..
     },
     check: {
       let param_names = parameters
@@ -593,6 +1237,7 @@ mod tests {
       found,
       min,
       max,
+      ..
     },
     check: {
       let param_names = parameters
@@ -617,6 +1262,7 @@ mod tests {
       found,
       min,
       max,
+      ..
     },
     check: {
       let param_names = parameters
@@ -658,13 +1304,13 @@ mod tests {
     args: ["--quiet", "wut"],
     error: Code {
       recipe,
-      line_number,
+      token,
       print_message,
       ..
     },
     check: {
       assert_eq!(recipe, "wut");
-      assert_eq!(line_number, Some(7));
+      assert_eq!(token.map(|token| token.line.ordinal()), Some(7));
       assert!(print_message);
     }
   }
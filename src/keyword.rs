@@ -5,20 +5,45 @@ use super::*;
 pub(crate) enum Keyword {
   Alias,
   AllowDuplicateRecipes,
+  CaseInsensitiveRecipes,
+  CleanEnv,
+  CommandLog,
   DotenvFilename,
+  DotenvFilenames,
   DotenvLoad,
   DotenvPath,
+  DotenvRequired,
+  Echo,
   Else,
+  EnvHook,
+  EnvPassthrough,
   Export,
+  ExportPrefix,
   Fallback,
+  FallbackJustfiles,
   False,
+  FmtAlignAssignments,
+  FmtBlankLines,
+  FmtIndent,
+  Fn,
   If,
   IgnoreComments,
+  InheritLoginEnv,
+  InteractiveArguments,
+  InterpolationDelimiters,
+  Lazy,
+  LineTimeout,
+  LocalOverlay,
   PositionalArguments,
+  RequiredEnv,
+  RequiredVersion,
   Set,
   Shell,
+  ShellFallbacks,
+  SourceMap,
   True,
   WindowsPowershell,
+  WindowsShebang,
   WindowsShell,
   Tempdir,
 }
@@ -0,0 +1,731 @@
+use {
+  super::*,
+  serde_json::{json, Value},
+  std::io::BufRead,
+};
+
+/// JSON-RPC error code for requests with unknown methods
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// A language server for justfiles, run with `--lsp`, which talks to an
+/// editor over standard input and output. It reports compile errors as
+/// diagnostics, and provides go-to-definition, hover, and completion, using
+/// the locations of names recorded by the parser.
+#[derive(Default)]
+pub(crate) struct LanguageServer {
+  /// The text of each open document, by URI
+  documents: BTreeMap<String, String>,
+}
+
+impl LanguageServer {
+  pub(crate) fn run() -> RunResult<'static, ()> {
+    Self::default()
+      .serve(&mut io::stdin().lock(), &mut io::stdout().lock())
+      .map_err(|io_error| Error::LanguageServer { io_error })
+  }
+
+  /// Handle messages from `input` until the client sends `exit` or closes
+  /// the connection, writing responses and notifications to `output`
+  fn serve(&mut self, input: &mut dyn BufRead, output: &mut dyn Write) -> io::Result<()> {
+    while let Some(message) = Self::read(input)? {
+      let method = message["method"].as_str().unwrap_or_default();
+      let params = &message["params"];
+
+      let result = match method {
+        "exit" => return Ok(()),
+        "initialize" => Some(json!({
+          "capabilities": {
+            "completionProvider": {},
+            "definitionProvider": true,
+            "hoverProvider": true,
+            "textDocumentSync": 1,
+          },
+          "serverInfo": {
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+          },
+        })),
+        "shutdown" => Some(Value::Null),
+        "textDocument/completion" => Some(self.completion(params)),
+        "textDocument/definition" => Some(self.definition(params)),
+        "textDocument/didChange" => {
+          // Documents are synchronized in full, so the last change has the
+          // whole text
+          if let Some(text) = params["contentChanges"]
+            .as_array()
+            .and_then(|changes| changes.last())
+            .and_then(|change| change["text"].as_str())
+          {
+            self.open(output, params, text)?;
+          }
+          None
+        }
+        "textDocument/didClose" => {
+          if let Some(uri) = params["textDocument"]["uri"].as_str() {
+            self.documents.remove(uri);
+            Self::publish_diagnostics(output, uri, Vec::new())?;
+          }
+          None
+        }
+        "textDocument/didOpen" => {
+          if let Some(text) = params["textDocument"]["text"].as_str() {
+            self.open(output, params, text)?;
+          }
+          None
+        }
+        "textDocument/hover" => Some(self.hover(params)),
+        _ => None,
+      };
+
+      let Some(id) = message.get("id") else {
+        continue;
+      };
+
+      let response = match result {
+        Some(result) => json!({
+          "jsonrpc": "2.0",
+          "id": id,
+          "result": result,
+        }),
+        None => json!({
+          "jsonrpc": "2.0",
+          "id": id,
+          "error": {
+            "code": METHOD_NOT_FOUND,
+            "message": format!("Unknown method `{method}`"),
+          },
+        }),
+      };
+
+      Self::write(output, &response)?;
+    }
+
+    Ok(())
+  }
+
+  /// Read a message, returning `None` if the connection was closed
+  fn read(input: &mut dyn BufRead) -> io::Result<Option<Value>> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    let mut length = None;
+
+    loop {
+      let mut line = String::new();
+
+      if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+      }
+
+      let line = line.trim_end();
+
+      if line.is_empty() {
+        break;
+      }
+
+      if let Some((name, value)) = line.split_once(':') {
+        if name.eq_ignore_ascii_case("content-length") {
+          length = Some(
+            value
+              .trim()
+              .parse::<usize>()
+              .map_err(|_| invalid(format!("invalid `Content-Length` header `{line}`")))?,
+          );
+        }
+      }
+    }
+
+    let length = length.ok_or_else(|| invalid("message has no `Content-Length` header".into()))?;
+
+    let mut content = vec![0; length];
+
+    input.read_exact(&mut content)?;
+
+    serde_json::from_slice(&content)
+      .map(Some)
+      .map_err(|error| invalid(error.to_string()))
+  }
+
+  fn write(output: &mut dyn Write, message: &Value) -> io::Result<()> {
+    let content = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{content}", content.len())?;
+    output.flush()
+  }
+
+  fn open(&mut self, output: &mut dyn Write, params: &Value, text: &str) -> io::Result<()> {
+    let Some(uri) = params["textDocument"]["uri"].as_str() else {
+      return Ok(());
+    };
+
+    let text = Self::mask_directives(text);
+
+    let diagnostics = match Compiler::compile(&text) {
+      Ok(_) => Vec::new(),
+      Err(errors) => errors
+        .iter()
+        .map(|error| {
+          json!({
+            "range": Self::range(&text, error.token.offset, error.token.length),
+            "severity": 1,
+            "source": env!("CARGO_PKG_NAME"),
+            "message": error.to_string(),
+          })
+        })
+        .collect(),
+    };
+
+    self.documents.insert(uri.into(), text);
+
+    Self::publish_diagnostics(output, uri, diagnostics)
+  }
+
+  fn publish_diagnostics(
+    output: &mut dyn Write,
+    uri: &str,
+    diagnostics: Vec<Value>,
+  ) -> io::Result<()> {
+    Self::write(
+      output,
+      &json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": {
+          "uri": uri,
+          "diagnostics": diagnostics,
+        },
+      }),
+    )
+  }
+
+  /// Replace the `!include` directives at the start of `text` with spaces.
+  /// Included files are read by the loader, so they can't be checked here,
+  /// and the directives aren't justfile syntax. They are replaced rather
+  /// than removed so that offsets are unchanged.
+  fn mask_directives(text: &str) -> String {
+    let mut masked = String::new();
+    let mut seen_content = false;
+
+    for line in text.split_inclusive('\n') {
+      if !seen_content && line.starts_with('!') {
+        let content = line.trim_end_matches(['\r', '\n']);
+        masked.extend(iter::repeat(' ').take(content.len()));
+        masked.push_str(&line[content.len()..]);
+      } else {
+        if !(line.trim().is_empty() || line.trim().starts_with('#')) {
+          seen_content = true;
+        }
+        masked.push_str(line);
+      }
+    }
+
+    masked
+  }
+
+  /// The LSP position of byte `offset` in `text`, whose characters are
+  /// counted in UTF-16 code units
+  fn position(text: &str, offset: usize) -> Value {
+    let before = &text[..offset];
+    let start = before.rfind('\n').map_or(0, |i| i + 1);
+    json!({
+      "line": before.matches('\n').count(),
+      "character": before[start..].encode_utf16().count(),
+    })
+  }
+
+  fn range(text: &str, offset: usize, length: usize) -> Value {
+    json!({
+      "start": Self::position(text, offset),
+      "end": Self::position(text, offset + length),
+    })
+  }
+
+  /// The byte offset in `text` of LSP `position`
+  fn offset(text: &str, position: &Value) -> Option<usize> {
+    let line = usize::try_from(position["line"].as_u64()?).ok()?;
+    let character = usize::try_from(position["character"].as_u64()?).ok()?;
+
+    let start = match line.checked_sub(1) {
+      None => 0,
+      Some(newline) => text.match_indices('\n').nth(newline)?.0 + 1,
+    };
+
+    let mut units = 0;
+
+    for (i, c) in text[start..].char_indices() {
+      if units >= character || c == '\n' {
+        return Some(start + i);
+      }
+      units += c.len_utf16();
+    }
+
+    Some(text.len())
+  }
+
+  /// The text of the document and the offset of the position in a text
+  /// document position request
+  fn document(&self, params: &Value) -> Option<(&str, usize)> {
+    let text = self
+      .documents
+      .get(params["textDocument"]["uri"].as_str()?)?;
+    Some((text, Self::offset(text, &params["position"])?))
+  }
+
+  /// The identifier at `offset` in `text`, and whether it is followed by an
+  /// opening parenthesis, and so is the name of a called function
+  fn identifier(text: &str, offset: usize) -> Option<(Token<'_>, bool)> {
+    let tokens = Lexer::lex(text, &InterpolationDelimiters::scan(text)).ok()?;
+
+    let i = tokens.iter().position(|token| {
+      token.kind == TokenKind::Identifier
+        && token.offset <= offset
+        && offset <= token.offset + token.length
+    })?;
+
+    let call = tokens[i + 1..]
+      .iter()
+      .find(|token| token.kind != TokenKind::Whitespace)
+      .is_some_and(|token| token.kind == TokenKind::ParenL);
+
+    Some((tokens[i], call))
+  }
+
+  /// The definition of the name `token` refers to
+  fn resolve<'src>(ast: &Ast<'src>, token: Token<'src>, call: bool) -> Option<Definition<'src>> {
+    let name = token.lexeme();
+
+    let functions = || {
+      ast.items.iter().find_map(|item| match item {
+        Item::Function(function) if function.name.lexeme() == name => {
+          Some(Definition::Function(function.name))
+        }
+        _ => None,
+      })
+    };
+
+    if call {
+      return functions().or_else(|| {
+        function::NAMES
+          .contains(&name)
+          .then_some(Definition::Builtin(name))
+      });
+    }
+
+    let recipes = || {
+      ast.items.iter().find_map(|item| match item {
+        Item::Alias(alias) if alias.name.lexeme() == name => Some(Definition::Alias(alias.name)),
+        Item::Recipe(recipe) if recipe.name() == name => Some(Definition::Recipe {
+          doc: recipe.doc().map(str::to_owned),
+          name: recipe.name,
+        }),
+        _ => None,
+      })
+    };
+
+    let recipe_reference = ast.items.iter().any(|item| match item {
+      Item::Alias(alias) => alias.target.offset == token.offset,
+      Item::Recipe(recipe) => recipe
+        .dependencies
+        .iter()
+        .any(|dependency| dependency.recipe.offset == token.offset),
+      _ => false,
+    });
+
+    if recipe_reference {
+      return recipes();
+    }
+
+    // Parameters are in scope in the item they belong to, which is the last
+    // item whose name comes before the identifier
+    let enclosing = ast
+      .items
+      .iter()
+      .rfind(|item| item_name(item).is_some_and(|name| name.offset <= token.offset));
+
+    let parameter = match enclosing {
+      Some(Item::Function(function)) => function
+        .parameters
+        .iter()
+        .find(|parameter| parameter.lexeme() == name)
+        .copied(),
+      Some(Item::Recipe(recipe)) => recipe
+        .parameters
+        .iter()
+        .find(|parameter| parameter.name.lexeme() == name)
+        .map(|parameter| parameter.name),
+      _ => None,
+    };
+
+    parameter
+      .map(Definition::Parameter)
+      .or_else(|| {
+        ast.items.iter().find_map(|item| match item {
+          Item::Assignment(assignment) if assignment.name.lexeme() == name => {
+            Some(Definition::Variable(assignment.name))
+          }
+          _ => None,
+        })
+      })
+      .or_else(recipes)
+      .or_else(functions)
+  }
+
+  /// The definition of the identifier at `offset` in `text`
+  fn lookup(text: &str, offset: usize) -> Option<Definition<'_>> {
+    let ast = Parser::parse(&Lexer::lex(text, &InterpolationDelimiters::scan(text)).ok()?).ok()?;
+    let (token, call) = Self::identifier(text, offset)?;
+    Self::resolve(&ast, token, call)
+  }
+
+  fn definition(&self, params: &Value) -> Value {
+    let Some((text, offset)) = self.document(params) else {
+      return Value::Null;
+    };
+
+    match Self::lookup(text, offset).and_then(|definition| definition.name()) {
+      Some(name) => json!({
+        "uri": params["textDocument"]["uri"],
+        "range": Self::range(text, name.offset, name.length),
+      }),
+      None => Value::Null,
+    }
+  }
+
+  fn hover(&self, params: &Value) -> Value {
+    let Some((text, offset)) = self.document(params) else {
+      return Value::Null;
+    };
+
+    let Some(definition) = Self::lookup(text, offset) else {
+      return Value::Null;
+    };
+
+    let mut contents = String::new();
+
+    if let Some(doc) = definition.doc() {
+      contents.push_str(doc);
+      contents.push_str("\n\n");
+    }
+
+    if let Some(name) = definition.name() {
+      contents.push_str("```just\n");
+      contents.push_str(text.lines().nth(name.line).unwrap_or_default().trim());
+      contents.push_str("\n```");
+    } else {
+      contents.push_str("Built-in function `");
+      contents.push_str(definition.lexeme());
+      contents.push('`');
+    }
+
+    json!({
+      "contents": {
+        "kind": "markdown",
+        "value": contents,
+      },
+    })
+  }
+
+  fn completion(&self, params: &Value) -> Value {
+    // Completion kinds from the LSP specification
+    const FUNCTION: u8 = 3;
+    const METHOD: u8 = 2;
+    const VARIABLE: u8 = 6;
+
+    let Some((text, _)) = self.document(params) else {
+      return json!([]);
+    };
+
+    let mut items = Vec::new();
+
+    if let Some(ast) = Lexer::lex(text, &InterpolationDelimiters::scan(text))
+      .ok()
+      .and_then(|tokens| Parser::parse(&tokens).ok())
+    {
+      for item in &ast.items {
+        match item {
+          Item::Alias(alias) => items.push(json!({
+            "label": alias.name.lexeme(),
+            "kind": METHOD,
+            "detail": format!("alias for {}", alias.target.lexeme()),
+          })),
+          Item::Assignment(assignment) => items.push(json!({
+            "label": assignment.name.lexeme(),
+            "kind": VARIABLE,
+          })),
+          Item::Function(function) => items.push(json!({
+            "label": function.name.lexeme(),
+            "kind": FUNCTION,
+          })),
+          Item::Recipe(recipe) => {
+            let mut completion = json!({
+              "label": recipe.name(),
+              "kind": METHOD,
+            });
+            if let Some(doc) = recipe.doc() {
+              completion["detail"] = json!(doc);
+            }
+            items.push(completion);
+          }
+          Item::Comment(_) | Item::Group(_) | Item::Set(_) => {}
+        }
+      }
+    }
+
+    for name in function::NAMES {
+      items.push(json!({
+        "label": name,
+        "kind": FUNCTION,
+        "detail": "built-in function",
+      }));
+    }
+
+    json!(items)
+  }
+}
+
+/// The name defined by `item`, if any
+fn item_name<'src>(item: &Item<'src>) -> Option<Name<'src>> {
+  match item {
+    Item::Alias(alias) => Some(alias.name),
+    Item::Assignment(assignment) => Some(assignment.name),
+    Item::Function(function) => Some(function.name),
+    Item::Recipe(recipe) => Some(recipe.name),
+    Item::Set(set) => Some(set.name),
+    Item::Comment(_) | Item::Group(_) => None,
+  }
+}
+
+/// What a name refers to
+enum Definition<'src> {
+  Alias(Name<'src>),
+  Builtin(&'src str),
+  Function(Name<'src>),
+  Parameter(Name<'src>),
+  Recipe {
+    doc: Option<String>,
+    name: Name<'src>,
+  },
+  Variable(Name<'src>),
+}
+
+impl<'src> Definition<'src> {
+  fn doc(&self) -> Option<&str> {
+    if let Self::Recipe { doc, .. } = self {
+      doc.as_deref()
+    } else {
+      None
+    }
+  }
+
+  fn lexeme(&self) -> &'src str {
+    match self {
+      Self::Builtin(name) => name,
+      _ => self.name().map(|name| name.lexeme()).unwrap_or_default(),
+    }
+  }
+
+  /// The name of the definition, which is `None` for built-in functions,
+  /// which aren't defined in the justfile
+  fn name(&self) -> Option<Name<'src>> {
+    match self {
+      Self::Alias(name)
+      | Self::Function(name)
+      | Self::Parameter(name)
+      | Self::Recipe { name, .. }
+      | Self::Variable(name) => Some(*name),
+      Self::Builtin(_) => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn message(value: &Value) -> String {
+    let content = value.to_string();
+    format!("Content-Length: {}\r\n\r\n{content}", content.len())
+  }
+
+  /// The messages the server writes in response to `requests`, after opening
+  /// a document with `text`
+  fn serve(text: &str, requests: &[Value]) -> Vec<Value> {
+    let mut input = message(&json!({
+      "jsonrpc": "2.0",
+      "method": "textDocument/didOpen",
+      "params": { "textDocument": { "uri": "file:///justfile", "text": text } },
+    }));
+
+    for request in requests {
+      input.push_str(&message(request));
+    }
+
+    let mut output = Vec::new();
+
+    LanguageServer::default()
+      .serve(&mut input.as_bytes(), &mut output)
+      .unwrap();
+
+    let mut output = output.as_slice();
+    let mut messages = Vec::new();
+
+    while let Some(message) = LanguageServer::read(&mut output).unwrap() {
+      messages.push(message);
+    }
+
+    messages
+  }
+
+  fn request(method: &str, line: usize, character: usize) -> Value {
+    json!({
+      "jsonrpc": "2.0",
+      "id": 1,
+      "method": method,
+      "params": {
+        "textDocument": { "uri": "file:///justfile" },
+        "position": { "line": line, "character": character },
+      },
+    })
+  }
+
+  fn range(line: usize, start: usize, end: usize) -> Value {
+    json!({
+      "start": { "line": line, "character": start },
+      "end": { "line": line, "character": end },
+    })
+  }
+
+  #[test]
+  fn diagnostics() {
+    let messages = serve("foo: bar\n", &[]);
+    assert_eq!(
+      messages[0]["params"]["diagnostics"],
+      json!([{
+        "range": range(0, 5, 8),
+        "severity": 1,
+        "source": "just",
+        "message": "Recipe `foo` has unknown dependency `bar`",
+      }]),
+    );
+  }
+
+  #[test]
+  fn no_diagnostics() {
+    let messages = serve("foo:\n", &[]);
+    assert_eq!(messages[0]["params"]["diagnostics"], json!([]));
+  }
+
+  #[test]
+  fn definition_of_dependency() {
+    let messages = serve(
+      "foo: bar\nbar:\n",
+      &[request("textDocument/definition", 0, 6)],
+    );
+    assert_eq!(messages[1]["result"]["range"], range(1, 0, 3));
+  }
+
+  #[test]
+  fn definition_of_parameter_shadows_variable() {
+    let messages = serve(
+      "x := 'a'\nfoo x:\n  echo {{x}}\n",
+      &[request("textDocument/definition", 2, 9)],
+    );
+    assert_eq!(messages[1]["result"]["range"], range(1, 4, 5));
+  }
+
+  #[test]
+  fn definition_of_variable() {
+    let messages = serve(
+      "foo:\n  echo {{x}}\nx := 'a'\n",
+      &[request("textDocument/definition", 1, 9)],
+    );
+    assert_eq!(messages[1]["result"]["range"], range(2, 0, 1));
+  }
+
+  #[test]
+  fn definition_of_unknown_name() {
+    let messages = serve(
+      "foo:\n  echo {{x}}\n",
+      &[request("textDocument/definition", 1, 9)],
+    );
+    assert_eq!(messages[1]["result"], Value::Null);
+  }
+
+  #[test]
+  fn hover_recipe() {
+    let messages = serve(
+      "# build it\nbuild:\nfoo: build\n",
+      &[request("textDocument/hover", 2, 6)],
+    );
+    assert_eq!(
+      messages[1]["result"]["contents"]["value"],
+      "build it\n\n```just\nbuild:\n```",
+    );
+  }
+
+  #[test]
+  fn hover_builtin_function() {
+    let messages = serve("x := env('A')\n", &[request("textDocument/hover", 0, 6)]);
+    assert_eq!(
+      messages[1]["result"]["contents"]["value"],
+      "Built-in function `env`",
+    );
+  }
+
+  #[test]
+  fn completion() {
+    let messages = serve(
+      "x := 'a'\nfoo:\n",
+      &[request("textDocument/completion", 0, 0)],
+    );
+    let labels = messages[1]["result"]
+      .as_array()
+      .unwrap()
+      .iter()
+      .map(|item| item["label"].as_str().unwrap())
+      .collect::<Vec<&str>>();
+    assert_eq!(&labels[..3], ["x", "foo", "absolute_path"]);
+    assert_eq!(labels.len(), 2 + function::NAMES.len());
+  }
+
+  #[test]
+  fn unknown_method() {
+    let messages = serve("", &[request("foo", 0, 0)]);
+    assert_eq!(messages[1]["error"]["code"], METHOD_NOT_FOUND);
+  }
+
+  #[test]
+  fn exit() {
+    let messages = serve(
+      "",
+      &[
+        json!({ "jsonrpc": "2.0", "method": "exit" }),
+        request("textDocument/hover", 0, 0),
+      ],
+    );
+    assert_eq!(messages.len(), 1);
+  }
+
+  #[test]
+  fn directives_are_masked() {
+    assert_eq!(
+      LanguageServer::mask_directives("!include foo\r\n# bar\nx := '!'\n"),
+      "            \r\n# bar\nx := '!'\n",
+    );
+  }
+
+  #[test]
+  fn positions_are_utf16() {
+    let text = "a\n😀b";
+    assert_eq!(
+      LanguageServer::position(text, 6),
+      json!({ "line": 1, "character": 2 }),
+    );
+    assert_eq!(
+      LanguageServer::offset(text, &json!({ "line": 1, "character": 2 })),
+      Some(6),
+    );
+    assert_eq!(
+      LanguageServer::offset(text, &json!({ "line": 2, "character": 0 })),
+      None,
+    );
+  }
+}
@@ -31,16 +31,22 @@ pub(crate) struct Lexer<'src> {
   interpolation_stack: Vec<Token<'src>>,
   /// Current open delimiters
   open_delimiters: Vec<(Delimiter, usize)>,
+  /// Delimiters of interpolations in recipe bodies
+  interpolation_delimiters: InterpolationDelimiters,
 }
 
 impl<'src> Lexer<'src> {
-  /// Lex `text`
-  pub(crate) fn lex(src: &'src str) -> CompileResult<Vec<Token<'src>>> {
-    Lexer::new(src).tokenize()
+  /// Lex `text`, with interpolations in recipe bodies delimited by
+  /// `interpolation_delimiters`
+  pub(crate) fn lex(
+    src: &'src str,
+    interpolation_delimiters: &InterpolationDelimiters,
+  ) -> CompileResult<'src, Vec<Token<'src>>> {
+    Lexer::new(src, interpolation_delimiters.clone()).tokenize()
   }
 
   /// Create a new Lexer to lex `text`
-  fn new(src: &'src str) -> Lexer<'src> {
+  fn new(src: &'src str, interpolation_delimiters: InterpolationDelimiters) -> Lexer<'src> {
     let mut chars = src.chars();
     let next = chars.next();
 
@@ -59,6 +65,7 @@ impl<'src> Lexer<'src> {
       recipe_body: false,
       interpolation_stack: Vec::new(),
       open_delimiters: Vec::new(),
+      interpolation_delimiters,
       chars,
       next,
       src,
@@ -221,11 +228,8 @@ impl<'src> Lexer<'src> {
     // The width of the error site to highlight depends on the kind of error:
     let length = match kind {
       UnterminatedString | UnterminatedBacktick => {
-        let kind = match StringKind::from_token_start(self.lexeme()) {
-          Some(kind) => kind,
-          None => {
-            return self.internal_error("Lexer::error: expected string or backtick token start")
-          }
+        let Some(kind) = StringKind::from_token_start(self.lexeme()) else {
+          return self.internal_error("Lexer::error: expected string or backtick token start");
         };
         kind.delimiter().len()
       }
@@ -249,19 +253,13 @@ impl<'src> Lexer<'src> {
     CompileError::new(interpolation_start, UnterminatedInterpolation)
   }
 
-  /// True if `text` could be an identifier
+  /// True if `text` could be an identifier, including a namespaced
+  /// identifier like `ci::lint`
   pub(crate) fn is_identifier(text: &str) -> bool {
-    if !text.chars().next().map_or(false, Self::is_identifier_start) {
-      return false;
-    }
-
-    for c in text.chars().skip(1) {
-      if !Self::is_identifier_continue(c) {
-        return false;
-      }
-    }
-
-    true
+    text.split("::").all(|part| {
+      part.chars().next().is_some_and(Self::is_identifier_start)
+        && part.chars().skip(1).all(Self::is_identifier_continue)
+    })
   }
 
   /// True if `c` can be the first character of an identifier
@@ -470,7 +468,7 @@ impl<'src> Lexer<'src> {
   fn lex_normal(&mut self, start: char) -> CompileResult<'src, ()> {
     match start {
       ' ' | '\t' => self.lex_whitespace(),
-      '!' => self.lex_digraph('!', '=', BangEquals),
+      '!' => self.lex_choices('!', &[('=', BangEquals)], Bang),
       '#' => self.lex_comment(),
       '$' => self.lex_single(Dollar),
       '&' => self.lex_digraph('&', '&', AmpersandAmpersand),
@@ -490,6 +488,7 @@ impl<'src> Lexer<'src> {
       ']' => self.lex_delimiter(BracketR),
       '`' | '"' | '\'' => self.lex_string(),
       '{' => self.lex_delimiter(BraceL),
+      '|' => self.lex_digraph('|', '|', BarBar),
       '}' => self.lex_delimiter(BraceR),
       _ if Self::is_identifier_start(start) => self.lex_identifier(),
       _ => {
@@ -505,7 +504,7 @@ impl<'src> Lexer<'src> {
     interpolation_start: Token<'src>,
     start: char,
   ) -> CompileResult<'src, ()> {
-    if self.rest_starts_with("}}") {
+    if self.rest_starts_with(&self.interpolation_delimiters.close) {
       // end current interpolation
       if self.interpolation_stack.pop().is_none() {
         self.advance()?;
@@ -515,7 +514,7 @@ impl<'src> Lexer<'src> {
         ));
       }
       // Emit interpolation end token
-      self.lex_double(InterpolationEnd)
+      self.lex_interpolation_delimiter(InterpolationEnd)
     } else if self.at_eol_or_eof() {
       // Return unterminated interpolation error that highlights the opening
       // {{
@@ -537,9 +536,11 @@ impl<'src> Lexer<'src> {
 
     use Terminator::*;
 
+    let escaped_open = self.interpolation_delimiters.escaped_open();
+
     let terminator = loop {
-      if self.rest_starts_with("{{{{") {
-        self.skip(4)?;
+      if self.rest_starts_with(&escaped_open) {
+        self.skip(escaped_open.chars().count())?;
         continue;
       }
 
@@ -551,7 +552,7 @@ impl<'src> Lexer<'src> {
         break NewlineCarriageReturn;
       }
 
-      if self.rest_starts_with("{{") {
+      if self.rest_starts_with(&self.interpolation_delimiters.open) {
         break Interpolation;
       }
 
@@ -571,7 +572,7 @@ impl<'src> Lexer<'src> {
       Newline => self.lex_single(Eol),
       NewlineCarriageReturn => self.lex_double(Eol),
       Interpolation => {
-        self.lex_double(InterpolationStart)?;
+        self.lex_interpolation_delimiter(InterpolationStart)?;
         self
           .interpolation_stack
           .push(self.tokens[self.tokens.len() - 1]);
@@ -604,6 +605,18 @@ impl<'src> Lexer<'src> {
     Ok(())
   }
 
+  /// Lex an interpolation start or end token, whose lexeme is the
+  /// corresponding interpolation delimiter
+  fn lex_interpolation_delimiter(&mut self, kind: TokenKind) -> CompileResult<'src, ()> {
+    let delimiter = match kind {
+      InterpolationStart => &self.interpolation_delimiters.open,
+      _ => &self.interpolation_delimiters.close,
+    };
+    self.skip(delimiter.chars().count())?;
+    self.token(kind);
+    Ok(())
+  }
+
   /// Lex a double-character token of kind `then` if the second character of
   /// that token would be `second`, otherwise lex a single-character token of
   /// kind `otherwise`
@@ -756,16 +769,27 @@ impl<'src> Lexer<'src> {
     Ok(())
   }
 
-  /// Lex name: [a-zA-Z_][a-zA-Z0-9_]*
+  /// Lex name: [a-zA-Z_][a-zA-Z0-9_]*, optionally followed by further names
+  /// separated by `::`, as in the names of recipes and variables in files
+  /// included into a namespace
   fn lex_identifier(&mut self) -> CompileResult<'src, ()> {
     self.advance()?;
 
-    while let Some(c) = self.next {
-      if !Self::is_identifier_continue(c) {
-        break;
+    loop {
+      match self.next {
+        Some(c) if Self::is_identifier_continue(c) => self.advance()?,
+        Some(':')
+          if self
+            .rest()
+            .strip_prefix("::")
+            .and_then(|rest| rest.chars().next())
+            .is_some_and(Self::is_identifier_start) =>
+        {
+          self.advance()?;
+          self.advance()?;
+        }
+        _ => break,
       }
-
-      self.advance()?;
     }
 
     self.token(Identifier);
@@ -803,9 +827,7 @@ impl<'src> Lexer<'src> {
   /// Cooked string: "[^"]*" # also processes escape sequences
   /// Raw string:    '[^']*'
   fn lex_string(&mut self) -> CompileResult<'src, ()> {
-    let kind = if let Some(kind) = StringKind::from_token_start(self.rest()) {
-      kind
-    } else {
+    let Some(kind) = StringKind::from_token_start(self.rest()) else {
       self.advance()?;
       return Err(self.internal_error("Lexer::lex_string: invalid string start"));
     };
@@ -893,7 +915,7 @@ mod tests {
       text.to_owned()
     };
 
-    let have = Lexer::lex(&text).unwrap();
+    let have = Lexer::lex(&text, &InterpolationDelimiters::scan(&text)).unwrap();
 
     let have_kinds = have
       .iter()
@@ -942,7 +964,9 @@ mod tests {
       AmpersandAmpersand => "&&",
       Asterisk => "*",
       At => "@",
+      Bang => "!",
       BangEquals => "!=",
+      BarBar => "||",
       BraceL => "{",
       BraceR => "}",
       BracketL => "[",
@@ -1000,7 +1024,7 @@ mod tests {
     length: usize,
     kind: CompileErrorKind,
   ) {
-    match Lexer::lex(src) {
+    match Lexer::lex(src, &InterpolationDelimiters::default()) {
       Ok(_) => panic!("Lexing succeeded but expected"),
       Err(have) => {
         let want = CompileError {
@@ -1025,6 +1049,18 @@ mod tests {
     tokens: (Identifier:"foo"),
   }
 
+  test! {
+    name:   name_namespaced,
+    text:   "ci::lint-all::x",
+    tokens: (Identifier:"ci::lint-all::x"),
+  }
+
+  test! {
+    name:   name_followed_by_colons,
+    text:   "foo::",
+    tokens: (Identifier:"foo", Colon, Colon),
+  }
+
   test! {
     name:   comment,
     text:   "# hello",
@@ -1079,6 +1115,24 @@ mod tests {
     tokens: (AmpersandAmpersand),
   }
 
+  test! {
+    name:   bang,
+    text:   "!",
+    tokens: (Bang),
+  }
+
+  test! {
+    name:   bang_bang_equals,
+    text:   "!!=",
+    tokens: (Bang, BangEquals),
+  }
+
+  test! {
+    name:   bar_bar,
+    text:   "||",
+    tokens: (BarBar),
+  }
+
   test! {
     name:   equals,
     text:   "=",
@@ -1486,6 +1540,35 @@ mod tests {
     ),
   }
 
+  test! {
+    name: interpolation_custom_delimiters,
+    text: "set interpolation-delimiters := ['<%', '%>']\nhello:\n echo <%<% {{a}} <%a%>",
+    tokens: (
+      Identifier:"set",
+      Whitespace,
+      Identifier:"interpolation-delimiters",
+      Whitespace,
+      ColonEquals,
+      Whitespace,
+      BracketL,
+      StringToken:"'<%'",
+      Comma,
+      Whitespace,
+      StringToken:"'%>'",
+      BracketR,
+      Eol,
+      Identifier:"hello",
+      Colon,
+      Eol,
+      Indent:" ",
+      Text:"echo <%<% {{a}} ",
+      InterpolationStart:"<%",
+      Identifier:"a",
+      InterpolationEnd:"%>",
+      Dedent,
+    ),
+  }
+
   test! {
     name: interpolation_expression,
     text: "hello:\n echo {{`echo hello` + `echo goodbye`}}",
@@ -2220,16 +2303,6 @@ mod tests {
     kind:   UnterminatedInterpolation,
   }
 
-  error! {
-    name:   unexpected_character_after_bang,
-    input:  "!{",
-    offset: 1,
-    line:   0,
-    column: 1,
-    width:  1,
-    kind:   UnexpectedCharacter { expected: '=' },
-  }
-
   error! {
     name:   mismatched_closing_brace,
     input:  "(]",
@@ -2245,26 +2318,26 @@ mod tests {
   }
 
   error! {
-    name:   bang_eof,
-    input:  "!",
+    name:   bar_eof,
+    input:  "|",
     offset: 1,
     line:   0,
     column: 1,
     width:  0,
     kind:   UnexpectedEndOfToken {
-      expected: '=',
+      expected: '|',
     },
   }
 
   error! {
-    name:   bang_unexpected,
-    input:  "!%",
+    name:   bar_unexpected,
+    input:  "|%",
     offset: 1,
     line:   0,
     column: 1,
     width:  1,
     kind:   UnexpectedCharacter {
-      expected: '=',
+      expected: '|',
     },
   }
 
@@ -2293,7 +2366,9 @@ mod tests {
 
   #[test]
   fn presume_error() {
-    let compile_error = Lexer::new("!").presume('-').unwrap_err();
+    let compile_error = Lexer::new("!", InterpolationDelimiters::default())
+      .presume('-')
+      .unwrap_err();
     assert_matches!(
       compile_error.token,
       Token {
@@ -2311,7 +2386,7 @@ mod tests {
     );
 
     assert_eq!(
-      Error::Compile { compile_error }
+      Error::from(compile_error)
         .color_display(Color::never())
         .to_string(),
       "error: Internal error, this may indicate a bug in just: \
@@ -11,28 +11,43 @@
 pub(crate) use {
   crate::{
     alias::Alias, analyzer::Analyzer, assignment::Assignment,
-    assignment_resolver::AssignmentResolver, ast::Ast, attribute::Attribute, binding::Binding,
-    color::Color, color_display::ColorDisplay, command_ext::CommandExt,
+    assignment_resolver::AssignmentResolver, ast::Ast, attribute::Attribute,
+    attribute::AttributeDiscriminant, attribute_override::AttributeOverride, binding::Binding,
+    color::Color, color_display::ColorDisplay, command_ext::CommandExt, command_log::CommandLog,
     compile_error::CompileError, compile_error_kind::CompileErrorKind, compiler::Compiler,
-    conditional_operator::ConditionalOperator, config::Config, config_error::ConfigError,
-    count::Count, delimiter::Delimiter, dependency::Dependency, dump_format::DumpFormat,
-    enclosure::Enclosure, error::Error, evaluator::Evaluator, expression::Expression,
-    fragment::Fragment, function::Function, function_context::FunctionContext,
-    interrupt_guard::InterruptGuard, interrupt_handler::InterruptHandler, item::Item,
-    justfile::Justfile, keyed::Keyed, keyword::Keyword, lexer::Lexer, line::Line, list::List,
-    load_dotenv::load_dotenv, loader::Loader, name::Name, ordinal::Ordinal, output::output,
-    output_error::OutputError, parameter::Parameter, parameter_kind::ParameterKind, parser::Parser,
-    platform::Platform, platform_interface::PlatformInterface, position::Position,
-    positional::Positional, range_ext::RangeExt, recipe::Recipe, recipe_context::RecipeContext,
-    recipe_resolver::RecipeResolver, scope::Scope, search::Search, search_config::SearchConfig,
-    search_error::SearchError, set::Set, setting::Setting, settings::Settings, shebang::Shebang,
-    shell::Shell, show_whitespace::ShowWhitespace, string_kind::StringKind,
-    string_literal::StringLiteral, subcommand::Subcommand, suggestion::Suggestion, table::Table,
-    thunk::Thunk, token::Token, token_kind::TokenKind, unresolved_dependency::UnresolvedDependency,
-    unresolved_recipe::UnresolvedRecipe, use_color::UseColor, variables::Variables,
-    verbosity::Verbosity, warning::Warning,
+    completion::Completion, condition::Condition, conditional_operator::ConditionalOperator,
+    config::Config, config_error::ConfigError, count::Count, data_path::lookup_data_path,
+    delimiter::Delimiter, dependency::Dependency, dump_format::DumpFormat, echo_mode::EchoMode,
+    enclosure::Enclosure, env_hook::env_hook, env_var_name::env_var_name,
+    env_var_name::is_env_var_name, error::Error, error_format::ErrorFormat, evaluator::Evaluator,
+    exit_status_mode::ExitStatusMode, export_tasks::jetbrains_run_configurations,
+    export_tasks::vscode_tasks, expression::Expression, fragment::Fragment, function::Function,
+    function_context::FunctionContext, function_resolver::FunctionResolver,
+    group_declaration::GroupDeclaration, highlight::highlight, include::Include,
+    interpolation_delimiters::InterpolationDelimiters, interrupt_guard::InterruptGuard,
+    interrupt_handler::InterruptHandler, interval::Interval, item::Item, justfile::Justfile,
+    keyed::Keyed, keyword::Keyword, language_server::LanguageServer, lexer::Lexer,
+    library::Library, line::Line, line_endings::keep_line_endings, list::List,
+    load_dotenv::dotenv_key, load_dotenv::load_dotenv, load_dotenv::load_from_filenames,
+    loader::Loader, login_env::login_env, migration::Migration, monitor::Capture, monitor::Monitor,
+    name::Name, namespace::namespace, ordinal::Ordinal, output::output, output_error::OutputError,
+    parameter::Parameter, parameter_kind::ParameterKind, parser::Parser, platform::Platform,
+    platform_interface::PlatformInterface, position::Position, positional::Positional,
+    range_ext::RangeExt, recipe::Recipe, recipe_context::RecipeContext,
+    recipe_resolver::RecipeResolver, remote::Remote, report::Report,
+    required_version::check_required_version, required_version::scan_required_version,
+    scope::Scope, search::Search, search_config::SearchConfig, search_error::SearchError, set::Set,
+    setting::Setting, settings::Settings, shebang::Shebang, shell::Shell,
+    show_whitespace::ShowWhitespace, string_kind::StringKind, string_literal::StringLiteral,
+    subcommand::Subcommand, suggestion::Suggestion, syntax_tree::syntax_tree, table::Table,
+    template::Template, theme::Theme, thunk::Thunk, timestamps::Timestamps, token::Token,
+    token_kind::TokenKind, trust::Trust, unresolved_dependency::UnresolvedDependency,
+    unresolved_recipe::UnresolvedRecipe, use_color::UseColor, user_function::UserFunction,
+    variables::Variables, verbosity::Verbosity, warning::Warning, windows_shebang::WindowsShebang,
+    workspace_config::WorkspaceConfig,
   },
   std::{
+    cell::RefCell,
     cmp,
     collections::{BTreeMap, BTreeSet},
     env,
@@ -46,8 +61,9 @@ pub(crate) use {
     path::{self, Path, PathBuf},
     process::{self, Command, ExitStatus, Stdio},
     rc::Rc,
-    str::{self, Chars},
+    str::{self, Chars, FromStr},
     sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
     vec,
   },
   {
@@ -55,15 +71,14 @@ pub(crate) use {
     derivative::Derivative,
     edit_distance::edit_distance,
     lexiclean::Lexiclean,
-    libc::EXIT_FAILURE,
     log::{info, warn},
     regex::Regex,
     serde::{
       ser::{SerializeMap, SerializeSeq},
-      Serialize, Serializer,
+      Deserialize, Serialize, Serializer,
     },
     snafu::{ResultExt, Snafu},
-    strum::{Display, EnumString, IntoStaticStr},
+    strum::{Display, EnumDiscriminants, EnumString, IntoStaticStr},
     typed_arena::Arena,
     unicode_width::{UnicodeWidthChar, UnicodeWidthStr},
   },
@@ -83,6 +98,9 @@ pub(crate) type ConfigResult<T> = Result<T, ConfigError>;
 pub(crate) type RunResult<'a, T> = Result<T, Error<'a>>;
 pub(crate) type SearchResult<T> = Result<T, SearchError>;
 
+// `libc` doesn't define this on every target, including WebAssembly
+const EXIT_FAILURE: i32 = 1;
+
 #[cfg(test)]
 #[macro_use]
 pub mod testing;
@@ -103,46 +121,75 @@ pub mod fuzzing;
 #[doc(hidden)]
 pub mod summary;
 
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
 mod alias;
 mod analyzer;
 mod assignment;
 mod assignment_resolver;
 mod ast;
 mod attribute;
+mod attribute_override;
 mod binding;
 mod color;
 mod color_display;
 mod command_ext;
+mod command_log;
 mod compile_error;
 mod compile_error_kind;
 mod compiler;
+mod completion;
 mod completions;
+mod condition;
 mod conditional_operator;
 mod config;
 mod config_error;
 mod count;
+mod data_path;
 mod delimiter;
 mod dependency;
+mod documentation;
 mod dump_format;
+mod echo_mode;
 mod enclosure;
+mod env_hook;
+mod env_var_name;
 mod error;
+mod error_format;
 mod evaluator;
+mod exit_status_mode;
+mod export_script;
+mod export_tasks;
 mod expression;
 mod fragment;
 mod function;
 mod function_context;
+mod function_resolver;
+mod group_declaration;
+mod highlight;
+mod include;
+mod interpolation_delimiters;
 mod interrupt_guard;
 mod interrupt_handler;
+mod interval;
 mod item;
 mod justfile;
 mod keyed;
 mod keyword;
+mod language_server;
 mod lexer;
+mod library;
 mod line;
+mod line_endings;
 mod list;
 mod load_dotenv;
 mod loader;
+mod login_env;
+mod migration;
+mod monitor;
 mod name;
+mod namespace;
 mod ordinal;
 mod output;
 mod output_error;
@@ -157,6 +204,9 @@ mod range_ext;
 mod recipe;
 mod recipe_context;
 mod recipe_resolver;
+mod remote;
+mod report;
+mod required_version;
 mod run;
 mod scope;
 mod search;
@@ -172,14 +222,22 @@ mod string_kind;
 mod string_literal;
 mod subcommand;
 mod suggestion;
+mod syntax_tree;
 mod table;
+mod template;
+mod theme;
 mod thunk;
+mod timestamps;
 mod token;
 mod token_kind;
+mod trust;
 mod unindent;
 mod unresolved_dependency;
 mod unresolved_recipe;
 mod use_color;
+mod user_function;
 mod variables;
 mod verbosity;
 mod warning;
+mod windows_shebang;
+mod workspace_config;
@@ -0,0 +1,322 @@
+use {
+  super::*,
+  sha2::{Digest, Sha256},
+};
+
+/// A recipe library, fetched with `git` from a source written as
+/// `github:ORG/REPO@REF` or `git:URL@REF`, where `@REF` is an optional tag or
+/// branch
+///
+/// Installed libraries are stored in the library directory under the SHA-256
+/// hash of their contents, and the libraries used by a project are pinned, by
+/// source and hash, in a manifest next to its justfile. `!include lib('NAME')`
+/// includes the file `NAME.just` from the first library in the manifest which
+/// contains it.
+pub(crate) struct Library<'a> {
+  reference: Option<&'a str>,
+  source: &'a str,
+  url: String,
+}
+
+impl<'a> Library<'a> {
+  pub(crate) const MANIFEST_FILENAME: &'static str = "just-libs.lock";
+
+  pub(crate) fn parse(source: &'a str) -> RunResult<'static, Self> {
+    let invalid = || Error::LibrarySource {
+      library: source.to_owned(),
+    };
+
+    let (location, reference) = match source.rsplit_once('@') {
+      Some((location, reference)) if !reference.is_empty() && !reference.contains(['/', ':']) => {
+        (location, Some(reference))
+      }
+      _ => (source, None),
+    };
+
+    let url = if let Some(repository) = location.strip_prefix("github:") {
+      match repository.split_once('/') {
+        Some((org, repo)) if !org.is_empty() && !repo.is_empty() && !repo.contains('/') => {
+          format!("https://github.com/{org}/{repo}.git")
+        }
+        _ => return Err(invalid()),
+      }
+    } else if let Some(url) = location.strip_prefix("git:") {
+      if url.is_empty() {
+        return Err(invalid());
+      }
+      url.to_owned()
+    } else {
+      return Err(invalid());
+    };
+
+    Ok(Self {
+      reference,
+      source,
+      url,
+    })
+  }
+
+  /// Fetch the library into the library directory and pin it in the manifest
+  /// next to `justfile`, replacing any existing entry with the same location
+  pub(crate) fn install(&self, justfile: &Path) -> RunResult<'static, PathBuf> {
+    let directory = Self::directory()?;
+
+    fs::create_dir_all(&directory).map_err(|io_error| Error::LibraryIo {
+      path: directory.clone(),
+      io_error,
+    })?;
+
+    let tempdir = tempfile::Builder::new()
+      .prefix(".install")
+      .tempdir_in(&directory)
+      .map_err(|io_error| Error::LibraryIo {
+        path: directory.clone(),
+        io_error,
+      })?;
+
+    let checkout = tempdir.path().join("checkout");
+
+    let mut command = Command::new("git");
+
+    command.args([
+      "-c",
+      "advice.detachedHead=false",
+      "clone",
+      "--quiet",
+      "--depth",
+      "1",
+    ]);
+
+    if let Some(reference) = self.reference {
+      command.args(["--branch", reference]);
+    }
+
+    command.arg(&self.url).arg(&checkout);
+
+    command.stdin(Stdio::null());
+
+    command.stderr(Stdio::inherit());
+
+    InterruptHandler::guard(|| output(command)).map_err(|output_error| Error::LibraryFetch {
+      library: self.source.to_owned(),
+      output_error,
+    })?;
+
+    let io_error = |io_error| Error::LibraryIo {
+      path: checkout.clone(),
+      io_error,
+    };
+
+    fs::remove_dir_all(checkout.join(".git")).map_err(io_error)?;
+
+    let hash = Self::hash(&checkout).map_err(io_error)?;
+
+    let path = directory.join(&hash);
+
+    if !path.is_dir() {
+      fs::rename(&checkout, &path).map_err(io_error)?;
+    }
+
+    self.pin(justfile, &hash)?;
+
+    Ok(path)
+  }
+
+  fn pin(&self, justfile: &Path, hash: &str) -> RunResult<'static, ()> {
+    let manifest = justfile.with_file_name(Self::MANIFEST_FILENAME);
+
+    let io_error = |io_error| Error::LibraryIo {
+      path: manifest.clone(),
+      io_error,
+    };
+
+    let existing = match fs::read_to_string(&manifest) {
+      Ok(existing) => existing,
+      Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+      Err(error) => return Err(io_error(error)),
+    };
+
+    let location = |source: &str| {
+      Library::parse(source).map_or_else(|_| source.to_owned(), |library| library.url)
+    };
+
+    let entries = Self::entries(&existing)
+      .filter(|(source, _)| location(source) != self.url)
+      .chain(iter::once((self.source, hash)));
+
+    let mut contents = String::new();
+
+    for (source, hash) in entries {
+      contents.push_str(source);
+      contents.push(' ');
+      contents.push_str(hash);
+      contents.push('\n');
+    }
+
+    fs::write(&manifest, contents).map_err(io_error)
+  }
+
+  /// Find the path of the file in an installed library which is included by
+  /// `!include lib('NAME')` in a justfile in `directory`, checking that the
+  /// library's contents still match the hash it was pinned with
+  pub(crate) fn resolve(name: &str, directory: &Path) -> RunResult<'static, PathBuf> {
+    if !Self::is_valid_name(name) {
+      return Err(Error::LibraryName {
+        name: name.to_owned(),
+      });
+    }
+
+    let not_found = |manifest: Option<PathBuf>| Error::LibraryNotFound {
+      name: name.to_owned(),
+      manifest,
+    };
+
+    let manifest = directory
+      .ancestors()
+      .map(|directory| directory.join(Self::MANIFEST_FILENAME))
+      .find(|path| path.is_file())
+      .ok_or_else(|| not_found(None))?;
+
+    let entries = fs::read_to_string(&manifest).map_err(|io_error| Error::LibraryIo {
+      path: manifest.clone(),
+      io_error,
+    })?;
+
+    let libraries = Self::directory()?;
+
+    let filename = format!("{name}.just");
+
+    let (source, hash, path) = Self::entries(&entries)
+      .filter(|(_, hash)| hash.chars().all(|c| c.is_ascii_hexdigit()))
+      .map(|(source, hash)| (source, hash, libraries.join(hash).join(&filename)))
+      .find(|(_, _, path)| path.is_file())
+      .ok_or_else(|| not_found(Some(manifest.clone())))?;
+
+    let library = libraries.join(hash);
+
+    let actual = Self::hash(&library).map_err(|io_error| Error::LibraryIo {
+      path: library.clone(),
+      io_error,
+    })?;
+
+    if actual != hash {
+      return Err(Error::LibraryHash {
+        library: source.to_owned(),
+        manifest,
+        path: library,
+      });
+    }
+
+    Ok(path)
+  }
+
+  /// Whether `name` names a file in a library, rather than a path which could
+  /// escape it
+  fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['/', '\\', ':']) && !name.contains("..")
+  }
+
+  /// The `(source, hash)` pairs in a manifest, ignoring blank lines and
+  /// comments
+  fn entries(manifest: &str) -> impl Iterator<Item = (&str, &str)> + '_ {
+    manifest
+      .lines()
+      .map(str::trim)
+      .filter(|line| !line.is_empty() && !line.starts_with('#'))
+      .filter_map(|line| line.split_once(char::is_whitespace))
+      .map(|(source, hash)| (source, hash.trim()))
+  }
+
+  /// The directory libraries are installed in, which is `$JUST_LIB_DIR` if
+  /// set, and otherwise `just/libs` in `$XDG_DATA_HOME` or `~/.local/share`
+  fn directory() -> RunResult<'static, PathBuf> {
+    if let Some(directory) = env::var_os("JUST_LIB_DIR") {
+      return Ok(directory.into());
+    }
+
+    let data = env::var_os("XDG_DATA_HOME")
+      .filter(|data| !data.is_empty())
+      .map(PathBuf::from)
+      .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))
+      .ok_or(Error::LibraryDirectory)?;
+
+    Ok(data.join("just").join("libs"))
+  }
+
+  /// Hash the relative paths and contents of the files in `directory`
+  fn hash(directory: &Path) -> io::Result<String> {
+    let mut files = Vec::new();
+    let mut stack = vec![directory.to_owned()];
+
+    while let Some(path) = stack.pop() {
+      for entry in fs::read_dir(path)? {
+        let path = entry?.path();
+        if path.is_dir() {
+          stack.push(path);
+        } else {
+          files.push(path);
+        }
+      }
+    }
+
+    files.sort();
+
+    let mut hasher = Sha256::new();
+
+    for file in files {
+      let relative = file.strip_prefix(directory).unwrap_or(&file);
+      hasher.update(relative.to_string_lossy().replace('\\', "/").as_bytes());
+      hasher.update([0]);
+      hasher.update(fs::read(&file)?);
+      hasher.update([0]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_github() {
+    let library = Library::parse("github:org/just-lib@v1").unwrap();
+    assert_eq!(library.url, "https://github.com/org/just-lib.git");
+    assert_eq!(library.reference, Some("v1"));
+  }
+
+  #[test]
+  fn parse_git_without_reference() {
+    let library = Library::parse("git:git@example.com:org/lib.git").unwrap();
+    assert_eq!(library.url, "git@example.com:org/lib.git");
+    assert_eq!(library.reference, None);
+  }
+
+  #[test]
+  fn parse_invalid() {
+    assert!(Library::parse("org/just-lib@v1").is_err());
+    assert!(Library::parse("github:just-lib").is_err());
+    assert!(Library::parse("git:").is_err());
+  }
+
+  #[test]
+  fn valid_names() {
+    assert!(Library::is_valid_name("rust"));
+    assert!(Library::is_valid_name("rust.ci"));
+    assert!(!Library::is_valid_name(""));
+    assert!(!Library::is_valid_name("../rust"));
+    assert!(!Library::is_valid_name(".."));
+    assert!(!Library::is_valid_name("ci/rust"));
+    assert!(!Library::is_valid_name("ci\\rust"));
+    assert!(!Library::is_valid_name("C:rust"));
+  }
+
+  #[test]
+  fn entries() {
+    assert_eq!(
+      Library::entries("# comment\n\ngithub:a/b@v1 abc\ngit:c  def\n").collect::<Vec<_>>(),
+      [("github:a/b@v1", "abc"), ("git:c", "def")],
+    );
+  }
+}
@@ -0,0 +1,27 @@
+/// Give `text`, generated from the contents of `src`, the line endings of
+/// `src`, which are CRLF if its first line ends with one
+pub(crate) fn keep_line_endings(src: &str, text: String) -> String {
+  if src.find('\n').is_some_and(|i| src[..i].ends_with('\r')) {
+    text.replace("\r\n", "\n").replace('\n', "\r\n")
+  } else {
+    text
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn lf() {
+    assert_eq!(keep_line_endings("a\nb\r\n", "a\nb\n".into()), "a\nb\n");
+  }
+
+  #[test]
+  fn crlf() {
+    assert_eq!(
+      keep_line_endings("a\r\nb\n", "a\nb\r\n".into()),
+      "a\r\nb\r\n",
+    );
+  }
+}
@@ -1,6 +1,16 @@
 use super::*;
 
 const DEFAULT_DOTENV_FILENAME: &str = ".env";
+const DOTENV_NAMESPACE: &str = "dotenv::";
+
+/// The key referred to by `variable` if it is in the `dotenv` namespace, as
+/// in `dotenv::KEY`, which refers to the value of `KEY` loaded from an
+/// environment file
+pub(crate) fn dotenv_key(variable: &str) -> Option<&str> {
+  variable
+    .strip_prefix(DOTENV_NAMESPACE)
+    .filter(|key| !key.contains("::"))
+}
 
 pub(crate) fn load_dotenv(
   config: &Config,
@@ -17,7 +27,12 @@ pub(crate) fn load_dotenv(
     .as_ref()
     .or(settings.dotenv_path.as_ref());
 
-  if !settings.dotenv_load.unwrap_or(false) && dotenv_filename.is_none() && dotenv_path.is_none() {
+  if !settings.dotenv_load.unwrap_or(false)
+    && !settings.dotenv_required
+    && dotenv_filename.is_none()
+    && dotenv_path.is_none()
+    && settings.dotenv_filenames.is_empty()
+  {
     return Ok(BTreeMap::new());
   }
 
@@ -25,16 +40,104 @@ pub(crate) fn load_dotenv(
     return load_from_file(path);
   }
 
+  if config.dotenv_filename.is_none() && !settings.dotenv_filenames.is_empty() {
+    return load_from_filenames(
+      settings.dotenv_filenames.iter().map(String::as_str),
+      working_directory,
+      settings.dotenv_required,
+    );
+  }
+
   let filename = dotenv_filename.map_or(DEFAULT_DOTENV_FILENAME, |s| s.as_str());
 
-  for directory in working_directory.ancestors() {
-    let path = directory.join(filename);
-    if path.is_file() {
-      return load_from_file(&path);
+  match find(filename, working_directory, settings.dotenv_required)? {
+    Some(path) => load_from_file(&path),
+    None => Ok(BTreeMap::new()),
+  }
+}
+
+/// Load each of `filenames` which can be found, in order, so that variables
+/// in later files override those in earlier ones. Filenames may refer to
+/// environment variables as `$NAME` or `${NAME}`, and are skipped if those
+/// variables are not set. If `primary_required` is true, the first file is
+/// the primary environment file, and is an error if it cannot be found, while
+/// the others only override it if present.
+pub(crate) fn load_from_filenames<'a>(
+  filenames: impl IntoIterator<Item = &'a str>,
+  working_directory: &Path,
+  primary_required: bool,
+) -> RunResult<'static, BTreeMap<String, String>> {
+  let mut dotenv = BTreeMap::new();
+
+  for (i, filename) in filenames.into_iter().enumerate() {
+    let Some(filename) = expand(filename) else {
+      continue;
+    };
+
+    if let Some(path) = find(&filename, working_directory, primary_required && i == 0)? {
+      dotenv.extend(load_from_file(&path)?);
     }
   }
 
-  Ok(BTreeMap::new())
+  Ok(dotenv)
+}
+
+/// Find the file named `filename` in `working_directory` or the nearest of
+/// its ancestors, returning an error if it cannot be found and `required` is
+/// true
+fn find(
+  filename: &str,
+  working_directory: &Path,
+  required: bool,
+) -> RunResult<'static, Option<PathBuf>> {
+  let path = working_directory
+    .ancestors()
+    .map(|directory| directory.join(filename))
+    .find(|path| path.is_file());
+
+  if path.is_none() && required {
+    return Err(Error::DotenvRequired {
+      path: working_directory.join(filename),
+    });
+  }
+
+  Ok(path)
+}
+
+/// Replace references to environment variables in `filename`, returning
+/// `None` if any of them are not set
+fn expand(filename: &str) -> Option<String> {
+  let mut expanded = String::new();
+  let mut rest = filename;
+
+  while let Some(i) = rest.find('$') {
+    expanded.push_str(&rest[..i]);
+    rest = &rest[i + 1..];
+
+    let (name, after) = if let Some(braced) = rest.strip_prefix('{') {
+      match braced.find('}') {
+        Some(end) => (&braced[..end], &braced[end + 1..]),
+        None => ("", rest),
+      }
+    } else {
+      let end = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(rest.len());
+      (&rest[..end], &rest[end..])
+    };
+
+    if name.is_empty() {
+      expanded.push('$');
+    } else {
+      expanded.push_str(&env::var(name).ok()?);
+    }
+
+    rest = after;
+  }
+
+  expanded.push_str(rest);
+
+  Some(expanded)
 }
 
 fn load_from_file(path: &Path) -> RunResult<'static, BTreeMap<String, String>> {
@@ -48,3 +151,28 @@ fn load_from_file(path: &Path) -> RunResult<'static, BTreeMap<String, String>> {
   }
   Ok(dotenv)
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn expand_without_variables() {
+    assert_eq!(expand(".env.local").unwrap(), ".env.local");
+    assert_eq!(expand(".env$").unwrap(), ".env$");
+    assert_eq!(expand(".env.$-").unwrap(), ".env.$-");
+  }
+
+  #[test]
+  fn expand_unset_variable() {
+    assert_eq!(expand(".env.$JUST_TEST_UNSET_VARIABLE"), None);
+    assert_eq!(expand(".env.${JUST_TEST_UNSET_VARIABLE}"), None);
+  }
+
+  #[test]
+  fn expand_set_variable() {
+    let path = env::var("PATH").unwrap();
+    assert_eq!(expand("$PATH.env").unwrap(), format!("{path}.env"));
+    assert_eq!(expand("${PATH}x").unwrap(), format!("{path}x"));
+  }
+}
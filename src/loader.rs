@@ -1,5 +1,5 @@
 use super::*;
-use std::collections::HashSet;
+use std::{cell::RefCell, collections::HashSet, io::Read};
 
 struct LinesWithEndings<'a> {
   input: &'a str,
@@ -25,79 +25,361 @@ impl<'a> Iterator for LinesWithEndings<'a> {
   }
 }
 
+/// The file and zero-based line which each line of a loaded source came from
+type Origins = Vec<(PathBuf, usize)>;
+
 pub(crate) struct Loader {
   arena: Arena<String>,
+  offline: bool,
+  /// The address of each loaded source, and its origins, which differ from
+  /// the loaded file for included lines
+  origins: RefCell<Vec<(usize, Origins)>>,
+  stdin: RefCell<Option<String>>,
   unstable: bool,
+  warnings: RefCell<Vec<Warning<'static>>>,
 }
 
 impl Loader {
-  pub(crate) fn new(unstable: bool) -> Self {
+  pub(crate) fn new(unstable: bool, offline: bool) -> Self {
     Loader {
       arena: Arena::new(),
+      offline,
+      origins: RefCell::new(Vec::new()),
+      stdin: RefCell::new(None),
       unstable,
+      warnings: RefCell::new(Vec::new()),
     }
   }
 
+  /// Take the warnings produced while loading, for example by remote
+  /// includes which fell back to a cached copy
+  pub(crate) fn take_warnings(&self) -> Vec<Warning<'static>> {
+    self.warnings.take()
+  }
+
   pub(crate) fn load<'src>(&'src self, path: &Path) -> RunResult<&'src str> {
-    let src = self.load_recursive(path, HashSet::new())?;
-    Ok(self.arena.alloc(src))
+    let mut origins = Vec::new();
+    let src = self.load_recursive(path, false, HashSet::new(), None, &mut origins)?;
+    Ok(self.alloc_with_origins(src, origins))
+  }
+
+  /// Load the justfile found by `search`, reading it from standard input if
+  /// it was given with `--justfile -`
+  pub(crate) fn load_justfile<'src>(&'src self, search: &Search) -> RunResult<&'src str> {
+    let mut origins = Vec::new();
+    let src = self.load_recursive(
+      &search.justfile,
+      search.stdin,
+      HashSet::new(),
+      None,
+      &mut origins,
+    )?;
+    Ok(self.alloc_with_origins(src, origins))
+  }
+
+  fn alloc_with_origins(&self, src: String, origins: Origins) -> &str {
+    let src = self.arena.alloc(src);
+    self
+      .origins
+      .borrow_mut()
+      .push((src.as_ptr() as usize, origins));
+    src
+  }
+
+  /// The file and zero-based line which `token`, in a source returned by
+  /// `load` or `load_justfile`, was loaded from, which is an included file
+  /// if it came from an `!include` directive
+  pub(crate) fn origin(&self, token: Token) -> Option<(PathBuf, usize)> {
+    self
+      .origins
+      .borrow()
+      .iter()
+      .find(|(address, _)| *address == token.src.as_ptr() as usize)
+      .and_then(|(_, origins)| origins.get(token.line))
+      .cloned()
+  }
+
+  /// Store `src` for the lifetime of the loader
+  pub(crate) fn alloc<'src>(&'src self, src: String) -> &'src str {
+    self.arena.alloc(src)
+  }
+
+  /// Load the justfile at `path`, replacing `!include` directives with the
+  /// contents of the included files, surrounded by comments recording which
+  /// file they came from
+  pub(crate) fn resolve(&self, search: &Search) -> RunResult<'static, String> {
+    let path = &search.justfile;
+    let root = path.parent().unwrap_or(path);
+    self.load_recursive(
+      path,
+      search.stdin,
+      HashSet::new(),
+      Some(root),
+      &mut Vec::new(),
+    )
   }
 
-  fn load_file<'a>(path: &Path) -> RunResult<'a, String> {
-    fs::read_to_string(path).map_err(|io_error| Error::Load {
+  /// Read the justfile at `path`, or from standard input if `stdin` is true.
+  /// Standard input can only be read once, so its contents are kept for
+  /// subsequent loads.
+  fn load_file(&self, path: &Path, stdin: bool) -> RunResult<'static, String> {
+    let io_error = |io_error| Error::Load {
       path: path.to_owned(),
       io_error,
-    })
+    };
+
+    if !stdin {
+      return fs::read_to_string(path).map_err(io_error);
+    }
+
+    let mut cached = self.stdin.borrow_mut();
+
+    if let Some(src) = &*cached {
+      return Ok(src.clone());
+    }
+
+    let mut src = String::new();
+
+    io::stdin().read_to_string(&mut src).map_err(io_error)?;
+
+    *cached = Some(src.clone());
+
+    Ok(src)
   }
 
-  fn load_recursive(&self, file: &Path, seen: HashSet<PathBuf>) -> RunResult<String> {
-    let src = Self::load_file(file)?;
+  fn load_recursive(
+    &self,
+    file: &Path,
+    stdin: bool,
+    seen: HashSet<PathBuf>,
+    provenance: Option<&Path>,
+    origins: &mut Origins,
+  ) -> RunResult<'static, String> {
+    let src = self.load_file(file, stdin)?;
 
     let mut output = String::new();
 
     let mut seen_content = false;
 
     for (i, line) in LinesWithEndings::new(&src).enumerate() {
-      if !seen_content && line.starts_with('!') {
-        let include = line
-          .strip_prefix("!include")
-          .ok_or_else(|| Error::InvalidDirective { line: line.into() })?;
+      let include = if seen_content {
+        None
+      } else {
+        Include::parse(line, file, i).transpose()?
+      };
 
-        if !self.unstable {
+      if let Some(include) = include {
+        if !include.import && !self.unstable {
           return Err(Error::Unstable {
             message: "The !include directive is currently unstable.".into(),
           });
         }
 
-        let argument = include.trim();
-
-        if argument.is_empty() {
-          return Err(Error::IncludeMissingPath {
-            file: file.to_owned(),
-            line: i,
-          });
+        let Include {
+          argument,
+          namespace,
+          optional,
+          ..
+        } = include;
+
+        let argument = argument.as_ref();
+
+        let includes = if let Some(name) = Self::library_name(argument) {
+          vec![Library::resolve(name, file.parent().unwrap_or(file))?]
+        } else if let Some(remote) = Remote::parse(argument) {
+          remote
+            .fetch(self.offline, optional, &mut self.warnings.borrow_mut())?
+            .into_iter()
+            .collect()
+        } else if Self::is_glob(argument) {
+          Self::expand_glob(file, argument, optional)?
+        } else {
+          vec![PathBuf::from(argument)]
+        };
+
+        for include in includes {
+          let Some((path, contents)) =
+            self.process_include(file, &include, optional, &seen, provenance, origins)?
+          else {
+            continue;
+          };
+
+          let contents = match namespace {
+            Some(name) => self::namespace(&contents, name),
+            None => contents,
+          };
+
+          if let Some(root) = provenance {
+            let path = path
+              .strip_prefix(root)
+              .unwrap_or(&path)
+              .display()
+              .to_string();
+
+            output.push_str("# begin !include ");
+            output.push_str(&path);
+            output.push('\n');
+            output.push_str(&contents);
+            if !contents.is_empty() && !contents.ends_with('\n') {
+              output.push('\n');
+            }
+            output.push_str("# end !include ");
+            output.push_str(&path);
+            output.push('\n');
+          } else {
+            output.push_str(&contents);
+            if !contents.is_empty() && !contents.ends_with('\n') {
+              output.push('\n');
+            }
+          }
         }
-
-        let contents = self.process_include(file, Path::new(argument), &seen)?;
-
-        output.push_str(&contents);
       } else {
         if !(line.trim().is_empty() || line.trim().starts_with('#')) {
           seen_content = true;
         }
         output.push_str(line);
+        origins.push((file.to_owned(), i));
       }
     }
 
     Ok(output)
   }
 
+  /// The local files which make up the justfile found by `search`, which
+  /// are the justfile itself and the files it includes, directly or
+  /// indirectly, in the order they're first included. Remote and library
+  /// includes aren't local, and nor are the files they include.
+  pub(crate) fn local_sources(&self, search: &Search) -> RunResult<'static, Vec<PathBuf>> {
+    let mut sources = Vec::new();
+    self.collect_local_sources(&search.justfile.lexiclean(), &mut sources)?;
+    Ok(sources)
+  }
+
+  fn collect_local_sources(
+    &self,
+    file: &Path,
+    sources: &mut Vec<PathBuf>,
+  ) -> RunResult<'static, ()> {
+    if sources.iter().any(|source| source == file) {
+      return Ok(());
+    }
+
+    sources.push(file.to_owned());
+
+    let src = self.load_file(file, false)?;
+
+    for (i, line) in LinesWithEndings::new(&src).enumerate() {
+      let Some(include) = Include::parse(line, file, i).transpose()? else {
+        if line.trim().is_empty() || line.trim().starts_with('#') {
+          continue;
+        }
+        break;
+      };
+
+      let argument = include.argument.as_ref();
+
+      if Self::library_name(argument).is_some() || Remote::parse(argument).is_some() {
+        continue;
+      }
+
+      let includes = if Self::is_glob(argument) {
+        Self::expand_glob(file, argument, include.optional)?
+      } else {
+        vec![PathBuf::from(argument)]
+      };
+
+      for path in includes {
+        let path = file.parent().unwrap_or(file).join(path).lexiclean();
+
+        if path.exists() || !include.optional {
+          self.collect_local_sources(&path, sources)?;
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Whether the argument of an `!include` directive is a glob pattern
+  fn is_glob(argument: &str) -> bool {
+    argument.contains(['*', '?', '['])
+  }
+
+  /// The files matching the glob `pattern` in an `!include` directive in
+  /// `file`, relative to its directory, in sorted order. Patterns which
+  /// match no files are an error unless the directive is `optional`.
+  fn expand_glob(file: &Path, pattern: &str, optional: bool) -> RunResult<'static, Vec<PathBuf>> {
+    let error = |message: String| Error::IncludeGlob {
+      justfile: file.to_owned(),
+      pattern: pattern.to_owned(),
+      message,
+    };
+
+    let directory = file.parent().unwrap_or(file);
+
+    let escaped = directory
+      .to_str()
+      .map(glob::Pattern::escape)
+      .ok_or_else(|| error("justfile directory is not valid unicode".into()))?;
+
+    let pattern_path = Path::new(pattern);
+
+    let full = if pattern_path.is_relative() {
+      Path::new(&escaped).join(pattern_path)
+    } else {
+      pattern_path.to_owned()
+    };
+
+    let options = glob::MatchOptions {
+      case_sensitive: true,
+      require_literal_separator: true,
+      require_literal_leading_dot: true,
+    };
+
+    let mut matches = glob::glob_with(&full.to_string_lossy(), options)
+      .map_err(|err| error(format!("invalid glob pattern: {}", err.msg)))?
+      .map(|path| {
+        path.map_err(|err| {
+          error(format!(
+            "failed to read `{}`: {}",
+            err.path().display(),
+            err.error()
+          ))
+        })
+      })
+      .collect::<RunResult<Vec<PathBuf>>>()?;
+
+    if matches.is_empty() && !optional {
+      return Err(error("no files matched".into()));
+    }
+
+    matches.sort();
+
+    Ok(matches)
+  }
+
+  /// The name of the library included by an `!include lib('NAME')`
+  /// directive
+  fn library_name(argument: &str) -> Option<&str> {
+    let quoted = argument.strip_prefix("lib(")?.strip_suffix(')')?.trim();
+
+    ['\'', '"'].iter().find_map(|quote| {
+      quoted
+        .strip_prefix(*quote)?
+        .strip_suffix(*quote)
+        .filter(|name| !name.is_empty())
+    })
+  }
+
   fn process_include(
     &self,
     file: &Path,
     include: &Path,
+    optional: bool,
     seen: &HashSet<PathBuf>,
-  ) -> RunResult<String> {
+    provenance: Option<&Path>,
+    origins: &mut Origins,
+  ) -> RunResult<'static, Option<(PathBuf, String)>> {
     let canonical_path = if include.is_relative() {
       let current_dir = file.parent().ok_or(Error::Internal {
         message: format!(
@@ -112,6 +394,10 @@ impl Loader {
 
     let canonical_path = canonical_path.lexiclean();
 
+    if optional && !canonical_path.exists() {
+      return Ok(None);
+    }
+
     if seen.contains(&canonical_path) {
       return Err(Error::CircularInclude {
         current: file.to_owned(),
@@ -122,7 +408,9 @@ impl Loader {
     let mut seen_paths = seen.clone();
     seen_paths.insert(file.lexiclean());
 
-    self.load_recursive(&canonical_path, seen_paths)
+    let contents = self.load_recursive(&canonical_path, false, seen_paths, provenance, origins)?;
+
+    Ok(Some((canonical_path, contents)))
   }
 }
 
@@ -171,7 +459,7 @@ some_recipe: recipe_b
     echo "some recipe"
 "#;
 
-    let loader = Loader::new(true);
+    let loader = Loader::new(true, false);
 
     let justfile_a_path = tmp.path().join("justfile");
     let loader_output = loader.load(&justfile_a_path).unwrap();
@@ -203,7 +491,7 @@ recipe_b:
         }
     };
 
-    let loader = Loader::new(true);
+    let loader = Loader::new(true, false);
 
     let justfile_a_path = tmp.path().join("justfile");
     let loader_output = loader.load(&justfile_a_path).unwrap_err();
@@ -0,0 +1,84 @@
+use super::*;
+
+/// Run `$SHELL -lc 'env -0'` and parse the environment it prints, for
+/// `set inherit-login-env`. Processes started outside a terminal, for example
+/// by editors launched from the macOS Dock, don't inherit the environment set
+/// up by the user's shell profile, so `PATH` in particular may be missing
+/// directories recipes rely on.
+///
+/// Only variables which are unset or differ from the environment `just` was
+/// run in are returned. They are kept separate from those loaded from `.env`
+/// files, which override them. If `$SHELL` is unset, `sh` is used.
+pub(crate) fn login_env(config: &Config) -> RunResult<'static, BTreeMap<String, String>> {
+  let shell = env::var_os("SHELL")
+    .filter(|shell| !shell.is_empty())
+    .unwrap_or_else(|| "sh".into());
+
+  let mut command = Command::new(&shell);
+
+  command.args(["-lc", "env -0"]);
+
+  command.stdin(Stdio::null());
+
+  command.stderr(if config.verbosity.quiet() {
+    Stdio::null()
+  } else {
+    Stdio::inherit()
+  });
+
+  let stdout =
+    InterruptHandler::guard(|| output(command)).map_err(|output_error| Error::LoginEnv {
+      shell: shell.to_string_lossy().into_owned(),
+      output_error,
+    })?;
+
+  Ok(
+    parse(&stdout)
+      .into_iter()
+      .filter(|(key, value)| env::var_os(key).as_deref() != Some(OsStr::new(value)))
+      .collect(),
+  )
+}
+
+/// Parse the NUL-terminated `KEY=VALUE` entries printed by `env -0`, so that
+/// values may contain newlines. Entries which aren't of that form are
+/// skipped.
+fn parse(stdout: &str) -> BTreeMap<String, String> {
+  stdout
+    .split('\0')
+    .filter_map(|line| line.split_once('='))
+    .filter(|(key, _value)| is_env_var_name(key))
+    .map(|(key, value)| (key.to_owned(), value.to_owned()))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_entries() {
+    assert_eq!(
+      parse("FOO=bar\0BAZ=a=b\0EMPTY=\0MULTI=a\nb\0"),
+      [
+        ("BAZ", "a=b"),
+        ("EMPTY", ""),
+        ("FOO", "bar"),
+        ("MULTI", "a\nb")
+      ]
+      .iter()
+      .map(|(key, value)| ((*key).to_owned(), (*value).to_owned()))
+      .collect(),
+    );
+  }
+
+  #[test]
+  fn parse_skips_other_entries() {
+    assert_eq!(
+      parse("FOO=first\0second entry\0not a name=x\0"),
+      [("FOO".to_owned(), "first".to_owned())]
+        .into_iter()
+        .collect(),
+    );
+  }
+}
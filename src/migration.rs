@@ -0,0 +1,362 @@
+use {
+  super::*,
+  crate::settings::{WINDOWS_POWERSHELL_ARGS, WINDOWS_POWERSHELL_SHELL},
+};
+
+/// A legacy construct rewritten to its modern equivalent by `--migrate`
+#[derive(Debug, PartialEq)]
+pub(crate) enum Migration {
+  /// An `!include` directive, which is deprecated in favor of `import`
+  Include { line: usize },
+  /// `[linux]`, `[macos]`, `[unix]`, or `[windows]`, which are deprecated in
+  /// favor of `[os(…)]` and `[os-family(…)]`
+  PlatformAttributes {
+    line: usize,
+    recipe: String,
+    replacement: String,
+  },
+  /// `set windows-powershell`, which is deprecated in favor of `windows-shell`
+  WindowsPowershell { line: usize },
+  /// `set windows-powershell` which has no effect, because it is false or
+  /// `windows-shell` is also set
+  WindowsPowershellRemoved { line: usize },
+}
+
+impl Migration {
+  /// Rewrite the legacy constructs in `ast`, returning the changes made, in
+  /// the order they appear
+  pub(crate) fn apply(ast: &mut Ast) -> Vec<Self> {
+    let mut migrations = Vec::new();
+
+    let windows_shell = ast.items.iter().any(|item| {
+      matches!(
+        item,
+        Item::Set(Set {
+          value: Setting::WindowsShell(_),
+          ..
+        })
+      )
+    });
+
+    ast.items.retain_mut(|item| {
+      let Item::Set(set) = item else {
+        return true;
+      };
+
+      let Setting::WindowsPowerShell(enabled) = set.value else {
+        return true;
+      };
+
+      let line = set.name.line;
+
+      if !enabled || windows_shell {
+        migrations.push(Self::WindowsPowershellRemoved { line });
+        return false;
+      }
+
+      *set = Self::windows_shell(set.name);
+
+      migrations.push(Self::WindowsPowershell { line });
+
+      true
+    });
+
+    Self::platform_attributes(ast, &mut migrations);
+
+    migrations
+  }
+
+  /// Replace platform attributes on recipes with the equivalent `[os(…)]`
+  /// and `[os-family(…)]` attributes. Recipe attributes override attributes
+  /// of the same kind inherited from group declarations, so recipes in
+  /// groups which declare platform attributes, and the group declarations
+  /// themselves, are left alone, since replacing them could change which
+  /// platforms the recipes are enabled on.
+  fn platform_attributes(ast: &mut Ast, migrations: &mut Vec<Self>) {
+    const PLATFORM_ATTRIBUTES: [(Attribute, bool, &str); 4] = [
+      (Attribute::Linux, false, "linux"),
+      (Attribute::Macos, false, "macos"),
+      (Attribute::Unix, true, "unix"),
+      (Attribute::Windows, true, "windows"),
+    ];
+
+    let platform = |attribute: &Attribute| {
+      matches!(
+        attribute,
+        Attribute::Linux
+          | Attribute::Macos
+          | Attribute::Os(_)
+          | Attribute::OsFamily(_)
+          | Attribute::Unix
+          | Attribute::Windows
+      )
+    };
+
+    let groups = ast
+      .items
+      .iter()
+      .filter_map(|item| match item {
+        Item::Group(group) if group.attributes.iter().any(platform) => {
+          Some(group.name.cooked.clone())
+        }
+        _ => None,
+      })
+      .collect::<BTreeSet<String>>();
+
+    for item in &mut ast.items {
+      let Item::Recipe(recipe) = item else {
+        continue;
+      };
+
+      let grouped = recipe.attributes.iter().any(
+        |attribute| matches!(attribute, Attribute::Group(name) if groups.contains(&name.cooked)),
+      );
+
+      if grouped
+        || !PLATFORM_ATTRIBUTES
+          .iter()
+          .any(|(attribute, _, _)| recipe.attributes.contains(attribute))
+      {
+        continue;
+      }
+
+      let mut oses = Vec::new();
+      let mut os_families = Vec::new();
+
+      recipe.attributes = mem::take(&mut recipe.attributes)
+        .into_iter()
+        .filter(|attribute| match attribute {
+          Attribute::Os(values) => {
+            oses.extend(values.iter().cloned());
+            false
+          }
+          Attribute::OsFamily(values) => {
+            os_families.extend(values.iter().cloned());
+            false
+          }
+          _ => true,
+        })
+        .collect();
+
+      let mut replaced = Vec::new();
+      let mut os_replaced = false;
+      let mut os_family_replaced = false;
+
+      for (attribute, family, name) in PLATFORM_ATTRIBUTES {
+        if recipe.attributes.remove(&attribute) {
+          let values = if family {
+            os_family_replaced = true;
+            &mut os_families
+          } else {
+            os_replaced = true;
+            &mut oses
+          };
+
+          if !values.iter().any(|value| value.cooked == name) {
+            values.push(Self::literal(name));
+          }
+
+          replaced.push(format!("[{name}]"));
+        }
+      }
+
+      let mut replacements = Vec::new();
+      let mut described = Vec::new();
+
+      for (values, replaced, os_family) in [
+        (oses, os_replaced, false),
+        (os_families, os_family_replaced, true),
+      ] {
+        if values.is_empty() {
+          continue;
+        }
+
+        let attribute = if os_family {
+          Attribute::OsFamily(values)
+        } else {
+          Attribute::Os(values)
+        };
+
+        if replaced {
+          described.push(format!("[{attribute}]"));
+        }
+
+        replacements.push(attribute);
+      }
+
+      migrations.push(Self::PlatformAttributes {
+        line: recipe.name.line,
+        recipe: recipe.name.lexeme().into(),
+        replacement: format!(
+          "{} with {}",
+          List::and_ticked(&replaced),
+          List::and_ticked(&described),
+        ),
+      });
+
+      recipe.attributes.extend(replacements);
+    }
+  }
+
+  /// A single-quoted string literal containing `raw`
+  fn literal(raw: &'static str) -> StringLiteral<'static> {
+    StringLiteral {
+      kind: StringKind::from_token_start("'").unwrap(),
+      raw,
+      cooked: raw.into(),
+    }
+  }
+
+  /// Increase the line numbers of this migration by `lines`, for migrations
+  /// found in the part of a file following its `!include` directives
+  pub(crate) fn offset(mut self, lines: usize) -> Self {
+    match &mut self {
+      Self::Include { line }
+      | Self::PlatformAttributes { line, .. }
+      | Self::WindowsPowershell { line }
+      | Self::WindowsPowershellRemoved { line } => *line += lines,
+    }
+    self
+  }
+
+  /// `set windows-shell := ["powershell.exe", "-NoLogo", "-Command"]`, which
+  /// is equivalent to `set windows-powershell`, in place of `name`
+  fn windows_shell<'src>(name: Name<'src>) -> Set<'src> {
+    let literal = |raw: &'static str| StringLiteral {
+      kind: StringKind::from_token_start("\"").unwrap(),
+      raw,
+      cooked: raw.into(),
+    };
+
+    let lexeme = Keyword::WindowsShell.lexeme();
+
+    Set {
+      name: Name {
+        offset: 0,
+        length: lexeme.len(),
+        src: lexeme,
+        ..name
+      },
+      value: Setting::WindowsShell(Shell {
+        command: literal(WINDOWS_POWERSHELL_SHELL),
+        arguments: WINDOWS_POWERSHELL_ARGS
+          .iter()
+          .copied()
+          .map(literal)
+          .collect(),
+      }),
+    }
+  }
+}
+
+impl Display for Migration {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Include { line } => write!(
+        f,
+        "line {}: replaced `!include` with `import`",
+        line.ordinal()
+      ),
+      Self::PlatformAttributes {
+        line,
+        recipe,
+        replacement,
+      } => write!(
+        f,
+        "line {}: replaced {replacement} on recipe `{recipe}`",
+        line.ordinal()
+      ),
+      Self::WindowsPowershell { line } => write!(
+        f,
+        "line {}: replaced `set windows-powershell` with `set windows-shell`",
+        line.ordinal()
+      ),
+      Self::WindowsPowershellRemoved { line } => write!(
+        f,
+        "line {}: removed `set windows-powershell`, which has no effect",
+        line.ordinal()
+      ),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn migrate(src: &str) -> (String, Vec<String>) {
+    let tokens = Lexer::lex(src, &InterpolationDelimiters::default()).unwrap();
+    let mut ast = Parser::parse(&tokens).unwrap();
+    let migrations = Migration::apply(&mut ast);
+    (
+      ast.to_string(),
+      migrations.iter().map(ToString::to_string).collect(),
+    )
+  }
+
+  #[test]
+  fn windows_powershell() {
+    assert_eq!(
+      migrate("set windows-powershell\n"),
+      (
+        "set windows-shell := [\"powershell.exe\", \"-NoLogo\", \"-Command\"]\n".into(),
+        vec!["line 1: replaced `set windows-powershell` with `set windows-shell`".into()],
+      ),
+    );
+  }
+
+  #[test]
+  fn windows_powershell_without_effect() {
+    assert_eq!(
+      migrate("set windows-powershell := false\nx := 'a'\n"),
+      (
+        "x := 'a'\n".into(),
+        vec!["line 1: removed `set windows-powershell`, which has no effect".into()],
+      ),
+    );
+
+    assert_eq!(
+      migrate("set windows-shell := ['cmd.exe', '/c']\nset windows-powershell\n"),
+      (
+        "set windows-shell := ['cmd.exe', '/c']\n".into(),
+        vec!["line 2: removed `set windows-powershell`, which has no effect".into()],
+      ),
+    );
+  }
+
+  #[test]
+  fn platform_attributes() {
+    assert_eq!(
+      migrate("[linux]\n[macos]\nfoo:\n"),
+      (
+        "[os('linux', 'macos')]\nfoo:\n".into(),
+        vec![
+          "line 3: replaced `[linux]` and `[macos]` with `[os('linux', 'macos')]` on recipe `foo`"
+            .into()
+        ],
+      ),
+    );
+
+    assert_eq!(
+      migrate("[unix]\n[os('windows')]\nfoo:\n"),
+      (
+        "[os('windows')]\n[os-family('unix')]\nfoo:\n".into(),
+        vec!["line 3: replaced `[unix]` with `[os-family('unix')]` on recipe `foo`".into()],
+      ),
+    );
+  }
+
+  #[test]
+  fn platform_attributes_in_groups_with_platform_attributes() {
+    let src = "[group('ci'): windows]\n\n[group('ci')]\n[linux]\nfoo:\n";
+    assert_eq!(migrate(src), (src.into(), Vec::new()));
+  }
+
+  #[test]
+  fn modern() {
+    assert_eq!(
+      migrate("x := 'a'\n\nfoo:\n"),
+      ("x := 'a'\n\nfoo:\n".into(), Vec::new()),
+    );
+  }
+}
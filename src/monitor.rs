@@ -0,0 +1,426 @@
+use {
+  super::*,
+  std::{
+    io::Read,
+    process::Child,
+    sync::{
+      atomic::{AtomicBool, Ordering},
+      mpsc::{self, RecvTimeoutError},
+      Arc,
+    },
+    thread,
+  },
+};
+
+/// Watches a running recipe line, printing a warning each time it goes
+/// `stall_warning` without producing any output, and terminating it if it is
+/// still running after `timeout`.
+///
+/// Detecting stalls requires relaying the command's output through just, so
+/// its stdout and stderr are only replaced with pipes when `stall_warning` is
+/// set.
+///
+/// It also writes `input`, if any, to the command's stdin, which is how
+/// `[script-stdin]` recipes are passed to their interpreter, applies
+/// `stderr_replacement`, if any, to its stderr, which is how `set source-map`
+/// replaces the paths of script files with that of the justfile, and collects
+/// its stdout in `capture`, if any, which is how the output of a dependency
+/// is bound to a variable.
+pub(crate) struct Monitor {
+  capture: Option<Capture>,
+  color: Color,
+  command: String,
+  input: Option<String>,
+  recipe: String,
+  stall_warning: Option<Duration>,
+  stderr_replacement: Option<Replacement>,
+  timed_out: Arc<AtomicBool>,
+  timeout: Option<Interval>,
+}
+
+/// The standard output of the commands of a recipe, collected so that it can
+/// be bound to a variable of a recipe which depends on it
+#[derive(Clone, Default)]
+pub(crate) struct Capture(Arc<Mutex<Vec<u8>>>);
+
+impl Capture {
+  /// The collected output, without a trailing newline, as with backticks
+  pub(crate) fn output(&self) -> String {
+    let output = String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned();
+
+    output
+      .strip_suffix("\r\n")
+      .or_else(|| output.strip_suffix('\n'))
+      .unwrap_or(&output)
+      .to_owned()
+  }
+}
+
+impl Write for Capture {
+  fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+    self.0.lock().unwrap().extend_from_slice(buffer);
+    Ok(buffer.len())
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+/// Replaces each occurrence of `from` in a stream with `to`
+#[derive(Clone)]
+struct Replacement {
+  from: Vec<u8>,
+  to: Vec<u8>,
+}
+
+impl Replacement {
+  /// Replace occurrences of `from` in `pending`, returning the output which
+  /// is ready to be written, and leaving in `pending` any suffix which may be
+  /// the start of an occurrence completed by later input
+  fn apply(&self, pending: &mut Vec<u8>) -> Vec<u8> {
+    let mut output = Vec::new();
+    let mut rest = &pending[..];
+
+    while let Some(i) = rest
+      .windows(self.from.len())
+      .position(|window| window == self.from)
+    {
+      output.extend_from_slice(&rest[..i]);
+      output.extend_from_slice(&self.to);
+      rest = &rest[i + self.from.len()..];
+    }
+
+    let held = (1..self.from.len().min(rest.len() + 1))
+      .rev()
+      .find(|&len| rest.ends_with(&self.from[..len]))
+      .unwrap_or_default();
+
+    output.extend_from_slice(&rest[..rest.len() - held]);
+
+    let held = pending.len() - held;
+    pending.drain(..held);
+
+    output
+  }
+}
+
+/// Handle to the threads started by `Monitor::watch`, which stops them when
+/// dropped.
+pub(crate) struct Watch {
+  done: Option<mpsc::Sender<()>>,
+  relays: Vec<mpsc::Receiver<()>>,
+}
+
+impl Monitor {
+  /// How long to wait for output from a command to be relayed after it has
+  /// exited, which may take forever if it left a background process holding
+  /// its stdout or stderr open.
+  const RELAY_GRACE_PERIOD: Duration = Duration::from_millis(100);
+
+  pub(crate) fn new(config: &Config, settings: &Settings, recipe: &str, command: &str) -> Self {
+    Self {
+      capture: None,
+      color: config.color.stderr(),
+      command: command.trim().to_owned(),
+      input: None,
+      recipe: recipe.to_owned(),
+      stall_warning: if config.verbosity.quiet() {
+        None
+      } else {
+        config.stall_warning.map(Interval::duration)
+      },
+      stderr_replacement: None,
+      timed_out: Arc::new(AtomicBool::new(false)),
+      timeout: settings.line_timeout,
+    }
+  }
+
+  pub(crate) fn input(self, input: Option<String>) -> Self {
+    Self { input, ..self }
+  }
+
+  /// Collect the command's stdout in `capture`, instead of writing it to
+  /// just's own
+  pub(crate) fn capture(self, capture: Option<&Capture>) -> Self {
+    Self {
+      capture: capture.cloned(),
+      ..self
+    }
+  }
+
+  /// Replace each occurrence of `from` in the command's stderr with `to`
+  pub(crate) fn replace_stderr(self, from: &str, to: &str) -> Self {
+    Self {
+      stderr_replacement: (!from.is_empty()).then(|| Replacement {
+        from: from.into(),
+        to: to.into(),
+      }),
+      ..self
+    }
+  }
+
+  pub(crate) fn is_active(&self) -> bool {
+    self.stall_warning.is_some() || self.timeout.is_some()
+  }
+
+  /// The timeout which the command exceeded, if it was terminated for taking
+  /// too long
+  pub(crate) fn timed_out(&self) -> Option<Interval> {
+    if self.timed_out.load(Ordering::SeqCst) {
+      self.timeout
+    } else {
+      None
+    }
+  }
+
+  /// Replace the stdout and stderr of `command` with pipes, if needed to
+  /// watch for stalls, to capture stdout, or to replace text in stderr, and
+  /// its stdin, if there is input to write to it.
+  pub(crate) fn configure(&self, command: &mut Command) {
+    if self.input.is_some() {
+      command.stdin(Stdio::piped());
+    }
+
+    if self.stall_warning.is_some() || self.capture.is_some() {
+      command.stdout(Stdio::piped());
+    }
+
+    if self.stall_warning.is_some() || self.stderr_replacement.is_some() {
+      command.stderr(Stdio::piped());
+    }
+  }
+
+  /// Start watching `child`, calling `terminate` if it times out.
+  pub(crate) fn watch(
+    &self,
+    child: &mut Child,
+    terminate: impl FnOnce() + Send + 'static,
+  ) -> Watch {
+    let mut watch = Watch {
+      done: None,
+      relays: Vec::new(),
+    };
+
+    if let (Some(input), Some(mut stdin)) = (self.input.clone(), child.stdin.take()) {
+      // The command may exit without reading all of its input, so errors
+      // writing it are ignored. Dropping stdin closes it once it is written.
+      thread::spawn(move || stdin.write_all(input.as_bytes()).ok());
+    }
+
+    let last_output = Arc::new(Mutex::new(Instant::now()));
+
+    if let Some(stdout) = child.stdout.take() {
+      watch
+        .relays
+        .push(if let Some(capture) = self.capture.clone() {
+          Self::relay(stdout, move || capture.clone(), last_output.clone(), None)
+        } else {
+          Self::relay(stdout, io::stdout, last_output.clone(), None)
+        });
+    }
+
+    if let Some(stderr) = child.stderr.take() {
+      watch.relays.push(Self::relay(
+        stderr,
+        io::stderr,
+        last_output.clone(),
+        self.stderr_replacement.clone(),
+      ));
+    }
+
+    if !self.is_active() {
+      return watch;
+    }
+
+    let (done, stop) = mpsc::channel();
+
+    watch.done = Some(done);
+
+    let color = self.color;
+    let command = self.command.clone();
+    let recipe = self.recipe.clone();
+    let stall_warning = self.stall_warning;
+    let timed_out = self.timed_out.clone();
+    let timeout = self.timeout.map(Interval::duration);
+
+    thread::spawn(move || {
+      let start = Instant::now();
+      let mut silent_since = start;
+      let mut warnings = 0;
+
+      loop {
+        let now = Instant::now();
+
+        let mut deadline = None;
+
+        if let Some(timeout) = timeout {
+          if now.duration_since(start) >= timeout {
+            timed_out.store(true, Ordering::SeqCst);
+            terminate();
+            return;
+          }
+
+          deadline = Some(start + timeout);
+        }
+
+        if let Some(stall_warning) = stall_warning {
+          let last_output = *last_output.lock().unwrap();
+
+          if last_output > silent_since {
+            silent_since = last_output;
+            warnings = 0;
+          }
+
+          let next_warning = silent_since + stall_warning * (warnings + 1);
+
+          if now >= next_warning {
+            warnings += 1;
+            let warning = color.warning();
+            let message = color.message();
+            eprintln!(
+              "{} {}Recipe `{recipe}` has produced no output for {}, still running `{command}`{}",
+              warning.paint("warning:"),
+              message.prefix(),
+              Interval::from(stall_warning * warnings),
+              message.suffix(),
+            );
+            continue;
+          }
+
+          deadline =
+            Some(deadline.map_or(next_warning, |deadline: Instant| deadline.min(next_warning)));
+        }
+
+        let result = match deadline {
+          Some(deadline) => stop.recv_timeout(deadline.saturating_duration_since(now)),
+          None => stop.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        if result != Err(RecvTimeoutError::Timeout) {
+          return;
+        }
+      }
+    });
+
+    watch
+  }
+
+  fn relay<W: Write + 'static>(
+    mut source: impl Read + Send + 'static,
+    destination: impl Fn() -> W + Send + 'static,
+    last_output: Arc<Mutex<Instant>>,
+    replacement: Option<Replacement>,
+  ) -> mpsc::Receiver<()> {
+    let (finished, relay) = mpsc::channel();
+
+    thread::spawn(move || {
+      let mut buffer = [0; 8192];
+      let mut pending = Vec::new();
+
+      let write = |data: &[u8]| {
+        let mut destination = destination();
+        destination
+          .write_all(data)
+          .and_then(|()| destination.flush())
+      };
+
+      loop {
+        match source.read(&mut buffer) {
+          Ok(0) => break,
+          Ok(n) => {
+            *last_output.lock().unwrap() = Instant::now();
+
+            let result = match &replacement {
+              Some(replacement) => {
+                pending.extend_from_slice(&buffer[..n]);
+                write(&replacement.apply(&mut pending))
+              }
+              None => write(&buffer[..n]),
+            };
+
+            if result.is_err() {
+              break;
+            }
+          }
+          Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+          Err(_) => break,
+        }
+      }
+
+      write(&pending).ok();
+
+      finished.send(()).ok();
+    });
+
+    relay
+  }
+}
+
+impl Drop for Watch {
+  fn drop(&mut self) {
+    self.done.take();
+
+    // Wait for output to be relayed, so that it isn't interleaved with that of
+    // the next line.
+    for relay in &self.relays {
+      relay.recv_timeout(Monitor::RELAY_GRACE_PERIOD).ok();
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn replace(chunks: &[&str]) -> String {
+    let replacement = Replacement {
+      from: b"/tmp/foo".to_vec(),
+      to: b"justfile".to_vec(),
+    };
+
+    let mut pending = Vec::new();
+    let mut output = Vec::new();
+
+    for chunk in chunks {
+      pending.extend_from_slice(chunk.as_bytes());
+      output.extend(replacement.apply(&mut pending));
+    }
+
+    output.extend(pending);
+
+    String::from_utf8(output).unwrap()
+  }
+
+  #[test]
+  fn replacement() {
+    assert_eq!(
+      replace(&["File \"/tmp/foo\", line 3\n/tmp/foo"]),
+      "File \"justfile\", line 3\njustfile"
+    );
+  }
+
+  #[test]
+  fn replacement_split_across_chunks() {
+    assert_eq!(
+      replace(&["File \"/tm", "p/fo", "o\", line 3\n"]),
+      "File \"justfile\", line 3\n"
+    );
+  }
+
+  #[test]
+  fn replacement_holds_only_possible_prefixes() {
+    let replacement = Replacement {
+      from: b"/tmp/foo".to_vec(),
+      to: b"justfile".to_vec(),
+    };
+
+    let mut pending = b"prompt: /tm".to_vec();
+    assert_eq!(replacement.apply(&mut pending), b"prompt: ");
+    assert_eq!(pending, b"/tm");
+
+    let mut pending = b"prompt: ".to_vec();
+    assert_eq!(replacement.apply(&mut pending), b"prompt: ");
+    assert!(pending.is_empty());
+  }
+}
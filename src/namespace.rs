@@ -0,0 +1,164 @@
+use super::*;
+
+/// Prefix the recipes, aliases, and variables defined in `src`, the contents
+/// of a file included with `!include PATH as NAMESPACE`, and references to
+/// them, with `NAMESPACE::`. References to recipes and variables which `src`
+/// doesn't define, and so come from the including justfile, are left alone,
+/// as are functions and settings.
+///
+/// If `src` can't be parsed, it is returned unchanged, so that the error is
+/// reported when the justfile it is included in is compiled.
+pub(crate) fn namespace(src: &str, namespace: &str) -> String {
+  let Ok(tokens) = Lexer::lex(src, &InterpolationDelimiters::scan(src)) else {
+    return src.to_owned();
+  };
+
+  let Ok(ast) = Parser::parse(&tokens) else {
+    return src.to_owned();
+  };
+
+  let mut recipes = BTreeSet::new();
+  let mut variables = BTreeSet::new();
+
+  for item in &ast.items {
+    match item {
+      Item::Alias(alias) => {
+        recipes.insert(alias.name.lexeme());
+      }
+      Item::Assignment(assignment) => {
+        variables.insert(assignment.name.lexeme());
+      }
+      Item::Recipe(recipe) => {
+        recipes.insert(recipe.name.lexeme());
+      }
+      Item::Comment(_) | Item::Function(_) | Item::Group(_) | Item::Set(_) => {}
+    }
+  }
+
+  let mut offsets = Vec::new();
+
+  let variable_references = |expression: &Expression, parameters: &[Parameter]| {
+    expression
+      .variables()
+      .filter(|variable| {
+        let name = variable.lexeme();
+        variables.contains(name)
+          && !parameters
+            .iter()
+            .any(|parameter| parameter.name.lexeme() == name)
+      })
+      .map(|variable| variable.offset)
+      .collect::<Vec<usize>>()
+  };
+
+  for item in &ast.items {
+    match item {
+      Item::Alias(alias) => {
+        offsets.push(alias.name.offset);
+        if recipes.contains(alias.target.lexeme()) {
+          offsets.push(alias.target.offset);
+        }
+      }
+      Item::Assignment(assignment) => {
+        offsets.push(assignment.name.offset);
+        offsets.extend(variable_references(&assignment.value, &[]));
+      }
+      Item::Recipe(recipe) => {
+        offsets.push(recipe.name.offset);
+
+        for parameter in &recipe.parameters {
+          if let Some(default) = &parameter.default {
+            offsets.extend(variable_references(default, &recipe.parameters));
+          }
+        }
+
+        for dependency in &recipe.dependencies {
+          if recipes.contains(dependency.recipe.lexeme()) {
+            offsets.push(dependency.recipe.offset);
+          }
+
+          for argument in &dependency.arguments {
+            offsets.extend(variable_references(argument, &recipe.parameters));
+          }
+        }
+
+        for line in &recipe.body {
+          for fragment in &line.fragments {
+            if let Fragment::Interpolation { expression } = fragment {
+              offsets.extend(variable_references(expression, &recipe.parameters));
+            }
+          }
+        }
+
+        for name in recipe
+          .attributes
+          .iter()
+          .filter_map(|attribute| match attribute {
+            Attribute::SeeAlso(names) => Some(names),
+            _ => None,
+          })
+          .flatten()
+        {
+          if recipes.contains(name.cooked.as_str()) {
+            offsets.push(name.raw.as_ptr() as usize - src.as_ptr() as usize);
+          }
+        }
+      }
+      Item::Comment(_) | Item::Function(_) | Item::Group(_) | Item::Set(_) => {}
+    }
+  }
+
+  offsets.sort_unstable();
+  offsets.dedup();
+
+  let mut namespaced = String::new();
+  let mut start = 0;
+
+  for offset in offsets {
+    namespaced.push_str(&src[start..offset]);
+    namespaced.push_str(namespace);
+    namespaced.push_str("::");
+    start = offset;
+  }
+
+  namespaced.push_str(&src[start..]);
+
+  namespaced
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn definitions_and_references() {
+    assert_eq!(
+      namespace(
+        "x := 'a'\ny := x + z\nalias b := bar\nfoo: bar\nbar p=x: (foo x)\n  echo {{p}} {{y}}\n",
+        "ci",
+      ),
+      "ci::x := 'a'\nci::y := ci::x + z\nalias ci::b := ci::bar\nci::foo: ci::bar\nci::bar p=ci::x: (ci::foo ci::x)\n  echo {{p}} {{ci::y}}\n",
+    );
+  }
+
+  #[test]
+  fn parameters_shadow_variables() {
+    assert_eq!(
+      namespace("x := 'a'\nfoo x:\n  echo {{x}}\n", "ci"),
+      "ci::x := 'a'\nci::foo x:\n  echo {{x}}\n",
+    );
+  }
+
+  #[test]
+  fn undefined_names_are_unchanged() {
+    assert_eq!(
+      namespace("foo: bar\n  echo {{baz}}\n", "ci"),
+      "ci::foo: bar\n  echo {{baz}}\n",
+    );
+  }
+
+  #[test]
+  fn invalid_source_is_unchanged() {
+    assert_eq!(namespace("foo: (\n", "ci"), "foo: (\n");
+  }
+}
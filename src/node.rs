@@ -21,6 +21,8 @@ impl<'src> Node<'src> for Item<'src> {
       Item::Alias(alias) => alias.tree(),
       Item::Assignment(assignment) => assignment.tree(),
       Item::Comment(comment) => comment.tree(),
+      Item::Function(function) => function.tree(),
+      Item::Group(group) => group.tree(),
       Item::Recipe(recipe) => recipe.tree(),
       Item::Set(set) => set.tree(),
     }
@@ -41,6 +43,10 @@ impl<'src> Node<'src> for Assignment<'src> {
       Tree::atom("assignment")
         .push("#")
         .push(Keyword::Export.lexeme())
+    } else if self.lazy {
+      Tree::atom("assignment")
+        .push("#")
+        .push(Keyword::Lazy.lexeme())
     } else {
       Tree::atom("assignment")
     }
@@ -49,21 +55,32 @@ impl<'src> Node<'src> for Assignment<'src> {
   }
 }
 
+impl<'src> Node<'src> for UserFunction<'src> {
+  fn tree(&self) -> Tree<'src> {
+    Tree::atom(Keyword::Fn.lexeme())
+      .push(self.name.lexeme())
+      .push(Tree::atom("params").extend(self.parameters.iter().map(Name::lexeme)))
+      .push(self.body.tree())
+  }
+}
+
 impl<'src> Node<'src> for Expression<'src> {
   fn tree(&self) -> Tree<'src> {
     match self {
       Expression::Concatenation { lhs, rhs } => Tree::atom("+").push(lhs.tree()).push(rhs.tree()),
       Expression::Conditional {
-        lhs,
-        rhs,
+        condition,
         then,
         otherwise,
-        operator,
       } => {
         let mut tree = Tree::atom(Keyword::If.lexeme());
-        tree.push_mut(lhs.tree());
-        tree.push_mut(operator.to_string());
-        tree.push_mut(rhs.tree());
+        if let Condition::Comparison { lhs, rhs, operator } = condition {
+          tree.push_mut(lhs.tree());
+          tree.push_mut(operator.to_string());
+          tree.push_mut(rhs.tree());
+        } else {
+          tree.push_mut(condition.tree());
+        }
         tree.push_mut(then.tree());
         tree.push_mut(otherwise.tree());
         tree
@@ -88,6 +105,17 @@ impl<'src> Node<'src> for Expression<'src> {
               tree.push_mut(b.tree());
             }
           }
+          UnaryPlus {
+            name,
+            args: (a, rest),
+            ..
+          } => {
+            tree.push_mut(name.lexeme());
+            tree.push_mut(a.tree());
+            for arg in rest {
+              tree.push_mut(arg.tree());
+            }
+          }
           Binary {
             name, args: [a, b], ..
           } => {
@@ -117,6 +145,12 @@ impl<'src> Node<'src> for Expression<'src> {
             tree.push_mut(b.tree());
             tree.push_mut(c.tree());
           }
+          User { name, args } => {
+            tree.push_mut(name.lexeme());
+            for arg in args {
+              tree.push_mut(arg.tree());
+            }
+          }
         }
 
         tree
@@ -136,6 +170,22 @@ impl<'src> Node<'src> for Expression<'src> {
   }
 }
 
+impl<'src> Node<'src> for Condition<'src> {
+  fn tree(&self) -> Tree<'src> {
+    match self {
+      Condition::And { lhs, rhs } => Tree::atom("&&").push(lhs.tree()).push(rhs.tree()),
+      Condition::Comparison { lhs, rhs, operator } => Tree::List(vec![
+        lhs.tree(),
+        Tree::atom(operator.to_string()),
+        rhs.tree(),
+      ]),
+      Condition::Group { contents } => Tree::List(vec![contents.tree()]),
+      Condition::Not { condition } => Tree::atom("!").push(condition.tree()),
+      Condition::Or { lhs, rhs } => Tree::atom("||").push(lhs.tree()).push(rhs.tree()),
+    }
+  }
+}
+
 impl<'src> Node<'src> for UnresolvedRecipe<'src> {
   fn tree(&self) -> Tree<'src> {
     let mut t = Tree::atom("recipe");
@@ -176,6 +226,10 @@ impl<'src> Node<'src> for UnresolvedRecipe<'src> {
           d.push_mut(argument.tree());
         }
 
+        if let Some(capture) = dependency.capture {
+          d = Tree::atom("capture").push(capture.lexeme()).push(d);
+        }
+
         if i < self.priors {
           dependencies.push_mut(d);
         } else {
@@ -227,6 +281,19 @@ impl<'src> Node<'src> for Fragment<'src> {
   }
 }
 
+impl<'src> Node<'src> for GroupDeclaration<'src> {
+  fn tree(&self) -> Tree<'src> {
+    Tree::atom("group")
+      .push(Tree::string(&self.name.cooked))
+      .extend(
+        self
+          .attributes
+          .iter()
+          .map(|attribute| Tree::atom(attribute.name().replace('-', "_"))),
+      )
+  }
+}
+
 impl<'src> Node<'src> for Set<'src> {
   fn tree(&self) -> Tree<'src> {
     let mut set = Tree::atom(Keyword::Set.lexeme());
@@ -234,31 +301,77 @@ impl<'src> Node<'src> for Set<'src> {
 
     match &self.value {
       Setting::AllowDuplicateRecipes(value)
+      | Setting::CaseInsensitiveRecipes(value)
+      | Setting::CleanEnv(value)
       | Setting::DotenvLoad(value)
+      | Setting::DotenvRequired(value)
       | Setting::Export(value)
       | Setting::Fallback(value)
+      | Setting::FmtAlignAssignments(value)
+      | Setting::InheritLoginEnv(value)
+      | Setting::InteractiveArguments(value)
+      | Setting::LocalOverlay(value)
       | Setting::PositionalArguments(value)
+      | Setting::SourceMap(value)
       | Setting::WindowsPowerShell(value)
       | Setting::IgnoreComments(value) => {
         set.push_mut(value.to_string());
       }
-      Setting::Shell(Shell { command, arguments })
+      Setting::EnvHook(Shell { command, arguments })
+      | Setting::Shell(Shell { command, arguments })
       | Setting::WindowsShell(Shell { command, arguments }) => {
         set.push_mut(Tree::string(&command.cooked));
         for argument in arguments {
           set.push_mut(Tree::string(&argument.cooked));
         }
       }
-      Setting::DotenvFilename(value) | Setting::DotenvPath(value) | Setting::Tempdir(value) => {
+      Setting::CommandLog(value)
+      | Setting::DotenvFilename(value)
+      | Setting::DotenvPath(value)
+      | Setting::ExportPrefix(value)
+      | Setting::FmtIndent(value)
+      | Setting::RequiredVersion(value)
+      | Setting::Tempdir(value) => {
         set.push_mut(Tree::string(value));
       }
+      Setting::Echo(echo) => {
+        set.push_mut(Tree::string(echo.to_string()));
+      }
+      Setting::WindowsShebang(windows_shebang) => {
+        set.push_mut(Tree::string(windows_shebang.to_string()));
+      }
+      Setting::FmtBlankLines(blank_lines) => {
+        set.push_mut(Tree::string(blank_lines.to_string()));
+      }
+      Setting::LineTimeout(interval) => {
+        set.push_mut(Tree::string(interval.to_string()));
+      }
+      Setting::DotenvFilenames(strings)
+      | Setting::EnvPassthrough(strings)
+      | Setting::FallbackJustfiles(strings)
+      | Setting::InterpolationDelimiters(strings)
+      | Setting::RequiredEnv(strings) => {
+        for string in strings {
+          set.push_mut(Tree::string(&string.cooked));
+        }
+      }
+      Setting::ShellFallbacks(shells) => {
+        for Shell { command, arguments } in shells {
+          set.push_mut(Tree::List(
+            iter::once(&command.cooked)
+              .chain(arguments.iter().map(|argument| &argument.cooked))
+              .map(Tree::string)
+              .collect(),
+          ));
+        }
+      }
     }
 
     set
   }
 }
 
-impl<'src> Node<'src> for Warning {
+impl<'src> Node<'src> for Warning<'src> {
   fn tree(&self) -> Tree<'src> {
     unreachable!()
   }
@@ -5,6 +5,8 @@ use super::*;
 pub(crate) struct Parameter<'src> {
   /// An optional default expression
   pub(crate) default: Option<Expression<'src>>,
+  /// Documentation given by a `[param-doc]` attribute
+  pub(crate) doc: Option<String>,
   /// Export parameter as environment variable
   pub(crate) export: bool,
   /// The kind of parameter
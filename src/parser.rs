@@ -23,6 +23,10 @@ use {super::*, TokenKind::*};
 /// find it, it adds that token to the set. When the parser accepts a token, the
 /// set is cleared. If the parser finds a token which is unexpected, the
 /// contents of the set is printed in the resultant error message.
+///
+/// When a syntax error is encountered while parsing a top-level item, the
+/// parser records it, skips to the start of the next item, and continues, so
+/// that up to `MAX_ERRORS` errors can be reported at once.
 pub(crate) struct Parser<'tokens, 'src> {
   /// Source tokens
   tokens: &'tokens [Token<'src>],
@@ -32,11 +36,22 @@ pub(crate) struct Parser<'tokens, 'src> {
   expected: BTreeSet<TokenKind>,
   /// Current recursion depth
   depth: usize,
+  /// Errors recovered from so far
+  errors: Vec<CompileError<'src>>,
+  /// Delimiters of interpolations in recipes, which the lexer has already
+  /// used to lex their bodies
+  interpolation_delimiters: InterpolationDelimiters,
 }
 
+/// Maximum number of syntax errors reported before parsing is abandoned
+const MAX_ERRORS: usize = 16;
+
 impl<'tokens, 'src> Parser<'tokens, 'src> {
-  /// Parse `tokens` into an `Ast`
-  pub(crate) fn parse(tokens: &'tokens [Token<'src>]) -> CompileResult<'src, Ast<'src>> {
+  /// Parse `tokens` into an `Ast`, recovering from syntax errors and returning
+  /// all errors encountered
+  pub(crate) fn parse(
+    tokens: &'tokens [Token<'src>],
+  ) -> Result<Ast<'src>, Vec<CompileError<'src>>> {
     Self::new(tokens).parse_ast()
   }
 
@@ -47,6 +62,8 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
       expected: BTreeSet::new(),
       tokens,
       depth: 0,
+      errors: Vec::new(),
+      interpolation_delimiters: InterpolationDelimiters::scan_tokens(tokens),
     }
   }
 
@@ -263,9 +280,18 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     if let Some(recipe) = self.accept_name()? {
       Ok(Some(UnresolvedDependency {
         arguments: Vec::new(),
+        capture: None,
         recipe,
       }))
     } else if self.accepted(ParenL)? {
+      let capture = if self.next_are(&[Identifier, ColonEquals]) {
+        let capture = self.parse_name()?;
+        self.expect(ColonEquals)?;
+        Some(capture)
+      } else {
+        None
+      };
+
       let recipe = self.parse_name()?;
 
       let mut arguments = Vec::new();
@@ -274,7 +300,11 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
         arguments.push(self.parse_expression()?);
       }
 
-      Ok(Some(UnresolvedDependency { recipe, arguments }))
+      Ok(Some(UnresolvedDependency {
+        recipe,
+        arguments,
+        capture,
+      }))
     } else {
       Ok(None)
     }
@@ -286,7 +316,59 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
   }
 
   /// Parse a justfile, consumes self
-  fn parse_ast(mut self) -> CompileResult<'src, Ast<'src>> {
+  fn parse_ast(mut self) -> Result<Ast<'src>, Vec<CompileError<'src>>> {
+    let mut items = Vec::new();
+
+    let mut eol_since_last_comment = false;
+
+    if let Err(error) = self.accept(ByteOrderMark) {
+      return Err(vec![error]);
+    }
+
+    loop {
+      match self.parse_item(&mut items, &mut eol_since_last_comment) {
+        Ok(true) => break,
+        Ok(false) => {}
+        Err(error) => {
+          let internal = matches!(*error.kind, CompileErrorKind::Internal { .. });
+
+          self.errors.push(error);
+
+          if internal || self.errors.len() == MAX_ERRORS {
+            return Err(self.errors);
+          }
+
+          self.recover();
+        }
+      }
+    }
+
+    if !self.errors.is_empty() {
+      return Err(self.errors);
+    }
+
+    if self.next == self.tokens.len() {
+      Ok(Ast {
+        warnings: Vec::new(),
+        items,
+      })
+    } else {
+      Err(vec![self
+        .internal_error(format!(
+          "Parse completed with {} unparsed tokens",
+          self.tokens.len() - self.next,
+        ))
+        .unwrap_or_else(|error| error)])
+    }
+  }
+
+  /// Parse a single top-level item, appending it to `items`. Returns `true`
+  /// when the end of the token stream has been reached.
+  fn parse_item(
+    &mut self,
+    items: &mut Vec<Item<'src>>,
+    eol_since_last_comment: &mut bool,
+  ) -> CompileResult<'src, bool> {
     fn pop_doc_comment<'src>(
       items: &mut Vec<Item<'src>>,
       eol_since_last_comment: bool,
@@ -302,95 +384,137 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
       None
     }
 
-    let mut items = Vec::new();
-
-    let mut eol_since_last_comment = false;
-
-    self.accept(ByteOrderMark)?;
-
-    loop {
-      let next = self.next()?;
+    let next = self.next()?;
 
-      if let Some(comment) = self.accept(Comment)? {
-        items.push(Item::Comment(comment.lexeme().trim_end()));
-        self.expect_eol()?;
-        eol_since_last_comment = false;
-      } else if self.accepted(Eol)? {
-        eol_since_last_comment = true;
-      } else if self.accepted(Eof)? {
-        break;
-      } else if self.next_is(Identifier) {
-        match Keyword::from_lexeme(next.lexeme()) {
-          Some(Keyword::Alias) if self.next_are(&[Identifier, Identifier, ColonEquals]) => {
-            items.push(Item::Alias(self.parse_alias(BTreeSet::new())?));
-          }
-          Some(Keyword::Export) if self.next_are(&[Identifier, Identifier, ColonEquals]) => {
-            self.presume_keyword(Keyword::Export)?;
-            items.push(Item::Assignment(self.parse_assignment(true)?));
-          }
-          Some(Keyword::Set)
-            if self.next_are(&[Identifier, Identifier, ColonEquals])
-              || self.next_are(&[Identifier, Identifier, Comment, Eof])
-              || self.next_are(&[Identifier, Identifier, Comment, Eol])
-              || self.next_are(&[Identifier, Identifier, Eof])
-              || self.next_are(&[Identifier, Identifier, Eol]) =>
-          {
-            items.push(Item::Set(self.parse_set()?));
-          }
-          _ => {
-            if self.next_are(&[Identifier, ColonEquals]) {
-              items.push(Item::Assignment(self.parse_assignment(false)?));
-            } else {
-              let doc = pop_doc_comment(&mut items, eol_since_last_comment);
-              items.push(Item::Recipe(self.parse_recipe(
-                doc,
-                false,
-                BTreeSet::new(),
-              )?));
-            }
-          }
+    if let Some(comment) = self.accept(Comment)? {
+      items.push(Item::Comment(comment.lexeme().trim_end()));
+      self.expect_eol()?;
+      *eol_since_last_comment = false;
+    } else if self.accepted(Eol)? {
+      *eol_since_last_comment = true;
+    } else if self.accepted(Eof)? {
+      return Ok(true);
+    } else if self.next_is(Identifier) {
+      match Keyword::from_lexeme(next.lexeme()) {
+        Some(Keyword::Alias) if self.next_are(&[Identifier, Identifier, ColonEquals]) => {
+          items.push(Item::Alias(self.parse_alias(BTreeSet::new())?));
         }
-      } else if self.accepted(At)? {
-        let doc = pop_doc_comment(&mut items, eol_since_last_comment);
-        items.push(Item::Recipe(self.parse_recipe(
-          doc,
-          true,
-          BTreeSet::new(),
-        )?));
-      } else if let Some(attributes) = self.parse_attributes()? {
-        let next_keyword = Keyword::from_lexeme(self.next()?.lexeme());
-        match next_keyword {
-          Some(Keyword::Alias) if self.next_are(&[Identifier, Identifier, ColonEquals]) => {
-            items.push(Item::Alias(self.parse_alias(attributes)?));
-          }
-          _ => {
-            let quiet = self.accepted(At)?;
-            let doc = pop_doc_comment(&mut items, eol_since_last_comment);
-            items.push(Item::Recipe(self.parse_recipe(doc, quiet, attributes)?));
+        Some(Keyword::Export)
+          if self.next_are(&[Identifier, Identifier, Identifier, ColonEquals])
+            && self.rest().nth(1).map(|token| token.lexeme()) == Some(Keyword::Lazy.lexeme()) =>
+        {
+          self.presume_keyword(Keyword::Export)?;
+          let lazy = self.presume(Identifier)?;
+          let name = self.parse_name()?;
+          return Err(lazy.error(CompileErrorKind::ExportedLazyVariable {
+            variable: name.lexeme(),
+          }));
+        }
+        Some(Keyword::Export) if self.next_are(&[Identifier, Identifier, ColonEquals]) => {
+          self.presume_keyword(Keyword::Export)?;
+          items.push(Item::Assignment(self.parse_assignment(true, false)?));
+        }
+        Some(Keyword::Lazy) if self.next_are(&[Identifier, Identifier, ColonEquals]) => {
+          self.presume_keyword(Keyword::Lazy)?;
+          items.push(Item::Assignment(self.parse_assignment(false, true)?));
+        }
+        Some(Keyword::Fn) if self.next_are(&[Identifier, Identifier, ParenL]) => {
+          items.push(Item::Function(self.parse_function()?));
+        }
+        Some(Keyword::Set)
+          if self.next_are(&[Identifier, Identifier, ColonEquals])
+            || self.next_are(&[Identifier, Identifier, Comment, Eof])
+            || self.next_are(&[Identifier, Identifier, Comment, Eol])
+            || self.next_are(&[Identifier, Identifier, Eof])
+            || self.next_are(&[Identifier, Identifier, Eol]) =>
+        {
+          items.push(Item::Set(self.parse_set()?));
+        }
+        _ => {
+          if self.next_are(&[Identifier, ColonEquals]) {
+            items.push(Item::Assignment(self.parse_assignment(false, false)?));
+          } else {
+            let doc = pop_doc_comment(items, *eol_since_last_comment);
+            items.push(Item::Recipe(self.parse_recipe(
+              doc,
+              false,
+              BTreeSet::new(),
+            )?));
           }
         }
-      } else {
-        return Err(self.unexpected_token()?);
       }
+    } else if self.accepted(At)? {
+      let doc = pop_doc_comment(items, *eol_since_last_comment);
+      items.push(Item::Recipe(self.parse_recipe(
+        doc,
+        true,
+        BTreeSet::new(),
+      )?));
+    } else if self.next_are(&[BracketL, Identifier, ParenL, StringToken, ParenR, Colon])
+      && self.rest().nth(1).map(|token| token.lexeme()) == Some("group")
+    {
+      items.push(Item::Group(self.parse_group_declaration()?));
+    } else if let Some(attributes) = self.parse_attributes()? {
+      let next_keyword = Keyword::from_lexeme(self.next()?.lexeme());
+      match next_keyword {
+        Some(Keyword::Alias) if self.next_are(&[Identifier, Identifier, ColonEquals]) => {
+          items.push(Item::Alias(self.parse_alias(attributes)?));
+        }
+        _ => {
+          let quiet = self.accepted(At)?;
+          let doc = pop_doc_comment(items, *eol_since_last_comment);
+          items.push(Item::Recipe(self.parse_recipe(doc, quiet, attributes)?));
+        }
+      }
+    } else {
+      return Err(self.unexpected_token()?);
     }
 
-    if self.next == self.tokens.len() {
-      Ok(Ast {
-        warnings: Vec::new(),
-        items,
+    Ok(false)
+  }
+
+  /// Skip the remainder of the item in which a syntax error occurred,
+  /// including any indented lines that follow it, so that parsing can resume
+  /// at the start of the next top-level item.
+  fn recover(&mut self) {
+    self.expected.clear();
+    self.depth = 0;
+
+    let mut indentation = self.tokens[..self.next]
+      .iter()
+      .map(|token| match token.kind {
+        Indent => 1,
+        Dedent => -1,
+        _ => 0,
       })
-    } else {
-      Err(self.internal_error(format!(
-        "Parse completed with {} unparsed tokens",
-        self.tokens.len() - self.next,
-      ))?)
+      .sum::<isize>();
+
+    while let Some(token) = self.tokens.get(self.next) {
+      if token.kind == Eof {
+        break;
+      }
+
+      self.next += 1;
+
+      match token.kind {
+        Indent => indentation += 1,
+        Dedent => indentation -= 1,
+        _ => {}
+      }
+
+      if indentation <= 0
+        && matches!(token.kind, Eol | Dedent)
+        && !self.rest().next().is_some_and(|next| next.kind == Indent)
+      {
+        break;
+      }
     }
   }
 
   /// Parse an alias, e.g `alias name := target`
   fn parse_alias(
     &mut self,
-    attributes: BTreeSet<Attribute>,
+    attributes: BTreeSet<Attribute<'src>>,
   ) -> CompileResult<'src, Alias<'src, Name<'src>>> {
     self.presume_keyword(Keyword::Alias)?;
     let name = self.parse_name()?;
@@ -405,20 +529,54 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
   }
 
   /// Parse an assignment, e.g. `foo := bar`
-  fn parse_assignment(&mut self, export: bool) -> CompileResult<'src, Assignment<'src>> {
+  fn parse_assignment(
+    &mut self,
+    export: bool,
+    lazy: bool,
+  ) -> CompileResult<'src, Assignment<'src>> {
     let name = self.parse_name()?;
     self.presume_any(&[Equals, ColonEquals])?;
     let value = self.parse_expression()?;
     self.expect_eol()?;
     Ok(Assignment {
       export,
+      lazy,
       name,
       value,
     })
   }
 
-  /// Parse an expression, e.g. `1 + 2`
-  fn parse_expression(&mut self) -> CompileResult<'src, Expression<'src>> {
+  /// Parse a function definition, e.g. `fn image(tag) := "app:" + tag`
+  fn parse_function(&mut self) -> CompileResult<'src, UserFunction<'src>> {
+    self.presume_keyword(Keyword::Fn)?;
+    let name = self.parse_name()?;
+    self.presume(ParenL)?;
+
+    let mut parameters = Vec::new();
+
+    while !self.next_is(ParenR) {
+      parameters.push(self.parse_name()?);
+
+      if !self.accepted(Comma)? {
+        break;
+      }
+    }
+
+    self.expect(ParenR)?;
+    self.expect(ColonEquals)?;
+    let body = self.parse_expression()?;
+    self.expect_eol()?;
+
+    Ok(UserFunction {
+      body,
+      name,
+      parameters,
+    })
+  }
+
+  /// Return an error if the maximum recursion depth has been reached,
+  /// otherwise increment the current recursion depth
+  fn descend(&mut self) -> CompileResult<'src, ()> {
     if self.depth == if cfg!(windows) { 48 } else { 256 } {
       let token = self.next()?;
       return Err(CompileError::new(
@@ -429,6 +587,13 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
 
     self.depth += 1;
 
+    Ok(())
+  }
+
+  /// Parse an expression, e.g. `1 + 2`
+  fn parse_expression(&mut self) -> CompileResult<'src, Expression<'src>> {
+    self.descend()?;
+
     let expression = if self.accepted_keyword(Keyword::If)? {
       self.parse_conditional()?
     } else if self.accepted(Slash)? {
@@ -458,18 +623,7 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
 
   /// Parse a conditional, e.g. `if a == b { "foo" } else { "bar" }`
   fn parse_conditional(&mut self) -> CompileResult<'src, Expression<'src>> {
-    let lhs = self.parse_expression()?;
-
-    let operator = if self.accepted(BangEquals)? {
-      ConditionalOperator::Inequality
-    } else if self.accepted(EqualsTilde)? {
-      ConditionalOperator::RegexMatch
-    } else {
-      self.expect(EqualsEquals)?;
-      ConditionalOperator::Equality
-    };
-
-    let rhs = self.parse_expression()?;
+    let condition = self.parse_condition()?;
 
     self.expect(BraceL)?;
 
@@ -489,14 +643,137 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     };
 
     Ok(Expression::Conditional {
-      lhs: Box::new(lhs),
-      rhs: Box::new(rhs),
+      condition,
       then: Box::new(then),
       otherwise: Box::new(otherwise),
-      operator,
     })
   }
 
+  /// Parse the condition of a conditional, e.g. `a == b || !(c == d && e =~ f)`
+  fn parse_condition(&mut self) -> CompileResult<'src, Condition<'src>> {
+    self.descend()?;
+
+    let lhs = self.parse_conjunction()?;
+
+    let condition = if self.accepted(BarBar)? {
+      Condition::Or {
+        lhs: Box::new(lhs),
+        rhs: Box::new(self.parse_condition()?),
+      }
+    } else {
+      lhs
+    };
+
+    self.depth -= 1;
+
+    Ok(condition)
+  }
+
+  /// Parse a condition joined with `&&`, e.g. `a == b && c == d`
+  fn parse_conjunction(&mut self) -> CompileResult<'src, Condition<'src>> {
+    let lhs = self.parse_negation()?;
+
+    if self.accepted(AmpersandAmpersand)? {
+      Ok(Condition::And {
+        lhs: Box::new(lhs),
+        rhs: Box::new(self.parse_conjunction()?),
+      })
+    } else {
+      Ok(lhs)
+    }
+  }
+
+  /// Parse a possibly negated comparison or parenthesized condition, e.g.
+  /// `!(a == b)`
+  fn parse_negation(&mut self) -> CompileResult<'src, Condition<'src>> {
+    let mut negations = 0;
+
+    while self.accepted(Bang)? {
+      negations += 1;
+    }
+
+    let mut condition = if self.next_is_condition_group() {
+      self.presume(ParenL)?;
+      let contents = self.parse_condition()?;
+      self.expect(ParenR)?;
+      Condition::Group {
+        contents: Box::new(contents),
+      }
+    } else {
+      let lhs = self.parse_expression()?;
+
+      let operator = if self.accepted(BangEquals)? {
+        ConditionalOperator::Inequality
+      } else if self.accepted(EqualsTilde)? {
+        ConditionalOperator::RegexMatch
+      } else {
+        self.expect(EqualsEquals)?;
+        ConditionalOperator::Equality
+      };
+
+      let rhs = self.parse_expression()?;
+
+      Condition::Comparison {
+        lhs: Box::new(lhs),
+        rhs: Box::new(rhs),
+        operator,
+      }
+    };
+
+    for _ in 0..negations {
+      condition = Condition::Not {
+        condition: Box::new(condition),
+      };
+    }
+
+    Ok(condition)
+  }
+
+  /// Check if the next token opens a parenthesized condition, like `(a == b)`,
+  /// rather than a parenthesized expression, like `(a + b)`, by looking for a
+  /// comparison or logical operator inside the parentheses which is not part
+  /// of a nested conditional expression
+  fn next_is_condition_group(&mut self) -> bool {
+    if !self.next_is(ParenL) {
+      return false;
+    }
+
+    let mut braces = 0usize;
+    let mut conditionals = 0usize;
+    let mut parens = 0usize;
+
+    for token in self.rest() {
+      match token.kind {
+        ParenL => parens += 1,
+        ParenR => {
+          parens -= 1;
+          if parens == 0 {
+            return false;
+          }
+        }
+        BraceL => {
+          if braces == 0 && conditionals > 0 {
+            conditionals -= 1;
+          }
+          braces += 1;
+        }
+        BraceR => braces = braces.saturating_sub(1),
+        Identifier if parens == 1 && braces == 0 && Keyword::If == token.lexeme() => {
+          conditionals += 1;
+        }
+        AmpersandAmpersand | Bang | BangEquals | BarBar | EqualsEquals | EqualsTilde
+          if parens == 1 && braces == 0 && conditionals == 0 =>
+        {
+          return true;
+        }
+        Eof => return false,
+        _ => {}
+      }
+    }
+
+    false
+  }
+
   /// Parse a value, e.g. `(bar)`
   fn parse_value(&mut self) -> CompileResult<'src, Expression<'src>> {
     if self.next_is(StringToken) {
@@ -619,7 +896,7 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     &mut self,
     doc: Option<&'src str>,
     quiet: bool,
-    attributes: BTreeSet<Attribute>,
+    attributes: BTreeSet<Attribute<'src>>,
   ) -> CompileResult<'src, UnresolvedRecipe<'src>> {
     let name = self.parse_name()?;
 
@@ -679,10 +956,26 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
 
     let body = self.parse_body()?;
 
+    let mut parameters = positional
+      .into_iter()
+      .chain(variadic)
+      .collect::<Vec<Parameter>>();
+
+    for attribute in &attributes {
+      if let Attribute::ParamDoc { parameter, doc } = attribute {
+        for candidate in &mut parameters {
+          if candidate.name.lexeme() == parameter.cooked {
+            candidate.doc = Some(doc.cooked.clone());
+          }
+        }
+      }
+    }
+
     Ok(Recipe {
-      parameters: positional.into_iter().chain(variadic).collect(),
+      interpolation_delimiters: self.interpolation_delimiters.clone(),
+      parameters,
       private: name.lexeme().starts_with('_'),
-      shebang: body.first().map_or(false, Line::is_shebang),
+      shebang: body.first().is_some_and(Line::is_shebang),
       attributes,
       priors,
       body,
@@ -707,6 +1000,7 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
 
     Ok(Parameter {
       default,
+      doc: None,
       export,
       kind,
       name,
@@ -746,7 +1040,7 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
       }
     }
 
-    while lines.last().map_or(false, Line::is_empty) {
+    while lines.last().is_some_and(Line::is_empty) {
       lines.pop();
     }
 
@@ -790,11 +1084,21 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
       Keyword::AllowDuplicateRecipes => {
         Some(Setting::AllowDuplicateRecipes(self.parse_set_bool()?))
       }
+      Keyword::CaseInsensitiveRecipes => {
+        Some(Setting::CaseInsensitiveRecipes(self.parse_set_bool()?))
+      }
+      Keyword::CleanEnv => Some(Setting::CleanEnv(self.parse_set_bool()?)),
       Keyword::DotenvLoad => Some(Setting::DotenvLoad(self.parse_set_bool()?)),
+      Keyword::DotenvRequired => Some(Setting::DotenvRequired(self.parse_set_bool()?)),
       Keyword::Export => Some(Setting::Export(self.parse_set_bool()?)),
       Keyword::Fallback => Some(Setting::Fallback(self.parse_set_bool()?)),
+      Keyword::FmtAlignAssignments => Some(Setting::FmtAlignAssignments(self.parse_set_bool()?)),
       Keyword::IgnoreComments => Some(Setting::IgnoreComments(self.parse_set_bool()?)),
+      Keyword::InheritLoginEnv => Some(Setting::InheritLoginEnv(self.parse_set_bool()?)),
+      Keyword::InteractiveArguments => Some(Setting::InteractiveArguments(self.parse_set_bool()?)),
+      Keyword::LocalOverlay => Some(Setting::LocalOverlay(self.parse_set_bool()?)),
       Keyword::PositionalArguments => Some(Setting::PositionalArguments(self.parse_set_bool()?)),
+      Keyword::SourceMap => Some(Setting::SourceMap(self.parse_set_bool()?)),
       Keyword::WindowsPowershell => Some(Setting::WindowsPowerShell(self.parse_set_bool()?)),
       _ => None,
     };
@@ -806,10 +1110,27 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     self.expect(ColonEquals)?;
 
     let set_value = match keyword {
+      Keyword::CommandLog => Some(Setting::CommandLog(self.parse_string_literal()?.cooked)),
       Keyword::DotenvFilename => Some(Setting::DotenvFilename(self.parse_string_literal()?.cooked)),
       Keyword::DotenvPath => Some(Setting::DotenvPath(self.parse_string_literal()?.cooked)),
+      Keyword::Echo => Some(Setting::Echo(self.parse_echo_mode()?)),
+      Keyword::EnvHook => Some(Setting::EnvHook(self.parse_shell()?)),
+      Keyword::EnvPassthrough => Some(Setting::EnvPassthrough(self.parse_string_list()?)),
+      Keyword::ExportPrefix => Some(Setting::ExportPrefix(self.parse_export_prefix()?)),
+      Keyword::FallbackJustfiles => Some(Setting::FallbackJustfiles(self.parse_string_list()?)),
+      Keyword::FmtBlankLines => Some(Setting::FmtBlankLines(self.parse_fmt_blank_lines()?)),
+      Keyword::FmtIndent => Some(Setting::FmtIndent(self.parse_fmt_indent()?)),
+      Keyword::InterpolationDelimiters => Some(Setting::InterpolationDelimiters(
+        self.parse_interpolation_delimiters(name)?,
+      )),
+      Keyword::LineTimeout => Some(Setting::LineTimeout(self.parse_interval()?)),
+      Keyword::DotenvFilenames => Some(Setting::DotenvFilenames(self.parse_string_list()?)),
+      Keyword::RequiredEnv => Some(Setting::RequiredEnv(self.parse_string_list()?)),
+      Keyword::RequiredVersion => Some(Setting::RequiredVersion(self.parse_required_version()?)),
       Keyword::Shell => Some(Setting::Shell(self.parse_shell()?)),
+      Keyword::ShellFallbacks => Some(Setting::ShellFallbacks(self.parse_shell_list()?)),
       Keyword::Tempdir => Some(Setting::Tempdir(self.parse_string_literal()?.cooked)),
+      Keyword::WindowsShebang => Some(Setting::WindowsShebang(self.parse_windows_shebang()?)),
       Keyword::WindowsShell => Some(Setting::WindowsShell(self.parse_shell()?)),
       _ => None,
     };
@@ -823,6 +1144,132 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     }))
   }
 
+  /// Parse an interpolation delimiters setting value, e.g. `['<%', '%>']`.
+  /// The setting is found before lexing, so that it applies to all recipes,
+  /// and is only validated here.
+  fn parse_interpolation_delimiters(
+    &mut self,
+    name: Name<'src>,
+  ) -> CompileResult<'src, Vec<StringLiteral<'src>>> {
+    let delimiters = self.parse_string_list()?;
+
+    let error = |message: &str| {
+      name.error(CompileErrorKind::InvalidInterpolationDelimiters {
+        message: message.into(),
+      })
+    };
+
+    let [open, close] = &delimiters[..] else {
+      return Err(error(
+        "Interpolation delimiters must be a list of two strings, the opening and closing delimiters",
+      ));
+    };
+
+    if open.cooked != open.raw || close.cooked != close.raw {
+      return Err(error(
+        "Interpolation delimiters may not contain escape sequences",
+      ));
+    }
+
+    InterpolationDelimiters::new(&open.cooked, &close.cooked).map_err(|message| error(&message))?;
+
+    Ok(delimiters)
+  }
+
+  /// Parse an echo setting value, e.g. `'prefixed'`
+  fn parse_echo_mode(&mut self) -> CompileResult<'src, EchoMode> {
+    let token = self.next()?;
+
+    self
+      .parse_string_literal()?
+      .cooked
+      .parse()
+      .map_err(|message| token.error(CompileErrorKind::InvalidEchoMode { message }))
+  }
+
+  /// Parse a `windows-shebang` setting value, e.g. `'native'`
+  fn parse_windows_shebang(&mut self) -> CompileResult<'src, WindowsShebang> {
+    let token = self.next()?;
+
+    self
+      .parse_string_literal()?
+      .cooked
+      .parse()
+      .map_err(|message| token.error(CompileErrorKind::InvalidWindowsShebang { message }))
+  }
+
+  /// Parse an export prefix setting value, which must itself be a valid
+  /// environment variable name, e.g. `'JUST_'`
+  fn parse_export_prefix(&mut self) -> CompileResult<'src, String> {
+    let token = self.next()?;
+
+    let prefix = self.parse_string_literal()?.cooked;
+
+    if !is_env_var_name(&prefix) {
+      return Err(token.error(CompileErrorKind::InvalidExportPrefix { prefix }));
+    }
+
+    Ok(prefix)
+  }
+
+  /// Parse a `fmt-blank-lines` setting value, the number of blank lines
+  /// `--fmt` puts between recipes, e.g. `'2'`
+  fn parse_fmt_blank_lines(&mut self) -> CompileResult<'src, usize> {
+    let token = self.next()?;
+
+    let value = self.parse_string_literal()?.cooked;
+
+    value.parse().map_err(|_| {
+      token.error(CompileErrorKind::InvalidFormatSetting {
+        message: format!("`fmt-blank-lines` must be a whole number, but is `{value}`"),
+      })
+    })
+  }
+
+  /// Parse a `fmt-indent` setting value, the spaces or tabs `--fmt` indents
+  /// recipe bodies with, e.g. `'  '` or `"\t"`
+  fn parse_fmt_indent(&mut self) -> CompileResult<'src, String> {
+    let token = self.next()?;
+
+    let indent = self.parse_string_literal()?.cooked;
+
+    if indent.is_empty() || !indent.chars().all(|c| c == ' ' || c == '\t') {
+      return Err(token.error(CompileErrorKind::InvalidFormatSetting {
+        message: "`fmt-indent` must be one or more spaces or tabs".into(),
+      }));
+    }
+
+    Ok(indent)
+  }
+
+  /// Parse an interval setting value, e.g. `'30s'`
+  fn parse_interval(&mut self) -> CompileResult<'src, Interval> {
+    let token = self.next()?;
+
+    self
+      .parse_string_literal()?
+      .cooked
+      .parse()
+      .map_err(|message| token.error(CompileErrorKind::InvalidInterval { message }))
+  }
+
+  /// Parse a required version setting value, a semantic version requirement
+  /// like `>=1.14.0`
+  fn parse_required_version(&mut self) -> CompileResult<'src, String> {
+    let token = self.next()?;
+
+    let requirement = self.parse_string_literal()?.cooked;
+
+    if let Err(err) = semver::VersionReq::parse(&requirement) {
+      return Err(token.error(CompileErrorKind::InvalidRequiredVersion {
+        requirement,
+        message: err.to_string(),
+      }));
+    }
+
+    Ok(requirement)
+  }
+
   /// Parse a shell setting value
   fn parse_shell(&mut self) -> CompileResult<'src, Shell<'src>> {
     self.expect(BracketL)?;
@@ -846,30 +1293,46 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     Ok(Shell { arguments, command })
   }
 
-  /// Parse recipe attributes
-  fn parse_attributes(&mut self) -> CompileResult<'src, Option<BTreeSet<Attribute>>> {
-    let mut attributes = BTreeMap::new();
+  /// Parse a list of shells, e.g. `[['bash', '-cu'], ['sh', '-cu']]`
+  fn parse_shell_list(&mut self) -> CompileResult<'src, Vec<Shell<'src>>> {
+    self.parse_list(Self::parse_shell)
+  }
 
-    while self.accepted(BracketL)? {
-      loop {
-        let name = self.parse_name()?;
-        let attribute = Attribute::from_name(name).ok_or_else(|| {
-          name.error(CompileErrorKind::UnknownAttribute {
-            attribute: name.lexeme(),
-          })
-        })?;
-        if let Some(line) = attributes.get(&attribute) {
-          return Err(name.error(CompileErrorKind::DuplicateAttribute {
-            attribute: name.lexeme(),
-            first: *line,
-          }));
-        }
-        attributes.insert(attribute, name.line);
+  /// Parse a list of strings, e.g. `['foo', 'bar']`
+  fn parse_string_list(&mut self) -> CompileResult<'src, Vec<StringLiteral<'src>>> {
+    self.parse_list(Self::parse_string_literal)
+  }
 
-        if !self.accepted(Comma)? {
-          break;
-        }
+  /// Parse a bracketed, comma-separated list of items with `parse_item`,
+  /// which may have a trailing comma
+  fn parse_list<T>(
+    &mut self,
+    parse_item: fn(&mut Self) -> CompileResult<'src, T>,
+  ) -> CompileResult<'src, Vec<T>> {
+    self.expect(BracketL)?;
+
+    let mut items = Vec::new();
+
+    while !self.next_is(BracketR) {
+      items.push(parse_item(self)?);
+
+      if !self.accepted(Comma)? {
+        break;
       }
+    }
+
+    self.expect(BracketR)?;
+
+    Ok(items)
+  }
+
+  /// Parse recipe attributes
+  fn parse_attributes(&mut self) -> CompileResult<'src, Option<BTreeSet<Attribute<'src>>>> {
+    let mut attributes = BTreeSet::new();
+    let mut lines = BTreeMap::new();
+
+    while self.accepted(BracketL)? {
+      self.parse_attribute_list(&mut attributes, &mut lines)?;
       self.expect(BracketR)?;
       self.expect_eol()?;
     }
@@ -877,9 +1340,100 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     if attributes.is_empty() {
       Ok(None)
     } else {
-      Ok(Some(attributes.into_keys().collect()))
+      Ok(Some(attributes))
     }
   }
+
+  /// Parse a comma-separated list of attributes into `attributes`, using
+  /// `lines` to record the line on which each non-repeatable attribute was
+  /// first used
+  fn parse_attribute_list(
+    &mut self,
+    attributes: &mut BTreeSet<Attribute<'src>>,
+    lines: &mut BTreeMap<AttributeDiscriminant, usize>,
+  ) -> CompileResult<'src, ()> {
+    loop {
+      let name = self.parse_name()?;
+
+      let mut arguments = Vec::new();
+
+      // the value of an `[env]` attribute is an expression, rather than a
+      // string literal, so that it can refer to variables and parameters
+      if name.lexeme() == "env" {
+        self.expect(ParenL)?;
+        let variable = self.parse_string_literal()?;
+        self.expect(Comma)?;
+        let value = self.parse_expression()?;
+        self.expect(ParenR)?;
+
+        attributes.insert(Attribute::Env {
+          name: variable,
+          value,
+        });
+
+        if !self.accepted(Comma)? {
+          break;
+        }
+
+        continue;
+      }
+
+      if self.accepted(ParenL)? {
+        while !self.next_is(ParenR) {
+          arguments.push(self.parse_string_literal()?);
+
+          if !self.accepted(Comma)? {
+            break;
+          }
+        }
+
+        self.expect(ParenR)?;
+      }
+
+      let attribute = Attribute::new(name, arguments)?;
+
+      let discriminant = AttributeDiscriminant::from(&attribute);
+
+      if !discriminant.repeatable() {
+        if let Some(line) = lines.insert(discriminant, name.line) {
+          return Err(name.error(CompileErrorKind::DuplicateAttribute {
+            attribute: name.lexeme(),
+            first: line,
+          }));
+        }
+      }
+
+      attributes.insert(attribute);
+
+      if !self.accepted(Comma)? {
+        break;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Parse a group declaration, `[group('NAME'): ATTRIBUTE…]`
+  fn parse_group_declaration(&mut self) -> CompileResult<'src, GroupDeclaration<'src>> {
+    self.expect(BracketL)?;
+    let keyword = self.parse_name()?;
+    self.expect(ParenL)?;
+    let name = self.parse_string_literal()?;
+    self.expect(ParenR)?;
+    self.expect(Colon)?;
+
+    let mut attributes = BTreeSet::new();
+    self.parse_attribute_list(&mut attributes, &mut BTreeMap::new())?;
+
+    self.expect(BracketR)?;
+    self.expect_eol()?;
+
+    Ok(GroupDeclaration {
+      attributes,
+      keyword,
+      name,
+    })
+  }
 }
 
 #[cfg(test)]
@@ -906,7 +1460,8 @@ mod tests {
 
   fn test(text: &str, want: Tree) {
     let unindented = unindent(text);
-    let tokens = Lexer::lex(&unindented).expect("lexing failed");
+    let tokens =
+      Lexer::lex(&unindented, &InterpolationDelimiters::scan(&unindented)).expect("lexing failed");
     let justfile = Parser::parse(&tokens).expect("parsing failed");
     let have = justfile.tree();
     if have != want {
@@ -943,11 +1498,13 @@ mod tests {
     length: usize,
     kind: CompileErrorKind,
   ) {
-    let tokens = Lexer::lex(src).expect("Lexing failed in parse test...");
+    let tokens =
+      Lexer::lex(src, &InterpolationDelimiters::default()).expect("Lexing failed in parse test...");
 
     match Parser::parse(&tokens) {
       Ok(_) => panic!("Parsing unexpectedly succeeded"),
-      Err(have) => {
+      Err(mut errors) => {
+        let have = errors.remove(0);
         let want = CompileError {
           token: Token {
             kind: have.token.kind,
@@ -982,6 +1539,18 @@ mod tests {
     tree: (justfile),
   }
 
+  test! {
+    name: group_declaration,
+    text: "[group('deploy'): confirm, no-cd]\n\nfoo:",
+    tree: (justfile (group "deploy" confirm no_cd) (recipe foo)),
+  }
+
+  test! {
+    name: group_declaration_env,
+    text: "[group('deploy'): env('TARGET', 'prod' + x)]\n\nfoo:",
+    tree: (justfile (group "deploy" env) (recipe foo)),
+  }
+
   test! {
     name: whitespace,
     text: " ",
@@ -1036,6 +1605,12 @@ mod tests {
     tree: (justfile (assignment #export x "hello")),
   }
 
+  test! {
+    name: lazy,
+    text: r#"lazy x := "hello""#,
+    tree: (justfile (assignment #lazy x "hello")),
+  }
+
   test! {
     name: export_equals,
     text: r#"export x := "hello""#,
@@ -1094,6 +1669,33 @@ mod tests {
     tree: (justfile (assignment x (call env_var y))),
   }
 
+  test! {
+    name: function,
+    text: "fn image(tag) := 'app:' + tag",
+    tree: (justfile (fn image (params tag) (+ "app:" tag))),
+  }
+
+  test! {
+    name: function_no_parameters,
+    text: "fn f() := 'a'",
+    tree: (justfile (fn f (params) "a")),
+  }
+
+  test! {
+    name: function_multiple_parameters,
+    text: "fn f(a, b,) := a + b\nx := f('a', 'b')",
+    tree: (justfile
+      (fn f (params a b) (+ a b))
+      (assignment x (call f "a" "b"))
+    ),
+  }
+
+  test! {
+    name: variable_named_fn,
+    text: "fn := 'a'",
+    tree: (justfile (assignment fn "a")),
+  }
+
   test! {
     name: call_multiple_args,
     text: "x := env_var_or_default(y, z)",
@@ -1212,6 +1814,12 @@ mod tests {
     tree: (justfile (recipe foo (deps (bar baz)))),
   }
 
+  test! {
+    name: recipe_dependency_capture,
+    text: "foo: (v := bar 'baz') && qux",
+    tree: (justfile (recipe foo (deps (capture v (bar "baz"))) (sups qux))),
+  }
+
   test! {
     name: recipe_dependency_argument_concatenation,
     text: "foo: (bar 'a' + 'b' 'c' + 'd')",
@@ -1820,6 +2428,12 @@ mod tests {
     tree: (justfile (set export false)),
   }
 
+  test! {
+    name: set_export_prefix,
+    text: "set export-prefix := 'JUST_'",
+    tree: (justfile (set export_prefix "JUST_")),
+  }
+
   test! {
     name: set_dotenv_load_implicit,
     text: "set dotenv-load",
@@ -1832,6 +2446,96 @@ mod tests {
     tree: (justfile (set allow_duplicate_recipes true)),
   }
 
+  test! {
+    name: set_case_insensitive_recipes,
+    text: "set case-insensitive-recipes := true",
+    tree: (justfile (set case_insensitive_recipes true)),
+  }
+
+  test! {
+    name: set_clean_env,
+    text: "set clean-env",
+    tree: (justfile (set clean_env true)),
+  }
+
+  test! {
+    name: set_env_passthrough,
+    text: "set env-passthrough := ['HOME', 'PATH']",
+    tree: (justfile (set env_passthrough "HOME" "PATH")),
+  }
+
+  test! {
+    name: set_inherit_login_env,
+    text: "set inherit-login-env := true",
+    tree: (justfile (set inherit_login_env true)),
+  }
+
+  test! {
+    name: set_interactive_arguments,
+    text: "set interactive-arguments",
+    tree: (justfile (set interactive_arguments true)),
+  }
+
+  test! {
+    name: set_source_map,
+    text: "set source-map",
+    tree: (justfile (set source_map true)),
+  }
+
+  test! {
+    name: set_local_overlay,
+    text: "set local-overlay",
+    tree: (justfile (set local_overlay true)),
+  }
+
+  test! {
+    name: set_dotenv_required_implicit,
+    text: "set dotenv-required",
+    tree: (justfile (set dotenv_required true)),
+  }
+
+  test! {
+    name: set_dotenv_filenames,
+    text: "set dotenv-filenames := ['.env', '.env.local']",
+    tree: (justfile (set dotenv_filenames ".env" ".env.local")),
+  }
+
+  test! {
+    name: set_interpolation_delimiters,
+    text: "set interpolation-delimiters := ['<%', '%>']\nfoo:\n echo <%a%>",
+    tree: (justfile (set interpolation_delimiters "<%" "%>") (recipe foo (body ("echo " (a))))),
+  }
+
+  test! {
+    name: set_required_env,
+    text: "set required-env := ['FOO', \"BAR\",]",
+    tree: (justfile (set required_env "FOO" "BAR")),
+  }
+
+  test! {
+    name: set_fallback_justfiles,
+    text: "set fallback-justfiles := ['../justfile', '../../ops/justfile']",
+    tree: (justfile (set fallback_justfiles "../justfile" "../../ops/justfile")),
+  }
+
+  test! {
+    name: set_shell_fallbacks,
+    text: "set shell-fallbacks := [['bash', '-cu'], [\"sh\"],]",
+    tree: (justfile (set shell_fallbacks ("bash" "-cu") ("sh"))),
+  }
+
+  test! {
+    name: set_required_version,
+    text: "set required-version := '>=1.14.0'",
+    tree: (justfile (set required_version ">=1.14.0")),
+  }
+
+  test! {
+    name: set_required_env_empty,
+    text: "set required-env := []",
+    tree: (justfile (set required_env)),
+  }
+
   test! {
     name: set_dotenv_load_true,
     text: "set dotenv-load := true",
@@ -1862,6 +2566,18 @@ mod tests {
     tree: (justfile (set positional_arguments false)),
   }
 
+  test! {
+    name: set_env_hook,
+    text: "set env-hook := ['direnv', 'export', 'json']",
+    tree: (justfile (set env_hook "direnv" "export" "json")),
+  }
+
+  test! {
+    name: set_line_timeout,
+    text: "set line-timeout := '2m'",
+    tree: (justfile (set line_timeout "2m")),
+  }
+
   test! {
     name: set_shell_no_arguments,
     text: "set shell := ['tclsh']",
@@ -1916,6 +2632,18 @@ mod tests {
     tree: (justfile (set windows_powershell false)),
   }
 
+  test! {
+    name: set_windows_shebang,
+    text: "set windows-shebang := 'native'",
+    tree: (justfile (set windows_shebang "native")),
+  }
+
+  test! {
+    name: set_command_log,
+    text: "set command-log := 'commands.log'",
+    tree: (justfile (set command_log "commands.log")),
+  }
+
   test! {
     name: conditional,
     text: "a := if b == c { d } else { e }",
@@ -1958,6 +2686,48 @@ mod tests {
     tree: (justfile (assignment a (if b == c d (if b == c d e)))),
   }
 
+  test! {
+    name: conditional_and,
+    text: "a := if b == c && d != e { f } else { g }",
+    tree: (justfile (assignment a (if (&& (b == c) (d != e)) f g))),
+  }
+
+  test! {
+    name: conditional_or,
+    text: "a := if b == c || d != e { f } else { g }",
+    tree: (justfile (assignment a (if (|| (b == c) (d != e)) f g))),
+  }
+
+  test! {
+    name: conditional_and_binds_tighter_than_or,
+    text: "a := if b == c || d == e && f == g { h } else { i }",
+    tree: (justfile (assignment a (if (|| (b == c) (&& (d == e) (f == g))) h i))),
+  }
+
+  test! {
+    name: conditional_not,
+    text: "a := if !b == c { d } else { e }",
+    tree: (justfile (assignment a (if (! (b == c)) d e))),
+  }
+
+  test! {
+    name: conditional_not_group,
+    text: "a := if !(b == c || d == e) && f == g { h } else { i }",
+    tree: (justfile (assignment a (if (&& (! ((|| (b == c) (d == e)))) (f == g)) h i))),
+  }
+
+  test! {
+    name: conditional_expression_group,
+    text: "a := if (b + c) == d && e == f { g } else { h }",
+    tree: (justfile (assignment a (if (&& (((+ b c)) == d) (e == f)) g h))),
+  }
+
+  test! {
+    name: conditional_group_containing_conditional,
+    text: "a := if (if b == c { d } else { e }) == f { g } else { h }",
+    tree: (justfile (assignment a (if ((if b == c d e)) == f g h))),
+  }
+
   error! {
     name:   alias_syntax_multiple_rhs,
     input:  "alias foo := bar baz",
@@ -2113,6 +2883,30 @@ mod tests {
     kind:   InvalidEscapeSequence{character: 'b'},
   }
 
+  error! {
+    name:   invalid_line_timeout,
+    input:  "set line-timeout := '10 days'",
+    offset: 20,
+    line:   0,
+    column: 20,
+    width:  9,
+    kind:   InvalidInterval {
+      message: "`days` is not a valid interval unit, expected `ms`, `s`, `m`, or `h`".into(),
+    },
+  }
+
+  error! {
+    name:   invalid_export_prefix,
+    input:  "set export-prefix := 'JUST-'",
+    offset: 21,
+    line:   0,
+    column: 21,
+    width:  7,
+    kind:   InvalidExportPrefix {
+      prefix: "JUST-".into(),
+    },
+  }
+
   error! {
     name:   bad_export,
     input:  "export a",
@@ -2250,57 +3044,90 @@ mod tests {
   }
 
   error! {
-    name:   set_unknown,
-    input:  "set shall := []",
-    offset: 4,
+    name:   attribute_unexpected_argument,
+    input:  "[no-cd('foo')]\nsome_recipe:\n @exit 3",
+    offset: 1,
     line:   0,
-    column: 4,
+    column: 1,
     width:  5,
-    kind:   UnknownSetting {
-      setting: "shall",
+    kind:   AttributeArgumentCountMismatch {
+      attribute: "no-cd",
+      found: 1,
+      min: 0,
+      max: 0,
     },
   }
 
   error! {
-    name:   set_shell_non_string,
-    input:  "set shall := []",
-    offset: 4,
+    name:   env_attribute_missing_value,
+    input:  "[env('A')]\nsome_recipe:\n @exit 3",
+    offset: 8,
     line:   0,
-    column: 4,
-    width:  5,
-    kind:   UnknownSetting {
-      setting: "shall",
+    column: 8,
+    width:  1,
+    kind:   UnexpectedToken {
+      expected: vec![Comma],
+      found: ParenR,
     },
   }
 
   error! {
-    name:   unknown_function,
-    input:  "a := foo()",
-    offset: 5,
+    name:   attribute_missing_argument,
+    input:  "[working-directory]\nsome_recipe:\n @exit 3",
+    offset: 1,
     line:   0,
-    column: 5,
-    width:  3,
-    kind:   UnknownFunction{function: "foo"},
+    column: 1,
+    width:  17,
+    kind:   AttributeArgumentCountMismatch {
+      attribute: "working-directory",
+      found: 0,
+      min: 1,
+      max: 1,
+    },
   }
 
   error! {
-    name:   unknown_function_in_interpolation,
-    input:  "a:\n echo {{bar()}}",
-    offset: 11,
+    name:   duplicate_attribute_with_different_arguments,
+    input:  "[working-directory('a')]\n[working-directory('b')]\nsome_recipe:\n @exit 3",
+    offset: 26,
     line:   1,
-    column: 8,
-    width:  3,
-    kind:   UnknownFunction{function: "bar"},
+    column: 1,
+    width:  17,
+    kind:   DuplicateAttribute { attribute: "working-directory", first: 0 },
+  }
+
+  error! {
+    name:   group_declaration_duplicate_attribute,
+    input:  "[group('deploy'): no-cd, no-cd]",
+    offset: 25,
+    line:   0,
+    column: 25,
+    width:  5,
+    kind:   DuplicateAttribute { attribute: "no-cd", first: 0 },
   }
 
   error! {
-    name:   unknown_function_in_default,
-    input:  "a f=baz():",
+    name:   set_unknown,
+    input:  "set shall := []",
     offset: 4,
     line:   0,
     column: 4,
-    width:  3,
-    kind:   UnknownFunction{function: "baz"},
+    width:  5,
+    kind:   UnknownSetting {
+      setting: "shall",
+    },
+  }
+
+  error! {
+    name:   set_shell_non_string,
+    input:  "set shall := []",
+    offset: 4,
+    line:   0,
+    column: 4,
+    width:  5,
+    kind:   UnknownSetting {
+      setting: "shall",
+    },
   }
 
   error! {
@@ -2400,4 +3227,26 @@ mod tests {
       expected: 3..3,
     },
   }
+
+  #[test]
+  fn recover_after_error() {
+    let src = "foo bar\n  echo foo\nx := \nbaz:\n";
+    let tokens = Lexer::lex(src, &InterpolationDelimiters::default()).unwrap();
+    let errors = Parser::parse(&tokens).unwrap_err();
+    assert_eq!(
+      errors
+        .iter()
+        .map(|error| error.token.line)
+        .collect::<Vec<usize>>(),
+      [0, 2],
+    );
+  }
+
+  #[test]
+  fn error_limit() {
+    let src = "foo\n".repeat(MAX_ERRORS * 2);
+    let tokens = Lexer::lex(&src, &InterpolationDelimiters::default()).unwrap();
+    let errors = Parser::parse(&tokens).unwrap_err();
+    assert_eq!(errors.len(), MAX_ERRORS);
+  }
 }
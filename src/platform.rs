@@ -8,6 +8,7 @@ impl PlatformInterface for Platform {
     path: &Path,
     working_directory: Option<&Path>,
     _shebang: Shebang,
+    _windows_shebang: WindowsShebang,
   ) -> Result<Command, OutputError> {
     // shebang scripts can be executed directly on unix
     let mut cmd = Command::new(path);
@@ -19,6 +20,13 @@ impl PlatformInterface for Platform {
     Ok(cmd)
   }
 
+  fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::metadata(path)
+      .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+  }
+
   fn set_execute_permission(path: &Path) -> Result<(), io::Error> {
     use std::os::unix::fs::PermissionsExt;
 
@@ -44,6 +52,254 @@ impl PlatformInterface for Platform {
       .map(str::to_string)
       .ok_or_else(|| String::from("Error getting current directory: unicode decode error"))
   }
+
+  fn install_signal_handler(handler: fn(i32)) -> Result<(), io::Error> {
+    use std::{
+      ptr,
+      sync::atomic::{AtomicI32, Ordering},
+      thread,
+    };
+
+    // The signal handler itself may only perform async-signal-safe
+    // operations, so it writes the signal number to a pipe, which is read by a
+    // thread that calls `handler`.
+    static WRITE_FD: AtomicI32 = AtomicI32::new(-1);
+
+    extern "C" fn on_signal(signal: libc::c_int) {
+      #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+      let byte = signal as u8;
+      unsafe {
+        libc::write(
+          WRITE_FD.load(Ordering::Relaxed),
+          ptr::addr_of!(byte).cast(),
+          1,
+        );
+      }
+    }
+
+    let mut fds = [0; 2];
+
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } == -1 {
+      return Err(io::Error::last_os_error());
+    }
+
+    for fd in fds {
+      if unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) } == -1 {
+        return Err(io::Error::last_os_error());
+      }
+    }
+
+    let [read_fd, write_fd] = fds;
+
+    WRITE_FD.store(write_fd, Ordering::Relaxed);
+
+    for signal in [libc::SIGHUP, libc::SIGINT, libc::SIGTERM] {
+      let mut action: libc::sigaction = unsafe { mem::zeroed() };
+      action.sa_sigaction = on_signal as *const () as libc::sighandler_t;
+      action.sa_flags = libc::SA_RESTART;
+
+      if unsafe { libc::sigaction(signal, &action, ptr::null_mut()) } == -1 {
+        return Err(io::Error::last_os_error());
+      }
+    }
+
+    thread::spawn(move || loop {
+      let mut byte = 0u8;
+
+      match unsafe { libc::read(read_fd, ptr::addr_of_mut!(byte).cast(), 1) } {
+        1 => handler(byte.into()),
+        -1 if io::Error::last_os_error().kind() == io::ErrorKind::Interrupted => {}
+        _ => break,
+      }
+    });
+
+    Ok(())
+  }
+
+  fn run_in_process_group(
+    command: &mut Command,
+    monitor: &Monitor,
+  ) -> Result<ExitStatus, io::Error> {
+    use std::os::unix::process::{CommandExt, ExitStatusExt};
+
+    unsafe {
+      command.pre_exec(|| {
+        if libc::setpgid(0, 0) == -1 {
+          Err(io::Error::last_os_error())
+        } else {
+          Ok(())
+        }
+      });
+    }
+
+    let terminal = ForegroundTerminal::open();
+
+    monitor.configure(command);
+
+    let mut child = command.spawn()?;
+
+    let id = child.id();
+
+    #[allow(clippy::cast_possible_wrap)]
+    let pid = id as libc::pid_t;
+
+    let watch = monitor.watch(&mut child, move || terminate_process_group(pid));
+
+    // Also set the process group from the parent, so that it is in place
+    // before we hand over the terminal, regardless of which process runs first
+    unsafe {
+      libc::setpgid(pid, pid);
+    }
+
+    InterruptHandler::instance().add_process_group(id);
+
+    if let Some(terminal) = &terminal {
+      terminal.set_foreground(pid);
+    }
+
+    let result = loop {
+      let mut status = 0;
+
+      if unsafe { libc::waitpid(pid, &mut status, libc::WUNTRACED) } == -1 {
+        let error = io::Error::last_os_error();
+        if error.kind() == io::ErrorKind::Interrupted {
+          continue;
+        }
+        break Err(error);
+      }
+
+      if libc::WIFSTOPPED(status) {
+        // The recipe was stopped, most likely by the user typing ^Z, so stop
+        // ourselves too, letting the shell regain control of the terminal,
+        // and continue the recipe when we are continued.
+        if let Some(terminal) = &terminal {
+          terminal.reclaim();
+        }
+
+        unsafe {
+          libc::kill(libc::getpid(), libc::SIGSTOP);
+        }
+
+        if let Some(terminal) = &terminal {
+          terminal.set_foreground(pid);
+        }
+
+        Self::signal_process_group(id, libc::SIGCONT);
+
+        continue;
+      }
+
+      break Ok(ExitStatus::from_raw(status));
+    };
+
+    drop(watch);
+
+    if let Some(terminal) = &terminal {
+      terminal.reclaim();
+    }
+
+    let interrupted = {
+      let mut interrupt_handler = InterruptHandler::instance();
+      interrupt_handler.remove_process_group(id);
+      interrupt_handler.interrupted()
+    };
+
+    if interrupted
+      || result
+        .as_ref()
+        .map_or(true, |status| status.signal().is_some())
+    {
+      terminate_process_group(pid);
+    }
+
+    result
+  }
+
+  fn signal_process_group(process_group: u32, signal: i32) {
+    #[allow(clippy::cast_possible_wrap)]
+    unsafe {
+      libc::kill(-(process_group as libc::pid_t), signal);
+    }
+  }
+}
+
+/// The controlling terminal, if just is in its foreground process group.
+/// While a recipe is running, its process group is made the foreground process
+/// group, so that it receives signals generated by the terminal and may read
+/// from it.
+#[cfg(unix)]
+struct ForegroundTerminal {
+  fd: libc::c_int,
+}
+
+#[cfg(unix)]
+impl ForegroundTerminal {
+  fn open() -> Option<Self> {
+    let fd = unsafe {
+      libc::open(
+        b"/dev/tty\0".as_ptr().cast(),
+        libc::O_RDWR | libc::O_NOCTTY | libc::O_CLOEXEC,
+      )
+    };
+
+    if fd == -1 {
+      return None;
+    }
+
+    let terminal = Self { fd };
+
+    if unsafe { libc::tcgetpgrp(fd) == libc::getpgrp() } {
+      Some(terminal)
+    } else {
+      None
+    }
+  }
+
+  fn set_foreground(&self, process_group: libc::pid_t) {
+    // Changing the foreground process group while not in it raises `SIGTTOU`,
+    // which would stop us, so ignore it for the duration of the call.
+    unsafe {
+      let previous = libc::signal(libc::SIGTTOU, libc::SIG_IGN);
+      libc::tcsetpgrp(self.fd, process_group);
+      libc::signal(libc::SIGTTOU, previous);
+    }
+  }
+
+  fn reclaim(&self) {
+    self.set_foreground(unsafe { libc::getpgrp() });
+  }
+}
+
+#[cfg(unix)]
+impl Drop for ForegroundTerminal {
+  fn drop(&mut self) {
+    unsafe {
+      libc::close(self.fd);
+    }
+  }
+}
+
+/// Terminate any processes remaining in `process_group`, giving them a short
+/// grace period to exit after `SIGTERM` before sending `SIGKILL`
+#[cfg(unix)]
+fn terminate_process_group(process_group: libc::pid_t) {
+  use std::{thread, time::Duration};
+
+  if unsafe { libc::kill(-process_group, libc::SIGTERM) } == -1 {
+    return;
+  }
+
+  for _ in 0..50 {
+    thread::sleep(Duration::from_millis(10));
+
+    if unsafe { libc::kill(-process_group, 0) } == -1 {
+      return;
+    }
+  }
+
+  unsafe {
+    libc::kill(-process_group, libc::SIGKILL);
+  }
 }
 
 #[cfg(windows)]
@@ -52,11 +308,13 @@ impl PlatformInterface for Platform {
     path: &Path,
     working_directory: Option<&Path>,
     shebang: Shebang,
+    windows_shebang: WindowsShebang,
   ) -> Result<Command, OutputError> {
     use std::borrow::Cow;
 
-    // If the path contains forward slashes…
-    let command = if shebang.interpreter.contains('/') {
+    // If the path contains forward slashes, and isn't to a native interpreter…
+    let command = if shebang.interpreter.contains('/') && windows_shebang == WindowsShebang::Cygpath
+    {
       // …translate path to the interpreter from unix style to windows style.
       let mut cygpath = Command::new("cygpath");
       if let Some(working_directory) = working_directory {
@@ -81,10 +339,43 @@ impl PlatformInterface for Platform {
       cmd.arg(argument);
     }
 
+    if shebang.is_powershell() {
+      cmd.arg("-File");
+    }
+
     cmd.arg(path);
     Ok(cmd)
   }
 
+  fn is_executable(path: &Path) -> bool {
+    // Only files with one of the extensions in `PATHEXT` are executable, and
+    // they may be named without their extension, so also try appending each
+    // of them
+    let pathext = env::var_os("PATHEXT").unwrap_or_else(|| ".COM;.EXE;.BAT;.CMD".into());
+    let pathext = pathext.to_string_lossy();
+    let extensions = || pathext.split(';').filter(|extension| !extension.is_empty());
+
+    let executable_extension = path.extension().is_some_and(|extension| {
+      let extension = extension.to_string_lossy();
+      extensions().any(|candidate| {
+        candidate
+          .strip_prefix('.')
+          .unwrap_or(candidate)
+          .eq_ignore_ascii_case(&extension)
+      })
+    });
+
+    if executable_extension && path.is_file() {
+      return true;
+    }
+
+    extensions().any(|extension| {
+      let mut candidate = path.as_os_str().to_owned();
+      candidate.push(extension);
+      Path::new(&candidate).is_file()
+    })
+  }
+
   fn set_execute_permission(_path: &Path) -> Result<(), io::Error> {
     // it is not necessary to set an execute permission on a script on windows, so
     // this is a nop
@@ -112,4 +403,99 @@ impl PlatformInterface for Platform {
         .ok_or_else(|| String::from("Error getting current directory: unicode decode error")),
     }
   }
+
+  fn install_signal_handler(handler: fn(i32)) -> Result<(), io::Error> {
+    ctrlc::set_handler(move || handler(libc::SIGINT))
+      .map_err(|error| io::Error::new(io::ErrorKind::Other, error))
+  }
+
+  fn run_in_process_group(
+    command: &mut Command,
+    monitor: &Monitor,
+  ) -> Result<ExitStatus, io::Error> {
+    use std::thread;
+
+    // Windows has no process groups in the unix sense, and console control
+    // events are already delivered to all attached processes
+    monitor.configure(command);
+
+    let mut child = command.spawn()?;
+
+    let _watch = monitor.watch(&mut child, || {});
+
+    if !monitor.is_active() {
+      return child.wait();
+    }
+
+    // The child can't be killed from the monitor thread while we are blocked
+    // waiting for it, so poll it instead
+    loop {
+      if let Some(status) = child.try_wait()? {
+        return Ok(status);
+      }
+
+      if monitor.timed_out().is_some() {
+        child.kill()?;
+        return child.wait();
+      }
+
+      thread::sleep(Duration::from_millis(10));
+    }
+  }
+
+  fn signal_process_group(_process_group: u32, _signal: i32) {}
+}
+
+/// Processes can't be run from WebAssembly, so only the parts of `just` which
+/// don't run anything, like checking and formatting justfiles, work there
+#[cfg(target_arch = "wasm32")]
+impl PlatformInterface for Platform {
+  fn make_shebang_command(
+    _path: &Path,
+    _working_directory: Option<&Path>,
+    _shebang: Shebang,
+    _windows_shebang: WindowsShebang,
+  ) -> Result<Command, OutputError> {
+    Err(OutputError::Io(unsupported()))
+  }
+
+  fn is_executable(_path: &Path) -> bool {
+    false
+  }
+
+  fn set_execute_permission(_path: &Path) -> Result<(), io::Error> {
+    Err(unsupported())
+  }
+
+  fn signal_from_exit_status(_exit_status: ExitStatus) -> Option<i32> {
+    None
+  }
+
+  fn convert_native_path(_working_directory: &Path, path: &Path) -> Result<String, String> {
+    path
+      .to_str()
+      .map(str::to_string)
+      .ok_or_else(|| String::from("Error getting current directory: unicode decode error"))
+  }
+
+  fn install_signal_handler(_handler: fn(i32)) -> Result<(), io::Error> {
+    Ok(())
+  }
+
+  fn run_in_process_group(
+    _command: &mut Command,
+    _monitor: &Monitor,
+  ) -> Result<ExitStatus, io::Error> {
+    Err(unsupported())
+  }
+
+  fn signal_process_group(_process_group: u32, _signal: i32) {}
+}
+
+#[cfg(target_arch = "wasm32")]
+fn unsupported() -> io::Error {
+  io::Error::new(
+    io::ErrorKind::Unsupported,
+    "running processes is not supported on WebAssembly",
+  )
 }
@@ -2,13 +2,18 @@ use super::*;
 
 pub(crate) trait PlatformInterface {
   /// Construct a command equivalent to running the script at `path` with the
-  /// shebang line `shebang`
+  /// shebang line `shebang`, translating the interpreter path on Windows
+  /// according to `windows_shebang`
   fn make_shebang_command(
     path: &Path,
     working_directory: Option<&Path>,
     shebang: Shebang,
+    windows_shebang: WindowsShebang,
   ) -> Result<Command, OutputError>;
 
+  /// Whether `path` is a file that can be executed
+  fn is_executable(path: &Path) -> bool;
+
   /// Set the execute permission on the file pointed to by `path`
   fn set_execute_permission(path: &Path) -> Result<(), io::Error>;
 
@@ -18,4 +23,21 @@ pub(crate) trait PlatformInterface {
 
   /// Translate a path from a "native" path to a path the interpreter expects
   fn convert_native_path(working_directory: &Path, path: &Path) -> Result<String, String>;
+
+  /// Call `handler` with the signal number whenever a termination signal is
+  /// received
+  fn install_signal_handler(handler: fn(i32)) -> Result<(), io::Error>;
+
+  /// Run `command` to completion in its own process group, forwarding
+  /// termination signals to the group while it runs. If the command is
+  /// terminated by a signal, or just is interrupted, any processes left in the
+  /// group are terminated before returning. While the command runs it is
+  /// watched by `monitor`.
+  fn run_in_process_group(
+    command: &mut Command,
+    monitor: &Monitor,
+  ) -> Result<ExitStatus, io::Error>;
+
+  /// Send `signal` to all processes in `process_group`
+  fn signal_process_group(process_group: u32, signal: i32);
 }
@@ -1,31 +1,48 @@
 use {
   super::*,
-  std::process::{ExitStatus, Stdio},
+  std::{
+    process::{ExitStatus, Stdio},
+    slice,
+  },
 };
 
 /// Return a `Error::Signal` if the process was terminated by a signal,
 /// otherwise return an `Error::UnknownFailure`
-fn error_from_signal(recipe: &str, line_number: Option<usize>, exit_status: ExitStatus) -> Error {
+fn error_from_signal<'src>(
+  recipe: &'src str,
+  token: Option<Token<'src>>,
+  exit_status: ExitStatus,
+) -> Error<'src> {
   match Platform::signal_from_exit_status(exit_status) {
     Some(signal) => Error::Signal {
       recipe,
-      line_number,
+      token,
       signal,
     },
-    None => Error::Unknown {
-      recipe,
-      line_number,
-    },
+    None => Error::Unknown { recipe, token },
   }
 }
 
+/// A command in the body of a linewise recipe
+struct BodyCommand {
+  /// The evaluated command, without its `@` and `-` prefixes, which is empty
+  /// if it is a comment ignored with `set ignore-comments`
+  text: String,
+  infallible: bool,
+  /// The number of lines of the body the command spans
+  lines: usize,
+  quiet: bool,
+}
+
 /// A recipe, e.g. `foo: bar baz`
 #[derive(PartialEq, Debug, Clone, Serialize)]
 pub(crate) struct Recipe<'src, D = Dependency<'src>> {
-  pub(crate) attributes: BTreeSet<Attribute>,
+  pub(crate) attributes: BTreeSet<Attribute<'src>>,
   pub(crate) body: Vec<Line<'src>>,
   pub(crate) dependencies: Vec<D>,
   pub(crate) doc: Option<&'src str>,
+  #[serde(skip)]
+  pub(crate) interpolation_delimiters: InterpolationDelimiters,
   pub(crate) name: Name<'src>,
   pub(crate) parameters: Vec<Parameter<'src>>,
   pub(crate) priors: usize,
@@ -59,16 +76,394 @@ impl<'src, D> Recipe<'src, D> {
     self.name.lexeme()
   }
 
+  /// The namespace of a recipe from a file included with `!include PATH as
+  /// NAMESPACE`, e.g. `ci` for `ci::lint`
+  pub(crate) fn namespace(&self) -> Option<&'src str> {
+    self
+      .name()
+      .rsplit_once("::")
+      .map(|(namespace, _name)| namespace)
+  }
+
   pub(crate) fn line_number(&self) -> usize {
     self.name.line
   }
 
+  /// A token spanning the text of line `line_number`, counting from one, of
+  /// the justfile this recipe was parsed from, so that errors running the
+  /// line can show it
+  fn line_token(&self, line_number: usize) -> Option<Token<'src>> {
+    let src = self.name.src;
+
+    let mut offset = 0;
+
+    for (i, line) in src.split_inclusive('\n').enumerate() {
+      if i + 1 == line_number {
+        let text = line.trim_end_matches(|c| c == '\n' || c == '\r');
+        let column = text.len() - text.trim_start().len();
+        return Some(Token {
+          offset: offset + column,
+          length: text.len() - column,
+          line: i,
+          column,
+          src,
+          kind: TokenKind::Text,
+        });
+      }
+
+      offset += line.len();
+    }
+
+    None
+  }
+
   pub(crate) fn public(&self) -> bool {
     !self.private && !self.attributes.contains(&Attribute::Private)
   }
 
-  pub(crate) fn change_directory(&self) -> bool {
-    !self.attributes.contains(&Attribute::NoCd)
+  /// The recipe's documentation, taken from its `[doc]` attribute if it has
+  /// one, and otherwise from the comment preceding it
+  pub(crate) fn doc(&self) -> Option<&str> {
+    for attribute in &self.attributes {
+      if let Attribute::Doc(doc) = attribute {
+        return doc.as_ref().map(|doc| doc.cooked.as_str());
+      }
+    }
+
+    self.doc
+  }
+
+  pub(crate) fn groups(&self) -> Vec<&str> {
+    self
+      .attributes
+      .iter()
+      .filter_map(|attribute| match attribute {
+        Attribute::Group(group) => Some(group.cooked.as_str()),
+        _ => None,
+      })
+      .collect()
+  }
+
+  /// A copy of the recipe with `overrides`, given on the command line with
+  /// `--attribute` and `--no-attribute`, applied to its attributes
+  pub(crate) fn with_attribute_overrides(&self, overrides: &[AttributeOverride]) -> Self
+  where
+    D: Clone,
+  {
+    let mut recipe = self.clone();
+
+    for attribute_override in overrides {
+      attribute_override.apply(&mut recipe.attributes);
+    }
+
+    recipe
+  }
+
+  /// Ask the user whether to run the recipe, if it has a `[confirm]` attribute
+  pub(crate) fn confirm(&self, config: &Config) -> RunResult<'src, bool> {
+    for attribute in &self.attributes {
+      if let Attribute::Confirm(prompt) = attribute {
+        let prompt = prompt.as_ref().map_or_else(
+          || format!("Run recipe `{}`?", self.name()),
+          |prompt| prompt.cooked.clone(),
+        );
+
+        return config
+          .confirm(&prompt)
+          .map_err(|io_error| Error::GetConfirmation { io_error });
+      }
+    }
+
+    Ok(true)
+  }
+
+  /// Split `argument` into a parameter name and a value, if it is a keyword
+  /// argument of the form `NAME=VALUE` or `--NAME=VALUE` and `NAME` is one of
+  /// the recipe's parameters. Other arguments of the form `NAME=VALUE` are
+  /// positional, but an argument of the form `--NAME=VALUE` is an error if
+  /// `NAME` is not one of the recipe's parameters, since it was clearly meant
+  /// as a keyword argument.
+  pub(crate) fn keyword_argument<'a>(
+    &self,
+    argument: &'a str,
+  ) -> RunResult<'src, Option<(&'src str, &'a str)>> {
+    let (marked, keyword) = match argument.strip_prefix("--") {
+      Some(keyword) => (true, keyword),
+      None => (false, argument),
+    };
+
+    let Some((name, value)) = keyword.split_once('=') else {
+      return Ok(None);
+    };
+
+    let parameter = self
+      .parameters
+      .iter()
+      .find(|parameter| parameter.name.lexeme() == name);
+
+    match parameter {
+      Some(parameter) => Ok(Some((parameter.name.lexeme(), value))),
+      None if marked && Lexer::is_identifier(name) => Err(Error::UnknownKeywordArgument {
+        recipe: self.name(),
+        argument: argument.to_owned(),
+        name: name.to_owned(),
+      }),
+      None => Ok(None),
+    }
+  }
+
+  /// The number of positional arguments the recipe accepts, once `keywords`
+  /// have been bound
+  pub(crate) fn positional_capacity(&self, keywords: &BTreeMap<&str, &str>) -> usize {
+    let unbound = self
+      .parameters
+      .iter()
+      .filter(|parameter| !keywords.contains_key(parameter.name.lexeme()));
+
+    if unbound
+      .clone()
+      .any(|parameter| parameter.kind.is_variadic())
+    {
+      self.max_arguments()
+    } else {
+      unbound.count()
+    }
+  }
+
+  /// Ask the user for a value for each parameter not given one by
+  /// `positional` or `keywords`, if any of them are required. Answers are
+  /// added to `keywords`, except empty answers for parameters with defaults,
+  /// which are left to use their default.
+  pub(crate) fn prompt_for_arguments<'a>(
+    &self,
+    positional: &[&str],
+    keywords: &mut BTreeMap<&'src str, &'a str>,
+    answers: &'a Arena<String>,
+  ) -> RunResult<'src, ()> {
+    let unsupplied = self
+      .parameters
+      .iter()
+      .filter(|parameter| !keywords.contains_key(parameter.name.lexeme()))
+      .skip(positional.len())
+      .collect::<Vec<&Parameter>>();
+
+    if !unsupplied
+      .iter()
+      .any(|parameter| parameter.default.is_none() && parameter.kind != ParameterKind::Star)
+    {
+      return Ok(());
+    }
+
+    for parameter in unsupplied {
+      let prompt = match &parameter.default {
+        Some(default) => format!("{} (default: {default}):", parameter.name),
+        None => format!("{}:", parameter.name),
+      };
+
+      let answer =
+        Config::read_answer(&prompt).map_err(|io_error| Error::ArgumentPrompt { io_error })?;
+
+      if answer.is_empty() && (parameter.default.is_some() || parameter.kind == ParameterKind::Star)
+      {
+        continue;
+      }
+
+      keywords.insert(parameter.name.lexeme(), answers.alloc(answer));
+    }
+
+    Ok(())
+  }
+
+  /// Check that `positional` arguments, which are bound in order to the
+  /// parameters not given a value by `keywords`, are enough for the recipe's
+  /// required parameters and not too many for the rest
+  pub(crate) fn check_argument_count(
+    &self,
+    positional: &[&str],
+    keywords: &BTreeMap<&str, &str>,
+  ) -> RunResult<'src, ()> {
+    if keywords.is_empty() {
+      if !self.argument_range().range_contains(&positional.len()) {
+        return Err(Error::ArgumentCountMismatch {
+          recipe: self.name(),
+          parameters: self.parameters.clone(),
+          found: positional.len(),
+          min: self.min_arguments(),
+          max: self.max_arguments(),
+          doc: self.doc().map(str::to_owned),
+        });
+      }
+
+      return Ok(());
+    }
+
+    let mut remaining = positional.len();
+
+    for parameter in &self.parameters {
+      if keywords.contains_key(parameter.name.lexeme()) {
+        continue;
+      }
+
+      let required = parameter.default.is_none() && parameter.kind != ParameterKind::Star;
+
+      if remaining == 0 && required {
+        return Err(Error::MissingArgument {
+          recipe: self.name(),
+          parameter: parameter.name.lexeme(),
+        });
+      }
+
+      if parameter.kind.is_variadic() {
+        remaining = 0;
+      } else {
+        remaining = remaining.saturating_sub(1);
+      }
+    }
+
+    if remaining > 0 {
+      if let Some(variadic) = self
+        .parameters
+        .last()
+        .filter(|parameter| parameter.kind.is_variadic())
+      {
+        return Err(Error::TooManyArguments {
+          recipe: self.name(),
+          variadic: variadic.name.lexeme(),
+          found: positional.len(),
+          max: positional.len() - remaining,
+        });
+      }
+
+      return Err(Error::ArgumentCountMismatch {
+        recipe: self.name(),
+        parameters: self.parameters.clone(),
+        found: positional.len() + keywords.len(),
+        min: self.min_arguments(),
+        max: self.max_arguments(),
+        doc: self.doc().map(str::to_owned),
+      });
+    }
+
+    Ok(())
+  }
+
+  /// Check `arguments` against the `[choices]` and `[pattern]` attributes of
+  /// the parameters they are bound to
+  pub(crate) fn check_arguments<S: AsRef<str>>(
+    &self,
+    arguments: &[S],
+    keywords: &BTreeMap<&str, S>,
+  ) -> RunResult<'src, ()> {
+    let mut rest = arguments;
+
+    for parameter in &self.parameters {
+      let values = if let Some(value) = keywords.get(parameter.name.lexeme()) {
+        slice::from_ref(value)
+      } else if parameter.kind.is_variadic() {
+        mem::take(&mut rest)
+      } else {
+        let (values, tail) = rest.split_at(rest.len().min(1));
+        rest = tail;
+        values
+      };
+
+      for attribute in &self.attributes {
+        if attribute.parameter() != Some(parameter.name.lexeme()) {
+          continue;
+        }
+
+        for value in values {
+          let value = value.as_ref();
+
+          if attribute.accepts(value) {
+            continue;
+          }
+
+          match attribute {
+            Attribute::Choices { choices, .. } => {
+              return Err(Error::ArgumentChoice {
+                recipe: self.name(),
+                parameter: parameter.name.lexeme(),
+                value: value.to_owned(),
+                choices: choices.iter().map(|choice| choice.cooked.clone()).collect(),
+              });
+            }
+            Attribute::Pattern { pattern, .. } => {
+              return Err(Error::ArgumentPattern {
+                recipe: self.name(),
+                parameter: parameter.name.lexeme(),
+                value: value.to_owned(),
+                pattern: pattern.cooked.clone(),
+              });
+            }
+            _ => {}
+          }
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  /// The files listed in the recipe's `[dotenv]` attribute, if it has one
+  pub(crate) fn dotenv_filenames(&self) -> Option<impl Iterator<Item = &str>> {
+    self
+      .attributes
+      .iter()
+      .find_map(|attribute| match attribute {
+        Attribute::Dotenv(filenames) => {
+          Some(filenames.iter().map(|filename| filename.cooked.as_str()))
+        }
+        _ => None,
+      })
+  }
+
+  /// The environment variables listed in the recipe's `[requires-env]`
+  /// attributes
+  pub(crate) fn required_env(&self) -> impl Iterator<Item = &str> {
+    self
+      .attributes
+      .iter()
+      .filter_map(|attribute| match attribute {
+        Attribute::RequiresEnv(variables) => Some(variables),
+        _ => None,
+      })
+      .flatten()
+      .map(|variable| variable.cooked.as_str())
+  }
+
+  /// The names of the related recipes listed in the recipe's `[see-also]`
+  /// attributes
+  pub(crate) fn see_also(&self) -> impl Iterator<Item = &str> {
+    self
+      .attributes
+      .iter()
+      .filter_map(|attribute| match attribute {
+        Attribute::SeeAlso(recipes) => Some(recipes),
+        _ => None,
+      })
+      .flatten()
+      .map(|recipe| recipe.cooked.as_str())
+  }
+
+  pub(crate) fn working_directory(&self, search: &Search) -> Option<PathBuf> {
+    for attribute in &self.attributes {
+      if let Attribute::WorkingDirectory(directory) = attribute {
+        return Some(
+          search
+            .justfile
+            .parent()
+            .unwrap_or(&search.working_directory)
+            .join(&directory.cooked),
+        );
+      }
+    }
+
+    if self.attributes.contains(&Attribute::NoCd) {
+      None
+    } else {
+      Some(search.working_directory.clone())
+    }
   }
 
   pub(crate) fn enabled(&self) -> bool {
@@ -77,26 +472,125 @@ impl<'src, D> Recipe<'src, D> {
     let macos = self.attributes.contains(&Attribute::Macos);
     let unix = self.attributes.contains(&Attribute::Unix);
 
-    (!windows && !linux && !macos && !unix)
+    let mut arches = None;
+    let mut oses = None;
+    let mut os_families = None;
+
+    for attribute in &self.attributes {
+      match attribute {
+        Attribute::Arch(values) => arches = Some(values),
+        Attribute::Os(values) => oses = Some(values),
+        Attribute::OsFamily(values) => os_families = Some(values),
+        _ => {}
+      }
+    }
+
+    let matches = |values: Option<&Vec<StringLiteral>>, target: &str| {
+      values.is_some_and(|values| values.iter().any(|value| value.cooked == target))
+    };
+
+    let os = (!windows && !linux && !macos && !unix && oses.is_none() && os_families.is_none())
       || (cfg!(target_os = "windows") && windows)
       || (cfg!(target_os = "linux") && (linux || unix))
       || (cfg!(target_os = "macos") && (macos || unix))
       || (cfg!(windows) && windows)
       || (cfg!(unix) && unix)
+      || matches(oses, function::target_os())
+      || matches(os_families, function::target_os_family());
+
+    let arch = arches.is_none() || matches(arches, function::target_arch());
+
+    os && arch
+  }
+
+  /// The interpreter given by a `[script]` or `[script-stdin]` attribute,
+  /// and whether the script is passed to it on standard input
+  fn script_interpreter(&self) -> Option<(&Shell<'src>, bool)> {
+    self
+      .attributes
+      .iter()
+      .find_map(|attribute| match attribute {
+        Attribute::Script(interpreter) => Some((interpreter, false)),
+        Attribute::ScriptStdin(interpreter) => Some((interpreter, true)),
+        _ => None,
+      })
+  }
+
+  /// Whether the recipe body is run as a single script, rather than line by
+  /// line, either because it starts with a shebang line or because it has a
+  /// `[script]` or `[script-stdin]` attribute
+  pub(crate) fn is_script(&self) -> bool {
+    self.shebang || self.script_interpreter().is_some()
+  }
+
+  /// The shell set with the `[shell]` attribute, if any
+  pub(crate) fn shell(&self) -> Option<&Shell<'src>> {
+    self
+      .attributes
+      .iter()
+      .find_map(|attribute| match attribute {
+        Attribute::Shell(shell) => Some(shell),
+        _ => None,
+      })
+  }
+
+  fn shell_command(&self, context: &RecipeContext) -> RunResult<'src, Command> {
+    context
+      .settings
+      .recipe_shell_command(context.config, self.shell())
+  }
+
+  /// Append `command`, which started at `timestamp` and finished with
+  /// `result`, to the command log, if there is one. Failing to write the log
+  /// only produces a warning, so that it doesn't hide the command's result.
+  fn log_command(
+    &self,
+    context: &RecipeContext<'src, '_>,
+    command: &str,
+    timestamp: String,
+    result: &io::Result<ExitStatus>,
+  ) {
+    let Some(path) = CommandLog::path(context.config, context.settings, context.search) else {
+      return;
+    };
+
+    let directory = self
+      .working_directory(context.search)
+      .unwrap_or_else(|| context.config.invocation_directory.clone());
+
+    let entry = CommandLog {
+      command,
+      directory: &directory,
+      exit_code: result.as_ref().ok().and_then(ExitStatus::code),
+      recipe: self.name(),
+      timestamp,
+    };
+
+    if let Err(io_error) = entry.append(&path) {
+      if context.config.verbosity.loud() {
+        let warning = Warning::WriteCommandLog {
+          path,
+          message: io_error.to_string(),
+        };
+        eprintln!("{}", warning.color_display(context.config.color.stderr()));
+      }
+    }
   }
 
   fn print_exit_message(&self) -> bool {
     !self.attributes.contains(&Attribute::NoExitMessage)
   }
 
+  /// Run this recipe, returning its standard output instead of writing it to
+  /// just's own if `capture` is true
   pub(crate) fn run<'run>(
     &self,
     context: &RecipeContext<'src, 'run>,
     dotenv: &BTreeMap<String, String>,
     scope: Scope<'src, 'run>,
-    search: &'run Search,
     positional: &[String],
-  ) -> RunResult<'src, ()> {
+    capture: bool,
+  ) -> RunResult<'src, Option<String>> {
     let config = &context.config;
 
     if config.verbosity.loquacious() {
@@ -109,94 +603,252 @@ impl<'src, D> Recipe<'src, D> {
       );
     }
 
-    let evaluator =
-      Evaluator::recipe_evaluator(context.config, dotenv, &scope, context.settings, search);
+    let mut evaluator = Evaluator::recipe_evaluator(context, dotenv, self, &scope);
+
+    let environment = self.environment(&mut evaluator)?;
+
+    if config.diff_env {
+      self.print_environment_diff(context, dotenv, &scope, &environment);
+    }
 
-    if self.shebang {
-      self.run_shebang(context, dotenv, &scope, positional, config, evaluator)
+    let capture = capture.then(Capture::default);
+
+    if self.is_script() {
+      self.run_script(
+        context,
+        dotenv,
+        &scope,
+        positional,
+        &environment,
+        capture.as_ref(),
+        evaluator,
+      )?;
     } else {
-      self.run_linewise(context, dotenv, &scope, positional, config, evaluator)
+      self.run_linewise(
+        context,
+        dotenv,
+        &scope,
+        positional,
+        &environment,
+        capture.as_ref(),
+        evaluator,
+      )?;
     }
+
+    // Nothing is run with `--dry-run`, so a placeholder stands in for the
+    // output
+    Ok(capture.as_ref().map(|capture| {
+      if config.dry_run {
+        format!("<output of {}>", self.name())
+      } else {
+        capture.output()
+      }
+    }))
   }
 
-  fn run_linewise<'run>(
+  /// The variables set by this recipe's `[env]` attributes, and their
+  /// evaluated values
+  fn environment(
     &self,
-    context: &RecipeContext<'src, 'run>,
-    dotenv: &BTreeMap<String, String>,
-    scope: &Scope<'src, 'run>,
-    positional: &[String],
-    config: &Config,
-    mut evaluator: Evaluator<'src, 'run>,
-  ) -> RunResult<'src, ()> {
-    let mut lines = self.body.iter().peekable();
-    let mut line_number = self.line_number() + 1;
-    loop {
-      if lines.peek().is_none() {
-        return Ok(());
+    evaluator: &mut Evaluator<'src, '_>,
+  ) -> RunResult<'src, BTreeMap<String, String>> {
+    let mut environment = BTreeMap::new();
+
+    for attribute in &self.attributes {
+      if let Attribute::Env { name, value } = attribute {
+        environment.insert(name.cooked.clone(), evaluator.evaluate_expression(value)?);
       }
-      let mut evaluated = String::new();
-      let mut continued = false;
-      let quiet_command = lines.peek().map_or(false, |line| line.is_quiet());
-      let infallible_command = lines.peek().map_or(false, |line| line.is_infallible());
+    }
+
+    Ok(environment)
+  }
 
-      let comment_line =
-        context.settings.ignore_comments && lines.peek().map_or(false, |line| line.is_comment());
+  /// Print the environment variables which running the recipe adds to,
+  /// changes in, or removes from just's own environment
+  fn print_environment_diff(
+    &self,
+    context: &RecipeContext<'src, '_>,
+    dotenv: &BTreeMap<String, String>,
+    scope: &Scope,
+    environment: &BTreeMap<String, String>,
+  ) {
+    let mut command = Command::new("");
 
-      loop {
-        if lines.peek().is_none() {
-          break;
-        }
-        let line = lines.next().unwrap();
-        line_number += 1;
-        if !comment_line {
-          evaluated += &evaluator.evaluate_line(line, continued)?;
-        }
-        if line.is_continuation() && !comment_line {
-          continued = true;
-          evaluated.pop();
-        } else {
-          break;
+    command.export(
+      context.config,
+      context.settings,
+      context.login_env,
+      dotenv,
+      scope,
+    );
+    command.envs(environment);
+
+    let changes = command
+      .get_envs()
+      .filter_map(|(name, value)| {
+        let name = name.to_string_lossy();
+        let value = value.map(OsStr::to_string_lossy);
+        // Variables from the login environment are part of the environment
+        // recipes start with, rather than changes to it
+        let old = context
+          .login_env
+          .get(name.as_ref())
+          .map(OsString::from)
+          .or_else(|| env::var_os(name.as_ref()));
+        match (old, value) {
+          (None, Some(value)) => Some(format!("+ {name}={value}")),
+          (Some(old), Some(value)) if old.to_string_lossy() != value => {
+            Some(format!("~ {name}={value} (was {})", old.to_string_lossy()))
+          }
+          (Some(_), None) => Some(format!("- {name}")),
+          _ => None,
         }
+      })
+      .collect::<Vec<String>>();
+
+    let color = context.config.color.stderr().banner();
+
+    if changes.is_empty() {
+      eprintln!(
+        "{}Environment for recipe `{}` is unchanged{}",
+        color.prefix(),
+        self.name(),
+        color.suffix(),
+      );
+    } else {
+      eprintln!(
+        "{}Environment for recipe `{}`:{}",
+        color.prefix(),
+        self.name(),
+        color.suffix(),
+      );
+
+      for change in changes {
+        eprintln!("    {change}");
       }
+    }
+  }
 
-      if comment_line {
-        continue;
+  /// Echo `command`, a line of this recipe, to standard error
+  fn echo(&self, config: &Config, echo_mode: EchoMode, command: &str) {
+    let color = if config.highlight {
+      config.color.command(config.command_color)
+    } else {
+      config.color
+    };
+    let text = echo_mode.text(self.name(), command);
+    let text = match &config.timestamps {
+      Some(timestamps) => timestamps.prefix(&text),
+      None => text,
+    };
+    eprintln!("{}", color.stderr().paint(&text));
+  }
+
+  /// Evaluate the next command in the body of a linewise recipe, joining
+  /// lines ended by a line continuation, or return `None` at the end of the
+  /// body
+  fn next_command<'run>(
+    &self,
+    settings: &Settings,
+    body: &mut iter::Peekable<slice::Iter<Line<'src>>>,
+    evaluator: &mut Evaluator<'src, 'run>,
+  ) -> RunResult<'src, Option<BodyCommand>> {
+    let Some(first) = body.peek() else {
+      return Ok(None);
+    };
+
+    let quiet = first.is_quiet();
+    let infallible = first.is_infallible();
+    let comment = settings.ignore_comments && first.is_comment();
+
+    let mut text = String::new();
+    let mut continued = false;
+    let mut lines = 0;
+
+    for line in body.by_ref() {
+      lines += 1;
+
+      if comment {
+        break;
       }
 
-      let mut command = evaluated.as_str();
+      text += &evaluator.evaluate_line(line, continued, &self.interpolation_delimiters)?;
+
+      if line.is_continuation() {
+        continued = true;
+        text.pop();
+      } else {
+        break;
+      }
+    }
 
-      if quiet_command {
-        command = &command[1..];
+    if !comment {
+      if quiet {
+        text.remove(0);
       }
 
-      if infallible_command {
-        command = &command[1..];
+      if infallible {
+        text.remove(0);
       }
+    }
+
+    Ok(Some(BodyCommand {
+      text,
+      infallible,
+      lines,
+      quiet,
+    }))
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  fn run_linewise<'run>(
+    &self,
+    context: &RecipeContext<'src, 'run>,
+    dotenv: &BTreeMap<String, String>,
+    scope: &Scope<'src, 'run>,
+    positional: &[String],
+    environment: &BTreeMap<String, String>,
+    capture: Option<&Capture>,
+    mut evaluator: Evaluator<'src, 'run>,
+  ) -> RunResult<'src, ()> {
+    let config = context.config;
+
+    let mut lines = self.body.iter().peekable();
+    let mut line_number = self.line_number() + 1;
+    let echo_mode = config.echo.or(context.settings.echo).unwrap_or_default();
+    while let Some(BodyCommand {
+      text,
+      infallible: infallible_command,
+      lines: command_lines,
+      quiet: quiet_command,
+    }) = self.next_command(context.settings, &mut lines, &mut evaluator)?
+    {
+      line_number += command_lines;
+
+      let command = text.as_str();
 
       if command.is_empty() {
         continue;
       }
 
-      if config.dry_run
+      let echo = config.dry_run
         || config.verbosity.loquacious()
-        || !((quiet_command ^ self.quiet) || config.verbosity.quiet())
-      {
-        let color = if config.highlight {
-          config.color.command(config.command_color)
-        } else {
-          config.color
-        };
-        eprintln!("{}", color.stderr().paint(command));
+        || !((quiet_command ^ self.quiet) || config.verbosity.quiet());
+
+      if echo && (config.dry_run || echo_mode.before()) {
+        self.echo(config, echo_mode, command);
       }
 
       if config.dry_run {
         continue;
       }
 
-      let mut cmd = context.settings.shell_command(config);
+      InterruptHandler::check(self.name(), None)?;
 
-      if self.change_directory() {
-        cmd.current_dir(&context.search.working_directory);
+      let mut cmd = self.shell_command(context)?;
+
+      if let Some(working_directory) = self.working_directory(context.search) {
+        cmd.current_dir(working_directory);
       }
 
       cmd.arg(command);
@@ -211,49 +863,90 @@ impl<'src, D> Recipe<'src, D> {
         cmd.stdout(Stdio::null());
       }
 
-      cmd.export(context.settings, dotenv, scope);
+      cmd.export(
+        context.config,
+        context.settings,
+        context.login_env,
+        dotenv,
+        scope,
+      );
+      cmd.envs(environment);
+
+      let monitor = Monitor::new(config, context.settings, self.name(), command).capture(capture);
+
+      let timestamp = CommandLog::now();
+
+      let result = InterruptHandler::guard(|| Platform::run_in_process_group(&mut cmd, &monitor));
+
+      self.log_command(context, command, timestamp, &result);
+
+      let token = self.line_token(line_number);
+
+      if echo && echo_mode == EchoMode::Failed {
+        let failed = monitor.timed_out().is_some()
+          || result.as_ref().map_or(true, |exit_status| {
+            exit_status
+              .code()
+              .map_or(true, |code| code != 0 && !infallible_command)
+          });
+
+        if failed {
+          self.echo(config, echo_mode, command);
+        }
+      }
+
+      if let Some(timeout) = monitor.timed_out() {
+        return Err(Error::Timeout {
+          recipe: self.name(),
+          token,
+          timeout,
+        });
+      }
 
-      match InterruptHandler::guard(|| cmd.status()) {
+      match result {
         Ok(exit_status) => {
           if let Some(code) = exit_status.code() {
             if code != 0 && !infallible_command {
               return Err(Error::Code {
                 recipe: self.name(),
-                line_number: Some(line_number),
+                token,
                 code,
                 print_message: self.print_exit_message(),
               });
             }
           } else {
-            return Err(error_from_signal(
-              self.name(),
-              Some(line_number),
-              exit_status,
-            ));
+            return Err(error_from_signal(self.name(), token, exit_status));
           }
         }
         Err(io_error) => {
           return Err(Error::Io {
             recipe: self.name(),
+            token,
             io_error,
           });
         }
       };
     }
+
+    Ok(())
   }
 
-  pub(crate) fn run_shebang<'run>(
+  #[allow(clippy::too_many_arguments)]
+  pub(crate) fn run_script<'run>(
     &self,
     context: &RecipeContext<'src, 'run>,
     dotenv: &BTreeMap<String, String>,
     scope: &Scope<'src, 'run>,
     positional: &[String],
-    config: &Config,
+    environment: &BTreeMap<String, String>,
+    capture: Option<&Capture>,
     mut evaluator: Evaluator<'src, 'run>,
   ) -> RunResult<'src, ()> {
+    let config = context.config;
+
     let mut evaluated_lines = vec![];
     for line in &self.body {
-      evaluated_lines.push(evaluator.evaluate_line(line, false)?);
+      evaluated_lines.push(evaluator.evaluate_line(line, false, &self.interpolation_delimiters)?);
     }
 
     if config.verbosity.loud() && (config.dry_run || self.quiet) {
@@ -266,34 +959,37 @@ impl<'src, D> Recipe<'src, D> {
       return Ok(());
     }
 
-    let shebang_line = evaluated_lines.first().ok_or_else(|| Error::Internal {
-      message: "evaluated_lines was empty".to_owned(),
-    })?;
+    let (interpreter, stdin) = match self.script_interpreter() {
+      Some((interpreter, stdin)) => (Some(interpreter), stdin),
+      None => (None, false),
+    };
 
-    let shebang = Shebang::new(shebang_line).ok_or_else(|| Error::Internal {
-      message: format!("bad shebang line: {shebang_line}"),
-    })?;
+    let shebang = if let Some(interpreter) = interpreter {
+      Shebang {
+        interpreter: &interpreter.command.cooked,
+        argument: None,
+      }
+    } else {
+      let shebang_line = evaluated_lines.first().ok_or_else(|| Error::Internal {
+        message: "evaluated_lines was empty".to_owned(),
+      })?;
 
-    let mut tempdir_builder = tempfile::Builder::new();
-    tempdir_builder.prefix("just");
-    let tempdir = match &context.settings.tempdir {
-      Some(tempdir) => tempdir_builder.tempdir_in(context.search.working_directory.join(tempdir)),
-      None => tempdir_builder.tempdir(),
-    }
-    .map_err(|error| Error::TmpdirIo {
-      recipe: self.name(),
-      io_error: error,
-    })?;
-    let mut path = tempdir.path().to_path_buf();
-    path.push(shebang.script_filename(self.name()));
+      Shebang::new(shebang_line).ok_or_else(|| Error::Internal {
+        message: format!("bad shebang line: {shebang_line}"),
+      })?
+    };
 
-    {
-      let mut f = fs::File::create(&path).map_err(|error| Error::TmpdirIo {
-        recipe: self.name(),
-        io_error: error,
-      })?;
-      let mut text = String::new();
+    let mut text = String::new();
+
+    let body = if interpreter.is_some() {
+      // add blank lines so that lines in the generated script have the same
+      // line number as the corresponding lines in the justfile
+      for _ in 0..=self.line_number() {
+        text += "\n";
+      }
 
+      &evaluated_lines[..]
+    } else {
       if shebang.include_shebang_line() {
         text += &evaluated_lines[0];
       } else {
@@ -306,51 +1002,165 @@ impl<'src, D> Recipe<'src, D> {
       for _ in 1..(self.line_number() + 2) {
         text += "\n";
       }
-      for line in &evaluated_lines[1..] {
-        text += line;
-        text += "\n";
-      }
 
-      if config.verbosity.grandiloquent() {
-        eprintln!("{}", config.color.doc().stderr().paint(&text));
-      }
+      &evaluated_lines[1..]
+    };
 
-      f.write_all(text.as_bytes())
-        .map_err(|error| Error::TmpdirIo {
-          recipe: self.name(),
-          io_error: error,
-        })?;
+    for line in body {
+      text += line;
+      text += "\n";
     }
 
-    // make the script executable
-    Platform::set_execute_permission(&path).map_err(|error| Error::TmpdirIo {
-      recipe: self.name(),
-      io_error: error,
-    })?;
+    if config.verbosity.grandiloquent() {
+      eprintln!("{}", config.color.doc().stderr().paint(&text));
+    }
 
-    // create a command to run the script
-    let mut command = Platform::make_shebang_command(
-      &path,
-      if self.change_directory() {
-        Some(&context.search.working_directory)
-      } else {
+    // `[script-stdin]` scripts are written to the interpreter's standard
+    // input, and other scripts are saved to a file in a temporary directory,
+    // which must live until the script has finished, unless it is kept with
+    // `--keep-tempfiles`
+    let (input, script) = if stdin {
+      (Some(text), None)
+    } else {
+      let mut tempdir_builder = tempfile::Builder::new();
+      tempdir_builder.prefix("just");
+      let tempdir = match &context.settings.tempdir {
+        Some(tempdir) => tempdir_builder.tempdir_in(context.search.working_directory.join(tempdir)),
+        None => tempdir_builder.tempdir(),
+      }
+      .map_err(|error| Error::TmpdirIo {
+        recipe: self.name(),
+        io_error: error,
+      })?;
+      let mut path = tempdir.path().to_path_buf();
+      path.push(shebang.script_filename(self.name()));
+
+      fs::write(&path, text).map_err(|error| Error::TmpdirIo {
+        recipe: self.name(),
+        io_error: error,
+      })?;
+
+      let tempdir = if config.keep_tempfiles {
+        // the directory is deleted when it's dropped, unless it's persisted
+        let _ = tempdir.into_path();
+        if config.verbosity.loud() {
+          eprintln!(
+            "Saved script for recipe `{}` to {}",
+            self.name(),
+            path.display()
+          );
+        }
         None
-      },
-      shebang,
-    )
-    .map_err(|output_error| Error::Cygpath {
-      recipe: self.name(),
-      output_error,
-    })?;
+      } else {
+        Some(tempdir)
+      };
+
+      (None, Some((tempdir, path)))
+    };
+
+    // create a command to run the script
+    let mut command = if let Some(interpreter) = interpreter {
+      // scripts are passed to the interpreter as an argument or on stdin, so
+      // they need not be executable, and the interpreter path needs no
+      // translation
+      let mut command = Command::new(&interpreter.command.cooked);
+
+      command.args(
+        interpreter
+          .arguments
+          .iter()
+          .map(|argument| &argument.cooked),
+      );
+
+      if let Some((_tempdir, path)) = &script {
+        command.arg(path);
+      }
+
+      if let Some(working_directory) = self.working_directory(context.search) {
+        command.current_dir(working_directory);
+      }
+
+      command
+    } else {
+      let Some((_tempdir, path)) = &script else {
+        return Err(Error::Internal {
+          message: "shebang recipe was not saved to a file".to_owned(),
+        });
+      };
+
+      // make the script executable
+      Platform::set_execute_permission(path).map_err(|error| Error::TmpdirIo {
+        recipe: self.name(),
+        io_error: error,
+      })?;
+
+      Platform::make_shebang_command(
+        path,
+        self.working_directory(context.search).as_deref(),
+        shebang,
+        context.settings.windows_shebang,
+      )
+      .map_err(|output_error| Error::Cygpath {
+        recipe: self.name(),
+        output_error,
+      })?
+    };
 
     if context.settings.positional_arguments {
       command.args(positional);
     }
 
-    command.export(context.settings, dotenv, scope);
+    command.export(
+      context.config,
+      context.settings,
+      context.login_env,
+      dotenv,
+      scope,
+    );
+    command.envs(environment);
+
+    let interpreter_command = match interpreter {
+      Some(interpreter) => iter::once(&interpreter.command)
+        .chain(&interpreter.arguments)
+        .map(|argument| argument.cooked.as_str())
+        .collect::<Vec<&str>>()
+        .join(" "),
+      None => evaluated_lines[0].clone(),
+    };
+
+    let mut monitor = Monitor::new(config, context.settings, self.name(), &interpreter_command)
+      .input(input)
+      .capture(capture);
+
+    // line numbers in the script already match those in the justfile, so
+    // errors can be mapped back to it by replacing the path of the script
+    if context.settings.source_map {
+      if let Some((_tempdir, path)) = &script {
+        monitor = monitor.replace_stderr(
+          &path.to_string_lossy(),
+          &context.search.justfile.to_string_lossy(),
+        );
+      }
+    }
+
+    let timestamp = CommandLog::now();
 
     // run it!
-    match InterruptHandler::guard(|| command.status()) {
+    let result = InterruptHandler::guard(|| Platform::run_in_process_group(&mut command, &monitor));
+
+    // scripts are logged in full, since the files they were saved to are
+    // usually deleted, so the log contains any secrets they include
+    self.log_command(context, &evaluated_lines.join("\n"), timestamp, &result);
+
+    if let Some(timeout) = monitor.timed_out() {
+      return Err(Error::Timeout {
+        recipe: self.name(),
+        token: None,
+        timeout,
+      });
+    }
+
+    match result {
       Ok(exit_status) => exit_status.code().map_or_else(
         || Err(error_from_signal(self.name(), None, exit_status)),
         |code| {
@@ -359,31 +1169,189 @@ impl<'src, D> Recipe<'src, D> {
           } else {
             Err(Error::Code {
               recipe: self.name(),
-              line_number: None,
+              token: None,
               code,
               print_message: self.print_exit_message(),
             })
           }
         },
       ),
-      Err(io_error) => Err(Error::Shebang {
-        recipe: self.name(),
-        command: shebang.interpreter.to_owned(),
-        argument: shebang.argument.map(String::from),
-        io_error,
+      Err(io_error) => Err(match interpreter {
+        Some(_) => Error::Script {
+          recipe: self.name(),
+          interpreter: interpreter_command,
+          io_error,
+        },
+        None => Error::Shebang {
+          recipe: self.name(),
+          command: shebang.interpreter.to_owned(),
+          argument: shebang.argument.map(String::from),
+          io_error,
+        },
       }),
     }
   }
+
+  /// The evaluated commands of this recipe, as lines of a script exported by
+  /// `--export-script`. They're run in a subshell, so that changing directory
+  /// and exporting variables doesn't affect later recipes, and variables in
+  /// `environment`, which the script has already exported, aren't exported
+  /// again.
+  pub(crate) fn export<'run>(
+    &self,
+    context: &RecipeContext<'src, 'run>,
+    dotenv: &BTreeMap<String, String>,
+    scope: Scope<'src, 'run>,
+    positional: &[String],
+    environment: &BTreeMap<String, String>,
+  ) -> RunResult<'src, Vec<String>> {
+    let mut lines = vec!["(".to_owned()];
+
+    for attribute in &self.attributes {
+      if let Attribute::WorkingDirectory(directory) = attribute {
+        lines.push(format!("cd {}", export_script::quote(&directory.cooked)));
+      }
+    }
+
+    let mut evaluator = Evaluator::recipe_evaluator(context, dotenv, self, &scope);
+
+    let mut variables = export_script::environment(
+      context.config,
+      context.settings,
+      context.login_env,
+      dotenv,
+      &scope,
+    );
+
+    variables.extend(self.environment(&mut evaluator)?);
+
+    for (name, value) in variables {
+      if environment.get(&name) != Some(&value) {
+        lines.push(format!("export {name}={}", export_script::quote(&value)));
+      }
+    }
+
+    let arguments = if context.settings.positional_arguments {
+      iter::once(String::new())
+        .chain(
+          positional
+            .iter()
+            .map(|argument| export_script::quote(argument)),
+        )
+        .collect::<Vec<String>>()
+        .join(" ")
+    } else {
+      String::new()
+    };
+
+    if self.is_script() {
+      let mut text = String::new();
+
+      for line in &self.body {
+        text += &evaluator.evaluate_line(line, false, &self.interpolation_delimiters)?;
+        text += "\n";
+      }
+
+      let delimiter = export_script::delimiter(&text);
+
+      lines.push("script=\"$(mktemp)\"".into());
+      lines.push("trap 'rm -f \"$script\"' EXIT".into());
+      lines.push(format!("cat > \"$script\" <<'{delimiter}'"));
+      lines.push(text + &delimiter);
+
+      if let Some((interpreter, stdin)) = self.script_interpreter() {
+        let command = iter::once(&interpreter.command)
+          .chain(&interpreter.arguments)
+          .map(|argument| export_script::quote(&argument.cooked))
+          .collect::<Vec<String>>()
+          .join(" ");
+
+        if stdin {
+          lines.push(format!("{command}{arguments} < \"$script\""));
+        } else {
+          lines.push(format!("{command} \"$script\"{arguments}"));
+        }
+      } else {
+        lines.push("chmod +x \"$script\"".into());
+        lines.push(format!("\"$script\"{arguments}"));
+      }
+    } else {
+      if context.settings.positional_arguments && !positional.is_empty() {
+        lines.push(format!("set --{arguments}"));
+      }
+
+      let (shell, shell_arguments) = context.settings.shell(context.config)?;
+
+      let command = self.shell_command(context)?;
+
+      // lines are run with the script's own shell, unless the recipe has a
+      // `[shell]` attribute, in which case they're passed to that shell
+      let wrapper = if command.get_program() == shell
+        && command
+          .get_args()
+          .eq(shell_arguments.iter().map(OsStr::new))
+      {
+        None
+      } else {
+        Some(
+          iter::once(command.get_program())
+            .chain(command.get_args())
+            .map(|argument| export_script::quote(&argument.to_string_lossy()))
+            .collect::<Vec<String>>()
+            .join(" "),
+        )
+      };
+
+      let mut body = self.body.iter().peekable();
+
+      while let Some(BodyCommand {
+        text, infallible, ..
+      }) = self.next_command(context.settings, &mut body, &mut evaluator)?
+      {
+        let command = text.as_str();
+
+        if command.is_empty() {
+          continue;
+        }
+
+        let command = match &wrapper {
+          Some(wrapper) if context.settings.positional_arguments => format!(
+            "{wrapper} {} {}{arguments}",
+            export_script::quote(command),
+            export_script::quote(self.name()),
+          ),
+          Some(wrapper) => format!("{wrapper} {}", export_script::quote(command)),
+          None => command.to_owned(),
+        };
+
+        if infallible {
+          lines.push(format!("{{ {command}\n}} || true"));
+        } else {
+          lines.push(command);
+        }
+      }
+    }
+
+    lines.push(")".into());
+
+    Ok(lines)
+  }
 }
 
-impl<'src, D: Display> ColorDisplay for Recipe<'src, D> {
-  fn fmt(&self, f: &mut Formatter, color: Color) -> Result<(), fmt::Error> {
+impl<'src, D: Display> Recipe<'src, D> {
+  /// Write this recipe to `f`, with its body indented by `indent`
+  pub(crate) fn write(
+    &self,
+    f: &mut dyn fmt::Write,
+    color: Color,
+    indent: &str,
+  ) -> Result<(), fmt::Error> {
     if let Some(doc) = self.doc {
-      writeln!(f, "# {doc}")?;
+      writeln!(f, "{}", color.doc().paint(&format!("# {doc}")))?;
     }
 
     for attribute in &self.attributes {
-      writeln!(f, "[{}]", attribute.to_str())?;
+      writeln!(f, "{}", color.annotation().paint(&format!("[{attribute}]")))?;
     }
 
     if self.quiet {
@@ -402,7 +1370,7 @@ impl<'src, D: Display> ColorDisplay for Recipe<'src, D> {
         write!(f, " &&")?;
       }
 
-      write!(f, " {dependency}")?;
+      write!(f, " {}", highlight(&dependency.to_string(), color))?;
     }
 
     for (i, line) in self.body.iter().enumerate() {
@@ -411,11 +1379,21 @@ impl<'src, D: Display> ColorDisplay for Recipe<'src, D> {
       }
       for (j, fragment) in line.fragments.iter().enumerate() {
         if j == 0 {
-          write!(f, "    ")?;
+          write!(f, "{indent}")?;
         }
         match fragment {
           Fragment::Text { token } => write!(f, "{}", token.lexeme())?,
-          Fragment::Interpolation { expression, .. } => write!(f, "{{{{ {expression} }}}}")?,
+          Fragment::Interpolation { expression, .. } => write!(
+            f,
+            "{} {} {}",
+            color
+              .annotation()
+              .paint(&self.interpolation_delimiters.open),
+            highlight(&expression.to_string(), color),
+            color
+              .annotation()
+              .paint(&self.interpolation_delimiters.close),
+          )?,
         }
       }
       if i + 1 < self.body.len() {
@@ -426,6 +1404,12 @@ impl<'src, D: Display> ColorDisplay for Recipe<'src, D> {
   }
 }
 
+impl<'src, D: Display> ColorDisplay for Recipe<'src, D> {
+  fn fmt(&self, f: &mut Formatter, color: Color) -> Result<(), fmt::Error> {
+    self.write(f, color, "    ")
+  }
+}
+
 impl<'src, D> Keyed<'src> for Recipe<'src, D> {
   fn key(&self) -> &'src str {
     self.name.lexeme()
@@ -1,7 +1,10 @@
-use super::*;
+use {super::*, std::cell::RefCell};
 
 pub(crate) struct RecipeContext<'src: 'run, 'run> {
   pub(crate) config: &'run Config,
+  pub(crate) functions: &'run Table<'src, UserFunction<'src>>,
+  pub(crate) login_env: &'run BTreeMap<String, String>,
+  pub(crate) report: &'run RefCell<Report>,
   pub(crate) scope: Scope<'src, 'run>,
   pub(crate) search: &'run Search,
   pub(crate) settings: &'run Settings<'src>,
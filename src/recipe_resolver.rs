@@ -18,14 +18,20 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
     };
 
     while let Some(unresolved) = resolver.unresolved_recipes.pop() {
-      resolver.resolve_recipe(&mut Vec::new(), unresolved)?;
+      resolver.resolve_recipe(&mut Vec::new(), &mut Vec::new(), unresolved)?;
     }
 
     for recipe in resolver.resolved_recipes.values() {
+      let captures = recipe
+        .dependencies
+        .iter()
+        .filter_map(|dependency| dependency.capture)
+        .collect::<Vec<Name>>();
+
       for parameter in &recipe.parameters {
         if let Some(expression) = &parameter.default {
           for variable in expression.variables() {
-            resolver.resolve_variable(&variable, &[])?;
+            resolver.resolve_variable(&variable, &[], &[])?;
           }
         }
       }
@@ -33,7 +39,15 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
       for dependency in &recipe.dependencies {
         for argument in &dependency.arguments {
           for variable in argument.variables() {
-            resolver.resolve_variable(&variable, &recipe.parameters)?;
+            resolver.resolve_variable(&variable, &recipe.parameters, &[])?;
+          }
+        }
+      }
+
+      for attribute in &recipe.attributes {
+        if let Attribute::Env { value, .. } = attribute {
+          for variable in value.variables() {
+            resolver.resolve_variable(&variable, &recipe.parameters, &captures)?;
           }
         }
       }
@@ -42,7 +56,7 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
         for fragment in &line.fragments {
           if let Fragment::Interpolation { expression, .. } = fragment {
             for variable in expression.variables() {
-              resolver.resolve_variable(&variable, &recipe.parameters)?;
+              resolver.resolve_variable(&variable, &recipe.parameters, &captures)?;
             }
           }
         }
@@ -56,10 +70,13 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
     &self,
     variable: &Token<'src>,
     parameters: &[Parameter],
+    captures: &[Name],
   ) -> CompileResult<'src, ()> {
     let name = variable.lexeme();
-    let undefined =
-      !self.assignments.contains_key(name) && !parameters.iter().any(|p| p.name.lexeme() == name);
+    let undefined = !self.assignments.contains_key(name)
+      && !parameters.iter().any(|p| p.name.lexeme() == name)
+      && !captures.iter().any(|capture| capture.lexeme() == name)
+      && dotenv_key(name).is_none();
 
     if undefined {
       return Err(variable.error(UndefinedVariable { variable: name }));
@@ -68,9 +85,14 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
     Ok(())
   }
 
+  /// Resolve `recipe` and its dependencies. `stack` contains the recipes
+  /// currently being resolved, and `edges` the line of the dependency by
+  /// which each was reached from the previous one, and whether it is a
+  /// subsequent dependency, which are used to report circular dependencies.
   fn resolve_recipe(
     &mut self,
     stack: &mut Vec<&'src str>,
+    edges: &mut Vec<(usize, bool)>,
     recipe: UnresolvedRecipe<'src>,
   ) -> CompileResult<'src, Rc<Recipe<'src>>> {
     if let Some(resolved) = self.resolved_recipes.get(recipe.name()) {
@@ -80,28 +102,31 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
     stack.push(recipe.name());
 
     let mut dependencies: Vec<Rc<Recipe>> = Vec::new();
-    for dependency in &recipe.dependencies {
+    for (i, dependency) in recipe.dependencies.iter().enumerate() {
       let name = dependency.recipe.lexeme();
 
+      let edge = (dependency.recipe.line, i >= recipe.priors);
+
       if let Some(resolved) = self.resolved_recipes.get(name) {
         // dependency already resolved
         dependencies.push(Rc::clone(resolved));
-      } else if stack.contains(&name) {
-        let first = stack[0];
-        stack.push(first);
-        return Err(
-          dependency.recipe.error(CircularRecipeDependency {
-            recipe: recipe.name(),
-            circle: stack
-              .iter()
-              .skip_while(|name| **name != dependency.recipe.lexeme())
-              .copied()
-              .collect(),
-          }),
-        );
+      } else if let Some(start) = stack.iter().position(|recipe| *recipe == name) {
+        let mut circle = stack[start..].to_vec();
+        circle.push(name);
+
+        let mut edges = edges[start..].to_vec();
+        edges.push(edge);
+
+        return Err(dependency.recipe.error(CircularRecipeDependency {
+          recipe: recipe.name(),
+          circle,
+          edges,
+        }));
       } else if let Some(unresolved) = self.unresolved_recipes.remove(name) {
         // resolve unresolved dependency
-        dependencies.push(self.resolve_recipe(stack, unresolved)?);
+        edges.push(edge);
+        dependencies.push(self.resolve_recipe(stack, edges, unresolved)?);
+        edges.pop();
       } else {
         // dependency is unknown
         return Err(dependency.recipe.error(UnknownDependency {
@@ -130,7 +155,39 @@ mod tests {
     line:   1,
     column: 3,
     width:  1,
-    kind:   CircularRecipeDependency{recipe: "b", circle: vec!["a", "b", "a"]},
+    kind:   CircularRecipeDependency {
+      recipe: "b",
+      circle: vec!["a", "b", "a"],
+      edges: vec![(0, false), (1, false)],
+    },
+  }
+
+  analysis_error! {
+    name:   circular_recipe_dependency_not_including_first_recipe,
+    input:  "a: b\nb: c\nc: b",
+    offset: 13,
+    line:   2,
+    column: 3,
+    width:  1,
+    kind:   CircularRecipeDependency {
+      recipe: "c",
+      circle: vec!["b", "c", "b"],
+      edges: vec![(1, false), (2, false)],
+    },
+  }
+
+  analysis_error! {
+    name:   circular_subsequent_dependency,
+    input:  "a: && b\nb: a",
+    offset: 11,
+    line:   1,
+    column: 3,
+    width:  1,
+    kind:   CircularRecipeDependency {
+      recipe: "b",
+      circle: vec!["a", "b", "a"],
+      edges: vec![(0, true), (1, false)],
+    },
   }
 
   analysis_error! {
@@ -140,7 +197,11 @@ mod tests {
     line:   0,
     column: 3,
     width:  1,
-    kind:   CircularRecipeDependency{recipe: "a", circle: vec!["a", "a"]},
+    kind:   CircularRecipeDependency {
+      recipe: "a",
+      circle: vec!["a", "a"],
+      edges: vec![(0, false)],
+    },
   }
 
   analysis_error! {
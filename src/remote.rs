@@ -0,0 +1,207 @@
+use {
+  super::*,
+  sha2::{Digest, Sha256},
+};
+
+/// A justfile included from an HTTPS URL with `!include https://…`,
+/// optionally pinned to the SHA-256 hash of its contents with a
+/// `#sha256=HASH` fragment
+///
+/// Remote justfiles are fetched with `curl` and cached in the cache directory
+/// under the hash of their URL. Pinned justfiles which are already cached
+/// with the pinned hash are not fetched again, unpinned justfiles fall back
+/// to their cached copy if they can't be fetched, and with `--offline`, only
+/// cached copies are used.
+pub(crate) struct Remote<'a> {
+  hash: Option<&'a str>,
+  url: &'a str,
+}
+
+impl<'a> Remote<'a> {
+  /// Parse the argument of an `!include` directive, returning `None` if it
+  /// isn't a URL
+  pub(crate) fn parse(argument: &'a str) -> Option<Self> {
+    if !argument.starts_with("https://") {
+      return None;
+    }
+
+    Some(match argument.split_once("#sha256=") {
+      Some((url, hash)) => Self {
+        hash: Some(hash),
+        url,
+      },
+      None => Self {
+        hash: None,
+        url: argument,
+      },
+    })
+  }
+
+  /// The path of a local copy of the justfile, fetching it into the cache
+  /// unless `offline`, or it is pinned and already cached. A pinned justfile
+  /// whose cached copy doesn't match its hash, for example because the pin
+  /// was changed, is fetched again. An unpinned justfile which can't be
+  /// fetched falls back to its cached copy, with a warning. Returns `None` if
+  /// it can't be fetched and the include is `optional`.
+  pub(crate) fn fetch(
+    &self,
+    offline: bool,
+    optional: bool,
+    warnings: &mut Vec<Warning<'static>>,
+  ) -> RunResult<'static, Option<PathBuf>> {
+    let path = Self::directory()?.join(format!("{:x}.just", Sha256::digest(self.url)));
+
+    let io_error = |io_error| Error::RemoteIo {
+      path: path.clone(),
+      io_error,
+    };
+
+    let cached = match fs::read(&path) {
+      Ok(contents) => Some(contents),
+      Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+      Err(error) => return Err(io_error(error)),
+    };
+
+    if let Some(contents) = &cached {
+      if offline || (self.hash.is_some() && self.verify(contents).is_ok()) {
+        self.verify(contents)?;
+        return Ok(Some(path));
+      }
+    }
+
+    if offline {
+      if optional {
+        return Ok(None);
+      }
+
+      return Err(Error::RemoteNotCached {
+        url: self.url.to_owned(),
+        path,
+      });
+    }
+
+    let directory = path.parent().unwrap();
+
+    fs::create_dir_all(directory).map_err(io_error)?;
+
+    let download = tempfile::Builder::new()
+      .prefix(".fetch")
+      .tempfile_in(directory)
+      .map_err(io_error)?;
+
+    let mut command = Command::new("curl");
+
+    command.args([
+      "--fail",
+      "--silent",
+      "--show-error",
+      "--location",
+      "--proto",
+      "=https",
+      "--output",
+    ]);
+
+    command.arg(download.path()).arg(self.url);
+
+    command.stdin(Stdio::null());
+
+    command.stderr(Stdio::inherit());
+
+    if let Err(output_error) = InterruptHandler::guard(|| output(command)) {
+      if self.hash.is_none() && cached.is_some() {
+        warnings.push(Warning::RemoteCached {
+          url: self.url.to_owned(),
+          path: path.clone(),
+        });
+        return Ok(Some(path));
+      }
+
+      if optional {
+        return Ok(None);
+      }
+
+      return Err(Error::RemoteFetch {
+        url: self.url.to_owned(),
+        output_error,
+      });
+    }
+
+    let contents = fs::read(download.path()).map_err(io_error)?;
+
+    self.verify(&contents)?;
+
+    download
+      .persist(&path)
+      .map_err(|error| io_error(error.error))?;
+
+    Ok(Some(path))
+  }
+
+  fn verify(&self, contents: &[u8]) -> RunResult<'static, ()> {
+    let Some(expected) = self.hash else {
+      return Ok(());
+    };
+
+    let actual = format!("{:x}", Sha256::digest(contents));
+
+    if actual.eq_ignore_ascii_case(expected) {
+      Ok(())
+    } else {
+      Err(Error::RemoteHash {
+        url: self.url.to_owned(),
+        expected: expected.to_owned(),
+        actual,
+      })
+    }
+  }
+
+  /// The directory remote justfiles are cached in, which is `$JUST_CACHE_DIR`
+  /// if set, and otherwise `just/remote` in `$XDG_CACHE_HOME` or `~/.cache`
+  fn directory() -> RunResult<'static, PathBuf> {
+    if let Some(directory) = env::var_os("JUST_CACHE_DIR") {
+      return Ok(directory.into());
+    }
+
+    let cache = env::var_os("XDG_CACHE_HOME")
+      .filter(|cache| !cache.is_empty())
+      .map(PathBuf::from)
+      .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+      .ok_or(Error::RemoteDirectory)?;
+
+    Ok(cache.join("just").join("remote"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse() {
+    let remote = Remote::parse("https://example.com/common.just").unwrap();
+    assert_eq!(remote.url, "https://example.com/common.just");
+    assert_eq!(remote.hash, None);
+
+    let remote = Remote::parse("https://example.com/common.just#sha256=abc").unwrap();
+    assert_eq!(remote.url, "https://example.com/common.just");
+    assert_eq!(remote.hash, Some("abc"));
+
+    assert!(Remote::parse("common.just").is_none());
+    assert!(Remote::parse("http://example.com/common.just").is_none());
+  }
+
+  #[test]
+  fn verify() {
+    let hash = format!("{:x}", Sha256::digest("foo"));
+
+    assert!(Remote::parse(&format!("https://example.com#sha256={hash}"))
+      .unwrap()
+      .verify(b"foo")
+      .is_ok());
+
+    assert!(Remote::parse(&format!("https://example.com#sha256={hash}"))
+      .unwrap()
+      .verify(b"bar")
+      .is_err());
+  }
+}
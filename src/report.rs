@@ -0,0 +1,122 @@
+use {super::*, std::io::BufWriter};
+
+/// A machine-readable report of a run, written as JSON to the path given by
+/// `--report`, with the recipes requested on the command line, each recipe
+/// which ran in the order they ran, so that dependencies precede the recipes
+/// that depend on them, and the outcome of the run as a whole
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct Report {
+  duration: f64,
+  error: Option<String>,
+  exit_code: i32,
+  recipes: Vec<RecipeReport>,
+  requested: Vec<Invocation>,
+  status: Status,
+}
+
+#[derive(Debug, Serialize)]
+struct Invocation {
+  arguments: Vec<String>,
+  recipe: String,
+}
+
+#[derive(Debug, Serialize)]
+struct RecipeReport {
+  arguments: Vec<String>,
+  duration: f64,
+  error: Option<String>,
+  exit_code: Option<i32>,
+  recipe: String,
+  status: Status,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum Status {
+  Failed,
+  #[default]
+  Succeeded,
+}
+
+impl Report {
+  /// Record that `recipe` was requested on the command line with
+  /// `positional` and `keywords` arguments
+  pub(crate) fn request(
+    &mut self,
+    recipe: &str,
+    positional: &[&str],
+    keywords: &BTreeMap<&str, &str>,
+  ) {
+    self.requested.push(Invocation {
+      arguments: positional
+        .iter()
+        .map(|&argument| argument.to_owned())
+        .chain(
+          keywords
+            .iter()
+            .map(|(parameter, value)| format!("--{parameter}={value}")),
+        )
+        .collect(),
+      recipe: recipe.to_owned(),
+    });
+  }
+
+  /// Record that the body of `recipe` ran with `arguments` for `duration`,
+  /// and failed with `error`, if any
+  pub(crate) fn record(
+    &mut self,
+    recipe: &str,
+    arguments: &[String],
+    duration: Duration,
+    error: Option<&Error>,
+  ) {
+    self.recipes.push(RecipeReport {
+      arguments: arguments.to_vec(),
+      duration: duration.as_secs_f64(),
+      error: error.map(Error::message),
+      exit_code: error.map_or(Some(0), Error::code),
+      recipe: recipe.to_owned(),
+      status: Status::from(error),
+    });
+  }
+
+  /// Record that the run took `duration` and exited with `exit_code`, after
+  /// failing with `error`, if any, and write the report to `path`
+  pub(crate) fn write(
+    mut self,
+    path: &Path,
+    duration: Duration,
+    error: Option<&Error>,
+    exit_code: i32,
+  ) -> RunResult<'static, ()> {
+    self.duration = duration.as_secs_f64();
+    self.error = error.map(Error::message);
+    self.exit_code = exit_code;
+    self.status = Status::from(error);
+
+    let io_error = |io_error| Error::WriteReport {
+      path: path.to_owned(),
+      io_error,
+    };
+
+    let mut file = BufWriter::new(fs::File::create(path).map_err(io_error)?);
+
+    serde_json::to_writer_pretty(&mut file, &self)
+      .map_err(io::Error::from)
+      .map_err(io_error)?;
+
+    writeln!(file).map_err(io_error)?;
+
+    file.flush().map_err(io_error)
+  }
+}
+
+impl From<Option<&Error<'_>>> for Status {
+  fn from(error: Option<&Error>) -> Self {
+    if error.is_some() {
+      Self::Failed
+    } else {
+      Self::Succeeded
+    }
+  }
+}
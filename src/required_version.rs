@@ -0,0 +1,69 @@
+use super::*;
+
+/// Check that this version of `just` satisfies `requirement`, given by the
+/// `required-version` setting. Invalid requirements are reported by the
+/// parser, so they are ignored here.
+pub(crate) fn check_required_version(requirement: &str) -> RunResult<'static, ()> {
+  let Ok(parsed) = semver::VersionReq::parse(requirement) else {
+    return Ok(());
+  };
+
+  let version = semver::Version::parse(env!("CARGO_PKG_VERSION")).unwrap();
+
+  if parsed.matches(&version) {
+    Ok(())
+  } else {
+    Err(Error::RequiredVersion {
+      requirement: requirement.to_owned(),
+    })
+  }
+}
+
+/// Find the requirement of a `set required-version := '…'` line in `src`
+/// without parsing it, so that it can be checked even if `src` uses syntax
+/// which this version of `just` doesn't support
+pub(crate) fn scan_required_version(src: &str) -> Option<String> {
+  let regex = Regex::new(
+    r#"(?m)^set[ \t]+required-version[ \t]*:=[ \t]*(?:'([^'\n]*)'|"([^"\\\n]*)")[ \t]*(?:#.*)?\r?$"#,
+  )
+  .unwrap();
+
+  let captures = regex.captures(src)?;
+
+  Some(
+    captures
+      .get(1)
+      .or_else(|| captures.get(2))?
+      .as_str()
+      .to_owned(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn scan() {
+    assert_eq!(
+      scan_required_version("foo:\n\nset required-version := '>=1.14.0'\n").as_deref(),
+      Some(">=1.14.0"),
+    );
+    assert_eq!(
+      scan_required_version("set required-version:=\">=2\" # comment\r\n").as_deref(),
+      Some(">=2"),
+    );
+    assert_eq!(scan_required_version("set required-env := ['A']\n"), None);
+    assert_eq!(
+      scan_required_version("x := 'set required-version := \"1\"'"),
+      None
+    );
+  }
+
+  #[test]
+  fn check() {
+    assert!(check_required_version(&format!("={}", env!("CARGO_PKG_VERSION"))).is_ok());
+    assert!(check_required_version(">=1000.0.0").is_err());
+    assert!(check_required_version("foo").is_ok());
+  }
+}
@@ -20,19 +20,70 @@ pub fn run() -> Result<(), i32> {
 
   let config = Config::from_matches(&matches).map_err(Error::from);
 
-  let (color, verbosity, unstable) = config
+  let (color, verbosity, unstable, offline, error_format, exit_status, timestamps) = config
     .as_ref()
-    .map(|config| (config.color, config.verbosity, config.unstable))
-    .unwrap_or((Color::auto(), Verbosity::default(), false));
-
-  let loader = Loader::new(unstable);
-
-  config
-    .and_then(|config| config.run(&loader))
-    .map_err(|error| {
-      if !verbosity.quiet() && error.print_message() {
-        eprintln!("{}", error.color_display(color.stderr()));
-      }
-      error.code().unwrap_or(EXIT_FAILURE)
+    .map(|config| {
+      (
+        config.color,
+        config.verbosity,
+        config.unstable,
+        // one-shot runs only use cached remote includes
+        config.offline || config.one_shot,
+        config.error_format,
+        config.exit_status,
+        config.timestamps.clone(),
+      )
     })
+    .unwrap_or((
+      Color::auto(),
+      Verbosity::default(),
+      false,
+      false,
+      ErrorFormat::Human,
+      ExitStatusMode::Passthrough,
+      None,
+    ));
+
+  // Only runs have a report, which is written whether or not they succeed
+  let report_path = config
+    .as_ref()
+    .ok()
+    .and_then(|config| match config.subcommand {
+      Subcommand::Choose { .. } | Subcommand::Run { .. } => config.report.clone(),
+      _ => None,
+    });
+
+  let loader = Loader::new(unstable, offline);
+
+  let report = RefCell::new(Report::default());
+
+  let start = Instant::now();
+
+  let result = config.and_then(|config| config.run(&loader, &report));
+
+  // Like shells, exit with 128 plus the number of the signal which
+  // interrupted `just`
+  let code = |error: &Error| match InterruptHandler::instance().signal() {
+    Some(signal) if exit_status == ExitStatusMode::Passthrough => 128 + signal,
+    _ => exit_status.code(error),
+  };
+
+  let result = match report_path {
+    Some(path) => {
+      let error = result.as_ref().err();
+      let written = report
+        .into_inner()
+        .write(&path, start.elapsed(), error, error.map_or(0, code));
+      result.and(written)
+    }
+    None => result,
+  };
+
+  result.map_err(|error| {
+    let code = code(&error);
+    if !verbosity.quiet() && error.print_message() {
+      error_format.print(&error, &loader, color.stderr(), timestamps.as_ref(), code);
+    }
+    code
+  })
 }
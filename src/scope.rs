@@ -4,6 +4,10 @@ use super::*;
 pub(crate) struct Scope<'src: 'run, 'run> {
   parent: Option<&'run Scope<'src, 'run>>,
   bindings: Table<'src, Binding<'src, String>>,
+  /// Lazy assignments which haven't been evaluated yet
+  deferred: BTreeMap<&'src str, &'run Assignment<'src>>,
+  /// The values of deferred assignments which have since been evaluated
+  evaluated: RefCell<BTreeMap<&'src str, String>>,
 }
 
 impl<'src, 'run> Scope<'src, 'run> {
@@ -11,6 +15,8 @@ impl<'src, 'run> Scope<'src, 'run> {
     Scope {
       parent: Some(self),
       bindings: Table::new(),
+      deferred: BTreeMap::new(),
+      evaluated: RefCell::default(),
     }
   }
 
@@ -18,17 +24,47 @@ impl<'src, 'run> Scope<'src, 'run> {
     Scope {
       parent: None,
       bindings: Table::new(),
+      deferred: BTreeMap::new(),
+      evaluated: RefCell::default(),
     }
   }
 
   pub(crate) fn bind(&mut self, export: bool, name: Name<'src>, value: String) {
     self.bindings.insert(Binding {
       export,
+      lazy: false,
       name,
       value,
     });
   }
 
+  pub(crate) fn bind_assignment(&mut self, assignment: &Assignment<'src>, value: String) {
+    self.bindings.insert(Binding {
+      export: assignment.export,
+      lazy: assignment.lazy,
+      name: assignment.name,
+      value,
+    });
+  }
+
+  /// Defer evaluation of `assignment` until it is first used
+  pub(crate) fn defer(&mut self, assignment: &'run Assignment<'src>) {
+    self.deferred.insert(assignment.name.lexeme(), assignment);
+  }
+
+  pub(crate) fn deferred(&self, name: &str) -> Option<&'run Assignment<'src>> {
+    self.deferred.get(name).copied()
+  }
+
+  /// The value of deferred assignment `name`, if it has been evaluated
+  pub(crate) fn evaluated(&self, name: &str) -> Option<String> {
+    self.evaluated.borrow().get(name).cloned()
+  }
+
+  pub(crate) fn memoize(&self, name: &'src str, value: String) {
+    self.evaluated.borrow_mut().insert(name, value);
+  }
+
   pub(crate) fn bound(&self, name: &str) -> bool {
     self.bindings.contains_key(name)
   }
@@ -49,6 +85,15 @@ impl<'src, 'run> Scope<'src, 'run> {
     self.bindings.keys().copied()
   }
 
+  /// The outermost scope, which contains the justfile's variables
+  pub(crate) fn root(&self) -> &Self {
+    let mut scope = self;
+    while let Some(parent) = scope.parent {
+      scope = parent;
+    }
+    scope
+  }
+
   pub(crate) fn parent(&self) -> Option<&'run Scope<'src, 'run>> {
     self.parent
   }
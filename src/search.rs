@@ -3,9 +3,16 @@ use {super::*, std::path::Component};
 const DEFAULT_JUSTFILE_NAME: &str = JUSTFILE_NAMES[0];
 const JUSTFILE_NAMES: &[&str] = &["justfile", ".justfile"];
 const PROJECT_ROOT_CHILDREN: &[&str] = &[".bzr", ".git", ".hg", ".svn", "_darcs"];
+const STDIN: &str = "-";
+const STDIN_JUSTFILE_NAME: &str = "<stdin>";
 
+#[derive(Clone, Debug, PartialEq)]
 pub(crate) struct Search {
   pub(crate) justfile: PathBuf,
+  /// Whether the justfile is read from standard input, with `--justfile -`,
+  /// in which case `justfile` is a `<stdin>` placeholder in the invocation
+  /// directory, against which includes are resolved
+  pub(crate) stdin: bool,
   pub(crate) working_directory: PathBuf,
 }
 
@@ -16,6 +23,21 @@ impl Search {
   ) -> SearchResult<Self> {
     match search_config {
       SearchConfig::FromInvocationDirectory => Self::find_next(invocation_directory),
+      SearchConfig::GlobalJustfile => {
+        let candidates = Self::global_justfiles();
+
+        let justfile = candidates
+          .iter()
+          .find(|candidate| candidate.is_file())
+          .cloned()
+          .ok_or(SearchError::GlobalJustfileNotFound { candidates })?;
+
+        Ok(Self {
+          justfile,
+          stdin: false,
+          working_directory: invocation_directory.to_owned(),
+        })
+      }
       SearchConfig::FromSearchDirectory { search_directory } => {
         let search_directory = Self::clean(invocation_directory, search_directory);
 
@@ -25,9 +47,15 @@ impl Search {
 
         Ok(Self {
           justfile,
+          stdin: false,
           working_directory,
         })
       }
+      SearchConfig::WithJustfile { justfile } if justfile == Path::new(STDIN) => Ok(Self {
+        justfile: invocation_directory.join(STDIN_JUSTFILE_NAME),
+        stdin: true,
+        working_directory: invocation_directory.to_owned(),
+      }),
       SearchConfig::WithJustfile { justfile } => {
         let justfile = Self::clean(invocation_directory, justfile);
 
@@ -35,14 +63,24 @@ impl Search {
 
         Ok(Self {
           justfile,
+          stdin: false,
           working_directory,
         })
       }
+      SearchConfig::WithJustfileAndWorkingDirectory {
+        justfile,
+        working_directory,
+      } if justfile == Path::new(STDIN) => Ok(Self {
+        justfile: invocation_directory.join(STDIN_JUSTFILE_NAME),
+        stdin: true,
+        working_directory: Self::clean(invocation_directory, working_directory),
+      }),
       SearchConfig::WithJustfileAndWorkingDirectory {
         justfile,
         working_directory,
       } => Ok(Self {
         justfile: Self::clean(invocation_directory, justfile),
+        stdin: false,
         working_directory: Self::clean(invocation_directory, working_directory),
       }),
     }
@@ -55,6 +93,7 @@ impl Search {
 
     Ok(Self {
       justfile,
+      stdin: false,
       working_directory,
     })
   }
@@ -71,6 +110,7 @@ impl Search {
 
         Ok(Self {
           justfile,
+          stdin: false,
           working_directory,
         })
       }
@@ -84,10 +124,30 @@ impl Search {
 
         Ok(Self {
           justfile,
+          stdin: false,
           working_directory,
         })
       }
 
+      SearchConfig::GlobalJustfile => {
+        let candidates = Self::global_justfiles();
+
+        let justfile = candidates
+          .iter()
+          .find(|candidate| candidate.is_file())
+          .or_else(|| candidates.first())
+          .cloned()
+          .ok_or(SearchError::GlobalJustfileNotFound {
+            candidates: Vec::new(),
+          })?;
+
+        Ok(Self {
+          justfile,
+          stdin: false,
+          working_directory: invocation_directory.to_owned(),
+        })
+      }
+
       SearchConfig::WithJustfile { justfile } => {
         let justfile = Self::clean(invocation_directory, justfile);
 
@@ -95,6 +155,7 @@ impl Search {
 
         Ok(Self {
           justfile,
+          stdin: false,
           working_directory,
         })
       }
@@ -104,11 +165,57 @@ impl Search {
         working_directory,
       } => Ok(Self {
         justfile: Self::clean(invocation_directory, justfile),
+        stdin: false,
         working_directory: Self::clean(invocation_directory, working_directory),
       }),
     }
   }
 
+  /// The paths the global justfile used by `--global` may be found at, in
+  /// order of precedence
+  fn global_justfiles() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+
+    if let Some(config) = env::var_os("XDG_CONFIG_HOME").filter(|config| !config.is_empty()) {
+      candidates.push(Path::new(&config).join("just").join(DEFAULT_JUSTFILE_NAME));
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"));
+
+    if let Some(home) = &home {
+      candidates.push(
+        Path::new(home)
+          .join(".config")
+          .join("just")
+          .join(DEFAULT_JUSTFILE_NAME),
+      );
+    }
+
+    if cfg!(windows) {
+      if let Some(appdata) = env::var_os("APPDATA") {
+        candidates.push(Path::new(&appdata).join("just").join(DEFAULT_JUSTFILE_NAME));
+      }
+    }
+
+    if let Some(home) = &home {
+      if cfg!(target_os = "macos") {
+        candidates.push(
+          Path::new(home)
+            .join("Library")
+            .join("Application Support")
+            .join("just")
+            .join(DEFAULT_JUSTFILE_NAME),
+        );
+      }
+
+      for name in JUSTFILE_NAMES {
+        candidates.push(Path::new(home).join(name));
+      }
+    }
+
+    candidates
+  }
+
   fn justfile(directory: &Path) -> SearchResult<PathBuf> {
     for directory in directory.ancestors() {
       let mut candidates = BTreeSet::new();
@@ -12,6 +12,15 @@ pub(crate) enum SearchError {
     directory: PathBuf,
     io_error: io::Error,
   },
+  #[snafu(display(
+    "No global justfile found{}",
+    if candidates.is_empty() {
+      String::new()
+    } else {
+      format!(", looked for {}", List::or_ticked(candidates.iter().map(|candidate| candidate.display())))
+    }
+  ))]
+  GlobalJustfileNotFound { candidates: Vec<PathBuf> },
   #[snafu(display("Justfile path had no parent: {}", path.display()))]
   JustfileHadNoParent { path: PathBuf },
   #[snafu(display(
@@ -3,16 +3,39 @@ use super::*;
 #[derive(Debug, Clone)]
 pub(crate) enum Setting<'src> {
   AllowDuplicateRecipes(bool),
+  CaseInsensitiveRecipes(bool),
+  CleanEnv(bool),
+  CommandLog(String),
   DotenvFilename(String),
+  DotenvFilenames(Vec<StringLiteral<'src>>),
   DotenvLoad(bool),
   DotenvPath(String),
+  DotenvRequired(bool),
+  Echo(EchoMode),
+  EnvHook(Shell<'src>),
+  EnvPassthrough(Vec<StringLiteral<'src>>),
   Export(bool),
+  ExportPrefix(String),
   Fallback(bool),
+  FallbackJustfiles(Vec<StringLiteral<'src>>),
+  FmtAlignAssignments(bool),
+  FmtBlankLines(usize),
+  FmtIndent(String),
   IgnoreComments(bool),
+  InheritLoginEnv(bool),
+  InteractiveArguments(bool),
+  InterpolationDelimiters(Vec<StringLiteral<'src>>),
+  LineTimeout(Interval),
+  LocalOverlay(bool),
   PositionalArguments(bool),
+  RequiredEnv(Vec<StringLiteral<'src>>),
+  RequiredVersion(String),
   Shell(Shell<'src>),
+  ShellFallbacks(Vec<Shell<'src>>),
+  SourceMap(bool),
   Tempdir(String),
   WindowsPowerShell(bool),
+  WindowsShebang(WindowsShebang),
   WindowsShell(Shell<'src>),
 }
 
@@ -20,16 +43,57 @@ impl<'src> Display for Setting<'src> {
   fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
     match self {
       Setting::AllowDuplicateRecipes(value)
+      | Setting::CaseInsensitiveRecipes(value)
+      | Setting::CleanEnv(value)
       | Setting::DotenvLoad(value)
+      | Setting::DotenvRequired(value)
       | Setting::Export(value)
       | Setting::Fallback(value)
+      | Setting::FmtAlignAssignments(value)
       | Setting::IgnoreComments(value)
+      | Setting::InheritLoginEnv(value)
+      | Setting::InteractiveArguments(value)
+      | Setting::LocalOverlay(value)
       | Setting::PositionalArguments(value)
+      | Setting::SourceMap(value)
       | Setting::WindowsPowerShell(value) => write!(f, "{value}"),
-      Setting::Shell(shell) | Setting::WindowsShell(shell) => write!(f, "{shell}"),
-      Setting::DotenvFilename(value) | Setting::DotenvPath(value) | Setting::Tempdir(value) => {
-        write!(f, "{value:?}")
+      Setting::EnvHook(shell) | Setting::Shell(shell) | Setting::WindowsShell(shell) => {
+        write!(f, "{shell}")
       }
+      Setting::CommandLog(value)
+      | Setting::DotenvFilename(value)
+      | Setting::DotenvPath(value)
+      | Setting::ExportPrefix(value)
+      | Setting::FmtIndent(value)
+      | Setting::RequiredVersion(value)
+      | Setting::Tempdir(value) => write!(f, "{value:?}"),
+      Setting::Echo(echo) => write!(f, "\"{echo}\""),
+      Setting::WindowsShebang(windows_shebang) => write!(f, "\"{windows_shebang}\""),
+      Setting::FmtBlankLines(blank_lines) => write!(f, "\"{blank_lines}\""),
+      Setting::LineTimeout(interval) => write!(f, "\"{interval}\""),
+      Setting::ShellFallbacks(shells) => Self::fmt_list(f, shells),
+      Setting::DotenvFilenames(strings)
+      | Setting::EnvPassthrough(strings)
+      | Setting::FallbackJustfiles(strings)
+      | Setting::InterpolationDelimiters(strings)
+      | Setting::RequiredEnv(strings) => Self::fmt_list(f, strings),
     }
   }
 }
+
+impl Setting<'_> {
+  /// Write `items` as a bracketed, comma-separated list
+  fn fmt_list<T: Display>(f: &mut Formatter, items: &[T]) -> Result<(), fmt::Error> {
+    write!(f, "[")?;
+
+    for (i, item) in items.iter().enumerate() {
+      if i > 0 {
+        write!(f, ", ")?;
+      }
+
+      write!(f, "{item}")?;
+    }
+
+    write!(f, "]")
+  }
+}
@@ -9,16 +9,42 @@ pub(crate) const WINDOWS_POWERSHELL_ARGS: &[&str] = &["-NoLogo", "-Command"];
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct Settings<'src> {
   pub(crate) allow_duplicate_recipes: bool,
+  pub(crate) case_insensitive_recipes: Option<bool>,
+  pub(crate) clean_env: bool,
+  pub(crate) command_log: Option<PathBuf>,
   pub(crate) dotenv_filename: Option<String>,
+  pub(crate) dotenv_filenames: Vec<String>,
   pub(crate) dotenv_load: Option<bool>,
   pub(crate) dotenv_path: Option<PathBuf>,
+  pub(crate) dotenv_required: bool,
+  pub(crate) echo: Option<EchoMode>,
+  pub(crate) env_hook: Option<Shell<'src>>,
+  pub(crate) env_passthrough: Vec<String>,
   pub(crate) export: bool,
+  pub(crate) export_prefix: Option<String>,
   pub(crate) fallback: bool,
+  pub(crate) fallback_justfiles: Vec<String>,
+  pub(crate) fmt_align_assignments: bool,
+  pub(crate) fmt_blank_lines: Option<usize>,
+  pub(crate) fmt_indent: Option<String>,
   pub(crate) ignore_comments: bool,
+  pub(crate) inherit_login_env: bool,
+  pub(crate) interactive_arguments: bool,
+  pub(crate) line_timeout: Option<Interval>,
+  pub(crate) local_overlay: bool,
   pub(crate) positional_arguments: bool,
+  pub(crate) required_env: Vec<String>,
+  pub(crate) required_version: Option<String>,
   pub(crate) shell: Option<Shell<'src>>,
+  /// The index of the shell in `shell_fallbacks` which was found, once it
+  /// has been found, so that `PATH` is only searched once per run
+  #[serde(skip)]
+  pub(crate) shell_fallback: RefCell<Option<usize>>,
+  pub(crate) shell_fallbacks: Vec<Shell<'src>>,
+  pub(crate) source_map: bool,
   pub(crate) tempdir: Option<String>,
   pub(crate) windows_powershell: bool,
+  pub(crate) windows_shebang: WindowsShebang,
   pub(crate) windows_shell: Option<Shell<'src>>,
 }
 
@@ -31,33 +57,114 @@ impl<'src> Settings<'src> {
         Setting::AllowDuplicateRecipes(allow_duplicate_recipes) => {
           settings.allow_duplicate_recipes = allow_duplicate_recipes;
         }
+        Setting::CaseInsensitiveRecipes(case_insensitive_recipes) => {
+          settings.case_insensitive_recipes = Some(case_insensitive_recipes);
+        }
+        Setting::CleanEnv(clean_env) => {
+          settings.clean_env = clean_env;
+        }
+        Setting::CommandLog(path) => {
+          settings.command_log = Some(PathBuf::from(path));
+        }
         Setting::DotenvFilename(filename) => {
           settings.dotenv_filename = Some(filename);
         }
+        Setting::DotenvFilenames(filenames) => {
+          settings.dotenv_filenames = filenames
+            .into_iter()
+            .map(|filename| filename.cooked)
+            .collect();
+        }
         Setting::DotenvLoad(dotenv_load) => {
           settings.dotenv_load = Some(dotenv_load);
         }
         Setting::DotenvPath(path) => {
           settings.dotenv_path = Some(PathBuf::from(path));
         }
+        Setting::DotenvRequired(dotenv_required) => {
+          settings.dotenv_required = dotenv_required;
+        }
+        Setting::Echo(echo) => {
+          settings.echo = Some(echo);
+        }
+        Setting::EnvHook(env_hook) => {
+          settings.env_hook = Some(env_hook);
+        }
+        Setting::EnvPassthrough(variables) => {
+          settings.env_passthrough = variables
+            .into_iter()
+            .map(|variable| variable.cooked)
+            .collect();
+        }
         Setting::Export(export) => {
           settings.export = export;
         }
+        Setting::ExportPrefix(export_prefix) => {
+          settings.export_prefix = Some(export_prefix);
+        }
         Setting::Fallback(fallback) => {
           settings.fallback = fallback;
         }
+        Setting::FallbackJustfiles(justfiles) => {
+          settings.fallback_justfiles = justfiles
+            .into_iter()
+            .map(|justfile| justfile.cooked)
+            .collect();
+        }
+        Setting::FmtAlignAssignments(fmt_align_assignments) => {
+          settings.fmt_align_assignments = fmt_align_assignments;
+        }
+        Setting::FmtBlankLines(fmt_blank_lines) => {
+          settings.fmt_blank_lines = Some(fmt_blank_lines);
+        }
+        Setting::FmtIndent(fmt_indent) => {
+          settings.fmt_indent = Some(fmt_indent);
+        }
         Setting::IgnoreComments(ignore_comments) => {
           settings.ignore_comments = ignore_comments;
         }
+        Setting::InheritLoginEnv(inherit_login_env) => {
+          settings.inherit_login_env = inherit_login_env;
+        }
+        Setting::InteractiveArguments(interactive_arguments) => {
+          settings.interactive_arguments = interactive_arguments;
+        }
+        // Interpolation delimiters only affect how recipes in the justfile
+        // which sets them are lexed and parsed
+        Setting::InterpolationDelimiters(_) => {}
+        Setting::LineTimeout(line_timeout) => {
+          settings.line_timeout = Some(line_timeout);
+        }
+        Setting::LocalOverlay(local_overlay) => {
+          settings.local_overlay = local_overlay;
+        }
         Setting::PositionalArguments(positional_arguments) => {
           settings.positional_arguments = positional_arguments;
         }
+        Setting::RequiredEnv(variables) => {
+          settings.required_env = variables
+            .into_iter()
+            .map(|variable| variable.cooked)
+            .collect();
+        }
+        Setting::RequiredVersion(requirement) => {
+          settings.required_version = Some(requirement);
+        }
         Setting::Shell(shell) => {
           settings.shell = Some(shell);
         }
+        Setting::ShellFallbacks(shells) => {
+          settings.shell_fallbacks = shells;
+        }
+        Setting::SourceMap(source_map) => {
+          settings.source_map = source_map;
+        }
         Setting::WindowsPowerShell(windows_powershell) => {
           settings.windows_powershell = windows_powershell;
         }
+        Setting::WindowsShebang(windows_shebang) => {
+          settings.windows_shebang = windows_shebang;
+        }
         Setting::WindowsShell(windows_shell) => {
           settings.windows_shell = Some(windows_shell);
         }
@@ -70,18 +177,90 @@ impl<'src> Settings<'src> {
     settings
   }
 
-  pub(crate) fn shell_command(&self, config: &Config) -> Command {
-    let (command, args) = self.shell(config);
+  /// Whether recipes named on the command line may be matched regardless of
+  /// case, which defaults to true on Windows
+  pub(crate) fn case_insensitive_recipes(&self) -> bool {
+    self.case_insensitive_recipes.unwrap_or(cfg!(windows))
+  }
+
+  /// Whether commands are run with a clean environment, containing only the
+  /// variables in `env-passthrough` from just's own environment, plus `.env`
+  /// and exported variables
+  pub(crate) fn clean_env(&self, config: &Config) -> bool {
+    config.clean_env || self.clean_env
+  }
+
+  /// Whether `name` is passed through from just's own environment when
+  /// commands are run with a clean environment. Names are case-insensitive on
+  /// Windows, as environment variable names are there.
+  pub(crate) fn passes_through(&self, name: &OsStr) -> bool {
+    self.env_passthrough.iter().any(|variable| {
+      if cfg!(windows) {
+        OsStr::new(variable).eq_ignore_ascii_case(name)
+      } else {
+        OsStr::new(variable) == name
+      }
+    })
+  }
+
+  /// The name of the environment variable which the variable or parameter
+  /// `name` is exported as, without any namespace. With `export-prefix`,
+  /// `name` is uppercased and prefixed, so `version` is exported as
+  /// `JUST_VERSION` with a prefix of `JUST_`.
+  pub(crate) fn export_name(&self, name: &str) -> String {
+    let name = env_var_name(name);
+    match &self.export_prefix {
+      Some(prefix) => format!("{prefix}{}", name.to_uppercase()),
+      None => name.to_owned(),
+    }
+  }
+
+  pub(crate) fn shell_command(&self, config: &Config) -> RunResult<'static, Command> {
+    self.recipe_shell_command(config, None)
+  }
+
+  /// The command used to run the lines of a recipe, see `recipe_shell`
+  pub(crate) fn recipe_shell_command(
+    &self,
+    config: &Config,
+    attribute: Option<&Shell>,
+  ) -> RunResult<'static, Command> {
+    let (command, args) = self.recipe_shell(config, attribute)?;
 
     let mut cmd = Command::new(command);
 
     cmd.args(args);
 
-    cmd
+    Ok(cmd)
   }
 
-  pub(crate) fn shell<'a>(&'a self, config: &'a Config) -> (&'a str, Vec<&'a str>) {
-    match (&config.shell, &config.shell_args) {
+  pub(crate) fn shell<'a>(
+    &'a self,
+    config: &'a Config,
+  ) -> RunResult<'static, (&'a str, Vec<&'a str>)> {
+    self.recipe_shell(config, None)
+  }
+
+  /// The shell used to run the lines of a recipe, where `attribute` is the
+  /// shell set with its `[shell]` attribute, which takes the place of the
+  /// `shell` setting, but not of `windows-shell` or `windows-powershell`
+  pub(crate) fn recipe_shell<'a>(
+    &'a self,
+    config: &'a Config,
+    attribute: Option<&'a Shell>,
+  ) -> RunResult<'static, (&'a str, Vec<&'a str>)> {
+    fn cooked<'a>(shell: &'a Shell) -> (&'a str, Vec<&'a str>) {
+      (
+        shell.command.cooked.as_ref(),
+        shell
+          .arguments
+          .iter()
+          .map(|argument| argument.cooked.as_ref())
+          .collect(),
+      )
+    }
+
+    Ok(match (&config.shell, &config.shell_args) {
       (Some(shell), Some(shell_args)) => (shell, shell_args.iter().map(String::as_ref).collect()),
       (Some(shell), None) => (shell, DEFAULT_SHELL_ARGS.to_vec()),
       (None, Some(shell_args)) => (
@@ -90,30 +269,54 @@ impl<'src> Settings<'src> {
       ),
       (None, None) => {
         if let (true, Some(shell)) = (cfg!(windows), &self.windows_shell) {
-          (
-            shell.command.cooked.as_ref(),
-            shell
-              .arguments
-              .iter()
-              .map(|argument| argument.cooked.as_ref())
-              .collect(),
-          )
+          cooked(shell)
         } else if cfg!(windows) && self.windows_powershell {
           (WINDOWS_POWERSHELL_SHELL, WINDOWS_POWERSHELL_ARGS.to_vec())
-        } else if let Some(shell) = &self.shell {
-          (
-            shell.command.cooked.as_ref(),
-            shell
-              .arguments
-              .iter()
-              .map(|argument| argument.cooked.as_ref())
-              .collect(),
-          )
+        } else if let Some(shell) = attribute.or(self.shell.as_ref()) {
+          cooked(shell)
+        } else if !self.shell_fallbacks.is_empty() {
+          cooked(self.shell_fallback()?)
         } else {
           (DEFAULT_SHELL, DEFAULT_SHELL_ARGS.to_vec())
         }
       }
+    })
+  }
+
+  /// The first shell in `shell-fallbacks` whose command can be found
+  fn shell_fallback(&self) -> RunResult<'static, &Shell<'src>> {
+    let mut index = self.shell_fallback.borrow_mut();
+
+    if index.is_none() {
+      *index = self
+        .shell_fallbacks
+        .iter()
+        .position(|shell| Self::find_command(&shell.command.cooked));
     }
+
+    index
+      .map(|index| &self.shell_fallbacks[index])
+      .ok_or_else(|| Error::ShellFallbacksNotFound {
+        shells: self
+          .shell_fallbacks
+          .iter()
+          .map(|shell| shell.command.cooked.clone())
+          .collect(),
+      })
+  }
+
+  /// Whether `command` is a path to an executable, or the name of an
+  /// executable in one of the directories in `PATH`
+  pub(crate) fn find_command(command: &str) -> bool {
+    let path = Path::new(command);
+
+    if path.components().count() > 1 {
+      return Platform::is_executable(path);
+    }
+
+    env::var_os("PATH").is_some_and(|paths| {
+      env::split_paths(&paths).any(|directory| Platform::is_executable(&directory.join(path)))
+    })
   }
 }
 
@@ -130,7 +333,7 @@ mod tests {
       ..testing::config(&[])
     };
 
-    assert_eq!(settings.shell(&config), ("sh", vec!["-cu"]));
+    assert_eq!(settings.shell(&config).unwrap(), ("sh", vec!["-cu"]));
   }
 
   #[test]
@@ -147,11 +350,11 @@ mod tests {
 
     if cfg!(windows) {
       assert_eq!(
-        settings.shell(&config),
+        settings.shell(&config).unwrap(),
         ("powershell.exe", vec!["-NoLogo", "-Command"])
       );
     } else {
-      assert_eq!(settings.shell(&config), ("sh", vec!["-cu"]));
+      assert_eq!(settings.shell(&config).unwrap(), ("sh", vec!["-cu"]));
     }
   }
 
@@ -166,7 +369,7 @@ mod tests {
       ..testing::config(&[])
     };
 
-    assert_eq!(settings.shell(&config), ("lol", vec!["-nice"]));
+    assert_eq!(settings.shell(&config).unwrap(), ("lol", vec!["-nice"]));
   }
 
   #[test]
@@ -183,7 +386,7 @@ mod tests {
       ..testing::config(&[])
     };
 
-    assert_eq!(settings.shell(&config), ("lol", vec!["-nice"]));
+    assert_eq!(settings.shell(&config).unwrap(), ("lol", vec!["-nice"]));
   }
 
   #[test]
@@ -209,7 +412,54 @@ mod tests {
       ..testing::config(&[])
     };
 
-    assert_eq!(settings.shell(&config), ("asdf.exe", vec!["-nope"]));
+    assert_eq!(
+      settings.shell(&config).unwrap(),
+      ("asdf.exe", vec!["-nope"])
+    );
+  }
+
+  #[test]
+  fn recipe_shell_attribute() {
+    let literal = |cooked: &'static str| StringLiteral {
+      kind: StringKind::from_token_start("\"").unwrap(),
+      raw: cooked,
+      cooked: cooked.to_string(),
+    };
+
+    let attribute = Shell {
+      command: literal("bash"),
+      arguments: vec![literal("-c")],
+    };
+
+    let settings = Settings {
+      shell: Some(Shell {
+        command: literal("zsh"),
+        arguments: Vec::new(),
+      }),
+      windows_powershell: true,
+      ..Default::default()
+    };
+
+    let config = testing::config(&[]);
+
+    if cfg!(windows) {
+      assert_eq!(
+        settings.recipe_shell(&config, Some(&attribute)).unwrap(),
+        ("powershell.exe", vec!["-NoLogo", "-Command"])
+      );
+    } else {
+      assert_eq!(
+        settings.recipe_shell(&config, Some(&attribute)).unwrap(),
+        ("bash", vec!["-c"])
+      );
+    }
+
+    let config = testing::config(&["--shell", "fish"]);
+
+    assert_eq!(
+      settings.recipe_shell(&config, Some(&attribute)).unwrap(),
+      ("fish", vec!["-cu"])
+    );
   }
 
   #[test]
@@ -224,7 +474,7 @@ mod tests {
       ..testing::config(&[])
     };
 
-    assert_eq!(settings.shell(&config).0, "lol");
+    assert_eq!(settings.shell(&config).unwrap().0, "lol");
   }
 
   #[test]
@@ -240,6 +490,46 @@ mod tests {
       ..testing::config(&[])
     };
 
-    assert_eq!(settings.shell(&config), ("sh", vec!["-nice"]));
+    assert_eq!(settings.shell(&config).unwrap(), ("sh", vec!["-nice"]));
+  }
+
+  #[test]
+  fn shell_fallback_is_only_searched_for_once() {
+    let literal = |cooked: &'static str| StringLiteral {
+      kind: StringKind::from_token_start("\"").unwrap(),
+      raw: cooked,
+      cooked: cooked.to_string(),
+    };
+
+    let settings = Settings {
+      shell_fallbacks: vec![
+        Shell {
+          command: literal("just-test-missing-shell"),
+          arguments: Vec::new(),
+        },
+        Shell {
+          command: literal("sh"),
+          arguments: vec![literal("-c")],
+        },
+      ],
+      ..Default::default()
+    };
+
+    let config = Config {
+      shell_command: false,
+      ..testing::config(&[])
+    };
+
+    if cfg!(unix) {
+      assert_eq!(settings.shell(&config).unwrap(), ("sh", vec!["-c"]));
+      assert_eq!(*settings.shell_fallback.borrow(), Some(1));
+    }
+
+    settings.shell_fallback.replace(Some(0));
+
+    assert_eq!(
+      settings.shell(&config).unwrap(),
+      ("just-test-missing-shell", Vec::new())
+    );
   }
 }
@@ -38,11 +38,22 @@ impl<'line> Shebang<'line> {
       .unwrap_or(self.interpreter)
   }
 
+  /// Whether the interpreter is PowerShell, which runs scripts by path only
+  /// when they are passed with `-File`
+  pub(crate) fn is_powershell(&self) -> bool {
+    matches!(
+      self.interpreter_filename(),
+      "powershell" | "powershell.exe" | "pwsh" | "pwsh.exe"
+    )
+  }
+
   pub(crate) fn script_filename(&self, recipe: &str) -> String {
-    match self.interpreter_filename() {
-      "cmd" | "cmd.exe" => format!("{recipe}.bat"),
-      "powershell" | "powershell.exe" | "pwsh" | "pwsh.exe" => format!("{recipe}.ps1"),
-      _ => recipe.to_owned(),
+    if self.is_powershell() {
+      format!("{recipe}.ps1")
+    } else if matches!(self.interpreter_filename(), "cmd" | "cmd.exe") {
+      format!("{recipe}.bat")
+    } else {
+      recipe.to_owned()
     }
   }
 
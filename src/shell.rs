@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Ord, PartialOrd, Eq)]
 pub(crate) struct Shell<'src> {
   pub(crate) arguments: Vec<StringLiteral<'src>>,
   pub(crate) command: StringLiteral<'src>,
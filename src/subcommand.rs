@@ -14,18 +14,48 @@ pub(crate) enum Subcommand {
     binary: OsString,
     overrides: BTreeMap<String, String>,
   },
+  Complete {
+    words: Vec<String>,
+  },
   Completions {
     shell: String,
   },
+  Doc {
+    format: String,
+  },
   Dump,
-  Edit,
+  DumpAst,
+  Edit {
+    recipe: Option<String>,
+  },
+  EmitAliases {
+    shell: String,
+  },
   Evaluate {
     overrides: BTreeMap<String, String>,
-    variable: Option<String>,
+    variables: Vec<String>,
+  },
+  ExportScript {
+    arguments: Vec<String>,
+    inline_dependencies: bool,
+    output: Option<PathBuf>,
+    overrides: BTreeMap<String, String>,
+  },
+  ExportTasks {
+    editor: String,
   },
   Format,
-  Init,
+  Init {
+    template: Option<String>,
+  },
+  InstallLib {
+    source: String,
+  },
   List,
+  ListGroups,
+  ListTemplates,
+  Lsp,
+  Migrate,
   Run {
     arguments: Vec<String>,
     overrides: BTreeMap<String, String>,
@@ -34,6 +64,7 @@ pub(crate) enum Subcommand {
     name: String,
   },
   Summary,
+  Trust,
   Variables,
 }
 
@@ -42,6 +73,7 @@ impl Subcommand {
     &self,
     config: &Config,
     loader: &'src Loader,
+    report: &RefCell<Report>,
   ) -> Result<(), Error<'src>> {
     use Subcommand::*;
 
@@ -51,36 +83,85 @@ impl Subcommand {
         return Ok(());
       }
       Completions { shell } => return Self::completions(shell),
-      Init => return Self::init(config),
+      Init { template } => return Self::init(config, template.as_deref()),
+      ListTemplates => {
+        Self::list_templates();
+        return Ok(());
+      }
+      Lsp => return LanguageServer::run(),
       Run {
         arguments,
         overrides,
-      } => return Self::run(config, loader, arguments, overrides),
+      } => return Self::run(config, loader, arguments, overrides, report),
       _ => {}
     }
 
-    let search = Search::find(&config.search_config, &config.invocation_directory)?;
+    let search = config.search()?;
+
+    if let Edit { recipe } = self {
+      return Self::edit(config, loader, &search, recipe.as_deref());
+    }
 
-    if let Edit = self {
-      return Self::edit(&search);
+    if let InstallLib { source } = self {
+      return Self::install_lib(config, &search, source);
     }
 
-    let (src, ast, justfile) = Self::compile(config, loader, &search)?;
+    if let Trust = self {
+      return Self::trust(config, loader, &search);
+    }
+
+    // `--fmt` formats the justfile itself, so its overlay must not be merged
+    let (src, ast, justfile) =
+      Self::compile(config, loader, &search, !matches!(self, Format | Migrate))?;
+
+    if let Choose { .. } | Command { .. } | Evaluate { .. } | ExportScript { .. } = self {
+      self::Trust::check(config, &search, src)?;
+    }
 
     match self {
       Choose { overrides, chooser } => {
-        Self::choose(config, justfile, &search, overrides, chooser.as_deref())?;
+        Self::choose(
+          config,
+          loader,
+          justfile,
+          &search,
+          overrides,
+          chooser.as_deref(),
+          report,
+        )?;
       }
       Command { overrides, .. } | Evaluate { overrides, .. } => {
-        justfile.run(config, &search, overrides, &[])?;
+        justfile.run(config, loader, &search, overrides, &[], report)?;
       }
-      Dump => Self::dump(config, ast, justfile)?,
-      Format => Self::format(config, &search, src, ast)?,
-      List => Self::list(config, justfile),
-      Show { ref name } => Self::show(config, name, justfile)?,
+      Complete { words } => Self::complete(&justfile, words),
+      Doc { format } => Self::doc(config, &justfile, format),
+      Dump => Self::dump(config, loader, &search, ast, justfile)?,
+      DumpAst => println!("{}", syntax_tree(&ast)),
+      EmitAliases { shell } => Self::emit_aliases(config, &justfile, shell),
+      ExportScript {
+        arguments,
+        overrides,
+        ..
+      } => justfile.run(config, loader, &search, overrides, arguments, report)?,
+      ExportTasks { editor } => Self::export_tasks(config, &justfile, editor),
+      Format => Self::format(config, &search, src, ast, &justfile.settings)?,
+      List => Self::list(config, loader, &search, justfile),
+      ListGroups => Self::list_groups(config, &justfile),
+      Migrate => Self::migrate(config, loader, &search, &justfile.settings)?,
+      Show { ref name } => Self::show(config, loader, &search, name, justfile)?,
       Summary => Self::summary(config, justfile),
       Variables => Self::variables(justfile),
-      Changelog | Completions { .. } | Edit | Init | Run { .. } => unreachable!(),
+      Changelog
+      | Completions { .. }
+      | Edit { .. }
+      | Init { .. }
+      | InstallLib { .. }
+      | ListTemplates
+      | Lsp
+      | Run { .. }
+      | Trust => {
+        unreachable!()
+      }
     }
 
     Ok(())
@@ -91,6 +172,7 @@ impl Subcommand {
     loader: &'src Loader,
     arguments: &[String],
     overrides: &BTreeMap<String, String>,
+    report: &RefCell<Report>,
   ) -> Result<(), Error<'src>> {
     if matches!(
       config.search_config,
@@ -109,7 +191,13 @@ impl Subcommand {
       let mut unknown_recipes_errors = None;
 
       loop {
-        let search = match Search::find_next(&path) {
+        let search = if path == starting_path {
+          config.search()
+        } else {
+          Search::find_next(&path)
+        };
+
+        let search = match search {
           Err(SearchError::NotFound) => match unknown_recipes_errors {
             Some(err) => return Err(err),
             None => return Err(SearchError::NotFound.into()),
@@ -119,22 +207,28 @@ impl Subcommand {
             if config.verbosity.loquacious() && path != starting_path {
               eprintln!(
                 "Trying {}",
-                starting_path
-                  .strip_prefix(path)
-                  .unwrap()
-                  .components()
-                  .map(|_| path::Component::ParentDir)
-                  .collect::<PathBuf>()
-                  .join(search.justfile.file_name().unwrap())
-                  .display()
+                Self::fallback_display(&starting_path, &search.justfile)
               );
             }
             search
           }
         };
 
-        match Self::run_inner(config, loader, arguments, overrides, &search) {
-          Err((err @ Error::UnknownRecipes { .. }, true)) => {
+        match Self::run_inner(config, loader, arguments, overrides, &search, report) {
+          Err((err @ Error::UnknownRecipes { .. }, Some(justfiles))) if !justfiles.is_empty() => {
+            return Self::run_fallback_justfiles(
+              config,
+              loader,
+              arguments,
+              overrides,
+              &starting_path,
+              &search,
+              &justfiles,
+              report,
+            )
+            .unwrap_or(Err(err));
+          }
+          Err((err @ Error::UnknownRecipes { .. }, Some(_))) => {
             match search.justfile.parent().unwrap().parent() {
               Some(parent) => {
                 unknown_recipes_errors.get_or_insert(err);
@@ -143,7 +237,12 @@ impl Subcommand {
               None => return Err(err),
             }
           }
-          result => return result.map_err(|(err, _fallback)| err),
+          result => {
+            if config.verbosity.loquacious() && path != starting_path {
+              Self::report_fallback(&starting_path, &search);
+            }
+            return result.map_err(|(err, _fallback)| err);
+          }
         }
       }
     } else {
@@ -152,37 +251,158 @@ impl Subcommand {
         loader,
         arguments,
         overrides,
-        &Search::find(&config.search_config, &config.invocation_directory)?,
+        &config.search()?,
+        report,
       )
       .map_err(|(err, _fallback)| err)
     }
   }
 
+  /// Try each of the justfiles named by the `fallback-justfiles` setting of
+  /// the justfile found by `search`, in order, returning `None` if none of
+  /// them have the recipes being run
+  #[allow(clippy::too_many_arguments)]
+  fn run_fallback_justfiles<'src>(
+    config: &Config,
+    loader: &'src Loader,
+    arguments: &[String],
+    overrides: &BTreeMap<String, String>,
+    starting_path: &Path,
+    search: &Search,
+    justfiles: &[String],
+    report: &RefCell<Report>,
+  ) -> Option<Result<(), Error<'src>>> {
+    let directory = search.justfile.parent().unwrap();
+
+    for justfile in justfiles {
+      let search = match Search::find(
+        &SearchConfig::WithJustfile {
+          justfile: directory.join(justfile).lexiclean(),
+        },
+        directory,
+      ) {
+        Ok(search) => search,
+        Err(err) => return Some(Err(err.into())),
+      };
+
+      if config.verbosity.loquacious() {
+        eprintln!(
+          "Trying {}",
+          Self::fallback_display(starting_path, &search.justfile)
+        );
+      }
+
+      match Self::run_inner(config, loader, arguments, overrides, &search, report) {
+        Err((Error::UnknownRecipes { .. }, _fallback)) => {}
+        result => {
+          if config.verbosity.loquacious() {
+            Self::report_fallback(starting_path, &search);
+          }
+          return Some(result.map_err(|(err, _fallback)| err));
+        }
+      }
+    }
+
+    None
+  }
+
+  /// Display `justfile` relative to `starting_path` if it's in one of its
+  /// ancestors, and otherwise in full
+  fn fallback_display(starting_path: &Path, justfile: &Path) -> String {
+    let directory = justfile.parent().unwrap();
+
+    match (starting_path.strip_prefix(directory), justfile.file_name()) {
+      (Ok(relative), Some(file_name)) => relative
+        .components()
+        .map(|_| path::Component::ParentDir)
+        .collect::<PathBuf>()
+        .join(file_name)
+        .display()
+        .to_string(),
+      _ => justfile.display().to_string(),
+    }
+  }
+
+  fn report_fallback(starting_path: &Path, search: &Search) {
+    eprintln!(
+      "Recipes were run by fallback justfile {}",
+      Self::fallback_display(starting_path, &search.justfile)
+    );
+  }
+
+  /// Run the justfile found by `search`. If it fails, also returns the
+  /// `fallback-justfiles` to search, which are empty if any parent directory
+  /// should be searched, or `None` if fallback is disabled.
   fn run_inner<'src>(
     config: &Config,
     loader: &'src Loader,
     arguments: &[String],
     overrides: &BTreeMap<String, String>,
     search: &Search,
-  ) -> Result<(), (Error<'src>, bool)> {
-    let (_src, _ast, justfile) =
-      Self::compile(config, loader, search).map_err(|err| (err, false))?;
+    report: &RefCell<Report>,
+  ) -> Result<(), (Error<'src>, Option<Vec<String>>)> {
+    let (src, _ast, justfile) =
+      Self::compile(config, loader, search, true).map_err(|err| (err, None))?;
+    Trust::check(config, search, src).map_err(|err| (err, None))?;
     justfile
-      .run(config, search, overrides, arguments)
-      .map_err(|err| (err, justfile.settings.fallback))
+      .run(config, loader, search, overrides, arguments, report)
+      .map_err(|err| {
+        let settings = &justfile.settings;
+        let fallback =
+          config.fallback && (settings.fallback || !settings.fallback_justfiles.is_empty());
+        (err, fallback.then(|| settings.fallback_justfiles.clone()))
+      })
   }
 
+  /// Compile the justfile found by `search`, merging its `justfile.local`
+  /// overlay on top of it if `overlay` is true and it has `set local-overlay`.
+  /// Returns the source of the justfile, followed by that of the overlay if
+  /// it was merged, so that trusting it covers both.
   fn compile<'src>(
     config: &Config,
     loader: &'src Loader,
     search: &Search,
+    overlay: bool,
   ) -> Result<(&'src str, Ast<'src>, Justfile<'src>), Error<'src>> {
-    let src = loader.load(&search.justfile)?;
+    let mut src = loader.load_justfile(search)?;
+
+    let mut compiled = Compiler::compile(src);
 
-    let (ast, justfile) = Compiler::compile(src)?;
+    if let Ok((_, justfile)) = &compiled {
+      let path = Self::overlay_path(&search.justfile);
+
+      if overlay && justfile.settings.local_overlay && path.is_file() {
+        let overlay = loader.load(&path)?;
+        compiled = Compiler::compile_with_overlay(src, overlay);
+        src = loader.alloc(format!("{src}{overlay}"));
+      }
+    }
+
+    let (ast, justfile) = match compiled {
+      Ok(compiled) => compiled,
+      Err(compile_errors) => {
+        // A justfile which requires a newer version of `just` may fail to
+        // compile because it uses newer syntax, so check the requirement
+        // before reporting the errors
+        if let Some(requirement) = scan_required_version(src) {
+          check_required_version(&requirement)?;
+        }
+
+        let justfile = search
+          .justfile
+          .strip_prefix(&config.invocation_directory)
+          .unwrap_or(&search.justfile);
+
+        return Err(Error::from(compile_errors).in_justfile(justfile));
+      }
+    };
+
+    if let Some(requirement) = &justfile.settings.required_version {
+      check_required_version(requirement)?;
+    }
 
     if config.verbosity.loud() {
-      for warning in &justfile.warnings {
+      for warning in loader.take_warnings().iter().chain(&justfile.warnings) {
         eprintln!("{}", warning.color_display(config.color.stderr()));
       }
     }
@@ -190,16 +410,26 @@ impl Subcommand {
     Ok((src, ast, justfile))
   }
 
+  /// The path of the overlay merged on top of `justfile` with `set
+  /// local-overlay`, which is its filename with `.local` appended
+  fn overlay_path(justfile: &Path) -> PathBuf {
+    let mut filename = justfile.file_name().unwrap_or_default().to_owned();
+    filename.push(".local");
+    justfile.with_file_name(filename)
+  }
+
   fn changelog() {
     print!("{}", include_str!("../CHANGELOG.md"));
   }
 
   fn choose<'src>(
     config: &Config,
+    loader: &Loader,
     justfile: Justfile<'src>,
     search: &Search,
     overrides: &BTreeMap<String, String>,
     chooser: Option<&str>,
+    report: &RefCell<Report>,
   ) -> Result<(), Error<'src>> {
     let recipes = justfile
       .public_recipes(config.unsorted)
@@ -219,7 +449,7 @@ impl Subcommand {
 
     let result = justfile
       .settings
-      .shell_command(config)
+      .shell_command(config)?
       .arg(&chooser)
       .current_dir(&search.working_directory)
       .stdin(Stdio::piped())
@@ -229,7 +459,7 @@ impl Subcommand {
     let mut child = match result {
       Ok(child) => child,
       Err(io_error) => {
-        let (shell_binary, shell_arguments) = justfile.settings.shell(config);
+        let (shell_binary, shell_arguments) = justfile.settings.shell(config)?;
         return Err(Error::ChooserInvoke {
           shell_binary: shell_binary.to_owned(),
           shell_arguments: shell_arguments.join(" "),
@@ -271,7 +501,13 @@ impl Subcommand {
       .map(str::to_owned)
       .collect::<Vec<String>>();
 
-    justfile.run(config, search, overrides, &recipes)
+    justfile.run(config, loader, search, overrides, &recipes, report)
+  }
+
+  fn complete(justfile: &Justfile, words: &[String]) {
+    for completion in Completion::complete(justfile, words) {
+      println!("{completion}");
+    }
   }
 
   fn completions(shell: &str) -> RunResult<'static, ()> {
@@ -288,6 +524,11 @@ impl Subcommand {
       }
     }
 
+    if shell.eq_ignore_ascii_case("nushell") {
+      println!("{}", completions::nushell(&Config::app()));
+      return Ok(());
+    }
+
     let shell = shell
       .parse::<Shell>()
       .expect("Invalid value for clap::Shell");
@@ -318,7 +559,11 @@ impl Subcommand {
           replace(&mut script, needle, replacement)?;
         }
       }
-      Shell::Elvish => {}
+      Shell::Elvish => {
+        for (needle, replacement) in completions::ELVISH_COMPLETION_REPLACEMENTS {
+          replace(&mut script, needle, replacement)?;
+        }
+      }
     }
 
     println!("{}", script.trim());
@@ -326,26 +571,73 @@ impl Subcommand {
     Ok(())
   }
 
-  fn dump(config: &Config, ast: Ast, justfile: Justfile) -> Result<(), Error<'static>> {
+  fn doc(config: &Config, justfile: &Justfile, format: &str) {
+    let recipes = justfile.public_recipes(config.unsorted);
+
+    if format.eq_ignore_ascii_case("man") {
+      print!("{}", documentation::man(justfile, &recipes));
+    } else {
+      print!("{}", documentation::markdown(justfile, &recipes));
+    }
+  }
+
+  fn dump(
+    config: &Config,
+    loader: &Loader,
+    search: &Search,
+    ast: Ast,
+    justfile: Justfile,
+  ) -> Result<(), Error<'static>> {
     match config.dump_format {
       DumpFormat::Json => {
         serde_json::to_writer(io::stdout(), &justfile)
           .map_err(|serde_json_error| Error::DumpJson { serde_json_error })?;
         println!();
       }
+      DumpFormat::Just if config.resolve_imports => print!("{}", loader.resolve(search)?),
       DumpFormat::Just => print!("{ast}"),
     }
     Ok(())
   }
 
-  fn edit(search: &Search) -> Result<(), Error<'static>> {
+  fn edit<'src>(
+    config: &Config,
+    loader: &'src Loader,
+    search: &Search,
+    recipe: Option<&str>,
+  ) -> RunResult<'src, ()> {
     let editor = env::var_os("VISUAL")
       .or_else(|| env::var_os("EDITOR"))
       .unwrap_or_else(|| "vim".into());
 
+    let arguments = if let Some(name) = recipe {
+      let (_, _, justfile) = Self::compile(config, loader, search, false)?;
+
+      let Some(recipe) = justfile
+        .get_alias(name)
+        .map(|alias| alias.target.as_ref())
+        .or_else(|| justfile.get_recipe(name))
+      else {
+        return Err(Error::UnknownRecipes {
+          recipes: vec![name.to_owned()],
+          suggestions: justfile.suggest_recipes(name),
+        });
+      };
+
+      let token = recipe.name.token();
+
+      let (path, line) = loader
+        .origin(token)
+        .unwrap_or_else(|| (search.justfile.clone(), token.line));
+
+      Self::editor_arguments(&editor, &path, line + 1)
+    } else {
+      vec![search.justfile.clone().into()]
+    };
+
     let error = Command::new(&editor)
       .current_dir(&search.working_directory)
-      .arg(&search.justfile)
+      .args(arguments)
       .status();
 
     let status = match error {
@@ -360,42 +652,62 @@ impl Subcommand {
     Ok(())
   }
 
-  fn format(config: &Config, search: &Search, src: &str, ast: Ast) -> Result<(), Error<'static>> {
+  /// Arguments which open `path` at one-based `line` in `editor`, for editors
+  /// known to support it, and which otherwise just open `path`
+  fn editor_arguments(editor: &OsStr, path: &Path, line: usize) -> Vec<OsString> {
+    let name = Path::new(editor)
+      .file_stem()
+      .and_then(OsStr::to_str)
+      .unwrap_or_default()
+      .to_lowercase();
+
+    let mut location = path.as_os_str().to_owned();
+    location.push(format!(":{line}"));
+
+    match name.as_str() {
+      "emacs" | "emacsclient" | "gedit" | "gvim" | "kak" | "micro" | "mvim" | "nano" | "nvim"
+      | "vi" | "vim" => vec![format!("+{line}").into(), path.into()],
+      "code" | "code-insiders" | "codium" | "cursor" => vec!["--goto".into(), location],
+      "hx" | "subl" | "zed" => vec![location],
+      _ => vec![path.into()],
+    }
+  }
+
+  fn format(
+    config: &Config,
+    search: &Search,
+    src: &str,
+    ast: Ast,
+    settings: &Settings,
+  ) -> Result<(), Error<'static>> {
     config.require_unstable("The `--fmt` command is currently unstable.")?;
 
-    let formatted = ast.to_string();
+    let formatted = keep_line_endings(src, ast.format(settings));
+
+    if config.stdout {
+      print!("{formatted}");
+      return Ok(());
+    }
 
     if config.check {
       return if formatted == src {
         Ok(())
       } else {
         if !config.verbosity.quiet() {
-          use similar::{ChangeTag, TextDiff};
-
-          let diff = TextDiff::configure()
-            .algorithm(similar::Algorithm::Patience)
-            .diff_lines(src, &formatted);
-
-          for op in diff.ops() {
-            for change in diff.iter_changes(op) {
-              let (symbol, color) = match change.tag() {
-                ChangeTag::Delete => ("-", config.color.stdout().diff_deleted()),
-                ChangeTag::Equal => (" ", config.color.stdout()),
-                ChangeTag::Insert => ("+", config.color.stdout().diff_added()),
-              };
-
-              print!("{}{symbol}{change}{}", color.prefix(), color.suffix());
-            }
-          }
+          Self::print_format_diff(config, search, src, &formatted);
         }
 
-        Err(Error::FormatCheckFoundDiff)
+        Err(Error::FormatCheckFoundDiff {
+          print_message: !config.diff_only,
+        })
       };
     }
 
-    fs::write(&search.justfile, formatted).map_err(|io_error| Error::WriteJustfile {
-      justfile: search.justfile.clone(),
-      io_error,
+    Self::replace_justfile(&search.justfile, &formatted).map_err(|io_error| {
+      Error::WriteJustfile {
+        justfile: search.justfile.clone(),
+        io_error,
+      }
     })?;
 
     if config.verbosity.loud() {
@@ -405,14 +717,194 @@ impl Subcommand {
     Ok(())
   }
 
-  fn init(config: &Config) -> Result<(), Error<'static>> {
+  /// Print a unified diff of the justfile against its formatted version. With
+  /// `--diff-only`, hunks have no context lines, so that each hunk header
+  /// gives the exact lines which need to change.
+  fn print_format_diff(config: &Config, search: &Search, src: &str, formatted: &str) {
+    use similar::{ChangeTag, TextDiff};
+
+    let color = config.color.stdout();
+
+    let path = search
+      .justfile
+      .strip_prefix(&config.invocation_directory)
+      .unwrap_or(&search.justfile)
+      .display();
+
+    let diff = TextDiff::configure()
+      .algorithm(similar::Algorithm::Patience)
+      .diff_lines(src, formatted);
+
+    println!("{}", color.diff_deleted().paint(&format!("--- {path}")));
+    println!("{}", color.diff_added().paint(&format!("+++ {path}")));
+
+    for hunk in diff
+      .unified_diff()
+      .context_radius(if config.diff_only { 0 } else { 3 })
+      .iter_hunks()
+    {
+      println!("{}", color.annotation().paint(&hunk.header().to_string()));
+
+      for change in hunk.iter_changes() {
+        let (symbol, color) = match change.tag() {
+          ChangeTag::Delete => ("-", color.diff_deleted()),
+          ChangeTag::Equal => (" ", color),
+          ChangeTag::Insert => ("+", color.diff_added()),
+        };
+
+        print!("{}{symbol}{change}{}", color.prefix(), color.suffix());
+      }
+    }
+  }
+
+  /// Rewrite the legacy constructs in the justfile, and the local files it
+  /// includes, to their modern equivalents, and print a summary of the
+  /// changes made. Each file is migrated on its own, so that included files
+  /// aren't inlined, and files with legacy constructs are also formatted,
+  /// except for their `!include` directives and `import` statements.
+  fn migrate<'src>(
+    config: &Config,
+    loader: &'src Loader,
+    search: &Search,
+    settings: &Settings,
+  ) -> RunResult<'src, ()> {
+    config.require_unstable("The `--migrate` command is currently unstable.")?;
+
+    for path in loader.local_sources(search)? {
+      let src = fs::read_to_string(&path).map_err(|io_error| Error::Load {
+        path: path.clone(),
+        io_error,
+      })?;
+
+      let mut migrations = Vec::new();
+      let mut header = String::new();
+      let mut header_lines = 0;
+      let mut offset = 0;
+      let mut body_start = 0;
+
+      for (i, line) in src.split_inclusive('\n').enumerate() {
+        match Include::parse(line, &path, i).transpose()? {
+          Some(include) => {
+            header.push_str(&src[body_start..offset]);
+
+            if include.import {
+              header.push_str(line);
+            } else {
+              header.push_str(&include.to_string());
+              header.push('\n');
+              migrations.push(Migration::Include { line: i });
+            }
+
+            header_lines = i + 1;
+            body_start = offset + line.len();
+          }
+          None if line.trim().is_empty() || line.trim().starts_with('#') => {}
+          None => break,
+        }
+
+        offset += line.len();
+      }
+
+      let body = loader.alloc(src[body_start..].to_owned());
+
+      let mut ast = Parser::parse(&Lexer::lex(body, &InterpolationDelimiters::scan(body))?)?;
+
+      migrations.extend(
+        Migration::apply(&mut ast)
+          .into_iter()
+          .map(|migration| migration.offset(header_lines)),
+      );
+
+      if migrations.is_empty() {
+        continue;
+      }
+
+      let formatted = ast.format(settings);
+
+      let mut migrated = header;
+
+      if !migrated.is_empty()
+        && !formatted.is_empty()
+        && body.trim_start_matches(' ').starts_with(['\n', '\r'])
+      {
+        migrated.push('\n');
+      }
+
+      migrated.push_str(&formatted);
+
+      let migrated = keep_line_endings(&src, migrated);
+
+      Self::replace_justfile(&path, &migrated).map_err(|io_error| Error::WriteJustfile {
+        justfile: path.clone(),
+        io_error,
+      })?;
+
+      if !config.verbosity.quiet() {
+        let path = path
+          .strip_prefix(&config.invocation_directory)
+          .unwrap_or(&path)
+          .display();
+
+        for migration in migrations {
+          eprintln!("{path}: {migration}");
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Replace the contents of the justfile at `path` by writing them to a
+  /// temporary file in the same directory and renaming it over the original,
+  /// so that the justfile is never left partially written. The original's
+  /// permissions are kept, and if `path` is a symlink, its target is replaced.
+  fn replace_justfile(path: &Path, contents: &str) -> io::Result<()> {
+    let path = fs::canonicalize(path)?;
+
+    // Renaming would succeed even if the justfile is read-only, so check that
+    // it is writable first
+    fs::OpenOptions::new().write(true).open(&path)?;
+
+    let permissions = fs::metadata(&path)?.permissions();
+
+    let mut tempfile = tempfile::Builder::new()
+      .prefix(".justfile")
+      .tempfile_in(path.parent().unwrap_or_else(|| Path::new(".")))?;
+
+    tempfile.write_all(contents.as_bytes())?;
+    tempfile.as_file().sync_all()?;
+
+    fs::set_permissions(tempfile.path(), permissions)?;
+
+    tempfile.persist(&path).map_err(|error| error.error)?;
+
+    Ok(())
+  }
+
+  fn init(config: &Config, template: Option<&str>) -> Result<(), Error<'static>> {
     let search = Search::init(&config.search_config, &config.invocation_directory)?;
 
     if search.justfile.is_file() {
-      Err(Error::InitExists {
+      return Err(Error::InitExists {
         justfile: search.justfile,
-      })
-    } else if let Err(io_error) = fs::write(&search.justfile, INIT_JUSTFILE) {
+      });
+    }
+
+    // the directory containing the global justfile may not exist yet
+    if config.search_config == SearchConfig::GlobalJustfile {
+      if let Some(parent) = search.justfile.parent() {
+        fs::create_dir_all(parent).map_err(|io_error| Error::WriteJustfile {
+          justfile: search.justfile.clone(),
+          io_error,
+        })?;
+      }
+    }
+
+    let justfile = template
+      .and_then(Template::get)
+      .map_or(INIT_JUSTFILE, |template| template.justfile);
+
+    if let Err(io_error) = fs::write(&search.justfile, justfile) {
       Err(Error::WriteJustfile {
         justfile: search.justfile,
         io_error,
@@ -425,7 +917,28 @@ impl Subcommand {
     }
   }
 
-  fn list(config: &Config, justfile: Justfile) {
+  fn list(config: &Config, loader: &Loader, search: &Search, justfile: Justfile) {
+    // The file and zero-based line each recipe was defined on
+    let source = |recipe: &Recipe<Dependency>| {
+      let token = recipe.name.token();
+      loader
+        .origin(token)
+        .unwrap_or_else(|| (search.justfile.clone(), token.line))
+    };
+
+    let root = search.justfile.parent().unwrap_or(&search.justfile);
+
+    let source_file = config
+      .list_source_file
+      .as_ref()
+      .map(|path| config.invocation_directory.join(path).lexiclean());
+
+    let listed = |recipe: &Recipe<Dependency>| {
+      source_file.as_ref().map_or(true, |source_file| {
+        source(recipe).0.lexiclean() == *source_file
+      })
+    };
+
     // Construct a target to alias map.
     let mut recipe_aliases: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
     for alias in justfile.aliases.values() {
@@ -444,7 +957,7 @@ impl Subcommand {
     let mut line_widths: BTreeMap<&str, usize> = BTreeMap::new();
 
     for (name, recipe) in &justfile.recipes {
-      if recipe.private {
+      if recipe.private || !listed(recipe) {
         continue;
       }
 
@@ -468,7 +981,7 @@ impl Subcommand {
     let doc_color = config.color.stdout().doc();
     print!("{}", config.list_heading);
 
-    for recipe in justfile.public_recipes(config.unsorted) {
+    let print_recipe = |recipe: &Recipe<Dependency>| {
       let name = recipe.name();
 
       for (i, name) in iter::once(&name)
@@ -483,18 +996,34 @@ impl Subcommand {
         // Declaring this outside of the nested loops will probably be more efficient,
         // but it creates all sorts of lifetime issues with variables inside the loops.
         // If this is inlined like the docs say, it shouldn't make any difference.
-        let print_doc = |doc| {
-          print!(
-            " {:padding$}{} {}",
-            "",
-            doc_color.paint("#"),
-            doc_color.paint(doc),
-            padding = max_line_width
-              .saturating_sub(line_widths.get(name).copied().unwrap_or(max_line_width))
-          );
+        let print_doc = |doc: &str| {
+          for (i, line) in doc.trim_end_matches('\n').split('\n').enumerate() {
+            if i == 0 {
+              print!(
+                " {:padding$}{} {}",
+                "",
+                doc_color.paint("#"),
+                doc_color.paint(line),
+                padding = max_line_width
+                  .saturating_sub(line_widths.get(name).copied().unwrap_or(max_line_width))
+              );
+            } else {
+              // align continuation lines of multi-line docs with the first
+              print!(
+                "\n{:padding$} {}",
+                "",
+                doc_color.paint("#"),
+                padding = UnicodeWidthStr::width(config.list_prefix.as_str()) + max_line_width
+              );
+
+              if !line.is_empty() {
+                print!(" {}", doc_color.paint(line));
+              }
+            }
+          }
         };
 
-        match (i, recipe.doc) {
+        match (i, recipe.doc()) {
           (0, Some(doc)) => print_doc(doc),
           (0, None) => (),
           _ => {
@@ -503,23 +1032,290 @@ impl Subcommand {
           }
         }
         println!();
+
+        if i == 0 && config.list_source {
+          let (path, line) = source(recipe);
+          println!(
+            "{}    {}",
+            config.list_prefix,
+            doc_color.paint(&format!(
+              "source: {}:{}",
+              path.strip_prefix(root).unwrap_or(&path).display(),
+              line + 1
+            )),
+          );
+        }
+
+        if i == 0 && config.verbosity.loquacious() {
+          for parameter in &recipe.parameters {
+            if let Some(doc) = &parameter.doc {
+              println!(
+                "{}    {}: {}",
+                config.list_prefix,
+                config
+                  .color
+                  .stdout()
+                  .parameter()
+                  .paint(parameter.name.lexeme()),
+                doc_color.paint(doc),
+              );
+            }
+          }
+
+          let see_also = recipe.see_also().collect::<Vec<&str>>();
+
+          if !see_also.is_empty() {
+            println!(
+              "{}    {}",
+              config.list_prefix,
+              doc_color.paint(&format!("see also: {}", see_also.join(", "))),
+            );
+          }
+        }
+      }
+    };
+
+    let recipes = justfile
+      .public_recipes(config.unsorted)
+      .into_iter()
+      .filter(|recipe| listed(recipe))
+      .collect::<Vec<&Recipe<Dependency>>>();
+
+    let mut printed = false;
+
+    for recipe in &recipes {
+      if recipe.groups().is_empty() && recipe.namespace().is_none() {
+        print_recipe(recipe);
+        printed = true;
+      }
+    }
+
+    for group in justfile.public_groups(config.unsorted) {
+      if !recipes
+        .iter()
+        .any(|recipe| recipe.groups().contains(&group))
+      {
+        continue;
+      }
+
+      if printed {
+        println!();
+      }
+
+      println!("{}[{group}]", config.list_prefix);
+
+      for recipe in &recipes {
+        if recipe.groups().contains(&group) {
+          print_recipe(recipe);
+        }
+      }
+
+      printed = true;
+    }
+
+    for namespace in justfile.public_namespaces(config.unsorted) {
+      if !recipes
+        .iter()
+        .any(|recipe| recipe.groups().is_empty() && recipe.namespace() == Some(namespace))
+      {
+        continue;
+      }
+
+      if printed {
+        println!();
+      }
+
+      println!("{}{namespace}::", config.list_prefix);
+
+      for recipe in &recipes {
+        if recipe.groups().is_empty() && recipe.namespace() == Some(namespace) {
+          print_recipe(recipe);
+        }
       }
+
+      printed = true;
     }
   }
 
-  fn show<'src>(config: &Config, name: &str, justfile: Justfile<'src>) -> Result<(), Error<'src>> {
+  fn install_lib(config: &Config, search: &Search, source: &str) -> RunResult<'static, ()> {
+    let path = Library::parse(source)?.install(&search.justfile)?;
+
+    if config.verbosity.loud() {
+      eprintln!("Installed library `{source}` to `{}`", path.display());
+    }
+
+    Ok(())
+  }
+
+  fn export_tasks(config: &Config, justfile: &Justfile, editor: &str) {
+    let recipes = justfile.public_recipes(config.unsorted);
+
+    if editor.eq_ignore_ascii_case("jetbrains") {
+      print!("{}", jetbrains_run_configurations(&recipes));
+    } else {
+      print!("{}", vscode_tasks(&recipes));
+    }
+  }
+
+  fn trust<'src>(config: &Config, loader: &'src Loader, search: &Search) -> RunResult<'src, ()> {
+    let src = loader.load(&search.justfile)?;
+
+    Trust::trust(&search.justfile, src)?;
+
+    if config.verbosity.loud() {
+      eprintln!("Trusted justfile `{}`", search.justfile.display());
+    }
+
+    Ok(())
+  }
+
+  /// Print a shell alias for each public recipe, named `j` followed by the
+  /// initial of each word of the recipe's name, or, if that would be the same
+  /// for more than one recipe, `j` followed by the whole name. Aliases which
+  /// would still be the same for more than one recipe, or which would shadow
+  /// a command, are skipped with a warning.
+  fn emit_aliases(config: &Config, justfile: &Justfile, shell: &str) {
+    // Shell builtins which aliases could shadow, but which aren't on `PATH`
+    const BUILTINS: &[&str] = &["jobs"];
+
+    let recipes = justfile.public_recipes(config.unsorted);
+
+    let abbreviate = |name: &str| {
+      iter::once('j')
+        .chain(
+          name
+            .split(|c| c == '-' || c == '_')
+            .filter_map(|word| word.chars().next()),
+        )
+        .collect::<String>()
+    };
+
+    let mut counts = BTreeMap::<String, usize>::new();
+
+    for recipe in &recipes {
+      *counts.entry(abbreviate(recipe.name())).or_default() += 1;
+    }
+
+    let aliases = recipes
+      .iter()
+      .map(|recipe| {
+        let name = recipe.name();
+        let alias = abbreviate(name);
+        if counts[&alias] > 1 {
+          (name, format!("j{name}"))
+        } else {
+          (name, alias)
+        }
+      })
+      .collect::<Vec<(&str, String)>>();
+
+    let warn = |message: String| {
+      if config.verbosity.loud() {
+        let color = config.color.stderr();
+        let message_color = color.message();
+        eprintln!(
+          "{} {}{message}{}",
+          color.warning().paint("warning:"),
+          message_color.prefix(),
+          message_color.suffix(),
+        );
+      }
+    };
+
+    for (name, alias) in &aliases {
+      if let Some((other, _)) = aliases
+        .iter()
+        .find(|(other, other_alias)| other != name && other_alias == alias)
+      {
+        warn(format!(
+          "Skipping alias `{alias}` for recipe `{name}`, since it would also be the alias for \
+           recipe `{other}`"
+        ));
+        continue;
+      }
+
+      if BUILTINS.contains(&alias.as_str()) || Settings::find_command(alias) {
+        warn(format!(
+          "Skipping alias `{alias}` for recipe `{name}`, since it would shadow the command \
+           `{alias}`"
+        ));
+        continue;
+      }
+
+      match shell.to_ascii_lowercase().as_str() {
+        "fish" => println!("alias {alias} 'just {name}'"),
+        "pwsh" => println!("function {alias} {{ just {name} @args }}"),
+        _ => println!("alias {alias}='just {name}'"),
+      }
+    }
+  }
+
+  fn list_templates() {
+    let width = Template::ALL
+      .iter()
+      .map(|template| template.name.len())
+      .max()
+      .unwrap_or_default();
+
+    println!("Available templates:");
+
+    for template in Template::ALL {
+      println!("    {:width$} # {}", template.name, template.description);
+    }
+  }
+
+  fn list_groups(config: &Config, justfile: &Justfile) {
+    println!("Recipe groups:");
+
+    for group in justfile.public_groups(config.unsorted) {
+      println!("{}{group}", config.list_prefix);
+    }
+  }
+
+  fn show<'src>(
+    config: &Config,
+    loader: &Loader,
+    search: &Search,
+    name: &str,
+    justfile: Justfile<'src>,
+  ) -> Result<(), Error<'src>> {
+    let color = if config.highlight {
+      config.color.stdout()
+    } else {
+      Color::never()
+    };
+
+    // Recipes which come from an included file are preceded by a comment
+    // recording where they were defined
+    let show = |recipe: &Recipe| {
+      if let Some((path, line)) = loader
+        .origin(recipe.name.token())
+        .filter(|(path, _)| path.lexiclean() != search.justfile.lexiclean())
+      {
+        let root = search.justfile.parent().unwrap_or(&search.justfile);
+        let path = path.strip_prefix(root).unwrap_or(&path);
+        println!(
+          "{}",
+          color
+            .doc()
+            .paint(&format!("# defined in {}:{}", path.display(), line + 1))
+        );
+      }
+      println!("{}", recipe.color_display(color));
+    };
+
     if let Some(alias) = justfile.get_alias(name) {
       let recipe = justfile.get_recipe(alias.target.name.lexeme()).unwrap();
       println!("{alias}");
-      println!("{}", recipe.color_display(config.color.stdout()));
+      show(recipe);
       Ok(())
     } else if let Some(recipe) = justfile.get_recipe(name) {
-      println!("{}", recipe.color_display(config.color.stdout()));
+      show(recipe);
       Ok(())
     } else {
       Err(Error::UnknownRecipes {
         recipes: vec![name.to_owned()],
-        suggestion: justfile.suggest_recipe(name),
+        suggestions: justfile.suggest_recipes(name),
       })
     }
   }
@@ -6,6 +6,17 @@ pub(crate) struct Suggestion<'src> {
   pub(crate) target: Option<&'src str>,
 }
 
+impl<'src> Suggestion<'src> {
+  /// The suggested name, and what it is an alias for, if anything, for use
+  /// in a list of several suggestions
+  pub(crate) fn candidate(&self) -> String {
+    match self.target {
+      Some(target) => format!("`{}` (an alias for `{target}`)", self.name),
+      None => format!("`{}`", self.name),
+    }
+  }
+}
+
 impl<'src> Display for Suggestion<'src> {
   fn fmt(&self, f: &mut Formatter) -> fmt::Result {
     write!(f, "Did you mean `{}`", self.name)?;
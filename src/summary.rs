@@ -19,9 +19,9 @@ use {
 
 mod full {
   pub(crate) use crate::{
-    assignment::Assignment, conditional_operator::ConditionalOperator, dependency::Dependency,
-    expression::Expression, fragment::Fragment, justfile::Justfile, line::Line,
-    parameter::Parameter, parameter_kind::ParameterKind, recipe::Recipe, thunk::Thunk,
+    assignment::Assignment, condition::Condition, conditional_operator::ConditionalOperator,
+    dependency::Dependency, expression::Expression, fragment::Fragment, justfile::Justfile,
+    line::Line, parameter::Parameter, parameter_kind::ParameterKind, recipe::Recipe, thunk::Thunk,
   };
 }
 
@@ -30,7 +30,13 @@ pub fn summary(path: &Path) -> Result<Result<Summary, String>, io::Error> {
 
   match Compiler::compile(&text) {
     Ok((_, justfile)) => Ok(Ok(Summary::new(justfile))),
-    Err(compilation_error) => Ok(Err(compilation_error.to_string())),
+    Err(compilation_errors) => Ok(Err(
+      compilation_errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join("\n"),
+    )),
   }
 }
 
@@ -166,6 +172,7 @@ impl Fragment {
 pub struct Assignment {
   pub exported: bool,
   pub expression: Expression,
+  pub lazy: bool,
 }
 
 impl Assignment {
@@ -173,6 +180,7 @@ impl Assignment {
     Assignment {
       exported: assignment.export,
       expression: Expression::new(&assignment.value),
+      lazy: assignment.lazy,
     }
   }
 }
@@ -191,11 +199,9 @@ pub enum Expression {
     rhs: Box<Expression>,
   },
   Conditional {
-    lhs: Box<Expression>,
-    rhs: Box<Expression>,
+    condition: Condition,
     then: Box<Expression>,
     otherwise: Box<Expression>,
-    operator: ConditionalOperator,
   },
   Join {
     lhs: Option<Box<Expression>>,
@@ -242,6 +248,20 @@ impl Expression {
             arguments,
           }
         }
+        full::Thunk::UnaryPlus {
+          name,
+          args: (a, rest),
+          ..
+        } => {
+          let mut arguments = vec![Expression::new(a)];
+          for arg in rest {
+            arguments.push(Expression::new(arg));
+          }
+          Expression::Call {
+            name: name.lexeme().to_owned(),
+            arguments,
+          }
+        }
         full::Thunk::Binary {
           name, args: [a, b], ..
         } => Expression::Call {
@@ -270,6 +290,10 @@ impl Expression {
           name: name.lexeme().to_owned(),
           arguments: vec![Expression::new(a), Expression::new(b), Expression::new(c)],
         },
+        full::Thunk::User { name, args } => Expression::Call {
+          name: name.lexeme().to_owned(),
+          arguments: args.iter().map(Expression::new).collect(),
+        },
       },
       Concatenation { lhs, rhs } => Expression::Concatenation {
         lhs: Box::new(Expression::new(lhs)),
@@ -280,16 +304,12 @@ impl Expression {
         rhs: Box::new(Expression::new(rhs)),
       },
       Conditional {
-        lhs,
-        operator,
+        condition,
         otherwise,
-        rhs,
         then,
       } => Expression::Conditional {
-        lhs: Box::new(Expression::new(lhs)),
-        operator: ConditionalOperator::new(*operator),
+        condition: Condition::new(condition),
         otherwise: Box::new(Expression::new(otherwise)),
-        rhs: Box::new(Expression::new(rhs)),
         then: Box::new(Expression::new(then)),
       },
       StringLiteral { string_literal } => Expression::String {
@@ -303,6 +323,51 @@ impl Expression {
   }
 }
 
+#[derive(Eq, PartialEq, Hash, Ord, PartialOrd, Debug, Clone)]
+pub enum Condition {
+  And {
+    lhs: Box<Condition>,
+    rhs: Box<Condition>,
+  },
+  Comparison {
+    lhs: Box<Expression>,
+    rhs: Box<Expression>,
+    operator: ConditionalOperator,
+  },
+  Not {
+    condition: Box<Condition>,
+  },
+  Or {
+    lhs: Box<Condition>,
+    rhs: Box<Condition>,
+  },
+}
+
+impl Condition {
+  fn new(condition: &full::Condition) -> Self {
+    use full::Condition::*;
+    match condition {
+      And { lhs, rhs } => Self::And {
+        lhs: Box::new(Self::new(lhs)),
+        rhs: Box::new(Self::new(rhs)),
+      },
+      Comparison { lhs, rhs, operator } => Self::Comparison {
+        lhs: Box::new(Expression::new(lhs)),
+        rhs: Box::new(Expression::new(rhs)),
+        operator: ConditionalOperator::new(*operator),
+      },
+      Group { contents } => Self::new(contents),
+      Not { condition } => Self::Not {
+        condition: Box::new(Self::new(condition)),
+      },
+      Or { lhs, rhs } => Self::Or {
+        lhs: Box::new(Self::new(lhs)),
+        rhs: Box::new(Self::new(rhs)),
+      },
+    }
+  }
+}
+
 #[derive(Eq, PartialEq, Hash, Ord, PartialOrd, Debug, Clone)]
 pub enum ConditionalOperator {
   Equality,
@@ -324,6 +389,7 @@ impl ConditionalOperator {
 pub struct Dependency {
   pub recipe: String,
   pub arguments: Vec<Expression>,
+  pub capture: Option<String>,
 }
 
 impl Dependency {
@@ -331,6 +397,9 @@ impl Dependency {
     Self {
       recipe: dependency.recipe.name().to_owned(),
       arguments: dependency.arguments.iter().map(Expression::new).collect(),
+      capture: dependency
+        .capture
+        .map(|capture| capture.lexeme().to_owned()),
     }
   }
 }
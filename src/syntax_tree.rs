@@ -0,0 +1,211 @@
+use {
+  super::*,
+  serde_json::{json, Value},
+};
+
+/// The location of `token` in the justfile, with zero-based offset, line, and
+/// column
+fn span(token: Token) -> Value {
+  json!({
+    "offset": token.offset,
+    "length": token.length,
+    "line": token.line,
+    "column": token.column,
+  })
+}
+
+fn name(name: Name) -> Value {
+  json!({
+    "name": name.lexeme(),
+    "span": span(name.token()),
+  })
+}
+
+fn condition(condition: &Condition) -> Value {
+  match condition {
+    Condition::And { lhs, rhs } => json!({
+      "kind": "and",
+      "lhs": self::condition(lhs),
+      "rhs": self::condition(rhs),
+    }),
+    Condition::Comparison { lhs, rhs, operator } => json!({
+      "kind": "comparison",
+      "operator": operator.to_string(),
+      "lhs": expression(lhs),
+      "rhs": expression(rhs),
+    }),
+    Condition::Group { contents } => json!({
+      "kind": "group",
+      "contents": self::condition(contents),
+    }),
+    Condition::Not { condition } => json!({
+      "kind": "not",
+      "condition": self::condition(condition),
+    }),
+    Condition::Or { lhs, rhs } => json!({
+      "kind": "or",
+      "lhs": self::condition(lhs),
+      "rhs": self::condition(rhs),
+    }),
+  }
+}
+
+fn expression(expression: &Expression) -> Value {
+  match expression {
+    Expression::Backtick { contents, token } => json!({
+      "kind": "backtick",
+      "contents": contents,
+      "span": span(*token),
+    }),
+    Expression::Call { thunk } => json!({
+      "kind": "call",
+      "function": name(*thunk.name()),
+      "arguments": thunk.arguments().into_iter().map(self::expression).collect::<Vec<Value>>(),
+    }),
+    Expression::Concatenation { lhs, rhs } => json!({
+      "kind": "concatenation",
+      "lhs": self::expression(lhs),
+      "rhs": self::expression(rhs),
+    }),
+    Expression::Conditional {
+      condition,
+      then,
+      otherwise,
+    } => json!({
+      "kind": "conditional",
+      "condition": self::condition(condition),
+      "then": self::expression(then),
+      "otherwise": self::expression(otherwise),
+    }),
+    Expression::Group { contents } => json!({
+      "kind": "group",
+      "contents": self::expression(contents),
+    }),
+    Expression::Join { lhs, rhs } => json!({
+      "kind": "join",
+      "lhs": lhs.as_deref().map(self::expression),
+      "rhs": self::expression(rhs),
+    }),
+    Expression::StringLiteral { string_literal } => json!({
+      "kind": "string",
+      "raw": string_literal.raw,
+      "cooked": string_literal.cooked,
+    }),
+    Expression::Variable { name } => json!({
+      "kind": "variable",
+      "variable": self::name(*name),
+    }),
+  }
+}
+
+fn recipe(recipe: &UnresolvedRecipe) -> Value {
+  let parameters = recipe
+    .parameters
+    .iter()
+    .map(|parameter| {
+      json!({
+        "name": name(parameter.name),
+        "kind": parameter.kind,
+        "export": parameter.export,
+        "default": parameter.default.as_ref().map(expression),
+        "doc": parameter.doc,
+      })
+    })
+    .collect::<Vec<Value>>();
+
+  let dependencies = recipe
+    .dependencies
+    .iter()
+    .enumerate()
+    .map(|(i, dependency)| {
+      json!({
+        "recipe": name(dependency.recipe),
+        "arguments": dependency.arguments.iter().map(expression).collect::<Vec<Value>>(),
+        "subsequent": i >= recipe.priors,
+      })
+    })
+    .collect::<Vec<Value>>();
+
+  let body = recipe
+    .body
+    .iter()
+    .map(|line| {
+      line
+        .fragments
+        .iter()
+        .map(|fragment| match fragment {
+          Fragment::Text { token } => json!({
+            "kind": "text",
+            "text": token.lexeme(),
+            "span": span(*token),
+          }),
+          Fragment::Interpolation { expression } => json!({
+            "kind": "interpolation",
+            "expression": self::expression(expression),
+          }),
+        })
+        .collect::<Vec<Value>>()
+    })
+    .collect::<Vec<Vec<Value>>>();
+
+  json!({
+    "kind": "recipe",
+    "name": name(recipe.name),
+    "doc": recipe.doc,
+    "attributes": recipe.attributes,
+    "quiet": recipe.quiet,
+    "shebang": recipe.shebang,
+    "parameters": parameters,
+    "dependencies": dependencies,
+    "body": body,
+  })
+}
+
+fn item(item: &Item) -> Value {
+  match item {
+    Item::Alias(alias) => json!({
+      "kind": "alias",
+      "name": name(alias.name),
+      "target": name(alias.target),
+      "attributes": alias.attributes,
+    }),
+    Item::Assignment(assignment) => json!({
+      "kind": "assignment",
+      "name": name(assignment.name),
+      "export": assignment.export,
+      "lazy": assignment.lazy,
+      "value": expression(&assignment.value),
+    }),
+    Item::Comment(comment) => json!({
+      "kind": "comment",
+      "text": comment,
+    }),
+    Item::Function(function) => json!({
+      "kind": "function",
+      "name": name(function.name),
+      "parameters": function.parameters.iter().copied().map(name).collect::<Vec<Value>>(),
+      "body": expression(&function.body),
+    }),
+    Item::Group(group) => json!({
+      "kind": "group",
+      "name": group.name.cooked,
+      "attributes": group.attributes,
+      "span": span(group.keyword.token()),
+    }),
+    Item::Recipe(recipe) => self::recipe(recipe),
+    Item::Set(set) => json!({
+      "kind": "set",
+      "name": name(set.name),
+      "value": set.value.to_string(),
+    }),
+  }
+}
+
+/// The syntax tree of `ast`, as printed by `--dump-ast`, for tools which need
+/// to analyze justfiles. The tree is unresolved, so names are as written,
+/// and not checked to refer to anything.
+pub(crate) fn syntax_tree(ast: &Ast) -> Value {
+  json!({
+    "items": ast.items.iter().map(item).collect::<Vec<Value>>(),
+  })
+}
@@ -0,0 +1,140 @@
+use super::*;
+
+/// A starter justfile for a kind of project, written by `--init TEMPLATE`
+pub(crate) struct Template {
+  pub(crate) description: &'static str,
+  pub(crate) justfile: &'static str,
+  pub(crate) name: &'static str,
+}
+
+impl Template {
+  pub(crate) const ALL: &'static [Self] = &[
+    Self {
+      description: "Node.js project using npm",
+      justfile: "# List available recipes
+default:
+    @just --list
+
+# Install dependencies
+install:
+    npm install
+
+# Build the project
+build:
+    npm run build
+
+# Run the tests
+test *args:
+    npm test -- {{ args }}
+
+# Check for lint errors
+lint:
+    npm run lint
+
+# Start the development server
+dev:
+    npm run dev
+
+# Run the checks which CI runs
+ci: lint test
+",
+      name: "node",
+    },
+    Self {
+      description: "Python project using pytest, ruff, and mypy",
+      justfile: "python := 'python3'
+
+# List available recipes
+default:
+    @just --list
+
+# Install the project and its development dependencies
+install:
+    {{ python }} -m pip install --editable '.[dev]'
+
+# Run the tests
+test *args:
+    {{ python }} -m pytest {{ args }}
+
+# Check for lint errors and formatting
+lint:
+    {{ python }} -m ruff check .
+    {{ python }} -m ruff format --check .
+
+# Format the code
+fmt:
+    {{ python }} -m ruff format .
+
+# Check types
+typecheck:
+    {{ python }} -m mypy .
+
+# Run the checks which CI runs
+ci: lint typecheck test
+",
+      name: "python",
+    },
+    Self {
+      description: "Rust project using cargo",
+      justfile: "# List available recipes
+default:
+    @just --list
+
+# Build the project
+build *args:
+    cargo build {{ args }}
+
+# Run the project
+run *args:
+    cargo run -- {{ args }}
+
+# Run the tests
+test *args:
+    cargo test {{ args }}
+
+# Check for lint errors and formatting
+lint:
+    cargo clippy --all-targets -- --deny warnings
+    cargo fmt --all -- --check
+
+# Format the code
+fmt:
+    cargo fmt --all
+
+# Run the checks which CI runs
+ci: lint test
+",
+      name: "rust",
+    },
+  ];
+
+  pub(crate) fn names() -> Vec<&'static str> {
+    Self::ALL.iter().map(|template| template.name).collect()
+  }
+
+  pub(crate) fn get(name: &str) -> Option<&'static Self> {
+    Self::ALL
+      .iter()
+      .find(|template| template.name.eq_ignore_ascii_case(name))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn templates_compile() {
+    for template in Template::ALL {
+      testing::compile(template.justfile);
+    }
+  }
+
+  #[test]
+  fn templates_are_formatted() {
+    for template in Template::ALL {
+      let (ast, _) = Compiler::compile(template.justfile).unwrap();
+      assert_eq!(ast.to_string(), template.justfile, "{}", template.name);
+    }
+  }
+}
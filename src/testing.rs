@@ -3,7 +3,14 @@ use {super::*, crate::compiler::Compiler, pretty_assertions::assert_eq};
 pub(crate) fn compile(text: &str) -> Justfile {
   match Compiler::compile(text) {
     Ok((_, justfile)) => justfile,
-    Err(error) => panic!("Expected successful compilation but got error:\n {error}"),
+    Err(errors) => panic!(
+      "Expected successful compilation but got errors:\n {}",
+      errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join("\n ")
+    ),
   }
 }
 
@@ -24,6 +31,7 @@ pub(crate) fn search(config: &Config) -> Search {
 
   Search {
     justfile,
+    stdin: false,
     working_directory,
   }
 }
@@ -60,7 +68,8 @@ pub(crate) fn analysis_error(
   length: usize,
   kind: CompileErrorKind,
 ) {
-  let tokens = Lexer::lex(src).expect("Lexing failed in parse test...");
+  let tokens =
+    Lexer::lex(src, &InterpolationDelimiters::default()).expect("Lexing failed in parse test...");
 
   let ast = Parser::parse(&tokens).expect("Parsing failed in analysis test...");
 
@@ -102,9 +111,11 @@ macro_rules! run_error {
           .1
           .run(
             &config,
+            &$crate::loader::Loader::new(false, false),
             &search,
             &overrides,
             &arguments,
+            &RefCell::default(),
           ).expect_err("Expected runtime error") {
             $error => $check
             other => {
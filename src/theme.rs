@@ -0,0 +1,166 @@
+use {
+  super::*,
+  ansi_term::{Color::*, Style},
+};
+
+/// The styles used for each kind of colored output, which may be customized
+/// with the `JUST_COLORS` environment variable, for example
+/// `JUST_COLORS='error=red,bold:command=blue:doc=dim'`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct Theme {
+  pub(crate) annotation: Style,
+  pub(crate) banner: Style,
+  pub(crate) command: Style,
+  pub(crate) diff_added: Style,
+  pub(crate) diff_deleted: Style,
+  pub(crate) doc: Style,
+  pub(crate) error: Style,
+  pub(crate) keyword: Style,
+  pub(crate) message: Style,
+  pub(crate) parameter: Style,
+  pub(crate) string: Style,
+  pub(crate) warning: Style,
+}
+
+impl Theme {
+  pub(crate) const VARIABLE: &'static str = "JUST_COLORS";
+
+  /// The default theme, customized by `JUST_COLORS`, if set
+  pub(crate) fn from_env() -> Result<Self, String> {
+    match env::var(Self::VARIABLE) {
+      Ok(colors) => colors.parse(),
+      Err(env::VarError::NotPresent) => Ok(Self::default()),
+      Err(env::VarError::NotUnicode(_)) => Err("value is not valid unicode".into()),
+    }
+  }
+
+  fn style(value: &str) -> Result<Style, String> {
+    let mut style = Style::new();
+
+    for attribute in value.split(',').filter(|attribute| !attribute.is_empty()) {
+      match attribute {
+        "bold" => style = style.bold(),
+        "dim" => style = style.dimmed(),
+        "italic" => style = style.italic(),
+        "reverse" => style = style.reverse(),
+        "strikethrough" => style = style.strikethrough(),
+        "underline" => style = style.underline(),
+        "black" => style = style.fg(Black),
+        "blue" => style = style.fg(Blue),
+        "cyan" => style = style.fg(Cyan),
+        "green" => style = style.fg(Green),
+        "purple" => style = style.fg(Purple),
+        "red" => style = style.fg(Red),
+        "white" => style = style.fg(White),
+        "yellow" => style = style.fg(Yellow),
+        _ => match attribute.parse::<u8>() {
+          Ok(color) => style = style.fg(Fixed(color)),
+          Err(_) => return Err(format!("unknown color or attribute `{attribute}`")),
+        },
+      }
+    }
+
+    Ok(style)
+  }
+}
+
+impl FromStr for Theme {
+  type Err = String;
+
+  fn from_str(colors: &str) -> Result<Self, Self::Err> {
+    let mut theme = Self::default();
+
+    for entry in colors.split(':').filter(|entry| !entry.is_empty()) {
+      let (name, value) = entry
+        .split_once('=')
+        .ok_or_else(|| format!("expected `NAME=STYLE`, but found `{entry}`"))?;
+
+      let style = Self::style(value).map_err(|message| format!("{message} for `{name}`"))?;
+
+      match name {
+        "annotation" => theme.annotation = style,
+        "banner" => theme.banner = style,
+        "command" => theme.command = style,
+        "diff-added" => theme.diff_added = style,
+        "diff-deleted" => theme.diff_deleted = style,
+        "doc" => theme.doc = style,
+        "error" => theme.error = style,
+        "keyword" => theme.keyword = style,
+        "message" => theme.message = style,
+        "parameter" => theme.parameter = style,
+        "string" => theme.string = style,
+        "warning" => theme.warning = style,
+        _ => return Err(format!("unknown name `{name}`")),
+      }
+    }
+
+    Ok(theme)
+  }
+}
+
+impl Default for Theme {
+  fn default() -> Self {
+    Self {
+      annotation: Style::new().fg(Purple),
+      banner: Style::new().fg(Cyan).bold(),
+      command: Style::new().bold(),
+      diff_added: Style::new().fg(Green),
+      diff_deleted: Style::new().fg(Red),
+      doc: Style::new().fg(Blue),
+      error: Style::new().fg(Red).bold(),
+      keyword: Style::new().fg(Yellow),
+      message: Style::new().bold(),
+      parameter: Style::new().fg(Cyan),
+      string: Style::new().fg(Green),
+      warning: Style::new().fg(Yellow).bold(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse() {
+    assert_eq!(
+      "error=red,bold:command=blue:doc=dim:string=208:"
+        .parse::<Theme>()
+        .unwrap(),
+      Theme {
+        command: Style::new().fg(Blue),
+        doc: Style::new().dimmed(),
+        error: Style::new().fg(Red).bold(),
+        string: Style::new().fg(Fixed(208)),
+        ..Theme::default()
+      },
+    );
+  }
+
+  #[test]
+  fn empty_style_is_plain() {
+    assert_eq!(
+      "doc=".parse::<Theme>().unwrap(),
+      Theme {
+        doc: Style::new(),
+        ..Theme::default()
+      },
+    );
+  }
+
+  #[test]
+  fn errors() {
+    assert_eq!(
+      "error".parse::<Theme>().unwrap_err(),
+      "expected `NAME=STYLE`, but found `error`",
+    );
+    assert_eq!(
+      "error=reed".parse::<Theme>().unwrap_err(),
+      "unknown color or attribute `reed` for `error`",
+    );
+    assert_eq!(
+      "errors=red".parse::<Theme>().unwrap_err(),
+      "unknown name `errors`",
+    );
+  }
+}
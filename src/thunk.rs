@@ -20,6 +20,12 @@ pub(crate) enum Thunk<'src> {
     function: fn(&FunctionContext, &str, Option<&str>) -> Result<String, String>,
     args: (Box<Expression<'src>>, Box<Option<Expression<'src>>>),
   },
+  UnaryPlus {
+    name: Name<'src>,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    function: fn(&FunctionContext, &str, &[String]) -> Result<String, String>,
+    args: (Box<Expression<'src>>, Vec<Expression<'src>>),
+  },
   Binary {
     name: Name<'src>,
     #[derivative(Debug = "ignore", PartialEq = "ignore")]
@@ -38,17 +44,45 @@ pub(crate) enum Thunk<'src> {
     function: fn(&FunctionContext, &str, &str, &str) -> Result<String, String>,
     args: [Box<Expression<'src>>; 3],
   },
+  /// A call to a function defined in the justfile with `fn`, which is checked
+  /// by the analyzer, since it may be defined after it is called
+  User {
+    name: Name<'src>,
+    args: Vec<Expression<'src>>,
+  },
 }
 
 impl<'src> Thunk<'src> {
-  fn name(&self) -> &Name<'src> {
+  pub(crate) fn name(&self) -> &Name<'src> {
     match self {
       Self::Nullary { name, .. }
       | Self::Unary { name, .. }
       | Self::UnaryOpt { name, .. }
+      | Self::UnaryPlus { name, .. }
       | Self::Binary { name, .. }
       | Self::BinaryPlus { name, .. }
-      | Self::Ternary { name, .. } => name,
+      | Self::Ternary { name, .. }
+      | Self::User { name, .. } => name,
+    }
+  }
+
+  /// The arguments of this call, from left to right
+  pub(crate) fn arguments(&self) -> Vec<&Expression<'src>> {
+    match self {
+      Self::Nullary { .. } => Vec::new(),
+      Self::Unary { arg, .. } => vec![arg],
+      Self::UnaryOpt { args: (a, b), .. } => iter::once(a.as_ref()).chain(b.as_ref()).collect(),
+      Self::UnaryPlus {
+        args: (first, rest),
+        ..
+      } => iter::once(first.as_ref()).chain(rest).collect(),
+      Self::Binary { args, .. } => args.iter().map(Box::as_ref).collect(),
+      Self::BinaryPlus {
+        args: (first, rest),
+        ..
+      } => first.iter().map(Box::as_ref).chain(rest).collect(),
+      Self::Ternary { args, .. } => args.iter().map(Box::as_ref).collect(),
+      Self::User { args, .. } => args.iter().collect(),
     }
   }
 
@@ -56,65 +90,76 @@ impl<'src> Thunk<'src> {
     name: Name<'src>,
     mut arguments: Vec<Expression<'src>>,
   ) -> CompileResult<'src, Thunk<'src>> {
-    function::get(name.lexeme()).map_or(
-      Err(name.error(CompileErrorKind::UnknownFunction {
-        function: name.lexeme(),
-      })),
-      |function| match (function, arguments.len()) {
-        (Function::Nullary(function), 0) => Ok(Thunk::Nullary { function, name }),
-        (Function::Unary(function), 1) => Ok(Thunk::Unary {
+    let Some(function) = function::get(name.lexeme()) else {
+      return Ok(Thunk::User {
+        name,
+        args: arguments,
+      });
+    };
+
+    match (function, arguments.len()) {
+      (Function::Nullary(function), 0) => Ok(Thunk::Nullary { function, name }),
+      (Function::Unary(function), 1) => Ok(Thunk::Unary {
+        function,
+        arg: Box::new(arguments.pop().unwrap()),
+        name,
+      }),
+      (Function::UnaryOpt(function), 1..=2) => {
+        let a = Box::new(arguments.remove(0));
+        let b = match arguments.pop() {
+          Some(value) => Box::new(Some(value)),
+          None => Box::new(None),
+        };
+        Ok(Thunk::UnaryOpt {
           function,
-          arg: Box::new(arguments.pop().unwrap()),
+          args: (a, b),
           name,
-        }),
-        (Function::UnaryOpt(function), 1..=2) => {
-          let a = Box::new(arguments.remove(0));
-          let b = match arguments.pop() {
-            Some(value) => Box::new(Some(value)),
-            None => Box::new(None),
-          };
-          Ok(Thunk::UnaryOpt {
-            function,
-            args: (a, b),
-            name,
-          })
-        }
-        (Function::Binary(function), 2) => {
-          let b = Box::new(arguments.pop().unwrap());
-          let a = Box::new(arguments.pop().unwrap());
-          Ok(Thunk::Binary {
-            function,
-            args: [a, b],
-            name,
-          })
-        }
-        (Function::BinaryPlus(function), 2..=usize::MAX) => {
-          let rest = arguments.drain(2..).collect();
-          let b = Box::new(arguments.pop().unwrap());
-          let a = Box::new(arguments.pop().unwrap());
-          Ok(Thunk::BinaryPlus {
-            function,
-            args: ([a, b], rest),
-            name,
-          })
-        }
-        (Function::Ternary(function), 3) => {
-          let c = Box::new(arguments.pop().unwrap());
-          let b = Box::new(arguments.pop().unwrap());
-          let a = Box::new(arguments.pop().unwrap());
-          Ok(Thunk::Ternary {
-            function,
-            args: [a, b, c],
-            name,
-          })
-        }
-        (function, _) => Err(name.error(CompileErrorKind::FunctionArgumentCountMismatch {
-          function: name.lexeme(),
-          found: arguments.len(),
-          expected: function.argc(),
-        })),
-      },
-    )
+        })
+      }
+      (Function::UnaryPlus(function), 1..=usize::MAX) => {
+        let rest = arguments.drain(1..).collect();
+        let a = Box::new(arguments.pop().unwrap());
+        Ok(Thunk::UnaryPlus {
+          function,
+          args: (a, rest),
+          name,
+        })
+      }
+      (Function::Binary(function), 2) => {
+        let b = Box::new(arguments.pop().unwrap());
+        let a = Box::new(arguments.pop().unwrap());
+        Ok(Thunk::Binary {
+          function,
+          args: [a, b],
+          name,
+        })
+      }
+      (Function::BinaryPlus(function), 2..=usize::MAX) => {
+        let rest = arguments.drain(2..).collect();
+        let b = Box::new(arguments.pop().unwrap());
+        let a = Box::new(arguments.pop().unwrap());
+        Ok(Thunk::BinaryPlus {
+          function,
+          args: ([a, b], rest),
+          name,
+        })
+      }
+      (Function::Ternary(function), 3) => {
+        let c = Box::new(arguments.pop().unwrap());
+        let b = Box::new(arguments.pop().unwrap());
+        let a = Box::new(arguments.pop().unwrap());
+        Ok(Thunk::Ternary {
+          function,
+          args: [a, b, c],
+          name,
+        })
+      }
+      (function, _) => Err(name.error(CompileErrorKind::FunctionArgumentCountMismatch {
+        function: name.lexeme(),
+        found: arguments.len(),
+        expected: function.argc(),
+      })),
+    }
   }
 }
 
@@ -133,6 +178,17 @@ impl Display for Thunk<'_> {
           write!(f, "{}({a})", name.lexeme())
         }
       }
+      UnaryPlus {
+        name,
+        args: (a, rest),
+        ..
+      } => {
+        write!(f, "{}({a}", name.lexeme())?;
+        for arg in rest {
+          write!(f, ", {arg}")?;
+        }
+        write!(f, ")")
+      }
       Binary {
         name, args: [a, b], ..
       } => write!(f, "{}({a}, {b})", name.lexeme()),
@@ -152,6 +208,16 @@ impl Display for Thunk<'_> {
         args: [a, b, c],
         ..
       } => write!(f, "{}({a}, {b}, {c})", name.lexeme()),
+      User { name, args } => {
+        write!(f, "{}(", name.lexeme())?;
+        for (i, arg) in args.iter().enumerate() {
+          if i > 0 {
+            write!(f, ", ")?;
+          }
+          write!(f, "{arg}")?;
+        }
+        write!(f, ")")
+      }
     }
   }
 }
@@ -175,6 +241,11 @@ impl<'src> Serialize for Thunk<'src> {
           seq.serialize_element(b)?;
         }
       }
+      Self::UnaryPlus { args, .. } => {
+        for arg in iter::once(args.0.as_ref()).chain(&args.1) {
+          seq.serialize_element(arg)?;
+        }
+      }
       Self::Binary { args, .. } => {
         for arg in args {
           seq.serialize_element(arg)?;
@@ -190,6 +261,11 @@ impl<'src> Serialize for Thunk<'src> {
           seq.serialize_element(arg)?;
         }
       }
+      Self::User { args, .. } => {
+        for arg in args {
+          seq.serialize_element(arg)?;
+        }
+      }
     }
     seq.end()
   }
@@ -0,0 +1,47 @@
+use {super::*, chrono::format::StrftimeItems};
+
+/// Wall-clock timestamps printed before echoed recipe lines and error
+/// messages with `--timestamps`, so that logs show where the time went
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Timestamps {
+  format: String,
+}
+
+impl Timestamps {
+  pub(crate) const DEFAULT_FORMAT: &'static str = "%H:%M:%S";
+
+  /// Timestamps formatted with the strftime-style `format`, which is checked
+  /// here, since formatting with an invalid format panics
+  pub(crate) fn new(format: &str) -> Result<Self, String> {
+    if StrftimeItems::new(format).any(|item| item == chrono::format::Item::Error) {
+      return Err(format!("Invalid timestamp format `{format}`"));
+    }
+
+    Ok(Self {
+      format: format.into(),
+    })
+  }
+
+  /// `text`, prefixed with the current time
+  pub(crate) fn prefix(&self, text: &str) -> String {
+    format!("[{}] {text}", chrono::Local::now().format(&self.format))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn invalid_format() {
+    assert_eq!(
+      Timestamps::new("%Q").unwrap_err(),
+      "Invalid timestamp format `%Q`",
+    );
+  }
+
+  #[test]
+  fn prefix() {
+    assert_eq!(Timestamps::new("T").unwrap().prefix("foo"), "[T] foo");
+  }
+}
@@ -49,6 +49,18 @@ macro_rules! tree {
     $crate::tree::Tree::atom("&&")
   };
 
+  {
+    ||
+  } => {
+    $crate::tree::Tree::atom("||")
+  };
+
+  {
+    !
+  } => {
+    $crate::tree::Tree::atom("!")
+  };
+
   {
     ==
   } => {
@@ -0,0 +1,150 @@
+use {super::*, sha2::Digest};
+
+/// The justfiles which the user has trusted to run, recorded in a trust store
+/// as lines of a hash of the justfile's contents followed by its canonical
+/// path, so that a justfile must be trusted again when it changes.
+///
+/// Trust is only required if `require-trust = true` is set in the global
+/// configuration file, `just/config.toml` in `$XDG_CONFIG_HOME` or
+/// `~/.config`. The configuration can't be given by a justfile or the
+/// `.just.toml` next to it, since they are exactly what is not yet trusted.
+pub(crate) struct Trust;
+
+impl Trust {
+  const CONFIG: &'static str = "config.toml";
+  const STORE: &'static str = "trusted";
+
+  /// Check that the justfile found by `search` with contents `src` is
+  /// trusted, if trust is required. Untrusted justfiles are trusted if the
+  /// user confirms it when prompted. Justfiles piped in with `--justfile -`
+  /// are given explicitly, and have no path to trust, so they are exempt.
+  pub(crate) fn check(config: &Config, search: &Search, src: &str) -> RunResult<'static, ()> {
+    if search.stdin || !Self::required()? {
+      return Ok(());
+    }
+
+    let path = Self::canonicalize(&search.justfile)?;
+
+    if Self::load()?.contains(&Self::entry(&path, src)) {
+      return Ok(());
+    }
+
+    let prompt = format!(
+      "Justfile `{}` has not been trusted. Trust it and continue?",
+      path.display()
+    );
+
+    if !config
+      .confirm(&prompt)
+      .map_err(|io_error| Error::GetConfirmation { io_error })?
+    {
+      return Err(Error::Untrusted { justfile: path });
+    }
+
+    Self::trust(&path, src)
+  }
+
+  /// Trust the justfile at `path` with contents `src`, replacing any entry
+  /// for an earlier version of it
+  pub(crate) fn trust(path: &Path, src: &str) -> RunResult<'static, ()> {
+    let path = Self::canonicalize(path)?;
+
+    let entry = Self::entry(&path, src);
+
+    let suffix = format!(" {}", path.display());
+
+    let mut entries = Self::load()?
+      .into_iter()
+      .filter(|existing| !existing.ends_with(&suffix))
+      .collect::<Vec<String>>();
+
+    entries.push(entry);
+
+    let store = Self::store()?;
+
+    let io_error = |io_error| Error::TrustStore {
+      path: store.clone(),
+      io_error,
+    };
+
+    fs::create_dir_all(store.parent().unwrap()).map_err(io_error)?;
+
+    fs::write(&store, entries.join("\n") + "\n").map_err(io_error)
+  }
+
+  fn canonicalize(path: &Path) -> RunResult<'static, PathBuf> {
+    path.canonicalize().map_err(|io_error| Error::TrustStore {
+      path: path.to_owned(),
+      io_error,
+    })
+  }
+
+  fn entry(path: &Path, src: &str) -> String {
+    format!("{:x} {}", sha2::Sha256::digest(src), path.display())
+  }
+
+  fn load() -> RunResult<'static, Vec<String>> {
+    let store = Self::store()?;
+
+    match fs::read_to_string(&store) {
+      Ok(contents) => Ok(contents.lines().map(str::to_owned).collect()),
+      Err(io_error) if io_error.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+      Err(io_error) => Err(Error::TrustStore {
+        path: store,
+        io_error,
+      }),
+    }
+  }
+
+  /// Whether `require-trust = true` is set in the global configuration file
+  fn required() -> RunResult<'static, bool> {
+    let Some(path) =
+      Self::directory("XDG_CONFIG_HOME", ".config").map(|dir| dir.join(Self::CONFIG))
+    else {
+      return Ok(false);
+    };
+
+    let contents = match fs::read_to_string(&path) {
+      Ok(contents) => contents,
+      Err(io_error) if io_error.kind() == io::ErrorKind::NotFound => return Ok(false),
+      Err(io_error) => {
+        return Err(Error::GlobalConfig {
+          path,
+          message: io_error.to_string(),
+        })
+      }
+    };
+
+    let table = contents
+      .parse::<toml::Table>()
+      .map_err(|err| Error::GlobalConfig {
+        path: path.clone(),
+        message: err.message().to_owned(),
+      })?;
+
+    match table.get("require-trust") {
+      None => Ok(false),
+      Some(toml::Value::Boolean(required)) => Ok(*required),
+      Some(_) => Err(Error::GlobalConfig {
+        path,
+        message: "`require-trust` must be a boolean".into(),
+      }),
+    }
+  }
+
+  fn store() -> RunResult<'static, PathBuf> {
+    Self::directory("XDG_DATA_HOME", ".local/share")
+      .map(|dir| dir.join(Self::STORE))
+      .ok_or(Error::TrustDirectory)
+  }
+
+  /// The `just` directory in the directory named by the environment variable
+  /// `xdg`, or else in `fallback` in the home directory
+  fn directory(xdg: &str, fallback: &str) -> Option<PathBuf> {
+    env::var_os(xdg)
+      .filter(|directory| !directory.is_empty())
+      .map(PathBuf::from)
+      .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(fallback)))
+      .map(|directory| directory.join("just"))
+  }
+}
@@ -4,14 +4,21 @@ use super::*;
 pub(crate) struct UnresolvedDependency<'src> {
   pub(crate) recipe: Name<'src>,
   pub(crate) arguments: Vec<Expression<'src>>,
+  pub(crate) capture: Option<Name<'src>>,
 }
 
 impl<'src> Display for UnresolvedDependency<'src> {
   fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-    if self.arguments.is_empty() {
+    if self.arguments.is_empty() && self.capture.is_none() {
       write!(f, "{}", self.recipe)
     } else {
-      write!(f, "({}", self.recipe)?;
+      write!(f, "(")?;
+
+      if let Some(capture) = self.capture {
+        write!(f, "{capture} := ")?;
+      }
+
+      write!(f, "{}", self.recipe)?;
 
       for argument in &self.arguments {
         write!(f, " {argument}")?;
@@ -41,12 +41,14 @@ impl<'src> UnresolvedRecipe<'src> {
       .map(|(unresolved, resolved)| Dependency {
         recipe: resolved,
         arguments: unresolved.arguments,
+        capture: unresolved.capture,
       })
       .collect();
 
     Ok(Recipe {
       body: self.body,
       doc: self.doc,
+      interpolation_delimiters: self.interpolation_delimiters,
       name: self.name,
       parameters: self.parameters,
       private: self.private,
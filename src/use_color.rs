@@ -1,4 +1,7 @@
-#[derive(Copy, Clone, Debug, PartialEq)]
+use super::*;
+
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
 pub(crate) enum UseColor {
   Auto,
   Always,
@@ -0,0 +1,37 @@
+use super::*;
+
+/// A user-defined function, e.g. `fn image(tag) := "registry/app:" + tag`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct UserFunction<'src> {
+  /// Expression evaluated when the function is called
+  pub(crate) body: Expression<'src>,
+  /// Function name
+  pub(crate) name: Name<'src>,
+  /// Parameter names, bound to the arguments of each call
+  pub(crate) parameters: Vec<Name<'src>>,
+}
+
+impl<'src> UserFunction<'src> {
+  pub(crate) fn line_number(&self) -> usize {
+    self.name.line
+  }
+}
+
+impl<'src> Keyed<'src> for UserFunction<'src> {
+  fn key(&self) -> &'src str {
+    self.name.lexeme()
+  }
+}
+
+impl<'src> Display for UserFunction<'src> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "fn {}(", self.name)?;
+    for (i, parameter) in self.parameters.iter().enumerate() {
+      if i > 0 {
+        write!(f, ", ")?;
+      }
+      write!(f, "{parameter}")?;
+    }
+    write!(f, ") := {}", self.body)
+  }
+}
@@ -28,6 +28,14 @@ impl<'expression, 'src> Iterator for Variables<'expression, 'src> {
               self.stack.push(b);
             }
           }
+          Thunk::UnaryPlus {
+            args: (a, rest), ..
+          } => {
+            let first: &[&Expression] = &[a];
+            for arg in first.iter().copied().chain(rest).rev() {
+              self.stack.push(arg);
+            }
+          }
           Thunk::Binary { args, .. } => {
             for arg in args.iter().rev() {
               self.stack.push(arg);
@@ -47,18 +55,22 @@ impl<'expression, 'src> Iterator for Variables<'expression, 'src> {
               self.stack.push(arg);
             }
           }
+          Thunk::User { args, .. } => {
+            for arg in args.iter().rev() {
+              self.stack.push(arg);
+            }
+          }
         },
         Expression::Conditional {
-          lhs,
-          rhs,
+          condition,
           then,
           otherwise,
-          ..
         } => {
           self.stack.push(otherwise);
           self.stack.push(then);
-          self.stack.push(rhs);
-          self.stack.push(lhs);
+          for expression in condition.expressions().into_iter().rev() {
+            self.stack.push(expression);
+          }
         }
         Expression::Variable { name, .. } => return Some(name.token()),
         Expression::Concatenation { lhs, rhs } => {
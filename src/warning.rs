@@ -1,22 +1,73 @@
 use super::*;
 
 #[derive(Clone, Debug, PartialEq)]
-pub(crate) enum Warning {}
+pub(crate) enum Warning<'src> {
+  /// A variable or parameter which is explicitly exported, but whose name
+  /// isn't a valid environment variable name, which will become an error
+  InvalidExportName {
+    name: Name<'src>,
+  },
+  RemoteCached {
+    url: String,
+    path: PathBuf,
+  },
+  /// A variable or parameter which `set export` doesn't export, because its
+  /// name isn't a valid environment variable name
+  UnexportedName {
+    name: Name<'src>,
+  },
+  /// An entry which couldn't be appended to the command log
+  WriteCommandLog {
+    path: PathBuf,
+    message: String,
+  },
+}
 
-impl Warning {
-  #[allow(clippy::unused_self)]
-  fn context(&self) -> Option<&Token> {
-    None
+impl<'src> Warning<'src> {
+  fn context(&self) -> Option<Token<'src>> {
+    match self {
+      Self::InvalidExportName { name } | Self::UnexportedName { name } => Some(name.token()),
+      Self::RemoteCached { .. } | Self::WriteCommandLog { .. } => None,
+    }
   }
 }
 
-impl ColorDisplay for Warning {
+impl ColorDisplay for Warning<'_> {
   fn fmt(&self, f: &mut Formatter, color: Color) -> fmt::Result {
     let warning = color.warning();
     let message = color.message();
 
     write!(f, "{} {}", warning.paint("warning:"), message.prefix())?;
 
+    match self {
+      Self::InvalidExportName { name } => {
+        write!(
+          f,
+          "Exporting `{name}` is deprecated and will be an error in a future version, since \
+           it is not a valid environment variable name, which may only contain letters, \
+           digits, and underscores, and may not start with a digit"
+        )?;
+      }
+      Self::RemoteCached { url, path } => {
+        write!(
+          f,
+          "Failed to fetch `{url}`, using the copy cached at `{}`, which may be out of date",
+          path.display()
+        )?;
+      }
+      Self::UnexportedName { name } => {
+        write!(
+          f,
+          "`{name}` is not exported by `set export`, since it is not a valid environment \
+           variable name"
+        )?;
+      }
+      Self::WriteCommandLog { path, message } => {
+        let path = path.display();
+        write!(f, "Failed to write command log to `{path}`: {message}")?;
+      }
+    }
+
     write!(f, "{}", message.suffix())?;
 
     if let Some(token) = self.context() {
@@ -28,7 +79,7 @@ impl ColorDisplay for Warning {
   }
 }
 
-impl Serialize for Warning {
+impl Serialize for Warning<'_> {
   fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
   where
     S: Serializer,
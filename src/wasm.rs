@@ -0,0 +1,94 @@
+//! A JavaScript API for checking and formatting justfiles, so that
+//! playgrounds and web-based editors can do so client-side.
+//!
+//! Enabled with the `wasm` feature, and built with:
+//!
+//! ```sh
+//! cargo rustc --lib --release --features wasm --crate-type cdylib \
+//!   --target wasm32-unknown-unknown
+//! ```
+//!
+//! The resulting module can then be given JavaScript bindings with
+//! `wasm-bindgen`. Only compilation is supported, since recipes and backticks
+//! can't be run from WebAssembly.
+
+use {
+  crate::{
+    color::Color, color_display::ColorDisplay, compiler::Compiler, error::Error,
+    line_endings::keep_line_endings,
+  },
+  wasm_bindgen::prelude::wasm_bindgen,
+};
+
+/// Check `src`, returning `undefined` if it is a valid justfile, or the error
+/// messages, as `just` would print them, if it is not
+#[wasm_bindgen]
+#[must_use]
+pub fn check(src: &str) -> Option<String> {
+  Compiler::compile(src).err().map(|errors| {
+    Error::from(errors)
+      .color_display(Color::never())
+      .to_string()
+  })
+}
+
+/// Format `src`, as `just --fmt` would, throwing the error messages if it is
+/// not a valid justfile
+#[wasm_bindgen]
+#[allow(clippy::missing_errors_doc)]
+pub fn format(src: &str) -> Result<String, String> {
+  let (ast, justfile) = Compiler::compile(src).map_err(|errors| {
+    Error::from(errors)
+      .color_display(Color::never())
+      .to_string()
+  })?;
+
+  Ok(keep_line_endings(src, ast.format(&justfile.settings)))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn check_valid() {
+    assert_eq!(check("foo:\n  echo bar\n"), None);
+  }
+
+  #[test]
+  fn check_invalid() {
+    assert_eq!(
+      check("foo:\n  echo {{bar}}\n").unwrap(),
+      "error: Variable `bar` not defined\n  |\n2 |   echo {{bar}}\n  |          ^^^",
+    );
+  }
+
+  #[test]
+  fn format_justfile() {
+    assert_eq!(
+      format("x:=\"a\"\nfoo   :  \n  echo {{x}}\n").unwrap(),
+      "x := \"a\"\n\nfoo:\n    echo {{ x }}\n",
+    );
+  }
+
+  #[test]
+  fn format_keeps_crlf() {
+    assert_eq!(
+      format("foo:\r\n  echo\r\n").unwrap(),
+      "foo:\r\n    echo\r\n"
+    );
+  }
+
+  #[test]
+  fn format_uses_settings() {
+    assert_eq!(
+      format("set fmt-indent := '  '\nfoo:\n    echo foo\n").unwrap(),
+      "set fmt-indent := \"  \"\n\nfoo:\n  echo foo\n",
+    );
+  }
+
+  #[test]
+  fn format_invalid() {
+    assert!(format("foo: bar").is_err());
+  }
+}
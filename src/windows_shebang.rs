@@ -0,0 +1,41 @@
+use super::*;
+
+/// How shebang interpreter paths are translated on Windows, selected with
+/// `set windows-shebang := "…"`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum WindowsShebang {
+  /// Translate interpreter paths containing `/` with `cygpath`
+  #[default]
+  Cygpath,
+  /// Use interpreter paths as-is, for native Windows interpreters
+  Native,
+}
+
+impl WindowsShebang {
+  pub(crate) const VALUES: &'static [&'static str] = &["cygpath", "native"];
+}
+
+impl FromStr for WindowsShebang {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "cygpath" => Ok(Self::Cygpath),
+      "native" => Ok(Self::Native),
+      _ => Err(format!(
+        "Invalid windows shebang mode `{s}`, expected {}",
+        List::or_ticked(Self::VALUES)
+      )),
+    }
+  }
+}
+
+impl Display for WindowsShebang {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.write_str(match self {
+      Self::Cygpath => "cygpath",
+      Self::Native => "native",
+    })
+  }
+}
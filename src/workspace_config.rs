@@ -0,0 +1,139 @@
+use super::*;
+
+/// Defaults for command line options, read from a `.just.toml` file in the
+/// same directory as the justfile. Options given on the command line take
+/// precedence over these.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub(crate) struct WorkspaceConfig {
+  pub(crate) color: Option<UseColor>,
+  pub(crate) dotenv_filename: Option<String>,
+  pub(crate) list_heading: Option<String>,
+  pub(crate) list_prefix: Option<String>,
+  pub(crate) shell: Option<String>,
+  pub(crate) shell_args: Option<Vec<String>>,
+  pub(crate) unsorted: Option<bool>,
+  pub(crate) unstable: Option<bool>,
+}
+
+impl WorkspaceConfig {
+  pub(crate) const FILENAME: &'static str = ".just.toml";
+
+  /// Load the workspace configuration file next to the justfile found by
+  /// `search`, returning the default, empty configuration if there is none.
+  pub(crate) fn load(search: &Search) -> ConfigResult<Self> {
+    let path = search.justfile.with_file_name(Self::FILENAME);
+
+    match fs::read_to_string(&path) {
+      Ok(src) => Self::parse(&path, &src),
+      Err(io_error) if io_error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+      Err(source) => Err(ConfigError::WorkspaceConfigIo { path, source }),
+    }
+  }
+
+  fn parse(path: &Path, src: &str) -> ConfigResult<Self> {
+    toml::from_str(src).map_err(|error: toml::de::Error| ConfigError::WorkspaceConfigParse {
+      path: path.to_owned(),
+      line: error
+        .span()
+        .map(|span| src[..span.start].matches('\n').count()),
+      message: error.message().to_owned(),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(src: &str) -> ConfigResult<WorkspaceConfig> {
+    WorkspaceConfig::parse(Path::new(WorkspaceConfig::FILENAME), &unindent(src))
+  }
+
+  #[test]
+  fn empty() {
+    assert_eq!(parse("").unwrap(), WorkspaceConfig::default());
+  }
+
+  #[test]
+  fn all_keys() {
+    assert_eq!(
+      parse(
+        r#"
+          # comment
+          color = "never"
+          dotenv-filename = '.env.local'
+          list-heading = "Recipes:\n"
+          list-prefix = "  " # trailing comment
+          shell = "bash"
+          shell-args = [
+            "-eu",
+            "-c",
+          ]
+          unsorted = true
+          unstable = false
+        "#
+      )
+      .unwrap(),
+      WorkspaceConfig {
+        color: Some(UseColor::Never),
+        dotenv_filename: Some(".env.local".into()),
+        list_heading: Some("Recipes:\n".into()),
+        list_prefix: Some("  ".into()),
+        shell: Some("bash".into()),
+        shell_args: Some(vec!["-eu".into(), "-c".into()]),
+        unsorted: Some(true),
+        unstable: Some(false),
+      }
+    );
+  }
+
+  #[test]
+  fn empty_array() {
+    assert_eq!(
+      parse("shell-args = []").unwrap().shell_args,
+      Some(Vec::new())
+    );
+  }
+
+  #[test]
+  fn unknown_key() {
+    assert!(matches!(
+      parse("foo = true"),
+      Err(ConfigError::WorkspaceConfigParse { line: Some(0), message, .. })
+        if message.starts_with("unknown field `foo`"),
+    ));
+  }
+
+  #[test]
+  fn invalid_color() {
+    assert!(matches!(
+      parse("# comment\ncolor = \"red\""),
+      Err(ConfigError::WorkspaceConfigParse { line: Some(1), .. }),
+    ));
+  }
+
+  #[test]
+  fn wrong_type() {
+    assert!(matches!(
+      parse("unstable = \"yes\""),
+      Err(ConfigError::WorkspaceConfigParse { line: Some(0), .. }),
+    ));
+  }
+
+  #[test]
+  fn unterminated_string() {
+    assert!(matches!(
+      parse("shell = \"bash"),
+      Err(ConfigError::WorkspaceConfigParse { .. }),
+    ));
+  }
+
+  #[test]
+  fn tables_are_unknown_keys() {
+    assert!(matches!(
+      parse("[settings]"),
+      Err(ConfigError::WorkspaceConfigParse { .. }),
+    ));
+  }
+}
@@ -0,0 +1,340 @@
+use super::*;
+
+#[test]
+fn choices_accept_listed_value() {
+  Test::new()
+    .justfile(
+      "
+        [choices('env', 'staging', 'prod')]
+        deploy env:
+          @echo {{env}}
+      ",
+    )
+    .args(["deploy", "prod"])
+    .stdout("prod\n")
+    .run();
+}
+
+#[test]
+fn choices_reject_other_value() {
+  Test::new()
+    .justfile(
+      "
+        [choices('env', 'staging', 'prod')]
+        deploy env:
+          echo {{env}}
+      ",
+    )
+    .args(["deploy", "dev"])
+    .stderr(
+      "error: Recipe `deploy` parameter `env` got invalid value `dev`, expected `staging` or `prod`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn pattern_accepts_matching_value() {
+  Test::new()
+    .justfile(
+      r"
+        [pattern('version', '\d+\.\d+\.\d+')]
+        release version:
+          @echo {{version}}
+      ",
+    )
+    .args(["release", "1.2.3"])
+    .stdout("1.2.3\n")
+    .run();
+}
+
+#[test]
+fn pattern_must_match_whole_value() {
+  Test::new()
+    .justfile(
+      r"
+        [pattern('version', '\d+\.\d+\.\d+')]
+        release version:
+          echo {{version}}
+      ",
+    )
+    .args(["release", "v1.2.3"])
+    .stderr(
+      r"error: Recipe `release` parameter `version` got invalid value `v1.2.3`, which does not match pattern `\d+\.\d+\.\d+`
+",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn pattern_alternatives_are_anchored() {
+  Test::new()
+    .justfile(
+      "
+        [pattern('mode', 'debug|release')]
+        build mode:
+          echo {{mode}}
+      ",
+    )
+    .args(["build", "debugging"])
+    .stderr("error: Recipe `build` parameter `mode` got invalid value `debugging`, which does not match pattern `debug|release`\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn validation_happens_before_any_recipe_runs() {
+  Test::new()
+    .justfile(
+      "
+        setup:
+          @echo setup
+
+        [choices('env', 'staging', 'prod')]
+        deploy env: setup
+          @echo {{env}}
+      ",
+    )
+    .args(["setup", "deploy", "dev"])
+    .stderr(
+      "error: Recipe `deploy` parameter `env` got invalid value `dev`, expected `staging` or `prod`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn default_value_is_validated() {
+  Test::new()
+    .justfile(
+      "
+        [choices('env', 'staging', 'prod')]
+        deploy env=('d' + 'ev'):
+          echo {{env}}
+      ",
+    )
+    .arg("deploy")
+    .stderr(
+      "error: Recipe `deploy` parameter `env` got invalid value `dev`, expected `staging` or `prod`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn string_literal_default_not_in_choices() {
+  Test::new()
+    .justfile(
+      "
+        [choices('env', 'staging', 'prod')]
+        deploy env='dev':
+          echo {{env}}
+
+        build:
+      ",
+    )
+    .arg("build")
+    .stderr(
+      "
+        error: Recipe `deploy` parameter `env` has default `dev`, which is not accepted by its `choices` attribute
+          |
+        2 | deploy env='dev':
+          | ^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn string_literal_default_not_matching_pattern() {
+  Test::new()
+    .justfile(
+      "
+        [pattern('version', '[0-9]+')]
+        release version='latest':
+      ",
+    )
+    .arg("release")
+    .stderr(
+      "
+        error: Recipe `release` parameter `version` has default `latest`, which is not accepted by its `pattern` attribute
+          |
+        2 | release version='latest':
+          | ^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn string_literal_default_accepted() {
+  Test::new()
+    .justfile(
+      "
+        [choices('env', 'staging', 'prod')]
+        [pattern('env', '[a-z]+')]
+        deploy env='prod':
+          @echo {{env}}
+      ",
+    )
+    .arg("deploy")
+    .stdout("prod\n")
+    .run();
+}
+
+#[test]
+fn dependency_argument_is_validated() {
+  Test::new()
+    .justfile(
+      "
+        all: (deploy 'dev')
+          echo all
+
+        [choices('env', 'staging', 'prod')]
+        deploy env:
+          echo {{env}}
+      ",
+    )
+    .arg("all")
+    .stderr(
+      "error: Recipe `deploy` parameter `env` got invalid value `dev`, expected `staging` or `prod`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn each_variadic_argument_is_validated() {
+  Test::new()
+    .justfile(
+      "
+        [choices('targets', 'linux', 'macos', 'windows')]
+        build +targets:
+          @echo {{targets}}
+      ",
+    )
+    .args(["build", "linux", "freebsd"])
+    .stderr("error: Recipe `build` parameter `targets` got invalid value `freebsd`, expected `linux`, `macos`, or `windows`\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn empty_star_parameter_is_not_validated() {
+  Test::new()
+    .justfile(
+      "
+        [pattern('flags', '-.*')]
+        build *flags:
+          @echo built {{flags}}
+      ",
+    )
+    .arg("build")
+    .stdout("built\n")
+    .run();
+}
+
+#[test]
+fn multiple_parameters() {
+  Test::new()
+    .justfile(
+      r"
+        [choices('env', 'staging', 'prod')]
+        [pattern('replicas', '\d+')]
+        deploy env replicas:
+          @echo {{env}} {{replicas}}
+      ",
+    )
+    .args(["deploy", "staging", "3"])
+    .stdout("staging 3\n")
+    .run();
+}
+
+#[test]
+fn unknown_parameter() {
+  Test::new()
+    .justfile(
+      "
+        [choices('environment', 'staging', 'prod')]
+        deploy env:
+      ",
+    )
+    .arg("deploy")
+    .stderr(
+      "
+        error: Recipe `deploy` has `choices` attribute for unknown parameter `environment`
+          |
+        2 | deploy env:
+          | ^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn invalid_pattern() {
+  Test::new()
+    .justfile(
+      "
+        [pattern('env', '(')]
+        deploy env:
+      ",
+    )
+    .arg("deploy")
+    .stderr_regex(
+      "error: Invalid pattern `\\(`: .*
+  |
+1 \\| \\[pattern\\('env', '\\('\\)\\]
+  \\|  \\^\\^\\^\\^\\^\\^\\^
+",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn choices_require_a_value() {
+  Test::new()
+    .justfile(
+      "
+        [choices('env')]
+        deploy env:
+      ",
+    )
+    .arg("deploy")
+    .stderr(
+      "
+        error: Attribute `choices` got 1 argument but takes at least 2 arguments
+          |
+        1 | [choices('env')]
+          |  ^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile(
+      "
+        [choices('env', 'staging', 'prod')]
+        [pattern('replicas', '[0-9]+')]
+        deploy env replicas:
+      ",
+    )
+    .arg("--dump")
+    .stdout(
+      "
+        [choices('env', 'staging', 'prod')]
+        [pattern('replicas', '[0-9]+')]
+        deploy env replicas:
+      ",
+    )
+    .run();
+}
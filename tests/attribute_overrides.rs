@@ -0,0 +1,118 @@
+use super::*;
+
+#[test]
+fn remove_confirm() {
+  Test::new()
+    .arg("--no-attribute")
+    .arg("confirm")
+    .justfile(
+      "
+      [confirm]
+      deploy:
+        echo deployed
+    ",
+    )
+    .stderr("echo deployed\n")
+    .stdout("deployed\n")
+    .run();
+}
+
+#[test]
+fn add_confirm() {
+  Test::new()
+    .args(["--attribute", "confirm:Really?", "deploy"])
+    .justfile(
+      "
+      deploy:
+        echo deployed
+    ",
+    )
+    .stderr("error: Recipe `deploy` was not confirmed\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dependencies_are_not_overridden() {
+  Test::new()
+    .args(["--no-attribute", "confirm", "deploy"])
+    .justfile(
+      "
+      [confirm]
+      migrate:
+        echo migrated
+
+      [confirm]
+      deploy: migrate
+        echo deployed
+    ",
+    )
+    .stderr("error: Recipe `migrate` was not confirmed\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn working_directory() {
+  Test::new()
+    .args(["--attribute", "working-directory:bar", "foo"])
+    .justfile(
+      "
+      [no-cd]
+      @foo:
+        cat data
+    ",
+    )
+    .write("bar/data", "baz")
+    .stdout("baz")
+    .run();
+}
+
+#[test]
+fn remove_no_exit_message() {
+  Test::new()
+    .args(["--no-attribute", "no-exit-message"])
+    .justfile(
+      "
+      [no-exit-message]
+      @foo:
+        exit 100
+    ",
+    )
+    .stderr(
+      "
+        error: Recipe `foo` failed on line 3 with exit code 100
+          |
+        3 |   exit 100
+          |   ^^^^^^^^
+      ",
+    )
+    .status(100)
+    .run();
+}
+
+#[test]
+fn attribute_cannot_be_overridden() {
+  Test::new()
+    .args(["--attribute", "private"])
+    .justfile("foo:")
+    .stderr(
+      "error: Invalid value for '--attribute <NAME[:VALUE]>...': Attribute `private` cannot be \
+       overridden, only `confirm`, `no-cd`, `no-exit-message`, and `working-directory` can be\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn attribute_requires_value() {
+  Test::new()
+    .args(["--attribute", "working-directory"])
+    .justfile("foo:")
+    .stderr(
+      "error: Invalid value for '--attribute <NAME[:VALUE]>...': Attribute `working-directory` \
+       requires a value, e.g. `working-directory:VALUE`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
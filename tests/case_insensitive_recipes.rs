@@ -0,0 +1,149 @@
+use super::*;
+
+#[test]
+fn recipe_is_matched_ignoring_case() {
+  Test::new()
+    .justfile(
+      "
+        set case-insensitive-recipes
+
+        build:
+          @echo build
+      ",
+    )
+    .arg("Build")
+    .stdout("build\n")
+    .stderr("note: Running recipe `build` for `Build`\n")
+    .run();
+}
+
+#[test]
+fn alias_is_matched_ignoring_case() {
+  Test::new()
+    .justfile(
+      "
+        set case-insensitive-recipes
+
+        alias b := build
+
+        build:
+          @echo build
+      ",
+    )
+    .arg("B")
+    .stdout("build\n")
+    .stderr("note: Running recipe `build` for `B`\n")
+    .run();
+}
+
+#[test]
+fn exact_match_is_preferred() {
+  Test::new()
+    .justfile(
+      "
+        set case-insensitive-recipes
+
+        build:
+          @echo build
+
+        Build:
+          @echo Build
+      ",
+    )
+    .arg("Build")
+    .stdout("Build\n")
+    .run();
+}
+
+#[test]
+fn ambiguous_match_is_unknown() {
+  Test::new()
+    .justfile(
+      "
+        set case-insensitive-recipes
+
+        build:
+
+        Build:
+      ",
+    )
+    .arg("BUILD")
+    .stderr("error: Justfile does not contain recipe `BUILD`.\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn note_is_suppressed_by_quiet() {
+  Test::new()
+    .justfile(
+      "
+        set case-insensitive-recipes
+
+        build:
+          @echo build
+      ",
+    )
+    .args(["--quiet", "Build"])
+    .run();
+}
+
+#[test]
+fn recipe_arguments_are_passed() {
+  Test::new()
+    .justfile(
+      "
+        set case-insensitive-recipes
+
+        build target:
+          @echo {{target}}
+      ",
+    )
+    .args(["BUILD", "Release"])
+    .stdout("Release\n")
+    .stderr("note: Running recipe `build` for `BUILD`\n")
+    .run();
+}
+
+#[test]
+fn disabled() {
+  Test::new()
+    .justfile(
+      "
+        set case-insensitive-recipes := false
+
+        build:
+      ",
+    )
+    .arg("Build")
+    .stderr("error: Justfile does not contain recipe `Build`.\nDid you mean `build`?\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+#[cfg(not(windows))]
+fn disabled_by_default() {
+  Test::new()
+    .justfile("build:")
+    .arg("Build")
+    .stderr("error: Justfile does not contain recipe `Build`.\nDid you mean `build`?\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+#[cfg(windows)]
+fn enabled_by_default_on_windows() {
+  Test::new()
+    .justfile(
+      "
+        build:
+          @echo build
+      ",
+    )
+    .arg("Build")
+    .stdout("build\n")
+    .stderr("note: Running recipe `build` for `Build`\n")
+    .run();
+}
@@ -0,0 +1,126 @@
+use super::*;
+
+#[test]
+fn variables_are_removed() {
+  Test::new()
+    .justfile(
+      "
+        set clean-env
+        set env-passthrough := ['PATH']
+
+        foo:
+          @echo ${JUST_TEST_A:-unset}
+      ",
+    )
+    .env("JUST_TEST_A", "a")
+    .stdout("unset\n")
+    .run();
+}
+
+#[test]
+fn passthrough_variables_are_kept() {
+  Test::new()
+    .justfile(
+      "
+        set clean-env
+        set env-passthrough := ['PATH', 'JUST_TEST_B']
+
+        foo:
+          @echo ${JUST_TEST_A:-unset} $JUST_TEST_B
+      ",
+    )
+    .env("JUST_TEST_A", "a")
+    .env("JUST_TEST_B", "b")
+    .stdout("unset b\n")
+    .run();
+}
+
+#[test]
+fn exports_and_dotenv_are_kept() {
+  Test::new()
+    .justfile(
+      "
+        set clean-env
+        set dotenv-load
+        set env-passthrough := ['PATH']
+
+        export FOO := 'foo'
+
+        bar $BAR:
+          @echo $FOO $BAR $DOTENV_KEY
+      ",
+    )
+    .args(["bar", "bar"])
+    .stdout("foo bar dotenv-value\n")
+    .run();
+}
+
+#[test]
+fn backticks() {
+  Test::new()
+    .justfile(
+      "
+        set clean-env
+        set env-passthrough := ['PATH']
+
+        x := `echo ${JUST_TEST_A:-unset}`
+
+        foo:
+          @echo {{x}}
+      ",
+    )
+    .env("JUST_TEST_A", "a")
+    .stdout("unset\n")
+    .run();
+}
+
+#[test]
+fn command_line_flag() {
+  Test::new()
+    .justfile(
+      "
+        set env-passthrough := ['PATH']
+
+        foo:
+          @echo ${JUST_TEST_A:-unset}
+      ",
+    )
+    .arg("--clean-env")
+    .env("JUST_TEST_A", "a")
+    .stdout("unset\n")
+    .run();
+}
+
+#[test]
+fn disabled_by_default() {
+  Test::new()
+    .justfile(
+      "
+        set env-passthrough := ['PATH']
+
+        foo:
+          @echo ${JUST_TEST_A:-unset}
+      ",
+    )
+    .env("JUST_TEST_A", "a")
+    .stdout("a\n")
+    .run();
+}
+
+#[test]
+fn required_env_must_be_passed_through() {
+  Test::new()
+    .justfile(
+      "
+        set clean-env
+        set env-passthrough := ['PATH']
+        set required-env := ['JUST_TEST_A']
+
+        foo:
+      ",
+    )
+    .env("JUST_TEST_A", "a")
+    .stderr("error: Required environment variable `JUST_TEST_A` is not set\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -44,8 +44,9 @@ test! {
 
     USAGE:
         just{EXE_SUFFIX} --color <COLOR> --dump-format <FORMAT> --shell <SHELL> \
-        <--changelog|--choose|--command <COMMAND>|--completions <SHELL>|--dump|--edit|\
-        --evaluate|--fmt|--init|--list|--show <RECIPE>|--summary|--variables>
+        <--changelog|--choose|--command <COMMAND>|--complete <WORDS>...|--completions <SHELL>|--doc <FORMAT>|--dump|--dump-ast|--edit|\
+        --emit-aliases <SHELL>|--evaluate|--export-script|--export-tasks <EDITOR>|--fmt|--init|--install-lib <SOURCE>|--list|--list-groups|--lsp|\
+        --migrate|--show <RECIPE>|--summary|--trust|--variables>
 
     For more information try --help
   "),
@@ -0,0 +1,262 @@
+use super::*;
+
+fn entries(tempdir: &TempDir, path: &str) -> Vec<Value> {
+  fs::read_to_string(tempdir.path().join(path))
+    .unwrap()
+    .lines()
+    .map(|line| {
+      let mut entry = serde_json::from_str::<Value>(line).unwrap();
+      assert!(!entry["timestamp"].as_str().unwrap().is_empty());
+      entry["timestamp"] = json!("");
+      entry
+    })
+    .collect()
+}
+
+#[test]
+fn commands_are_logged() {
+  let output = Test::new()
+    .justfile(
+      "
+        set command-log := 'commands.log'
+
+        foo: bar
+          @echo foo
+
+        bar:
+          echo bar
+      ",
+    )
+    .stdout("bar\nfoo\n")
+    .stderr("echo bar\n")
+    .run();
+
+  let directory = output.tempdir.path().to_str().unwrap();
+
+  assert_eq!(
+    entries(&output.tempdir, "commands.log"),
+    vec![
+      json!({
+        "command": "echo bar",
+        "directory": directory,
+        "exit_code": 0,
+        "recipe": "bar",
+        "timestamp": "",
+      }),
+      json!({
+        "command": "echo foo",
+        "directory": directory,
+        "exit_code": 0,
+        "recipe": "foo",
+        "timestamp": "",
+      }),
+    ],
+  );
+}
+
+#[test]
+fn flag() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo:
+          @echo foo
+      ",
+    )
+    .args(["--command-log", "commands.log"])
+    .stdout("foo\n")
+    .run();
+
+  assert_eq!(entries(&output.tempdir, "commands.log").len(), 1);
+}
+
+#[test]
+fn flag_overrides_setting() {
+  let output = Test::new()
+    .justfile(
+      "
+        set command-log := 'setting.log'
+
+        foo:
+          @echo foo
+      ",
+    )
+    .args(["--command-log", "flag.log"])
+    .stdout("foo\n")
+    .run();
+
+  assert_eq!(entries(&output.tempdir, "flag.log").len(), 1);
+  assert!(!output.tempdir.path().join("setting.log").exists());
+}
+
+#[test]
+fn log_is_appended_to() {
+  let output = Test::new()
+    .justfile(
+      "
+        set command-log := 'commands.log'
+
+        foo:
+          @echo foo
+      ",
+    )
+    .tree(tree! {
+      "commands.log": "{}\n",
+    })
+    .stdout("foo\n")
+    .run();
+
+  assert_eq!(
+    fs::read_to_string(output.tempdir.path().join("commands.log"))
+      .unwrap()
+      .lines()
+      .count(),
+    2,
+  );
+}
+
+#[test]
+fn failed_commands_are_logged() {
+  let output = Test::new()
+    .justfile(
+      "
+        set command-log := 'commands.log'
+
+        foo:
+          @exit 3
+      ",
+    )
+    .stderr(
+      "
+        error: Recipe `foo` failed on line 4 with exit code 3
+          |
+        4 |   @exit 3
+          |   ^^^^^^^
+      ",
+    )
+    .status(3)
+    .run();
+
+  assert_eq!(entries(&output.tempdir, "commands.log")[0]["exit_code"], 3);
+}
+
+#[test]
+fn infallible_commands_are_logged_with_exit_code() {
+  let output = Test::new()
+    .justfile(
+      "
+        set command-log := 'commands.log'
+
+        foo:
+          -@exit 3
+      ",
+    )
+    .run();
+
+  let entries = entries(&output.tempdir, "commands.log");
+
+  assert_eq!(entries[0]["command"], "exit 3");
+  assert_eq!(entries[0]["exit_code"], 3);
+}
+
+#[test]
+fn scripts_are_logged_in_full() {
+  let output = Test::new()
+    .justfile(
+      "
+        set command-log := 'commands.log'
+
+        foo:
+          #!/usr/bin/env sh
+          echo foo
+      ",
+    )
+    .stdout("foo\n")
+    .run();
+
+  assert_eq!(
+    entries(&output.tempdir, "commands.log")[0]["command"],
+    "#!/usr/bin/env sh\necho foo",
+  );
+}
+
+#[test]
+fn working_directory() {
+  let output = Test::new()
+    .justfile(
+      "
+        set command-log := 'commands.log'
+
+        [working-directory('bar')]
+        foo:
+          @echo foo
+      ",
+    )
+    .tree(tree! {
+      bar: {},
+    })
+    .stdout("foo\n")
+    .run();
+
+  assert_eq!(
+    entries(&output.tempdir, "commands.log")[0]["directory"],
+    output.tempdir.path().join("bar").to_str().unwrap(),
+  );
+}
+
+#[test]
+fn dry_run_commands_are_not_logged() {
+  let output = Test::new()
+    .justfile(
+      "
+        set command-log := 'commands.log'
+
+        foo:
+          echo foo
+      ",
+    )
+    .arg("--dry-run")
+    .stderr("echo foo\n")
+    .run();
+
+  assert!(!output.tempdir.path().join("commands.log").exists());
+}
+
+#[test]
+fn unwritable_log() {
+  Test::new()
+    .justfile(
+      "
+        set command-log := 'missing/commands.log'
+
+        foo:
+          @echo foo
+          @echo bar
+      ",
+    )
+    .stdout("foo\nbar\n")
+    .stderr_regex(
+      "warning: Failed to write command log to `.*commands.log`: .*\n\
+       warning: Failed to write command log to `.*commands.log`: .*\n",
+    )
+    .run();
+}
+
+#[test]
+fn unwritable_log_does_not_hide_failure() {
+  Test::new()
+    .justfile(
+      "
+        set command-log := 'missing/commands.log'
+
+        foo:
+          @exit 3
+      ",
+    )
+    .stderr_regex(
+      "(?s)warning: Failed to write command log to `.*commands.log`: [^\n]*\n\
+       error: Recipe `foo` failed on line 4 with exit code 3\n.*",
+    )
+    .status(3)
+    .run();
+}
@@ -1,19 +1,45 @@
 use super::*;
 
-#[test]
-fn output() {
+fn completions(shell: &str) -> String {
   let tempdir = tempdir();
 
   let output = Command::new(executable_path("just"))
     .arg("--completions")
-    .arg("bash")
+    .arg(shell)
     .current_dir(tempdir.path())
     .output()
     .unwrap();
 
   assert!(output.status.success());
 
-  let text = String::from_utf8_lossy(&output.stdout);
+  String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn output() {
+  assert!(completions("bash").starts_with("_just() {"));
+}
+
+#[test]
+fn elvish() {
+  let text = completions("elvish");
+  assert!(text.starts_with("use str\n\nset edit:completion:arg-completer[just] = {|@words|"));
+  assert!(text.contains("just --complete (all $words[1..])"));
+}
 
-  assert!(text.starts_with("_just() {"));
+#[test]
+fn nushell() {
+  let text = completions("nushell");
+  assert!(text.starts_with("def \"nu-complete just\" [context: string] {"));
+  assert!(text.contains("\nexport extern \"just\" [\n"));
+  assert!(text.contains("\n    --dry-run(-n) # Print what just would do without doing it\n"));
+  assert!(
+    text.contains("\n    --color: string@\"nu-complete just color\" # Print colorful output\n")
+  );
+  assert!(!text.contains("--complete:"));
+}
+
+#[test]
+fn nushell_is_case_insensitive() {
+  assert_eq!(completions("NuShell"), completions("nushell"));
 }
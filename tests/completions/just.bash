@@ -27,13 +27,17 @@ exit_code=0
 # --- Tests ---
 test_complete_all_recipes() {
   COMP_WORDS=(just)
+  COMP_LINE='just '
+  COMP_POINT=5
   COMP_CWORD=1 _just just
-  reply_equals 'declare -a COMPREPLY=([0]="deploy" [1]="install" [2]="publish" [3]="push" [4]="test")'
+  reply_equals 'declare -a COMPREPLY=([0]="version=" [1]="deploy" [2]="install" [3]="publish" [4]="push" [5]="test")'
 }
 test_complete_all_recipes
 
 test_complete_recipes_starting_with_i() {
   COMP_WORDS=(just i)
+  COMP_LINE='just i'
+  COMP_POINT=6
   COMP_CWORD=1 _just just
   reply_equals 'declare -a COMPREPLY=([0]="install")'
 }
@@ -41,6 +45,8 @@ test_complete_recipes_starting_with_i
 
 test_complete_recipes_starting_with_p() {
   COMP_WORDS=(just p)
+  COMP_LINE='just p'
+  COMP_POINT=6
   COMP_CWORD=1 _just just
   reply_equals 'declare -a COMPREPLY=([0]="publish" [1]="push")'
 }
@@ -53,6 +59,33 @@ test_complete_recipes_from_subdirs() {
 }
 test_complete_recipes_from_subdirs
 
+test_complete_recipe_arguments() {
+  COMP_WORDS=(just deploy)
+  COMP_LINE='just deploy '
+  COMP_POINT=12
+  COMP_CWORD=2 _just just
+  reply_equals 'declare -a COMPREPLY=([0]="production" [1]="staging" [2]="target=production" [3]="target=staging" [4]="target=" [5]="deploy" [6]="install" [7]="publish" [8]="push" [9]="test")'
+}
+test_complete_recipe_arguments
+
+test_complete_keyword_arguments() {
+  COMP_WORDS=(just deploy --target = p)
+  COMP_LINE='just deploy --target=p'
+  COMP_POINT=22
+  COMP_CWORD=4 _just just
+  reply_equals 'declare -a COMPREPLY=([0]="production")'
+}
+test_complete_keyword_arguments
+
+test_complete_overrides() {
+  COMP_WORDS=(just v)
+  COMP_LINE='just v'
+  COMP_POINT=6
+  COMP_CWORD=1 _just just
+  reply_equals 'declare -a COMPREPLY=([0]="version=")'
+}
+test_complete_overrides
+
 # --- Conclusion ---
 if [ $exit_code = 0 ]; then
   echo "All tests passed."
@@ -198,3 +198,85 @@ test! {
   ",
   status: EXIT_FAILURE,
 }
+
+test! {
+  name: and,
+  justfile: "
+    x := if 'a' == 'a' && 'b' == 'b' { 'yes' } else { 'no' }
+    y := if 'a' == 'a' && 'b' == 'c' { 'yes' } else { 'no' }
+  ",
+  args: ("--evaluate"),
+  stdout: "x := \"yes\"\ny := \"no\"\n",
+}
+
+test! {
+  name: or,
+  justfile: "
+    x := if 'a' == 'b' || 'b' == 'b' { 'yes' } else { 'no' }
+    y := if 'a' == 'b' || 'b' == 'c' { 'yes' } else { 'no' }
+  ",
+  args: ("--evaluate"),
+  stdout: "x := \"yes\"\ny := \"no\"\n",
+}
+
+test! {
+  name: not,
+  justfile: "
+    x := if !'a' == 'b' { 'yes' } else { 'no' }
+    y := if !('a' == 'b' || 'b' =~ '^b$') { 'yes' } else { 'no' }
+  ",
+  args: ("--evaluate"),
+  stdout: "x := \"yes\"\ny := \"no\"\n",
+}
+
+test! {
+  name: and_binds_tighter_than_or,
+  justfile: "
+    x := if 'a' == 'a' || 'a' == 'b' && 'b' == 'c' { 'yes' } else { 'no' }
+    y := if ('a' == 'a' || 'a' == 'b') && 'b' == 'c' { 'yes' } else { 'no' }
+  ",
+  args: ("--evaluate"),
+  stdout: "x := \"yes\"\ny := \"no\"\n",
+}
+
+test! {
+  name: parenthesized_expression_in_condition,
+  justfile: "
+    x := if ('a' + 'b') == 'ab' && ('c') != 'd' { 'yes' } else { 'no' }
+  ",
+  args: ("--evaluate", "x"),
+  stdout: "yes",
+}
+
+test! {
+  name: logical_operators_short_circuit,
+  justfile: "
+    x := if 'a' == 'b' && `exit 1` == '' { 'yes' } else { 'no' }
+    y := if 'a' == 'a' || `exit 1` == '' { 'yes' } else { 'no' }
+  ",
+  args: ("--evaluate"),
+  stdout: "x := \"no\"\ny := \"yes\"\n",
+}
+
+test! {
+  name: logical_operators_dump,
+  justfile: "
+    x := if !('a' == 'b' || 'c' != 'd') && 'e' =~ 'f' { 'yes' } else { 'no' }
+  ",
+  args: ("--dump"),
+  stdout: "x := if !('a' == 'b' || 'c' != 'd') && 'e' =~ 'f' { 'yes' } else { 'no' }\n",
+}
+
+test! {
+  name: missing_operand_after_and,
+  justfile: "
+    x := if 'a' == 'a' && { 'yes' } else { 'no' }
+  ",
+  stderr: "
+    error: Expected backtick, '!', identifier, '(', '/', or string, but found '{'
+      |
+    1 | x := if 'a' == 'a' && { 'yes' } else { 'no' }
+      |                       ^
+  ",
+  status: EXIT_FAILURE,
+}
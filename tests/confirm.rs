@@ -0,0 +1,134 @@
+use super::*;
+
+#[test]
+fn confirm_attribute_with_yes() {
+  Test::new()
+    .justfile(
+      "
+        [confirm]
+        deploy:
+          @echo deployed
+      ",
+    )
+    .args(["--yes", "deploy"])
+    .stdout("deployed\n")
+    .run();
+}
+
+#[test]
+fn confirm_attribute_is_refused_without_terminal() {
+  Test::new()
+    .justfile(
+      "
+        [confirm]
+        deploy:
+          @echo deployed
+      ",
+    )
+    .arg("deploy")
+    .stderr("error: Recipe `deploy` was not confirmed\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn confirm_attribute_prevents_dependencies_from_running() {
+  Test::new()
+    .justfile(
+      "
+        build:
+          @echo built
+
+        [confirm('Deploy to production?')]
+        deploy: build
+          @echo deployed
+      ",
+    )
+    .arg("deploy")
+    .stderr("error: Recipe `deploy` was not confirmed\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn confirm_attribute_dump() {
+  Test::new()
+    .justfile(
+      "
+        [confirm]
+        a:
+
+        [confirm('Really?')]
+        b:
+      ",
+    )
+    .arg("--dump")
+    .stdout(
+      "
+        [confirm]
+        a:
+
+        [confirm('Really?')]
+        b:
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn confirm_function_with_yes() {
+  Test::new()
+    .justfile(
+      "
+        deploy:
+          @echo {{ confirm('Deploy?') }}
+      ",
+    )
+    .args(["--yes", "deploy"])
+    .stdout("true\n")
+    .run();
+}
+
+#[test]
+fn confirm_function_is_false_without_terminal() {
+  Test::new()
+    .justfile(
+      "
+        deploy:
+          @echo {{ confirm('Deploy?') }}
+      ",
+    )
+    .arg("deploy")
+    .stdout("false\n")
+    .run();
+}
+
+#[test]
+fn confirm_function_in_conditional() {
+  Test::new()
+    .justfile(
+      "
+        deploy:
+          @echo {{ if confirm('Migrate database?') == 'true' { 'migrating' } else { 'skipping' } }}
+      ",
+    )
+    .arg("deploy")
+    .stdout("skipping\n")
+    .run();
+}
+
+#[test]
+fn confirm_function_in_assignment() {
+  Test::new()
+    .justfile(
+      "
+        migrate := confirm('Migrate database?')
+
+        deploy:
+          @echo {{ migrate }}
+      ",
+    )
+    .args(["--yes", "deploy"])
+    .stdout("true\n")
+    .run();
+}
@@ -0,0 +1,289 @@
+use super::*;
+
+#[test]
+fn output_is_bound() {
+  Test::new()
+    .justfile(
+      "
+        release: (v := version)
+          @echo releasing {{v}}
+
+        version:
+          @echo 1.2.3
+      ",
+    )
+    .stdout("releasing 1.2.3\n")
+    .run();
+}
+
+#[test]
+fn arguments() {
+  Test::new()
+    .justfile(
+      "
+        release: (v := version 'rc')
+          @echo releasing {{v}}
+
+        version suffix:
+          @echo 1.2.3-{{suffix}}
+      ",
+    )
+    .stdout("releasing 1.2.3-rc\n")
+    .run();
+}
+
+#[test]
+fn echoed_commands_are_printed() {
+  Test::new()
+    .justfile(
+      "
+        release: (v := version)
+          @echo {{v}}
+
+        version:
+          echo 1.2.3
+      ",
+    )
+    .stdout("1.2.3\n")
+    .stderr("echo 1.2.3\n")
+    .run();
+}
+
+#[test]
+fn shebang_dependency() {
+  Test::new()
+    .justfile(
+      "
+        release: (v := version)
+          @echo {{v}}
+
+        version:
+          #!/usr/bin/env bash
+          echo 1.2.3
+      ",
+    )
+    .stdout("1.2.3\n")
+    .run();
+}
+
+#[test]
+fn multiple_lines() {
+  Test::new()
+    .justfile(
+      "
+        foo: (v := bar)
+          @echo '{{v}}'
+
+        bar:
+          @echo a
+          @echo b
+      ",
+    )
+    .stdout("a\nb\n")
+    .run();
+}
+
+#[test]
+fn env_attribute() {
+  Test::new()
+    .justfile(
+      "
+        [env('VERSION', v)]
+        release: (v := version)
+          #!/usr/bin/env bash
+          echo $VERSION
+
+        version:
+          @echo 1.2.3
+      ",
+    )
+    .stdout("1.2.3\n")
+    .run();
+}
+
+#[test]
+fn captured_output_is_reused() {
+  Test::new()
+    .justfile(
+      "
+        release: (v := version) build
+          @echo releasing {{v}}
+
+        build: version
+          @echo building
+
+        version:
+          @echo 1.2.3 >&2
+          @echo 1.2.3
+      ",
+    )
+    .stdout("building\nreleasing 1.2.3\n")
+    .stderr("1.2.3\n")
+    .run();
+}
+
+#[test]
+fn recipe_is_not_rerun_to_capture_output() {
+  Test::new()
+    .justfile(
+      "
+        release: (v := version)
+          @echo releasing {{v}}
+
+        version:
+          @echo 1.2.3
+      ",
+    )
+    .args(["version", "release"])
+    .stdout("1.2.3\n")
+    .stderr(
+      "error: The output of recipe `version` can't be captured, since it has already run without its output being captured\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dry_run_uses_placeholder() {
+  Test::new()
+    .justfile(
+      "
+        release: (v := version)
+          echo releasing {{v}}
+
+        version:
+          echo 1.2.3
+      ",
+    )
+    .arg("--dry-run")
+    .stderr("echo 1.2.3\necho releasing <output of version>\n")
+    .run();
+}
+
+#[test]
+fn failing_dependency() {
+  Test::new()
+    .justfile(
+      "
+        release: (v := version)
+          @echo releasing {{v}}
+
+        version:
+          @exit 3
+      ",
+    )
+    .stderr(
+      "
+        error: Recipe `version` failed on line 5 with exit code 3
+          |
+        5 |   @exit 3
+          |   ^^^^^^^
+      ",
+    )
+    .status(3)
+    .run();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile(
+      "
+        release: (v := version 'rc') && build
+
+        version suffix:
+
+        build:
+      ",
+    )
+    .arg("--dump")
+    .stdout("release: (v := version 'rc') && build\n\nversion suffix:\n\nbuild:\n")
+    .run();
+}
+
+#[test]
+fn subsequent_dependency() {
+  Test::new()
+    .justfile(
+      "
+        release: && (v := version)
+
+        version:
+      ",
+    )
+    .stderr(
+      "
+        error: Recipe `release` cannot capture the output of subsequent dependency `version`, since it runs after the recipe
+          |
+        1 | release: && (v := version)
+          |              ^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn capture_shadows_parameter() {
+  Test::new()
+    .justfile(
+      "
+        release v: (v := version)
+
+        version:
+      ",
+    )
+    .stderr(
+      "
+        error: Recipe `release` already has a parameter or capture named `v`
+          |
+        1 | release v: (v := version)
+          |             ^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn capture_is_not_visible_to_other_recipes() {
+  Test::new()
+    .justfile(
+      "
+        release: (v := version)
+
+        build:
+          echo {{v}}
+
+        version:
+      ",
+    )
+    .stderr(
+      "
+        error: Variable `v` not defined
+          |
+        4 |   echo {{v}}
+          |          ^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn export_script() {
+  Test::new()
+    .justfile(
+      "
+        release: (v := version)
+
+        version:
+      ",
+    )
+    .args(["--export-script", "release"])
+    .stderr(
+      "error: Recipe `release` can't be exported, since it uses the output of dependency `version`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
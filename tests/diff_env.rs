@@ -0,0 +1,112 @@
+use super::*;
+
+#[test]
+fn added_variables() {
+  Test::new()
+    .justfile(
+      "
+        export JUST_TEST_ADDED := 'foo'
+
+        build:
+          @echo $JUST_TEST_ADDED
+      ",
+    )
+    .args(["--diff-env", "build"])
+    .stdout("foo\n")
+    .stderr("Environment for recipe `build`:\n    + JUST_TEST_ADDED=foo\n")
+    .run();
+}
+
+#[test]
+fn changed_variables() {
+  Test::new()
+    .justfile(
+      "
+        export JUST_TEST_CHANGED := 'new'
+
+        build:
+          @echo $JUST_TEST_CHANGED
+      ",
+    )
+    .env("JUST_TEST_CHANGED", "old")
+    .args(["--diff-env", "build"])
+    .stdout("new\n")
+    .stderr("Environment for recipe `build`:\n    ~ JUST_TEST_CHANGED=new (was old)\n")
+    .run();
+}
+
+#[test]
+fn variables_set_to_their_current_value_are_unchanged() {
+  Test::new()
+    .justfile(
+      "
+        export JUST_TEST_SAME := 'same'
+
+        build:
+          @echo $JUST_TEST_SAME
+      ",
+    )
+    .env("JUST_TEST_SAME", "same")
+    .args(["--diff-env", "build"])
+    .stdout("same\n")
+    .stderr("Environment for recipe `build` is unchanged\n")
+    .run();
+}
+
+#[test]
+fn dotenv_and_exported_parameters() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-load
+
+        build $JUST_TEST_PARAMETER:
+          @echo $DOTENV_KEY $JUST_TEST_PARAMETER
+      ",
+    )
+    .args(["--diff-env", "build", "parameter"])
+    .stdout("dotenv-value parameter\n")
+    .stderr(
+      "
+        Environment for recipe `build`:
+            + DOTENV_KEY=dotenv-value
+            + JUST_TEST_PARAMETER=parameter
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn each_recipe() {
+  Test::new()
+    .justfile(
+      "
+        set export
+
+        a x:
+          @echo {{x}}
+
+        b: (a 'y')
+      ",
+    )
+    .args(["--diff-env", "b"])
+    .stdout("y\n")
+    .stderr("Environment for recipe `a`:\n    + x=y\nEnvironment for recipe `b` is unchanged\n")
+    .run();
+}
+
+#[test]
+fn dry_run() {
+  Test::new()
+    .justfile(
+      "
+        export JUST_TEST_ADDED := 'foo'
+
+        build:
+          echo $JUST_TEST_ADDED
+      ",
+    )
+    .args(["--diff-env", "--dry-run", "build"])
+    .stderr("Environment for recipe `build`:\n    + JUST_TEST_ADDED=foo\necho $JUST_TEST_ADDED\n")
+    .run();
+}
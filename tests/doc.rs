@@ -0,0 +1,176 @@
+use super::*;
+
+const JUSTFILE: &str = "
+  alias b := build
+  alias _c := build
+
+  # build the project
+  [group('dev')]
+  [param-doc('target', 'the cargo target triple')]
+  build target profile='dev':
+
+  test *flags:
+
+  _private:
+";
+
+#[test]
+fn markdown() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--doc", "markdown"])
+    .stdout(
+      "
+        # Recipes
+
+        ## `build`
+
+        build the project
+
+        ```sh
+        just build target profile='dev'
+        ```
+
+        | Parameter | Default | Description |
+        | --- | --- | --- |
+        | `target` |  | the cargo target triple |
+        | `profile` | `'dev'` |  |
+
+        **Aliases:** `b`
+
+        **Groups:** `dev`
+
+        ## `test`
+
+        ```sh
+        just test *flags
+        ```
+
+        | Parameter | Default | Description |
+        | --- | --- | --- |
+        | `*flags` |  |  |
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn markdown_escapes_pipes() {
+  Test::new()
+    .justfile(
+      "
+        [param-doc('x', 'a | b')]
+        foo x='|':
+      ",
+    )
+    .args(["--doc", "markdown"])
+    .stdout(
+      "
+        # Recipes
+
+        ## `foo`
+
+        ```sh
+        just foo x='|'
+        ```
+
+        | Parameter | Default | Description |
+        | --- | --- | --- |
+        | `x` | `'\\|'` | a \\| b |
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn man() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--doc", "man"])
+    .stdout(
+      r"
+        .TH JUSTFILE 7
+        .SH NAME
+        justfile \- recipes
+        .SH RECIPES
+        .SS build
+        .PP
+        .B just build target profile='dev'
+        .PP
+        build the project
+        .TP
+        .B target
+        the cargo target triple
+        .TP
+        .B profile
+        Default: 'dev'
+        .PP
+        Aliases: b
+        .PP
+        Groups: dev
+        .SS test
+        .PP
+        .B just test *flags
+        .TP
+        .B *flags
+        Optional
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn man_escapes() {
+  Test::new()
+    .justfile(
+      "
+        [doc('.starts-with-dot\\')]
+        foo:
+      ",
+    )
+    .args(["--doc", "MAN"])
+    .stdout(
+      r"
+        .TH JUSTFILE 7
+        .SH NAME
+        justfile \- recipes
+        .SH RECIPES
+        .SS foo
+        .PP
+        .B just foo
+        .PP
+        \&.starts\-with\-dot\e
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn unsorted() {
+  Test::new()
+    .justfile(
+      "
+        b:
+        a:
+      ",
+    )
+    .args(["--doc", "markdown", "--unsorted"])
+    .stdout(
+      "
+        # Recipes
+
+        ## `b`
+
+        ```sh
+        just b
+        ```
+
+        ## `a`
+
+        ```sh
+        just a
+        ```
+      ",
+    )
+    .run();
+}
@@ -0,0 +1,156 @@
+use super::*;
+
+#[test]
+fn doc_attribute_overrides_comment() {
+  Test::new()
+    .justfile(
+      "
+        # implementation note
+        [doc('Build the project')]
+        build:
+
+        # Run the tests
+        test:
+      ",
+    )
+    .arg("--list")
+    .stdout(
+      "
+        Available recipes:
+            build # Build the project
+            test  # Run the tests
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn empty_doc_attribute_suppresses_comment() {
+  Test::new()
+    .justfile(
+      "
+        # implementation note
+        [doc]
+        build:
+      ",
+    )
+    .arg("--list")
+    .stdout(
+      "
+        Available recipes:
+            build
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn multi_line_doc() {
+  Test::new()
+    .justfile(
+      "
+        [doc('''
+          Build the project
+
+          Pass --release for an optimized build
+        ''')]
+        build *args:
+
+        test:
+      ",
+    )
+    .arg("--list")
+    .stdout(
+      "
+        Available recipes:
+            build *args # Build the project
+                        #
+                        # Pass --release for an optimized build
+            test
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn doc_attribute_is_used_for_aliases() {
+  Test::new()
+    .justfile(
+      "
+        alias b := build
+
+        [doc('Build the project')]
+        build:
+      ",
+    )
+    .arg("--list")
+    .stdout(
+      "
+        Available recipes:
+            build # Build the project
+            b     # alias for `build`
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn show() {
+  Test::new()
+    .justfile(
+      "
+        # implementation note
+        [doc('Build the project')]
+        build:
+          cargo build
+      ",
+    )
+    .args(["--show", "build"])
+    .stdout(
+      "
+        # implementation note
+        [doc('Build the project')]
+        build:
+            cargo build
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn dump_json() {
+  Test::new()
+    .justfile(
+      "
+        # implementation note
+        [doc('Build the project')]
+        build:
+      ",
+    )
+    .args(["--dump", "--dump-format", "json"])
+    .stdout_regex(
+      r#"(?s).*"attributes":\[\{"doc":"Build the project"\}\],.*"doc":"implementation note".*"#,
+    )
+    .run();
+}
+
+#[test]
+fn doc_attribute_takes_at_most_one_argument() {
+  Test::new()
+    .justfile(
+      "
+        [doc('a', 'b')]
+        build:
+      ",
+    )
+    .stderr(
+      "
+        error: Attribute `doc` got 2 arguments but takes at most 1 argument
+          |
+        1 | [doc('a', 'b')]
+          |  ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
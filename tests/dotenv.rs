@@ -245,3 +245,358 @@ fn program_argument_has_priority_for_dotenv_path() {
     .status(EXIT_SUCCESS)
     .run();
 }
+
+#[test]
+fn dotenv_filenames_later_files_take_precedence() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-filenames := ['.env.base', '.env.local']
+
+        foo:
+          @echo $BASE $NAME
+      ",
+    )
+    .tree(tree! {
+      ".env.base": "BASE=base\nNAME=bar",
+      ".env.local": "NAME=baz",
+    })
+    .stdout("base baz\n")
+    .run();
+}
+
+#[test]
+fn dotenv_filenames_skip_missing_files() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-filenames := ['.env.missing', '.env.base']
+
+        foo:
+          @echo $NAME
+      ",
+    )
+    .write(".env.base", "NAME=bar")
+    .stdout("bar\n")
+    .run();
+}
+
+#[test]
+fn dotenv_filenames_expand_environment_variables() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-filenames := ['.env.base', '.env.$STAGE', '.env.${JUST_TEST_UNSET_VARIABLE}']
+
+        foo:
+          @echo $NAME
+      ",
+    )
+    .tree(tree! {
+      ".env.base": "NAME=bar",
+      ".env.prod": "NAME=baz",
+    })
+    .env("STAGE", "prod")
+    .stdout("baz\n")
+    .run();
+}
+
+#[test]
+fn dotenv_filenames_do_not_override_environment() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-filenames := ['.env.base']
+
+        foo:
+          @echo $NAME
+      ",
+    )
+    .write(".env.base", "NAME=bar")
+    .env("NAME", "baz")
+    .stdout("baz\n")
+    .run();
+}
+
+#[test]
+fn program_argument_has_priority_for_dotenv_filenames() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-filenames := ['.env.base']
+
+        foo:
+          @echo $NAME
+      ",
+    )
+    .tree(tree! {
+      ".env.base": "NAME=bar",
+      ".env.special": "NAME=baz",
+    })
+    .args(["--dotenv-filename", ".env.special"])
+    .stdout("baz\n")
+    .run();
+}
+
+#[test]
+fn dotenv_attribute_overrides_global_dotenv() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-filenames := ['.env.base']
+
+        [dotenv('.env.test')]
+        test: build
+          @echo test $NAME
+
+        build:
+          @echo build $NAME
+      ",
+    )
+    .tree(tree! {
+      ".env.base": "NAME=bar",
+      ".env.test": "NAME=baz",
+    })
+    .arg("test")
+    .stdout("build bar\ntest baz\n")
+    .run();
+}
+
+#[test]
+fn dotenv_attribute_without_dotenv_setting() {
+  Test::new()
+    .justfile(
+      "
+        [dotenv('.env.test', '.env.missing')]
+        foo:
+          @echo $NAME
+      ",
+    )
+    .write(".env.test", "NAME=baz")
+    .stdout("baz\n")
+    .run();
+}
+
+#[test]
+fn dotenv_attribute_is_ignored_with_no_dotenv() {
+  Test::new()
+    .justfile(
+      "
+        [dotenv('.env.test')]
+        foo:
+          @echo ${NAME:-unset}
+      ",
+    )
+    .write(".env.test", "NAME=baz")
+    .arg("--no-dotenv")
+    .stdout("unset\n")
+    .run();
+}
+
+#[test]
+fn dotenv_required_present() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-required
+
+        foo:
+          @echo $DOTENV_KEY
+      ",
+    )
+    .stdout("dotenv-value\n")
+    .run();
+}
+
+#[test]
+fn dotenv_required_with_filename() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-required
+        set dotenv-filename := '.env.missing'
+
+        foo:
+          @echo foo
+      ",
+    )
+    .stderr_regex(r"error: Required environment file `.*[/\\]\.env\.missing` not found\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dotenv_required_with_filenames() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-required
+        set dotenv-filenames := ['.env.missing', '.env']
+
+        foo:
+          @echo foo
+      ",
+    )
+    .stderr_regex(r"error: Required environment file `.*[/\\]\.env\.missing` not found\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dotenv_required_with_filenames_only_requires_first() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-required
+        set dotenv-filenames := ['.env', '.env.local']
+
+        foo:
+          @echo $DOTENV_KEY
+      ",
+    )
+    .stdout("dotenv-value\n")
+    .run();
+}
+
+#[test]
+fn dotenv_required_false() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-required := false
+        set dotenv-filename := '.env.missing'
+
+        foo:
+          @echo foo
+      ",
+    )
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn dotenv_required_does_not_require_dotenv_attribute_files() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-required
+
+        [dotenv('.env.test')]
+        foo:
+          @echo $DOTENV_KEY
+      ",
+    )
+    .stdout("dotenv-value\n")
+    .run();
+}
+
+#[test]
+fn dotenv_required_with_no_dotenv() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-required
+        set dotenv-filename := '.env.missing'
+
+        foo:
+          @echo foo
+      ",
+    )
+    .arg("--no-dotenv")
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn dotenv_function() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-load
+
+        foo:
+          @echo {{ dotenv('DOTENV_KEY') }} {{ dotenv('MISSING', 'default') }}
+      ",
+    )
+    .stdout("dotenv-value default\n")
+    .run();
+}
+
+#[test]
+fn dotenv_function_missing_key() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-load
+
+        foo:
+          echo {{ dotenv('MISSING') }}
+      ",
+    )
+    .stderr(
+      "
+        error: Call to function `dotenv` failed: `MISSING` was not loaded from an environment file
+          |
+        4 |   echo {{ dotenv('MISSING') }}
+          |           ^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dotenv_namespace() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-load
+
+        key := dotenv::DOTENV_KEY
+
+        foo:
+          @echo {{ key }} {{ dotenv::DOTENV_KEY }}
+      ",
+    )
+    .stdout("dotenv-value dotenv-value\n")
+    .run();
+}
+
+#[test]
+fn dotenv_does_not_include_environment() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-load
+
+        foo:
+          @echo {{ dotenv('DOTENV_KEY', 'unset') }} {{ env('DOTENV_KEY') }}
+      ",
+    )
+    .env("DOTENV_KEY", "environment-value")
+    .stdout("unset environment-value\n")
+    .run();
+}
+
+#[test]
+fn dotenv_namespace_missing_key() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-load
+
+        foo:
+          echo {{ dotenv::MISSING }}
+      ",
+    )
+    .stderr(
+      "
+        error: `MISSING` was not loaded from an environment file
+          |
+        4 |   echo {{ dotenv::MISSING }}
+          |           ^^^^^^^^^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
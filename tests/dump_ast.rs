@@ -0,0 +1,224 @@
+use super::*;
+
+fn test(justfile: &str, value: Value) {
+  Test::new()
+    .justfile(justfile)
+    .arg("--dump-ast")
+    .stdout(format!("{}\n", serde_json::to_string(&value).unwrap()))
+    .run();
+}
+
+fn span(offset: usize, length: usize, line: usize, column: usize) -> Value {
+  json!({
+    "offset": offset,
+    "length": length,
+    "line": line,
+    "column": column,
+  })
+}
+
+#[test]
+fn assignment() {
+  test(
+    "x := 'a' + y\ny := `ls`",
+    json!({
+      "items": [
+        {
+          "kind": "assignment",
+          "name": { "name": "x", "span": span(0, 1, 0, 0) },
+          "export": false,
+          "lazy": false,
+          "value": {
+            "kind": "concatenation",
+            "lhs": { "kind": "string", "raw": "a", "cooked": "a" },
+            "rhs": {
+              "kind": "variable",
+              "variable": { "name": "y", "span": span(11, 1, 0, 11) },
+            },
+          },
+        },
+        {
+          "kind": "assignment",
+          "name": { "name": "y", "span": span(13, 1, 1, 0) },
+          "export": false,
+          "lazy": false,
+          "value": { "kind": "backtick", "contents": "ls", "span": span(18, 4, 1, 5) },
+        },
+      ],
+    }),
+  );
+}
+
+#[test]
+fn recipe() {
+  test(
+    "
+      # hello
+      foo a *b='': (bar a)
+        echo {{a}}
+
+      bar x:
+    ",
+    json!({
+      "items": [
+        {
+          "kind": "recipe",
+          "name": { "name": "foo", "span": span(8, 3, 1, 0) },
+          "doc": "hello",
+          "attributes": [],
+          "quiet": false,
+          "shebang": false,
+          "parameters": [
+            {
+              "name": { "name": "a", "span": span(12, 1, 1, 4) },
+              "kind": "singular",
+              "export": false,
+              "default": null,
+              "doc": null,
+            },
+            {
+              "name": { "name": "b", "span": span(15, 1, 1, 7) },
+              "kind": "star",
+              "export": false,
+              "default": { "kind": "string", "raw": "", "cooked": "" },
+              "doc": null,
+            },
+          ],
+          "dependencies": [
+            {
+              "recipe": { "name": "bar", "span": span(22, 3, 1, 14) },
+              "arguments": [
+                {
+                  "kind": "variable",
+                  "variable": { "name": "a", "span": span(26, 1, 1, 18) },
+                },
+              ],
+              "subsequent": false,
+            },
+          ],
+          "body": [
+            [
+              { "kind": "text", "text": "echo ", "span": span(31, 5, 2, 2) },
+              {
+                "kind": "interpolation",
+                "expression": {
+                  "kind": "variable",
+                  "variable": { "name": "a", "span": span(38, 1, 2, 9) },
+                },
+              },
+            ],
+          ],
+        },
+        {
+          "kind": "recipe",
+          "name": { "name": "bar", "span": span(43, 3, 4, 0) },
+          "doc": null,
+          "attributes": [],
+          "quiet": false,
+          "shebang": false,
+          "parameters": [
+            {
+              "name": { "name": "x", "span": span(47, 1, 4, 4) },
+              "kind": "singular",
+              "export": false,
+              "default": null,
+              "doc": null,
+            },
+          ],
+          "dependencies": [],
+          "body": [],
+        },
+      ],
+    }),
+  );
+}
+
+#[test]
+fn conditional_and_call() {
+  test(
+    "x := if env('A') != 'b' { 'c' } else { /'d' }",
+    json!({
+      "items": [
+        {
+          "kind": "assignment",
+          "name": { "name": "x", "span": span(0, 1, 0, 0) },
+          "export": false,
+          "lazy": false,
+          "value": {
+            "kind": "conditional",
+            "condition": {
+              "kind": "comparison",
+              "operator": "!=",
+              "lhs": {
+                "kind": "call",
+                "function": { "name": "env", "span": span(8, 3, 0, 8) },
+                "arguments": [{ "kind": "string", "raw": "A", "cooked": "A" }],
+              },
+              "rhs": { "kind": "string", "raw": "b", "cooked": "b" },
+            },
+            "then": { "kind": "string", "raw": "c", "cooked": "c" },
+            "otherwise": {
+              "kind": "join",
+              "lhs": null,
+              "rhs": { "kind": "string", "raw": "d", "cooked": "d" },
+            },
+          },
+        },
+      ],
+    }),
+  );
+}
+
+#[test]
+fn alias_and_setting() {
+  test(
+    "
+      set export
+      alias f := foo
+      foo:
+    ",
+    json!({
+      "items": [
+        {
+          "kind": "set",
+          "name": { "name": "export", "span": span(4, 6, 0, 4) },
+          "value": "true",
+        },
+        {
+          "kind": "alias",
+          "name": { "name": "f", "span": span(17, 1, 1, 6) },
+          "target": { "name": "foo", "span": span(22, 3, 1, 11) },
+          "attributes": [],
+        },
+        {
+          "kind": "recipe",
+          "name": { "name": "foo", "span": span(26, 3, 2, 0) },
+          "doc": null,
+          "attributes": [],
+          "quiet": false,
+          "shebang": false,
+          "parameters": [],
+          "dependencies": [],
+          "body": [],
+        },
+      ],
+    }),
+  );
+}
+
+#[test]
+fn invalid_justfile() {
+  Test::new()
+    .justfile("foo: bar")
+    .arg("--dump-ast")
+    .stderr(
+      "
+        error: Recipe `foo` has unknown dependency `bar`
+          |
+        1 | foo: bar
+          |      ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -0,0 +1,123 @@
+use super::*;
+
+const JUSTFILE: &str = "
+foo:
+  echo foo
+  @echo quiet
+";
+
+#[test]
+fn all() {
+  Test::new()
+    .justfile(format!("set echo := 'all'\n{JUSTFILE}"))
+    .arg("foo")
+    .stdout("foo\nquiet\n")
+    .stderr("echo foo\n")
+    .run();
+}
+
+#[test]
+fn none() {
+  Test::new()
+    .justfile(format!("set echo := 'none'\n{JUSTFILE}"))
+    .arg("foo")
+    .stdout("foo\nquiet\n")
+    .run();
+}
+
+#[test]
+fn prefixed() {
+  Test::new()
+    .justfile(format!("set echo := 'prefixed'\n{JUSTFILE}"))
+    .arg("foo")
+    .stdout("foo\nquiet\n")
+    .stderr("[foo] echo foo\n")
+    .run();
+}
+
+#[test]
+fn failed() {
+  Test::new()
+    .justfile(
+      "
+        set echo := 'failed'
+
+        foo:
+          echo foo
+          -exit 1
+          exit 2
+      ",
+    )
+    .arg("foo")
+    .stdout("foo\n")
+    .stderr(
+      "
+        exit 2
+        error: Recipe `foo` failed on line 6 with exit code 2
+          |
+        6 |   exit 2
+          |   ^^^^^^
+      ",
+    )
+    .status(2)
+    .run();
+}
+
+#[test]
+fn failed_quiet_lines_are_not_echoed() {
+  Test::new()
+    .justfile(
+      "
+        set echo := 'failed'
+
+        foo:
+          @exit 2
+      ",
+    )
+    .arg("foo")
+    .stderr(
+      "
+        error: Recipe `foo` failed on line 4 with exit code 2
+          |
+        4 |   @exit 2
+          |   ^^^^^^^
+      ",
+    )
+    .status(2)
+    .run();
+}
+
+#[test]
+fn dry_run_echoes_lines() {
+  Test::new()
+    .justfile(format!("set echo := 'none'\n{JUSTFILE}"))
+    .args(["--dry-run", "foo"])
+    .stderr("echo foo\necho quiet\n")
+    .run();
+}
+
+#[test]
+fn flag_overrides_setting() {
+  Test::new()
+    .justfile(format!("set echo := 'none'\n{JUSTFILE}"))
+    .args(["--echo", "prefixed", "foo"])
+    .stdout("foo\nquiet\n")
+    .stderr("[foo] echo foo\n")
+    .run();
+}
+
+#[test]
+fn invalid() {
+  Test::new()
+    .justfile("set echo := 'some'")
+    .stderr(
+      "
+        error: Invalid echo mode `some`, expected `all`, `failed`, `none`, or `prefixed`
+          |
+        1 | set echo := 'some'
+          |             ^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
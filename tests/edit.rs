@@ -170,3 +170,88 @@ fn editor_working_directory() {
 
   assert_stdout(&output, &want);
 }
+
+/// Test that `--edit RECIPE` passes the recipe's line to known editors
+#[cfg(unix)]
+#[test]
+fn recipe_line() {
+  let tmp = temptree! {
+    justfile: "foo:\n\n# bar\nbar:\n\nalias b := bar\n",
+    bin: {},
+  };
+
+  for (editor, arguments) in [
+    ("vim", "+4 JUSTFILE"),
+    ("nvim", "+4 JUSTFILE"),
+    ("code", "--goto JUSTFILE:4"),
+    ("hx", "JUSTFILE:4"),
+    ("unknown", "JUSTFILE"),
+  ] {
+    let path = tmp.path().join("bin").join(editor);
+
+    fs::write(&path, "#!/usr/bin/env sh\necho \"$@\"").unwrap();
+
+    let permissions = std::os::unix::fs::PermissionsExt::from_mode(0o700);
+    fs::set_permissions(&path, permissions).unwrap();
+
+    for recipe in ["bar", "b"] {
+      let output = Command::new(executable_path("just"))
+        .current_dir(tmp.path())
+        .args(["--edit", recipe])
+        .env("VISUAL", &path)
+        .output()
+        .unwrap();
+
+      let justfile = tmp.path().join("justfile");
+
+      assert_stdout(
+        &output,
+        &format!(
+          "{}\n",
+          arguments.replace("JUSTFILE", justfile.to_str().unwrap())
+        ),
+      );
+    }
+  }
+}
+
+/// Test that `--edit RECIPE` opens the file which a recipe was included from
+#[cfg(unix)]
+#[test]
+fn included_recipe() {
+  let tmp = temptree! {
+    justfile: "!include ./sub/included.just\n\nfoo:\n",
+    sub: {
+      "included.just": "# baz\n\nbaz:\n  echo baz\n",
+    },
+    vim: "#!/usr/bin/env sh\necho \"$@\"",
+  };
+
+  let editor = tmp.path().join("vim");
+
+  let permissions = std::os::unix::fs::PermissionsExt::from_mode(0o700);
+  fs::set_permissions(&editor, permissions).unwrap();
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .args(["--unstable", "--edit", "baz"])
+    .env("VISUAL", &editor)
+    .output()
+    .unwrap();
+
+  assert_stdout(
+    &output,
+    &format!("+3 {}\n", tmp.path().join("sub/included.just").display()),
+  );
+}
+
+#[test]
+fn unknown_recipe() {
+  Test::new()
+    .justfile("foo:")
+    .args(["--edit", "fooo"])
+    .env("VISUAL", "cat")
+    .stderr("error: Justfile does not contain recipe `fooo`.\nDid you mean `foo`?\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -0,0 +1,189 @@
+use super::*;
+
+const JUSTFILE: &str = "
+  build:
+
+  build-docs:
+
+  _private:
+
+  test:
+";
+
+#[test]
+fn bash() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--emit-aliases", "bash"])
+    .stdout(
+      "
+        alias jb='just build'
+        alias jbd='just build-docs'
+        alias jt='just test'
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn fish() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--emit-aliases", "fish"])
+    .stdout(
+      "
+        alias jb 'just build'
+        alias jbd 'just build-docs'
+        alias jt 'just test'
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn pwsh() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--emit-aliases", "pwsh"])
+    .stdout(
+      "
+        function jb { just build @args }
+        function jbd { just build-docs @args }
+        function jt { just test @args }
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn shell_is_case_insensitive() {
+  Test::new()
+    .justfile("build:")
+    .args(["--emit-aliases", "BASH"])
+    .stdout("alias jb='just build'\n")
+    .run();
+}
+
+#[test]
+fn ambiguous_initials_use_whole_name() {
+  Test::new()
+    .justfile(
+      "
+        bench:
+
+        build:
+
+        check:
+      ",
+    )
+    .args(["--emit-aliases", "bash"])
+    .stdout(
+      "
+        alias jbench='just bench'
+        alias jbuild='just build'
+        alias jc='just check'
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn colliding_aliases_are_skipped() {
+  Test::new()
+    .justfile(
+      "
+        a:
+
+        ab:
+
+        a-b:
+      ",
+    )
+    .args(["--emit-aliases", "bash"])
+    .stdout("alias ja='just a'\n")
+    .stderr(
+      "
+        warning: Skipping alias `jab` for recipe `a-b`, since it would also be the alias for recipe `ab`
+        warning: Skipping alias `jab` for recipe `ab`, since it would also be the alias for recipe `a-b`
+      ",
+    )
+    .run();
+}
+
+#[cfg(unix)]
+#[test]
+fn aliases_which_shadow_commands_are_skipped() {
+  let test = Test::new()
+    .justfile(
+      "
+        query:
+
+        test:
+      ",
+    )
+    .write("bin/jq", "#!/bin/sh\n");
+
+  let bin = test.tempdir.path().join("bin");
+
+  fs::set_permissions(
+    bin.join("jq"),
+    std::os::unix::fs::PermissionsExt::from_mode(0o755),
+  )
+  .unwrap();
+
+  test
+    .env("PATH", bin.to_str().unwrap())
+    .args(["--emit-aliases", "bash"])
+    .stdout("alias jt='just test'\n")
+    .stderr(
+      "warning: Skipping alias `jq` for recipe `query`, since it would shadow the command `jq`\n",
+    )
+    .run();
+}
+
+#[test]
+fn warnings_are_not_printed_when_quiet() {
+  Test::new()
+    .justfile(
+      "
+        a:
+
+        ab:
+
+        a-b:
+      ",
+    )
+    .args(["--quiet", "--emit-aliases", "bash"])
+    .stdout("alias ja='just a'\n")
+    .run();
+}
+
+#[test]
+fn unsorted() {
+  Test::new()
+    .justfile(
+      "
+        test:
+
+        build:
+      ",
+    )
+    .args(["--emit-aliases", "bash", "--unsorted"])
+    .stdout(
+      "
+        alias jt='just test'
+        alias jb='just build'
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn invalid_shell() {
+  Test::new()
+    .justfile("build:")
+    .args(["--emit-aliases", "tcsh"])
+    .stderr_regex("(?s)error: 'tcsh' isn't a valid value for '--emit-aliases <SHELL>'.*")
+    .status(EXIT_FAILURE)
+    .run();
+}
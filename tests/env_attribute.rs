@@ -0,0 +1,179 @@
+use super::*;
+
+#[test]
+fn linewise() {
+  Test::new()
+    .justfile(
+      "
+        level := 'debug'
+
+        [env('RUST_LOG', level + ',hyper=info')]
+        [env('TARGET', target)]
+        test target:
+          @echo $RUST_LOG $TARGET
+      ",
+    )
+    .args(["test", "wasm"])
+    .stdout("debug,hyper=info wasm\n")
+    .run();
+}
+
+#[test]
+fn shebang() {
+  Test::new()
+    .justfile(
+      "
+        [env('GREETING', `echo hello`)]
+        foo:
+          #!/usr/bin/env bash
+          echo $GREETING
+      ",
+    )
+    .stdout("hello\n")
+    .run();
+}
+
+#[test]
+fn other_recipes_are_unaffected() {
+  Test::new()
+    .justfile(
+      "
+        [env('FOO', 'foo')]
+        foo: bar
+          @echo $FOO
+
+        bar:
+          @echo ${FOO:-unset}
+      ",
+    )
+    .stdout("unset\nfoo\n")
+    .run();
+}
+
+#[test]
+fn overrides_exports() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-load
+
+        export FOO := 'exported'
+
+        [env('FOO', 'attribute')]
+        [env('DOTENV_KEY', 'attribute')]
+        foo:
+          @echo $FOO $DOTENV_KEY
+      ",
+    )
+    .stdout("attribute attribute\n")
+    .run();
+}
+
+#[test]
+fn group_declaration() {
+  Test::new()
+    .justfile(
+      "
+        [group('deploy'): env('STAGE', 'prod'), env('REGION', 'eu')]
+
+        [group('deploy')]
+        [env('REGION', 'us')]
+        deploy:
+          @echo $STAGE $REGION
+      ",
+    )
+    .stdout("prod us\n")
+    .run();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile(
+      "
+        [env('FOO', 'a' + x)]
+        foo x:
+      ",
+    )
+    .arg("--dump")
+    .stdout("[env('FOO', 'a' + x)]\nfoo x:\n")
+    .run();
+}
+
+#[test]
+fn invalid_name() {
+  Test::new()
+    .justfile(
+      "
+        [env('FOO-BAR', 'x')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: `FOO-BAR` cannot be exported, since it is not a valid environment variable name, which may only contain letters, digits, and underscores, and may not start with a digit
+          |
+        2 | foo:
+          | ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn duplicate_name() {
+  Test::new()
+    .justfile(
+      "
+        [env('FOO', 'a')]
+        [env('FOO', 'b')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Recipe `foo` sets environment variable `FOO` more than once
+          |
+        3 | foo:
+          | ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn undefined_variable() {
+  Test::new()
+    .justfile(
+      "
+        [env('FOO', bar)]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Variable `bar` not defined
+          |
+        1 | [env('FOO', bar)]
+          |             ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn diff_env() {
+  Test::new()
+    .justfile(
+      "
+        [env('JUST_TEST_ENV', 'foo')]
+        foo:
+      ",
+    )
+    .arg("--diff-env")
+    .stderr("Environment for recipe `foo`:\n    + JUST_TEST_ENV=foo\n")
+    .run();
+}
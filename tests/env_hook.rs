@@ -0,0 +1,117 @@
+use super::*;
+
+#[test]
+fn key_value_lines() {
+  Test::new()
+    .justfile(
+      "
+        set env-hook := ['sh', '-c', 'echo FOO=bar; echo export BAZ=qux']
+
+        foo:
+          @echo $FOO $BAZ
+      ",
+    )
+    .stdout("bar qux\n")
+    .run();
+}
+
+#[test]
+fn json() {
+  Test::new()
+    .justfile(
+      r#"
+        set env-hook := ['sh', '-c', 'echo "{\"FOO\": \"bar\", \"BAZ\": null}"']
+
+        foo:
+          @echo $FOO
+      "#,
+    )
+    .stdout("bar\n")
+    .run();
+}
+
+#[test]
+fn available_in_backticks_and_env_var() {
+  Test::new()
+    .justfile(
+      "
+        set env-hook := ['sh', '-c', 'echo FOO=bar']
+
+        x := `echo $FOO`
+        y := env_var('FOO')
+
+        foo:
+          @echo {{x}} {{y}}
+      ",
+    )
+    .stdout("bar bar\n")
+    .run();
+}
+
+#[test]
+fn overrides_dotenv() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-load
+        set env-hook := ['sh', '-c', 'echo FOO=hook']
+
+        foo:
+          @echo $FOO
+      ",
+    )
+    .write(".env", "FOO=dotenv")
+    .stdout("hook\n")
+    .run();
+}
+
+#[test]
+fn runs_in_justfile_directory() {
+  Test::new()
+    .justfile(
+      "
+        set env-hook := ['sh', '-c', 'cat env']
+
+        foo:
+          @echo $FOO
+      ",
+    )
+    .write("env", "FOO=bar")
+    .write("sub/file", "")
+    .current_dir("sub")
+    .stdout("bar\n")
+    .run();
+}
+
+#[test]
+fn hook_failure() {
+  Test::new()
+    .justfile(
+      "
+        set env-hook := ['sh', '-c', 'exit 3']
+
+        foo:
+      ",
+    )
+    .stderr("error: Env hook `['sh', '-c', 'exit 3']` failed with exit code 3\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn invalid_output() {
+  Test::new()
+    .justfile(
+      "
+        set env-hook := ['sh', '-c', 'echo bar']
+
+        foo:
+      ",
+    )
+    .stderr(
+      "error: Failed to parse output of env hook `['sh', '-c', 'echo bar']`: expected `KEY=VALUE` \
+       but found `bar`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
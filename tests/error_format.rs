@@ -0,0 +1,136 @@
+use super::*;
+
+#[test]
+fn human() {
+  Test::new()
+    .justfile("foo:\n  @exit 42")
+    .args(["--error-format", "human", "foo"])
+    .stderr(
+      "
+        error: Recipe `foo` failed on line 2 with exit code 42
+          |
+        2 |   @exit 42
+          |   ^^^^^^^^
+      ",
+    )
+    .status(42)
+    .run();
+}
+
+#[test]
+fn recipe_failure() {
+  Test::new()
+    .justfile("foo:\n  @exit 42")
+    .args(["--error-format", "json", "foo"])
+    .stderr(
+      r#"{"kind":"code","message":"Recipe `foo` failed on line 2 with exit code 42","recipe":"foo","path":"justfile","line":2,"column":3,"exit-code":42}
+"#,
+    )
+    .status(42)
+    .run();
+}
+
+#[test]
+fn exit_code_follows_exit_status() {
+  Test::new()
+    .justfile("foo:\n  @exit 42")
+    .args(["--error-format", "json", "--exit-status", "categorized", "foo"])
+    .stderr(
+      r#"{"kind":"code","message":"Recipe `foo` failed on line 2 with exit code 42","recipe":"foo","path":"justfile","line":2,"column":3,"exit-code":2}
+"#,
+    )
+    .status(2)
+    .run();
+}
+
+#[test]
+fn unknown_recipe() {
+  Test::new()
+    .justfile("foo:")
+    .args(["--error-format", "json", "bar"])
+    .stderr(
+      r#"{"kind":"unknown-recipes","message":"Justfile does not contain recipe `bar`.","recipe":null,"path":null,"line":null,"column":null,"exit-code":1}
+"#,
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn compile_error() {
+  Test::new()
+    .justfile("foo: bar")
+    .args(["--error-format", "json"])
+    .stderr(
+      r#"{"kind":"unknown-dependency","message":"Recipe `foo` has unknown dependency `bar`","recipe":null,"path":"justfile","line":1,"column":6,"exit-code":1}
+"#,
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn multiple_compile_errors() {
+  Test::new()
+    .justfile("foo bar\nx :=\n")
+    .args(["--error-format", "json"])
+    .stderr(
+      r#"{"kind":"unexpected-token","message":"Expected '*', ':', '$', '=', identifier, or '+', but found end of line","recipe":null,"path":"justfile","line":1,"column":8,"exit-code":1}
+{"kind":"unexpected-token","message":"Expected backtick, identifier, '(', '/', or string, but found end of line","recipe":null,"path":"justfile","line":2,"column":5,"exit-code":1}
+"#,
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn runtime_error_with_context() {
+  Test::new()
+    .justfile("x := `exit 3`\nfoo:\n  echo {{x}}")
+    .args(["--error-format", "json", "foo"])
+    .stderr(
+      r#"{"kind":"backtick","message":"Backtick failed with exit code 3","recipe":null,"path":"justfile","line":1,"column":6,"exit-code":3}
+"#,
+    )
+    .status(3)
+    .run();
+}
+
+#[test]
+fn compile_error_in_included_file() {
+  Test::new()
+    .write("ci.just", "# lint\nlint: setup\n")
+    .justfile("!include ci.just\n\nfoo:")
+    .args(["--unstable", "--error-format", "json"])
+    .test_round_trip(false)
+    .stderr(
+      r#"{"kind":"unknown-dependency","message":"Recipe `lint` has unknown dependency `setup`","recipe":null,"path":"ci.just","line":2,"column":7,"exit-code":1}
+"#,
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn recipe_failure_in_included_file() {
+  Test::new()
+    .write("ci.just", "# lint\nlint:\n  @exit 42\n")
+    .justfile("!include ci.just\n\nfoo:")
+    .args(["--unstable", "--error-format", "json", "lint"])
+    .test_round_trip(false)
+    .stderr(
+      r#"{"kind":"code","message":"Recipe `lint` failed on line 3 with exit code 42","recipe":"lint","path":"ci.just","line":3,"column":3,"exit-code":42}
+"#,
+    )
+    .status(42)
+    .run();
+}
+
+#[test]
+fn quiet() {
+  Test::new()
+    .justfile("foo:\n  @exit 42")
+    .args(["--error-format", "json", "--quiet", "foo"])
+    .status(42)
+    .run();
+}
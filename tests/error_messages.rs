@@ -36,6 +36,86 @@ test! {
   status: EXIT_FAILURE,
 }
 
+test! {
+  name: multiple_parse_errors,
+  justfile: "
+    foo bar
+      echo foo
+
+    x :=
+
+    baz:
+      echo baz
+  ",
+  stderr: "
+    error: Expected '*', ':', '$', '=', identifier, or '+', but found end of line
+      |
+    1 | foo bar
+      |        ^
+
+    error: Expected backtick, identifier, '(', '/', or string, but found end of line
+      |
+    4 | x :=
+      |     ^
+
+    error: Found 2 errors:
+      justfile:1:8  Expected '*', ':', '$', '=', identifier, or '+', but found end of line
+      justfile:4:5  Expected backtick, identifier, '(', '/', or string, but found end of line
+  ",
+  status: EXIT_FAILURE,
+}
+
+#[test]
+fn multiple_errors_summary_is_aligned() {
+  Test::new()
+    .justfile("foo bar\n\n\n\n\n\n\n\n\n\nx :=\n")
+    .stderr(
+      "
+      error: Expected '*', ':', '$', '=', identifier, or '+', but found end of line
+        |
+      1 | foo bar
+        |        ^
+
+      error: Expected backtick, identifier, '(', '/', or string, but found end of line
+         |
+      11 | x :=
+         |     ^
+
+      error: Found 2 errors:
+        justfile:1:8   Expected '*', ':', '$', '=', identifier, or '+', but found end of line
+        justfile:11:5  Expected backtick, identifier, '(', '/', or string, but found end of line
+    ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn multiple_errors_summary_uses_included_path() {
+  Test::new()
+    .write("import.just", "x :=\n")
+    .justfile("import 'import.just'\n\nfoo bar\n")
+    .stderr(
+      "
+      error: Expected backtick, identifier, '(', '/', or string, but found end of line
+        |
+      1 | x :=
+        |     ^
+
+      error: Expected '*', ':', '$', '=', identifier, or '+', but found end of line
+        |
+      3 | foo bar
+        |        ^
+
+      error: Found 2 errors:
+        import.just:1:5  Expected backtick, identifier, '(', '/', or string, but found end of line
+        justfile:3:8     Expected '*', ':', '$', '=', identifier, or '+', but found end of line
+    ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
 #[test]
 fn argument_count_mismatch() {
   Test::new()
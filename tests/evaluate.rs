@@ -38,10 +38,50 @@ test! {
     c := 'z'
   ",
   args:   ("--evaluate", "a", "c"),
-  stderr: "error: `--evaluate` used with unexpected argument: `c`\n",
+  stdout: "a=x\nc=z\n",
+}
+
+test! {
+  name:     evaluate_multiple_unknown_variable,
+  justfile: "
+    a := 'x'
+  ",
+  args:   ("--evaluate", "a", "zzz"),
+  stderr: "error: Justfile does not contain variable `zzz`.\n",
   status: EXIT_FAILURE,
 }
 
+test! {
+  name:     evaluate_json,
+  justfile: "
+    a := 'x'
+    b := 'y'
+  ",
+  args:   ("--evaluate", "--dump-format", "json"),
+  stdout: "{\"a\":\"x\",\"b\":\"y\"}\n",
+}
+
+test! {
+  name:     evaluate_multiple_json,
+  justfile: "
+    a := 'x'
+    b := \"y\\nz\"
+    c := 'w'
+  ",
+  args:   ("--evaluate", "--dump-format", "json", "c", "b"),
+  stdout: "{\"b\":\"y\\nz\",\"c\":\"w\"}\n",
+}
+
+test! {
+  name:     evaluate_single_json,
+  justfile: "
+    a := 'x'
+    b := 'y'
+  ",
+  args:   ("--evaluate", "--dump-format", "json", "a"),
+  stdout: "{\"a\":\"x\"}\n",
+}
+
 test! {
   name:     evaluate_single_free,
   justfile: "
@@ -0,0 +1,165 @@
+use super::*;
+
+const JUSTFILE: &str = "
+  fail:
+    @exit 42
+
+  ok:
+";
+
+#[test]
+fn passthrough_is_the_default() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .arg("fail")
+    .stderr(
+      "
+        error: Recipe `fail` failed on line 2 with exit code 42
+          |
+        2 |   @exit 42
+          |   ^^^^^^^^
+      ",
+    )
+    .status(42)
+    .run();
+}
+
+#[test]
+fn passthrough() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--exit-status", "passthrough", "fail"])
+    .stderr(
+      "
+        error: Recipe `fail` failed on line 2 with exit code 42
+          |
+        2 |   @exit 42
+          |   ^^^^^^^^
+      ",
+    )
+    .status(42)
+    .run();
+}
+
+#[test]
+fn categorized_recipe_failed() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--exit-status", "categorized", "fail"])
+    .stderr(
+      "
+        error: Recipe `fail` failed on line 2 with exit code 42
+          |
+        2 |   @exit 42
+          |   ^^^^^^^^
+      ",
+    )
+    .status(2)
+    .run();
+}
+
+#[test]
+fn categorized_keep_going() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--exit-status", "categorized", "--keep-going", "fail", "ok"])
+    .stderr(
+      "
+        error: Recipe `fail` failed on line 2 with exit code 42
+          |
+        2 |   @exit 42
+          |   ^^^^^^^^
+        error: Recipe `fail` failed
+      ",
+    )
+    .status(2)
+    .run();
+}
+
+#[test]
+fn categorized_command_failed() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--exit-status", "categorized", "--command", "false"])
+    .stderr("error: Command `false` failed: exit status: 1\n")
+    .status(2)
+    .run();
+}
+
+#[test]
+fn categorized_unknown_recipe() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--exit-status", "categorized", "bar"])
+    .stderr("error: Justfile does not contain recipe `bar`.\n")
+    .status(3)
+    .run();
+}
+
+#[test]
+fn categorized_other_error() {
+  Test::new()
+    .justfile("foo: bar")
+    .args(["--exit-status", "categorized", "bar"])
+    .stderr(
+      "
+        error: Recipe `foo` has unknown dependency `bar`
+          |
+        1 | foo: bar
+          |      ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn categorized_success() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--exit-status", "categorized", "ok"])
+    .run();
+}
+
+#[test]
+fn categorized_backtick_failed() {
+  Test::new()
+    .justfile("foo := `exit 42`\n\nok:\n")
+    .args(["--exit-status", "categorized", "ok"])
+    .stderr(
+      "
+        error: Backtick failed with exit code 42
+          |
+        1 | foo := `exit 42`
+          |        ^^^^^^^^^
+      ",
+    )
+    .status(2)
+    .run();
+}
+
+#[test]
+fn categorized_shell_not_found() {
+  Test::new()
+    .justfile("set shell := ['nonexistent-shell']\n\nfoo:\n  echo foo\n")
+    .shell(false)
+    .args(["--exit-status", "categorized", "foo"])
+    .stderr_regex(
+      "(?s)echo foo\nerror: Recipe `foo` could not be run because just could not find the shell: .*",
+    )
+    .status(2)
+    .run();
+}
+
+#[cfg(unix)]
+#[test]
+fn categorized_shebang_interpreter_not_found() {
+  Test::new()
+    .justfile("foo:\n  #!/nonexistent-interpreter\n  echo foo\n")
+    .args(["--exit-status", "categorized", "foo"])
+    .stderr_regex(
+      "error: Recipe `foo` with shebang `#!/nonexistent-interpreter` execution error: .*\n",
+    )
+    .status(2)
+    .run();
+}
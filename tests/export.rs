@@ -1,3 +1,5 @@
+use super::*;
+
 test! {
   name:     success,
   justfile: r#"
@@ -175,3 +177,175 @@ test! {
   stdout: "undefined\n",
   stderr: "echo $B\n",
 }
+
+#[test]
+fn invalid_name_is_deprecated() {
+  Test::new()
+    .justfile(
+      "
+        export foo-bar := 'a'
+
+        recipe $baz-qux='b':
+          @env | grep -E '^(foo-bar|baz-qux)=' | sort
+      ",
+    )
+    .stdout("baz-qux=b\nfoo-bar=a\n")
+    .stderr(
+      "
+        warning: Exporting `foo-bar` is deprecated and will be an error in a future version, \
+        since it is not a valid environment variable name, which may only contain letters, \
+        digits, and underscores, and may not start with a digit
+          |
+        1 | export foo-bar := 'a'
+          |        ^^^^^^^
+        warning: Exporting `baz-qux` is deprecated and will be an error in a future version, \
+        since it is not a valid environment variable name, which may only contain letters, \
+        digits, and underscores, and may not start with a digit
+          |
+        3 | recipe $baz-qux='b':
+          |         ^^^^^^^
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn setting_skips_invalid_names() {
+  Test::new()
+    .justfile(
+      "
+        set export
+
+        foo-bar := 'a'
+        baz := 'b'
+
+        recipe $qux='c' quux-corge='d':
+          @env | grep -E '^(foo-bar|baz|qux|quux-corge)=' | sort
+      ",
+    )
+    .stdout("baz=b\nqux=c\n")
+    .stderr(
+      "
+        warning: `foo-bar` is not exported by `set export`, since it is not a valid environment \
+        variable name
+          |
+        3 | foo-bar := 'a'
+          | ^^^^^^^
+        warning: `quux-corge` is not exported by `set export`, since it is not a valid \
+        environment variable name
+          |
+        6 | recipe $qux='c' quux-corge='d':
+          |                 ^^^^^^^^^^
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn warnings_are_not_printed_when_quiet() {
+  Test::new()
+    .justfile(
+      "
+        set export
+
+        foo-bar := 'a'
+
+        recipe:
+      ",
+    )
+    .arg("--quiet")
+    .run();
+}
+
+test! {
+  name:     prefix,
+  justfile: "
+    set export-prefix := 'JUST_'
+
+    export version := '1.0'
+
+    recipe $target='x86':
+      echo $JUST_VERSION $JUST_TARGET ${version-unset}
+  ",
+  stdout:   "1.0 x86 unset\n",
+  stderr:   "echo $JUST_VERSION $JUST_TARGET ${version-unset}\n",
+}
+
+test! {
+  name:     prefix_with_setting,
+  justfile: "
+    set export
+    set export-prefix := 'JUST_'
+
+    name := 'foo'
+
+    recipe $target='bar':
+      echo $JUST_NAME $JUST_TARGET ${name-unset}
+  ",
+  stdout:   "foo bar unset\n",
+  stderr:   "echo $JUST_NAME $JUST_TARGET ${name-unset}\n",
+}
+
+test! {
+  name:     prefix_satisfies_required_env,
+  justfile: "
+    set export-prefix := 'JUST_'
+    set required-env := ['JUST_TARGET']
+
+    recipe $target:
+      echo $JUST_TARGET
+  ",
+  args:     ("recipe", "foo"),
+  stdout:   "foo\n",
+  stderr:   "echo $JUST_TARGET\n",
+}
+
+test! {
+  name:     invalid_prefix,
+  justfile: "
+    set export-prefix := 'JUST-'
+  ",
+  stderr:   "
+    error: Export prefix `JUST-` is not a valid environment variable name, which may only \
+    contain letters, digits, and underscores, and may not start with a digit
+      |
+    1 | set export-prefix := 'JUST-'
+      |                      ^^^^^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     prefix_collision,
+  justfile: "
+    set export-prefix := 'JUST_'
+
+    export foo := 'a'
+    export FOO := 'b'
+  ",
+  stderr:   "
+    error: `foo` and `FOO` are both exported as `JUST_FOO`
+      |
+    4 | export FOO := 'b'
+      |        ^^^
+  ",
+  status:   EXIT_FAILURE,
+}
+
+test! {
+  name:     prefix_parameter_collision,
+  justfile: "
+    set export
+    set export-prefix := 'JUST_'
+
+    recipe foo FOO:
+  ",
+  args:     ("recipe", "a", "b"),
+  stderr:   "
+    error: `foo` and `FOO` are both exported as `JUST_FOO`
+      |
+    4 | recipe foo FOO:
+      |            ^^^
+  ",
+  status:   EXIT_FAILURE,
+}
@@ -0,0 +1,366 @@
+use super::*;
+
+#[test]
+fn linewise() {
+  Test::new()
+    .justfile(
+      "
+        export GREETING := 'hello'
+        version := '1.0'
+
+        build target='debug':
+          @echo building {{target}} {{version}}
+          -false
+          echo \"$GREETING\"
+      ",
+    )
+    .args(["--export-script", "build", "release"])
+    .stdout(
+      "
+        #!/usr/bin/env bash
+        set -eu
+        export GREETING=hello
+
+        # build release
+        (
+        echo building release 1.0
+        { false
+        } || true
+        echo \"$GREETING\"
+        )
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn default_recipe() {
+  Test::new()
+    .justfile(
+      "
+        default:
+          echo hello
+      ",
+    )
+    .arg("--export-script")
+    .stdout(
+      "
+        #!/usr/bin/env bash
+        set -eu
+
+        # default
+        (
+        echo hello
+        )
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn dependencies_are_omitted() {
+  Test::new()
+    .justfile(
+      "
+        build: setup && cleanup
+          echo build
+
+        setup:
+          echo setup
+
+        cleanup:
+          echo cleanup
+      ",
+    )
+    .args(["--export-script", "build"])
+    .stdout(
+      "
+        #!/usr/bin/env bash
+        set -eu
+
+        # build
+        # Dependencies omitted: setup, cleanup
+        (
+        echo build
+        )
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn inline_dependencies() {
+  Test::new()
+    .justfile(
+      "
+        build: (setup 'fast') && cleanup
+          echo build
+
+        setup mode:
+          echo setup {{mode}}
+
+        cleanup:
+          echo cleanup
+      ",
+    )
+    .args(["--inline-dependencies", "--export-script", "build"])
+    .stdout(
+      "
+        #!/usr/bin/env bash
+        set -eu
+
+        # setup fast
+        (
+        echo setup fast
+        )
+
+        # build
+        (
+        echo build
+        )
+
+        # cleanup
+        (
+        echo cleanup
+        )
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn exported_parameters_and_working_directory() {
+  Test::new()
+    .justfile(
+      "
+        [working-directory('sub dir')]
+        build $MODE:
+          echo $MODE
+      ",
+    )
+    .args(["--export-script", "build", "it's"])
+    .stdout(
+      "
+        #!/usr/bin/env bash
+        set -eu
+
+        # build 'it'\\''s'
+        (
+        cd 'sub dir'
+        export MODE='it'\\''s'
+        echo $MODE
+        )
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn env_attribute() {
+  Test::new()
+    .justfile(
+      "
+        [env('TARGET', 'x86_64-' + os)]
+        build os:
+          cargo build --target $TARGET
+      ",
+    )
+    .args(["--export-script", "build", "linux"])
+    .stdout(
+      "
+        #!/usr/bin/env bash
+        set -eu
+
+        # build linux
+        (
+        export TARGET=x86_64-linux
+        cargo build --target $TARGET
+        )
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn shebang_recipe() {
+  let output = Test::new()
+    .justfile(
+      "
+        greet name:
+          #!/usr/bin/env bash
+          echo 'hello {{name}}'
+      ",
+    )
+    .args(["--output", "greet.sh", "--export-script", "greet", "world"])
+    .run();
+
+  let path = output.tempdir.path().join("greet.sh");
+
+  assert_eq!(
+    fs::read_to_string(&path).unwrap(),
+    unindent(
+      "
+        #!/usr/bin/env bash
+        set -eu
+
+        # greet world
+        (
+        script=\"$(mktemp)\"
+        trap 'rm -f \"$script\"' EXIT
+        cat > \"$script\" <<'JUST_SCRIPT'
+        #!/usr/bin/env bash
+        echo 'hello world'
+        JUST_SCRIPT
+        chmod +x \"$script\"
+        \"$script\"
+        )
+      ",
+    ),
+  );
+
+  let script = Command::new(&path)
+    .current_dir(output.tempdir.path())
+    .output()
+    .unwrap();
+
+  assert!(script.status.success());
+  assert_eq!(str::from_utf8(&script.stdout).unwrap(), "hello world\n");
+}
+
+#[test]
+fn script_attribute() {
+  Test::new()
+    .justfile(
+      "
+        set positional-arguments
+
+        [script('bash', '-eu')]
+        build *args:
+          echo \"$@\"
+      ",
+    )
+    .args(["--export-script", "build", "a", "b"])
+    .stdout(
+      "
+        #!/usr/bin/env bash
+        set -eu
+
+        # build a b
+        (
+        script=\"$(mktemp)\"
+        trap 'rm -f \"$script\"' EXIT
+        cat > \"$script\" <<'JUST_SCRIPT'
+        echo \"$@\"
+        JUST_SCRIPT
+        bash -eu \"$script\" a b
+        )
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn positional_arguments() {
+  Test::new()
+    .justfile(
+      "
+        set positional-arguments
+
+        build target:
+          echo $1
+      ",
+    )
+    .args(["--export-script", "build", "release"])
+    .stdout(
+      "
+        #!/usr/bin/env bash
+        set -eu
+
+        # build release
+        (
+        set -- release
+        echo $1
+        )
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn shell_attribute() {
+  Test::new()
+    .justfile(
+      "
+        [shell('python3', '-c')]
+        build:
+          print('hello')
+      ",
+    )
+    .shell(false)
+    .args(["--export-script", "build"])
+    .stdout(
+      "
+        #!/usr/bin/env sh
+        set -eu
+
+        # build
+        (
+        python3 -c 'print('\\''hello'\\'')'
+        )
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn script_runs_recipe() {
+  let output = Test::new()
+    .justfile(
+      "
+        export NAME := 'world'
+
+        build: setup
+          echo \"hello $NAME\" >> out.txt
+
+        setup:
+          echo setup > out.txt
+      ",
+    )
+    .args([
+      "--inline-dependencies",
+      "--output",
+      "build.sh",
+      "--export-script",
+      "build",
+    ])
+    .run();
+
+  let status = Command::new(output.tempdir.path().join("build.sh"))
+    .current_dir(output.tempdir.path())
+    .status()
+    .unwrap();
+
+  assert!(status.success());
+  assert_eq!(
+    fs::read_to_string(output.tempdir.path().join("out.txt")).unwrap(),
+    "setup\nhello world\n",
+  );
+}
+
+#[test]
+fn unsupported_shell() {
+  Test::new()
+    .justfile(
+      "
+        set shell := ['python3', '-c']
+
+        build:
+          print('hello')
+      ",
+    )
+    .shell(false)
+    .args(["--export-script", "build"])
+    .stderr("error: Recipes run with `python3` can't be exported, since it isn't a POSIX shell\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
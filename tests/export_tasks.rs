@@ -0,0 +1,130 @@
+use super::*;
+
+#[test]
+fn vscode() {
+  Test::new()
+    .justfile(
+      "
+        # build the project
+        [choices('profile', 'dev', 'release')]
+        [param-doc('target', 'the cargo target triple')]
+        build target profile='dev':
+
+        test:
+
+        _private:
+      ",
+    )
+    .args(["--export-tasks", "vscode"])
+    .stdout(
+      r#"
+        {
+          "inputs": [
+            {
+              "description": "the cargo target triple",
+              "id": "build.target",
+              "type": "promptString"
+            },
+            {
+              "default": "dev",
+              "description": "profile",
+              "id": "build.profile",
+              "options": [
+                "dev",
+                "release"
+              ],
+              "type": "pickString"
+            }
+          ],
+          "tasks": [
+            {
+              "args": [
+                "build",
+                "${input:build.target}",
+                "${input:build.profile}"
+              ],
+              "command": "just",
+              "detail": "build the project",
+              "label": "just build",
+              "problemMatcher": [],
+              "type": "shell"
+            },
+            {
+              "args": [
+                "test"
+              ],
+              "command": "just",
+              "label": "just test",
+              "problemMatcher": [],
+              "type": "shell"
+            }
+          ],
+          "version": "2.0.0"
+        }
+      "#,
+    )
+    .run();
+}
+
+#[test]
+fn vscode_skips_parameters_with_computed_defaults() {
+  Test::new()
+    .justfile(
+      "
+        build target=arch() profile='dev':
+      ",
+    )
+    .args(["--export-tasks", "vscode"])
+    .stdout(
+      r#"
+        {
+          "tasks": [
+            {
+              "args": [
+                "build"
+              ],
+              "command": "just",
+              "label": "just build",
+              "problemMatcher": [],
+              "type": "shell"
+            }
+          ],
+          "version": "2.0.0"
+        }
+      "#,
+    )
+    .run();
+}
+
+#[test]
+fn jetbrains() {
+  Test::new()
+    .justfile(
+      "
+        [param-doc('target', 'the <target>')]
+        build target:
+      ",
+    )
+    .args(["--export-tasks", "jetbrains"])
+    .stdout(
+      r#"        <component name="ProjectRunConfigurationManager">
+          <configuration default="false" name="just build" type="ShConfigurationType">
+            <option name="SCRIPT_TEXT" value="just build '$Prompt:the &lt;target&gt;$'" />
+            <option name="INDEPENDENT_SCRIPT_PATH" value="true" />
+            <option name="SCRIPT_PATH" value="" />
+            <option name="SCRIPT_OPTIONS" value="" />
+            <option name="INDEPENDENT_SCRIPT_WORKING_DIRECTORY" value="true" />
+            <option name="SCRIPT_WORKING_DIRECTORY" value="$PROJECT_DIR$" />
+            <option name="INDEPENDENT_INTERPRETER_PATH" value="true" />
+            <option name="INTERPRETER_PATH" value="" />
+            <option name="INTERPRETER_OPTIONS" value="" />
+            <option name="EXECUTE_IN_TERMINAL" value="true" />
+            <option name="EXECUTE_SCRIPT_FILE" value="false" />
+            <envs />
+            <method v="2" />
+          </configuration>
+        </component>
+      "#,
+    )
+    .run();
+}
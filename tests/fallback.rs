@@ -61,6 +61,7 @@ fn fallback_from_subdir_verbose_message() {
       Trying ../justfile
       ===> Running recipe `bar`...
       echo bar
+      Recipes were run by fallback justfile ../justfile
       ",
     ))
     .stdout("bar\n")
@@ -97,6 +98,31 @@ fn runs_recipe_in_parent_if_not_found_in_current() {
     .run();
 }
 
+#[test]
+fn fallback_recipe_runs_in_its_justfile_directory() {
+  Test::new()
+    .tree(tree! {
+      bar: {
+        justfile: "
+          set fallback
+
+          baz:
+        "
+      }
+    })
+    .justfile(
+      "
+      foo:
+        @basename \"$PWD\"
+        @basename {{ invocation_directory() }}
+    ",
+    )
+    .args(["foo"])
+    .current_dir("bar")
+    .stdout_regex("just-test-tempdir.*\nbar\n")
+    .run();
+}
+
 #[test]
 fn setting_accepts_value() {
   Test::new()
@@ -361,3 +387,131 @@ fn stop_fallback_when_fallback_is_false() {
     .status(EXIT_FAILURE)
     .run();
 }
+
+#[test]
+fn no_fallback_flag_disables_fallback() {
+  Test::new()
+    .tree(tree! {
+      sub: {
+        justfile: "
+          set fallback
+
+          bar:
+        "
+      }
+    })
+    .justfile("foo:\n echo foo")
+    .args(["--no-fallback", "foo"])
+    .current_dir("sub")
+    .stderr("error: Justfile does not contain recipe `foo`.\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn fallback_justfiles_are_searched_in_order() {
+  Test::new()
+    .tree(tree! {
+      a: {
+        justfile: "
+          bar:
+            @echo a
+        "
+      },
+      b: {
+        justfile: "
+          foo:
+            @echo b
+        "
+      },
+      sub: {
+        justfile: "
+          set fallback-justfiles := ['../a/justfile', '../b/justfile']
+
+          baz:
+        "
+      }
+    })
+    .justfile("foo:\n @echo root")
+    .args(["foo"])
+    .current_dir("sub")
+    .stdout("b\n")
+    .run();
+}
+
+#[test]
+fn fallback_justfiles_replace_parent_search() {
+  Test::new()
+    .tree(tree! {
+      a: {
+        justfile: "
+          bar:
+        "
+      },
+      sub: {
+        justfile: "
+          set fallback-justfiles := ['../a/justfile']
+
+          baz:
+        "
+      }
+    })
+    .justfile("foo:\n @echo root")
+    .args(["foo"])
+    .current_dir("sub")
+    .stderr("error: Justfile does not contain recipe `foo`.\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn fallback_justfiles_verbose_message() {
+  Test::new()
+    .tree(tree! {
+      a: {
+        justfile: "
+          bar:
+        "
+      },
+      sub: {
+        justfile: "
+          set fallback-justfiles := ['../justfile', '../a/justfile']
+
+          baz:
+        "
+      }
+    })
+    .justfile("bar:\n @echo root")
+    .args(["--verbose", "bar"])
+    .current_dir("sub")
+    .stderr(path(
+      "
+      Trying ../justfile
+      ===> Running recipe `bar`...
+      echo root
+      Recipes were run by fallback justfile ../justfile
+      ",
+    ))
+    .stdout("root\n")
+    .run();
+}
+
+#[test]
+fn no_fallback_flag_disables_fallback_justfiles() {
+  Test::new()
+    .tree(tree! {
+      sub: {
+        justfile: "
+          set fallback-justfiles := ['../justfile']
+
+          bar:
+        "
+      }
+    })
+    .justfile("foo:\n echo foo")
+    .args(["--no-fallback", "foo"])
+    .current_dir("sub")
+    .stderr("error: Justfile does not contain recipe `foo`.\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -46,6 +46,9 @@ test! {
   justfile: "x:=``\n",
   args: ("--unstable", "--fmt", "--check"),
   stdout: "
+    --- justfile
+    +++ justfile
+    @@ -1 +1 @@
     -x:=``
     +x := ``
   ",
@@ -68,6 +71,9 @@ test! {
   justfile: "x:=``\n",
   args: ("--unstable", "--fmt", "--check", "--color", "always"),
   stdout: "
+    \u{1b}[31m--- justfile\u{1b}[0m
+    \u{1b}[32m+++ justfile\u{1b}[0m
+    \u{1b}[35m@@ -1 +1 @@\u{1b}[0m
     \u{1b}[31m-x:=``
     \u{1b}[0m\u{1b}[32m+x := ``
     \u{1b}[0m",
@@ -129,6 +135,239 @@ fn write_error() {
   );
 }
 
+#[test]
+fn stdout() {
+  let tempdir = Test::new()
+    .justfile("x    :=    'hello'   ")
+    .args(["--fmt", "--unstable", "--stdout"])
+    .stdout("x := 'hello'\n")
+    .run()
+    .tempdir;
+
+  assert_eq!(
+    fs::read_to_string(tempdir.path().join("justfile")).unwrap(),
+    "x    :=    'hello'   "
+  );
+}
+
+#[test]
+fn stdout_requires_fmt() {
+  Test::new()
+    .arg("--stdout")
+    .stderr_regex("error: The following required arguments were not provided:\n    --fmt\n(.|\\n)+")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn stdout_conflicts_with_check() {
+  Test::new()
+    .args(["--fmt", "--unstable", "--check", "--stdout"])
+    .stderr_regex("error: The argument '--check' cannot be used with '--stdout'(.|\\n)+")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn crlf_line_endings_are_preserved() {
+  let tempdir = temptree! {
+    justfile: "x    :=    'hello'\r\nfoo:\r\n  echo {{x}}\r\n",
+  };
+
+  let tempdir = Test::with_tempdir(tempdir)
+    .no_justfile()
+    .args(["--fmt", "--unstable"])
+    .stderr_regex("Wrote justfile to `.*`\n")
+    .run()
+    .tempdir;
+
+  assert_eq!(
+    fs::read_to_string(tempdir.path().join("justfile")).unwrap(),
+    "x := 'hello'\r\n\r\nfoo:\r\n    echo {{ x }}\r\n"
+  );
+}
+
+#[test]
+fn check_diff_context() {
+  Test::new()
+    .justfile(
+      "
+        a := '1'
+        b := '2'
+        c := '3'
+        d := '4'
+        e := '5'
+        f:='6'
+
+        foo:
+            echo foo
+      ",
+    )
+    .args(["--fmt", "--unstable", "--check"])
+    .stdout(
+      "--- justfile
++++ justfile
+@@ -3,7 +3,7 @@
+ c := '3'
+ d := '4'
+ e := '5'
+-f:='6'
++f := '6'
+ \n foo:
+     echo foo
+",
+    )
+    .unindent_stdout(false)
+    .stderr("error: Formatted justfile differs from original.\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn check_diff_only() {
+  Test::new()
+    .justfile(
+      "
+        a:='1'
+        b := '2'
+
+        foo:
+          echo foo
+      ",
+    )
+    .args(["--fmt", "--unstable", "--check", "--diff-only"])
+    .stdout(
+      "
+        --- justfile
+        +++ justfile
+        @@ -1 +1 @@
+        -a:='1'
+        +a := '1'
+        @@ -5 +5 @@
+        -  echo foo
+        +    echo foo
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn check_diff_only_requires_check() {
+  Test::new()
+    .args(["--fmt", "--unstable", "--diff-only"])
+    .stderr_regex("error: The following required arguments were not provided:\n    --check(.|\\n)+")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn check_diff_missing_final_newline() {
+  Test::new()
+    .justfile("x:=''")
+    .args(["--fmt", "--unstable", "--check"])
+    .stdout(
+      "
+        --- justfile
+        +++ justfile
+        @@ -1 +1 @@
+        -x:=''
+        +x := ''
+      ",
+    )
+    .stderr("error: Formatted justfile differs from original.\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn check_crlf_line_endings() {
+  let tempdir = temptree! {
+    justfile: "x := 'hello'\r\n",
+  };
+
+  Test::with_tempdir(tempdir)
+    .no_justfile()
+    .args(["--fmt", "--unstable", "--check"])
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn permissions_are_preserved() {
+  use std::os::unix::fs::PermissionsExt;
+
+  let tempdir = temptree! {
+    justfile: "x    :=    'hello'   ",
+  };
+
+  let justfile = tempdir.path().join("justfile");
+
+  fs::set_permissions(&justfile, fs::Permissions::from_mode(0o754)).unwrap();
+
+  let _tempdir = Test::with_tempdir(tempdir)
+    .no_justfile()
+    .args(["--fmt", "--unstable"])
+    .stderr_regex("Wrote justfile to `.*`\n")
+    .run();
+
+  assert_eq!(fs::read_to_string(&justfile).unwrap(), "x := 'hello'\n");
+
+  assert_eq!(
+    fs::metadata(&justfile).unwrap().permissions().mode() & 0o777,
+    0o754
+  );
+}
+
+#[test]
+#[cfg(unix)]
+fn symlink_target_is_replaced() {
+  let tempdir = temptree! {
+    "real.just": "x    :=    'hello'   ",
+  };
+
+  std::os::unix::fs::symlink("real.just", tempdir.path().join("justfile")).unwrap();
+
+  let tempdir = Test::with_tempdir(tempdir)
+    .no_justfile()
+    .args(["--fmt", "--unstable"])
+    .stderr_regex("Wrote justfile to `.*`\n")
+    .run()
+    .tempdir;
+
+  assert!(fs::symlink_metadata(tempdir.path().join("justfile"))
+    .unwrap()
+    .file_type()
+    .is_symlink());
+
+  assert_eq!(
+    fs::read_to_string(tempdir.path().join("real.just")).unwrap(),
+    "x := 'hello'\n"
+  );
+}
+
+#[test]
+fn no_temporary_files_are_left_behind() {
+  let tempdir = temptree! {
+    justfile: "x    :=    'hello'   ",
+  };
+
+  let tempdir = Test::with_tempdir(tempdir)
+    .no_justfile()
+    .args(["--fmt", "--unstable"])
+    .stderr_regex("Wrote justfile to `.*`\n")
+    .run()
+    .tempdir;
+
+  for entry in fs::read_dir(tempdir.path()).unwrap() {
+    let name = entry.unwrap().file_name();
+    assert!(
+      !name.to_string_lossy().starts_with(".justfile"),
+      "temporary file left behind: {name:?}"
+    );
+  }
+}
+
 test! {
   name: alias_good,
   justfile: "
@@ -1063,3 +1302,104 @@ fn exported_parameter() {
     .stdout("foo +$f:\n")
     .run();
 }
+
+#[test]
+fn fmt_indent() {
+  Test::new()
+    .justfile("set fmt-indent := '  '\nfoo:\n    echo foo\n    echo bar\n")
+    .args(["--fmt", "--unstable", "--stdout"])
+    .stdout("set fmt-indent := \"  \"\n\nfoo:\n  echo foo\n  echo bar\n")
+    .run();
+}
+
+#[test]
+fn fmt_indent_tab() {
+  Test::new()
+    .justfile("set fmt-indent := \"\\t\"\nfoo:\n    #!/bin/sh\n      echo foo\n")
+    .args(["--fmt", "--unstable", "--stdout"])
+    .stdout("set fmt-indent := \"\\t\"\n\nfoo:\n\t#!/bin/sh\n\t  echo foo\n")
+    .run();
+}
+
+#[test]
+fn fmt_indent_is_checked() {
+  Test::new()
+    .justfile("set fmt-indent := \"  \"\n\nfoo:\n  echo foo\n")
+    .args(["--fmt", "--unstable", "--check"])
+    .run();
+}
+
+#[test]
+fn fmt_indent_invalid() {
+  Test::new()
+    .justfile("set fmt-indent := 'x'")
+    .args(["--fmt", "--unstable", "--stdout"])
+    .stderr(
+      "
+        error: `fmt-indent` must be one or more spaces or tabs
+          |
+        1 | set fmt-indent := 'x'
+          |                   ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn fmt_align_assignments() {
+  Test::new()
+    .justfile(
+      "set fmt-align-assignments\nx := 'a'\nexport long := 'b'\nfoo:\ny := 'c'\nlonger := 'd'\n",
+    )
+    .args(["--fmt", "--unstable", "--stdout"])
+    .stdout(
+      "
+        set fmt-align-assignments := true
+
+        x           := 'a'
+        export long := 'b'
+
+        foo:
+
+        y      := 'c'
+        longer := 'd'
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn fmt_blank_lines() {
+  Test::new()
+    .justfile("set fmt-blank-lines := '2'\nx := 'a'\ny := 'b'\nfoo:\nbar:\n")
+    .args(["--fmt", "--unstable", "--stdout"])
+    .stdout("set fmt-blank-lines := \"2\"\n\n\nx := 'a'\ny := 'b'\n\n\nfoo:\n\n\nbar:\n")
+    .run();
+}
+
+#[test]
+fn fmt_blank_lines_invalid() {
+  Test::new()
+    .justfile("set fmt-blank-lines := 'two'")
+    .args(["--fmt", "--unstable", "--stdout"])
+    .stderr(
+      "
+        error: `fmt-blank-lines` must be a whole number, but is `two`
+          |
+        1 | set fmt-blank-lines := 'two'
+          |                        ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn fmt_settings_do_not_affect_dump() {
+  Test::new()
+    .justfile("set fmt-indent := '  '\nfoo:\n  echo foo\n")
+    .arg("--dump")
+    .stdout("set fmt-indent := \"  \"\n\nfoo:\n    echo foo\n")
+    .run();
+}
@@ -586,6 +586,167 @@ fn path_exists_subdir() {
     .run();
 }
 
+#[test]
+fn read() {
+  Test::new()
+    .tree(tree! {
+      VERSION: "1.2.3\n",
+    })
+    .justfile("x := trim(read('VERSION'))")
+    .args(["--evaluate", "x"])
+    .stdout("1.2.3")
+    .run();
+}
+
+#[test]
+fn read_subdir() {
+  Test::new()
+    .tree(tree! {
+      foo: "bar",
+      baz: {
+      }
+    })
+    .justfile("x := read('foo')")
+    .current_dir("baz")
+    .args(["--evaluate", "x"])
+    .stdout("bar")
+    .run();
+}
+
+#[test]
+fn read_missing_file() {
+  Test::new()
+    .justfile("x := read('VERSION')")
+    .args(["--evaluate", "x"])
+    .stderr_regex("(?s)error: Call to function `read` failed: I/O error reading `VERSION`: .*")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn json() {
+  Test::new()
+    .justfile(
+      r#"
+        version := json(read("package.json"), ".version")
+        first := json(read("package.json"), ".files[0]")
+        files := json(read("package.json"), ".files")
+      "#,
+    )
+    .tree(tree! {
+      "package.json": r#"{"version": "1.2.3", "files": ["a", "b"]}"#,
+    })
+    .args(["--evaluate"])
+    .stdout(
+      r#"
+        files   := "["a","b"]"
+        first   := "a"
+        version := "1.2.3"
+      "#,
+    )
+    .run();
+}
+
+#[test]
+fn toml() {
+  Test::new()
+    .justfile(
+      r#"
+        x := toml('[package]
+        edition = 2021
+        name = "foo"', ".package")
+      "#,
+    )
+    .args(["--evaluate", "x"])
+    .stdout(r#"{"edition":2021,"name":"foo"}"#)
+    .run();
+}
+
+#[test]
+fn yaml() {
+  Test::new()
+    .justfile(
+      r#"
+        x := yaml('steps:
+          - run: cargo test', ".steps[0].run")
+      "#,
+    )
+    .args(["--evaluate", "x"])
+    .stdout("cargo test")
+    .run();
+}
+
+#[test]
+fn json_path_not_found() {
+  Test::new()
+    .justfile(r#"x := json('{"a": 1}', ".b")"#)
+    .args(["--evaluate", "x"])
+    .stderr_regex("(?s)error: Call to function `json` failed: Path `.b` not found.*")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn json_invalid() {
+  Test::new()
+    .justfile(r#"x := json('{', ".")"#)
+    .args(["--evaluate", "x"])
+    .stderr_regex("(?s)error: Call to function `json` failed: Failed to parse JSON: .*")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn semver_matches() {
+  assert_eval_eq("semver_matches('0.1.0', '>=0.1.0')", "true");
+  assert_eval_eq("semver_matches('0.1.0', '=0.0.1')", "false");
+}
+
+#[test]
+fn semver_matches_invalid_version() {
+  Test::new()
+    .justfile("x := semver_matches('foo', '>=0.1.0')")
+    .args(["--evaluate", "x"])
+    .stderr_regex("(?s)error: Call to function `semver_matches` failed: Invalid version `foo`: .*")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn datetime() {
+  Test::new()
+    .justfile("x := datetime('%Y-%m-%d %H:%M')")
+    .args(["--evaluate", "x"])
+    .stdout_regex(r"\d{4}-\d\d-\d\d \d\d:\d\d")
+    .run();
+}
+
+#[test]
+fn datetime_utc() {
+  Test::new()
+    .justfile("x := datetime_utc('%Y-%m-%dT%H:%M:%SZ (%%)')")
+    .args(["--evaluate", "x"])
+    .stdout_regex(r"\d{4}-\d\d-\d\dT\d\d:\d\d:\d\dZ \(%\)")
+    .run();
+}
+
+#[test]
+fn datetime_invalid_format() {
+  Test::new()
+    .justfile("x := datetime_utc('%Q')")
+    .args(["--evaluate", "x"])
+    .stderr(
+      "
+      error: Call to function `datetime_utc` failed: Invalid datetime format `%Q`
+        |
+      1 | x := datetime_utc('%Q')
+        |      ^^^^^^^^^^^^
+    ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
 #[test]
 fn uuid() {
   Test::new()
@@ -595,6 +756,58 @@ fn uuid() {
     .run();
 }
 
+#[test]
+fn choose() {
+  Test::new()
+    .justfile("x := choose('10', 'xy')")
+    .args(["--evaluate", "x"])
+    .stdout_regex("^[xy]{10}$")
+    .run();
+}
+
+#[test]
+fn choose_zero() {
+  Test::new()
+    .justfile("x := choose('0', 'xy')")
+    .args(["--evaluate", "x"])
+    .stdout("")
+    .run();
+}
+
+#[test]
+fn choose_empty_alphabet() {
+  Test::new()
+    .justfile("x := choose('1', '')")
+    .args(["--evaluate", "x"])
+    .stderr_regex("(?s)error: Call to function `choose` failed: Alphabet is empty.*")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn choose_repeated_character() {
+  Test::new()
+    .justfile("x := choose('1', 'aa')")
+    .args(["--evaluate", "x"])
+    .stderr_regex(
+      "(?s)error: Call to function `choose` failed: Alphabet contains repeated character `a`.*",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn choose_invalid_count() {
+  Test::new()
+    .justfile("x := choose('-1', 'xy')")
+    .args(["--evaluate", "x"])
+    .stderr_regex(
+      "(?s)error: Call to function `choose` failed: Failed to parse `-1` as a positive integer: .*",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
 #[test]
 fn sha256() {
   Test::new()
@@ -618,3 +831,387 @@ fn sha256_file() {
     .stdout("177b3d79aaafb53a7a4d7aaba99a82f27c73370e8cb0295571aade1e4fea1cd2")
     .run();
 }
+
+#[test]
+fn blake3_file() {
+  Test::new()
+    .justfile("x := blake3_file('sub/blakefile')")
+    .tree(tree! {
+      sub: {
+        blakefile: "just is great\n",
+      }
+    })
+    .current_dir("sub")
+    .args(["--evaluate", "x"])
+    .stdout("8379241877190ca4b94076a8c8f89fe5747f95c62f3e4bf41f7408a0088ae16d")
+    .run();
+}
+
+#[test]
+fn blake3_file_missing() {
+  Test::new()
+    .justfile("x := blake3_file('missing')")
+    .args(["--evaluate", "x"])
+    .stderr_regex("(?s)error: Call to function `blake3_file` failed: Failed to open file at .*")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn arithmetic() {
+  assert_eval_eq("add('8000', '80')", "8080");
+  assert_eval_eq("sub('3', '5')", "-2");
+  assert_eval_eq("mul(' 6 ', '7')", "42");
+  assert_eval_eq("div('7', '2')", "3");
+  assert_eval_eq("rem('-7', '2')", "-1");
+  assert_eval_eq("add(mul('2', '3'), '1')", "7");
+}
+
+#[test]
+fn arithmetic_overflow() {
+  Test::new()
+    .justfile("x := add('9223372036854775807', '1')")
+    .args(["--evaluate", "x"])
+    .stderr_regex(
+      "(?s)error: Call to function `add` failed: Integer overflow in `9223372036854775807 \\+ 1`\n.*",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn arithmetic_division_by_zero() {
+  Test::new()
+    .justfile("x := div('1', '0')")
+    .args(["--evaluate", "x"])
+    .stderr_regex("(?s)error: Call to function `div` failed: Division by zero in `1 / 0`\n.*")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn arithmetic_invalid_integer() {
+  Test::new()
+    .justfile("x := mul('2', 'three')")
+    .args(["--evaluate", "x"])
+    .stderr_regex("(?s)error: Call to function `mul` failed: `three` is not an integer\n.*")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn line_functions() {
+  assert_eval_eq("first_line('a\nb\nc\n')", "a");
+  assert_eval_eq("first_line('')", "");
+  assert_eval_eq("last_line('a\r\nb\r\nc\r\n')", "c");
+  assert_eval_eq("line('2', 'a\nb\nc')", "b");
+  assert_eval_eq("line_count('a\nb\nc\n')", "3");
+  assert_eval_eq("line_count('')", "0");
+  assert_eval_eq("lines('a\r\nb\r\n')", "a\nb");
+  assert_eval_eq("lines('a\nb\nc\n', ',')", "a,b,c");
+  assert_eval_eq("head('2', 'a\nb\nc')", "a\nb");
+  assert_eval_eq("head('5', 'a\nb')", "a\nb");
+  assert_eval_eq("tail('2', 'a\nb\nc\n')", "b\nc");
+  assert_eval_eq("tail('5', 'a\nb')", "a\nb");
+}
+
+#[test]
+fn line_out_of_range() {
+  Test::new()
+    .justfile("x := line('3', 'a\nb')")
+    .args(["--evaluate", "x"])
+    .stderr_regex(
+      "(?s)error: Call to function `line` failed: Line 3 out of range, text has 2 lines\n.*",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn line_zero() {
+  Test::new()
+    .justfile("x := line('0', 'a')")
+    .args(["--evaluate", "x"])
+    .stderr_regex("(?s)error: Call to function `line` failed: Line numbers start at 1\n.*")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn invalid_line_number() {
+  Test::new()
+    .justfile("x := head('two', 'a')")
+    .args(["--evaluate", "x"])
+    .stderr_regex("(?s)error: Call to function `head` failed: `two` is not a valid line number\n.*")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn glob() {
+  Test::new()
+    .tree(tree! {
+      src: {
+        "a.c": "",
+        "b.c": "",
+        "c.h": "",
+        ".d.c": "",
+        lib: {
+          "e.c": "",
+        },
+      },
+    })
+    .justfile("x := glob('src/**/*.c')")
+    .args(["--evaluate", "x"])
+    .stdout("src/a.c src/b.c src/lib/e.c")
+    .run();
+}
+
+#[test]
+fn glob_separator() {
+  Test::new()
+    .tree(tree! {
+      "a b.txt": "",
+      "c.txt": "",
+    })
+    .justfile("x := glob('*.txt', ',')")
+    .args(["--evaluate", "x"])
+    .stdout("a b.txt,c.txt")
+    .run();
+}
+
+#[test]
+fn glob_is_relative_to_justfile_directory() {
+  Test::new()
+    .tree(tree! {
+      "foo.txt": "",
+      bar: {
+        "baz.txt": "",
+      },
+    })
+    .justfile("x := glob('*.txt')")
+    .current_dir("bar")
+    .args(["--evaluate", "x"])
+    .stdout("foo.txt")
+    .run();
+}
+
+#[test]
+fn glob_no_matches() {
+  Test::new()
+    .justfile("x := glob('*.txt')")
+    .args(["--evaluate", "x"])
+    .stdout("")
+    .run();
+}
+
+#[test]
+fn glob_invalid_pattern() {
+  Test::new()
+    .justfile("x := glob('[a')")
+    .args(["--evaluate", "x"])
+    .stderr(
+      "
+      error: Call to function `glob` failed: Invalid glob pattern `[a`: invalid range pattern
+        |
+      1 | x := glob('[a')
+        |      ^^^^
+    ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn exec() {
+  assert_eval_eq(
+    "exec('printf', '%s|', 'a b', '$HOME', '`c`')",
+    "a b|$HOME|`c`|",
+  );
+}
+
+#[test]
+#[cfg(unix)]
+fn exec_trims_trailing_newline() {
+  assert_eval_eq("exec('echo', 'foo')", "foo");
+}
+
+#[test]
+#[cfg(unix)]
+fn exec_sees_exported_variables() {
+  Test::new()
+    .justfile(
+      "
+        export foo := 'bar'
+
+        @baz:
+          echo {{ exec('sh', '-c', 'echo $foo') }}
+      ",
+    )
+    .stdout("bar\n")
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn exec_failure() {
+  Test::new()
+    .justfile("x := exec('false')")
+    .args(["--evaluate", "x"])
+    .stderr(
+      "
+      error: Call to function `exec` failed: Failed to run `false`: Process exited with status code 1
+        |
+      1 | x := exec('false')
+        |      ^^^^
+    ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn exec_argument_count_error() {
+  Test::new()
+    .justfile("x := exec()")
+    .args(["--evaluate"])
+    .stderr(
+      "
+      error: Function `exec` called with 0 arguments but takes 1 or more
+        |
+      1 | x := exec()
+        |      ^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn can_connect_to_listening_port() {
+  let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+  let port = listener.local_addr().unwrap().port();
+
+  Test::new()
+    .justfile(format!("x := can_connect('127.0.0.1', '{port}', '10s')"))
+    .args(["--evaluate", "x"])
+    .stdout("true")
+    .run();
+}
+
+#[test]
+fn can_connect_to_closed_port() {
+  let port = std::net::TcpListener::bind("127.0.0.1:0")
+    .unwrap()
+    .local_addr()
+    .unwrap()
+    .port();
+
+  Test::new()
+    .justfile(format!("x := can_connect('127.0.0.1', '{port}', '10s')"))
+    .args(["--evaluate", "x"])
+    .stdout("false")
+    .run();
+}
+
+#[test]
+fn can_connect_invalid_port() {
+  Test::new()
+    .justfile("x := can_connect('localhost', 'http', '1s')")
+    .args(["--evaluate", "x"])
+    .stderr(
+      "
+      error: Call to function `can_connect` failed: `http` is not a valid port: invalid digit found in string
+        |
+      1 | x := can_connect('localhost', 'http', '1s')
+        |      ^^^^^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn can_connect_invalid_timeout() {
+  Test::new()
+    .justfile("x := can_connect('localhost', '80', 'soon')")
+    .args(["--evaluate", "x"])
+    .stderr(
+      "
+      error: Call to function `can_connect` failed: `soon` is not a valid interval, expected a number and a unit
+        |
+      1 | x := can_connect('localhost', '80', 'soon')
+        |      ^^^^^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn poll_returns_output_on_success() {
+  Test::new()
+    .justfile("x := poll('echo ready', '10ms', '10s')")
+    .args(["--evaluate", "x"])
+    .stdout("ready")
+    .run();
+}
+
+#[test]
+fn poll_retries_until_success() {
+  Test::new()
+    .justfile("x := poll('if [ -e attempted ]; then echo ready; else touch attempted; exit 1; fi', '10ms', '10s')")
+    .args(["--evaluate", "x"])
+    .stdout("ready")
+    .run();
+}
+
+#[test]
+fn poll_sees_exported_variables() {
+  Test::new()
+    .justfile(
+      "
+        export foo := 'bar'
+
+        @baz:
+          echo {{ poll('echo $foo', '10ms', '10s') }}
+      ",
+    )
+    .stdout("bar\n")
+    .run();
+}
+
+#[test]
+fn poll_timeout() {
+  Test::new()
+    .justfile("x := poll('exit 1', '10ms', '50ms')")
+    .args(["--evaluate", "x"])
+    .stderr(
+      "
+      error: Call to function `poll` failed: `exit 1` did not succeed within 50ms: Process exited with status code 1
+        |
+      1 | x := poll('exit 1', '10ms', '50ms')
+        |      ^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn poll_invalid_interval() {
+  Test::new()
+    .justfile("x := poll('true', 'often', '1s')")
+    .args(["--evaluate", "x"])
+    .stderr(
+      "
+      error: Call to function `poll` failed: `often` is not a valid interval, expected a number and a unit
+        |
+      1 | x := poll('true', 'often', '1s')
+        |      ^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
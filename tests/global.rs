@@ -0,0 +1,118 @@
+use super::*;
+
+#[test]
+fn xdg_config_home() {
+  let tmp = tempdir();
+
+  let config = tmp.path().join("config");
+
+  Test::with_tempdir(tmp)
+    .no_justfile()
+    .write("config/just/justfile", "foo:\n  @echo global")
+    .write("justfile", "foo:\n  @echo local")
+    .env("XDG_CONFIG_HOME", config.to_str().unwrap())
+    .arg("--global")
+    .arg("foo")
+    .stdout("global\n")
+    .test_round_trip(false)
+    .run();
+}
+
+#[test]
+fn dot_config_fallback() {
+  let tmp = tempdir();
+
+  let home = tmp.path().join("home");
+
+  Test::with_tempdir(tmp)
+    .no_justfile()
+    .write("home/.config/just/justfile", "foo:\n  @echo global")
+    .env("HOME", home.to_str().unwrap())
+    .env("XDG_CONFIG_HOME", "")
+    .arg("-g")
+    .arg("foo")
+    .stdout("global\n")
+    .test_round_trip(false)
+    .run();
+}
+
+#[test]
+fn home_justfile_fallback() {
+  let tmp = tempdir();
+
+  let home = tmp.path().join("home");
+
+  Test::with_tempdir(tmp)
+    .no_justfile()
+    .write("home/.justfile", "foo:\n  @echo global")
+    .env("HOME", home.to_str().unwrap())
+    .env("XDG_CONFIG_HOME", "")
+    .arg("-g")
+    .arg("foo")
+    .stdout("global\n")
+    .test_round_trip(false)
+    .run();
+}
+
+#[test]
+fn working_directory_is_invocation_directory() {
+  let tmp = tempdir();
+
+  let config = tmp.path().join("config");
+
+  Test::with_tempdir(tmp)
+    .no_justfile()
+    .write("config/just/justfile", "foo:\n  @basename \"$PWD\"")
+    .write("bar/.keep", "")
+    .current_dir("bar")
+    .env("XDG_CONFIG_HOME", config.to_str().unwrap())
+    .arg("--global")
+    .arg("foo")
+    .stdout("bar\n")
+    .test_round_trip(false)
+    .run();
+}
+
+#[test]
+fn not_found() {
+  let tmp = tempdir();
+
+  let home = tmp.path().join("home");
+
+  Test::with_tempdir(tmp)
+    .no_justfile()
+    .env("HOME", home.to_str().unwrap())
+    .env("XDG_CONFIG_HOME", "")
+    .arg("--global")
+    .status(EXIT_FAILURE)
+    .stderr_regex(r"error: No global justfile found, looked for `.*justfile`, .*`.*\.justfile`\n")
+    .run();
+}
+
+#[test]
+fn conflicts_with_justfile() {
+  Test::new()
+    .args(["--global", "--justfile", "foo"])
+    .status(EXIT_FAILURE)
+    .stderr_regex(
+      "(?s)error: The argument '--justfile <JUSTFILE>' cannot be used with '--global'.*",
+    )
+    .run();
+}
+
+#[test]
+fn init() {
+  let tmp = tempdir();
+
+  let config = tmp.path().join("config");
+
+  let output = Test::with_tempdir(tmp)
+    .no_justfile()
+    .env("XDG_CONFIG_HOME", config.to_str().unwrap())
+    .args(["--global", "--init"])
+    .stderr_regex("Wrote justfile to `.*`\n")
+    .test_round_trip(false)
+    .run();
+
+  assert!(output.tempdir.path().join("config/just/justfile").is_file());
+}
@@ -0,0 +1,387 @@
+use super::*;
+
+#[test]
+fn list_with_groups() {
+  Test::new()
+    .justfile(
+      "
+        [group('alpha')]
+        a:
+        # Doc comment
+        [group('alpha')]
+        [group('beta')]
+        b:
+        c:
+        [group('multi word group')]
+        d:
+        [group('alpha')]
+        e:
+        [group('beta')]
+        [group('alpha')]
+        f:
+      ",
+    )
+    .arg("--list")
+    .stdout(
+      "
+        Available recipes:
+            c
+
+            [alpha]
+            a
+            b # Doc comment
+            e
+            f
+
+            [beta]
+            b # Doc comment
+            f
+
+            [multi word group]
+            d
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn list_with_groups_unsorted() {
+  Test::new()
+    .justfile(
+      "
+        [group('beta')]
+        [group('alpha')]
+        f:
+
+        [group('alpha')]
+        e:
+
+        [group('multi word group')]
+        d:
+
+        c:
+
+        [group('alpha')]
+        [group('beta')]
+        b:
+
+        [group('alpha')]
+        a:
+      ",
+    )
+    .args(["--list", "--unsorted"])
+    .stdout(
+      "
+        Available recipes:
+            c
+
+            [alpha]
+            f
+            e
+            b
+            a
+
+            [beta]
+            f
+            b
+
+            [multi word group]
+            d
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn list_only_grouped_recipes() {
+  Test::new()
+    .justfile(
+      "
+        [group('alpha')]
+        a:
+
+        [private]
+        [group('beta')]
+        b:
+      ",
+    )
+    .arg("--list")
+    .stdout(
+      "
+        Available recipes:
+            [alpha]
+            a
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn list_groups() {
+  Test::new()
+    .justfile(
+      "
+        [group('b')]
+        [group('a')]
+        foo:
+
+        [group('c')]
+        bar:
+
+        baz:
+      ",
+    )
+    .arg("--list-groups")
+    .stdout(
+      "
+        Recipe groups:
+            a
+            b
+            c
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn list_groups_unsorted() {
+  Test::new()
+    .justfile(
+      "
+        [group('c')]
+        bar:
+
+        [group('b')]
+        [group('a')]
+        foo:
+      ",
+    )
+    .args(["--list-groups", "--unsorted"])
+    .stdout(
+      "
+        Recipe groups:
+            c
+            a
+            b
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn list_groups_with_custom_prefix() {
+  Test::new()
+    .justfile(
+      "
+        [group('a')]
+        foo:
+      ",
+    )
+    .args(["--list-groups", "--list-prefix", "..."])
+    .stdout(
+      "
+        Recipe groups:
+        ...a
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn group_requires_argument() {
+  Test::new()
+    .justfile(
+      "
+        [group]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Attribute `group` got 0 arguments but takes 1 argument
+          |
+        1 | [group]
+          |  ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile(
+      "
+        [group('alpha')]
+        [group('beta')]
+        foo:
+      ",
+    )
+    .arg("--dump")
+    .stdout(
+      "
+        [group('alpha')]
+        [group('beta')]
+        foo:
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn group_declaration_attributes_are_inherited() {
+  Test::new()
+    .justfile(
+      "
+        [group('deploy'): confirm]
+
+        [group('deploy')]
+        production:
+          @echo deployed
+
+        staging:
+          @echo staged
+      ",
+    )
+    .arg("production")
+    .stderr("error: Recipe `production` was not confirmed\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn group_declaration_attributes_apply_only_to_members() {
+  Test::new()
+    .justfile(
+      "
+        [group('deploy'): confirm]
+
+        [group('deploy')]
+        production:
+          @echo deployed
+
+        staging:
+          @echo staged
+      ",
+    )
+    .arg("staging")
+    .stdout("staged\n")
+    .run();
+}
+
+#[test]
+fn group_declaration_may_follow_recipes() {
+  Test::new()
+    .justfile(
+      "
+        [group('internal')]
+        foo:
+
+        bar:
+
+        [group('internal'): private]
+      ",
+    )
+    .arg("--summary")
+    .stdout("bar\n")
+    .run();
+}
+
+#[test]
+fn group_declaration_with_multiple_attributes() {
+  Test::new()
+    .justfile(
+      "
+        [group('deploy'): no-cd, private]
+
+        [group('deploy')]
+        foo:
+          @basename \"$PWD\"
+      ",
+    )
+    .write("bar/.keep", "")
+    .current_dir("bar")
+    .arg("foo")
+    .stdout("bar\n")
+    .run();
+
+  Test::new()
+    .justfile(
+      "
+        [group('deploy'): no-cd, private]
+
+        [group('deploy')]
+        foo:
+
+        bar:
+      ",
+    )
+    .arg("--summary")
+    .stdout("bar\n")
+    .run();
+}
+
+#[test]
+fn recipe_attributes_take_precedence_over_group_declaration() {
+  Test::new()
+    .justfile(
+      "
+        [group('deploy'): no-cd, confirm('Deploy?')]
+
+        [group('deploy')]
+        [working-directory('bar')]
+        [confirm('Really deploy?')]
+        foo:
+          @basename \"$PWD\"
+      ",
+    )
+    .write("bar/.keep", "")
+    .args(["--yes", "foo"])
+    .stdout("bar\n")
+    .run();
+}
+
+#[test]
+fn duplicate_group_declaration() {
+  Test::new()
+    .justfile(
+      "
+        [group('deploy'): confirm]
+        [group('deploy'): no-cd]
+      ",
+    )
+    .stderr(
+      "
+        error: Group `deploy` first declared on line 1 is redeclared on line 2
+          |
+        2 | [group('deploy'): no-cd]
+          |  ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn group_declaration_dump() {
+  Test::new()
+    .justfile(
+      "
+        [group('deploy'): no-cd, confirm]
+
+        [group('deploy')]
+        foo:
+      ",
+    )
+    .arg("--dump")
+    .stdout(
+      "
+        [group('deploy'): confirm, no-cd]
+
+        [group('deploy')]
+        foo:
+      ",
+    )
+    .run();
+}
@@ -9,6 +9,29 @@ fn include_fails_without_unstable() {
     .run();
 }
 
+#[test]
+fn import_succeeds_without_unstable() {
+  Test::new()
+    .tree(tree! {
+      "include.justfile": "
+        b:
+          @echo B
+      ",
+    })
+    .justfile(
+      "
+        import './include.justfile'
+
+        a: b
+          @echo A
+      ",
+    )
+    .test_round_trip(false)
+    .arg("a")
+    .stdout("B\nA\n")
+    .run();
+}
+
 #[test]
 fn include_succeeds_with_unstable() {
   Test::new()
@@ -70,7 +93,9 @@ fn trailing_include() {
     )
     .arg("--unstable")
     .status(EXIT_FAILURE)
-    .stderr("error: Expected character `=`\n  |\n2 | !include ./include.justfile\n  |  ^\n")
+    .stderr(
+      "error: Unknown start of token:\n  |\n2 | !include ./include.justfile\n  |          ^\n",
+    )
     .run();
 }
 
@@ -89,3 +114,544 @@ fn circular_include() {
     ))
     .run();
 }
+
+#[test]
+fn dump_resolve_imports() {
+  Test::new()
+    .tree(tree! {
+      "include.justfile": "
+        !include ./subdir/nested.justfile
+
+        b:
+          @echo B
+      ",
+      subdir: {
+        "nested.justfile": "c:\n  @echo C\n",
+      },
+    })
+    .justfile(
+      "
+        # comment
+        !include ./include.justfile
+
+        a: b
+          @echo A
+      ",
+    )
+    .args(["--unstable", "--dump", "--resolve-imports"])
+    .test_round_trip(false)
+    .stdout(format!(
+      "
+        # comment
+        # begin !include include.justfile
+        # begin !include {}
+        c:
+          @echo C
+        # end !include {}
+
+        b:
+          @echo B
+        # end !include include.justfile
+
+        a: b
+          @echo A
+      ",
+      path("subdir/nested.justfile"),
+      path("subdir/nested.justfile"),
+    ))
+    .run();
+}
+
+#[test]
+fn resolve_imports_requires_dump() {
+  Test::new()
+    .justfile("a:")
+    .arg("--resolve-imports")
+    .stderr_regex("(?s)error: The following required arguments were not provided:\n    --dump\n.*")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn optional_include_of_missing_file_is_skipped() {
+  Test::new()
+    .justfile(
+      "
+        !include? ./local.justfile
+
+        a:
+          @echo A
+      ",
+    )
+    .arg("--unstable")
+    .test_round_trip(false)
+    .stdout("A\n")
+    .run();
+}
+
+#[test]
+fn optional_include_of_existing_file_is_included() {
+  Test::new()
+    .tree(tree! {
+      "local.justfile": "
+        b:
+          @echo B
+      ",
+    })
+    .justfile(
+      "
+        !include? ./local.justfile
+
+        a: b
+          @echo A
+      ",
+    )
+    .arg("--unstable")
+    .test_round_trip(false)
+    .arg("a")
+    .stdout("B\nA\n")
+    .run();
+}
+
+#[test]
+fn optional_include_directive_with_no_path() {
+  Test::new()
+    .justfile("!include?")
+    .arg("--unstable")
+    .status(EXIT_FAILURE)
+    .stderr_regex("error: !include directive on line 1 of `.*` has no argument\n")
+    .run();
+}
+
+#[test]
+fn missing_include_is_an_error() {
+  Test::new()
+    .justfile("!include ./local.justfile")
+    .arg("--unstable")
+    .status(EXIT_FAILURE)
+    .stderr_regex("error: Failed to read justfile at `.*local.justfile`: .*\n")
+    .run();
+}
+
+#[test]
+fn glob_includes_matching_files_in_sorted_order() {
+  Test::new()
+    .tree(tree! {
+      just: {
+        "b.just": "b:\n  @echo B",
+        "a.just": "a:\n  @echo A\n",
+        "c.txt": "c:\n  @echo C\n",
+      },
+    })
+    .justfile(
+      "
+        !include just/*.just
+
+        all: a b
+      ",
+    )
+    .args(["--unstable", "--dump", "--resolve-imports"])
+    .test_round_trip(false)
+    .stdout(format!(
+      "
+        # begin !include {}
+        a:
+          @echo A
+        # end !include {}
+        # begin !include {}
+        b:
+          @echo B
+        # end !include {}
+
+        all: a b
+      ",
+      path("just/a.just"),
+      path("just/a.just"),
+      path("just/b.just"),
+      path("just/b.just"),
+    ))
+    .run();
+}
+
+#[test]
+fn glob_include_runs_recipes() {
+  Test::new()
+    .tree(tree! {
+      just: {
+        "a.just": "a:\n  @echo A",
+        "b.just": "b:\n  @echo B",
+      },
+    })
+    .justfile(
+      "
+        !include just/*.just
+
+        all: a b
+      ",
+    )
+    .arg("--unstable")
+    .test_round_trip(false)
+    .arg("all")
+    .stdout("A\nB\n")
+    .run();
+}
+
+#[test]
+fn glob_include_with_no_matches_is_an_error() {
+  Test::new()
+    .justfile("!include just/*.just")
+    .arg("--unstable")
+    .status(EXIT_FAILURE)
+    .stderr_regex("error: !include pattern `just/\\*.just` in `.*` failed: no files matched\n")
+    .run();
+}
+
+#[test]
+fn optional_glob_include_with_no_matches_is_skipped() {
+  Test::new()
+    .justfile(
+      "
+        !include? just/*.just
+
+        a:
+          @echo A
+      ",
+    )
+    .arg("--unstable")
+    .test_round_trip(false)
+    .stdout("A\n")
+    .run();
+}
+
+#[test]
+fn glob_include_detects_circular_includes() {
+  Test::new()
+    .justfile("!include just/*.just")
+    .tree(tree! {
+      just: {
+        "a.just": "",
+        "b.just": "!include ../justfile",
+      },
+    })
+    .arg("--unstable")
+    .status(EXIT_FAILURE)
+    .stderr_regex(path_for_regex(
+      "error: Include `.*/justfile` in `.*/b.just` is a circular include\n",
+    ))
+    .run();
+}
+
+#[test]
+fn namespaced_include() {
+  Test::new()
+    .tree(tree! {
+      "ci.just": "
+        registry := 'example.com'
+
+        lint: fmt
+          @echo lint {{registry}}
+
+        fmt:
+          @echo fmt
+      ",
+    })
+    .justfile(
+      "
+        !include ci.just as ci
+
+        lint: ci::lint
+          @echo root lint {{ci::registry}}
+      ",
+    )
+    .args(["--unstable", "lint"])
+    .test_round_trip(false)
+    .stdout("fmt\nlint example.com\nroot lint example.com\n")
+    .run();
+}
+
+#[test]
+fn namespaced_recipes_can_be_run_from_the_command_line() {
+  Test::new()
+    .tree(tree! {
+      "ci.just": "
+        lint:
+          @echo ci lint
+      ",
+    })
+    .justfile(
+      "
+        !include ci.just as ci
+
+        lint:
+          @echo root lint
+      ",
+    )
+    .args(["--unstable", "ci::lint"])
+    .test_round_trip(false)
+    .stdout("ci lint\n")
+    .run();
+}
+
+#[test]
+fn namespaced_recipes_are_suggested_without_their_namespace() {
+  Test::new()
+    .tree(tree! {
+      "ci.just": "
+        deploy:
+      ",
+    })
+    .justfile(
+      "
+        !include ci.just as ci
+
+        lint:
+      ",
+    )
+    .args(["--unstable", "deploy"])
+    .test_round_trip(false)
+    .stderr("error: Justfile does not contain recipe `deploy`.\nDid you mean `ci::deploy`?\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn namespaced_include_can_reference_including_justfile() {
+  Test::new()
+    .tree(tree! {
+      "ci.just": "
+        lint: setup
+          @echo lint {{root}}
+      ",
+    })
+    .justfile(
+      "
+        !include ci.just as ci
+
+        root := 'root'
+
+        setup:
+          @echo setup
+      ",
+    )
+    .args(["--unstable", "ci::lint"])
+    .test_round_trip(false)
+    .stdout("setup\nlint root\n")
+    .run();
+}
+
+#[test]
+fn namespaced_include_exports_without_namespace() {
+  Test::new()
+    .tree(tree! {
+      "ci.just": "
+        export REGISTRY := 'ghcr.io'
+
+        push:
+          @echo $REGISTRY {{REGISTRY}}
+      ",
+    })
+    .justfile(
+      "
+        !include ci.just as ci
+      ",
+    )
+    .args(["--unstable", "ci::push"])
+    .test_round_trip(false)
+    .stdout("ghcr.io ghcr.io\n")
+    .run();
+}
+
+#[test]
+fn namespaced_include_dump() {
+  Test::new()
+    .tree(tree! {
+      "ci.just": "x := 'a'\nlint p=x:\n  echo {{p}} {{x}}\n",
+    })
+    .justfile("!include ci.just as ci")
+    .args(["--unstable", "--dump", "--resolve-imports"])
+    .test_round_trip(false)
+    .stdout(
+      "
+        # begin !include ci.just
+        ci::x := 'a'
+        ci::lint p=ci::x:
+          echo {{p}} {{ci::x}}
+        # end !include ci.just
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn namespaced_recipes_are_listed_by_namespace() {
+  Test::new()
+    .tree(tree! {
+      "ci.just": "
+        # check code
+        lint:
+
+        fmt:
+      ",
+    })
+    .justfile(
+      "
+        !include ci.just as ci
+
+        build:
+      ",
+    )
+    .args(["--unstable", "--list"])
+    .test_round_trip(false)
+    .stdout(
+      "
+        Available recipes:
+            build
+
+            ci::
+            ci::fmt
+            ci::lint # check code
+      ",
+    )
+    .run();
+}
+
+pub(crate) fn cached(url: &str) -> String {
+  use sha2::Digest;
+  format!("cache/{:x}.just", sha2::Sha256::digest(url))
+}
+
+#[test]
+fn remote_include_offline_uses_cache() {
+  Test::new()
+    .write(
+      cached("https://example.com/common.just"),
+      "foo:\n  echo foo\n",
+    )
+    .justfile("!include https://example.com/common.just")
+    .env("JUST_CACHE_DIR", "cache")
+    .args(["--unstable", "--offline", "foo"])
+    .test_round_trip(false)
+    .stdout("foo\n")
+    .stderr("echo foo\n")
+    .run();
+}
+
+#[test]
+fn remote_include_offline_not_cached() {
+  Test::new()
+    .justfile("!include https://example.com/common.just")
+    .env("JUST_CACHE_DIR", "cache")
+    .args(["--unstable", "--offline"])
+    .test_round_trip(false)
+    .stderr(format!(
+      "error: `https://example.com/common.just` is not cached at `{}`, and `--offline` or `--one-shot` was given\n",
+      cached("https://example.com/common.just")
+    ))
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn optional_remote_include_offline_not_cached() {
+  Test::new()
+    .justfile(
+      "
+        !include? https://example.com/common.just
+
+        bar:
+          echo bar
+      ",
+    )
+    .env("JUST_CACHE_DIR", "cache")
+    .args(["--unstable", "--offline"])
+    .test_round_trip(false)
+    .stdout("bar\n")
+    .stderr("echo bar\n")
+    .run();
+}
+
+#[test]
+fn pinned_remote_include_uses_cache() {
+  use sha2::Digest;
+
+  let contents = "foo:\n  echo foo\n";
+
+  Test::new()
+    .write(cached("https://example.com/common.just"), contents)
+    .justfile(format!(
+      "!include https://example.com/common.just#sha256={:x}",
+      sha2::Sha256::digest(contents)
+    ))
+    .env("JUST_CACHE_DIR", "cache")
+    .args(["--unstable", "foo"])
+    .test_round_trip(false)
+    .stdout("foo\n")
+    .stderr("echo foo\n")
+    .run();
+}
+
+#[test]
+fn pinned_remote_include_hash_mismatch() {
+  Test::new()
+    .write(cached("https://example.com/common.just"), "foo:\n  echo foo\n")
+    .justfile("!include https://example.com/common.just#sha256=abc")
+    .env("JUST_CACHE_DIR", "cache")
+    .args(["--unstable", "--offline"])
+    .test_round_trip(false)
+    .stderr_regex(
+      "error: Contents of `https://example.com/common.just` have SHA-256 hash `[0-9a-f]{64}`, but `abc` was expected\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn remote_include_fetch_failure() {
+  Test::new()
+    .justfile("!include https://localhost:1/common.just")
+    .env("JUST_CACHE_DIR", "cache")
+    .arg("--unstable")
+    .test_round_trip(false)
+    .stderr_regex(
+      "(?s).*error: Failed to fetch `https://localhost:1/common.just` with `curl`: .*\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn unpinned_remote_include_fetch_failure_uses_cache() {
+  Test::new()
+    .write(
+      cached("https://localhost:1/common.just"),
+      "foo:\n  echo foo\n",
+    )
+    .justfile("!include https://localhost:1/common.just")
+    .env("JUST_CACHE_DIR", "cache")
+    .args(["--unstable", "foo"])
+    .test_round_trip(false)
+    .stdout("foo\n")
+    .stderr_regex(
+      "(?s).*warning: Failed to fetch `https://localhost:1/common.just`, using the copy cached at \
+       `cache/[0-9a-f]{64}.just`, which may be out of date\necho foo\n",
+    )
+    .run();
+}
+
+#[test]
+fn pinned_remote_include_with_stale_cache_is_fetched_again() {
+  Test::new()
+    .write(
+      cached("https://localhost:1/common.just"),
+      "foo:\n  echo foo\n",
+    )
+    .justfile("!include https://localhost:1/common.just#sha256=abc")
+    .env("JUST_CACHE_DIR", "cache")
+    .args(["--unstable", "foo"])
+    .test_round_trip(false)
+    .stderr_regex(
+      "(?s).*error: Failed to fetch `https://localhost:1/common.just` with `curl`: .*\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -0,0 +1,153 @@
+use super::*;
+
+/// A test whose `$SHELL` is a fake login shell which runs `env_output` when
+/// invoked with `-lc 'env -0'`
+#[cfg(unix)]
+fn login_shell(env_output: &str) -> Test {
+  let test = Test::new().write(
+    "shell",
+    format!("#!/bin/sh\n[ \"$1\" = -lc ] && [ \"$2\" = 'env -0' ] || exit 1\n{env_output}\n"),
+  );
+
+  let shell = test.tempdir.path().join("shell");
+
+  let permissions = std::os::unix::fs::PermissionsExt::from_mode(0o700);
+  fs::set_permissions(&shell, permissions).unwrap();
+
+  let shell = shell.to_str().unwrap().to_owned();
+
+  test.env("SHELL", &shell)
+}
+
+#[cfg(unix)]
+#[test]
+fn login_environment_is_exported_to_recipes_and_backticks() {
+  login_shell("printf 'FOO=login\\0'")
+    .justfile(
+      "
+        set inherit-login-env
+
+        x := `echo $FOO`
+
+        foo:
+          @echo {{x}} $FOO
+      ",
+    )
+    .stdout("login login\n")
+    .run();
+}
+
+#[cfg(unix)]
+#[test]
+fn login_environment_overrides_inherited_environment() {
+  login_shell("printf 'PATH=/login/bin:%s\\0' \"$PATH\"")
+    .justfile(
+      "
+        set inherit-login-env
+
+        foo:
+          @echo $PATH | cut -d: -f1
+      ",
+    )
+    .stdout("/login/bin\n")
+    .run();
+}
+
+#[cfg(unix)]
+#[test]
+fn disabled_by_default() {
+  login_shell("printf 'FOO=login\\0'")
+    .justfile(
+      "
+        foo:
+          @echo ${FOO:-unset}
+      ",
+    )
+    .stdout("unset\n")
+    .run();
+}
+
+#[cfg(unix)]
+#[test]
+fn dotenv_takes_precedence() {
+  login_shell("printf 'DOTENV_KEY=login\\0FOO=login\\0'")
+    .justfile(
+      "
+        set inherit-login-env
+        set dotenv-load
+
+        foo:
+          @echo $DOTENV_KEY $FOO
+      ",
+    )
+    .stdout("dotenv-value login\n")
+    .run();
+}
+
+#[cfg(unix)]
+#[test]
+fn login_shell_failure() {
+  login_shell("exit 2")
+    .justfile(
+      "
+        set inherit-login-env
+
+        foo:
+      ",
+    )
+    .stderr_regex(
+      "error: Login shell `.*/shell` failed with exit code 2 while loading its environment\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[cfg(unix)]
+#[test]
+fn values_may_contain_newlines() {
+  login_shell("printf 'FOO=a\\nb\\0BAR=c\\0'")
+    .justfile(
+      "
+        set inherit-login-env
+
+        foo:
+          @echo \"$FOO\" $BAR
+      ",
+    )
+    .stdout("a\nb c\n")
+    .run();
+}
+
+#[cfg(unix)]
+#[test]
+fn login_environment_is_not_loaded_from_dotenv() {
+  login_shell("printf 'FOO=login\\0'")
+    .justfile(
+      "
+        set inherit-login-env
+
+        foo:
+          @echo {{env('FOO')}} {{dotenv('FOO', 'unset')}}
+      ",
+    )
+    .stdout("login unset\n")
+    .run();
+}
+
+#[cfg(unix)]
+#[test]
+fn login_environment_is_not_an_environment_change() {
+  login_shell("printf 'FOO=login\\0'")
+    .justfile(
+      "
+        set inherit-login-env
+
+        foo:
+          @echo $FOO
+      ",
+    )
+    .args(["--diff-env", "foo"])
+    .stdout("login\n")
+    .stderr("Environment for recipe `foo` is unchanged\n")
+    .run();
+}
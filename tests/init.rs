@@ -204,3 +204,104 @@ fn fmt_compatibility() {
     .status(EXIT_SUCCESS)
     .run();
 }
+
+#[test]
+fn template() {
+  let tmp = tempdir();
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .args(["--init", "rust"])
+    .output()
+    .unwrap();
+
+  assert!(output.status.success());
+
+  let justfile = fs::read_to_string(tmp.path().join("justfile")).unwrap();
+
+  assert!(justfile.starts_with("# List available recipes\ndefault:\n    @just --list\n"));
+  assert!(justfile.contains("\ntest *args:\n    cargo test {{ args }}\n"));
+}
+
+#[test]
+fn template_is_case_insensitive() {
+  let tmp = tempdir();
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .args(["--init", "Python"])
+    .output()
+    .unwrap();
+
+  assert!(output.status.success());
+
+  assert!(fs::read_to_string(tmp.path().join("justfile"))
+    .unwrap()
+    .contains("-m pytest"));
+}
+
+#[test]
+fn template_fmt_compatibility() {
+  for template in ["node", "python", "rust"] {
+    let output = Test::new()
+      .no_justfile()
+      .args(["--init", template])
+      .stderr_regex("Wrote justfile to `.*`\n")
+      .run();
+    Test::with_tempdir(output.tempdir)
+      .no_justfile()
+      .arg("--unstable")
+      .arg("--check")
+      .arg("--fmt")
+      .status(EXIT_SUCCESS)
+      .run();
+  }
+}
+
+#[test]
+fn unknown_template() {
+  Test::new()
+    .no_justfile()
+    .args(["--init", "cobol"])
+    .stderr("error: Unknown template `cobol`, expected `node`, `python`, or `rust`\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn template_with_search_directory() {
+  let tmp = temptree! {
+    sub: {
+      ".git": {},
+    },
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path())
+    .args(["--init", "sub/", "node"])
+    .output()
+    .unwrap();
+
+  assert!(output.status.success());
+
+  assert!(fs::read_to_string(tmp.path().join("sub/justfile"))
+    .unwrap()
+    .contains("npm install"));
+}
+
+#[test]
+fn list_templates() {
+  Test::new()
+    .no_justfile()
+    .args(["--init", "--list-templates"])
+    .test_round_trip(false)
+    .stdout(
+      "
+        Available templates:
+            node   # Node.js project using npm
+            python # Python project using pytest, ruff, and mypy
+            rust   # Rust project using cargo
+      ",
+    )
+    .run();
+}
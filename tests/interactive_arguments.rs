@@ -0,0 +1,130 @@
+use super::*;
+
+#[test]
+fn setting_without_terminal_reports_missing_arguments() {
+  Test::new()
+    .justfile(
+      "
+        set interactive-arguments
+
+        deploy target:
+          echo {{target}}
+      ",
+    )
+    .arg("deploy")
+    .stdin("prod\n")
+    .stderr(
+      "
+        error: Recipe `deploy` got 0 arguments but takes 1
+        usage:
+            just deploy target
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn flag_without_terminal_reports_missing_arguments() {
+  Test::new()
+    .justfile(
+      "
+        deploy target:
+          echo {{target}}
+      ",
+    )
+    .args(["--prompt", "deploy"])
+    .stdin("prod\n")
+    .stderr(
+      "
+        error: Recipe `deploy` got 0 arguments but takes 1
+        usage:
+            just deploy target
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn supplied_arguments_are_not_prompted_for() {
+  Test::new()
+    .justfile(
+      "
+        set interactive-arguments
+
+        deploy target region='us':
+          @echo {{target}} {{region}}
+      ",
+    )
+    .args(["--prompt", "deploy", "prod"])
+    .stdout("prod us\n")
+    .run();
+}
+
+/// Run `just` in `dir` with `arguments` and a pseudo-terminal as standard
+/// input, to which `input` has already been written
+#[cfg(unix)]
+fn run_with_terminal(dir: &Path, arguments: &[&str], input: &str) -> std::process::Output {
+  use std::{ffi::CStr, os::unix::io::FromRawFd};
+
+  let (mut master, slave) = unsafe {
+    let master = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY);
+    assert!(master >= 0, "failed to open pseudo-terminal");
+    assert_eq!(libc::grantpt(master), 0);
+    assert_eq!(libc::unlockpt(master), 0);
+    let slave = CStr::from_ptr(libc::ptsname(master))
+      .to_str()
+      .unwrap()
+      .to_owned();
+    (fs::File::from_raw_fd(master), slave)
+  };
+
+  let slave = fs::OpenOptions::new()
+    .read(true)
+    .write(true)
+    .open(slave)
+    .unwrap();
+
+  // the terminal buffers input until it's read, so answers can be written
+  // before `just` prompts for them
+  master.write_all(input.as_bytes()).unwrap();
+
+  Command::new(executable_path("just"))
+    .current_dir(dir)
+    .args(arguments)
+    .stdin(slave)
+    .output()
+    .unwrap()
+}
+
+#[cfg(unix)]
+#[test]
+fn missing_arguments_are_prompted_for_on_terminal() {
+  let tmp = temptree! {
+    justfile: "set interactive-arguments\n\ndeploy target region='us':\n  @echo {{target}} {{region}}\n",
+  };
+
+  let output = run_with_terminal(tmp.path(), &["deploy"], "prod\n\n");
+
+  assert_eq!(
+    str::from_utf8(&output.stderr).unwrap(),
+    "target: region (default: 'us'): "
+  );
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "prod us\n");
+  assert!(output.status.success());
+}
+
+#[cfg(unix)]
+#[test]
+fn flag_prompts_on_terminal() {
+  let tmp = temptree! {
+    justfile: "deploy target:\n  @echo {{target}}\n",
+  };
+
+  let output = run_with_terminal(tmp.path(), &["--prompt", "deploy"], "prod\n");
+
+  assert_eq!(str::from_utf8(&output.stderr).unwrap(), "target: ");
+  assert_eq!(str::from_utf8(&output.stdout).unwrap(), "prod\n");
+  assert!(output.status.success());
+}
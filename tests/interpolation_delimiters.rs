@@ -0,0 +1,159 @@
+use super::*;
+
+#[test]
+fn custom_delimiters() {
+  Test::new()
+    .justfile(
+      "
+      set interpolation-delimiters := ['<%', '%>']
+
+      name := 'world'
+
+      foo:
+        echo '{{ .Values.name }}' <% name %>
+    ",
+    )
+    .stdout("{{ .Values.name }} world\n")
+    .stderr("echo '{{ .Values.name }}' world\n")
+    .run();
+}
+
+#[test]
+fn escaped_open_delimiter() {
+  Test::new()
+    .justfile(
+      "
+      set interpolation-delimiters := ['<%', '%>']
+
+      foo:
+        echo '<%<% {{{{'
+    ",
+    )
+    .stdout("<% {{{{\n")
+    .stderr("echo '<% {{{{'\n")
+    .run();
+}
+
+#[test]
+fn delimiters_with_comment_and_trailing_comma() {
+  Test::new()
+    .justfile(
+      "
+      set interpolation-delimiters := [ '[[', ']]', ] # for jinja
+
+      foo:
+        echo [[ 'bar' ]]
+    ",
+    )
+    .stdout("bar\n")
+    .stderr("echo bar\n")
+    .run();
+}
+
+#[test]
+fn format_uses_delimiters() {
+  Test::new()
+    .justfile(
+      "
+      set interpolation-delimiters := ['<%', '%>']
+
+      foo:
+        echo <%'bar'%> {{ baz }}
+    ",
+    )
+    .args(["--unstable", "--fmt", "--stdout"])
+    .stdout(
+      "
+      set interpolation-delimiters := ['<%', '%>']
+
+      foo:
+          echo <% 'bar' %> {{ baz }}
+    ",
+    )
+    .run();
+}
+
+#[test]
+fn set_after_recipe() {
+  Test::new()
+    .justfile(
+      "
+      foo:
+        echo {{ bar }} <% 'baz' %>
+
+      set interpolation-delimiters := ['<%', '%>']
+    ",
+    )
+    .stdout("{{ bar }} baz\n")
+    .stderr("echo {{ bar }} baz\n")
+    .run();
+}
+
+#[test]
+fn applies_to_imports() {
+  Test::new()
+    .write("import.just", "bar:\n  echo <% 'bar' %>\n")
+    .justfile(
+      "
+      import 'import.just'
+
+      set interpolation-delimiters := ['<%', '%>']
+
+      foo: bar
+        echo <% 'foo' %>
+    ",
+    )
+    .test_round_trip(false)
+    .arg("foo")
+    .stdout("bar\nfoo\n")
+    .stderr("echo bar\necho foo\n")
+    .run();
+}
+
+#[test]
+fn wrong_number_of_delimiters() {
+  Test::new()
+    .justfile("set interpolation-delimiters := ['<%']")
+    .stderr(
+      "
+      error: Interpolation delimiters must be a list of two strings, the opening and closing delimiters
+        |
+      1 | set interpolation-delimiters := ['<%']
+        |     ^^^^^^^^^^^^^^^^^^^^^^^^
+    ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn empty_delimiter() {
+  Test::new()
+    .justfile("set interpolation-delimiters := ['', '%>']")
+    .stderr(
+      "
+      error: Interpolation delimiters may not be empty
+        |
+      1 | set interpolation-delimiters := ['', '%>']
+        |     ^^^^^^^^^^^^^^^^^^^^^^^^
+    ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn escape_sequence_in_delimiter() {
+  Test::new()
+    .justfile(r#"set interpolation-delimiters := ["<\t", '%>']"#)
+    .stderr(
+      r#"
+      error: Interpolation delimiters may not contain escape sequences
+        |
+      1 | set interpolation-delimiters := ["<\t", '%>']
+        |     ^^^^^^^^^^^^^^^^^^^^^^^^
+    "#,
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
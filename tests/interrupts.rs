@@ -1,15 +1,22 @@
 use {
   super::*,
-  std::time::{Duration, Instant},
+  std::{
+    thread,
+    time::{Duration, Instant},
+  },
 };
 
-fn kill(process_id: u32) {
+fn kill(process_id: u32, signal: i32) {
   unsafe {
-    libc::kill(process_id as i32, libc::SIGINT);
+    libc::kill(process_id as i32, signal);
   }
 }
 
 fn interrupt_test(arguments: &[&str], justfile: &str) {
+  signal_test(libc::SIGINT, arguments, justfile);
+}
+
+fn signal_test(signal: i32, arguments: &[&str], justfile: &str) {
   let tmp = tempdir();
   let mut justfile_path = tmp.path().to_path_buf();
   justfile_path.push("justfile");
@@ -25,7 +32,7 @@ fn interrupt_test(arguments: &[&str], justfile: &str) {
 
   while start.elapsed() < Duration::from_millis(500) {}
 
-  kill(child.id());
+  kill(child.id(), signal);
 
   let status = child.wait().unwrap();
 
@@ -39,7 +46,7 @@ fn interrupt_test(arguments: &[&str], justfile: &str) {
     panic!("process returned too early : {elapsed:?}");
   }
 
-  assert_eq!(status.code(), Some(130));
+  assert_eq!(status.code(), Some(128 + signal));
 }
 
 #[test]
@@ -86,3 +93,64 @@ fn interrupt_backtick() {
 fn interrupt_command() {
   interrupt_test(&["--command", "sleep", "1"], "");
 }
+
+#[test]
+#[ignore]
+fn terminate_line() {
+  signal_test(
+    libc::SIGTERM,
+    &[],
+    "
+        default:
+          @sleep 5
+      ",
+  );
+}
+
+#[test]
+#[ignore]
+fn terminate_background_process() {
+  signal_test(
+    libc::SIGTERM,
+    &[],
+    "
+        default:
+          #!/usr/bin/env sh
+          sleep 5 &
+          wait
+      ",
+  );
+}
+
+#[test]
+#[ignore]
+fn interrupted_recipe_is_reported() {
+  let tmp = tempdir();
+  fs::write(tmp.path().join("justfile"), "default:\n  @sleep 1\n").unwrap();
+
+  let child = Command::new(executable_path("just"))
+    .current_dir(&tmp)
+    .args(["--report", "report.json"])
+    .stderr(Stdio::piped())
+    .spawn()
+    .expect("just invocation failed");
+
+  thread::sleep(Duration::from_millis(500));
+
+  kill(child.id(), libc::SIGINT);
+
+  let output = child.wait_with_output().unwrap();
+
+  assert_eq!(output.status.code(), Some(130));
+
+  assert_eq!(
+    str::from_utf8(&output.stderr).unwrap(),
+    "error: Recipe `default` was terminated on line 2 by signal 2
+  |
+2 |   @sleep 1
+  |   ^^^^^^^^
+",
+  );
+
+  assert!(tmp.path().join("report.json").is_file());
+}
@@ -18,6 +18,7 @@ fn alias() {
     ",
     json!({
       "first": "foo",
+      "functions": {},
       "aliases": {
         "f": {
           "name": "f",
@@ -42,16 +43,38 @@ fn alias() {
       },
       "settings": {
         "allow_duplicate_recipes": false,
+        "case_insensitive_recipes": null,
+        "clean_env": false,
+        "command_log": null,
         "dotenv_filename": null,
+        "dotenv_filenames": [],
         "dotenv_load": null,
         "dotenv_path": null,
+        "dotenv_required": false,
+        "echo": null,
+        "env_hook": null,
+        "env_passthrough": [],
         "export": false,
+        "export_prefix": null,
         "fallback": false,
+        "fallback_justfiles": [],
+        "fmt_align_assignments": false,
+        "fmt_blank_lines": null,
+        "fmt_indent": null,
         "positional_arguments": false,
+        "required_env": [],
+        "required_version": null,
         "shell": null,
+        "shell_fallbacks": [],
+        "source_map": false,
         "tempdir" : null,
         "ignore_comments": false,
+        "inherit_login_env": false,
+        "interactive_arguments": false,
+        "line_timeout": null,
+        "local_overlay": false,
         "windows_powershell": false,
+        "windows_shebang": "cygpath",
         "windows_shell": null,
       },
       "warnings": [],
@@ -68,24 +91,48 @@ fn assignment() {
       "assignments": {
         "foo": {
           "export": false,
+          "lazy": false,
           "name": "foo",
           "value": "bar",
         }
       },
       "first": null,
+      "functions": {},
       "recipes": {},
       "settings": {
         "allow_duplicate_recipes": false,
+        "case_insensitive_recipes": null,
+        "clean_env": false,
+        "command_log": null,
         "dotenv_filename": null,
+        "dotenv_filenames": [],
         "dotenv_load": null,
         "dotenv_path": null,
+        "dotenv_required": false,
+        "echo": null,
+        "env_hook": null,
+        "env_passthrough": [],
         "export": false,
+        "export_prefix": null,
         "fallback": false,
+        "fallback_justfiles": [],
+        "fmt_align_assignments": false,
+        "fmt_blank_lines": null,
+        "fmt_indent": null,
         "ignore_comments": false,
+        "inherit_login_env": false,
+        "interactive_arguments": false,
+        "line_timeout": null,
+        "local_overlay": false,
         "positional_arguments": false,
+        "required_env": [],
+        "required_version": null,
         "shell": null,
+        "shell_fallbacks": [],
+        "source_map": false,
         "tempdir" : null,
         "windows_powershell": false,
+        "windows_shebang": "cygpath",
         "windows_shell": null,
       },
       "warnings": [],
@@ -105,6 +152,7 @@ fn body() {
       "aliases": {},
       "assignments": {},
       "first": "foo",
+      "functions": {},
       "recipes": {
         "foo": {
           "attributes": [],
@@ -124,16 +172,38 @@ fn body() {
       },
       "settings": {
         "allow_duplicate_recipes": false,
+        "case_insensitive_recipes": null,
+        "clean_env": false,
+        "command_log": null,
         "dotenv_filename": null,
+        "dotenv_filenames": [],
         "dotenv_load": null,
         "dotenv_path": null,
+        "dotenv_required": false,
+        "echo": null,
+        "env_hook": null,
+        "env_passthrough": [],
         "export": false,
+        "export_prefix": null,
         "fallback": false,
+        "fallback_justfiles": [],
+        "fmt_align_assignments": false,
+        "fmt_blank_lines": null,
+        "fmt_indent": null,
         "ignore_comments": false,
+        "inherit_login_env": false,
+        "interactive_arguments": false,
+        "line_timeout": null,
+        "local_overlay": false,
         "positional_arguments": false,
+        "required_env": [],
+        "required_version": null,
         "shell": null,
+        "shell_fallbacks": [],
+        "source_map": false,
         "tempdir" : null,
         "windows_powershell": false,
+        "windows_shebang": "cygpath",
         "windows_shell": null,
       },
       "warnings": [],
@@ -152,6 +222,7 @@ fn dependencies() {
       "aliases": {},
       "assignments": {},
       "first": "foo",
+      "functions": {},
       "recipes": {
         "bar": {
           "attributes": [],
@@ -160,6 +231,7 @@ fn dependencies() {
           "body": [],
           "dependencies": [{
             "arguments": [],
+            "capture": null,
             "recipe": "foo"
           }],
           "parameters": [],
@@ -183,16 +255,38 @@ fn dependencies() {
       },
       "settings": {
         "allow_duplicate_recipes": false,
+        "case_insensitive_recipes": null,
+        "clean_env": false,
+        "command_log": null,
         "dotenv_filename": null,
+        "dotenv_filenames": [],
         "dotenv_load": null,
         "dotenv_path": null,
+        "dotenv_required": false,
+        "echo": null,
+        "env_hook": null,
+        "env_passthrough": [],
         "export": false,
+        "export_prefix": null,
         "fallback": false,
+        "fallback_justfiles": [],
+        "fmt_align_assignments": false,
+        "fmt_blank_lines": null,
+        "fmt_indent": null,
         "ignore_comments": false,
+        "inherit_login_env": false,
+        "interactive_arguments": false,
+        "line_timeout": null,
+        "local_overlay": false,
         "positional_arguments": false,
+        "required_env": [],
+        "required_version": null,
         "shell": null,
+        "shell_fallbacks": [],
+        "source_map": false,
         "tempdir" : null,
         "windows_powershell": false,
+        "windows_shebang": "cygpath",
         "windows_shell": null,
       },
       "warnings": [],
@@ -214,6 +308,7 @@ fn dependency_argument() {
         `echo`
         x
         if 'a' == 'b' { 'c' } else { 'd' }
+        if !('a' == 'b' || 'c' != 'd') && 'e' =~ 'f' { 'g' } else { 'h' }
         arch()
         env_var('foo')
         join('a', 'b')
@@ -223,9 +318,11 @@ fn dependency_argument() {
     json!({
       "aliases": {},
       "first": "foo",
+      "functions": {},
       "assignments": {
         "x": {
           "export": false,
+          "lazy": false,
           "name": "x",
           "value": "foo",
         },
@@ -243,11 +340,20 @@ fn dependency_argument() {
               ["evaluate", "echo"],
               ["variable", "x"],
               ["if", "==", "a", "b", "c", "d"],
+              [
+                "if",
+                "&&",
+                ["!", ["||", ["==", "a", "b"], ["!=", "c", "d"]]],
+                ["=~", "e", "f"],
+                "g",
+                "h",
+              ],
               ["call", "arch"],
               ["call", "env_var", "foo"],
               ["call", "join", "a", "b"],
               ["call", "replace", "a", "b", "c"],
             ],
+            "capture": null,
             "recipe": "foo"
           }],
           "parameters": [],
@@ -267,6 +373,7 @@ fn dependency_argument() {
               "name": "args",
               "export": false,
               "default": null,
+              "doc": null,
               "kind": "star",
             }
           ],
@@ -279,16 +386,38 @@ fn dependency_argument() {
       },
       "settings": {
         "allow_duplicate_recipes": false,
+        "case_insensitive_recipes": null,
+        "clean_env": false,
+        "command_log": null,
         "dotenv_filename": null,
+        "dotenv_filenames": [],
         "dotenv_load": null,
         "dotenv_path": null,
+        "dotenv_required": false,
+        "echo": null,
+        "env_hook": null,
+        "env_passthrough": [],
         "export": false,
+        "export_prefix": null,
         "fallback": false,
+        "fallback_justfiles": [],
+        "fmt_align_assignments": false,
+        "fmt_blank_lines": null,
+        "fmt_indent": null,
         "ignore_comments": false,
+        "inherit_login_env": false,
+        "interactive_arguments": false,
+        "line_timeout": null,
+        "local_overlay": false,
         "positional_arguments": false,
+        "required_env": [],
+        "required_version": null,
         "shell": null,
+        "shell_fallbacks": [],
+        "source_map": false,
         "tempdir" : null,
         "windows_powershell": false,
+        "windows_shebang": "cygpath",
         "windows_shell": null,
       },
       "warnings": [],
@@ -308,6 +437,7 @@ fn duplicate_recipes() {
     ",
     json!({
       "first": "foo",
+      "functions": {},
       "aliases": {
         "f": {
           "attributes": [],
@@ -327,6 +457,7 @@ fn duplicate_recipes() {
               "name": "bar",
               "export": false,
               "default": null,
+              "doc": null,
               "kind": "singular",
             },
           ],
@@ -339,16 +470,38 @@ fn duplicate_recipes() {
       },
       "settings": {
         "allow_duplicate_recipes": true,
+        "case_insensitive_recipes": null,
+        "clean_env": false,
+        "command_log": null,
         "dotenv_filename": null,
+        "dotenv_filenames": [],
         "dotenv_load": null,
         "dotenv_path": null,
+        "dotenv_required": false,
+        "echo": null,
+        "env_hook": null,
+        "env_passthrough": [],
         "export": false,
+        "export_prefix": null,
         "fallback": false,
+        "fallback_justfiles": [],
+        "fmt_align_assignments": false,
+        "fmt_blank_lines": null,
+        "fmt_indent": null,
         "ignore_comments": false,
+        "inherit_login_env": false,
+        "interactive_arguments": false,
+        "line_timeout": null,
+        "local_overlay": false,
         "positional_arguments": false,
+        "required_env": [],
+        "required_version": null,
         "shell": null,
+        "shell_fallbacks": [],
+        "source_map": false,
         "tempdir" : null,
         "windows_powershell": false,
+        "windows_shebang": "cygpath",
         "windows_shell": null,
       },
       "warnings": [],
@@ -363,6 +516,7 @@ fn doc_comment() {
     json!({
       "aliases": {},
       "first": "foo",
+      "functions": {},
       "assignments": {},
       "recipes": {
         "foo": {
@@ -380,16 +534,38 @@ fn doc_comment() {
       },
       "settings": {
         "allow_duplicate_recipes": false,
+        "case_insensitive_recipes": null,
+        "clean_env": false,
+        "command_log": null,
         "dotenv_filename": null,
+        "dotenv_filenames": [],
         "dotenv_load": null,
         "dotenv_path": null,
+        "dotenv_required": false,
+        "echo": null,
+        "env_hook": null,
+        "env_passthrough": [],
         "export": false,
+        "export_prefix": null,
         "fallback": false,
+        "fallback_justfiles": [],
+        "fmt_align_assignments": false,
+        "fmt_blank_lines": null,
+        "fmt_indent": null,
         "ignore_comments": false,
+        "inherit_login_env": false,
+        "interactive_arguments": false,
+        "line_timeout": null,
+        "local_overlay": false,
         "positional_arguments": false,
+        "required_env": [],
+        "required_version": null,
         "shell": null,
+        "shell_fallbacks": [],
+        "source_map": false,
         "tempdir" : null,
         "windows_powershell": false,
+        "windows_shebang": "cygpath",
         "windows_shell": null,
       },
       "warnings": [],
@@ -405,19 +581,42 @@ fn empty_justfile() {
       "aliases": {},
       "assignments": {},
       "first": null,
+      "functions": {},
       "recipes": {},
       "settings": {
         "allow_duplicate_recipes": false,
+        "case_insensitive_recipes": null,
+        "clean_env": false,
+        "command_log": null,
         "dotenv_filename": null,
+        "dotenv_filenames": [],
         "dotenv_load": null,
         "dotenv_path": null,
+        "dotenv_required": false,
+        "echo": null,
+        "env_hook": null,
+        "env_passthrough": [],
         "export": false,
+        "export_prefix": null,
         "fallback": false,
+        "fallback_justfiles": [],
+        "fmt_align_assignments": false,
+        "fmt_blank_lines": null,
+        "fmt_indent": null,
         "ignore_comments": false,
+        "inherit_login_env": false,
+        "interactive_arguments": false,
+        "line_timeout": null,
+        "local_overlay": false,
         "positional_arguments": false,
+        "required_env": [],
+        "required_version": null,
         "shell": null,
+        "shell_fallbacks": [],
+        "source_map": false,
         "tempdir" : null,
         "windows_powershell": false,
+        "windows_shebang": "cygpath",
         "windows_shell": null,
       },
       "warnings": [],
@@ -439,6 +638,7 @@ fn parameters() {
     json!({
       "aliases": {},
       "first": "a",
+      "functions": {},
       "assignments": {},
       "recipes": {
         "a": {
@@ -463,6 +663,7 @@ fn parameters() {
               "name": "x",
               "export": false,
               "default": null,
+              "doc": null,
               "kind": "singular",
             },
           ],
@@ -482,6 +683,7 @@ fn parameters() {
               "name": "x",
               "export": false,
               "default": "y",
+              "doc": null,
               "kind": "singular",
             }
           ],
@@ -501,6 +703,7 @@ fn parameters() {
               "name": "x",
               "export": false,
               "default": null,
+              "doc": null,
               "kind": "plus",
             }
           ],
@@ -520,6 +723,7 @@ fn parameters() {
               "name": "x",
               "export": false,
               "default": null,
+              "doc": null,
               "kind": "star",
             }
           ],
@@ -539,6 +743,7 @@ fn parameters() {
               "name": "x",
               "export": true,
               "default": null,
+              "doc": null,
               "kind": "singular",
             }
           ],
@@ -551,16 +756,38 @@ fn parameters() {
       },
       "settings": {
         "allow_duplicate_recipes": false,
+        "case_insensitive_recipes": null,
+        "clean_env": false,
+        "command_log": null,
         "dotenv_filename": null,
+        "dotenv_filenames": [],
         "dotenv_load": null,
         "dotenv_path": null,
+        "dotenv_required": false,
+        "echo": null,
+        "env_hook": null,
+        "env_passthrough": [],
         "export": false,
+        "export_prefix": null,
         "fallback": false,
+        "fallback_justfiles": [],
+        "fmt_align_assignments": false,
+        "fmt_blank_lines": null,
+        "fmt_indent": null,
         "ignore_comments": false,
+        "inherit_login_env": false,
+        "interactive_arguments": false,
+        "line_timeout": null,
+        "local_overlay": false,
         "positional_arguments": false,
+        "required_env": [],
+        "required_version": null,
         "shell": null,
+        "shell_fallbacks": [],
+        "source_map": false,
         "tempdir" : null,
         "windows_powershell": false,
+        "windows_shebang": "cygpath",
         "windows_shell": null,
       },
       "warnings": [],
@@ -580,6 +807,7 @@ fn priors() {
       "aliases": {},
       "assignments": {},
       "first": "a",
+      "functions": {},
       "recipes": {
         "a": {
           "body": [],
@@ -598,10 +826,12 @@ fn priors() {
           "dependencies": [
             {
               "arguments": [],
+              "capture": null,
               "recipe": "a",
             },
             {
               "arguments": [],
+              "capture": null,
               "recipe": "c",
             }
           ],
@@ -630,16 +860,38 @@ fn priors() {
       },
       "settings": {
         "allow_duplicate_recipes": false,
+        "case_insensitive_recipes": null,
+        "clean_env": false,
+        "command_log": null,
         "dotenv_filename": null,
+        "dotenv_filenames": [],
         "dotenv_load": null,
         "dotenv_path": null,
+        "dotenv_required": false,
+        "echo": null,
+        "env_hook": null,
+        "env_passthrough": [],
         "export": false,
+        "export_prefix": null,
         "fallback": false,
+        "fallback_justfiles": [],
+        "fmt_align_assignments": false,
+        "fmt_blank_lines": null,
+        "fmt_indent": null,
         "ignore_comments": false,
+        "inherit_login_env": false,
+        "interactive_arguments": false,
+        "line_timeout": null,
+        "local_overlay": false,
         "positional_arguments": false,
+        "required_env": [],
+        "required_version": null,
         "shell": null,
+        "shell_fallbacks": [],
+        "source_map": false,
         "tempdir" : null,
         "windows_powershell": false,
+        "windows_shebang": "cygpath",
         "windows_shell": null,
       },
       "warnings": [],
@@ -655,6 +907,7 @@ fn private() {
       "aliases": {},
       "assignments": {},
       "first": "_foo",
+      "functions": {},
       "recipes": {
         "_foo": {
           "body": [],
@@ -671,16 +924,38 @@ fn private() {
       },
       "settings": {
         "allow_duplicate_recipes": false,
+        "case_insensitive_recipes": null,
+        "clean_env": false,
+        "command_log": null,
         "dotenv_filename": null,
+        "dotenv_filenames": [],
         "dotenv_load": null,
         "dotenv_path": null,
+        "dotenv_required": false,
+        "echo": null,
+        "env_hook": null,
+        "env_passthrough": [],
         "export": false,
+        "export_prefix": null,
         "fallback": false,
+        "fallback_justfiles": [],
+        "fmt_align_assignments": false,
+        "fmt_blank_lines": null,
+        "fmt_indent": null,
         "ignore_comments": false,
+        "inherit_login_env": false,
+        "interactive_arguments": false,
+        "line_timeout": null,
+        "local_overlay": false,
         "positional_arguments": false,
+        "required_env": [],
+        "required_version": null,
         "shell": null,
+        "shell_fallbacks": [],
+        "source_map": false,
         "tempdir" : null,
         "windows_powershell": false,
+        "windows_shebang": "cygpath",
         "windows_shell": null,
       },
       "warnings": [],
@@ -696,6 +971,7 @@ fn quiet() {
       "aliases": {},
       "assignments": {},
       "first": "foo",
+      "functions": {},
       "recipes": {
         "foo": {
           "body": [],
@@ -712,16 +988,38 @@ fn quiet() {
       },
       "settings": {
         "allow_duplicate_recipes": false,
+        "case_insensitive_recipes": null,
+        "clean_env": false,
+        "command_log": null,
         "dotenv_filename": null,
+        "dotenv_filenames": [],
         "dotenv_load": null,
         "dotenv_path": null,
+        "dotenv_required": false,
+        "echo": null,
+        "env_hook": null,
+        "env_passthrough": [],
         "export": false,
+        "export_prefix": null,
         "fallback": false,
+        "fallback_justfiles": [],
+        "fmt_align_assignments": false,
+        "fmt_blank_lines": null,
+        "fmt_indent": null,
         "ignore_comments": false,
+        "inherit_login_env": false,
+        "interactive_arguments": false,
+        "line_timeout": null,
+        "local_overlay": false,
         "positional_arguments": false,
+        "required_env": [],
+        "required_version": null,
         "shell": null,
+        "shell_fallbacks": [],
+        "source_map": false,
         "tempdir" : null,
         "windows_powershell": false,
+        "windows_shebang": "cygpath",
         "windows_shell": null,
       },
       "warnings": [],
@@ -740,6 +1038,7 @@ fn settings() {
       set fallback
       set positional-arguments
       set ignore-comments
+      set line-timeout := '90s'
       set shell := ['a', 'b', 'c']
       foo:
         #!bar
@@ -748,6 +1047,7 @@ fn settings() {
       "aliases": {},
       "assignments": {},
       "first": "foo",
+      "functions": {},
       "recipes": {
         "foo": {
           "body": [["#!bar"]],
@@ -764,19 +1064,41 @@ fn settings() {
       },
       "settings": {
         "allow_duplicate_recipes": false,
+        "case_insensitive_recipes": null,
+        "clean_env": false,
+        "command_log": null,
         "dotenv_filename": "filename",
+        "dotenv_filenames": [],
         "dotenv_load": true,
         "dotenv_path": "path",
+        "dotenv_required": false,
+        "echo": null,
+        "env_hook": null,
+        "env_passthrough": [],
         "export": true,
+        "export_prefix": null,
         "fallback": true,
+        "fallback_justfiles": [],
+        "fmt_align_assignments": false,
+        "fmt_blank_lines": null,
+        "fmt_indent": null,
         "ignore_comments": true,
+        "inherit_login_env": false,
+        "interactive_arguments": false,
+        "line_timeout": "90s",
+        "local_overlay": false,
         "positional_arguments": true,
+        "required_env": [],
+        "required_version": null,
         "shell": {
           "arguments": ["b", "c"],
           "command": "a",
         },
+        "shell_fallbacks": [],
+        "source_map": false,
         "tempdir": null,
         "windows_powershell": false,
+        "windows_shebang": "cygpath",
         "windows_shell": null,
       },
       "warnings": [],
@@ -795,6 +1117,7 @@ fn shebang() {
       "aliases": {},
       "assignments": {},
       "first": "foo",
+      "functions": {},
       "recipes": {
         "foo": {
           "body": [["#!bar"]],
@@ -811,16 +1134,38 @@ fn shebang() {
       },
       "settings": {
         "allow_duplicate_recipes": false,
+        "case_insensitive_recipes": null,
+        "clean_env": false,
+        "command_log": null,
         "dotenv_filename": null,
+        "dotenv_filenames": [],
         "dotenv_load": null,
         "dotenv_path": null,
+        "dotenv_required": false,
+        "echo": null,
+        "env_hook": null,
+        "env_passthrough": [],
         "export": false,
+        "export_prefix": null,
         "fallback": false,
+        "fallback_justfiles": [],
+        "fmt_align_assignments": false,
+        "fmt_blank_lines": null,
+        "fmt_indent": null,
         "ignore_comments": false,
+        "inherit_login_env": false,
+        "interactive_arguments": false,
+        "line_timeout": null,
+        "local_overlay": false,
         "positional_arguments": false,
+        "required_env": [],
+        "required_version": null,
         "shell": null,
+        "shell_fallbacks": [],
+        "source_map": false,
         "tempdir": null,
         "windows_powershell": false,
+        "windows_shebang": "cygpath",
         "windows_shell": null,
       },
       "warnings": [],
@@ -836,6 +1181,7 @@ fn simple() {
       "aliases": {},
       "assignments": {},
       "first": "foo",
+      "functions": {},
       "recipes": {
         "foo": {
           "body": [],
@@ -852,16 +1198,38 @@ fn simple() {
       },
       "settings": {
         "allow_duplicate_recipes": false,
+        "case_insensitive_recipes": null,
+        "clean_env": false,
+        "command_log": null,
         "dotenv_filename": null,
+        "dotenv_filenames": [],
         "dotenv_load": null,
         "dotenv_path": null,
+        "dotenv_required": false,
+        "echo": null,
+        "env_hook": null,
+        "env_passthrough": [],
         "export": false,
+        "export_prefix": null,
         "fallback": false,
+        "fallback_justfiles": [],
+        "fmt_align_assignments": false,
+        "fmt_blank_lines": null,
+        "fmt_indent": null,
         "ignore_comments": false,
+        "inherit_login_env": false,
+        "interactive_arguments": false,
+        "line_timeout": null,
+        "local_overlay": false,
         "positional_arguments": false,
+        "required_env": [],
+        "required_version": null,
         "shell": null,
+        "shell_fallbacks": [],
+        "source_map": false,
         "tempdir": null,
         "windows_powershell": false,
+        "windows_shebang": "cygpath",
         "windows_shell": null,
       },
       "warnings": [],
@@ -880,6 +1248,7 @@ fn attribute() {
       "aliases": {},
       "assignments": {},
       "first": "foo",
+      "functions": {},
       "recipes": {
         "foo": {
           "attributes": ["no-exit-message"],
@@ -896,16 +1265,38 @@ fn attribute() {
       },
       "settings": {
         "allow_duplicate_recipes": false,
+        "case_insensitive_recipes": null,
+        "clean_env": false,
+        "command_log": null,
         "dotenv_filename": null,
+        "dotenv_filenames": [],
         "dotenv_load": null,
         "dotenv_path": null,
+        "dotenv_required": false,
+        "echo": null,
+        "env_hook": null,
+        "env_passthrough": [],
         "export": false,
+        "export_prefix": null,
         "fallback": false,
+        "fallback_justfiles": [],
+        "fmt_align_assignments": false,
+        "fmt_blank_lines": null,
+        "fmt_indent": null,
         "positional_arguments": false,
+        "required_env": [],
+        "required_version": null,
         "shell": null,
+        "shell_fallbacks": [],
+        "source_map": false,
         "tempdir" : null,
         "ignore_comments": false,
+        "inherit_login_env": false,
+        "interactive_arguments": false,
+        "line_timeout": null,
+        "local_overlay": false,
         "windows_powershell": false,
+        "windows_shebang": "cygpath",
         "windows_shell": null,
       },
       "warnings": [],
@@ -0,0 +1,158 @@
+use super::*;
+
+const JUSTFILE: &str = "
+  lint:
+    @echo lint
+    @exit 3
+
+  test:
+    @echo test
+
+  build:
+    @echo build
+    @exit 4
+";
+
+#[test]
+fn stops_at_first_failure_by_default() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["lint", "test", "build"])
+    .stdout("lint\n")
+    .stderr(
+      "
+        error: Recipe `lint` failed on line 3 with exit code 3
+          |
+        3 |   @exit 3
+          |   ^^^^^^^
+      ",
+    )
+    .status(3)
+    .run();
+}
+
+#[test]
+fn remaining_recipes_run() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--keep-going", "lint", "test", "build"])
+    .stdout("lint\ntest\nbuild\n")
+    .stderr(
+      "
+        error: Recipe `lint` failed on line 3 with exit code 3
+          |
+        3 |   @exit 3
+          |   ^^^^^^^
+        error: Recipe `build` failed on line 10 with exit code 4
+           |
+        10 |   @exit 4
+           |   ^^^^^^^
+        error: Recipes `lint` and `build` failed
+
+        error: Found 2 errors:
+          justfile:3:3   Recipe `lint` failed on line 3 with exit code 3
+          justfile:10:3  Recipe `build` failed on line 10 with exit code 4
+      ",
+    )
+    .status(3)
+    .run();
+}
+
+#[test]
+fn success() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--keep-going", "test"])
+    .stdout("test\n")
+    .run();
+}
+
+#[test]
+fn failed_dependency() {
+  Test::new()
+    .justfile(
+      "
+        foo: bar
+          @echo foo
+
+        bar:
+          @exit 1
+
+        baz:
+          @echo baz
+      ",
+    )
+    .args(["--keep-going", "foo", "baz"])
+    .stdout("baz\n")
+    .stderr(
+      "
+        error: Recipe `bar` failed on line 5 with exit code 1
+          |
+        5 |   @exit 1
+          |   ^^^^^^^
+        error: Recipe `foo` failed
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn failed_dependency_is_not_rerun() {
+  Test::new()
+    .justfile(
+      "
+        foo: bar
+          @echo foo
+
+        bar:
+          @echo bar
+          @exit 1
+
+        baz: bar
+          @echo baz
+      ",
+    )
+    .args(["--keep-going", "foo", "baz"])
+    .stdout("bar\n")
+    .stderr(
+      "
+        error: Recipe `bar` failed on line 6 with exit code 1
+          |
+        6 |   @exit 1
+          |   ^^^^^^^
+        error: Recipe `bar` was not run again, since it already failed
+        error: Recipes `foo` and `baz` failed
+
+        error: Found 2 errors:
+          justfile:6:3  Recipe `bar` failed on line 6 with exit code 1
+          `baz`         Recipe `bar` was not run again, since it already failed
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn error_format() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--keep-going", "--error-format", "json", "lint", "test"])
+    .stdout("lint\ntest\n")
+    .stderr(
+      r#"{"kind":"code","message":"Recipe `lint` failed on line 3 with exit code 3","recipe":"lint","path":"justfile","line":3,"column":3,"exit-code":3}
+{"kind":"recipes-failed","message":"Recipe `lint` failed","recipe":null,"path":null,"line":null,"column":null,"exit-code":3}
+"#,
+    )
+    .status(3)
+    .run();
+}
+
+#[test]
+fn quiet() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--keep-going", "--quiet", "lint", "test"])
+    .status(3)
+    .run();
+}
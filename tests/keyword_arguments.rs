@@ -0,0 +1,265 @@
+use super::*;
+
+const JUSTFILE: &str = "
+  deploy target region='us' replicas='1':
+    @echo {{target}} {{region}} {{replicas}}
+";
+
+#[test]
+fn keyword_arguments() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "target=prod", "region=eu", "replicas=3"])
+    .stdout("prod eu 3\n")
+    .run();
+}
+
+#[test]
+fn keyword_arguments_in_any_order() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "replicas=3", "target=prod"])
+    .stdout("prod us 3\n")
+    .run();
+}
+
+#[test]
+fn keyword_arguments_skip_defaults() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "prod", "replicas=3"])
+    .stdout("prod us 3\n")
+    .run();
+}
+
+#[test]
+fn positional_arguments_fill_unbound_parameters() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "region=eu", "prod", "2"])
+    .stdout("prod eu 2\n")
+    .run();
+}
+
+#[test]
+fn values_may_contain_equals_signs() {
+  Test::new()
+    .justfile(
+      "
+        run flags:
+          @echo '{{flags}}'
+      ",
+    )
+    .args(["run", "flags=--level=3"])
+    .stdout("--level=3\n")
+    .run();
+}
+
+#[test]
+fn unknown_names_are_positional() {
+  Test::new()
+    .justfile(
+      "
+        run arg:
+          @echo '{{arg}}'
+      ",
+    )
+    .args(["run", "FOO=bar"])
+    .stdout("FOO=bar\n")
+    .run();
+}
+
+#[test]
+fn unknown_marked_names_are_an_error() {
+  Test::new()
+    .justfile(
+      "
+        run arg:
+          @echo '{{arg}}'
+      ",
+    )
+    .args(["run", "--FOO=bar"])
+    .stderr("error: Recipe `run` got keyword argument `--FOO=bar`, but has no parameter `FOO`\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn marked_keyword_arguments() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "--replicas=3", "prod", "--region=eu"])
+    .stdout("prod eu 3\n")
+    .run();
+}
+
+#[test]
+fn trailing_arguments_are_not_keyword_arguments() {
+  Test::new()
+    .justfile(
+      "
+        run *args:
+          @echo '{{args}}'
+      ",
+    )
+    .args(["run", "--", "--FOO=x", "args=y"])
+    .stdout("--FOO=x args=y\n")
+    .run();
+}
+
+#[test]
+fn keyword_and_positional_arguments_in_any_order() {
+  Test::new()
+    .justfile(
+      "
+        deploy target region='us' replicas='1' zone='a':
+          @echo {{target}} {{region}} {{replicas}} {{zone}}
+      ",
+    )
+    .args(["deploy", "zone=b", "prod", "--replicas=3", "eu"])
+    .stdout("prod eu 3 b\n")
+    .run();
+}
+
+#[test]
+fn following_recipes() {
+  Test::new()
+    .justfile(
+      "
+        deploy target region='us':
+          @echo {{target}} {{region}}
+
+        notify:
+          @echo notified
+      ",
+    )
+    .args(["deploy", "region=eu", "prod", "notify"])
+    .stdout("prod eu\nnotified\n")
+    .run();
+}
+
+#[test]
+fn variadic_keyword_argument() {
+  Test::new()
+    .justfile(
+      "
+        build mode +targets:
+          @echo {{mode}} {{targets}}
+
+        notify:
+          @echo notified
+      ",
+    )
+    .args(["build", "targets=linux", "release", "notify"])
+    .stdout("release linux\nnotified\n")
+    .run();
+}
+
+#[test]
+fn duplicate_keyword_argument() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "target=prod", "target=staging"])
+    .stderr("error: Recipe `deploy` got more than one value for parameter `target`\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn missing_argument() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "region=eu"])
+    .stderr("error: Recipe `deploy` got no value for parameter `target`\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn too_many_arguments() {
+  Test::new()
+    .justfile(
+      "
+        deploy target region='us':
+          @echo {{target}} {{region}}
+      ",
+    )
+    .args(["deploy", "prod", "eu", "region=ap"])
+    .stderr(
+      "
+        error: Recipe `deploy` got 3 arguments but takes at most 2
+        usage:
+            just deploy target region='us'
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn variadic_given_positionally_and_by_keyword() {
+  Test::new()
+    .justfile(
+      "
+        build +targets:
+          @echo {{targets}}
+      ",
+    )
+    .args(["build", "linux", "targets=macos"])
+    .stderr(
+      "error: Recipe `build` got 1 positional argument but takes at most 0, \
+       since variadic parameter `targets` was given by name\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn overrides_before_recipe_are_unaffected() {
+  Test::new()
+    .justfile(
+      "
+        region := 'us'
+
+        deploy target:
+          @echo {{target}} {{region}}
+      ",
+    )
+    .args(["region=eu", "deploy", "target=prod"])
+    .stdout("prod eu\n")
+    .run();
+}
+
+#[test]
+fn keyword_arguments_are_validated() {
+  Test::new()
+    .justfile(
+      "
+        [choices('target', 'staging', 'prod')]
+        deploy region target:
+          @echo {{target}}
+      ",
+    )
+    .args(["deploy", "target=dev", "eu"])
+    .stderr(
+      "error: Recipe `deploy` parameter `target` got invalid value `dev`, expected `staging` or `prod`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn positional_arguments_setting() {
+  Test::new()
+    .justfile(
+      "
+        set positional-arguments
+
+        deploy target region='us' replicas='1':
+          @echo $1 $2 $3
+      ",
+    )
+    .args(["deploy", "replicas=3", "prod"])
+    .stdout("prod us 3\n")
+    .run();
+}
@@ -0,0 +1,248 @@
+use super::*;
+
+#[test]
+fn unused_lazy_variables_are_not_evaluated() {
+  Test::new()
+    .justfile(
+      "
+        lazy version := `exit 1`
+
+        foo:
+          @echo foo
+      ",
+    )
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn lazy_variables_are_evaluated_on_first_use() {
+  Test::new()
+    .justfile(
+      "
+        lazy version := `echo evaluating >&2; echo 1.2.3`
+
+        build: bar
+          @echo building {{version}}
+
+        bar:
+          @echo bar
+      ",
+    )
+    .stdout("bar\nbuilding 1.2.3\n")
+    .stderr("evaluating\n")
+    .run();
+}
+
+#[test]
+fn lazy_variables_are_memoized() {
+  Test::new()
+    .justfile(
+      "
+        lazy version := `echo evaluating >&2; echo 1.2.3`
+
+        release: build
+          @echo releasing {{version}}
+
+        build:
+          @echo building {{version}}
+      ",
+    )
+    .stdout("building 1.2.3\nreleasing 1.2.3\n")
+    .stderr("evaluating\n")
+    .run();
+}
+
+#[test]
+fn lazy_variables_may_refer_to_variables() {
+  Test::new()
+    .justfile(
+      "
+        lazy b := a + c
+
+        a := 'a'
+
+        lazy c := 'c'
+
+        foo:
+          @echo {{b}}
+      ",
+    )
+    .stdout("ac\n")
+    .run();
+}
+
+#[test]
+fn variables_may_refer_to_lazy_variables() {
+  Test::new()
+    .justfile(
+      "
+        lazy a := `echo evaluating >&2; echo a`
+
+        b := a + 'b'
+
+        foo:
+          @echo {{a}} {{b}}
+      ",
+    )
+    .stdout("a ab\n")
+    .stderr("evaluating\n")
+    .run();
+}
+
+#[test]
+fn parameter_defaults() {
+  Test::new()
+    .justfile(
+      "
+        lazy version := '1.2.3'
+
+        foo v=version:
+          @echo {{v}}
+      ",
+    )
+    .stdout("1.2.3\n")
+    .run();
+}
+
+#[test]
+fn user_defined_functions() {
+  Test::new()
+    .justfile(
+      "
+        lazy registry := 'example.com'
+
+        fn image(tag) := registry + '/app:' + tag
+
+        foo:
+          @echo {{image('1.0')}}
+      ",
+    )
+    .stdout("example.com/app:1.0\n")
+    .run();
+}
+
+#[test]
+fn overrides() {
+  Test::new()
+    .justfile(
+      "
+        lazy version := `exit 1`
+
+        foo:
+          @echo {{version}}
+      ",
+    )
+    .args(["version=1.2.3", "foo"])
+    .stdout("1.2.3\n")
+    .run();
+}
+
+#[test]
+fn errors_are_reported_on_first_use() {
+  Test::new()
+    .justfile(
+      "
+        lazy version := `exit 3`
+
+        foo:
+          @echo foo
+
+        bar:
+          @echo {{version}}
+      ",
+    )
+    .args(["foo", "bar"])
+    .stdout("foo\n")
+    .stderr(
+      "
+        error: Backtick failed with exit code 3
+          |
+        1 | lazy version := `exit 3`
+          |                 ^^^^^^^^
+      ",
+    )
+    .status(3)
+    .run();
+}
+
+#[test]
+fn lazy_variables_are_not_exported() {
+  Test::new()
+    .justfile(
+      "
+        set export
+
+        lazy a := 'a'
+
+        b := a
+
+        foo:
+          @echo ${a:-unset} $b
+      ",
+    )
+    .stdout("unset a\n")
+    .run();
+}
+
+#[test]
+fn evaluate() {
+  Test::new()
+    .justfile(
+      "
+        lazy a := 'a'
+        b := 'b'
+      ",
+    )
+    .arg("--evaluate")
+    .stdout("a := \"a\"\nb := \"b\"\n")
+    .run();
+}
+
+#[test]
+fn lazy_is_not_reserved() {
+  Test::new()
+    .justfile(
+      "
+        lazy := 'lazy'
+
+        foo:
+          @echo {{lazy}}
+      ",
+    )
+    .stdout("lazy\n")
+    .run();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile(
+      "
+        lazy version := `git describe`
+      ",
+    )
+    .arg("--dump")
+    .stdout("lazy version := `git describe`\n")
+    .run();
+}
+
+#[test]
+fn lazy_variables_cannot_be_exported() {
+  Test::new()
+    .justfile(
+      "
+        export lazy version := `echo 1.2.3`
+      ",
+    )
+    .stderr(
+      "
+        error: Lazy variable `version` cannot be exported
+          |
+        1 | export lazy version := `echo 1.2.3`
+          |        ^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -33,40 +33,77 @@ pub(crate) use {
 mod test;
 
 mod allow_duplicate_recipes;
+mod argument_constraints;
 mod assert_stdout;
 mod assert_success;
+mod attribute_overrides;
 mod attributes;
 mod byte_order_mark;
+mod case_insensitive_recipes;
 mod changelog;
 mod choose;
+mod clean_env;
 mod command;
+mod command_log;
 mod completions;
 mod conditional;
+mod confirm;
 mod delimiters;
+mod dependency_output;
+mod diff_env;
+mod doc;
+mod doc_attribute;
 mod dotenv;
+mod dump_ast;
+mod echo;
 mod edit;
+mod emit_aliases;
+mod env_attribute;
+mod env_hook;
 mod equals;
+mod error_format;
 mod error_messages;
 mod evaluate;
 mod examples;
+mod exit_status;
 mod export;
+mod export_script;
+mod export_tasks;
 mod fallback;
 mod fmt;
 mod functions;
+mod global;
+mod groups;
 mod ignore_comments;
 mod includes;
+mod inherit_login_env;
 mod init;
+mod interactive_arguments;
+mod interpolation_delimiters;
 #[cfg(unix)]
 mod interrupts;
 mod invocation_directory;
 mod json;
+mod keep_going;
+mod keyword_arguments;
+mod lazy;
+#[cfg(unix)]
+mod library;
 mod line_prefixes;
+mod line_timeout;
+mod list_source;
+mod local_overlay;
+mod lsp;
+mod migrate;
 mod misc;
 mod multibyte_char;
 mod newline_escape;
 mod no_cd;
+mod no_deps;
 mod no_exit_message;
+mod one_shot;
 mod os_attributes;
+mod param_doc;
 mod parser;
 mod positional_arguments;
 mod private;
@@ -75,22 +112,37 @@ mod quote;
 mod readme;
 mod recursion_limit;
 mod regexes;
+mod report;
+mod required_env;
+mod required_version;
 mod run;
+mod script;
 mod search;
 mod search_arguments;
+mod see_also;
 mod shadowing_parameters;
 mod shebang;
 mod shell;
+#[cfg(unix)]
+mod shell_fallbacks;
 mod show;
 mod slash_operator;
+mod source_map;
+mod stall_warning;
+mod stdin_justfile;
 mod string;
 mod subsequents;
 mod tempdir;
+mod timestamps;
+mod trailing_arguments;
+mod trust;
 mod undefined_variables;
 mod unstable;
+mod user_functions;
 #[cfg(target_family = "windows")]
 mod windows_shell;
 mod working_directory;
+mod workspace_config;
 
 fn path(s: &str) -> String {
   if cfg!(windows) {
@@ -0,0 +1,227 @@
+use super::*;
+
+fn git(directory: &Path, arguments: &[&str]) {
+  let status = Command::new("git")
+    .current_dir(directory)
+    .args([
+      "-c",
+      "user.name=just",
+      "-c",
+      "user.email=just@example.com",
+      "-c",
+      "commit.gpgsign=false",
+      "-c",
+      "tag.gpgsign=false",
+    ])
+    .args(arguments)
+    .stdout(Stdio::null())
+    .stderr(Stdio::null())
+    .status()
+    .unwrap();
+
+  assert!(status.success());
+}
+
+/// Commit `files` to the git repository at `repository`, creating it if
+/// needed, and tag the commit with `tag`
+fn commit(repository: &Path, tag: &str, files: &[(&str, &str)]) {
+  if !repository.join(".git").is_dir() {
+    git(repository, &["init", "--quiet"]);
+  }
+
+  for (path, contents) in files {
+    fs::write(repository.join(path), contents).unwrap();
+  }
+
+  git(repository, &["add", "--all"]);
+  git(
+    repository,
+    &["commit", "--quiet", "--allow-empty", "--message", tag],
+  );
+  git(repository, &["tag", tag]);
+}
+
+fn source(repository: &Path, tag: &str) -> String {
+  format!("git:file://{}@{tag}", repository.display())
+}
+
+fn install(test: Test, libs: &Path, source: &str) -> TempDir {
+  test
+    .env("JUST_LIB_DIR", libs.to_str().unwrap())
+    .args(["--install-lib", source])
+    .stderr_regex("Installed library `.*` to `.*`\n")
+    .run()
+    .tempdir
+}
+
+#[test]
+fn install_and_include() {
+  let repository = tempdir();
+  commit(
+    repository.path(),
+    "v1",
+    &[("rust.just", "build:\n  @echo building\n")],
+  );
+
+  let libs = tempdir();
+  let source = source(repository.path(), "v1");
+
+  let project = install(Test::new(), libs.path(), &source);
+
+  let manifest = fs::read_to_string(project.path().join("just-libs.lock")).unwrap();
+  assert!(manifest.starts_with(&format!("{source} ")));
+  assert_eq!(manifest.lines().count(), 1);
+
+  Test::with_tempdir(project)
+    .justfile(
+      "
+        !include lib('rust')
+
+        default: build
+      ",
+    )
+    .env("JUST_LIB_DIR", libs.path().to_str().unwrap())
+    .args(["--unstable", "default"])
+    .test_round_trip(false)
+    .stdout("building\n")
+    .run();
+}
+
+#[test]
+fn libraries_are_content_addressed() {
+  let repository = tempdir();
+  commit(repository.path(), "v1", &[("rust.just", "build:\n")]);
+  commit(repository.path(), "v2", &[]);
+  commit(repository.path(), "v3", &[("rust.just", "test:\n")]);
+
+  let libs = tempdir();
+
+  let project = install(Test::new(), libs.path(), &source(repository.path(), "v1"));
+  let project = install(
+    Test::with_tempdir(project),
+    libs.path(),
+    &source(repository.path(), "v2"),
+  );
+
+  assert_eq!(fs::read_dir(libs.path()).unwrap().count(), 1);
+
+  let project = install(
+    Test::with_tempdir(project),
+    libs.path(),
+    &source(repository.path(), "v3"),
+  );
+
+  assert_eq!(fs::read_dir(libs.path()).unwrap().count(), 2);
+
+  let manifest = fs::read_to_string(project.path().join("just-libs.lock")).unwrap();
+  assert_eq!(manifest.lines().count(), 1);
+  assert!(manifest.starts_with(&format!("{} ", source(repository.path(), "v3"))));
+}
+
+#[test]
+fn include_without_manifest() {
+  Test::new()
+    .justfile("!include lib('rust')")
+    .arg("--unstable")
+    .test_round_trip(false)
+    .stderr(
+      "error: Library `rust` not found, no `just-libs.lock` manifest found, try `just --install-lib`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn include_missing_library() {
+  let libs = tempdir();
+
+  Test::new()
+    .justfile("!include lib('rust')")
+    .write("just-libs.lock", "github:org/just-lib@v1 0123\n")
+    .env("JUST_LIB_DIR", libs.path().to_str().unwrap())
+    .arg("--unstable")
+    .test_round_trip(false)
+    .stderr_regex(
+      "error: Library `rust` not found in any of the libraries listed in `.*just-libs.lock`, try \
+       `just --install-lib`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn include_with_path_in_name() {
+  Test::new()
+    .justfile("!include lib('../rust')")
+    .arg("--unstable")
+    .test_round_trip(false)
+    .stderr(
+      "error: Invalid library name `../rust`, library names may not contain path separators or \
+       `..`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn modified_library_is_an_error() {
+  let repository = tempdir();
+  commit(
+    repository.path(),
+    "v1",
+    &[("rust.just", "build:\n  @echo building\n")],
+  );
+
+  let libs = tempdir();
+
+  let project = install(Test::new(), libs.path(), &source(repository.path(), "v1"));
+
+  let library = fs::read_dir(libs.path())
+    .unwrap()
+    .next()
+    .unwrap()
+    .unwrap()
+    .path();
+
+  fs::write(library.join("rust.just"), "build:\n  @echo modified\n").unwrap();
+
+  Test::with_tempdir(project)
+    .justfile("!include lib('rust')")
+    .env("JUST_LIB_DIR", libs.path().to_str().unwrap())
+    .args(["--unstable", "build"])
+    .test_round_trip(false)
+    .stderr_regex(
+      "error: Contents of library `git:file://.*@v1` at `.*` do not match the hash pinned in \
+       `.*just-libs.lock`, try `just --install-lib`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn invalid_source() {
+  Test::new()
+    .args(["--install-lib", "org/just-lib@v1"])
+    .stderr(
+      "error: Invalid library source `org/just-lib@v1`, expected `github:ORG/REPO@REF` or \
+       `git:URL@REF`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn fetch_failure() {
+  let libs = tempdir();
+  let missing = libs.path().join("missing");
+
+  Test::new()
+    .env("JUST_LIB_DIR", libs.path().to_str().unwrap())
+    .args(["--install-lib", &source(&missing, "v1")])
+    .stderr_regex(
+      "(?s).*error: Failed to fetch library `git:file://.*missing@v1` with `git`: Process exited \
+       with status code 128\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -0,0 +1,104 @@
+use super::*;
+
+#[test]
+fn line_within_timeout() {
+  Test::new()
+    .justfile(
+      "
+        set line-timeout := '10s'
+
+        foo:
+          @echo foo
+      ",
+    )
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn line_exceeding_timeout_is_terminated() {
+  Test::new()
+    .justfile(
+      "
+        set line-timeout := '1s'
+
+        foo:
+          @echo foo
+          @sleep 30
+          @echo bar
+      ",
+    )
+    .stdout("foo\n")
+    .stderr(
+      "
+        error: Recipe `foo` timed out on line 5 after 1s
+          |
+        5 |   @sleep 30
+          |   ^^^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn timeout_applies_to_each_line() {
+  Test::new()
+    .justfile(
+      "
+        set line-timeout := '3s'
+
+        foo:
+          @sleep 2
+          @sleep 2
+          @echo done
+      ",
+    )
+    .stdout("done\n")
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn shebang_recipe_timeout() {
+  Test::new()
+    .justfile(
+      "
+        set line-timeout := '1s'
+
+        foo:
+          #!/bin/sh
+          sleep 30
+      ",
+    )
+    .stderr("error: Recipe `foo` timed out after 1s\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn invalid_interval() {
+  Test::new()
+    .justfile("set line-timeout := 'soon'")
+    .stderr(
+      "
+        error: `soon` is not a valid interval, expected a number and a unit
+          |
+        1 | set line-timeout := 'soon'
+          |                     ^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile("set line-timeout := '120s'")
+    .arg("--dump")
+    .stdout("set line-timeout := \"2m\"\n")
+    .run();
+}
@@ -0,0 +1,166 @@
+use super::*;
+
+#[test]
+fn source_is_listed() {
+  Test::new()
+    .justfile(
+      "
+        # build the project
+        build:
+
+        test:
+      ",
+    )
+    .args(["--list", "--source"])
+    .stdout(
+      "
+        Available recipes:
+            build # build the project
+                source: justfile:2
+            test
+                source: justfile:4
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn included_recipes_are_listed_with_included_file() {
+  Test::new()
+    .tree(tree! {
+      ci: {
+        "ci.just": "
+          lint:
+        ",
+      },
+    })
+    .justfile(
+      "
+        !include ci/ci.just
+
+        build:
+      ",
+    )
+    .args(["--unstable", "--list", "--source"])
+    .test_round_trip(false)
+    .stdout(
+      "
+        Available recipes:
+            build
+                source: justfile:3
+            lint
+                source: ci/ci.just:1
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn listing_can_be_filtered_by_source_file() {
+  Test::new()
+    .tree(tree! {
+      "ci.just": "
+        lint:
+
+        fmt:
+      ",
+    })
+    .justfile(
+      "
+        !include ci.just
+
+        build:
+      ",
+    )
+    .args(["--unstable", "--list", "--source", "ci.just"])
+    .test_round_trip(false)
+    .stdout(
+      "
+        Available recipes:
+            fmt
+                source: ci.just:3
+            lint
+                source: ci.just:1
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn source_file_is_relative_to_invocation_directory() {
+  Test::new()
+    .tree(tree! {
+      "ci.just": "
+        lint:
+      ",
+      bar: {},
+    })
+    .justfile(
+      "
+        !include ci.just
+
+        build:
+      ",
+    )
+    .current_dir("bar")
+    .args(["--unstable", "--list", "--source", "../justfile"])
+    .test_round_trip(false)
+    .stdout(
+      "
+        Available recipes:
+            build
+                source: justfile:3
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn empty_groups_are_not_listed_when_filtering() {
+  Test::new()
+    .tree(tree! {
+      "ci.just": "
+        lint:
+      ",
+    })
+    .justfile(
+      "
+        !include ci.just
+
+        [group('release')]
+        publish:
+      ",
+    )
+    .args(["--unstable", "--list", "--source", "ci.just"])
+    .test_round_trip(false)
+    .stdout(
+      "
+        Available recipes:
+            lint
+                source: ci.just:1
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn aliases_are_not_annotated() {
+  Test::new()
+    .justfile(
+      "
+        alias b := build
+
+        build:
+      ",
+    )
+    .args(["--list", "--source"])
+    .stdout(
+      "
+        Available recipes:
+            build
+                source: justfile:3
+            b     # alias for `build`
+      ",
+    )
+    .run();
+}
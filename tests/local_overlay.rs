@@ -0,0 +1,129 @@
+use super::*;
+
+#[test]
+fn overlay_overrides_recipes_and_variables() {
+  Test::new()
+    .justfile(
+      "
+        set local-overlay
+
+        target := 'debug'
+
+        all: build
+          @echo all
+
+        build:
+          @echo build {{target}}
+      ",
+    )
+    .write(
+      "justfile.local",
+      "target := 'release'\n\nbuild:\n  @echo local build {{target}}\n",
+    )
+    .stdout("local build release\nall\n")
+    .run();
+}
+
+#[test]
+fn overlay_adds_recipes() {
+  Test::new()
+    .justfile(
+      "
+        set local-overlay
+
+        foo:
+      ",
+    )
+    .write("justfile.local", "bar:\n")
+    .arg("--summary")
+    .stdout("bar foo\n")
+    .run();
+}
+
+#[test]
+fn overlay_is_ignored_without_setting() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @echo foo
+      ",
+    )
+    .write("justfile.local", "foo:\n  @echo local\n")
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn missing_overlay_is_ignored() {
+  Test::new()
+    .justfile(
+      "
+        set local-overlay
+
+        foo:
+          @echo foo
+      ",
+    )
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn overlay_settings_override_justfile_settings() {
+  Test::new()
+    .justfile(
+      "
+        set local-overlay
+        set positional-arguments := false
+
+        foo bar:
+          @echo $1
+      ",
+    )
+    .write("justfile.local", "set positional-arguments\n")
+    .args(["foo", "hello"])
+    .stdout("hello\n")
+    .run();
+}
+
+#[test]
+fn dump_includes_overlay() {
+  Test::new()
+    .justfile(
+      "
+        set local-overlay
+
+        foo:
+          echo foo
+      ",
+    )
+    .write("justfile.local", "foo:\n  echo local\n")
+    .arg("--dump")
+    .test_round_trip(false)
+    .stdout(
+      "
+        set local-overlay := true
+
+        foo:
+            echo local
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn format_ignores_overlay() {
+  Test::new()
+    .justfile(
+      "
+        set local-overlay := true
+
+        foo:
+            echo foo
+      ",
+    )
+    .write("justfile.local", "bar:\n")
+    .args(["--unstable", "--fmt", "--check"])
+    .run();
+}
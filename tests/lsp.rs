@@ -0,0 +1,75 @@
+use super::*;
+
+fn message(value: Value) -> String {
+  let content = value.to_string();
+  format!("Content-Length: {}\r\n\r\n{content}", content.len())
+}
+
+#[test]
+fn session() {
+  Test::new()
+    .no_justfile()
+    .arg("--lsp")
+    .stdin(
+      [
+        message(json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} })),
+        message(json!({
+          "jsonrpc": "2.0",
+          "method": "textDocument/didOpen",
+          "params": { "textDocument": { "uri": "file:///justfile", "text": "foo: bar\n" } },
+        })),
+        message(json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" })),
+        message(json!({ "jsonrpc": "2.0", "method": "exit" })),
+      ]
+      .concat(),
+    )
+    .stdout(
+      [
+        message(json!({
+          "id": 1,
+          "jsonrpc": "2.0",
+          "result": {
+            "capabilities": {
+              "completionProvider": {},
+              "definitionProvider": true,
+              "hoverProvider": true,
+              "textDocumentSync": 1,
+            },
+            "serverInfo": { "name": "just", "version": env!("CARGO_PKG_VERSION") },
+          },
+        })),
+        message(json!({
+          "jsonrpc": "2.0",
+          "method": "textDocument/publishDiagnostics",
+          "params": {
+            "diagnostics": [{
+              "message": "Recipe `foo` has unknown dependency `bar`",
+              "range": {
+                "end": { "character": 8, "line": 0 },
+                "start": { "character": 5, "line": 0 },
+              },
+              "severity": 1,
+              "source": "just",
+            }],
+            "uri": "file:///justfile",
+          },
+        })),
+        message(json!({ "id": 2, "jsonrpc": "2.0", "result": null })),
+      ]
+      .concat(),
+    )
+    .unindent_stdout(false)
+    .test_round_trip(false)
+    .run();
+}
+
+#[test]
+fn invalid_message() {
+  Test::new()
+    .no_justfile()
+    .arg("--lsp")
+    .stdin("Content-Length: 3\r\n\r\nfoo")
+    .stderr("error: Language server I/O error: expected ident at line 1 column 2\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -0,0 +1,188 @@
+use super::*;
+
+#[test]
+fn unstable_not_passed() {
+  Test::new()
+    .justfile("set windows-powershell")
+    .arg("--migrate")
+    .stderr(
+      "error: The `--migrate` command is currently unstable. \
+       Invoke `just` with the `--unstable` flag to enable unstable features.\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn windows_powershell() {
+  let output = Test::new()
+    .justfile(
+      "
+        set windows-powershell := true
+
+        foo:
+          echo foo
+      ",
+    )
+    .args(["--unstable", "--migrate"])
+    .stderr_regex(
+      "(?s).*justfile: line 1: replaced `set windows-powershell` with `set windows-shell`\n",
+    )
+    .run();
+
+  assert_eq!(
+    fs::read_to_string(output.tempdir.path().join("justfile")).unwrap(),
+    "set windows-shell := [\"powershell.exe\", \"-NoLogo\", \"-Command\"]\n\nfoo:\n    echo foo\n",
+  );
+}
+
+#[test]
+fn ineffective_windows_powershell_is_removed() {
+  let output = Test::new()
+    .justfile(
+      "
+        set windows-shell := ['cmd.exe', '/c']
+        set windows-powershell
+
+        foo:
+      ",
+    )
+    .args(["--unstable", "--migrate"])
+    .stderr_regex("(?s).*justfile: line 2: removed `set windows-powershell`, which has no effect\n")
+    .run();
+
+  assert_eq!(
+    fs::read_to_string(output.tempdir.path().join("justfile")).unwrap(),
+    "set windows-shell := ['cmd.exe', '/c']\n\nfoo:\n",
+  );
+}
+
+#[test]
+fn quiet() {
+  Test::new()
+    .justfile("set windows-powershell := false")
+    .args(["--unstable", "--migrate", "--quiet"])
+    .run();
+}
+
+#[test]
+fn nothing_to_migrate() {
+  let output = Test::new()
+    .justfile("foo:\n  echo   foo")
+    .args(["--unstable", "--migrate"])
+    .run();
+
+  assert_eq!(
+    fs::read_to_string(output.tempdir.path().join("justfile")).unwrap(),
+    "foo:\n  echo   foo",
+  );
+}
+
+#[test]
+fn included_files_are_migrated_on_their_own() {
+  let output = Test::new()
+    .tree(tree! {
+      "other.just": "
+        set windows-powershell := false
+
+        [linux]
+        bar:
+      ",
+    })
+    .justfile(
+      "
+        # shared recipes
+        !include other.just
+
+        foo:
+          echo foo
+      ",
+    )
+    .args(["--unstable", "--migrate"])
+    .stderr(
+      "
+        justfile: line 2: replaced `!include` with `import`
+        other.just: line 1: removed `set windows-powershell`, which has no effect
+        other.just: line 4: replaced `[linux]` with `[os('linux')]` on recipe `bar`
+      ",
+    )
+    .test_round_trip(false)
+    .run();
+
+  assert_eq!(
+    fs::read_to_string(output.tempdir.path().join("justfile")).unwrap(),
+    "# shared recipes\nimport 'other.just'\n\nfoo:\n    echo foo\n",
+  );
+
+  assert_eq!(
+    fs::read_to_string(output.tempdir.path().join("other.just")).unwrap(),
+    "[os('linux')]\nbar:\n",
+  );
+}
+
+#[test]
+fn imports_are_kept() {
+  let output = Test::new()
+    .tree(tree! {
+      "other.just": "
+        bar:
+      ",
+    })
+    .justfile(
+      "
+        import? 'other.just' as other
+        set windows-powershell := false
+
+        foo:
+      ",
+    )
+    .args(["--unstable", "--migrate"])
+    .stderr("justfile: line 2: removed `set windows-powershell`, which has no effect\n")
+    .test_round_trip(false)
+    .run();
+
+  assert_eq!(
+    fs::read_to_string(output.tempdir.path().join("justfile")).unwrap(),
+    "import? 'other.just' as other\nfoo:\n",
+  );
+}
+
+#[test]
+fn migrated_includes_still_work() {
+  let output = Test::new()
+    .tree(tree! {
+      "other.just": "
+        bar:
+          @echo bar
+      ",
+    })
+    .justfile(
+      "
+        !include? missing.just
+        !include other.just as other
+
+        foo: other::bar
+          @echo foo
+      ",
+    )
+    .args(["--unstable", "--migrate"])
+    .stderr(
+      "
+        justfile: line 1: replaced `!include` with `import`
+        justfile: line 2: replaced `!include` with `import`
+      ",
+    )
+    .test_round_trip(false)
+    .run();
+
+  assert_eq!(
+    fs::read_to_string(output.tempdir.path().join("justfile")).unwrap(),
+    "import? 'missing.just'\nimport 'other.just' as other\n\nfoo: other::bar\n    @echo foo\n",
+  );
+
+  Test::with_tempdir(output.tempdir)
+    .no_justfile()
+    .arg("foo")
+    .stdout("bar\nfoo\n")
+    .run();
+}
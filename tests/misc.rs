@@ -255,7 +255,12 @@ hello:
 recipe:
   @exit 100",
   args:     ("recipe"),
-  stderr:   "error: Recipe `recipe` failed on line 5 with exit code 100\n",
+  stderr:   "
+    error: Recipe `recipe` failed on line 5 with exit code 100
+      |
+    5 |   @exit 100
+      |   ^^^^^^^^^
+  ",
   status:   100,
 }
 
@@ -711,15 +716,54 @@ test! {
 test! {
   name:     colors_no_context,
   justfile: "
+recipe:
+  #!/bin/sh
+  exit 100",
+  args:     ("--color=always"),
+  stdout:   "",
+  stderr:   "\u{1b}[1;31merror\u{1b}[0m: \u{1b}[1m\
+Recipe `recipe` failed with exit code 100\u{1b}[0m\n",
+  status:   100,
+}
+
+test! {
+  name:     colors_with_context,
+  justfile: "
 recipe:
   @exit 100",
   args:     ("--color=always"),
   stdout:   "",
   stderr:   "\u{1b}[1;31merror\u{1b}[0m: \u{1b}[1m\
-Recipe `recipe` failed on line 2 with exit code 100\u{1b}[0m\n",
+Recipe `recipe` failed on line 2 with exit code 100\u{1b}[0m\n  |\n2 |   @exit 100\n  |   \u{1b}[1;31m^^^^^^^^^\u{1b}[0m\n",
+  status:   100,
+}
+
+test! {
+  name:     colors_custom,
+  justfile: "
+recipe:
+  #!/bin/sh
+  exit 100",
+  args:     ("--color=always"),
+  env:      {
+    "JUST_COLORS": "error=blue,underline:message=",
+  },
+  stdout:   "",
+  stderr:   "\u{1b}[4;34merror\u{1b}[0m: Recipe `recipe` failed with exit code 100\n",
   status:   100,
 }
 
+test! {
+  name:     colors_invalid,
+  justfile: "recipe:",
+  env:      {
+    "JUST_COLORS": "error=reed",
+  },
+  stdout:   "",
+  stderr:   "error: Invalid `JUST_COLORS`: unknown color or attribute `reed` for `error`\n",
+  status:   EXIT_FAILURE,
+}
+
 test! {
   name:     dump,
   justfile: r#"
@@ -1012,6 +1056,26 @@ a Z="\t z":
   status:   EXIT_FAILURE,
 }
 
+test! {
+  name:     run_suggestion_multiple,
+  justfile: "
+    alias boil := build
+
+    build:
+
+    built:
+
+    test:
+  ",
+  args:     ("buil"),
+  stdout:   "",
+  stderr:   "
+    error: Justfile does not contain recipe `buil`.
+    Did you mean `boil` (an alias for `build`), `build`, or `built`?
+  ",
+  status:   EXIT_FAILURE,
+}
+
 test! {
   name:     line_continuation_with_space,
   justfile: r"
@@ -1068,6 +1132,9 @@ infallible:
   stderr: r#"exit 101
 exit 202
 error: Recipe `infallible` failed on line 3 with exit code 202
+  |
+3 |   exit 202
+  |   ^^^^^^^^
 "#,
   status: 202,
 }
@@ -1312,6 +1379,10 @@ test! {
   args:     ("a"),
   stdout:   "",
   stderr:   "error: Recipe `d` has circular dependency `a -> b -> c -> d -> a`
+  `a` depends on `b` on line 1
+  `b` depends on `c` on line 2
+  `c` depends on `d` on line 3
+  `d` depends on `a` on line 4
   |
 4 | d: a
   |    ^
@@ -2121,6 +2192,9 @@ test! {
   stderr: "
     @echo foo 2>/dev/null
     error: Recipe `foo` failed on line 2 with exit code 127
+      |
+    2 |   {{\"@echo foo 2>/dev/null\"}}
+      |   ^^^^^^^^^^^^^^^^^^^^^^^^^^^
   ",
   status: 127,
   shell: false,
@@ -2138,6 +2212,9 @@ test! {
   stderr: "
     @echo foo 2>/dev/null
     error: Recipe `foo` failed on line 3 with exit code 127
+      |
+    3 |   @echo foo 2>/dev/null
+      |   ^^^^^^^^^^^^^^^^^^^^^
   ",
   status: 127,
   shell: false,
@@ -0,0 +1,68 @@
+use super::*;
+
+const JUSTFILE: &str = "
+  foo: bar && baz
+    @echo foo
+
+  bar: qux
+    @echo bar
+
+  baz:
+    @echo baz
+
+  qux:
+    @echo qux
+";
+
+#[test]
+fn dependencies_run_by_default() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .arg("foo")
+    .stdout("qux\nbar\nfoo\nbaz\n")
+    .run();
+}
+
+#[test]
+fn prior_dependencies_are_skipped() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--no-deps", "foo"])
+    .stdout("foo\nbaz\n")
+    .run();
+}
+
+#[test]
+fn only_recipes_given_on_the_command_line_run() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--no-deps", "bar", "foo"])
+    .stdout("bar\nfoo\nbaz\n")
+    .run();
+}
+
+#[test]
+fn default_recipe() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .arg("--no-deps")
+    .stdout("foo\nbaz\n")
+    .run();
+}
+
+#[test]
+fn arguments_are_still_required() {
+  Test::new()
+    .justfile(
+      "
+        foo x: bar
+          @echo {{x}}
+
+        bar:
+          @echo bar
+      ",
+    )
+    .args(["--no-deps", "foo", "a"])
+    .stdout("a\n")
+    .run();
+}
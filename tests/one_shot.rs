@@ -0,0 +1,59 @@
+use super::*;
+
+#[test]
+fn may_not_be_used_to_run_recipes() {
+  Test::new()
+    .justfile("foo:")
+    .args(["--one-shot", "foo"])
+    .stderr(
+      "error: `--one-shot` may only be used with `--dump`, `--list`, `--list-groups`, `--summary`, or `--variables`.\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn workspace_config_is_not_read() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+        bar:
+      ",
+    )
+    .write(".just.toml", "unsorted = true\n")
+    .args(["--one-shot", "--summary"])
+    .stdout("bar foo\n")
+    .run();
+}
+
+#[test]
+fn remote_includes_are_not_fetched() {
+  Test::new()
+    .write(
+      includes::cached("https://example.com/common.just"),
+      "foo:\n  echo foo\n",
+    )
+    .justfile("!include https://example.com/common.just")
+    .env("JUST_CACHE_DIR", "cache")
+    .args(["--unstable", "--one-shot", "--summary"])
+    .test_round_trip(false)
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn uncached_remote_includes_are_an_error() {
+  Test::new()
+    .justfile("!include https://example.com/common.just")
+    .env("JUST_CACHE_DIR", "cache")
+    .args(["--unstable", "--one-shot", "--summary"])
+    .test_round_trip(false)
+    .stderr(format!(
+      "error: `https://example.com/common.just` is not cached at `{}`, and `--offline` or \
+       `--one-shot` was given\n",
+      includes::cached("https://example.com/common.just")
+    ))
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -101,3 +101,150 @@ fn none() {
     .stderr("echo bar\n")
     .run();
 }
+
+#[test]
+fn arch() {
+  Test::new()
+    .justfile(format!(
+      "
+        [arch('{}')]
+        foo:
+          @echo this
+
+        [arch('not-an-arch')]
+        foo:
+          @echo other
+      ",
+      env::consts::ARCH,
+    ))
+    .stdout("this\n")
+    .run();
+}
+
+#[test]
+fn arch_with_multiple_values() {
+  Test::new()
+    .justfile(format!(
+      "
+        [arch('not-an-arch', '{}')]
+        foo:
+          @echo this
+      ",
+      env::consts::ARCH,
+    ))
+    .stdout("this\n")
+    .run();
+}
+
+#[test]
+fn os_attribute() {
+  Test::new()
+    .justfile(format!(
+      "
+        [os('not-an-os')]
+        foo:
+          @echo other
+
+        [os('{}')]
+        foo:
+          @echo this
+      ",
+      env::consts::OS,
+    ))
+    .stdout("this\n")
+    .run();
+}
+
+#[test]
+fn os_family_attribute() {
+  Test::new()
+    .justfile(format!(
+      "
+        [os-family('{}')]
+        foo:
+          @echo this
+
+        [os-family('not-a-family')]
+        foo:
+          @echo other
+      ",
+      env::consts::FAMILY,
+    ))
+    .stdout("this\n")
+    .run();
+}
+
+#[test]
+fn os_and_arch_must_both_match() {
+  Test::new()
+    .justfile(format!(
+      "
+        [os('{}')]
+        [arch('not-an-arch')]
+        foo:
+          @echo other
+
+        bar:
+      ",
+      env::consts::OS,
+    ))
+    .arg("foo")
+    .stderr("error: Justfile does not contain recipe `foo`.\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn os_attributes_are_combined() {
+  Test::new()
+    .justfile(format!(
+      "
+        [windows]
+        [linux]
+        [macos]
+        [os('{}')]
+        foo:
+          @echo this
+      ",
+      env::consts::OS,
+    ))
+    .stdout("this\n")
+    .run();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile(
+      "
+        [arch('x86_64', 'aarch64')]
+        [os('freebsd')]
+        [os-family('unix')]
+        foo:
+      ",
+    )
+    .arg("--dump")
+    .stdout("[arch('x86_64', 'aarch64')]\n[os('freebsd')]\n[os-family('unix')]\nfoo:\n")
+    .run();
+}
+
+#[test]
+fn arch_requires_argument() {
+  Test::new()
+    .justfile(
+      "
+        [arch]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Attribute `arch` got 0 arguments but takes at least 1 argument
+          |
+        1 | [arch]
+          |  ^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
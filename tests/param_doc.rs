@@ -0,0 +1,128 @@
+use super::*;
+
+#[test]
+fn param_doc_is_listed_with_verbose() {
+  Test::new()
+    .justfile(
+      "
+        # build the project
+        [param-doc('target', 'the cargo target triple')]
+        build target profile='dev':
+      ",
+    )
+    .args(["--list", "--verbose"])
+    .stdout(
+      "
+        Available recipes:
+            build target profile='dev' # build the project
+                target: the cargo target triple
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn param_doc_is_not_listed_without_verbose() {
+  Test::new()
+    .justfile(
+      "
+        [param-doc('target', 'the cargo target triple')]
+        build target:
+      ",
+    )
+    .arg("--list")
+    .stdout(
+      "
+        Available recipes:
+            build target
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn param_docs_are_listed_in_parameter_order() {
+  Test::new()
+    .justfile(
+      "
+        [param-doc('profile', 'the build profile')]
+        [param-doc('target', 'the cargo target triple')]
+        build target profile:
+      ",
+    )
+    .args(["--list", "--verbose"])
+    .stdout(
+      "
+        Available recipes:
+            build target profile
+                target: the cargo target triple
+                profile: the build profile
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn param_doc_is_shown() {
+  Test::new()
+    .justfile(
+      "
+        [param-doc('target', 'the cargo target triple')]
+        build target:
+          echo {{target}}
+      ",
+    )
+    .args(["--show", "build"])
+    .stdout(
+      "
+        [param-doc('target', 'the cargo target triple')]
+        build target:
+            echo {{ target }}
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn param_doc_is_included_in_usage() {
+  Test::new()
+    .justfile(
+      "
+        [param-doc('target', 'the cargo target triple')]
+        build target:
+      ",
+    )
+    .arg("build")
+    .stderr(
+      "
+        error: Recipe `build` got 0 arguments but takes 1
+        usage:
+            just build target
+                target: the cargo target triple
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn param_doc_for_unknown_parameter() {
+  Test::new()
+    .justfile(
+      "
+        [param-doc('platform', 'the cargo target triple')]
+        build target:
+      ",
+    )
+    .arg("build")
+    .stderr(
+      "
+        error: Recipe `build` has `param-doc` attribute for unknown parameter `platform`
+          |
+        2 | build target:
+          | ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
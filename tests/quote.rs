@@ -41,3 +41,118 @@ fn quoted_strings_can_be_used_as_arguments() {
     .stdout("foo ' bar\njustfile\n")
     .run();
 }
+
+#[test]
+fn quote_win_wraps_in_double_quotes() {
+  Test::new()
+    .justfile("x := quote_win('a b')")
+    .args(["--evaluate", "x"])
+    .stdout(r#""a b""#)
+    .run();
+}
+
+#[test]
+fn quote_win_escapes_double_quotes_and_backslashes() {
+  Test::new()
+    .justfile(r#"x := quote_win('a\b\"c\')"#)
+    .args(["--evaluate", "x"])
+    .stdout(r#""a\b\\\"c\\""#)
+    .run();
+}
+
+#[test]
+fn quote_all_quotes_each_argument() {
+  Test::new()
+    .justfile(r#"x := quote_all('a', "b ' c", '')"#)
+    .args(["--evaluate", "x"])
+    .stdout(r"'a' 'b '\'' c' ''")
+    .run();
+}
+
+#[test]
+fn quote_all_uses_shell_quoting() {
+  Test::new()
+    .justfile(
+      r#"
+      set shell := ['cmd.exe', '/C']
+
+      x := quote_all('a b', 'c\d')
+    "#,
+    )
+    .shell(false)
+    .args(["--evaluate", "x"])
+    .stdout(r#""a b" "c\d""#)
+    .run();
+}
+
+#[test]
+fn quote_all_cmd_refuses_unquotable_arguments() {
+  Test::new()
+    .justfile(
+      "
+      set shell := ['cmd.exe', '/C']
+
+      x := quote_all('a', '%PATH%')
+    ",
+    )
+    .shell(false)
+    .args(["--evaluate", "x"])
+    .stderr(
+      "
+      error: Call to function `quote_all` failed: Cannot quote `%PATH%` for `cmd`, since it contains `%`
+        |
+      3 | x := quote_all('a', '%PATH%')
+        |      ^^^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn quote_all_uses_recipe_shell() {
+  Test::new()
+    .justfile(
+      "
+      [shell('pwsh', '-c')]
+      foo:
+        echo {{ quote_all(\"it's\") }}
+    ",
+    )
+    .shell(false)
+    .arg("--dry-run")
+    .stderr("echo 'it''s'\n")
+    .run();
+}
+
+#[test]
+fn quote_all_powershell() {
+  Test::new()
+    .justfile(
+      "
+      set shell := ['pwsh', '-c']
+
+      x := quote_all(\"it's\")
+    ",
+    )
+    .shell(false)
+    .args(["--evaluate", "x"])
+    .stdout("'it''s'")
+    .run();
+}
+
+#[test]
+fn quote_all_arguments_can_be_used_as_arguments() {
+  Test::new()
+    .justfile(
+      r#"
+      files := quote_all("foo ' bar", 'baz $qux')
+
+      @foo:
+        touch {{ files }}
+        ls -1
+    "#,
+    )
+    .stdout("baz $qux\nfoo ' bar\njustfile\n")
+    .run();
+}
@@ -64,3 +64,26 @@ fn bad_regex_fails_at_runtime() {
     .status(EXIT_FAILURE)
     .run();
 }
+
+#[test]
+fn match_is_unanchored() {
+  Test::new()
+    .justfile(
+      "
+      branch := 'release/1.2'
+      prefix := if branch =~ '^release/' { 'yes' } else { 'no' }
+      middle := if branch =~ 'ease' { 'yes' } else { 'no' }
+      anchored := if branch =~ '^ease' { 'yes' } else { 'no' }
+    ",
+    )
+    .args(["--evaluate"])
+    .stdout(
+      "
+      anchored := \"no\"
+      branch   := \"release/1.2\"
+      middle   := \"yes\"
+      prefix   := \"yes\"
+    ",
+    )
+    .run();
+}
@@ -0,0 +1,220 @@
+use super::*;
+
+fn report(tempdir: &TempDir) -> Value {
+  serde_json::from_str(&fs::read_to_string(tempdir.path().join("report.json")).unwrap()).unwrap()
+}
+
+fn durations(value: &mut Value) {
+  assert!(value["duration"].as_f64().unwrap() >= 0.0);
+  value["duration"] = json!(0);
+
+  for recipe in value["recipes"].as_array_mut().unwrap() {
+    assert!(recipe["duration"].as_f64().unwrap() >= 0.0);
+    recipe["duration"] = json!(0);
+  }
+}
+
+#[test]
+fn success() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo: (bar 'a')
+          @echo foo
+
+        bar x:
+          @echo bar {{x}}
+      ",
+    )
+    .args(["--report", "report.json", "foo"])
+    .stdout("bar a\nfoo\n")
+    .run();
+
+  let mut report = report(&output.tempdir);
+
+  durations(&mut report);
+
+  assert_eq!(
+    report,
+    json!({
+      "duration": 0,
+      "error": null,
+      "exit_code": 0,
+      "recipes": [
+        {
+          "arguments": ["a"],
+          "duration": 0,
+          "error": null,
+          "exit_code": 0,
+          "recipe": "bar",
+          "status": "succeeded",
+        },
+        {
+          "arguments": [],
+          "duration": 0,
+          "error": null,
+          "exit_code": 0,
+          "recipe": "foo",
+          "status": "succeeded",
+        },
+      ],
+      "requested": [
+        {
+          "arguments": [],
+          "recipe": "foo",
+        },
+      ],
+      "status": "succeeded",
+    }),
+  );
+}
+
+#[test]
+fn failure() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo x:
+          @echo {{x}}
+
+        bar:
+          @exit 3
+      ",
+    )
+    .args(["--report", "report.json", "foo", "a", "bar", "foo", "b"])
+    .stdout("a\n")
+    .stderr(
+      "
+        error: Recipe `bar` failed on line 5 with exit code 3
+          |
+        5 |   @exit 3
+          |   ^^^^^^^
+      ",
+    )
+    .status(3)
+    .run();
+
+  let mut report = report(&output.tempdir);
+
+  durations(&mut report);
+
+  assert_eq!(
+    report,
+    json!({
+      "duration": 0,
+      "error": "Recipe `bar` failed on line 5 with exit code 3",
+      "exit_code": 3,
+      "recipes": [
+        {
+          "arguments": ["a"],
+          "duration": 0,
+          "error": null,
+          "exit_code": 0,
+          "recipe": "foo",
+          "status": "succeeded",
+        },
+        {
+          "arguments": [],
+          "duration": 0,
+          "error": "Recipe `bar` failed on line 5 with exit code 3",
+          "exit_code": 3,
+          "recipe": "bar",
+          "status": "failed",
+        },
+      ],
+      "requested": [
+        {
+          "arguments": ["a"],
+          "recipe": "foo",
+        },
+        {
+          "arguments": [],
+          "recipe": "bar",
+        },
+        {
+          "arguments": ["b"],
+          "recipe": "foo",
+        },
+      ],
+      "status": "failed",
+    }),
+  );
+}
+
+#[test]
+fn unknown_recipe() {
+  let output = Test::new()
+    .justfile("foo:")
+    .args(["--report", "report.json", "bar"])
+    .stderr("error: Justfile does not contain recipe `bar`.\n")
+    .status(EXIT_FAILURE)
+    .run();
+
+  let mut report = report(&output.tempdir);
+
+  durations(&mut report);
+
+  assert_eq!(
+    report,
+    json!({
+      "duration": 0,
+      "error": "Justfile does not contain recipe `bar`.",
+      "exit_code": EXIT_FAILURE,
+      "recipes": [],
+      "requested": [],
+      "status": "failed",
+    }),
+  );
+}
+
+#[test]
+fn unwritable() {
+  Test::new()
+    .justfile("foo:")
+    .args(["--report", "missing/report.json"])
+    .stderr_regex("error: Failed to write report to `missing/report.json`: .*\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn compile_error() {
+  let output = Test::new()
+    .justfile("foo")
+    .args(["--report", "report.json", "foo"])
+    .stderr_regex("(?s)error: Expected .*")
+    .status(EXIT_FAILURE)
+    .run();
+
+  let report = report(&output.tempdir);
+
+  assert_eq!(report["exit_code"], EXIT_FAILURE);
+  assert_eq!(report["status"], "failed");
+  assert!(report["error"].as_str().unwrap().starts_with("Expected"));
+}
+
+#[test]
+fn missing_justfile() {
+  let output = Test::new()
+    .no_justfile()
+    .args(["--report", "report.json", "foo"])
+    .stderr_regex("error: No justfile found\n")
+    .status(EXIT_FAILURE)
+    .run();
+
+  let mut report = report(&output.tempdir);
+
+  durations(&mut report);
+
+  assert_eq!(
+    report,
+    json!({
+      "duration": 0,
+      "error": "No justfile found",
+      "exit_code": EXIT_FAILURE,
+      "recipes": [],
+      "requested": [],
+      "status": "failed",
+    }),
+  );
+}
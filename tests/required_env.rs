@@ -0,0 +1,212 @@
+use super::*;
+
+#[test]
+fn recipe_attribute_missing() {
+  Test::new()
+    .justfile(
+      "
+        [requires-env('JUST_TEST_A', 'JUST_TEST_B')]
+        deploy:
+          echo deploying
+      ",
+    )
+    .arg("deploy")
+    .stderr("error: Required environment variables `JUST_TEST_A` and `JUST_TEST_B` are not set\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn recipe_attribute_present() {
+  Test::new()
+    .justfile(
+      "
+        [requires-env('JUST_TEST_A')]
+        deploy:
+          @echo $JUST_TEST_A
+      ",
+    )
+    .env("JUST_TEST_A", "a")
+    .arg("deploy")
+    .stdout("a\n")
+    .run();
+}
+
+#[test]
+fn single_missing_variable() {
+  Test::new()
+    .justfile(
+      "
+        [requires-env('JUST_TEST_A', 'JUST_TEST_B')]
+        deploy:
+          echo deploying
+      ",
+    )
+    .env("JUST_TEST_A", "a")
+    .arg("deploy")
+    .stderr("error: Required environment variable `JUST_TEST_B` is not set\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn checked_before_any_recipe_runs() {
+  Test::new()
+    .justfile(
+      "
+        build:
+          @echo building
+
+        [requires-env('JUST_TEST_A')]
+        deploy:
+          echo deploying
+
+        release: build deploy
+      ",
+    )
+    .args(["build", "release"])
+    .stderr("error: Required environment variable `JUST_TEST_A` is not set\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn missing_variables_of_all_recipes_are_listed() {
+  Test::new()
+    .justfile(
+      "
+        set required-env := ['JUST_TEST_C']
+
+        [requires-env('JUST_TEST_A')]
+        a:
+
+        [requires-env('JUST_TEST_B')]
+        b:
+      ",
+    )
+    .args(["a", "b"])
+    .stderr("error: Required environment variables `JUST_TEST_A`, `JUST_TEST_B`, and `JUST_TEST_C` are not set\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn setting_missing() {
+  Test::new()
+    .justfile(
+      "
+        set required-env := ['JUST_TEST_A']
+
+        build:
+          echo building
+      ",
+    )
+    .arg("build")
+    .stderr("error: Required environment variable `JUST_TEST_A` is not set\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn setting_not_checked_for_other_subcommands() {
+  Test::new()
+    .justfile(
+      "
+        set required-env := ['JUST_TEST_A']
+
+        build:
+      ",
+    )
+    .arg("--summary")
+    .stdout("build\n")
+    .run();
+}
+
+#[test]
+fn provided_by_dotenv() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-load
+        set required-env := ['DOTENV_KEY']
+
+        build:
+          @echo $DOTENV_KEY
+      ",
+    )
+    .arg("build")
+    .stdout("dotenv-value\n")
+    .run();
+}
+
+#[test]
+fn provided_by_export() {
+  Test::new()
+    .justfile(
+      "
+        export JUST_TEST_A := 'a'
+
+        [requires-env('JUST_TEST_A')]
+        build:
+          @echo $JUST_TEST_A
+      ",
+    )
+    .arg("build")
+    .stdout("a\n")
+    .run();
+}
+
+#[test]
+fn unexported_variable_does_not_count() {
+  Test::new()
+    .justfile(
+      "
+        JUST_TEST_A := 'a'
+
+        [requires-env('JUST_TEST_A')]
+        build:
+      ",
+    )
+    .arg("build")
+    .stderr("error: Required environment variable `JUST_TEST_A` is not set\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn provided_by_exported_parameter() {
+  Test::new()
+    .justfile(
+      "
+        [requires-env('JUST_TEST_A')]
+        build $JUST_TEST_A:
+          @echo $JUST_TEST_A
+      ",
+    )
+    .args(["build", "a"])
+    .stdout("a\n")
+    .run();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile(
+      "
+        set required-env := ['A', 'B']
+
+        [requires-env('C')]
+        build:
+      ",
+    )
+    .arg("--dump")
+    .stdout(
+      "
+        set required-env := ['A', 'B']
+
+        [requires-env('C')]
+        build:
+      ",
+    )
+    .run();
+}
@@ -0,0 +1,86 @@
+use super::*;
+
+#[test]
+fn satisfied() {
+  Test::new()
+    .justfile(
+      "
+        set required-version := '>=1.0.0'
+
+        foo:
+          echo foo
+      ",
+    )
+    .stdout("foo\n")
+    .stderr("echo foo\n")
+    .run();
+}
+
+#[test]
+fn not_satisfied() {
+  Test::new()
+    .justfile(
+      "
+        set required-version := '>=1000.0.0'
+
+        foo:
+          echo foo
+      ",
+    )
+    .stderr_regex(
+      "error: Justfile requires `just` version `>=1000.0.0`, but this is version .*\\. Please upgrade `just`\\.\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn not_satisfied_with_unsupported_syntax() {
+  Test::new()
+    .justfile(
+      "
+        set required-version := '>=1000.0.0'
+
+        foo: ?bar
+          echo foo
+      ",
+    )
+    .stderr_regex(
+      "error: Justfile requires `just` version `>=1000.0.0`, but this is version .*\\. Please upgrade `just`\\.\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn satisfied_with_invalid_syntax() {
+  Test::new()
+    .justfile(
+      "
+        set required-version := '>=1.0.0'
+
+        foo: ?bar
+      ",
+    )
+    .stderr(
+      "
+        error: Unknown start of token:
+          |
+        3 | foo: ?bar
+          |      ^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn invalid_requirement() {
+  Test::new()
+    .justfile("set required-version := 'foo'")
+    .stderr_regex(
+      "(?s)error: Invalid required version `foo`: .*\n  \\|\n1 \\| set required-version := 'foo'\n  \\|                         \\^\\^\\^\\^\\^\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -0,0 +1,258 @@
+use super::*;
+
+#[test]
+fn runs_body_as_single_script() {
+  Test::new()
+    .justfile(
+      "
+        [script('sh')]
+        foo:
+          x=hello
+          if true; then
+            echo $x
+          fi
+      ",
+    )
+    .stdout("hello\n")
+    .run();
+}
+
+#[test]
+fn interpreter_arguments() {
+  Test::new()
+    .justfile(
+      "
+        [script('sh', '-eu')]
+        foo:
+          echo $UNDEFINED
+          echo unreachable
+      ",
+    )
+    .stderr_regex(".*UNDEFINED.*\nerror: Recipe `foo` failed with exit code 2\n")
+    .status(2)
+    .run();
+}
+
+#[test]
+fn line_numbers_match_justfile() {
+  Test::new()
+    .justfile(
+      "
+
+        [script('bash')]
+        foo:
+          echo $LINENO
+      ",
+    )
+    .stdout("4\n")
+    .run();
+}
+
+#[test]
+fn positional_arguments() {
+  Test::new()
+    .justfile(
+      "
+        set positional-arguments
+
+        [script('sh')]
+        foo bar:
+          echo $1
+      ",
+    )
+    .args(["foo", "hello"])
+    .stdout("hello\n")
+    .run();
+}
+
+#[test]
+fn working_directory() {
+  Test::new()
+    .justfile(
+      "
+        [script('sh')]
+        [working-directory('bar')]
+        foo:
+          cat baz
+      ",
+    )
+    .write("bar/baz", "hello")
+    .stdout("hello")
+    .run();
+}
+
+#[test]
+fn interpreter_not_found() {
+  Test::new()
+    .justfile(
+      "
+        [script('foo-bar-baz')]
+        foo:
+          echo foo
+      ",
+    )
+    .stderr_regex("error: Recipe `foo` with script interpreter `foo-bar-baz` execution error: .*\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn conflicts_with_shell_attribute() {
+  Test::new()
+    .justfile(
+      "
+        [script('sh')]
+        [shell('bash', '-c')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Recipe `foo` has both `[script]` and `[shell]` attributes
+          |
+        3 | foo:
+          | ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile(
+      "
+        [script('python3', '-u')]
+        foo:
+          print('foo')
+      ",
+    )
+    .arg("--dump")
+    .stdout(
+      "
+        [script('python3', '-u')]
+        foo:
+            print('foo')
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn dump_json() {
+  Test::new()
+    .justfile(
+      "
+        [script('python3', '-u')]
+        foo:
+      ",
+    )
+    .args(["--dump", "--dump-format", "json"])
+    .stdout_regex(
+      r#"(?s).*"attributes":\[\{"script":\{"arguments":\["-u"\],"command":"python3"\}\}\].*"#,
+    )
+    .run();
+}
+
+#[test]
+fn stdin_runs_body_as_single_script() {
+  Test::new()
+    .justfile(
+      "
+        [script-stdin('sh')]
+        foo:
+          x=hello
+          if true; then
+            echo $x
+          fi
+      ",
+    )
+    .stdout("hello\n")
+    .run();
+}
+
+#[test]
+fn stdin_line_numbers_match_justfile() {
+  Test::new()
+    .justfile(
+      "
+
+        [script-stdin('bash')]
+        foo:
+          echo $LINENO
+      ",
+    )
+    .stdout("4\n")
+    .run();
+}
+
+#[test]
+fn stdin_positional_arguments() {
+  Test::new()
+    .justfile(
+      "
+        set positional-arguments
+
+        [script-stdin('sh', '-s')]
+        foo bar:
+          echo $1
+      ",
+    )
+    .args(["foo", "hello"])
+    .stdout("hello\n")
+    .run();
+}
+
+#[test]
+fn stdin_does_not_use_tempdir() {
+  Test::new()
+    .justfile(
+      "
+        set tempdir := 'missing'
+
+        [script-stdin('sh')]
+        foo:
+          echo hello
+      ",
+    )
+    .stdout("hello\n")
+    .run();
+}
+
+#[test]
+fn stdin_exit_code() {
+  Test::new()
+    .justfile(
+      "
+        [script-stdin('sh')]
+        foo:
+          exit 3
+      ",
+    )
+    .stderr("error: Recipe `foo` failed with exit code 3\n")
+    .status(3)
+    .run();
+}
+
+#[test]
+fn stdin_conflicts_with_script_attribute() {
+  Test::new()
+    .justfile(
+      "
+        [script('sh')]
+        [script-stdin('sh')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Recipe `foo` has both `[script]` and `[script-stdin]` attributes
+          |
+        3 | foo:
+          | ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -27,3 +27,16 @@ fn passing_dot_as_argument_is_allowed() {
     .stderr_regex("'.*' ../say .\necho .\n")
     .run();
 }
+
+#[test]
+fn path_prefixed_recipe_runs_in_nested_justfile_directory() {
+  Test::new()
+    .justfile("test:\n  @echo root")
+    .write(
+      "foo/bar/justfile",
+      "test ARG:\n  @echo {{ARG}} $(basename \"$PWD\")",
+    )
+    .args(["foo/bar/test", "baz"])
+    .stdout("baz bar\n")
+    .run();
+}
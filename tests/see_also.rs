@@ -0,0 +1,120 @@
+use super::*;
+
+#[test]
+fn see_also_is_listed_with_verbose() {
+  Test::new()
+    .justfile(
+      "
+        # deploy the service
+        [see-also('rollback', 'status')]
+        deploy:
+
+        rollback:
+
+        status:
+      ",
+    )
+    .args(["--list", "--verbose"])
+    .stdout(
+      "
+        Available recipes:
+            deploy   # deploy the service
+                see also: rollback, status
+            rollback
+            status
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn see_also_is_not_listed_without_verbose() {
+  Test::new()
+    .justfile(
+      "
+        [see-also('rollback')]
+        deploy:
+
+        rollback:
+      ",
+    )
+    .arg("--list")
+    .stdout(
+      "
+        Available recipes:
+            deploy
+            rollback
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn see_also_is_shown() {
+  Test::new()
+    .justfile(
+      "
+        [see-also('rollback')]
+        deploy:
+          echo deploy
+
+        rollback:
+      ",
+    )
+    .args(["--show", "deploy"])
+    .stdout(
+      "
+        [see-also('rollback')]
+        deploy:
+            echo deploy
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn see_also_may_name_alias() {
+  Test::new()
+    .justfile(
+      "
+        alias rb := rollback
+
+        [see-also('rb')]
+        deploy:
+
+        rollback:
+      ",
+    )
+    .args(["--list", "--verbose"])
+    .stdout(
+      "
+        Available recipes:
+            deploy
+                see also: rb
+            rollback
+            rb       # alias for `rollback`
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn see_also_unknown_recipe() {
+  Test::new()
+    .justfile(
+      "
+        [see-also('rollback')]
+        deploy:
+      ",
+    )
+    .stderr(
+      "
+        error: Recipe `deploy` has `see-also` attribute for unknown recipe `rollback`
+          |
+        2 | deploy:
+          | ^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
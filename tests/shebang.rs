@@ -1,3 +1,5 @@
+use super::*;
+
 #[cfg(windows)]
 test! {
   name:     powershell,
@@ -41,3 +43,60 @@ default:
 "#,
   stdout: "Hello-World\r\n",
 }
+
+#[cfg(windows)]
+test! {
+  name:     powershell_exit_code,
+  justfile: r#"
+default:
+  #!powershell
+  exit 3
+"#,
+  stderr: "error: Recipe `default` failed with exit code 3\n",
+  status: 3,
+}
+
+#[cfg(windows)]
+test! {
+  name:     windows_shebang_native,
+  justfile: r#"
+set windows-shebang := 'native'
+
+default:
+  #!C:/Windows/System32/WindowsPowerShell/v1.0/powershell.exe
+  Write-Host Hello-World
+"#,
+  stdout: "Hello-World\n",
+}
+
+#[cfg(unix)]
+test! {
+  name:     windows_shebang_native_on_unix,
+  justfile: r#"
+set windows-shebang := 'native'
+
+default:
+  #!/bin/sh
+  echo Hello-World
+"#,
+  stdout: "Hello-World\n",
+}
+
+test! {
+  name:     windows_shebang_invalid,
+  justfile: "set windows-shebang := 'msys'",
+  stderr:   "
+    error: Invalid windows shebang mode `msys`, expected `cygpath` or `native`
+      |
+    1 | set windows-shebang := 'msys'
+      |                        ^^^^^^
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name:     windows_shebang_dump,
+  justfile: "set windows-shebang := 'native'",
+  args:     ("--dump"),
+  stdout:   "set windows-shebang := \"native\"\n",
+}
@@ -151,3 +151,106 @@ test! {
   stderr: "echo bar\necho foo\n",
   shell: false,
 }
+
+test! {
+  name: attribute,
+  justfile: "
+    set shell := ['foo-bar-baz']
+
+    x := 'hello'
+
+    [shell('echo', '-n')]
+    foo:
+      echo {{x}}
+      echo foo
+  ",
+  args: (),
+  stdout: "echo helloecho foo",
+  stderr: "echo hello\necho foo\n",
+  shell: false,
+}
+
+test! {
+  name: attribute_only_applies_to_recipe,
+  justfile: "
+    set shell := ['echo', '-n']
+
+    x := `bar`
+
+    [shell('sh', '-c')]
+    foo: baz
+      echo {{x}}
+
+    baz:
+      echo baz
+  ",
+  args: (),
+  stdout: "echo bazbar\n",
+  stderr: "echo baz\necho bar\n",
+  shell: false,
+}
+
+test! {
+  name: attribute_flag_override,
+  justfile: "
+    [shell('foo-bar-baz')]
+    default:
+      echo hello
+  ",
+  args: ("--shell", "bash"),
+  stdout: "hello\n",
+  stderr: "echo hello\n",
+  shell: false,
+}
+
+test! {
+  name: attribute_shebang,
+  justfile: "
+    [shell('bash', '-c')]
+    foo:
+      #!/bin/sh
+      echo foo
+  ",
+  args: (),
+  stderr: "
+    error: Shebang recipe `foo` may not have a `[shell]` attribute
+      |
+    2 | foo:
+      | ^^^
+  ",
+  status: EXIT_FAILURE,
+  shell: false,
+}
+
+test! {
+  name: attribute_requires_command,
+  justfile: "
+    [shell]
+    foo:
+  ",
+  args: (),
+  stderr: "
+    error: Attribute `shell` got 0 arguments but takes at least 1 argument
+      |
+    1 | [shell]
+      |  ^^^^^
+  ",
+  status: EXIT_FAILURE,
+  shell: false,
+}
+
+test! {
+  name: attribute_dump,
+  justfile: "
+    [shell('bash', '-euo', 'pipefail', '-c')]
+    foo:
+      echo foo
+  ",
+  args: ("--dump"),
+  stdout: "
+    [shell('bash', '-euo', 'pipefail', '-c')]
+    foo:
+        echo foo
+  ",
+  shell: false,
+}
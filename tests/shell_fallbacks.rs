@@ -0,0 +1,137 @@
+use super::*;
+
+#[test]
+fn first_shell_found_is_used() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+        set shell-fallbacks := [['just-test-nonexistent-shell', '-c'], ['sh', '-cu'], ['bash', '-cu']]
+
+        foo:
+          @echo $0
+      ",
+    )
+    .stdout("sh\n")
+    .run();
+}
+
+#[test]
+fn shell_given_by_path() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+        set shell-fallbacks := [['/just-test-nonexistent/sh', '-c'], ['/bin/sh', '-cu']]
+
+        foo:
+          @echo $0
+      ",
+    )
+    .stdout("/bin/sh\n")
+    .run();
+}
+
+#[test]
+fn no_shell_found() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+        set shell-fallbacks := [['just-test-nonexistent-a', '-c'], ['just-test-nonexistent-b']]
+
+        foo:
+          echo foo
+      ",
+    )
+    .stderr(
+      "
+        echo foo
+        error: None of the shells in `shell-fallbacks` could be found, tried `just-test-nonexistent-a` and `just-test-nonexistent-b`
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn fallbacks_are_used_for_backticks() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+        set shell-fallbacks := [['just-test-nonexistent-shell', '-c'], ['sh', '-cu']]
+
+        x := `echo $0`
+
+        foo:
+          @echo {{x}}
+      ",
+    )
+    .stdout("sh\n")
+    .run();
+}
+
+#[test]
+fn shell_setting_takes_precedence() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+        set shell := ['sh', '-cu']
+        set shell-fallbacks := [['just-test-nonexistent-shell', '-c']]
+
+        foo:
+          @echo $0
+      ",
+    )
+    .stdout("sh\n")
+    .run();
+}
+
+#[test]
+fn shell_option_takes_precedence() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+        set shell-fallbacks := [['just-test-nonexistent-shell', '-c']]
+
+        foo:
+          @echo $0
+      ",
+    )
+    .args(["--shell", "sh", "foo"])
+    .stdout("sh\n")
+    .run();
+}
+
+#[test]
+fn empty_fallbacks_use_default_shell() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+        set shell-fallbacks := []
+
+        foo:
+          @echo $0
+      ",
+    )
+    .stdout("sh\n")
+    .run();
+}
+
+#[test]
+fn fallbacks_are_dumped() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+        set shell-fallbacks := [['bash', '-cu'], ['sh']]
+      ",
+    )
+    .arg("--dump")
+    .stdout("set shell-fallbacks := [['bash', '-cu'], ['sh']]\n")
+    .run();
+}
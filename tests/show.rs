@@ -99,3 +99,130 @@ a Z="\t z":
   stderr:   "error: Justfile does not contain recipe `fooooooo`.\n",
   status:   EXIT_FAILURE,
 }
+
+#[test]
+fn show_highlighted() {
+  Test::new()
+    .justfile(
+      "
+        # build it
+        [no-cd]
+        build target='x': (dep 'y')
+          echo {{ if target == 'x' { 'a' } else { `b` } }} done
+
+        dep arg:
+      ",
+    )
+    .args(["--color", "always", "--show", "build"])
+    .stdout(
+      "\u{1b}[34m# build it\u{1b}[0m
+\u{1b}[35m[no-cd]\u{1b}[0m
+build \u{1b}[36mtarget\u{1b}[0m=\u{1b}[32m'x'\u{1b}[0m: (dep \u{1b}[32m'y'\u{1b}[0m)
+    echo \u{1b}[35m{{\u{1b}[0m \u{1b}[33mif\u{1b}[0m target == \u{1b}[32m'x'\u{1b}[0m { \u{1b}[32m'a'\u{1b}[0m } \
+\u{1b}[33melse\u{1b}[0m { \u{1b}[32m`b`\u{1b}[0m } \u{1b}[35m}}\u{1b}[0m done
+",
+    )
+    .run();
+}
+
+#[test]
+fn show_no_highlight() {
+  Test::new()
+    .justfile(
+      "
+        # build it
+        build target='x':
+          echo {{ target }}
+      ",
+    )
+    .args(["--color", "always", "--no-highlight", "--show", "build"])
+    .stdout(
+      "
+        # build it
+        build target='x':
+            echo {{ target }}
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn show_included_recipe_origin() {
+  Test::new()
+    .tree(tree! {
+      "sub": {
+        "include.justfile": "
+          !include ./nested.justfile
+
+          # build it
+          build:
+            echo build
+        ",
+        "nested.justfile": "
+          x := 'x'
+          test:
+            echo test
+        ",
+      },
+    })
+    .justfile(
+      "
+        !include ./sub/include.justfile
+
+        foo:
+          echo foo
+      ",
+    )
+    .test_round_trip(false)
+    .args(["--unstable", "--show", "build"])
+    .stdout(
+      "
+        # defined in sub/include.justfile:4
+        # build it
+        build:
+            echo build
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn show_nested_included_recipe_origin() {
+  Test::new()
+    .tree(tree! {
+      "sub": {
+        "include.justfile": "
+          !include ./nested.justfile
+
+          build:
+            echo build
+        ",
+        "nested.justfile": "
+          x := 'x'
+          test:
+            echo test
+        ",
+      },
+    })
+    .justfile("!include ./sub/include.justfile")
+    .test_round_trip(false)
+    .args(["--unstable", "--show", "test"])
+    .stdout(
+      "
+        # defined in sub/nested.justfile:2
+        test:
+            echo test
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn show_overlay_recipe_origin() {
+  Test::new()
+    .justfile("set local-overlay\n\nfoo:\n  echo foo")
+    .write("justfile.local", "\nbar:\n  echo bar\n")
+    .args(["--show", "bar"])
+    .stdout("# defined in justfile.local:2\nbar:\n    echo bar\n")
+    .run();
+}
@@ -0,0 +1,63 @@
+use super::*;
+
+#[test]
+fn shebang_script_path_is_replaced_with_justfile_path() {
+  Test::new()
+    .justfile(
+      "
+        set source-map
+
+        foo:
+          #!/usr/bin/env bash
+          echo \"$0:$LINENO\" >&2
+      ",
+    )
+    .stderr_regex(".*/justfile:5\n")
+    .run();
+}
+
+#[test]
+fn script_path_is_replaced_with_justfile_path() {
+  Test::new()
+    .justfile(
+      "
+        set source-map
+
+        [script('bash')]
+        foo:
+          echo \"$0:$LINENO\" >&2
+      ",
+    )
+    .stderr_regex(".*/justfile:5\n")
+    .run();
+}
+
+#[test]
+fn script_path_is_not_replaced_without_setting() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          #!/usr/bin/env bash
+          echo \"$0\" >&2
+      ",
+    )
+    .stderr_regex(".*/just[^/]*/foo\n")
+    .run();
+}
+
+#[test]
+fn stdout_is_not_replaced() {
+  Test::new()
+    .justfile(
+      "
+        set source-map
+
+        foo:
+          #!/usr/bin/env bash
+          basename \"$0\"
+      ",
+    )
+    .stdout("foo\n")
+    .run();
+}
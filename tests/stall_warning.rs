@@ -0,0 +1,66 @@
+use super::*;
+
+#[test]
+#[cfg(unix)]
+fn warns_when_recipe_produces_no_output() {
+  Test::new()
+    .arg("--stall-warning")
+    .arg("1s")
+    .justfile(
+      "
+        foo:
+          @sleep 3
+      ",
+    )
+    .stderr_regex(
+      "(?s)warning: Recipe `foo` has produced no output for 1s, still running `sleep 3`\n.*",
+    )
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn output_resets_stall_warning() {
+  Test::new()
+    .arg("--stall-warning")
+    .arg("5s")
+    .justfile(
+      "
+        foo:
+          @for i in 1 2 3; do echo $i; sleep 0.5; done
+      ",
+    )
+    .stdout("1\n2\n3\n")
+    .run();
+}
+
+#[test]
+fn output_is_relayed() {
+  Test::new()
+    .arg("--stall-warning")
+    .arg("10s")
+    .justfile(
+      "
+        foo:
+          echo foo
+          echo bar >&2
+      ",
+    )
+    .stdout("foo\n")
+    .stderr("echo foo\necho bar >&2\nbar\n")
+    .run();
+}
+
+#[test]
+fn invalid_interval() {
+  Test::new()
+    .arg("--stall-warning")
+    .arg("1.5s")
+    .justfile("foo:")
+    .stderr(
+      "error: Invalid value for '--stall-warning <INTERVAL>': `1.5s` is not a valid interval, \
+       expected a number and a unit\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -0,0 +1,102 @@
+use super::*;
+
+#[test]
+fn justfile_is_read_from_stdin() {
+  Test::new()
+    .no_justfile()
+    .args(["--justfile", "-", "foo"])
+    .stdin("foo:\n  @echo bar\n")
+    .stdout("bar\n")
+    .test_round_trip(false)
+    .run();
+}
+
+#[test]
+fn stdin_replaces_justfile_in_current_directory() {
+  Test::new()
+    .justfile("foo:\n  @echo file")
+    .args(["-f", "-", "foo"])
+    .stdin("foo:\n  @echo stdin\n")
+    .stdout("stdin\n")
+    .test_round_trip(false)
+    .run();
+}
+
+#[test]
+fn working_directory_is_invocation_directory() {
+  Test::new()
+    .no_justfile()
+    .write("bar/.keep", "")
+    .current_dir("bar")
+    .args(["-f", "-", "foo"])
+    .stdin("foo:\n  @basename \"$PWD\"\n")
+    .stdout("bar\n")
+    .test_round_trip(false)
+    .run();
+}
+
+#[test]
+fn includes_are_relative_to_invocation_directory() {
+  Test::new()
+    .no_justfile()
+    .write("common.just", "bar:\n  @echo bar\n")
+    .args(["--unstable", "-f", "-", "foo"])
+    .stdin("!include common.just\n\nfoo: bar\n  @echo foo\n")
+    .stdout("bar\nfoo\n")
+    .test_round_trip(false)
+    .run();
+}
+
+#[test]
+fn stdin_is_only_read_once() {
+  Test::new()
+    .no_justfile()
+    .write("common.just", "bar:\n")
+    .args(["--unstable", "-f", "-", "--dump", "--resolve-imports"])
+    .stdin("!include common.just\n\nfoo:\n")
+    .stdout("# begin !include common.just\nbar:\n# end !include common.just\n\nfoo:\n")
+    .test_round_trip(false)
+    .run();
+}
+
+#[test]
+fn errors_are_located_in_stdin() {
+  Test::new()
+    .no_justfile()
+    .args(["-f", "-"])
+    .stdin("foo bar\nx :=\n")
+    .stderr(
+      "
+      error: Expected '*', ':', '$', '=', identifier, or '+', but found end of line
+        |
+      1 | foo bar
+        |        ^
+
+      error: Expected backtick, identifier, '(', '/', or string, but found end of line
+        |
+      2 | x :=
+        |     ^
+
+      error: Found 2 errors:
+        <stdin>:1:8  Expected '*', ':', '$', '=', identifier, or '+', but found end of line
+        <stdin>:2:5  Expected backtick, identifier, '(', '/', or string, but found end of line
+    ",
+    )
+    .status(EXIT_FAILURE)
+    .test_round_trip(false)
+    .run();
+}
+
+#[test]
+fn format_is_an_error() {
+  Test::new()
+    .no_justfile()
+    .args(["-f", "-", "--fmt"])
+    .stderr(
+      "error: `--justfile -` may not be used with `--edit`, `--fmt`, `--init`, `--install-lib`, \
+       `--migrate`, or `--trust`, since they need a justfile on disk.\n",
+    )
+    .status(EXIT_FAILURE)
+    .test_round_trip(false)
+    .run();
+}
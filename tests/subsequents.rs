@@ -36,6 +36,9 @@ test! {
     echo foo
     false
     error: Recipe `foo` failed on line 3 with exit code 1
+      |
+    3 |   false
+      |   ^^^^^
   ",
   status: EXIT_FAILURE,
 }
@@ -54,6 +57,25 @@ test! {
   status: EXIT_FAILURE,
 }
 
+test! {
+  name: circular_dependency_through_subsequent,
+  justfile: "
+    foo: && bar
+    bar: baz
+    baz: foo
+  ",
+  stderr: "
+    error: Recipe `foo` has circular dependency `bar -> baz -> foo && bar`
+      `bar` depends on `baz` on line 2
+      `baz` depends on `foo` on line 3
+      `foo` runs subsequent dependency `bar` on line 1
+      |
+    1 | foo: && bar
+      |         ^^^
+  ",
+  status: EXIT_FAILURE,
+}
+
 test! {
   name: unknown,
   justfile: "
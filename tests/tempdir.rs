@@ -42,3 +42,82 @@ fn test_tempdir_is_set() {
     })
     .run();
 }
+
+#[test]
+fn keep_tempfiles() {
+  let output = Test::new()
+    .justfile(
+      "
+        set tempdir := 'tmp'
+
+        foo:
+          #!/bin/sh
+          echo foo
+      ",
+    )
+    .tree(tree! { tmp: {} })
+    .arg("--keep-tempfiles")
+    .stdout("foo\n")
+    .stderr_regex("Saved script for recipe `foo` to .*just.*foo\n")
+    .run();
+
+  let tempdirs = fs::read_dir(output.tempdir.path().join("tmp"))
+    .unwrap()
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+
+  assert_eq!(tempdirs.len(), 1);
+
+  assert!(fs::read_to_string(tempdirs[0].path().join("foo"))
+    .unwrap()
+    .ends_with("echo foo\n"));
+}
+
+#[test]
+fn tempfiles_are_deleted_by_default() {
+  let output = Test::new()
+    .justfile(
+      "
+        set tempdir := 'tmp'
+
+        foo:
+          #!/bin/sh
+          echo foo
+      ",
+    )
+    .tree(tree! { tmp: {} })
+    .stdout("foo\n")
+    .run();
+
+  assert_eq!(
+    fs::read_dir(output.tempdir.path().join("tmp"))
+      .unwrap()
+      .count(),
+    0
+  );
+}
+
+#[test]
+fn keep_tempfiles_quiet() {
+  let output = Test::new()
+    .justfile(
+      "
+        set tempdir := 'tmp'
+
+        foo:
+          #!/bin/sh
+          echo foo
+      ",
+    )
+    .tree(tree! { tmp: {} })
+    .args(["--keep-tempfiles", "--quiet"])
+    .stdout("foo\n")
+    .run();
+
+  let tempdirs = fs::read_dir(output.tempdir.path().join("tmp"))
+    .unwrap()
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+
+  assert_eq!(tempdirs.len(), 1);
+}
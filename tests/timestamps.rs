@@ -0,0 +1,47 @@
+use super::*;
+
+#[test]
+fn echoed_lines() {
+  Test::new()
+    .justfile("foo:\n  echo foo\n  @echo bar")
+    .args(["--timestamps", "foo"])
+    .stdout("foo\nbar\n")
+    .stderr_regex(r"\[\d\d:\d\d:\d\d\] echo foo\n")
+    .run();
+}
+
+#[test]
+fn errors() {
+  Test::new()
+    .justfile("foo:\n  @exit 2")
+    .args(["--timestamps", "foo"])
+    .stderr_regex(
+      r"\[\d\d:\d\d:\d\d\] error: Recipe `foo` failed on line 2 with exit code 2
+  \|
+2 \|   @exit 2
+  \|   \^\^\^\^\^\^\^
+",
+    )
+    .status(2)
+    .run();
+}
+
+#[test]
+fn format() {
+  Test::new()
+    .justfile("foo:\n  echo foo")
+    .args(["--timestamps", "--timestamp-format", "%Y-%m-%d", "foo"])
+    .stdout("foo\n")
+    .stderr_regex(r"\[\d{4}-\d\d-\d\d\] echo foo\n")
+    .run();
+}
+
+#[test]
+fn prefixed() {
+  Test::new()
+    .justfile("set echo := 'prefixed'\nfoo:\n  echo foo")
+    .args(["--timestamps", "--timestamp-format", "T", "foo"])
+    .stdout("foo\n")
+    .stderr("[T] [foo] echo foo\n")
+    .run();
+}
@@ -0,0 +1,139 @@
+use super::*;
+
+#[test]
+fn passed_to_variadic_parameter() {
+  Test::new()
+    .justfile(
+      "
+        test *args:
+          @echo {{args}}
+      ",
+    )
+    .args(["test", "--", "--nocapture", "--ignored"])
+    .stdout("--nocapture --ignored\n")
+    .run();
+}
+
+#[test]
+fn not_parsed_as_overrides_or_recipes() {
+  Test::new()
+    .justfile(
+      "
+        x := 'x'
+
+        test *args:
+          @echo {{x}} {{args}}
+
+        build:
+          @echo build
+      ",
+    )
+    .args(["test", "--", "x=y", "build"])
+    .stdout("x x=y build\n")
+    .run();
+}
+
+#[test]
+fn not_parsed_as_keyword_arguments() {
+  Test::new()
+    .justfile(
+      "
+        test *args:
+          @echo {{args}}
+      ",
+    )
+    .args(["test", "--", "args=foo"])
+    .stdout("args=foo\n")
+    .run();
+}
+
+#[test]
+fn follow_other_arguments() {
+  Test::new()
+    .justfile(
+      "
+        test target *args:
+          @echo {{target}}: {{args}}
+      ",
+    )
+    .args(["test", "wasm", "--", "--release"])
+    .stdout("wasm: --release\n")
+    .run();
+}
+
+#[test]
+fn passed_to_last_recipe() {
+  Test::new()
+    .justfile(
+      "
+        build:
+          @echo build
+
+        test *args:
+          @echo test {{args}}
+      ",
+    )
+    .args(["build", "test", "--", "--ignored"])
+    .stdout("build\ntest --ignored\n")
+    .run();
+}
+
+#[test]
+fn passed_to_default_recipe() {
+  Test::new()
+    .justfile(
+      "
+        x := 'x'
+
+        test +args:
+          @echo {{x}} {{args}}
+      ",
+    )
+    .args(["x=y", "--", "--ignored"])
+    .stdout("y --ignored\n")
+    .run();
+}
+
+#[test]
+fn double_dash_is_passed_verbatim() {
+  Test::new()
+    .justfile(
+      "
+        test *args:
+          @echo {{args}}
+      ",
+    )
+    .args(["test", "--", "a", "--", "b"])
+    .stdout("a -- b\n")
+    .run();
+}
+
+#[test]
+fn positional_arguments() {
+  Test::new()
+    .justfile(
+      "
+        set positional-arguments
+
+        test *args:
+          @printf '[%s]' \"$@\"
+      ",
+    )
+    .args(["test", "--", "a b", "c=d"])
+    .stdout("[a b][c=d]")
+    .run();
+}
+
+#[test]
+fn recipe_without_variadic_parameter() {
+  Test::new()
+    .justfile(
+      "
+        test:
+      ",
+    )
+    .args(["test", "--", "--ignored"])
+    .stderr("error: Recipe `test` got 1 argument but only takes 0\nusage:\n    just test\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -0,0 +1,160 @@
+use super::*;
+
+const JUSTFILE: &str = "
+  foo:
+    echo foo
+";
+
+/// A home directory whose global configuration requires trust
+fn home() -> TempDir {
+  let home = tempdir();
+  fs::create_dir_all(home.path().join("config/just")).unwrap();
+  fs::write(
+    home.path().join("config/just/config.toml"),
+    "require-trust = true\n",
+  )
+  .unwrap();
+  home
+}
+
+fn test(home: &TempDir) -> Test {
+  Test::new()
+    .env(
+      "XDG_CONFIG_HOME",
+      home.path().join("config").to_str().unwrap(),
+    )
+    .env("XDG_DATA_HOME", home.path().join("data").to_str().unwrap())
+}
+
+#[test]
+fn untrusted_justfile_is_not_run() {
+  let home = home();
+
+  test(&home)
+    .justfile(JUSTFILE)
+    .stderr_regex("error: Justfile `.*justfile` is not trusted, run `just --trust` to trust it\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn trusted_justfile_is_run() {
+  let home = home();
+
+  let tempdir = test(&home)
+    .justfile(JUSTFILE)
+    .arg("--trust")
+    .stderr_regex("Trusted justfile `.*justfile`\n")
+    .run()
+    .tempdir;
+
+  let store = fs::read_to_string(home.path().join("data/just/trusted")).unwrap();
+  assert_eq!(store.lines().count(), 1);
+
+  Test::with_tempdir(tempdir)
+    .env(
+      "XDG_CONFIG_HOME",
+      home.path().join("config").to_str().unwrap(),
+    )
+    .env("XDG_DATA_HOME", home.path().join("data").to_str().unwrap())
+    .justfile(JUSTFILE)
+    .stdout("foo\n")
+    .stderr("echo foo\n")
+    .run();
+}
+
+#[test]
+fn changed_justfile_must_be_trusted_again() {
+  let home = home();
+
+  let tempdir = test(&home)
+    .justfile(JUSTFILE)
+    .arg("--trust")
+    .stderr_regex("Trusted justfile `.*justfile`\n")
+    .run()
+    .tempdir;
+
+  Test::with_tempdir(tempdir)
+    .env(
+      "XDG_CONFIG_HOME",
+      home.path().join("config").to_str().unwrap(),
+    )
+    .env("XDG_DATA_HOME", home.path().join("data").to_str().unwrap())
+    .justfile("foo:\n  echo bar")
+    .stderr_regex("error: Justfile `.*justfile` is not trusted, .*\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn confirming_prompt_trusts_justfile() {
+  let home = home();
+
+  test(&home)
+    .justfile(JUSTFILE)
+    .arg("--yes")
+    .stdout("foo\n")
+    .stderr("echo foo\n")
+    .run();
+
+  let store = fs::read_to_string(home.path().join("data/just/trusted")).unwrap();
+  assert_eq!(store.lines().count(), 1);
+}
+
+#[test]
+fn evaluate_requires_trust() {
+  let home = home();
+
+  test(&home)
+    .justfile("x := `echo foo`")
+    .arg("--evaluate")
+    .stderr_regex("error: Justfile `.*justfile` is not trusted, .*\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn list_does_not_require_trust() {
+  let home = home();
+
+  test(&home)
+    .justfile(JUSTFILE)
+    .arg("--list")
+    .stdout(
+      "
+        Available recipes:
+            foo
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn trust_not_required_by_default() {
+  let home = tempdir();
+
+  test(&home)
+    .justfile(JUSTFILE)
+    .stdout("foo\n")
+    .stderr("echo foo\n")
+    .run();
+}
+
+#[test]
+fn invalid_global_config() {
+  let home = tempdir();
+  fs::create_dir_all(home.path().join("config/just")).unwrap();
+  fs::write(
+    home.path().join("config/just/config.toml"),
+    "require-trust = 'yes'\n",
+  )
+  .unwrap();
+
+  test(&home)
+    .justfile(JUSTFILE)
+    .stderr_regex(
+      "error: Failed to load global configuration file `.*config.toml`: `require-trust` must be a boolean\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
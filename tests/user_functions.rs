@@ -0,0 +1,245 @@
+use super::*;
+
+#[test]
+fn call_in_recipe() {
+  Test::new()
+    .justfile(
+      "
+      fn image(tag) := 'registry.example.com/app:' + tag
+
+      push:
+        echo {{ image('v1') }}
+    ",
+    )
+    .stderr("echo registry.example.com/app:v1\n")
+    .stdout("registry.example.com/app:v1\n")
+    .run();
+}
+
+#[test]
+fn call_in_assignment() {
+  Test::new()
+    .justfile(
+      "
+      fn greet(a, b) := a + ', ' + b
+
+      x := greet('hello', 'world')
+
+      @foo:
+        echo {{ x }}
+    ",
+    )
+    .stdout("hello, world\n")
+    .run();
+}
+
+#[test]
+fn call_before_definition() {
+  Test::new()
+    .justfile(
+      "
+      @foo:
+        echo {{ f() }}
+
+      fn f() := 'bar'
+    ",
+    )
+    .stdout("bar\n")
+    .run();
+}
+
+#[test]
+fn arguments_may_refer_to_recipe_parameters() {
+  Test::new()
+    .justfile(
+      "
+      fn double(s) := s + s
+
+      @foo x:
+        echo {{ double(x) }}
+    ",
+    )
+    .args(["foo", "ab"])
+    .stdout("abab\n")
+    .run();
+}
+
+#[test]
+fn body_may_refer_to_variables() {
+  Test::new()
+    .justfile(
+      "
+      registry := 'example.com'
+
+      fn image(tag) := registry / 'app:' + tag
+
+      @foo:
+        echo {{ image('v1') }}
+    ",
+    )
+    .stdout("example.com/app:v1\n")
+    .run();
+}
+
+#[test]
+fn parameters_shadow_variables() {
+  Test::new()
+    .justfile(
+      "
+      tag := 'latest'
+
+      fn image(tag) := 'app:' + tag
+
+      @foo:
+        echo {{ image('v1') }} {{ tag }}
+    ",
+    )
+    .stdout("app:v1 latest\n")
+    .run();
+}
+
+#[test]
+fn body_may_not_refer_to_recipe_parameters() {
+  Test::new()
+    .justfile(
+      "
+      fn f() := x
+
+      foo x:
+        echo {{ f() }}
+    ",
+    )
+    .args(["foo", "bar"])
+    .stderr(
+      "
+      error: Variable `x` not defined
+        |
+      1 | fn f() := x
+        |           ^
+    ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn functions_may_call_functions() {
+  Test::new()
+    .justfile(
+      "
+      fn shout(s) := uppercase(s) + '!'
+
+      fn greet(name) := shout('hello ' + name)
+
+      @foo:
+        echo {{ greet('bob') }}
+    ",
+    )
+    .stdout("HELLO BOB!\n")
+    .run();
+}
+
+#[test]
+fn argument_count_mismatch() {
+  Test::new()
+    .justfile(
+      "
+      fn f(a) := a
+
+      foo:
+        echo {{ f('a', 'b') }}
+    ",
+    )
+    .stderr(
+      "
+      error: Function `f` called with 2 arguments but takes 1
+        |
+      4 |   echo {{ f('a', 'b') }}
+        |           ^
+    ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn circular_dependency() {
+  Test::new()
+    .justfile(
+      "
+      fn f() := g()
+      fn g() := f()
+    ",
+    )
+    .stderr(
+      "
+      error: Function `f` calls itself indirectly: `f -> g -> f`
+        |
+      2 | fn g() := f()
+        |           ^
+    ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn circular_variable_dependency() {
+  Test::new()
+    .justfile(
+      "
+      fn f() := x
+
+      x := f()
+    ",
+    )
+    .stderr(
+      "
+      error: Variable `x` is defined in terms of itself
+        |
+      3 | x := f()
+        | ^
+    ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn shadows_builtin() {
+  Test::new()
+    .justfile("fn uppercase(s) := s")
+    .stderr(
+      "
+      error: Function `uppercase` has the same name as a built-in function
+        |
+      1 | fn uppercase(s) := s
+        |    ^^^^^^^^^
+    ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile(
+      "
+      fn image(tag, suffix) := 'app:' + tag + suffix
+
+      foo:
+        echo {{ image('v1', '') }}
+    ",
+    )
+    .arg("--dump")
+    .stdout(
+      "
+      fn image(tag, suffix) := 'app:' + tag + suffix
+
+      foo:
+          echo {{ image('v1', '') }}
+    ",
+    )
+    .run();
+}
@@ -181,3 +181,88 @@ fn search_dir_parent() -> Result<(), Box<dyn Error>> {
 
   Ok(())
 }
+
+#[test]
+fn attribute_linewise() {
+  Test::new()
+    .justfile(
+      "
+        [working-directory('bar')]
+        foo:
+          cat baz
+      ",
+    )
+    .write("bar/baz", "hello")
+    .stderr("cat baz\n")
+    .stdout("hello")
+    .run();
+}
+
+#[test]
+fn attribute_shebang() {
+  Test::new()
+    .justfile(
+      "
+        [working-directory('bar')]
+        foo:
+          #!/bin/sh
+          cat baz
+      ",
+    )
+    .write("bar/baz", "hello")
+    .stdout("hello")
+    .run();
+}
+
+#[test]
+fn attribute_is_relative_to_justfile() {
+  Test::new()
+    .justfile(
+      "
+        [working-directory('bar')]
+        foo:
+          @cat baz
+      ",
+    )
+    .write("bar/baz", "hello")
+    .write("qux/quux", "")
+    .current_dir("qux")
+    .stdout("hello")
+    .run();
+}
+
+#[test]
+fn attribute_with_no_cd() {
+  Test::new()
+    .justfile(
+      "
+        [no-cd]
+        [working-directory('bar')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Recipe `foo` has both `[no-cd]` and `[working-directory]` attributes
+          |
+        3 | foo:
+          | ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn attribute_dump() {
+  Test::new()
+    .justfile(
+      "
+        [working-directory('bar')]
+        foo:
+      ",
+    )
+    .arg("--dump")
+    .stdout("[working-directory('bar')]\nfoo:\n")
+    .run();
+}
@@ -0,0 +1,92 @@
+use super::*;
+
+#[test]
+fn list_format() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+        bar:
+      ",
+    )
+    .write(
+      ".just.toml",
+      "list-heading = \"Recipes:\\n\"\nlist-prefix = \"- \"\nunsorted = true\n",
+    )
+    .arg("--list")
+    .stdout("Recipes:\n- foo\n- bar\n")
+    .run();
+}
+
+#[test]
+fn command_line_takes_precedence() {
+  Test::new()
+    .justfile("foo:")
+    .write(".just.toml", "list-heading = \"Recipes:\\n\"\n")
+    .args(["--list", "--list-heading", "Stuff:\n"])
+    .stdout("Stuff:\n    foo\n")
+    .run();
+}
+
+#[test]
+fn shell() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          print('hello')
+      ",
+    )
+    .write(".just.toml", "shell = \"python3\"\nshell-args = [\"-c\"]\n")
+    .shell(false)
+    .stdout("hello\n")
+    .stderr("print('hello')\n")
+    .run();
+}
+
+#[test]
+fn dotenv_filename() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @echo $NAME
+      ",
+    )
+    .write(".env.local", "NAME=bar")
+    .write(".just.toml", "dotenv-filename = '.env.local'")
+    .stdout("bar\n")
+    .run();
+}
+
+#[test]
+fn unstable() {
+  Test::new()
+    .justfile("foo:")
+    .write(".just.toml", "unstable = true")
+    .arg("--fmt")
+    .stderr_regex("Wrote justfile to `.*`\n")
+    .run();
+}
+
+#[test]
+fn found_next_to_justfile() {
+  Test::new()
+    .justfile("foo:")
+    .write(".just.toml", "list-heading = ''\nlist-prefix = ''")
+    .write("bar/baz", "")
+    .current_dir("bar")
+    .arg("--list")
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn unknown_key() {
+  Test::new()
+    .justfile("foo:")
+    .write(".just.toml", "\nfoo = true")
+    .stderr_regex("error: Error in `.*\\.just\\.toml` on line 2: unknown field `foo`.*\n")
+    .status(EXIT_FAILURE)
+    .run();
+}